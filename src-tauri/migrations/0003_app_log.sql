@@ -0,0 +1,13 @@
+-- ─── Journal applicatif ──────────────────────────────────────────────────────
+-- Événements techniques consultables depuis l'interface (sauvegardes, restaurations…).
+-- `level` : 'info' | 'error'
+CREATE TABLE IF NOT EXISTS app_log (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    level      TEXT    NOT NULL,
+    source     TEXT    NOT NULL,              -- ex. 'backup', 'restore'
+    message    TEXT    NOT NULL,
+    created_at TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_app_log_created_at
+    ON app_log(created_at);
@@ -0,0 +1,7 @@
+-- ─── Paramètres par défaut de l'église ───────────────────────────────────────
+-- N'écrase pas les valeurs déjà présentes (ex : backup_retention modifiée).
+INSERT OR IGNORE INTO settings (key, value) VALUES
+    ('church_name',      'FJKM Ambalavao Isotry'),
+    ('church_address',   ''),
+    ('default_pledge',   '0'),
+    ('backup_retention', '14');
@@ -0,0 +1,24 @@
+-- ─── Sous-totaux par type et nombre de cotisants ─────────────────────────────
+-- Recalculés par refresh_year_total ; figés avec le total à la clôture.
+ALTER TABLE year_summaries ADD COLUMN total_communiant  TEXT    NOT NULL DEFAULT '0';
+ALTER TABLE year_summaries ADD COLUMN total_cathekomen  TEXT    NOT NULL DEFAULT '0';
+ALTER TABLE year_summaries ADD COLUMN contributor_count INTEGER NOT NULL DEFAULT 0;
+
+-- Remplissage des lignes existantes (années clôturées comprises).
+-- Les contributions orphelines restent dans `total` mais pas dans les sous-totaux.
+UPDATE year_summaries SET
+    total_communiant = COALESCE((
+        SELECT CAST(SUM(CAST(c.amount AS REAL)) AS TEXT)
+        FROM contributions c JOIN members m ON m.id = c.member_id
+        WHERE c.recorded_year = year_summaries.year AND m.member_type = 'Communiant'
+    ), '0'),
+    total_cathekomen = COALESCE((
+        SELECT CAST(SUM(CAST(c.amount AS REAL)) AS TEXT)
+        FROM contributions c JOIN members m ON m.id = c.member_id
+        WHERE c.recorded_year = year_summaries.year AND m.member_type = 'Cathekomen'
+    ), '0'),
+    contributor_count = (
+        SELECT COUNT(DISTINCT c.member_id)
+        FROM contributions c JOIN members m ON m.id = c.member_id
+        WHERE c.recorded_year = year_summaries.year
+    );
@@ -0,0 +1,4 @@
+-- ─── Date de naissance des membres ───────────────────────────────────────────
+-- Facultative, au format YYYY-MM-DD (validée par validate_member_input).
+-- Sert à la liste des anniversaires de l'accueil.
+ALTER TABLE members ADD COLUMN birth_date TEXT;
@@ -0,0 +1,5 @@
+-- ─── Suppression douce des membres ───────────────────────────────────────────
+-- NULL : membre actif. Sinon date de mise à la corbeille ("YYYY-MM-DDTHH:MM:SS", UTC).
+-- Les cotisations d'un membre supprimé restent en base et comptent toujours dans
+-- les totaux annuels ; seule la purge (purge_member) les efface.
+ALTER TABLE members ADD COLUMN deleted_at TEXT;
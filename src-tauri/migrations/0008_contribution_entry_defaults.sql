@@ -0,0 +1,6 @@
+-- ─── Saisie des cotisations : valeurs proposées ──────────────────────────────
+-- default_period : période préremplie ('' = année de la date de paiement).
+-- quick_amounts  : montants proposés en raccourci, séparés par des virgules.
+INSERT OR IGNORE INTO settings (key, value) VALUES
+    ('default_period', ''),
+    ('quick_amounts',  '5000,10000,20000');
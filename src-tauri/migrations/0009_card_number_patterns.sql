@@ -0,0 +1,6 @@
+-- ─── Numéros de carte générés ────────────────────────────────────────────────
+-- Modèle par type de membre : "%04d" = numéro sur 4 chiffres complété de zéros,
+-- "%d" = sans zéros. Les numéros qui ne suivent pas le modèle sont ignorés.
+INSERT OR IGNORE INTO settings (key, value) VALUES
+    ('card_pattern_communiant', 'C-%04d'),
+    ('card_pattern_cathekomen', 'K-%04d');
@@ -0,0 +1,18 @@
+-- ─── Numéros de reçu ─────────────────────────────────────────────────────────
+-- "YYYY-NNNNN" : année d'enregistrement + séquence propre à l'année, attribué à la
+-- création (create_contribution) et jamais modifié ensuite, même si la cotisation
+-- change d'année.
+ALTER TABLE contributions ADD COLUMN receipt_number TEXT;
+
+-- Cotisations existantes : numérotées par année, dans l'ordre des paiements
+UPDATE contributions
+SET receipt_number = (
+    SELECT printf('%d-%05d', r.recorded_year, r.seq)
+    FROM (SELECT id, recorded_year,
+                 ROW_NUMBER() OVER (PARTITION BY recorded_year ORDER BY payment_date, id) AS seq
+          FROM contributions) r
+    WHERE r.id = contributions.id
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contributions_receipt_number
+    ON contributions(receipt_number);
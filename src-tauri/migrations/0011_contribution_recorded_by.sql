@@ -0,0 +1,6 @@
+-- ─── Opérateur de saisie ─────────────────────────────────────────────────────
+-- Nom de la personne qui a saisi la cotisation ; NULL pour les lignes antérieures.
+ALTER TABLE contributions ADD COLUMN recorded_by TEXT;
+
+-- operators : noms proposés à la saisie, séparés par des virgules (vide = saisie libre).
+INSERT OR IGNORE INTO settings (key, value) VALUES ('operators', '');
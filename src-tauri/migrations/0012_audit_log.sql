@@ -0,0 +1,17 @@
+-- ─── Journal d'audit des opérations ──────────────────────────────────────────
+-- Une ligne par opération métier, écrite dans la transaction de l'opération.
+-- `action`    : 'create' | 'update' | 'delete' | 'transfer' | 'close' | 'reopen'
+-- `entity`    : 'member' | 'contribution' | 'year'
+-- `entity_id` : id du membre / de la cotisation, ou l'année
+-- `details`   : objet JSON (NULL si la sérialisation a échoué)
+CREATE TABLE IF NOT EXISTS audit_log (
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT    NOT NULL,              -- "YYYY-MM-DDTHH:MM:SS", UTC
+    action    TEXT    NOT NULL,
+    entity    TEXT    NOT NULL,
+    entity_id INTEGER NOT NULL,
+    details   TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity
+    ON audit_log(entity, id);
@@ -0,0 +1,4 @@
+-- ─── Date de transfert ───────────────────────────────────────────────────────
+-- Dernier changement de type (Cathekomen → Communiant…) par `transfer_members`,
+-- "YYYY-MM-DDTHH:MM:SS" (UTC). NULL : jamais transféré.
+ALTER TABLE members ADD COLUMN transferred_at TEXT;
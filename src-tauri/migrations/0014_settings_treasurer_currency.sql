@@ -0,0 +1,6 @@
+-- ─── En-tête des reçus et rapports ───────────────────────────────────────────
+-- treasurer_name : nom imprimé sous la ligne de signature des reçus (vide = aucun).
+-- currency_label : libellé de la monnaie affiché sur les reçus.
+INSERT OR IGNORE INTO settings (key, value) VALUES
+    ('treasurer_name', ''),
+    ('currency_label', 'Ariary');
@@ -0,0 +1,3 @@
+-- ─── Langue de l'interface ───────────────────────────────────────────────────
+-- language : 'mg' (malgache) ou 'fr' (français) — boutons, en-têtes et messages d'erreur.
+INSERT OR IGNORE INTO settings (key, value) VALUES ('language', 'mg');
@@ -0,0 +1,9 @@
+-- ─── Nombre de cotisations par année ─────────────────────────────────────────
+-- Recalculé par refresh_year_total avec le total ; figé à la clôture.
+-- Comme `total`, il compte aussi les contributions orphelines.
+ALTER TABLE year_summaries ADD COLUMN contribution_count INTEGER NOT NULL DEFAULT 0;
+
+-- Remplissage des lignes existantes (années clôturées comprises).
+UPDATE year_summaries SET contribution_count = (
+    SELECT COUNT(*) FROM contributions c WHERE c.recorded_year = year_summaries.year
+);
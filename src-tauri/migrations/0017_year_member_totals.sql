@@ -0,0 +1,22 @@
+-- ─── Totaux par membre figés à la clôture ────────────────────────────────────
+-- Rempli par close_year, vidé par reopen_year. Nom et carte sont recopiés
+-- (sans clé étrangère) : l'archive reste exacte si le membre est ensuite
+-- renommé ou supprimé. Les contributions orphelines y figurent sous un nom vide.
+CREATE TABLE IF NOT EXISTS year_member_totals (
+    year        INTEGER NOT NULL,
+    member_id   INTEGER NOT NULL,
+    member_name TEXT    NOT NULL,
+    card_number TEXT    NOT NULL,
+    total       TEXT    NOT NULL,               -- Decimal stocké en TEXT
+    PRIMARY KEY (year, member_id)
+);
+
+-- Années déjà clôturées : instantané depuis les contributions actuelles.
+INSERT INTO year_member_totals (year, member_id, member_name, card_number, total)
+SELECT c.recorded_year, c.member_id,
+       COALESCE(m.full_name, ''), COALESCE(m.card_number, ''),
+       CAST(SUM(CAST(c.amount AS REAL)) AS TEXT)
+FROM contributions c
+JOIN year_summaries ys ON ys.year = c.recorded_year AND ys.closed_at IS NOT NULL
+LEFT JOIN members m ON m.id = c.member_id
+GROUP BY c.recorded_year, c.member_id;
@@ -0,0 +1,9 @@
+-- ─── Index couvrant des totaux annuels ───────────────────────────────────────
+-- refresh_year_total relit (recorded_year, member_id, amount) après chaque
+-- écriture : avec cet index, les lignes de l'année sont lues dans l'index seul,
+-- sans revenir à la table. Il remplace idx_contributions_recorded_year, dont il
+-- est un préfixe ; idx_contributions_member_id (fiche membre) reste en place.
+CREATE INDEX IF NOT EXISTS idx_contributions_year_member_amount
+    ON contributions(recorded_year, member_id, amount);
+
+DROP INDEX IF EXISTS idx_contributions_recorded_year;
@@ -0,0 +1,14 @@
+-- ─── Notes sur les membres ───────────────────────────────────────────────────
+-- Historique de courtes notes libres (déménagement, visites…), jamais modifiées :
+-- une note se supprime puis se réécrit. Effacées avec le membre (purge, fusion
+-- après réattribution).
+CREATE TABLE IF NOT EXISTS member_notes (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    member_id  INTEGER NOT NULL,
+    created_at TEXT    NOT NULL,                -- "YYYY-MM-DDTHH:MM:SS", UTC
+    text       TEXT    NOT NULL,
+    FOREIGN KEY (member_id) REFERENCES members(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_member_notes_member_id
+    ON member_notes(member_id, created_at);
@@ -0,0 +1,43 @@
+-- ─── Nom de famille / prénom(s) ──────────────────────────────────────────────
+-- full_name reste le nom affiché ; surname et given_name servent au tri et sont
+-- facultatifs. Remplissage au mieux depuis full_name, même règle que
+-- `validate::split_full_name` : le premier mot en capitales est le nom de
+-- famille ("RAKOTO Jean"), sinon le dernier ("Jean RAKOTO"), pourvu que le reste
+-- contienne une minuscule ; un mot unique en capitales est un nom de famille.
+-- Sinon les deux colonnes restent NULL (tri sur full_name).
+ALTER TABLE members ADD COLUMN surname    TEXT;
+ALTER TABLE members ADD COLUMN given_name TEXT;
+
+-- "RAKOTO Jean"
+UPDATE members
+SET surname    = substr(trim(full_name), 1, instr(trim(full_name), ' ') - 1),
+    given_name = trim(substr(trim(full_name), instr(trim(full_name), ' ') + 1))
+WHERE instr(trim(full_name), ' ') > 0
+  AND substr(trim(full_name), 1, instr(trim(full_name), ' ') - 1)
+      = upper(substr(trim(full_name), 1, instr(trim(full_name), ' ') - 1))
+  AND substr(trim(full_name), 1, instr(trim(full_name), ' ') - 1)
+      <> lower(substr(trim(full_name), 1, instr(trim(full_name), ' ') - 1))
+  AND trim(substr(trim(full_name), instr(trim(full_name), ' ') + 1))
+      <> upper(trim(substr(trim(full_name), instr(trim(full_name), ' ') + 1)));
+
+-- "Jean RAKOTO" : rtrim(t, replace(t, ' ', '')) garde tout jusqu'au dernier espace
+UPDATE members
+SET surname    = substr(trim(full_name),
+                        length(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))) + 1),
+    given_name = trim(rtrim(trim(full_name), replace(trim(full_name), ' ', '')))
+WHERE surname IS NULL
+  AND instr(trim(full_name), ' ') > 0
+  AND substr(trim(full_name), length(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))) + 1)
+      = upper(substr(trim(full_name), length(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))) + 1))
+  AND substr(trim(full_name), length(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))) + 1)
+      <> lower(substr(trim(full_name), length(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))) + 1))
+  AND trim(rtrim(trim(full_name), replace(trim(full_name), ' ', '')))
+      <> upper(trim(rtrim(trim(full_name), replace(trim(full_name), ' ', ''))));
+
+-- "RAKOTO"
+UPDATE members
+SET surname = trim(full_name)
+WHERE surname IS NULL
+  AND instr(trim(full_name), ' ') = 0
+  AND trim(full_name) = upper(trim(full_name))
+  AND trim(full_name) <> lower(trim(full_name));
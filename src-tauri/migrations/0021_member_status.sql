@@ -0,0 +1,6 @@
+-- ─── Statut du membre ────────────────────────────────────────────────────────
+-- Indépendant du type (Communiant / Cathekomen) : "Actif", "Inactif" ou
+-- "Décédé" (valeurs contrôlées par `MemberStatus`). Seuls les membres actifs
+-- sont comptés par le tableau de bord ; les cotisations des autres restent dans
+-- les archives et les totaux annuels.
+ALTER TABLE members ADD COLUMN status TEXT NOT NULL DEFAULT 'Actif';
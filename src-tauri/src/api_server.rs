@@ -6,24 +6,52 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
 use serde::Deserialize;
 use tower_http::cors::CorsLayer;
 
-use crate::db::Repository;
+use crate::db::{
+    AppError, ContributionSearchScope, MemberFilter, MemberSort, MemberStatus, MemberType, Repository, Settings,
+};
+use crate::i18n::Lang;
 use crate::export::{
-    build_csv_from_members, build_excel_bytes, parse_csv_to_members,
+    build_contributions_csv, build_csv_from_members, build_excel_bytes, build_member_statement_csv,
+    build_receipt_html, build_year_report_html, build_year_report_pdf,
 };
 
 type Repo = Arc<Repository>;
-type ApiErr = (StatusCode, String);
+type ApiErr = (StatusCode, Json<AppError>);
+
+/// En-tête portant le jeton de `verify_pin` pour les routes destructrices.
+pub const PIN_TOKEN_HEADER: &str = "x-pin-token";
 
-fn e500(e: impl std::fmt::Display) -> ApiErr {
-    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+/// Contrôle le jeton PIN d'une requête destructrice (sans PIN configuré, tout passe).
+async fn require_pin(repo: &Repository, headers: &HeaderMap) -> Result<(), ApiErr> {
+    let token = headers.get(PIN_TOKEN_HEADER).and_then(|v| v.to_str().ok());
+    repo.require_pin_token(token).await.map_err(api_err)
+}
+
+/// Erreur structurée `{ kind, message }` (relue telle quelle par RemoteClient)
+/// avec le code HTTP correspondant.
+fn api_err(e: impl Into<AppError>) -> ApiErr {
+    let e = e.into();
+    let status = match e {
+        AppError::Validation(_)              => StatusCode::BAD_REQUEST,
+        AppError::NotFound(_)                => StatusCode::NOT_FOUND,
+        AppError::Conflict(_)                => StatusCode::CONFLICT,
+        AppError::Network(_)                 => StatusCode::BAD_GATEWAY,
+        AppError::Busy                       => StatusCode::SERVICE_UNAVAILABLE,
+        AppError::Db
+        | AppError::Io(_)
+        | AppError::Migration(_)
+        | AppError::Corrupt(_)
+        | AppError::Internal(_)              => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(e))
 }
 
 // ── Lancement ─────────────────────────────────────────────────────────────────
@@ -36,28 +64,82 @@ pub async fn start_server(repo: Repository, port: u16) {
         .route("/api/health", get(health))
         // Members
         .route("/api/members", get(get_members).post(create_member))
+        .route("/api/members/counts", get(get_member_counts))
+        .route("/api/members/birthdays/:days", get(get_upcoming_birthdays))
+        .route("/api/members/renewals/:days", get(get_upcoming_renewals))
+        .route("/api/members/by-card", post(get_member_by_card))
+        .route("/api/members/search", post(search_members))
+        .route("/api/members/similar", post(find_similar_members))
+        .route("/api/members/lite", post(search_members_lite))
+        .route("/api/members/deleted", get(get_deleted_members))
+        .route("/api/members/without-contribution", post(get_members_without_contribution))
+        .route("/api/members/:id/restore", post(restore_member))
+        .route("/api/members/:id/year-totals", get(get_member_year_totals))
+        .route("/api/members/:id/notes", get(get_member_notes).post(add_member_note))
+        .route("/api/member-notes/:id", delete(delete_member_note_route))
+        .route("/api/members/:id/purge", delete(purge_member_route))
+        .route("/api/members/next-card/:member_type", get(next_card_number))
         .route("/api/members/by-type/:member_type", get(get_members_by_type))
-        .route("/api/members/by-type/:member_type/totals", get(get_members_by_type_with_total))
+        .route("/api/members/by-type/:member_type/totals", post(get_members_by_type_with_total))
+        .route("/api/members/by-type/:member_type/year-totals", post(get_members_with_year_totals))
+        .route("/api/members/by-type/:member_type/by-address", get(get_members_grouped_by_address))
+        .route("/api/members/by-type/:member_type/rows", post(get_member_rows))
         .route("/api/members/:id", get(get_member).put(update_member).delete(delete_member_route))
         .route("/api/transfer-members", post(transfer_members))
+        .route("/api/members/merge", post(merge_members))
         // Contributions
         .route("/api/contributions", post(create_contribution))
+        .route("/api/contributions/batch", post(create_contributions_batch))
+        .route("/api/contributions/:id/restore", post(restore_contribution))
+        .route("/api/contributions/by-receipt/:receipt", get(get_contribution_by_receipt))
         .route("/api/contributions/by-member/:member_id", get(get_contributions_by_member))
-        .route("/api/contributions/by-year/:year/with-member", get(get_contributions_by_year_with_member))
-        .route("/api/contributions/all/with-member", get(get_all_contributions_with_member))
+        .route(
+            "/api/contributions/by-member/:member_id/recent/:limit",
+            get(get_recent_contributions),
+        )
+        .route("/api/contributions/with-member", post(get_contributions_by_year_with_member))
         .route("/api/contributions/by-year/:year", get(get_contributions_by_year))
+        .route("/api/contributions/periods/:year", get(get_distinct_periods))
         .route("/api/contributions/:id", delete(delete_contribution_route).put(update_contribution_route))
         // PIN
         .route("/api/verify-pin", post(verify_pin_route))
+        .route("/api/has-pin", get(has_pin_route))
         // Year summaries
         .route("/api/year-summaries", get(get_year_summaries))
-        .route("/api/year-summaries/:year", get(get_year_summary))
+        .route("/api/year-summaries/:year", get(get_year_summary).delete(delete_empty_year_summary))
+        .route("/api/years-with-contributions", get(get_years_with_contributions))
         .route("/api/year-summaries/:year/close", post(close_year))
+        .route("/api/year-summaries/:year/close-preview", get(preview_year_close))
         .route("/api/year-summaries/:year/reopen", post(reopen_year))
+        .route("/api/year-summaries/:year/note", put(update_year_note))
+        .route("/api/year-summaries/:year/member-totals", get(get_year_member_totals))
         .route("/api/year/check-close", post(check_and_close_previous_year))
+        .route("/api/year-comparison", post(get_year_comparison))
+        .route("/api/monthly-totals/:year", get(get_monthly_totals))
+        .route("/api/weekly-totals/:year", get(get_weekly_totals))
+        .route("/api/compare-years/:year_a/:year_b", get(compare_years))
+        .route("/api/year-breakdown/:year", get(get_year_breakdown))
+        // Statistiques
+        .route("/api/statistics", get(get_statistics))
+        .route("/api/dashboard-stats", get(get_dashboard_stats))
+        // Paramètres
+        .route("/api/settings", get(get_settings).put(update_settings))
+        .route("/api/language", get(get_language).put(set_language))
+        // Intégrité
+        .route("/api/integrity", get(run_integrity_check))
+        .route("/api/integrity/repair", post(repair_integrity))
+        .route("/api/db-health", get(get_db_health))
+        .route("/api/pending-tasks", get(get_pending_tasks))
+        .route("/api/maintenance/phones", post(normalize_all_phones))
+        .route("/api/audit-log", post(get_audit_log))
         // Export / Import
         .route("/api/export/csv/:member_type", get(export_csv))
         .route("/api/export/excel/:member_type", get(export_excel))
+        .route("/api/export/year/:year", get(export_year))
+        .route("/api/export/year/:year/pdf", get(export_year_pdf))
+        .route("/api/export/receipt/:receipt", get(export_receipt))
+        .route("/api/export/contributions/:year", get(export_contributions))
+        .route("/api/export/statement/:member_id/:year", get(export_member_statement))
         .route("/api/import/csv/:member_type", post(import_csv))
         .layer(CorsLayer::permissive())
         .with_state(repo);
@@ -86,35 +168,201 @@ async fn health() -> &'static str {
 // ── Members ───────────────────────────────────────────────────────────────────
 
 async fn get_members(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_members().await.map(Json).map_err(e500)
+    repo.get_members().await.map(Json).map_err(api_err)
 }
 
 async fn get_member(
     State(repo): State<Repo>,
     Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_member(id).await.map(Json).map_err(e500)
+    repo.get_member(id).await.map(Json).map_err(api_err)
+}
+
+async fn get_member_year_totals(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_member_year_totals(id).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct NoteBody {
+    text: String,
+}
+
+async fn add_member_note(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
+    Json(body): Json<NoteBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.add_member_note(id, body.text).await.map(Json).map_err(api_err)
+}
+
+async fn get_member_notes(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_member_notes(id).await.map(Json).map_err(api_err)
+}
+
+async fn delete_member_note_route(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.delete_member_note(id).await.map(|_| StatusCode::NO_CONTENT).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct ByCardBody {
+    card_number: String,
+}
+
+async fn get_member_by_card(
+    State(repo): State<Repo>,
+    Json(body): Json<ByCardBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_member_by_card(&body.card_number).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct SearchBody {
+    query: String,
+    limit: Option<i64>,
+}
+
+async fn search_members(
+    State(repo): State<Repo>,
+    Json(body): Json<SearchBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.search_members(&body.query, body.limit).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct LiteSearchBody {
+    query: String,
+}
+
+async fn search_members_lite(
+    State(repo): State<Repo>,
+    Json(body): Json<LiteSearchBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.search_members_lite(&body.query).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct SimilarBody {
+    full_name: String,
+}
+
+async fn find_similar_members(
+    State(repo): State<Repo>,
+    Json(body): Json<SimilarBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.find_similar_members(&body.full_name).await.map(Json).map_err(api_err)
 }
 
 async fn get_members_by_type(
     State(repo): State<Repo>,
-    Path(member_type): Path<String>,
+    Path(member_type): Path<MemberType>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_members_by_type(&member_type).await.map(Json).map_err(e500)
+    repo.get_members_by_type(member_type).await.map(Json).map_err(api_err)
+}
+
+async fn get_member_counts(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_member_counts().await.map(Json).map_err(api_err)
+}
+
+async fn get_upcoming_birthdays(
+    State(repo): State<Repo>,
+    Path(days): Path<u32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_upcoming_birthdays(days).await.map(Json).map_err(api_err)
+}
+
+async fn get_upcoming_renewals(
+    State(repo): State<Repo>,
+    Path(days): Path<u32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_upcoming_renewals(days).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct StatusFilterBody {
+    status: Option<MemberStatus>,
 }
 
+/// POST : le filtre de statut facultatif passe dans le corps.
 async fn get_members_by_type_with_total(
     State(repo): State<Repo>,
-    Path(member_type): Path<String>,
+    Path(member_type): Path<MemberType>,
+    Json(b): Json<StatusFilterBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_members_by_type_with_total(&member_type).await.map(Json).map_err(e500)
+    repo.get_members_by_type_with_total(member_type, b.status).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct YearTotalsBody {
+    years: Vec<i32>,
+}
+
+async fn get_members_with_year_totals(
+    State(repo): State<Repo>,
+    Path(member_type): Path<MemberType>,
+    Json(b): Json<YearTotalsBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_members_with_year_totals(member_type, &b.years).await.map(Json).map_err(api_err)
+}
+
+async fn get_members_grouped_by_address(
+    State(repo): State<Repo>,
+    Path(member_type): Path<MemberType>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_members_grouped_by_address(member_type).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct WithoutContributionBody {
+    year:        i32,
+    member_type: Option<String>,
+}
+
+async fn get_members_without_contribution(
+    State(repo): State<Repo>,
+    Json(b): Json<WithoutContributionBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_members_without_contribution(b.year, b.member_type.as_deref())
+        .await
+        .map(Json)
+        .map_err(api_err)
+}
+
+/// POST : la recherche libre et les filtres passent dans le corps.
+#[derive(Deserialize)]
+struct MemberRowsBody {
+    page: i64,
+    page_size: i64,
+    #[serde(default)]
+    sort: MemberSort,
+    #[serde(default)]
+    filter: MemberFilter,
+}
+
+async fn get_member_rows(
+    State(repo): State<Repo>,
+    Path(member_type): Path<MemberType>,
+    Json(body): Json<MemberRowsBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_member_rows(member_type, body.page, body.page_size, body.sort, &body.filter)
+        .await
+        .map(Json)
+        .map_err(api_err)
 }
 
 async fn create_member(
     State(repo): State<Repo>,
     Json(input): Json<crate::db::MemberInput>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.create_member(input).await.map(Json).map_err(e500)
+    repo.create_member(input).await.map(Json).map_err(api_err)
 }
 
 async fn update_member(
@@ -122,30 +370,74 @@ async fn update_member(
     Path(id): Path<i64>,
     Json(input): Json<crate::db::MemberInput>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.update_member(id, input).await.map(Json).map_err(e500)
+    repo.update_member(id, input).await.map(Json).map_err(api_err)
 }
 
 async fn delete_member_route(
     State(repo): State<Repo>,
     Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiErr> {
+    require_pin(&repo, &headers).await?;
+    repo.delete_member(id).await.map(|_| StatusCode::NO_CONTENT).map_err(api_err)
+}
+
+async fn get_deleted_members(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_deleted_members().await.map(Json).map_err(api_err)
+}
+
+async fn restore_member(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.delete_member(id).await.map(|_| StatusCode::NO_CONTENT).map_err(e500)
+    repo.restore_member(id).await.map(Json).map_err(api_err)
+}
+
+async fn purge_member_route(
+    State(repo): State<Repo>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiErr> {
+    require_pin(&repo, &headers).await?;
+    repo.purge_member(id).await.map(|_| StatusCode::NO_CONTENT).map_err(api_err)
+}
+
+async fn next_card_number(
+    State(repo): State<Repo>,
+    Path(member_type): Path<MemberType>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.next_card_number(member_type).await.map(Json).map_err(api_err)
 }
 
 #[derive(Deserialize)]
 struct TransferBody {
     ids: Vec<i64>,
-    new_type: String,
+    new_type: MemberType,
 }
 
 async fn transfer_members(
     State(repo): State<Repo>,
     Json(body): Json<TransferBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.transfer_members(&body.ids, &body.new_type)
+    repo.transfer_members(&body.ids, body.new_type)
         .await
         .map(Json)
-        .map_err(e500)
+        .map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct MergeBody {
+    keep_id: i64,
+    remove_id: i64,
+}
+
+async fn merge_members(
+    State(repo): State<Repo>,
+    headers: HeaderMap,
+    Json(body): Json<MergeBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    require_pin(&repo, &headers).await?;
+    repo.merge_members(body.keep_id, body.remove_id).await.map(Json).map_err(api_err)
 }
 
 // ── Contributions ─────────────────────────────────────────────────────────────
@@ -154,60 +446,111 @@ async fn get_contributions_by_member(
     State(repo): State<Repo>,
     Path(member_id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_contributions(member_id).await.map(Json).map_err(e500)
+    repo.get_contributions(member_id).await.map(Json).map_err(api_err)
+}
+
+async fn get_recent_contributions(
+    State(repo): State<Repo>,
+    Path((member_id, limit)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_recent_contributions(member_id, limit).await.map(Json).map_err(api_err)
 }
 
 async fn get_contributions_by_year(
     State(repo): State<Repo>,
     Path(year): Path<i32>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_contributions_by_year(year).await.map(Json).map_err(e500)
+    repo.get_contributions_by_year(year).await.map(Json).map_err(api_err)
+}
+
+async fn get_distinct_periods(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_distinct_periods(year).await.map(Json).map_err(api_err)
 }
 
 async fn create_contribution(
     State(repo): State<Repo>,
     Json(input): Json<crate::db::ContributionInput>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.create_contribution(input).await.map(Json).map_err(e500)
+    repo.create_contribution(input).await.map(Json).map_err(api_err)
+}
+
+async fn create_contributions_batch(
+    State(repo): State<Repo>,
+    Json(inputs): Json<Vec<crate::db::ContributionInput>>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.create_contributions_batch(inputs).await.map(Json).map_err(api_err)
 }
 
 async fn delete_contribution_route(
     State(repo): State<Repo>,
     Path(id): Path<i64>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.delete_contribution(id).await.map(|_| StatusCode::NO_CONTENT).map_err(e500)
+    require_pin(&repo, &headers).await?;
+    repo.delete_contribution(id).await.map(|_| StatusCode::NO_CONTENT).map_err(api_err)
 }
 
-async fn get_contributions_by_year_with_member(
+async fn restore_contribution(
     State(repo): State<Repo>,
-    Path(year): Path<i32>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_contributions_by_year_with_member(year)
-        .await
-        .map(Json)
-        .map_err(e500)
+    repo.restore_contribution(id).await.map(Json).map_err(api_err)
+}
+
+async fn get_contribution_by_receipt(
+    State(repo): State<Repo>,
+    Path(receipt): Path<String>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_contribution_by_receipt(&receipt).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct ContributionPageBody {
+    year:        Option<i32>,
+    /// Texte recherché (nom conservé pour les clients plus anciens)
+    name_filter: Option<String>,
+    /// Absent des anciens clients : tous les champs
+    #[serde(default)]
+    scope:       ContributionSearchScope,
+    limit:       Option<i64>,
+    offset:      Option<i64>,
 }
 
-async fn get_all_contributions_with_member(
+async fn get_contributions_by_year_with_member(
     State(repo): State<Repo>,
+    Json(b): Json<ContributionPageBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_all_contributions_with_member()
+    repo.get_contributions_by_year_with_member(b.year, b.name_filter.as_deref(), b.scope, b.limit, b.offset)
         .await
         .map(Json)
-        .map_err(e500)
+        .map_err(api_err)
 }
 
 // ── Year Summaries ────────────────────────────────────────────────────────────
 
 async fn get_year_summaries(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_year_summaries().await.map(Json).map_err(e500)
+    repo.get_year_summaries().await.map(Json).map_err(api_err)
 }
 
 async fn get_year_summary(
     State(repo): State<Repo>,
     Path(year): Path<i32>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.get_year_summary(year).await.map(Json).map_err(e500)
+    repo.get_year_summary(year).await.map(Json).map_err(api_err)
+}
+
+async fn get_years_with_contributions(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_years_with_contributions().await.map(Json).map_err(api_err)
+}
+
+async fn delete_empty_year_summary(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.delete_empty_year_summary(year).await.map(|_| StatusCode::NO_CONTENT).map_err(api_err)
 }
 
 #[derive(Deserialize)]
@@ -220,20 +563,183 @@ async fn close_year(
     Path(year): Path<i32>,
     Json(body): Json<CloseYearBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.close_year(year, body.note).await.map(Json).map_err(e500)
+    repo.close_year(year, body.note).await.map(Json).map_err(api_err)
+}
+
+async fn preview_year_close(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.preview_year_close(year).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct ReopenYearBody {
+    #[serde(default)]
+    cascade: bool,
 }
 
 async fn reopen_year(
     State(repo): State<Repo>,
     Path(year): Path<i32>,
+    headers: HeaderMap,
+    Json(body): Json<ReopenYearBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.reopen_year(year).await.map(Json).map_err(e500)
+    require_pin(&repo, &headers).await?;
+    repo.reopen_year(year, body.cascade).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct YearNoteBody {
+    note: Option<String>,
+}
+
+async fn update_year_note(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+    headers: HeaderMap,
+    Json(body): Json<YearNoteBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    require_pin(&repo, &headers).await?;
+    repo.update_year_note(year, body.note).await.map(Json).map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct CheckCloseBody {
+    /// Absent → délai et activation lus dans les paramètres.
+    grace_days: Option<u32>,
 }
 
 async fn check_and_close_previous_year(
     State(repo): State<Repo>,
+    Json(body): Json<CheckCloseBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.check_and_close_previous_year(body.grace_days)
+        .await
+        .map(Json)
+        .map_err(api_err)
+}
+
+#[derive(Deserialize)]
+struct YearComparisonBody {
+    years: Vec<i32>,
+}
+
+async fn get_year_comparison(
+    State(repo): State<Repo>,
+    Json(body): Json<YearComparisonBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_year_comparison(body.years).await.map(Json).map_err(api_err)
+}
+
+async fn get_monthly_totals(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_monthly_totals(year).await.map(Json).map_err(api_err)
+}
+
+async fn get_year_member_totals(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_year_member_totals(year).await.map(Json).map_err(api_err)
+}
+
+async fn get_weekly_totals(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.check_and_close_previous_year().await.map(Json).map_err(e500)
+    repo.get_weekly_totals(year).await.map(Json).map_err(api_err)
+}
+
+async fn compare_years(
+    State(repo): State<Repo>,
+    Path((year_a, year_b)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.compare_years(year_a, year_b).await.map(Json).map_err(api_err)
+}
+
+async fn get_year_breakdown(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_year_breakdown(year).await.map(Json).map_err(api_err)
+}
+
+// ── Statistiques ──────────────────────────────────────────────────────────────
+
+async fn get_statistics(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_statistics().await.map(Json).map_err(api_err)
+}
+
+async fn get_dashboard_stats(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_dashboard_stats().await.map(Json).map_err(api_err)
+}
+
+// ── Paramètres ────────────────────────────────────────────────────────────────
+
+async fn get_settings(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_settings().await.map(Json).map_err(api_err)
+}
+
+async fn update_settings(
+    State(repo): State<Repo>,
+    Json(settings): Json<Settings>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.update_settings(settings)
+        .await
+        .map(Json)
+        .map_err(api_err)
+}
+
+async fn get_language(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_language().await.map(Json).map_err(api_err)
+}
+
+async fn set_language(
+    State(repo): State<Repo>,
+    Json(lang): Json<Lang>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.set_language(lang).await.map(Json).map_err(api_err)
+}
+
+// ── Intégrité ─────────────────────────────────────────────────────────────────
+
+async fn run_integrity_check(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.run_integrity_check().await.map(Json).map_err(api_err)
+}
+
+async fn repair_integrity(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.repair_integrity().await.map(Json).map_err(api_err)
+}
+
+async fn get_db_health(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.health().await.map(Json).map_err(api_err)
+}
+
+async fn get_pending_tasks(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_pending_tasks().await.map(Json).map_err(api_err)
+}
+
+async fn normalize_all_phones(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.normalize_all_phones().await.map(Json).map_err(api_err)
+}
+
+// ── Journal d'audit ───────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct AuditLogBody {
+    limit:  Option<i64>,
+    offset: Option<i64>,
+    entity: Option<String>,
+}
+
+async fn get_audit_log(
+    State(repo): State<Repo>,
+    Json(b): Json<AuditLogBody>,
+) -> Result<impl IntoResponse, ApiErr> {
+    repo.get_audit_log(b.limit, b.offset, b.entity.as_deref()).await.map(Json).map_err(api_err)
 }
 
 // ── PIN ───────────────────────────────────────────────────────────────────────
@@ -243,11 +749,15 @@ struct VerifyPinBody {
     pin: String,
 }
 
+async fn has_pin_route(State(repo): State<Repo>) -> Result<impl IntoResponse, ApiErr> {
+    repo.has_pin().await.map(Json).map_err(api_err)
+}
+
 async fn verify_pin_route(
     State(repo): State<Repo>,
     Json(body): Json<VerifyPinBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    repo.verify_pin(&body.pin).await.map(Json).map_err(e500)
+    repo.verify_pin(&body.pin).await.map(Json).map_err(api_err)
 }
 
 async fn update_contribution_route(
@@ -258,27 +768,74 @@ async fn update_contribution_route(
     repo.update_contribution(id, input)
         .await
         .map(Json)
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+        .map_err(api_err)
 }
 
 // ── Export / Import ───────────────────────────────────────────────────────────
 
 async fn export_csv(
     State(repo): State<Repo>,
-    Path(member_type): Path<String>,
+    Path(member_type): Path<MemberType>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    let members = repo.get_members_by_type(&member_type).await.map_err(e500)?;
+    let members = repo.get_members_by_type(member_type).await.map_err(api_err)?;
     let csv = build_csv_from_members(&members);
     // Retourner comme JSON string pour que le client puisse désérialiser facilement
     Ok(Json(csv))
 }
 
+async fn export_year(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    let report = repo.get_year_report(year).await.map_err(api_err)?;
+    let settings = repo.get_settings().await.map_err(api_err)?;
+    Ok(Json(build_year_report_html(&report, &settings.church_name)))
+}
+
+async fn export_receipt(
+    State(repo): State<Repo>,
+    Path(receipt): Path<String>,
+) -> Result<impl IntoResponse, ApiErr> {
+    let contribution = repo.get_contribution_by_receipt(&receipt).await.map_err(api_err)?;
+    let s = repo.get_settings().await.map_err(api_err)?;
+    Ok(Json(build_receipt_html(&contribution, &s)))
+}
+
+async fn export_year_pdf(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    let report = repo.get_year_report(year).await.map_err(api_err)?;
+    let settings = repo.get_settings().await.map_err(api_err)?;
+    Ok((
+        StatusCode::OK,
+        [("Content-Type", "application/pdf")],
+        build_year_report_pdf(&report, &settings.church_name),
+    ))
+}
+
+async fn export_contributions(
+    State(repo): State<Repo>,
+    Path(year): Path<i32>,
+) -> Result<impl IntoResponse, ApiErr> {
+    let report = repo.get_year_report(year).await.map_err(api_err)?;
+    Ok(Json(build_contributions_csv(&report)))
+}
+
+async fn export_member_statement(
+    State(repo): State<Repo>,
+    Path((member_id, year)): Path<(i64, i32)>,
+) -> Result<impl IntoResponse, ApiErr> {
+    let statement = repo.get_member_statement(member_id, year).await.map_err(api_err)?;
+    Ok(Json(build_member_statement_csv(&statement)))
+}
+
 async fn export_excel(
     State(repo): State<Repo>,
-    Path(member_type): Path<String>,
+    Path(member_type): Path<MemberType>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    let members = repo.get_members_by_type_with_total(&member_type).await.map_err(e500)?;
-    let bytes = build_excel_bytes(&members, &member_type).map_err(|e| e500(e))?;
+    let members = repo.get_members_by_type_with_total(member_type, None).await.map_err(api_err)?;
+    let bytes = build_excel_bytes(&members, member_type.as_str()).map_err(api_err)?;
     Ok((
         StatusCode::OK,
         [(
@@ -296,9 +853,8 @@ struct ImportCsvBody {
 
 async fn import_csv(
     State(repo): State<Repo>,
-    Path(member_type): Path<String>,
+    Path(member_type): Path<MemberType>,
     Json(body): Json<ImportCsvBody>,
 ) -> Result<impl IntoResponse, ApiErr> {
-    let inputs = parse_csv_to_members(&body.content, &member_type);
-    repo.import_members(inputs).await.map(Json).map_err(e500)
+    repo.import_members_csv(&body.content, member_type).await.map(Json).map_err(api_err)
 }
@@ -0,0 +1,202 @@
+/// Sauvegardes locales de la base SQLite (`app_data_dir/backups/`).
+///
+/// - automatique, une fois par jour : `eglise-YYYY-MM-DD.db` (tâche lancée au setup)
+/// - manuelle ou avant restauration : `eglise-YYYY-MM-DD-HHMMSS.db`
+/// - rotation : seules les N plus récentes sont conservées (`settings.backup_retention`)
+///
+/// Succès et échecs sont consignés dans `app_log` (source 'backup' / 'restore').
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::Serialize;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{db::Repository, DataSource};
+
+/// Sérialise sauvegarde automatique, sauvegarde manuelle et restauration.
+static BACKUP_LOCK: Mutex<()> = Mutex::const_new(());
+
+const FILE_PREFIX: &str = "eglise-";
+const FILE_EXT: &str = ".db";
+/// Délai avant la première vérification (ne ralentit pas le démarrage).
+const STARTUP_DELAY: Duration = Duration::from_secs(60);
+/// Fréquence de vérification « la sauvegarde du jour existe-t-elle ? ».
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub file_name:  String,
+    pub size_bytes: u64,
+    /// Date de dernière écriture du fichier, heure locale "YYYY-MM-DDTHH:MM:SS"
+    pub created_at: String,
+}
+
+// ── Fichiers ──────────────────────────────────────────────────────────────────
+
+pub fn backups_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("backups")
+}
+
+/// Nom de fichier produit par ce module (refuse tout chemin : pas de `..` ni de séparateur).
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(FILE_PREFIX)
+        && name.ends_with(FILE_EXT)
+        && !name.contains(['/', '\\'])
+        && !name.contains("..")
+}
+
+/// Sauvegardes présentes dans `dir`, les plus récentes d'abord.
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupInfo>, String> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut list = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if !is_backup_name(&file_name) {
+            continue;
+        }
+        let meta = entry.metadata().map_err(|e| e.to_string())?;
+        let modified: chrono::DateTime<chrono::Local> =
+            meta.modified().map_err(|e| e.to_string())?.into();
+        list.push(BackupInfo {
+            file_name,
+            size_bytes: meta.len(),
+            created_at: modified.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        });
+    }
+    list.sort_by(|a, b| {
+        b.created_at.cmp(&a.created_at).then_with(|| b.file_name.cmp(&a.file_name))
+    });
+    Ok(list)
+}
+
+/// Supprime les sauvegardes au-delà des `keep` plus récentes. Retourne le nombre supprimé.
+fn prune_backups(dir: &Path, keep: usize) -> Result<usize, String> {
+    let old = list_backups(dir)?.into_iter().skip(keep);
+    let mut removed = 0;
+    for b in old {
+        std::fs::remove_file(dir.join(&b.file_name)).map_err(|e| e.to_string())?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Écrit la sauvegarde `file_name` (remplacée si elle existe), puis applique la rotation si `prune`.
+/// L'appelant doit détenir `BACKUP_LOCK`.
+async fn write_backup(
+    repo: &Repository,
+    dir: &Path,
+    file_name: &str,
+    prune: bool,
+) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(file_name);
+    // VACUUM INTO refuse d'écraser un fichier existant
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    let path_str = path.to_str().ok_or("Chemin de sauvegarde invalide")?;
+    repo.backup_into(path_str).await.map_err(|e| e.to_string())?;
+    if prune {
+        let keep = repo.get_backup_retention().await.map_err(|e| e.to_string())?;
+        prune_backups(dir, keep)?;
+    }
+    Ok(())
+}
+
+/// Consigne l'issue d'une opération dans `app_log` (stderr si la base elle-même est en échec).
+async fn log_outcome(repo: &Repository, source: &str, result: &Result<String, String>) {
+    let (level, message) = match result {
+        Ok(msg)  => ("info", msg.as_str()),
+        Err(err) => ("error", err.as_str()),
+    };
+    if let Err(e) = repo.log_event(level, source, message).await {
+        eprintln!("[{source}] {message} (journal indisponible : {e})");
+    }
+}
+
+fn timestamped_name() -> String {
+    format!("{FILE_PREFIX}{}{FILE_EXT}", chrono::Local::now().format("%Y-%m-%d-%H%M%S"))
+}
+
+// ── Opérations ────────────────────────────────────────────────────────────────
+
+/// Sauvegarde manuelle immédiate. Retourne le nom du fichier créé.
+pub async fn backup_now(repo: &Repository, app_data_dir: &Path) -> Result<String, String> {
+    let _guard = BACKUP_LOCK.lock().await;
+    let file_name = timestamped_name();
+    let result = write_backup(repo, &backups_dir(app_data_dir), &file_name, true)
+        .await
+        .map(|_| format!("Sauvegarde manuelle créée : {file_name}"))
+        .map_err(|e| format!("Échec de la sauvegarde manuelle : {e}"));
+    log_outcome(repo, "backup", &result).await;
+    result.map(|_| file_name)
+}
+
+/// Restaure la sauvegarde `file_name` après avoir sauvegardé l'état courant.
+pub async fn restore_backup(
+    repo: &Repository,
+    app_data_dir: &Path,
+    file_name: &str,
+) -> Result<(), String> {
+    let dir = backups_dir(app_data_dir);
+    if !is_backup_name(file_name) || !dir.join(file_name).is_file() {
+        return Err(format!("Sauvegarde introuvable : {file_name}"));
+    }
+    let _guard = BACKUP_LOCK.lock().await;
+
+    // Filet de sécurité : l'état actuel reste restaurable (sans rotation, pour ne pas
+    // supprimer la sauvegarde qu'on s'apprête à restaurer).
+    let snapshot = timestamped_name();
+    let result = match write_backup(repo, &dir, &snapshot, false).await {
+        Err(e) => Err(format!("Restauration annulée, sauvegarde préalable impossible : {e}")),
+        Ok(()) => {
+            let path = dir.join(file_name);
+            let path_str = path.to_str().ok_or("Chemin de sauvegarde invalide")?;
+            repo.restore_from(path_str)
+                .await
+                .map(|_| format!("Base restaurée depuis {file_name} (état précédent : {snapshot})"))
+                .map_err(|e| format!("Échec de la restauration de {file_name} : {e}"))
+        }
+    };
+    log_outcome(repo, "restore", &result).await;
+    result.map(|_| ())
+}
+
+/// Crée la sauvegarde du jour si elle n'existe pas encore.
+async fn daily_backup_if_due(repo: &Repository, dir: &Path) {
+    let file_name = format!("{FILE_PREFIX}{}{FILE_EXT}", chrono::Local::now().format("%Y-%m-%d"));
+    if dir.join(&file_name).exists() {
+        return;
+    }
+    let _guard = BACKUP_LOCK.lock().await;
+    let result = write_backup(repo, dir, &file_name, true)
+        .await
+        .map(|_| format!("Sauvegarde automatique créée : {file_name}"))
+        .map_err(|e| format!("Échec de la sauvegarde automatique : {e}"));
+    log_outcome(repo, "backup", &result).await;
+}
+
+/// Boucle de fond : vérifie toutes les heures si la sauvegarde du jour est faite.
+/// Ne fait rien en mode client ou non configuré (la base est sur le PC serveur).
+pub async fn run_daily_backups(app_data_dir: PathBuf, source: Arc<RwLock<DataSource>>) {
+    let dir = backups_dir(&app_data_dir);
+    tokio::time::sleep(STARTUP_DELAY).await;
+    loop {
+        // Clone du Repository (pool partagé) : le verrou de lecture n'est pas gardé
+        // pendant la sauvegarde, `save_config` peut remplacer la source entre-temps.
+        let repo = match &*source.read().await {
+            DataSource::Local(r) => Some(r.clone()),
+            _ => None,
+        };
+        if let Some(repo) = repo {
+            daily_backup_if_due(&repo, &dir).await;
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
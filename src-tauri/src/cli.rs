@@ -0,0 +1,251 @@
+/// Maintenance en ligne de commande, sans ouvrir la fenêtre (WebView absente ou
+/// cassée, scripts de sauvegarde…) :
+///
+/// ```text
+/// fjkm-ambalavao-isotry --export-members membres.csv
+/// fjkm-ambalavao-isotry --backup copie.db
+/// fjkm-ambalavao-isotry --stats
+/// fjkm-ambalavao-isotry --vacuum
+/// ```
+///
+/// Opère sur la base locale du dossier de données par défaut (`--data-dir` pour
+/// un autre dossier). Codes de sortie : 0 succès, 1 échec de l'opération,
+/// 2 ligne de commande invalide.
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::{self, AppMode},
+    db::{format_ariary, Repository},
+    export,
+};
+
+const EXIT_OK: i32 = 0;
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str = "\
+Usage : fjkm-ambalavao-isotry [--data-dir <dossier>] <commande>
+
+Commandes :
+  --export-members <fichier.csv>  Exporte tous les membres en CSV
+  --backup <fichier.db>           Copie cohérente de la base (le fichier ne doit pas exister)
+  --stats                         Affiche les chiffres clés
+  --vacuum                        Compacte la base
+  --help                          Affiche cette aide
+
+Sans argument, l'application graphique démarre.";
+
+// ─── Analyse des arguments ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    ExportMembers(PathBuf),
+    Backup(PathBuf),
+    Stats,
+    Vacuum,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+struct Invocation {
+    data_dir: Option<PathBuf>,
+    command:  Command,
+}
+
+/// `Ok(None)` : aucune option `--…`, c'est un lancement de la GUI (les arguments
+/// ajoutés par le système, comme `-psn_…` sur macOS, sont ignorés).
+fn parse_args(args: &[String]) -> Result<Option<Invocation>, String> {
+    if !args.iter().any(|a| a.starts_with("--")) {
+        return Ok(None);
+    }
+    let mut data_dir = None;
+    let mut command = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .filter(|v| !v.starts_with("--"))
+                .map(PathBuf::from)
+                .ok_or_else(|| format!("{name} attend un chemin"))
+        };
+        let parsed = match arg.as_str() {
+            "--data-dir" => {
+                data_dir = Some(value(arg)?);
+                continue;
+            }
+            "--export-members" => Command::ExportMembers(value(arg)?),
+            "--backup"         => Command::Backup(value(arg)?),
+            "--stats"          => Command::Stats,
+            "--vacuum"         => Command::Vacuum,
+            "--help"           => Command::Help,
+            other              => return Err(format!("option inconnue : {other}")),
+        };
+        if command.replace(parsed).is_some() {
+            return Err("une seule commande à la fois".into());
+        }
+    }
+    let command = command.ok_or("aucune commande indiquée")?;
+    Ok(Some(Invocation { data_dir, command }))
+}
+
+// ─── Exécution ────────────────────────────────────────────────────────────────
+
+/// Exécute la commande demandée par `args` (sans le nom du programme) et renvoie
+/// le code de sortie ; `None` si l'application graphique doit démarrer.
+pub fn run_from_args(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let args: Vec<String> = args.into_iter().collect();
+    let invocation = match parse_args(&args) {
+        Ok(None) => return None,
+        Ok(Some(invocation)) => invocation,
+        Err(e) => {
+            attach_parent_console();
+            eprintln!("Erreur : {e}\n\n{USAGE}");
+            return Some(EXIT_USAGE);
+        }
+    };
+    attach_parent_console();
+    if invocation.command == Command::Help {
+        println!("{USAGE}");
+        return Some(EXIT_OK);
+    }
+
+    let Some(app_data_dir) = invocation.data_dir.or_else(config::default_app_data_dir) else {
+        eprintln!("Erreur : dossier de données introuvable, préciser --data-dir");
+        return Some(EXIT_USAGE);
+    };
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Erreur : {e}");
+            return Some(EXIT_FAILURE);
+        }
+    };
+    Some(match runtime.block_on(execute(&app_data_dir, &invocation.command)) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("Erreur : {e}");
+            EXIT_FAILURE
+        }
+    })
+}
+
+/// Ouvre la base existante : la CLI ne crée jamais de base vide, et refuse un
+/// poste client (les données sont sur le serveur).
+async fn open_repo(app_data_dir: &Path) -> Result<(Repository, PathBuf), String> {
+    let cfg = config::load_config(&app_data_dir.to_path_buf());
+    if cfg.as_ref().is_some_and(|c| c.mode == AppMode::Client) {
+        return Err("poste configuré en mode client : lancer la commande sur le serveur".into());
+    }
+    let db_path = cfg.map_or_else(|| config::db_path(app_data_dir), |c| c.db_path(app_data_dir));
+    if !db_path.is_file() {
+        return Err(format!("base introuvable : {}", db_path.display()));
+    }
+    let path_str = db_path.to_str().ok_or("Chemin DB invalide")?;
+    let repo = Repository::new(path_str).await.map_err(|e| e.to_string())?;
+    Ok((repo, db_path))
+}
+
+async fn execute(app_data_dir: &Path, command: &Command) -> Result<(), String> {
+    let (repo, db_path) = open_repo(app_data_dir).await?;
+    match command {
+        Command::ExportMembers(out) => {
+            let members = repo.get_members().await.map_err(|e| e.to_string())?;
+            std::fs::write(out, export::build_csv_from_members(&members))
+                .map_err(|e| format!("{} : {e}", out.display()))?;
+            println!("{} membre(s) exporté(s) vers {}", members.len(), out.display());
+        }
+        Command::Backup(out) => {
+            if out.exists() {
+                return Err(format!("{} existe déjà", out.display()));
+            }
+            let out_str = out.to_str().ok_or("Chemin de sauvegarde invalide")?;
+            repo.backup_into(out_str).await.map_err(|e| e.to_string())?;
+            println!("Sauvegarde écrite : {}", out.display());
+        }
+        Command::Stats => {
+            let stats = repo.get_dashboard_stats().await.map_err(|e| e.to_string())?;
+            let row = |label: &str, value: String| println!("{label:<22} : {value}");
+            row("Base", db_path.display().to_string());
+            row("Communiants", stats.communiants.to_string());
+            row("Catéchumènes", stats.cathekomens.to_string());
+            row("Hommes / Femmes", format!("{} / {}", stats.men, stats.women));
+            row(&format!("Total {}", stats.year), format_ariary(&stats.current_year_total));
+            row(&format!("Total {}", stats.year - 1), format_ariary(&stats.previous_year_total));
+            row(&format!("Cotisations {}", stats.year), stats.contributions_this_year.to_string());
+            row(
+                "Dernière cotisation",
+                stats.last_contribution_date.unwrap_or_else(|| "-".into()),
+            );
+        }
+        Command::Vacuum => {
+            let before = file_size(&db_path);
+            repo.vacuum().await.map_err(|e| e.to_string())?;
+            println!("Base compactée : {} → {} octets", before, file_size(&db_path));
+        }
+        Command::Help => unreachable!("traitée avant l'ouverture de la base"),
+    }
+    Ok(())
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// En release, le binaire Windows n'a pas de console (`windows_subsystem`) :
+/// on se rattache à celle de l'invite de commandes pour que la sortie s'affiche.
+#[cfg(windows)]
+fn attach_parent_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    // SAFETY : appel Win32 sans pointeur ; échoue sans effet si déjà rattaché.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Invocation>, String> {
+        parse_args(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    fn command(args: &[&str]) -> Command {
+        parse(args).unwrap().unwrap().command
+    }
+
+    #[test]
+    fn test_sans_option_lance_la_gui() {
+        assert_eq!(parse(&[]), Ok(None));
+        assert_eq!(parse(&["-psn_0_12345"]), Ok(None));
+    }
+
+    #[test]
+    fn test_commandes() {
+        assert_eq!(command(&["--export-members", "out.csv"]), Command::ExportMembers("out.csv".into()));
+        assert_eq!(command(&["--backup", "copie.db"]), Command::Backup("copie.db".into()));
+        assert_eq!(command(&["--stats"]), Command::Stats);
+        assert_eq!(command(&["--vacuum"]), Command::Vacuum);
+        assert_eq!(command(&["--help"]), Command::Help);
+
+        let inv = parse(&["--data-dir", "/tmp/fjkm", "--stats"]).unwrap().unwrap();
+        assert_eq!(inv.data_dir, Some(PathBuf::from("/tmp/fjkm")));
+        assert_eq!(inv.command, Command::Stats);
+    }
+
+    #[test]
+    fn test_erreurs() {
+        assert!(parse(&["--backup"]).is_err());
+        assert!(parse(&["--backup", "--stats"]).is_err());
+        assert!(parse(&["--stats", "--vacuum"]).is_err());
+        assert!(parse(&["--inconnue"]).is_err());
+        assert!(parse(&["--data-dir", "/tmp/fjkm"]).is_err());
+    }
+}
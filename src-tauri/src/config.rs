@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,12 +13,53 @@ pub struct AppConfig {
     pub mode: AppMode,
     pub server_ip: String,
     pub server_port: u16,
+    /// Dossier de la base choisi après un échec d'ouverture (`use_data_dir`) ;
+    /// `app_data_dir` si absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
 }
 
 impl AppConfig {
     pub fn server_url(&self) -> String {
         format!("http://{}:{}", self.server_ip, self.server_port)
     }
+
+    /// Fichier SQLite de ce poste : dans `data_dir` s'il a été choisi.
+    pub fn db_path(&self, app_data_dir: &Path) -> PathBuf {
+        db_path(self.data_dir.as_deref().unwrap_or(app_data_dir))
+    }
+}
+
+/// Port de l'API du mode serveur proposé par le Setup.
+pub const DEFAULT_SERVER_PORT: u16 = 7654;
+
+/// Identifiant de l'application (`tauri.conf.json`), nom du dossier de données.
+pub const APP_IDENTIFIER: &str = "mg.fjkm.ambalavao.isotry";
+
+/// Fichier SQLite de la base locale (mode serveur), partagé par la GUI et la CLI.
+pub fn db_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("fjkm.db")
+}
+
+/// `app_data_dir` tel que Tauri le résout, sans instancier l'application
+/// (maintenance en ligne de commande) :
+/// - Windows : `%APPDATA%\<identifiant>`
+/// - macOS   : `~/Library/Application Support/<identifiant>`
+/// - Linux   : `$XDG_DATA_HOME/<identifiant>` ou `~/.local/share/<identifiant>`
+pub fn default_app_data_dir() -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+    let base = if cfg!(windows) {
+        env_dir("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME")?.join("Library").join("Application Support")
+    } else {
+        env_dir("XDG_DATA_HOME").or_else(|| Some(env_dir("HOME")?.join(".local").join("share")))?
+    };
+    Some(base.join(APP_IDENTIFIER))
 }
 
 pub fn config_path(app_data_dir: &PathBuf) -> PathBuf {
@@ -1,33 +1,194 @@
 /// Type d'erreur interne du Repository.
-#[derive(Debug)]
+///
+/// Sérialisé vers le frontend (commandes Tauri, API HTTP) sous la forme
+/// `{ "kind": "validation" | "db" | "not_found" | "conflict" | "network" | "io" | "migration"
+/// | "corrupt" | "internal", "message": "…" }`.
+/// Les messages de validation sont traduits dans la langue courante (`i18n::current`).
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::i18n::{self, Lang, Message};
+
+#[derive(Debug, Clone)]
 pub enum AppError {
     /// Erreur SQLite — loggée à la conversion, jamais exposée au frontend.
     Db,
-    Validation(String),
+    /// Base verrouillée (sauvegarde, antivirus…) — rejouée par `retry_busy`, puis
+    /// présentée comme une erreur de validation : l'utilisateur peut simplement réessayer.
+    Busy,
+    /// Saisie refusée — message traduit à la sérialisation.
+    Validation(Message),
+    /// Ligne demandée absente (membre, résumé annuel…).
+    NotFound(String),
+    /// Violation d'unicité (numéro de carte déjà utilisé…).
+    Conflict(String),
     /// Erreur réseau (mode client HTTP).
     Network(String),
+    /// Fichier de base inaccessible à l'ouverture (dossier absent, droits, disque plein…).
+    Io(String),
+    /// Migrations en échec au démarrage (schéma plus récent que l'application…).
+    Migration(String),
+    /// Fichier présent mais illisible par SQLite (endommagé ou pas une base).
+    Corrupt(String),
+    /// Autre échec côté application (fichiers, export, source non prête…).
+    Internal(String),
 }
 
+impl AppError {
+    /// Erreur de validation à partir d'une clé de `i18n::MESSAGES`.
+    pub fn validation(key: &'static str, params: &[&dyn std::fmt::Display]) -> Self {
+        AppError::Validation(Message::new(key, params))
+    }
+
+    /// Message présenté à l'utilisateur dans `lang`.
+    pub fn message(&self, lang: Lang) -> String {
+        match self {
+            AppError::Validation(m) => m.text(lang),
+            other                   => other.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Db            => "db",
+            AppError::Busy
+            | AppError::Validation(_) => "validation",
+            AppError::NotFound(_)   => "not_found",
+            AppError::Conflict(_)   => "conflict",
+            AppError::Network(_)    => "network",
+            AppError::Io(_)         => "io",
+            AppError::Migration(_)  => "migration",
+            AppError::Corrupt(_)    => "corrupt",
+            AppError::Internal(_)   => "internal",
+        }
+    }
+
+    /// Échec de `Repository::new` à la connexion : distingue un fichier endommagé
+    /// d'un problème d'accès disque ; le reste suit `From<sqlx::Error>`.
+    pub fn from_open(e: sqlx::Error) -> Self {
+        disk_failure(&e).unwrap_or_else(|| e.into())
+    }
+
+    /// Échec des migrations au démarrage : `Corrupt` / `Io` si le fichier lui-même
+    /// est en cause, `Migration` sinon.
+    pub fn from_migrate(e: sqlx::migrate::MigrateError) -> Self {
+        if let sqlx::migrate::MigrateError::Execute(inner) = &e {
+            if let Some(err) = disk_failure(inner) {
+                return err;
+            }
+        }
+        eprintln!("[Migrate Error] {e}");
+        AppError::Migration(e.to_string())
+    }
+}
+
+/// Codes SQLite primaires d'un fichier illisible (CORRUPT, NOTADB) ou inaccessible
+/// (READONLY, IOERR, FULL, CANTOPEN, AUTH).
+fn disk_failure(e: &sqlx::Error) -> Option<AppError> {
+    match e {
+        sqlx::Error::Io(io) => Some(AppError::Io(io.to_string())),
+        sqlx::Error::Database(db) => {
+            let code = db.code().and_then(|c| c.parse::<i32>().ok())? & 0xff;
+            let message = db.message().to_string();
+            match code {
+                11 | 26               => Some(AppError::Corrupt(message)),
+                8 | 10 | 13 | 14 | 23 => Some(AppError::Io(message)),
+                _                     => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Texte en français (journaux, `to_string`) ; le frontend reçoit `message(i18n::current())`.
 impl std::fmt::Display for AppError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AppError::Db            => write!(f, "Erreur interne de la base de données."),
-            AppError::Validation(s) => write!(f, "{s}"),
+            AppError::Busy          => write!(f, "La base est momentanément occupée, réessayez."),
+            AppError::Validation(m) => write!(f, "{}", m.text(Lang::Fr)),
+            AppError::NotFound(s)
+            | AppError::Conflict(s)
+            | AppError::Internal(s) => write!(f, "{s}"),
             AppError::Network(s)    => write!(f, "Erreur réseau : {s}"),
+            AppError::Io(s)         => write!(f, "Fichier de base inaccessible : {s}"),
+            AppError::Migration(s)  => write!(f, "Mise à jour de la base impossible : {s}"),
+            AppError::Corrupt(s)    => write!(f, "Base de données endommagée : {s}"),
         }
     }
 }
 
 impl From<sqlx::Error> for AppError {
     fn from(e: sqlx::Error) -> Self {
-        if let sqlx::Error::Database(ref db_err) = e {
-            if db_err.message().contains("UNIQUE constraint failed") {
-                return AppError::Validation(
+        match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("Élément introuvable.".into()),
+            sqlx::Error::Database(ref db_err) if is_busy_code(db_err.code().as_deref()) => {
+                AppError::Busy
+            }
+            // SQLITE_READONLY : base de démonstration (`Repository::demo`)
+            sqlx::Error::Database(ref db_err)
+                if db_err.code().and_then(|c| c.parse::<i32>().ok()).is_some_and(|c| c & 0xff == 8) =>
+            {
+                AppError::validation("db.read_only", &[])
+            }
+            sqlx::Error::Database(ref db_err)
+                if db_err.message().contains("UNIQUE constraint failed") =>
+            {
+                AppError::Conflict(
                     "Ity laharana karatra ity dia efa misy. Mifidiana laharana hafa.".into(),
-                );
+                )
+            }
+            _ => {
+                eprintln!("[DB Error] {e}");
+                AppError::Db
             }
         }
-        eprintln!("[DB Error] {e}");
-        AppError::Db
+    }
+}
+
+/// SQLITE_BUSY (5) et SQLITE_LOCKED (6), codes étendus compris (ex. 517 = BUSY_SNAPSHOT).
+fn is_busy_code(code: Option<&str>) -> bool {
+    code.and_then(|c| c.parse::<i32>().ok())
+        .is_some_and(|c| matches!(c & 0xff, 5 | 6))
+}
+
+/// Erreurs déjà formatées (sauvegardes, export, configuration).
+impl From<String> for AppError {
+    fn from(s: String) -> Self {
+        AppError::Internal(s)
+    }
+}
+
+// ── Sérialisation { kind, message } ───────────────────────────────────────────
+
+#[derive(Serialize, Deserialize)]
+struct ErrorBody {
+    kind:    String,
+    message: String,
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let message = self.message(i18n::current());
+        ErrorBody { kind: self.kind().into(), message }.serialize(serializer)
+    }
+}
+
+/// Relecture côté client HTTP : le `kind` renvoyé par le serveur est conservé.
+impl<'de> Deserialize<'de> for AppError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ErrorBody { kind, message } = ErrorBody::deserialize(deserializer)?;
+        Ok(match kind.as_str() {
+            "db"         => AppError::Db,
+            "validation" => AppError::Validation(message.into()),
+            "not_found"  => AppError::NotFound(message),
+            "conflict"   => AppError::Conflict(message),
+            "network"    => AppError::Network(
+                message.trim_start_matches("Erreur réseau : ").to_string(),
+            ),
+            "io"         => AppError::Io(message),
+            "migration"  => AppError::Migration(message),
+            "corrupt"    => AppError::Corrupt(message),
+            _            => AppError::Internal(message),
+        })
     }
 }
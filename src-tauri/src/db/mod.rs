@@ -5,10 +5,17 @@
 pub mod error;
 mod models;
 mod repo;
+mod seed;
+mod similarity;
+mod validate;
 
 pub use error::AppError;
 pub use models::{
-    Contribution, ContributionEditInput, ContributionInput, ContributionWithMember,
-    Member, MemberInput, MemberWithTotal, YearSummary,
+    AddressGroup, AppLog, ArchiveImportReport, AuditEntry, Contribution, ContributionEditInput, ContributionInput,
+    ContributionSearchScope, ContributionWithMember, DashboardStats, DbHealth, DeletedMember, ImportMode, ImportReport, IntegrityReport, Member,
+    MemberCounts, MemberFilter, MemberInput, MemberLite, MemberNote, MemberRow, MemberSort, MemberStatement, MemberStatus, MemberType,
+    MemberWithTotal, MemberYearTotal, MemberYearTotals, MonthlyComparison, MonthlyTotal, Page, PendingTask, PhoneFixReport, RenewalDue, SeedReport, Settings, Statistics,
+    UpcomingBirthday, WeekTotal, YearBreakdown, YearComparison, YearClosePreview, YearCloseStatus, YearMemberSnapshot,
+    YearMemberTotal, YearReport, YearSummary,
 };
-pub use repo::Repository;
+pub use repo::{format_ariary, Repository};
@@ -1,6 +1,133 @@
 /// Modèles de données partagés entre le Repository et les commandes Tauri.
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use super::error::AppError;
+
+// ─── Gender / MemberType ──────────────────────────────────────────────────────
+
+/// Genre d'un membre — stocké en TEXT ("M" | "F").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Gender {
+    #[default]
+    M,
+    F,
+}
+
+impl Gender {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gender::M => "M",
+            Gender::F => "F",
+        }
+    }
+}
+
+impl FromStr for Gender {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "M" => Ok(Gender::M),
+            "F" => Ok(Gender::F),
+            _ => Err(AppError::validation("member.gender_invalid", &[&s])),
+        }
+    }
+}
+
+/// Type de membre — stocké en TEXT ("Communiant" | "Cathekomen").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum MemberType {
+    #[default]
+    Communiant,
+    Cathekomen,
+}
+
+impl MemberType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemberType::Communiant => "Communiant",
+            MemberType::Cathekomen => "Cathekomen",
+        }
+    }
+}
+
+impl FromStr for MemberType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Communiant" => Ok(MemberType::Communiant),
+            "Cathekomen" => Ok(MemberType::Cathekomen),
+            _ => Err(AppError::validation("member.type_invalid", &[&s])),
+        }
+    }
+}
+
+/// Conversions String ↔ enum communes aux deux types (serde + affichage).
+macro_rules! impl_text_enum {
+    ($t:ty) => {
+        impl TryFrom<String> for $t {
+            type Error = AppError;
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+
+        impl From<$t> for String {
+            fn from(v: $t) -> String {
+                v.as_str().to_string()
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+/// Statut du membre, indépendant du type — stocké en TEXT
+/// ("Actif" | "Inactif" | "Décédé").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum MemberStatus {
+    #[default]
+    Actif,
+    Inactif,
+    Decede,
+}
+
+impl MemberStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemberStatus::Actif   => "Actif",
+            MemberStatus::Inactif => "Inactif",
+            MemberStatus::Decede  => "Décédé",
+        }
+    }
+}
+
+impl FromStr for MemberStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Actif"   => Ok(MemberStatus::Actif),
+            "Inactif" => Ok(MemberStatus::Inactif),
+            "Décédé"  => Ok(MemberStatus::Decede),
+            _ => Err(AppError::validation("member.status_invalid", &[&s])),
+        }
+    }
+}
+
+impl_text_enum!(Gender);
+impl_text_enum!(MemberType);
+impl_text_enum!(MemberStatus);
 
 // ─── Member ───────────────────────────────────────────────────────────────────
 
@@ -12,9 +139,22 @@ pub struct Member {
     pub address:     Option<String>,
     pub phone:       Option<String>,
     pub job:         Option<String>,
-    pub gender:      String,      // "M" | "F"
-    pub member_type: String,      // "Communiant" | "Cathekomen"
+    pub gender:      Gender,
+    pub member_type: MemberType,
+    /// Nom de famille / prénom(s) pour le tri ; `None` si non déduits de `full_name`
+    #[serde(default)]
+    pub surname:     Option<String>,
+    #[serde(default)]
+    pub given_name:  Option<String>,
+    /// Absent des anciens serveurs : `Actif`
+    #[serde(default)]
+    pub status:      MemberStatus,
+    /// "YYYY-MM-DD" ; `None` si inconnue
+    pub birth_date:  Option<String>,
     pub created_at:  String,
+    /// Dernier transfert de type (UTC) ; `None` si jamais transféré
+    #[serde(default)]
+    pub transferred_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,8 +164,20 @@ pub struct MemberInput {
     pub address:     Option<String>,
     pub phone:       Option<String>,
     pub job:         Option<String>,
-    pub gender:      String,
-    pub member_type: String,
+    pub gender:      Gender,
+    pub member_type: MemberType,
+    /// Parties du nom ; `full_name` vide est composé à partir d'elles, et les
+    /// anciens clients qui n'envoient que `full_name` les laissent déduire
+    #[serde(default)]
+    pub surname:     Option<String>,
+    #[serde(default)]
+    pub given_name:  Option<String>,
+    /// Absent des anciens clients : `Actif`
+    #[serde(default)]
+    pub status:      MemberStatus,
+    /// Absent des anciens clients : `None`
+    #[serde(default)]
+    pub birth_date:  Option<String>,
 }
 
 // ─── MemberWithTotal ──────────────────────────────────────────────────────────
@@ -39,11 +191,218 @@ pub struct MemberWithTotal {
     pub address:             Option<String>,
     pub phone:               Option<String>,
     pub job:                 Option<String>,
-    pub gender:              String,
-    pub member_type:         String,
+    pub gender:              Gender,
+    pub member_type:         MemberType,
+    #[serde(default)]
+    pub surname:             Option<String>,
+    #[serde(default)]
+    pub given_name:          Option<String>,
+    #[serde(default)]
+    pub status:              MemberStatus,
+    pub birth_date:          Option<String>,
     pub created_at:          String,
-    /// Total en Ariary, arrondi à l'entier (ex: "15000")
-    pub total_contributions: String,
+    /// Total exact en Ariary (somme Decimal des montants texte, ex: "15000.5")
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total_contributions: Decimal,
+    /// Total de l'année civile en cours ("0" : rien versé cette année)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub current_year_total:  Decimal,
+}
+
+// ─── MemberYearTotals ─────────────────────────────────────────────────────────
+
+/// Membre et ses totaux pour quelques années (`get_members_with_year_totals`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberYearTotals {
+    pub member:      MemberWithTotal,
+    /// Année → total exact ; une année sans cotisation est absente
+    pub year_totals: BTreeMap<i32, Decimal>,
+}
+
+// ─── AddressGroup ─────────────────────────────────────────────────────────────
+
+/// Membres d'un même quartier, pour préparer les visites à domicile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressGroup {
+    /// Adresse normalisée (minuscules, espaces réduits) ; `None` : sans adresse
+    pub address: Option<String>,
+    pub members: Vec<MemberWithTotal>,
+}
+
+// ─── MemberRow ────────────────────────────────────────────────────────────────
+
+/// Ligne allégée des tableaux de membres : seules les colonnes affichées.
+/// Sur ~1500 membres, `MemberWithTotal` complet alourdit nettement l'IPC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberRow {
+    pub id:                 i64,
+    pub card_number:        String,
+    pub full_name:          String,
+    pub address:            Option<String>,
+    pub phone:              Option<String>,
+    pub job:                Option<String>,
+    pub gender:             Gender,
+    #[serde(default)]
+    pub status:             MemberStatus,
+    /// Reprise par le formulaire de modification
+    pub birth_date:         Option<String>,
+    /// Total exact de l'année civile en cours ("0" : rien versé)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub current_year_total: Decimal,
+}
+
+/// Colonne de tri de `get_member_rows`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSortCol {
+    CardNumber,
+    #[default]
+    FullName,
+    Address,
+    Phone,
+    Job,
+    Gender,
+    CurrentYearTotal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MemberSort {
+    pub col:  MemberSortCol,
+    #[serde(default)]
+    pub desc: bool,
+}
+
+/// Filtres de `get_member_rows` ; tous facultatifs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemberFilter {
+    /// Recherche dans carte, nom, adresse, téléphone et travail (insensible à la casse ASCII)
+    #[serde(default)]
+    pub search:      String,
+    pub gender:      Option<Gender>,
+    /// `None` : tous les statuts
+    #[serde(default)]
+    pub status:      Option<MemberStatus>,
+    /// Seulement les membres sans cotisation dans l'année en cours
+    #[serde(default)]
+    pub unpaid_only: bool,
+}
+
+// ─── UpcomingBirthday ────────────────────────────────────────────────────────
+
+/// Anniversaire à venir (carte de l'accueil), trié par `days_until`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingBirthday {
+    pub member_id:   i64,
+    pub full_name:   String,
+    pub member_type: MemberType,
+    pub birth_date:  String,
+    /// Date de l'anniversaire ("YYYY-MM-DD") ; le 29 février tombe le 28 les années non bissextiles
+    pub date:        String,
+    /// 0 = aujourd'hui
+    pub days_until:  i64,
+    /// Âge atteint ce jour-là
+    pub age:         i32,
+}
+
+// ─── RenewalDue ───────────────────────────────────────────────────────────────
+
+/// Carte à renouveler (anniversaire de l'inscription), triée par `days_until`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenewalDue {
+    pub member_id:     i64,
+    pub full_name:     String,
+    pub card_number:   String,
+    pub member_type:   MemberType,
+    /// Date d'inscription ("YYYY-MM-DD", tirée de `created_at`)
+    pub registered_on: String,
+    /// Échéance ("YYYY-MM-DD") ; inscrit un 29 février : le 28 les années non bissextiles
+    pub date:          String,
+    /// 0 = aujourd'hui
+    pub days_until:    i64,
+    /// Années d'inscription atteintes ce jour-là
+    pub years:         i32,
+}
+
+// ─── MemberLite ───────────────────────────────────────────────────────────────
+
+/// Membre réduit à l'essentiel pour un sélecteur (`search_members_lite`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberLite {
+    pub id:          i64,
+    pub full_name:   String,
+    pub card_number: String,
+}
+
+// ─── MemberCounts ─────────────────────────────────────────────────────────────
+
+/// Nombre de membres par type (badges de la barre de navigation).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberCounts {
+    pub communiants: i64,
+    pub cathekomens: i64,
+}
+
+// ─── DeletedMember ────────────────────────────────────────────────────────────
+
+/// Membre mis à la corbeille (`delete_member`), restaurable ou purgeable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedMember {
+    pub id:                 i64,
+    pub card_number:        String,
+    pub full_name:          String,
+    pub member_type:        MemberType,
+    /// "YYYY-MM-DDTHH:MM:SS" (UTC)
+    pub deleted_at:         String,
+    /// Cotisations conservées, effacées par une purge
+    pub contribution_count: i64,
+}
+
+// ─── MemberNote ───────────────────────────────────────────────────────────────
+
+/// Note libre sur un membre ("Nifindra tany Antananarivo 2024"…), jamais modifiée.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberNote {
+    pub id:         i64,
+    pub member_id:  i64,
+    /// "YYYY-MM-DDTHH:MM:SS" (UTC)
+    pub created_at: String,
+    pub text:       String,
+}
+
+// ─── ImportReport ─────────────────────────────────────────────────────────────
+
+/// Ligne du CSV non importée, avec son numéro de ligne dans le fichier (1 = en-tête).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRowError {
+    pub line:    usize,
+    pub message: String,
+}
+
+/// Bilan d'un import CSV de membres.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub inserted: usize,
+    /// Lignes non importées (doublons et lignes invalides), détaillées dans `errors`
+    pub skipped:  usize,
+    pub errors:   Vec<ImportRowError>,
+}
+
+// ─── PhoneFixReport ───────────────────────────────────────────────────────────
+
+/// Téléphone enregistré qui ne ressemble à aucun numéro malgache, laissé tel quel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvalidPhone {
+    pub member_id:   i64,
+    pub card_number: String,
+    pub phone:       String,
+}
+
+/// Bilan de `normalize_all_phones`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhoneFixReport {
+    /// Numéros réécrits au format "+261 3X XX XXX XX"
+    pub updated: usize,
+    pub invalid: Vec<InvalidPhone>,
 }
 
 // ─── Contribution ─────────────────────────────────────────────────────────────
@@ -58,19 +417,46 @@ pub struct Contribution {
     #[serde(with = "rust_decimal::serde::str")]
     pub amount:        Decimal,
     pub recorded_year: i32,
+    /// Numéro de reçu "2025-00123", attribué à la création et jamais modifié
+    pub receipt_number: String,
+    /// Opérateur qui a saisi la cotisation ; `None` pour les lignes antérieures
+    #[serde(default)]
+    pub recorded_by:   Option<String>,
 }
 
 /// `amount` reçu sous forme de chaîne depuis le frontend ("15000.50").
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContributionInput {
-    pub member_id:    i64,
-    pub payment_date: String,
-    pub period:       String,
-    pub amount:       String,
+    pub member_id:      i64,
+    pub payment_date:   String,
+    /// "2025" ou "2025-T1".."2025-T4"
+    pub period:         String,
+    pub amount:         String,
+    /// Autorise une période d'une autre année que `payment_date` (paiement en retard / d'avance).
+    #[serde(default)]
+    pub allow_mismatch: bool,
+    /// Opérateur de saisie (nom libre ou choisi parmi `Settings::operators`)
+    #[serde(default)]
+    pub recorded_by:    Option<String>,
 }
 
 // ─── ContributionWithMember ───────────────────────────────────────────────────
 
+/// Champs sur lesquels porte la recherche des Archives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContributionSearchScope {
+    /// Tous les champs ci-dessous
+    #[default]
+    All,
+    /// Nom du membre ou numéro de reçu
+    Member,
+    /// Période ("2024-T1") ou date de paiement ("2024-03")
+    Period,
+    /// Partie entière du montant, par préfixe (`validate::amount_search_digits`)
+    Amount,
+}
+
 /// Cotisation avec le nom complet du membre (JOIN SQL).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContributionWithMember {
@@ -82,27 +468,521 @@ pub struct ContributionWithMember {
     #[serde(with = "rust_decimal::serde::str")]
     pub amount:        Decimal,
     pub recorded_year: i32,
+    pub receipt_number: String,
+    #[serde(default)]
+    pub recorded_by:   Option<String>,
     /// Résumé des modifications : "5 000 Ar → 6 000 Ar · 2024 → 2025"
     pub audit_summary: Option<String>,
 }
 
+// ─── Page ─────────────────────────────────────────────────────────────────────
+
+/// Page de résultats : `items` de la page demandée + nombre total de lignes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items:       Vec<T>,
+    pub total_count: i64,
+}
+
 /// Données pour modifier une cotisation existante (avec PIN et motif).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ContributionEditInput {
-    pub payment_date: String,
-    pub period:       String,
-    pub amount:       String,
-    pub pin:          String,
-    pub reason:       String,
+    pub payment_date:   String,
+    pub period:         String,
+    pub amount:         String,
+    pub pin:            String,
+    pub reason:         String,
+    #[serde(default)]
+    pub allow_mismatch: bool,
 }
 
 // ─── YearSummary ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YearSummary {
+    pub year:               i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:              Decimal,
+    /// Sous-totaux selon le type actuel du membre (figés à la clôture).
+    /// Les contributions orphelines n'y figurent pas : leur somme peut être < `total`.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total_communiant:   Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total_cathekomen:   Decimal,
+    /// Membres distincts ayant cotisé dans l'année.
+    pub contributor_count:  i64,
+    /// Cotisations enregistrées dans l'année (orphelines comprises).
+    pub contribution_count: i64,
+    pub closed_at:          Option<String>,
+    pub note:               Option<String>,
+}
+
+// ─── MonthlyTotal ─────────────────────────────────────────────────────────────
+
+/// Total des cotisations payées un mois donné (`get_monthly_totals`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyTotal {
+    /// 1 = janvier … 12 = décembre
+    pub month: u32,
+    /// Nombre de cotisations du mois
+    pub count: i64,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total: Decimal,
+}
+
+// ─── WeekTotal ────────────────────────────────────────────────────────────────
+
+/// Total des cotisations payées une semaine ISO donnée (`get_weekly_totals`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeekTotal {
+    /// Semaine ISO 8601 : 1 … 52 (ou 53)
+    pub week:  u32,
+    /// Nombre de cotisations de la semaine
+    pub count: i64,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total: Decimal,
+}
+
+// ─── YearReport ───────────────────────────────────────────────────────────────
+
+/// Total d'un membre sur une année (rapport d'archive).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearMemberTotal {
+    pub member_id:   i64,
+    pub card_number: String,
+    pub full_name:   String,
+    pub member_type: MemberType,
+    /// Nombre de cotisations de l'année
+    pub count:       i64,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:       Decimal,
+}
+
+/// Total d'un membre figé à la clôture de l'année (`get_year_member_totals`).
+/// Nom et carte sont ceux du jour de la clôture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearMemberSnapshot {
+    pub year:        i32,
+    pub member_id:   i64,
+    pub member_name: String,
+    pub card_number: String,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:       Decimal,
+}
+
+/// Contenu du rapport annuel imprimable (`export_year_report`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearReport {
+    pub summary:       YearSummary,
+    /// Triés par type puis par nom
+    pub members:       Vec<YearMemberTotal>,
+    /// Triées par date de paiement
+    pub contributions: Vec<ContributionWithMember>,
+}
+
+/// Relevé annuel d'un membre (`export_member_statement_csv`) : ses cotisations
+/// enregistrées sur l'année, éventuellement aucune (total à 0).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberStatement {
+    pub member:        Member,
+    pub year:          i32,
+    /// Triées par date de paiement
+    pub contributions: Vec<Contribution>,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:         Decimal,
+}
+
+/// Sous-total des cotisations d'un membre pour une année (fiche membre).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberYearTotal {
+    pub year:               i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:              Decimal,
+    pub contribution_count: i64,
+}
+
+// ─── YearCloseStatus ──────────────────────────────────────────────────────────
+
+/// Résultat de `check_and_close_previous_year`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum YearCloseStatus {
+    /// Année précédente déjà clôturée : rien à faire.
+    AlreadyClosed,
+    /// Clôture effectuée (délai de grâce écoulé ou clôture demandée).
+    Closed { summary: YearSummary },
+    /// Délai de grâce en cours : l'utilisateur choisit de clôturer maintenant ou plus tard.
+    PendingUserAction {
+        year:          i32,
+        #[serde(with = "rust_decimal::serde::str")]
+        total:         Decimal,
+        /// Date de clôture automatique "YYYY-MM-DD"
+        auto_close_on: String,
+    },
+    /// Clôture automatique désactivée (`settings.auto_close_enabled`) : année encore ouverte.
+    Disabled {
+        year:  i32,
+        #[serde(with = "rust_decimal::serde::str")]
+        total: Decimal,
+    },
+}
+
+// ─── YearClosePreview ─────────────────────────────────────────────────────────
+
+/// Aperçu non destructif de la clôture d'une année (`preview_year_close`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearClosePreview {
+    pub year:           i32,
+    /// Total qui serait figé à la clôture.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:          Decimal,
+    /// Note générée, modifiable avant `close_year` ; note enregistrée si déjà clôturée.
+    pub default_note:   String,
+    pub already_closed: bool,
+}
+
+// ─── YearComparison ───────────────────────────────────────────────────────────
+
+/// Ligne du tableau comparatif des années (Archives → Comparaison).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearComparison {
+    pub year:          i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total:         Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub communiants:   Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub cathekomens:   Decimal,
+    /// Variation du total vs l'année précédente, en % (None si l'année précédente est à 0)
+    pub variation_pct: Option<f64>,
+}
+
+/// Un mois du comparatif mensuel entre deux années (`compare_years`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthComparison {
+    /// 1 = janvier … 12 = décembre
+    pub month:           u32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total_a:         Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total_b:         Decimal,
+    /// Cumul depuis janvier de l'année A moins celui de l'année B, à la fin du mois
+    #[serde(with = "rust_decimal::serde::str")]
+    pub cumulative_diff: Decimal,
+}
+
+/// Comparatif mois par mois de deux années (date de paiement), 12 mois.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyComparison {
+    pub year_a: i32,
+    pub year_b: i32,
+    pub months: Vec<MonthComparison>,
+}
+
+/// Cotisations d'un groupe de membres sur une année (genre ou type de membre).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakdownEntry {
+    /// "M" / "F", "Communiant" / "Cathekomen", ou "Inconnu" si le membre a disparu
+    pub key:   String,
+    /// Nombre de cotisations
+    pub count: i64,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total: Decimal,
+}
+
+/// Répartition des cotisations d'une année (année d'enregistrement) par genre et par
+/// type de membre. Les groupes M / F et Communiant / Cathekomen sont toujours présents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearBreakdown {
     pub year:      i32,
+    pub by_gender: Vec<BreakdownEntry>,
+    pub by_type:   Vec<BreakdownEntry>,
+}
+
+// ─── Statistics ───────────────────────────────────────────────────────────────
+
+/// Nombre de membres pour un couple (type, genre).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenderCount {
+    pub member_type: String,
+    pub gender:      String,
+    pub count:       i64,
+}
+
+/// Nombre de nouveaux membres inscrits une année donnée (d'après `created_at`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearCount {
+    pub year:  i32,
+    pub count: i64,
+}
+
+/// Total des contributions d'une année.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearTotal {
+    pub year:  i32,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub total: Decimal,
+}
+
+/// Séries pré-agrégées pour la page Statistiques (aucune ligne brute envoyée au WASM).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Statistics {
+    pub gender_by_type:      Vec<GenderCount>,
+    pub new_members_by_year: Vec<YearCount>,
+    pub totals_by_year:      Vec<YearTotal>,
+}
+
+// ─── DashboardStats ───────────────────────────────────────────────────────────
+
+/// Chiffres de la page d'accueil, calculés en une requête (membres actifs seulement).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardStats {
+    /// Année courante, à laquelle se rapportent les champs "current"
+    pub year:                    i32,
+    pub communiants:             i64,
+    pub cathekomens:             i64,
+    pub men:                     i64,
+    pub women:                   i64,
+    /// Total de `year_summaries` (figé si l'année est clôturée)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub current_year_total:      Decimal,
+    #[serde(with = "rust_decimal::serde::str")]
+    pub previous_year_total:     Decimal,
+    pub contributions_this_year: i64,
+    /// Date de paiement ("YYYY-MM-DD") la plus récente ; `None` sans aucune cotisation
+    pub last_contribution_date:  Option<String>,
+}
+
+// ─── AppLog ───────────────────────────────────────────────────────────────────
+
+/// Entrée du journal applicatif (`app_log`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLog {
+    pub id:         i64,
+    /// 'info' | 'error'
+    pub level:      String,
+    pub source:     String,
+    pub message:    String,
+    pub created_at: String,
+}
+
+// ─── IntegrityReport ──────────────────────────────────────────────────────────
+
+/// Anomalie relevée par `run_integrity_check`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    /// Ligne renvoyée par `PRAGMA integrity_check` (fichier endommagé, non réparable ici).
+    Sqlite { message: String },
+    /// Contribution dont le membre n'existe plus (copie de base sans contrôle des FK).
+    OrphanContribution { contribution_id: i64, member_id: i64 },
+    /// `recorded_year` différent de l'exercice de `payment_date` (`payment_year`).
+    RecordedYearMismatch { contribution_id: i64, recorded_year: i32, payment_year: i32 },
+    /// `year_summaries.total` différent de la somme des contributions (0 si la ligne manque).
+    YearTotalMismatch {
+        year:     i32,
+        #[serde(with = "rust_decimal::serde::str")]
+        stored:   Decimal,
+        #[serde(with = "rust_decimal::serde::str")]
+        computed: Decimal,
+    },
+}
+
+impl IntegrityIssue {
+    /// Corrigé par `repair_integrity` (totaux recalculés, `recorded_year` réaligné).
+    pub fn is_repairable(&self) -> bool {
+        matches!(self, Self::RecordedYearMismatch { .. } | Self::YearTotalMismatch { .. })
+    }
+}
+
+/// Résultat de `normalize_legacy_periods`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeriodNormalization {
+    /// Valeurs réécrites : (saisie d'origine, forme canonique, contributions concernées)
+    pub rewritten:   Vec<(String, String, u64)>,
+    /// Valeurs illisibles, laissées telles quelles
+    pub unparseable: Vec<String>,
+}
+
+/// Total annuel réécrit par `verify_and_repair_summaries`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepairedYear {
+    pub year:     i32,
+    /// Total qui était stocké dans `year_summaries` (0 si la ligne manquait)
+    #[serde(with = "rust_decimal::serde::str")]
+    pub stored:   Decimal,
+    /// Somme des contributions, désormais enregistrée
+    #[serde(with = "rust_decimal::serde::str")]
+    pub computed: Decimal,
+}
+
+/// Résultat de `run_integrity_check` ; `issues` vide = base saine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub checked_at: String,
+    pub issues:     Vec<IntegrityIssue>,
+}
+
+// ─── DbHealth ─────────────────────────────────────────────────────────────────
+
+/// Nombre de lignes d'une table (`DbHealth`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows:  i64,
+}
+
+/// Résultat de `health` : panneau « À propos de la base » des Paramètres.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbHealth {
+    /// Dernière migration appliquée (`_sqlx_migrations`)
+    pub schema_version: i64,
+    /// Tables de données, par nom
+    pub tables:         Vec<TableRowCount>,
+    /// Pages SQLite × taille de page : fichier principal, hors journal WAL
+    pub size_bytes:     i64,
+}
+
+// ─── PendingTask ──────────────────────────────────────────────────────────────
+
+/// Tâche à faire signalée dans la barre de navigation (`get_pending_tasks`).
+/// Un simple rappel : rien n'est corrigé automatiquement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingTask {
+    /// Année précédente non clôturée alors qu'elle a des contributions.
+    UnclosedYear { year: i32, contributions: i64 },
+    /// Membres actifs sans téléphone ni adresse.
+    IncompleteMembers { count: i64 },
+    /// Contributions dont la date de paiement est postérieure à aujourd'hui.
+    FutureContributions { count: i64 },
+}
+
+// ─── AuditEntry ───────────────────────────────────────────────────────────────
+
+/// Ligne du journal d'audit (création, modification, suppression, transfert,
+/// clôture…), écrite dans la transaction de l'opération.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id:        i64,
+    /// "YYYY-MM-DDTHH:MM:SS", UTC
+    pub timestamp: String,
+    /// 'create' | 'update' | 'delete' | 'transfer' | 'merge' | 'close' | 'reopen'
+    pub action:    String,
+    /// 'member' | 'contribution' | 'year'
+    pub entity:    String,
+    /// Id du membre / de la cotisation, ou l'année
+    pub entity_id: i64,
+    /// Objet JSON ; `None` si la sérialisation a échoué
+    pub details:   Option<String>,
+}
+
+// ─── Settings ─────────────────────────────────────────────────────────────────
+
+/// Paramètres de l'église (table clé/valeur `settings`), vue typée.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub church_name:      String,
+    pub church_address:   String,
+    /// Trésorier, imprimé sous la signature des reçus ; vide = aucun nom
+    #[serde(default)]
+    pub treasurer_name:   String,
+    /// Libellé de la monnaie sur les reçus, ex. "Ariary"
+    #[serde(default)]
+    pub currency_label:   String,
+    /// Cotisation annuelle suggérée, en Ariary
+    #[serde(with = "rust_decimal::serde::str")]
+    pub default_pledge:   Decimal,
+    /// Nombre de sauvegardes automatiques conservées
+    pub backup_retention: usize,
+    /// Accepte les périodes trimestrielles "YYYY-T1".."YYYY-T4" en plus de "YYYY"
+    pub quarter_periods:  bool,
+    /// Lance `run_integrity_check` au démarrage de l'application
+    pub startup_integrity_check: bool,
+    /// Clôture l'année précédente au démarrage, une fois le délai de grâce écoulé
+    pub auto_close_enabled: bool,
+    /// Jours après le début de l'exercice avant la clôture automatique (0 à `MAX_GRACE_DAYS`)
+    pub auto_close_grace_days: u32,
+    /// Jours d'avance admis sur une date de paiement (0 à `MAX_FUTURE_DAYS`)
+    pub max_future_days:  u32,
+    /// Fenêtre des renouvellements de carte affichés à l'accueil (0 à `MAX_RENEWAL_WINDOW_DAYS`)
+    pub renewal_window_days: u32,
+    /// Premier mois de l'exercice : 1 = année civile, 7 = juillet → juin
+    pub fiscal_year_start_month: u32,
+    /// Période préremplie à la saisie d'une cotisation ; vide = année de la date de paiement
+    pub default_period:   String,
+    /// Montants proposés en raccourci à la saisie d'une cotisation
+    pub quick_amounts:    Vec<Decimal>,
+    /// Modèle des numéros de carte générés, ex. "C-%04d"
+    pub card_pattern_communiant: String,
+    pub card_pattern_cathekomen: String,
+    /// Opérateurs proposés à la saisie d'une cotisation ; vide = saisie libre
+    pub operators:        Vec<String>,
+}
+
+// ─── DatabaseArchive ──────────────────────────────────────────────────────────
+
+/// Membre d'une archive : la fiche et sa mise à la corbeille éventuelle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMember {
+    #[serde(flatten)]
+    pub member:     Member,
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+}
+
+/// Base complète au format JSON portable (`export_archive` / `import_archive`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseArchive {
+    /// Version du format, vérifiée avant toute écriture
+    pub schema_version:     u32,
+    /// Horodatage UTC de l'export
+    pub exported_at:        String,
+    pub members:            Vec<ArchivedMember>,
+    pub contributions:      Vec<Contribution>,
+    pub year_summaries:     Vec<YearSummary>,
+    /// Totaux par membre figés à la clôture des années
+    #[serde(default)]
+    pub year_member_totals: Vec<YearMemberSnapshot>,
+    /// Paramètres, sans l'état du PIN
+    pub settings:           BTreeMap<String, String>,
+    /// Notes libres des membres (absentes avant la version 2)
+    #[serde(default)]
+    pub member_notes:       Vec<MemberNote>,
+}
+
+/// Mode d'import d'une archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Vide la base puis insère l'archive telle quelle (identifiants conservés).
+    Replace,
+    /// Complète la base : un membre dont la carte existe déjà est ignoré et ses
+    /// cotisations rattachées à la fiche locale.
+    Merge,
+}
+
+/// Bilan de `import_archive`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveImportReport {
+    pub members_inserted:       usize,
+    /// Cartes déjà présentes (fusion)
+    pub members_skipped:        usize,
+    pub contributions_inserted: usize,
+    /// Déjà présentes ou dans une année clôturée localement (fusion)
+    pub contributions_skipped:  usize,
+}
+
+// ─── SeedReport ───────────────────────────────────────────────────────────────
+
+/// Bilan de `seed_demo_data`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedReport {
+    pub members_inserted:       usize,
+    pub contributions_inserted: usize,
+    /// Somme des cotisations générées
     #[serde(with = "rust_decimal::serde::str")]
-    pub total:     Decimal,
-    pub closed_at: Option<String>,
-    pub note:      Option<String>,
+    pub total:                  Decimal,
+    /// Années couvertes, triées
+    pub years:                  Vec<i32>,
 }
@@ -4,30 +4,339 @@
 ///   - members        : membres de l'église (card_number unique)
 ///   - contributions  : cotisations (recorded_year extrait automatiquement de payment_date)
 ///   - year_summaries : totaux annuels (recalculés à chaque insert/delete de contribution)
-use chrono::{Datelike, NaiveDate};
-use rust_decimal::Decimal;
+use chrono::{Datelike, NaiveDate, Weekday};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use sqlx::{
-    sqlite::{SqliteConnectOptions, SqlitePool},
+    sqlite::{
+        SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+        SqliteSynchronous,
+    },
     QueryBuilder, Row,
 };
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    future::Future,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use super::{
     error::AppError,
     models::{
-        Contribution, ContributionInput, ContributionWithMember,
-        Member, MemberInput, MemberWithTotal, YearSummary,
+        AddressGroup, AppLog, ArchiveImportReport, ArchivedMember, AuditEntry, BreakdownEntry, Contribution,
+        ContributionInput, DatabaseArchive, ImportMode,
+        ContributionSearchScope, ContributionWithMember,
+        DashboardStats, DbHealth, DeletedMember, Gender, GenderCount, ImportReport, ImportRowError,
+        IntegrityIssue, IntegrityReport, InvalidPhone, Member, MemberCounts, MemberFilter,
+        MemberInput, MemberLite, MemberNote, MemberRow, MemberSort, MemberSortCol, MemberStatement, MemberStatus, MemberType,
+        MemberWithTotal, MemberYearTotal, MemberYearTotals, MonthComparison, MonthlyComparison, MonthlyTotal, Page, PendingTask, PeriodNormalization,
+        PhoneFixReport, RenewalDue, RepairedYear, SeedReport, Settings, Statistics, UpcomingBirthday, WeekTotal, YearBreakdown, YearClosePreview, YearCloseStatus, YearComparison,
+        TableRowCount, YearCount, YearMemberSnapshot, YearMemberTotal, YearReport, YearSummary, YearTotal,
+    },
+    seed::{self, SeedRng},
+    similarity::{name_similarity, SIMILAR_NAME_THRESHOLD},
+    validate::{
+        address_key, amount_search_digits, compose_full_name, normalize_optional_phone, parse_legacy_period,
+        parse_period, split_full_name, Period, PeriodPart,
     },
 };
+use crate::export::parse_member_csv;
+use crate::i18n::{self, Lang};
+
+/// Taille de page par défaut pour les listes paginées.
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+/// Taille de page maximale acceptée (évite les payloads IPC géants).
+pub const MAX_PAGE_SIZE: i64 = 500;
+/// Nombre de résultats de `search_members` si aucune limite n'est donnée.
+pub const DEFAULT_SEARCH_LIMIT: i64 = 50;
+/// Résultats au plus de `search_members_lite` (sélecteur de membre).
+pub const MEMBER_PICKER_LIMIT: i64 = 20;
+/// Membres au plus retournés par `find_similar_members`.
+pub const MAX_SIMILAR_MEMBERS: usize = 5;
+/// Jours de janvier pendant lesquels l'année précédente reste ouverte (saisies tardives),
+/// si `settings.auto_close_grace_days` est absent.
+pub const DEFAULT_GRACE_DAYS: u32 = 15;
+/// Délai de grâce maximal avant clôture automatique (jours après le début de l'exercice).
+pub const MAX_GRACE_DAYS: u32 = 90;
+/// Jours d'avance admis sur une date de paiement si `settings.max_future_days` est absent.
+pub const DEFAULT_MAX_FUTURE_DAYS: u32 = 7;
+/// Avance maximale réglable pour une date de paiement (jours).
+pub const MAX_FUTURE_DAYS: u32 = 366;
+/// Première année de paiement acceptée (date antérieure : faute de frappe probable).
+pub const MIN_PAYMENT_YEAR: i32 = 1990;
+/// Nombre de sauvegardes automatiques conservées si aucun réglage n'est enregistré.
+pub const DEFAULT_BACKUP_RETENTION: usize = 14;
+/// Âge à partir duquel une date de naissance est refusée (faute de frappe probable).
+pub const MAX_AGE_YEARS: i32 = 120;
+/// Fenêtre maximale de `get_upcoming_birthdays` (jours).
+pub const MAX_BIRTHDAY_WINDOW_DAYS: u32 = 366;
+/// Fenêtre des renouvellements de carte si `settings.renewal_window_days` est absent.
+pub const DEFAULT_RENEWAL_WINDOW_DAYS: u32 = 30;
+/// Fenêtre maximale de `get_upcoming_renewals` (jours).
+pub const MAX_RENEWAL_WINDOW_DAYS: u32 = 366;
+/// Longueur maximale d'une note d'année (caractères).
+pub const MAX_YEAR_NOTE_LEN: usize = 1000;
+/// Longueur maximale d'une note de membre (caractères).
+pub const MAX_MEMBER_NOTE_LEN: usize = 1000;
+/// Montants rapides proposés si `settings.quick_amounts` est absent (Ariary).
+pub const DEFAULT_QUICK_AMOUNTS: [i64; 3] = [5_000, 10_000, 20_000];
+/// Nombre maximal de montants rapides.
+pub const MAX_QUICK_AMOUNTS: usize = 6;
+/// Modèles de numéro de carte si `settings.card_pattern_*` est absent.
+pub const DEFAULT_CARD_PATTERN_COMMUNIANT: &str = "C-%04d";
+pub const DEFAULT_CARD_PATTERN_CATHEKOMEN: &str = "K-%04d";
+/// Nom affiché tant que `settings.church_name` n'est pas renseigné.
+pub const DEFAULT_CHURCH_NAME: &str = "FJKM Ambalavao Isotry";
+/// Libellé de monnaie si `settings.currency_label` est vide.
+pub const DEFAULT_CURRENCY_LABEL: &str = "Ariary";
+/// Longueur autorisée du code PIN (chiffres uniquement).
+pub const PIN_MIN_LEN: usize = 4;
+pub const PIN_MAX_LEN: usize = 6;
+/// Échecs de PIN consécutifs avant verrouillage.
+pub const PIN_MAX_ATTEMPTS: i64 = 5;
+/// Durée du verrouillage après trop d'échecs (secondes).
+pub const PIN_LOCKOUT_SECS: i64 = 300;
+/// Durée de validité d'un jeton délivré par `verify_pin` (secondes).
+pub const PIN_TOKEN_TTL_SECS: i64 = 120;
+/// Version du format produit par `export_archive` ; une version plus récente est refusée à
+/// l'import. 2 : notes des membres.
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 2;
+/// Lignes au plus par appel de `create_contributions_batch`.
+pub const MAX_CONTRIBUTION_BATCH: usize = 200;
+/// Membres fictifs au plus par appel de `seed_demo_data`.
+pub const MAX_SEED_MEMBERS: usize = 2000;
+/// Première année acceptée par `seed_demo_data`.
+const MIN_SEED_YEAR: i32 = 2000;
+/// Numéros de carte fictifs tirés dans `1..=SEED_CARD_RANGE` (au-delà de `MAX_SEED_MEMBERS`).
+const SEED_CARD_RANGE: u64 = 9999;
+
+/// Attente de SQLite sur un verrou avant de renvoyer SQLITE_BUSY.
+pub const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Tentatives d'une écriture sur base verrouillée (voir `retry_busy`).
+pub const BUSY_MAX_ATTEMPTS: u32 = 3;
+/// Pause avant la 2ᵉ tentative, doublée ensuite.
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Lignes d'une année pour `refresh_year_total_tx` : montant, membre et type seulement,
+/// lus dans l'index couvrant `idx_contributions_year_member_amount` (migration 0018).
+const YEAR_REFRESH_SQL: &str = "SELECT c.amount, c.member_id, m.member_type
+     FROM contributions c
+     LEFT JOIN members m ON m.id = c.member_id
+     WHERE c.recorded_year = ?";
+
+/// Colonnes lues par `map_year_summary`.
+const YEAR_SUMMARY_COLUMNS: &str =
+    "year, total, total_communiant, total_cathekomen, contributor_count, contribution_count,
+     closed_at, note";
+
+const UPSERT_SETTING_SQL: &str =
+    "INSERT INTO settings (key, value) VALUES (?, ?)
+     ON CONFLICT(key) DO UPDATE SET value = excluded.value";
+
+/// Modèle de numéro de carte "C-%04d" : préfixe, largeur minimale du numéro, suffixe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CardPattern {
+    prefix: String,
+    width:  usize,
+    suffix: String,
+}
+
+impl CardPattern {
+    /// Un seul "%d" ou "%0Nd" (N ≤ 9) ; `None` sinon.
+    fn parse(pattern: &str) -> Option<Self> {
+        let (prefix, rest) = pattern.split_once('%')?;
+        let (spec, suffix) = rest.split_once('d')?;
+        let width = match spec {
+            "" => 0,
+            s if s.len() == 2 && s.starts_with('0') => s[1..].parse().ok().filter(|w| *w > 0)?,
+            _ => return None,
+        };
+        if suffix.contains('%') {
+            return None;
+        }
+        Some(Self { prefix: prefix.to_string(), width, suffix: suffix.to_string() })
+    }
+
+    /// Numéro porté par `card` s'il suit le modèle ("C-0042" → 42, "c42" → None).
+    fn number_of(&self, card: &str) -> Option<u64> {
+        let digits = card.strip_prefix(&self.prefix)?.strip_suffix(&self.suffix)?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    fn format(&self, n: u64) -> String {
+        format!("{}{:0width$}{}", self.prefix, n, self.suffix, width = self.width)
+    }
+}
+
+/// Contribution telle que `delete_contribution` l'inscrit au journal d'audit.
+#[derive(serde::Deserialize)]
+struct DeletedContribution {
+    member_id:      i64,
+    payment_date:   String,
+    period:         String,
+    amount:         String,
+    receipt_number: String,
+    recorded_by:    Option<String>,
+}
+
+/// Contraintes sur `payment_date` et `period` lors d'une saisie de contribution.
+#[derive(Debug, Clone, Copy)]
+struct PeriodRules {
+    /// Trimestres "YYYY-Tn" admis (`settings.quarter_periods`, activé par défaut)
+    quarters:       bool,
+    /// Période d'une autre année que la date de paiement admise
+    allow_mismatch: bool,
+    /// Date de paiement la plus tardive admise (aujourd'hui + `settings.max_future_days`) ;
+    /// `None` : pas de limite (restauration d'une cotisation supprimée)
+    latest_date:    Option<NaiveDate>,
+    /// Premier mois de l'exercice (`settings.fiscal_year_start_month`, 1 = année civile)
+    fiscal_start:   u32,
+}
+
+/// Exercice auquel appartient `date`, désigné par l'année où il commence : avec un
+/// exercice juillet → juin (`start_month` = 7), le 15/03/2025 relève de 2024
+/// (« 2024–2025 »). `start_month` 1 (ou hors de 2..=12) : année civile.
+pub fn fiscal_year_of(date: NaiveDate, start_month: u32) -> i32 {
+    if (2..=12).contains(&start_month) && date.month() < start_month {
+        date.year() - 1
+    } else {
+        date.year()
+    }
+}
+
+/// Premier jour de l'exercice `year`.
+fn fiscal_year_start(year: i32, start_month: u32) -> Option<NaiveDate> {
+    let month = if (2..=12).contains(&start_month) { start_month } else { 1 };
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+/// Formate un montant "1 234 567 Ariary" ("1 234,50 Ariary" avec centimes) —
+/// partagé par la note de clôture et les exports.
+pub fn format_ariary(amount: &Decimal) -> String {
+    let n = amount.abs().normalize().to_string();
+    let (integer_part, decimals) = match n.split_once('.') {
+        Some((i, d)) => (i, Some(d)),
+        None         => (n.as_str(), None),
+    };
+    let len = integer_part.len();
+    let mut result = String::from(if amount.is_sign_negative() && !amount.is_zero() { "-" } else { "" });
+    for (i, c) in integer_part.chars().enumerate() {
+        if i > 0 && (len - i) % 3 == 0 {
+            result.push(' ');
+        }
+        result.push(c);
+    }
+    if let Some(d) = decimals {
+        result.push(',');
+        result.push_str(&format!("{d:0<2}"));
+    }
+    format!("{result} Ariary")
+}
+
+/// Montant maximal d'une cotisation, en Ariary (garde-fou contre une faute de frappe).
+pub const MAX_AMOUNT: i64 = 1_000_000_000;
+
+/// Parse un montant saisi : "15000", "15 000", "15\u{202f}000,50", "15000.50".
+/// Espaces (normales, insécables, fines) ignorés ; "," ou "." comme séparateur
+/// décimal, 2 décimales au plus ; montant positif, plafonné à `MAX_AMOUNT`.
+pub fn parse_amount(raw: &str) -> Result<Decimal, AppError> {
+    let compact: String = raw
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{2009}' | '\u{202f}'))
+        .collect();
+    if compact.is_empty() {
+        return Err(AppError::validation("amount.required", &[]));
+    }
+    if compact.starts_with('-') {
+        return Err(AppError::validation("amount.negative", &[]));
+    }
+    if compact.contains(',') && compact.contains('.') {
+        return Err(AppError::validation("amount.separators", &[&raw.trim()]));
+    }
+    let normalized = compact.replace(',', ".");
+    let (integer, decimals) = match normalized.split_once('.') {
+        Some((i, d)) => (i, Some(d)),
+        None         => (normalized.as_str(), None),
+    };
+    let digits_only = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits_only(integer) || !decimals.is_none_or(digits_only) {
+        return Err(AppError::validation("amount.format", &[&raw.trim()]));
+    }
+    if decimals.is_some_and(|d| d.len() > 2) {
+        return Err(AppError::validation("amount.decimals", &[&raw.trim()]));
+    }
+    let amount = Decimal::from_str(&normalized)
+        .map_err(|_| AppError::validation("amount.invalid", &[&raw.trim()]))?;
+    if amount > Decimal::from(MAX_AMOUNT) {
+        let max = format_ariary(&Decimal::from(MAX_AMOUNT));
+        return Err(AppError::validation("amount.too_large", &[&max]));
+    }
+    Ok(amount)
+}
+
+/// Rejoue `op` quand la base est verrouillée (sauvegarde, antivirus…), au plus
+/// `BUSY_MAX_ATTEMPTS` fois ; les autres erreurs sont renvoyées telles quelles.
+///
+/// Après un `SQLITE_BUSY`, le worker sqlx peut encore exécuter l'instruction en
+/// arrière-plan : une insertion hors transaction aboutirait malgré l'erreur et
+/// l'essai suivant heurterait sa propre ligne. Les insertions rejouées passent donc
+/// par une transaction, annulée quand l'essai échoue.
+async fn retry_busy<T, E, F, Fut>(mut op: F) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Into<AppError>,
+{
+    let mut delay = BUSY_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match op().await.map_err(Into::into) {
+            Err(AppError::Busy) if attempt < BUSY_MAX_ATTEMPTS => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Repository {
-    pool: SqlitePool,
+    pool:       SqlitePool,
+    /// Jetons PIN délivrés → expiration (secondes Unix). En mémoire : perdus au redémarrage.
+    pin_tokens: Arc<Mutex<HashMap<String, i64>>>,
+    /// Écrivain unique du processus (`write`), partagé par les clones (commandes,
+    /// serveur API, tâches de fond).
+    write_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl Repository {
+    /// Transaction d'écriture sérialisée : un seul écrivain à la fois dans le
+    /// processus, puis `retry_busy` contre les verrous tenus hors du processus.
+    /// En WAL, deux transactions qui lisent avant d'écrire ne s'attendent pas :
+    /// la seconde reçoit SQLITE_BUSY aussitôt, sans profiter du `busy_timeout`.
+    async fn write<T, E, F, Fut>(&self, op: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Into<AppError>,
+    {
+        let _writer = self.write_lock.lock().await;
+        retry_busy(op).await
+    }
+
     /// Ouvre (ou crée) la base SQLite, active les FK, puis exécute les migrations.
     pub async fn new(db_path: &str) -> Result<Self, AppError> {
+        Self::with_busy_timeout(db_path, DEFAULT_BUSY_TIMEOUT).await
+    }
+
+    /// `new` avec une attente sur verrou choisie (courte dans les tests de contention).
+    pub async fn with_busy_timeout(db_path: &str, busy_timeout: Duration) -> Result<Self, AppError> {
         // `filename()` prend un chemin OS (backslashes Windows OK, espaces OK).
         // `from_str("sqlite://:memory:")` est conservé pour les tests en mémoire.
         let base = if db_path == ":memory:" {
@@ -35,23 +344,305 @@ impl Repository {
         } else {
             SqliteConnectOptions::new().filename(db_path)
         };
+        // WAL : les lectures ne bloquent plus les écritures ; NORMAL suffit en WAL
+        // (pas de corruption possible, au pire la dernière transaction perdue sur coupure)
         let options = base
             .create_if_missing(true)
-            .foreign_keys(true);
-
-        let pool = SqlitePool::connect_with(options).await?;
+            .foreign_keys(true)
+            .busy_timeout(busy_timeout)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal);
+
+        let pool = SqlitePool::connect_with(options).await.map_err(AppError::from_open)?;
+        let repo = Self::migrated(pool).await?;
+        repo.check_file_integrity().await;
+        Ok(repo)
+    }
 
-        // Migrations embarquées (src-tauri/migrations/)
+    /// Migrations embarquées (src-tauri/migrations/). Les erreurs distinguent un
+    /// fichier endommagé ou inaccessible d'un schéma impossible à mettre à jour.
+    async fn migrated(pool: SqlitePool) -> Result<Self, AppError> {
+        // Version avant mise à jour ; 0 pour une base neuve (table encore absente)
+        let previous: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap_or(0);
         sqlx::migrate!("./migrations")
             .run(&pool)
             .await
-            .map_err(|e| { eprintln!("[Migrate Error] {e}"); AppError::Db })?;
+            .map_err(AppError::from_migrate)?;
+        let repo = Repository { pool, pin_tokens: Arc::default(), write_lock: Arc::default() };
+        repo.backfill_exact_totals(previous).await?;
+        Ok(repo)
+    }
+
+    /// Refait en Decimal les remplissages que les migrations calculent en REAL
+    /// (arrondis, « 5000.0 »), pour celles qui viennent d'être appliquées : les
+    /// données n'ont pas changé depuis, le résultat est celui attendu du SQL.
+    async fn backfill_exact_totals(&self, previous_version: i64) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        // 0005 : sous-totaux par type de toutes les années, clôturées comprises
+        if previous_version < 5 {
+            let years: Vec<i32> = sqlx::query_scalar("SELECT year FROM year_summaries")
+                .fetch_all(&mut *tx)
+                .await?;
+            for year in years {
+                let rows = sqlx::query(YEAR_REFRESH_SQL).bind(year).fetch_all(&mut *tx).await?;
+                let s = Self::sum_year_rows(year, &rows);
+                sqlx::query(
+                    "UPDATE year_summaries SET total_communiant = ?, total_cathekomen = ? WHERE year = ?",
+                )
+                .bind(s.total_communiant.to_string())
+                .bind(s.total_cathekomen.to_string())
+                .bind(year)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        // 0017 : totaux par membre des années déjà clôturées
+        if previous_version < 17 {
+            let closed: Vec<i32> =
+                sqlx::query_scalar("SELECT year FROM year_summaries WHERE closed_at IS NOT NULL")
+                    .fetch_all(&mut *tx)
+                    .await?;
+            for year in closed {
+                Self::snapshot_member_totals_tx(&mut tx, year).await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Base de démonstration en mémoire, en lecture seule, quand le fichier réel ne
+    /// s'ouvre pas : quelques membres et cotisations fictifs pour parcourir l'application.
+    /// Une seule connexion, jamais recyclée : c'est elle qui porte la base, et
+    /// `query_only` s'applique ainsi à toutes les requêtes suivantes.
+    pub async fn demo() -> Result<Self, AppError> {
+        let options = SqliteConnectOptions::from_str("sqlite://:memory:")?.foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await
+            .map_err(AppError::from_open)?;
+        let repo = Self::migrated(pool).await?;
+        repo.seed_demo(chrono::Local::now().date_naive()).await?;
+        sqlx::query("PRAGMA query_only = ON").execute(&repo.pool).await?;
+        Ok(repo)
+    }
+
+    async fn seed_demo(&self, today: NaiveDate) -> Result<(), AppError> {
+        const MEMBERS: &[(&str, &str, Gender, MemberType, &[&str])] = &[
+            ("C-0001", "RAKOTO Jean",   Gender::M, MemberType::Communiant, &["T1", "T2"]),
+            ("C-0002", "RASOA Marie",   Gender::F, MemberType::Communiant, &["T1"]),
+            ("C-0003", "RANDRIA Paul",  Gender::M, MemberType::Communiant, &[]),
+            ("K-0001", "RAHARY Nirina", Gender::F, MemberType::Cathekomen, &["T1"]),
+        ];
+        let year = today.year();
+        for &(card, name, gender, member_type, quarters) in MEMBERS {
+            let member = self
+                .create_member(MemberInput {
+                    card_number: card.into(),
+                    full_name:   name.into(),
+                    address:     Some("Ambalavao Isotry".into()),
+                    phone:       None,
+                    job:         None,
+                    gender,
+                    member_type,
+                    surname:     None,
+                    given_name:  None,
+                    status:      MemberStatus::Actif,
+                    birth_date:  None,
+                })
+                .await?;
+            for quarter in quarters {
+                self.create_contribution(ContributionInput {
+                    member_id:      member.id,
+                    payment_date:   today.to_string(),
+                    period:         format!("{year}-{quarter}"),
+                    amount:         "5000".into(),
+                    allow_mismatch: false,
+                    recorded_by:    None,
+                })
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remplit la base de `member_count` membres fictifs et de cotisations réparties
+    /// sur `years`, en une transaction. Même `seed` = mêmes données. Refusé si la
+    /// base contient déjà des membres ou des cotisations : jamais de fiches fictives
+    /// mêlées aux vraies.
+    pub async fn seed_demo_data(
+        &self,
+        member_count: usize,
+        years: Vec<i32>,
+        seed: u64,
+    ) -> Result<SeedReport, AppError> {
+        let today = chrono::Local::now().date_naive();
+        if !(1..=MAX_SEED_MEMBERS).contains(&member_count) {
+            return Err(AppError::validation("seed.member_count", &[&MAX_SEED_MEMBERS]));
+        }
+        let years: Vec<i32> = years.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+        if years.is_empty() || years.iter().any(|y| !(MIN_SEED_YEAR..=today.year()).contains(y)) {
+            return Err(AppError::validation("seed.years", &[&MIN_SEED_YEAR, &today.year()]));
+        }
+        let settings = self.get_settings().await?;
+        let pattern = |raw: &str| {
+            CardPattern::parse(raw)
+                .ok_or_else(|| AppError::validation("settings.card_pattern_invalid", &[&raw]))
+        };
+        let patterns = [
+            (MemberType::Communiant, pattern(&settings.card_pattern_communiant)?),
+            (MemberType::Cathekomen, pattern(&settings.card_pattern_cathekomen)?),
+        ];
+
+        let fiscal_start = settings.fiscal_year_start_month;
+        let (years_ref, patterns_ref) = (&years, &patterns);
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let existing: i64 = sqlx::query_scalar(
+                "SELECT (SELECT COUNT(*) FROM members) + (SELECT COUNT(*) FROM contributions)",
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if existing > 0 {
+                return Err(AppError::validation("seed.not_empty", &[]));
+            }
+            for &year in years_ref {
+                Self::ensure_year_open_tx(&mut tx, year).await?;
+            }
+            let mut taken: HashSet<String> = sqlx::query_scalar("SELECT card_number FROM members")
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .collect();
+
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+            let mut rng = SeedRng::new(seed);
+            let mut report = SeedReport {
+                members_inserted:       0,
+                contributions_inserted: 0,
+                total:                  Decimal::ZERO,
+                years:                  years_ref.clone(),
+            };
+            // Exercices touchés : les années civiles de `years` si l'exercice suit le calendrier
+            let mut touched = BTreeSet::new();
+            for _ in 0..member_count {
+                // Trois communiants pour un catéchumène environ
+                let (member_type, pattern) = &patterns_ref[usize::from(rng.chance(25))];
+                let card = loop {
+                    let card = pattern.format(rng.below(SEED_CARD_RANGE) + 1);
+                    if taken.insert(card.clone()) {
+                        break card;
+                    }
+                };
+                let gender = seed::gender(&mut rng);
+                let full_name = seed::full_name(&mut rng, gender);
+                let (surname, given_name) = split_full_name(&full_name);
+                let address = rng.chance(85).then(|| seed::address(&mut rng));
+                let phone = rng.chance(60).then(|| seed::phone(&mut rng));
+                let job = rng.chance(50).then(|| seed::job(&mut rng));
+                let member_id: i64 = sqlx::query_scalar(
+                    "INSERT INTO members
+                         (card_number, full_name, address, phone, job, gender, member_type,
+                          surname, given_name, created_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                     RETURNING id",
+                )
+                .bind(&card)
+                .bind(&full_name)
+                .bind(&address)
+                .bind(&phone)
+                .bind(&job)
+                .bind(gender.as_str())
+                .bind(member_type.as_str())
+                .bind(&surname)
+                .bind(&given_name)
+                .bind(&now)
+                .fetch_one(&mut *tx)
+                .await?;
+                report.members_inserted += 1;
+
+                for &year in years_ref {
+                    // Sept membres sur dix paient une année donnée, une à quatre fois
+                    if !rng.chance(70) {
+                        continue;
+                    }
+                    for _ in 0..=rng.below(4) {
+                        let Some(date) = seed::payment_date(&mut rng, year, today) else { break };
+                        let amount = Decimal::from(seed::amount(&mut rng));
+                        let recorded_year = fiscal_year_of(date, fiscal_start);
+                        let receipt = Self::next_receipt_number_tx(&mut tx, recorded_year).await?;
+                        sqlx::query(
+                            "INSERT INTO contributions
+                                 (member_id, payment_date, period, amount, recorded_year, receipt_number)
+                             VALUES (?, ?, ?, ?, ?, ?)",
+                        )
+                        .bind(member_id)
+                        .bind(date.format("%Y-%m-%d").to_string())
+                        .bind(seed::month_period(date))
+                        .bind(amount.to_string())
+                        .bind(recorded_year)
+                        .bind(&receipt)
+                        .execute(&mut *tx)
+                        .await?;
+                        report.contributions_inserted += 1;
+                        report.total += amount;
+                        touched.insert(recorded_year);
+                    }
+                }
+            }
+            for &year in &touched {
+                Self::refresh_year_total_tx(&mut tx, year).await?;
+            }
+            tx.commit().await?;
+            Ok(report)
+        })
+        .await
+    }
 
-        Ok(Repository { pool })
+    /// `PRAGMA integrity_check` à l'ouverture : un fichier endommagé (coupure de
+    /// courant en pleine écriture) est consigné dans `app_log` sans bloquer le démarrage.
+    async fn check_file_integrity(&self) {
+        let messages: Vec<String> = match sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await
+        {
+            Ok(messages) => messages,
+            Err(e) => {
+                eprintln!("[Integrity] {e}");
+                return;
+            }
+        };
+        for message in messages.iter().filter(|m| *m != "ok") {
+            if let Err(e) = self.log_event("error", "integrity", message).await {
+                eprintln!("[Integrity] {message} (journal indisponible : {e})");
+            }
+        }
     }
 
     // ── Helpers privés ────────────────────────────────────────────────────────
 
+    /// Lit `gender` / `member_type` (TEXT). Une valeur héritée invalide retombe
+    /// sur le DEFAULT de la colonne ('M', 'Communiant') plutôt que de faire échouer la liste.
+    fn read_gender(r: &sqlx::sqlite::SqliteRow) -> Gender {
+        r.get::<String, _>("gender").parse().unwrap_or_default()
+    }
+
+    fn read_member_type(r: &sqlx::sqlite::SqliteRow) -> MemberType {
+        r.get::<String, _>("member_type").parse().unwrap_or_default()
+    }
+
+    fn read_member_status(r: &sqlx::sqlite::SqliteRow) -> MemberStatus {
+        r.get::<String, _>("status").parse().unwrap_or_default()
+    }
+
     fn map_member(r: &sqlx::sqlite::SqliteRow) -> Member {
         Member {
             id:          r.get("id"),
@@ -60,9 +651,14 @@ impl Repository {
             address:     r.get("address"),
             phone:       r.get("phone"),
             job:         r.get("job"),
-            gender:      r.get("gender"),
-            member_type: r.get("member_type"),
+            gender:      Self::read_gender(r),
+            member_type: Self::read_member_type(r),
+            surname:     r.get("surname"),
+            given_name:  r.get("given_name"),
+            status:      Self::read_member_status(r),
+            birth_date:  r.get("birth_date"),
             created_at:  r.get("created_at"),
+            transferred_at: r.get("transferred_at"),
         }
     }
 
@@ -75,32 +671,41 @@ impl Repository {
             period:        r.get("period"),
             amount:        Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO),
             recorded_year: r.get("recorded_year"),
+            receipt_number: r.get("receipt_number"),
+            recorded_by:   r.get("recorded_by"),
+        }
+    }
+
+    fn map_year_member_snapshot(r: &sqlx::sqlite::SqliteRow) -> YearMemberSnapshot {
+        YearMemberSnapshot {
+            year:        r.get("year"),
+            member_id:   r.get("member_id"),
+            member_name: r.get("member_name"),
+            card_number: r.get("card_number"),
+            total:       Decimal::from_str(&r.get::<String, _>("total")).unwrap_or(Decimal::ZERO),
         }
     }
 
     fn map_year_summary(r: &sqlx::sqlite::SqliteRow) -> YearSummary {
-        let total_str: String = r.get("total");
+        let decimal = |col: &str| {
+            let s: String = r.get(col);
+            Decimal::from_str(&s).unwrap_or(Decimal::ZERO)
+        };
         YearSummary {
-            year:      r.get("year"),
-            total:     Decimal::from_str(&total_str).unwrap_or(Decimal::ZERO),
-            closed_at: r.get("closed_at"),
-            note:      r.get("note"),
+            year:               r.get("year"),
+            total:              decimal("total"),
+            total_communiant:   decimal("total_communiant"),
+            total_cathekomen:   decimal("total_cathekomen"),
+            contributor_count:  r.get("contributor_count"),
+            contribution_count: r.get("contribution_count"),
+            closed_at:          r.get("closed_at"),
+            note:               r.get("note"),
         }
     }
 
     /// Formate un Decimal en chaîne lisible "1 234 567 Ariary" (partie entière seulement).
     fn format_ariary_note(total: &Decimal) -> String {
-        let n = total.to_string();
-        let integer_part = n.split('.').next().unwrap_or("0");
-        let len = integer_part.len();
-        let mut result = String::new();
-        for (i, c) in integer_part.chars().enumerate() {
-            if i > 0 && (len - i) % 3 == 0 {
-                result.push(' ');
-            }
-            result.push(c);
-        }
-        format!("{} Ariary", result)
+        format_ariary(&total.trunc())
     }
 
     /// Variante transactionnelle de `refresh_year_total` — exécutée dans une tx ouverte.
@@ -108,100 +713,315 @@ impl Repository {
     ///
     /// `tx` est `&mut Transaction<'_, Sqlite>` ; pour obtenir `&mut SqliteConnection`
     /// (seul type implémentant `Executor`), on double-déréférence : `&mut **tx`.
+    ///
+    /// Les sous-totaux suivent le type *actuel* du membre. Une contribution orpheline
+    /// (membre introuvable) compte dans `total` et `contribution_count`, mais ni dans
+    /// les sous-totaux ni dans `contributor_count` : `total` peut donc dépasser leur somme.
     async fn refresh_year_total_tx(
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
         year: i32,
     ) -> Result<(), AppError> {
-        let rows = sqlx::query(
-            "SELECT amount FROM contributions WHERE recorded_year = ?",
-        )
-        .bind(year)
-        .fetch_all(&mut **tx)
-        .await?;
-
-        let total: Decimal = rows
-            .iter()
-            .filter_map(|r| {
-                let s: String = r.get("amount");
-                Decimal::from_str(&s).ok()
-            })
-            .fold(Decimal::ZERO, |acc, d| acc + d);
+        let rows = sqlx::query(YEAR_REFRESH_SQL).bind(year).fetch_all(&mut **tx).await?;
+        let s = Self::sum_year_rows(year, &rows);
 
         sqlx::query(
-            "INSERT INTO year_summaries (year, total)
-             VALUES (?, ?)
-             ON CONFLICT(year) DO UPDATE SET total = excluded.total",
+            "INSERT INTO year_summaries
+                 (year, total, total_communiant, total_cathekomen, contributor_count,
+                  contribution_count)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(year) DO UPDATE SET
+                 total              = excluded.total,
+                 total_communiant   = excluded.total_communiant,
+                 total_cathekomen   = excluded.total_cathekomen,
+                 contributor_count  = excluded.contributor_count,
+                 contribution_count = excluded.contribution_count",
         )
         .bind(year)
-        .bind(total.to_string())
+        .bind(s.total.to_string())
+        .bind(s.total_communiant.to_string())
+        .bind(s.total_cathekomen.to_string())
+        .bind(s.contributor_count)
+        .bind(s.contribution_count)
         .execute(&mut **tx)
         .await?;
 
         Ok(())
     }
 
-    /// Recalcule le total d'une année depuis les contributions, puis fait un UPSERT.
-    async fn refresh_year_total(&self, year: i32) -> Result<(), AppError> {
-        let rows = sqlx::query(
-            "SELECT amount FROM contributions WHERE recorded_year = ?",
-        )
-        .bind(year)
-        .fetch_all(&self.pool)
-        .await?;
+    /// Totaux d'une année calculés depuis les lignes de `YEAR_REFRESH_SQL`, sans
+    /// rien écrire : résumé encore ouvert (`closed_at` et `note` à `None`).
+    fn sum_year_rows(year: i32, rows: &[sqlx::sqlite::SqliteRow]) -> YearSummary {
+        let mut total            = Decimal::ZERO;
+        let mut total_communiant = Decimal::ZERO;
+        let mut total_cathekomen = Decimal::ZERO;
+        let mut contributors     = HashSet::new();
+        let mut contributions    = 0_i64;
+        for r in rows {
+            let amount_str: String = r.get("amount");
+            let Ok(amount) = Decimal::from_str(&amount_str) else { continue };
+            total += amount;
+            contributions += 1;
+            let member_type: Option<String> = r.get("member_type");
+            match member_type.as_deref().map(MemberType::from_str) {
+                Some(Ok(MemberType::Communiant)) => total_communiant += amount,
+                Some(Ok(MemberType::Cathekomen)) => total_cathekomen += amount,
+                _ => continue,
+            }
+            contributors.insert(r.get::<i64, _>("member_id"));
+        }
 
-        let total: Decimal = rows
-            .iter()
-            .filter_map(|r| {
-                let s: String = r.get("amount");
-                Decimal::from_str(&s).ok()
-            })
-            .fold(Decimal::ZERO, |acc, d| acc + d);
+        YearSummary {
+            year,
+            total,
+            total_communiant,
+            total_cathekomen,
+            contributor_count:  contributors.len() as i64,
+            contribution_count: contributions,
+            closed_at:          None,
+            note:               None,
+        }
+    }
 
-        sqlx::query(
-            "INSERT INTO year_summaries (year, total)
-             VALUES (?, ?)
-             ON CONFLICT(year) DO UPDATE SET total = excluded.total",
+    /// Note générée à la clôture : total, sous-totaux et effectifs de l'année.
+    fn default_close_note(s: &YearSummary) -> String {
+        format!(
+            "CONTRIBUTIONS de l'année {} / TOTAL : {} / Communiants : {} / Cathekomens : {} \
+             / {} cotisation(s) · {} membre(s)",
+            s.year,
+            Self::format_ariary_note(&s.total),
+            Self::format_ariary_note(&s.total_communiant),
+            Self::format_ariary_note(&s.total_cathekomen),
+            s.contribution_count,
+            s.contributor_count
         )
-        .bind(year)
-        .bind(total.to_string())
-        .execute(&self.pool)
-        .await?;
+    }
+
+    /// Note d'année saisie : espaces retirés, vide → `None`, longueur bornée.
+    fn normalize_year_note(note: Option<String>) -> Result<Option<String>, AppError> {
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        if note.as_deref().is_some_and(|n| n.chars().count() > MAX_YEAR_NOTE_LEN) {
+            return Err(AppError::validation("year.note_too_long", &[&MAX_YEAR_NOTE_LEN]));
+        }
+        Ok(note)
+    }
 
+    /// Recalcule les sous-totaux des années ouvertes où ces membres ont cotisé,
+    /// après un changement de type. Les années clôturées gardent la répartition
+    /// figée à la clôture.
+    async fn refresh_open_years_of_members_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        member_ids: &[i64],
+    ) -> Result<(), AppError> {
+        for year in Self::open_years_of_members_tx(tx, member_ids).await? {
+            Self::refresh_year_total_tx(tx, year).await?;
+        }
         Ok(())
     }
 
+    /// Années non clôturées où ces membres ont au moins une cotisation.
+    async fn open_years_of_members_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        member_ids: &[i64],
+    ) -> Result<Vec<i32>, AppError> {
+        let mut qb: QueryBuilder<sqlx::Sqlite> = QueryBuilder::new(
+            "SELECT DISTINCT c.recorded_year FROM contributions c
+             LEFT JOIN year_summaries ys ON ys.year = c.recorded_year
+             WHERE ys.closed_at IS NULL AND c.member_id IN (",
+        );
+        let mut sep = qb.separated(", ");
+        for id in member_ids {
+            sep.push_bind(*id);
+        }
+        qb.push(")");
+        Ok(qb
+            .build()
+            .fetch_all(&mut **tx)
+            .await?
+            .iter()
+            .map(|r| r.get("recorded_year"))
+            .collect())
+    }
+
     // ── Validation des entrées membre ─────────────────────────────────────────
 
+    /// Parties du nom nettoyées ; `full_name` vide est composé à partir d'elles.
+    /// Un client qui n'envoie que `full_name` voit les parties déduites au mieux
+    /// (`split_full_name`), comme lors de la migration.
+    fn normalize_member_name(input: &mut MemberInput) {
+        let clean = |part: Option<String>| {
+            part.map(|p| p.trim().to_string()).filter(|p| !p.is_empty())
+        };
+        input.surname    = clean(input.surname.take());
+        input.given_name = clean(input.given_name.take());
+        input.full_name  = input.full_name.trim().to_string();
+        if input.full_name.is_empty() {
+            input.full_name = compose_full_name(input.surname.as_deref(), input.given_name.as_deref());
+        } else if input.surname.is_none() && input.given_name.is_none() {
+            (input.surname, input.given_name) = split_full_name(&input.full_name);
+        }
+    }
+
     fn validate_member_input(input: &MemberInput) -> Result<(), AppError> {
         if input.card_number.trim().is_empty() {
-            return Err(AppError::Validation("Le numéro de carte est requis.".into()));
+            return Err(AppError::validation("member.card_required", &[]));
         }
         if input.card_number.len() > 50 {
-            return Err(AppError::Validation("Le numéro de carte ne doit pas dépasser 50 caractères.".into()));
+            return Err(AppError::validation("member.card_too_long", &[&50]));
         }
         if input.full_name.trim().is_empty() {
-            return Err(AppError::Validation("Le nom complet est requis.".into()));
+            return Err(AppError::validation("member.name_required", &[]));
         }
-        if input.full_name.len() > 200 {
-            return Err(AppError::Validation("Le nom complet ne doit pas dépasser 200 caractères.".into()));
+        let parts = [input.surname.as_deref(), input.given_name.as_deref()];
+        if input.full_name.len() > 200 || parts.iter().flatten().any(|p| p.len() > 200) {
+            return Err(AppError::validation("member.name_too_long", &[&200]));
         }
         if input.address.as_deref().unwrap_or("").len() > 300 {
-            return Err(AppError::Validation("L'adresse ne doit pas dépasser 300 caractères.".into()));
+            return Err(AppError::validation("member.address_too_long", &[&300]));
         }
         if input.phone.as_deref().unwrap_or("").len() > 30 {
-            return Err(AppError::Validation("Le téléphone ne doit pas dépasser 30 caractères.".into()));
+            return Err(AppError::validation("member.phone_too_long", &[&30]));
         }
         if input.job.as_deref().unwrap_or("").len() > 150 {
-            return Err(AppError::Validation("Le travail ne doit pas dépasser 150 caractères.".into()));
+            return Err(AppError::validation("member.job_too_long", &[&150]));
         }
-        if input.gender != "M" && input.gender != "F" {
-            return Err(AppError::Validation("Genre invalide. Valeurs acceptées : 'M', 'F'.".into()));
+        if let Some(birth) = input.birth_date.as_deref() {
+            Self::validate_birth_date(birth, chrono::Local::now().date_naive())?;
         }
-        if input.member_type != "Communiant" && input.member_type != "Cathekomen" {
-            return Err(AppError::Validation(
-                format!("Type de membre invalide : '{}'. Valeurs acceptées : 'Communiant', 'Cathekomen'.", input.member_type),
+        // gender / member_type : déjà validés à la désérialisation (enums Gender / MemberType)
+        Ok(())
+    }
+
+    /// "YYYY-MM-DD", pas dans le futur, âge plausible (moins de `MAX_AGE_YEARS` ans à `today`).
+    fn validate_birth_date(birth: &str, today: NaiveDate) -> Result<NaiveDate, AppError> {
+        let date = NaiveDate::parse_from_str(birth, "%Y-%m-%d")
+            .map_err(|_| AppError::validation("member.birth_date_invalid", &[&birth]))?;
+        if date > today {
+            return Err(AppError::validation("member.birth_date_future", &[]));
+        }
+        if Self::age_on(date, today) >= MAX_AGE_YEARS {
+            return Err(AppError::validation("member.birth_date_implausible", &[&MAX_AGE_YEARS]));
+        }
+        Ok(date)
+    }
+
+    /// Âge révolu au jour `day`.
+    fn age_on(birth: NaiveDate, day: NaiveDate) -> i32 {
+        let had_birthday = (day.month(), day.day()) >= (birth.month(), birth.day());
+        day.year() - birth.year() - i32::from(!had_birthday)
+    }
+
+    /// Anniversaire de `birth` en `year` ; le 29 février est fêté le 28 les années non bissextiles.
+    fn birthday_in(birth: NaiveDate, year: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, birth.month(), birth.day())
+            .or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))
+    }
+
+    /// Prochain anniversaire à partir de `today` inclus (passe à l'année suivante fin décembre).
+    fn next_birthday(birth: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        Self::birthday_in(birth, today.year())
+            .filter(|d| *d >= today)
+            .or_else(|| Self::birthday_in(birth, today.year() + 1))
+    }
+
+    // ── Validation des entrées contribution ───────────────────────────────────
+
+    /// Période bien formée (`parse_period`) ; les trimestres seulement si `quarters`.
+    fn parse_allowed_period(period: &str, quarters: bool) -> Option<Period> {
+        parse_period(period).filter(|p| quarters || !matches!(p.part, PeriodPart::Quarter(_)))
+    }
+
+    /// Règles de période en vigueur (`settings.quarter_periods`) pour une saisie.
+    async fn period_rules(&self, allow_mismatch: bool) -> Result<PeriodRules, AppError> {
+        let settings = self.get_settings().await?;
+        let today = chrono::Local::now().date_naive();
+        Ok(PeriodRules {
+            quarters: settings.quarter_periods,
+            allow_mismatch,
+            latest_date: today.checked_add_days(chrono::Days::new(u64::from(settings.max_future_days))),
+            fiscal_start: settings.fiscal_year_start_month,
+        })
+    }
+
+    /// Premier mois de l'exercice (`settings.fiscal_year_start_month`).
+    async fn fiscal_start_month(&self) -> Result<u32, AppError> {
+        Ok(self.get_settings().await?.fiscal_year_start_month)
+    }
+
+    /// Exercice en cours (année civile si l'exercice commence en janvier).
+    async fn current_year(&self) -> Result<i32, AppError> {
+        let start = self.fiscal_start_month().await?;
+        Ok(fiscal_year_of(chrono::Local::now().date_naive(), start))
+    }
+
+    /// Valide les champs communs à la création et à la modification d'une contribution.
+    /// Retourne le montant parsé, l'exercice de `payment_date` (`fiscal_year_of`) et
+    /// la période sous sa forme canonique ("2025-3" → "2025-03").
+    fn validate_contribution_fields(
+        payment_date: &str,
+        period: &str,
+        amount: &str,
+        rules: PeriodRules,
+    ) -> Result<(Decimal, i32, String), AppError> {
+        let period = period.trim();
+        if period.is_empty() {
+            return Err(AppError::validation("contribution.period_required", &[]));
+        }
+
+        let amount = parse_amount(amount)?;
+
+        // Date plausible : ni avant `MIN_PAYMENT_YEAR`, ni trop loin dans le futur
+        let date = NaiveDate::parse_from_str(payment_date, "%Y-%m-%d").map_err(|_| {
+            AppError::validation("contribution.payment_date_invalid", &[&payment_date])
+        })?;
+        if date.year() < MIN_PAYMENT_YEAR {
+            return Err(AppError::validation(
+                "contribution.payment_date_too_old",
+                &[&payment_date, &MIN_PAYMENT_YEAR],
+            ));
+        }
+        if let Some(latest) = rules.latest_date.filter(|latest| date > *latest) {
+            return Err(AppError::validation(
+                "contribution.payment_date_future",
+                &[&payment_date, &latest.format("%Y-%m-%d")],
             ));
         }
+        // recorded_year est automatique : l'exercice de la date
+        let recorded_year = fiscal_year_of(date, rules.fiscal_start);
+
+        // Valider la période : format strict, et même année que le paiement sauf dérogation
+        let period = Self::parse_allowed_period(period, rules.quarters).ok_or_else(|| {
+            let key = if rules.quarters {
+                "contribution.period_invalid_quarters"
+            } else {
+                "contribution.period_invalid"
+            };
+            AppError::validation(key, &[&period])
+        })?;
+        // Année civile du paiement, ou exercice (période "2024" payée en mars 2025)
+        if period.year != date.year() && period.year != recorded_year && !rules.allow_mismatch {
+            return Err(AppError::validation(
+                "contribution.period_mismatch",
+                &[&period, &date.year()],
+            ));
+        }
+
+        Ok((amount, recorded_year, period.to_string()))
+    }
+
+    /// Vérifie qu'une année n'est pas clôturée (dans la transaction ouverte).
+    async fn ensure_year_open_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        year: i32,
+    ) -> Result<(), AppError> {
+        let closed: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT closed_at FROM year_summaries WHERE year = ?",
+        )
+        .bind(year)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if closed.flatten().is_some() {
+            return Err(AppError::validation("year.closed", &[&year]));
+        }
         Ok(())
     }
 
@@ -210,8 +1030,9 @@ impl Repository {
     pub async fn get_members(&self) -> Result<Vec<Member>, AppError> {
         let rows = sqlx::query(
             "SELECT id, card_number, full_name, address, phone, job,
-                    gender, member_type, created_at
+                    gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at
              FROM members
+             WHERE deleted_at IS NULL
              ORDER BY full_name ASC",
         )
         .fetch_all(&self.pool)
@@ -220,954 +1041,7874 @@ impl Repository {
         Ok(rows.iter().map(Self::map_member).collect())
     }
 
-    pub async fn get_members_by_type(&self, member_type: &str) -> Result<Vec<Member>, AppError> {
+    pub async fn get_members_by_type(&self, member_type: MemberType) -> Result<Vec<Member>, AppError> {
         let rows = sqlx::query(
             "SELECT id, card_number, full_name, address, phone, job,
-                    gender, member_type, created_at
+                    gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at
              FROM members
-             WHERE member_type = ?
+             WHERE member_type = ? AND deleted_at IS NULL
              ORDER BY full_name ASC",
         )
-        .bind(member_type)
+        .bind(member_type.as_str())
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows.iter().map(Self::map_member).collect())
     }
 
-    pub async fn get_members_by_type_with_total(
+    /// Effectifs par type en une seule requête (sans charger les listes).
+    pub async fn get_member_counts(&self) -> Result<MemberCounts, AppError> {
+        let row = sqlx::query(
+            "SELECT COALESCE(SUM(member_type = ?), 0) AS communiants,
+                    COALESCE(SUM(member_type = ?), 0) AS cathekomens
+             FROM members
+             WHERE deleted_at IS NULL",
+        )
+        .bind(MemberType::Communiant.as_str())
+        .bind(MemberType::Cathekomen.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(MemberCounts {
+            communiants: row.get("communiants"),
+            cathekomens: row.get("cathekomens"),
+        })
+    }
+
+    /// Anniversaires des `days` prochains jours (aujourd'hui compris), les plus proches d'abord.
+    pub async fn get_upcoming_birthdays(&self, days: u32) -> Result<Vec<UpcomingBirthday>, AppError> {
+        self.get_upcoming_birthdays_on(chrono::Local::now().date_naive(), days).await
+    }
+
+    /// `get_upcoming_birthdays` à une date donnée (testable).
+    pub async fn get_upcoming_birthdays_on(
         &self,
-        member_type: &str,
-    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        today: NaiveDate,
+        days: u32,
+    ) -> Result<Vec<UpcomingBirthday>, AppError> {
+        let window = i64::from(days.min(MAX_BIRTHDAY_WINDOW_DAYS));
         let rows = sqlx::query(
-            "SELECT m.id, m.card_number, m.full_name, m.address, m.phone, m.job,
-                    m.gender, m.member_type, m.created_at,
-                    COALESCE(SUM(CAST(c.amount AS REAL)), 0.0) AS total_contributions
-             FROM members m
-             LEFT JOIN contributions c ON c.member_id = m.id
-             WHERE m.member_type = ?
-             GROUP BY m.id
-             ORDER BY m.full_name ASC",
+            "SELECT id, full_name, member_type, birth_date
+             FROM members
+             WHERE birth_date IS NOT NULL AND deleted_at IS NULL",
         )
-        .bind(member_type)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows
+        let mut list: Vec<UpcomingBirthday> = rows
             .iter()
-            .map(|r| {
-                let total: f64 = r.get("total_contributions");
-                MemberWithTotal {
-                    id:                  r.get("id"),
-                    card_number:         r.get("card_number"),
-                    full_name:           r.get("full_name"),
-                    address:             r.get("address"),
-                    phone:               r.get("phone"),
-                    job:                 r.get("job"),
-                    gender:              r.get("gender"),
-                    member_type:         r.get("member_type"),
-                    created_at:          r.get("created_at"),
-                    total_contributions: format!("{:.0}", total),
-                }
+            .filter_map(|r| {
+                let birth_date: String = r.get("birth_date");
+                // Valeur invalide (base modifiée à la main) : ignorée plutôt que de faire échouer la carte
+                let birth = NaiveDate::parse_from_str(&birth_date, "%Y-%m-%d").ok()?;
+                let date = Self::next_birthday(birth, today)?;
+                let days_until = (date - today).num_days();
+                let age = date.year() - birth.year();
+                (days_until <= window && age > 0).then(|| UpcomingBirthday {
+                    member_id:   r.get("id"),
+                    full_name:   r.get("full_name"),
+                    member_type: Self::read_member_type(r),
+                    birth_date,
+                    date:        date.format("%Y-%m-%d").to_string(),
+                    days_until,
+                    age,
+                })
             })
-            .collect())
+            .collect();
+        list.sort_by(|a, b| a.days_until.cmp(&b.days_until).then_with(|| a.full_name.cmp(&b.full_name)));
+        Ok(list)
     }
 
-    pub async fn get_member(&self, id: i64) -> Result<Member, AppError> {
-        let row = sqlx::query(
-            "SELECT id, card_number, full_name, address, phone, job,
-                    gender, member_type, created_at
+    /// Cartes à renouveler dans les `within_days` prochains jours (aujourd'hui compris) :
+    /// anniversaires de l'inscription (`created_at`) des membres actifs, les plus proches d'abord.
+    pub async fn get_upcoming_renewals(&self, within_days: u32) -> Result<Vec<RenewalDue>, AppError> {
+        self.get_upcoming_renewals_on(chrono::Local::now().date_naive(), within_days).await
+    }
+
+    /// `get_upcoming_renewals` à une date donnée (testable).
+    pub async fn get_upcoming_renewals_on(
+        &self,
+        today: NaiveDate,
+        within_days: u32,
+    ) -> Result<Vec<RenewalDue>, AppError> {
+        let window = i64::from(within_days.min(MAX_RENEWAL_WINDOW_DAYS));
+        let rows = sqlx::query(
+            "SELECT id, full_name, card_number, member_type, created_at
              FROM members
-             WHERE id = ?",
+             WHERE deleted_at IS NULL AND status = ?",
         )
-        .bind(id)
-        .fetch_one(&self.pool)
+        .bind(MemberStatus::Actif.as_str())
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(Self::map_member(&row))
+        let mut list: Vec<RenewalDue> = rows
+            .iter()
+            .filter_map(|r| {
+                let created_at: String = r.get("created_at");
+                // "YYYY-MM-DDTHH:MM:SS" ; valeur illisible ignorée comme pour les anniversaires
+                let registered = NaiveDate::parse_from_str(created_at.get(..10)?, "%Y-%m-%d").ok()?;
+                // Même règle que les anniversaires : inscrit un 29 février, renouvelé le 28
+                let date = Self::next_birthday(registered, today)?;
+                let days_until = (date - today).num_days();
+                let years = date.year() - registered.year();
+                (days_until <= window && years > 0).then(|| RenewalDue {
+                    member_id:     r.get("id"),
+                    full_name:     r.get("full_name"),
+                    card_number:   r.get("card_number"),
+                    member_type:   Self::read_member_type(r),
+                    registered_on: registered.format("%Y-%m-%d").to_string(),
+                    date:          date.format("%Y-%m-%d").to_string(),
+                    days_until,
+                    years,
+                })
+            })
+            .collect();
+        list.sort_by(|a, b| a.days_until.cmp(&b.days_until).then_with(|| a.full_name.cmp(&b.full_name)));
+        Ok(list)
     }
 
-    pub async fn create_member(&self, input: MemberInput) -> Result<Member, AppError> {
-        Self::validate_member_input(&input)?;
-
-        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    /// Membres d'un type ; `status = None` : tous les statuts.
+    pub async fn get_members_by_type_with_total(
+        &self,
+        member_type: MemberType,
+        status: Option<MemberStatus>,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        self.get_members_by_type_with_total_for_year(member_type, status, self.current_year().await?)
+            .await
+    }
 
-        let row = sqlx::query(
-            "INSERT INTO members
-                 (card_number, full_name, address, phone, job, gender, member_type, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-             RETURNING id",
-        )
-        .bind(&input.card_number)
-        .bind(&input.full_name)
-        .bind(&input.address)
-        .bind(&input.phone)
-        .bind(&input.job)
-        .bind(&input.gender)
-        .bind(&input.member_type)
-        .bind(&now)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(Member {
-            id:          row.get("id"),
-            card_number: input.card_number,
-            full_name:   input.full_name,
-            address:     input.address,
-            phone:       input.phone,
-            job:         input.job,
-            gender:      input.gender,
-            member_type: input.member_type,
-            created_at:  now,
-        })
-    }
-
-    pub async fn update_member(&self, id: i64, input: MemberInput) -> Result<Member, AppError> {
-        Self::validate_member_input(&input)?;
-
-        sqlx::query(
-            "UPDATE members
-             SET card_number = ?, full_name = ?, address = ?, phone = ?,
-                 job = ?, gender = ?, member_type = ?
-             WHERE id = ?",
+    /// `current_year_total` porte sur les cotisations enregistrées en `year`.
+    pub async fn get_members_by_type_with_total_for_year(
+        &self,
+        member_type: MemberType,
+        status: Option<MemberStatus>,
+        year: i32,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        let rows = sqlx::query(
+            "SELECT m.id, m.card_number, m.full_name, m.address, m.phone, m.job,
+                    m.gender, m.member_type, m.surname, m.given_name, m.status, m.birth_date, m.created_at
+             FROM members m
+             WHERE m.member_type = ?1 AND m.deleted_at IS NULL
+               AND (?2 IS NULL OR m.status = ?2)
+             ORDER BY m.full_name ASC",
         )
-        .bind(&input.card_number)
-        .bind(&input.full_name)
-        .bind(&input.address)
-        .bind(&input.phone)
-        .bind(&input.job)
-        .bind(&input.gender)
-        .bind(&input.member_type)
-        .bind(id)
-        .execute(&self.pool)
+        .bind(member_type.as_str())
+        .bind(status.map(MemberStatus::as_str))
+        .fetch_all(&self.pool)
         .await?;
 
-        self.get_member(id).await
-    }
-
-    pub async fn delete_member(&self, id: i64) -> Result<(), AppError> {
-        // Les contributions liées sont supprimées en cascade (FK ON DELETE CASCADE)
-        sqlx::query("DELETE FROM members WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+        self.with_exact_totals(rows, year).await
     }
 
-    /// Importe une liste de membres en bloc.
-    /// Les entrées avec un numéro de carte déjà existant sont ignorées (INSERT OR IGNORE).
-    /// Retourne le nombre de membres effectivement insérés.
-    pub async fn import_members(&self, members: Vec<MemberInput>) -> Result<usize, AppError> {
-        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
-        let mut count = 0usize;
-        for input in members {
-            // Ignorer les lignes invalides silencieusement
-            if Self::validate_member_input(&input).is_err() {
-                continue;
+    /// Membres d'un type (tous statuts) avec leur total exact pour chacune des `years`
+    /// (colonnes « par année » des tableaux). Les montants étant du texte, le
+    /// regroupement par (membre, année) se fait ici en Decimal, comme
+    /// `exact_member_totals`. Distinct de `get_members_by_type_with_total` : la vue
+    /// par défaut n'en paie pas le coût.
+    pub async fn get_members_with_year_totals(
+        &self,
+        member_type: MemberType,
+        years: &[i32],
+    ) -> Result<Vec<MemberYearTotals>, AppError> {
+        let members = self.get_members_by_type_with_total(member_type, None).await?;
+        let mut totals: HashMap<i64, BTreeMap<i32, Decimal>> = HashMap::new();
+        if !years.is_empty() {
+            let mut qb = QueryBuilder::new(
+                "SELECT c.member_id, c.recorded_year, c.amount
+                 FROM contributions c JOIN members m ON m.id = c.member_id
+                 WHERE m.deleted_at IS NULL AND m.member_type = ",
+            );
+            qb.push_bind(member_type.as_str());
+            qb.push(" AND c.recorded_year IN (");
+            let mut sep = qb.separated(", ");
+            for year in years {
+                sep.push_bind(*year);
             }
-            let result = sqlx::query(
-                "INSERT OR IGNORE INTO members
-                     (card_number, full_name, address, phone, job, gender, member_type, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-            )
-            .bind(&input.card_number)
-            .bind(&input.full_name)
-            .bind(&input.address)
-            .bind(&input.phone)
-            .bind(&input.job)
-            .bind(&input.gender)
-            .bind(&input.member_type)
-            .bind(&now)
-            .execute(&self.pool)
-            .await?;
-            if result.rows_affected() > 0 {
-                count += 1;
+            qb.push(")");
+            let rows: Vec<(i64, i32, String)> = qb.build_query_as().fetch_all(&self.pool).await?;
+            for (member_id, year, amount) in rows {
+                let amount = Decimal::from_str(&amount).unwrap_or(Decimal::ZERO);
+                *totals.entry(member_id).or_default().entry(year).or_default() += amount;
             }
         }
-        Ok(count)
+        Ok(members
+            .into_iter()
+            .map(|member| {
+                let year_totals = totals.remove(&member.id).unwrap_or_default();
+                MemberYearTotals { member, year_totals }
+            })
+            .collect())
     }
 
-    /// Transfère plusieurs membres vers un nouveau type (ex: "Cathekomen" → "Communiant").
-    /// Les contributions restent liées à leurs IDs — aucune perte de données.
-    pub async fn transfer_members(
+    /// Membres d'un type (tous statuts) regroupés par `address_key`, adresses dans
+    /// l'ordre alphabétique puis « sans adresse » en dernier ; chaque groupe garde
+    /// l'ordre par nom. Regroupement fait ici : la normalisation n'existe pas en SQL.
+    pub async fn get_members_grouped_by_address(
         &self,
-        ids: &[i64],
-        new_type: &str,
-    ) -> Result<usize, AppError> {
-        if ids.is_empty() {
-            return Ok(0);
-        }
-        if new_type != "Communiant" && new_type != "Cathekomen" {
-            return Err(AppError::Validation(
-                format!("Type de membre invalide : '{new_type}'. Valeurs acceptées : 'Communiant', 'Cathekomen'."),
-            ));
+        member_type: MemberType,
+    ) -> Result<Vec<AddressGroup>, AppError> {
+        let members = self.get_members_by_type_with_total(member_type, None).await?;
+        let mut groups: BTreeMap<String, Vec<MemberWithTotal>> = BTreeMap::new();
+        let mut without: Vec<MemberWithTotal> = Vec::new();
+        for m in members {
+            match address_key(m.address.as_deref()) {
+                Some(key) => groups.entry(key).or_default().push(m),
+                None      => without.push(m),
+            }
         }
-        let mut qb: QueryBuilder<sqlx::Sqlite> =
-            QueryBuilder::new("UPDATE members SET member_type = ");
-        qb.push_bind(new_type);
-        qb.push(" WHERE id IN (");
-        let mut sep = qb.separated(", ");
-        for id in ids {
-            sep.push_bind(*id);
+        let mut list: Vec<AddressGroup> = groups
+            .into_iter()
+            .map(|(address, members)| AddressGroup { address: Some(address), members })
+            .collect();
+        if !without.is_empty() {
+            list.push(AddressGroup { address: None, members: without });
         }
-        qb.push(")");
-        let result = qb.build().execute(&self.pool).await?;
-        Ok(result.rows_affected() as usize)
+        Ok(list)
     }
 
-    // ── Contribution CRUD ─────────────────────────────────────────────────────
-
-    pub async fn get_contributions(&self, member_id: i64) -> Result<Vec<Contribution>, AppError> {
+    /// Membres (d'un type, ou des deux si `None`) sans aucune cotisation
+    /// enregistrée en `year`, triés par nom : la liste des relances pastorales.
+    pub async fn get_members_without_contribution(
+        &self,
+        year: i32,
+        member_type: Option<&str>,
+    ) -> Result<Vec<Member>, AppError> {
+        let member_type = member_type.map(MemberType::from_str).transpose()?;
         let rows = sqlx::query(
-            "SELECT id, member_id, payment_date, period, amount, recorded_year
-             FROM contributions
-             WHERE member_id = ?
-             ORDER BY payment_date DESC",
+            "SELECT m.id, m.card_number, m.full_name, m.address, m.phone, m.job,
+                    m.gender, m.member_type, m.surname, m.given_name, m.status,
+                    m.birth_date, m.created_at, m.transferred_at
+             FROM members m
+             WHERE m.deleted_at IS NULL
+               AND (?1 IS NULL OR m.member_type = ?1)
+               AND NOT EXISTS (
+                   SELECT 1 FROM contributions c
+                   WHERE c.member_id = m.id AND c.recorded_year = ?2
+               )
+             ORDER BY m.full_name ASC",
         )
-        .bind(member_id)
+        .bind(member_type.map(MemberType::as_str))
+        .bind(year)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.iter().map(Self::map_contribution).collect())
+        Ok(rows.iter().map(Self::map_member).collect())
     }
 
-    pub async fn get_contributions_by_year(
+    /// Recherche dans les deux types de membres (nom, carte, téléphone, adresse),
+    /// insensible à la casse. Au plus `limit` résultats (`DEFAULT_SEARCH_LIMIT` si `None`),
+    /// triés par nom ; requête vide : aucun résultat.
+    pub async fn search_members(
         &self,
-        year: i32,
-    ) -> Result<Vec<Contribution>, AppError> {
+        query: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        let limit   = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_PAGE_SIZE);
+        let pattern = format!("%{}%", Self::escape_like(query));
+        // Téléphones stockés groupés ("+261 34 12 345 67") : comparaison sans espaces
+        let compact = query.split_whitespace().collect::<String>();
+        let phone_pattern = format!("%{}%", Self::escape_like(&compact));
+
         let rows = sqlx::query(
-            "SELECT id, member_id, payment_date, period, amount, recorded_year
-             FROM contributions
-             WHERE recorded_year = ?
-             ORDER BY payment_date DESC",
+            "SELECT m.id, m.card_number, m.full_name, m.address, m.phone, m.job,
+                    m.gender, m.member_type, m.surname, m.given_name, m.status, m.birth_date, m.created_at
+             FROM members m
+             WHERE m.deleted_at IS NULL
+               AND (m.full_name   LIKE ?1 ESCAPE '\\'
+                 OR m.card_number LIKE ?1 ESCAPE '\\'
+                 OR REPLACE(m.phone, ' ', '') LIKE ?3 ESCAPE '\\'
+                 OR m.address     LIKE ?1 ESCAPE '\\')
+             ORDER BY m.full_name COLLATE NOCASE ASC, m.id
+             LIMIT ?2",
         )
-        .bind(year)
+        .bind(pattern)
+        .bind(limit)
+        .bind(phone_pattern)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.iter().map(Self::map_contribution).collect())
+        self.with_exact_totals(rows, self.current_year().await?).await
     }
 
-    /// Cotisations d'une année avec le nom du membre + résumé audit (JOIN).
-    pub async fn get_contributions_by_year_with_member(
-        &self,
-        year: i32,
-    ) -> Result<Vec<ContributionWithMember>, AppError> {
-        let rows = sqlx::query(
-            "SELECT c.id, c.member_id, m.full_name AS member_name,
-                    c.payment_date, c.period, c.amount, c.recorded_year,
-                    (SELECT GROUP_CONCAT(summary, ' · ')
-                     FROM (SELECT CASE field
-                               WHEN 'amount' THEN old_value || ' Ar → ' || new_value || ' Ar'
-                               ELSE old_value || ' → ' || new_value
-                           END AS summary
-                           FROM contribution_audits
-                           WHERE contribution_id = c.id
-                           ORDER BY changed_at ASC)
-                    ) AS audit_summary
-             FROM contributions c
-             JOIN members m ON m.id = c.member_id
-             WHERE c.recorded_year = ?
-             ORDER BY c.payment_date ASC",
+    /// Sélecteur de membre : id, nom et carte des membres actifs dont le nom ou la
+    /// carte contient `query` (insensible à la casse), triés par nom, au plus
+    /// `MEMBER_PICKER_LIMIT`. Requête vide : aucun résultat.
+    pub async fn search_members_lite(&self, query: &str) -> Result<Vec<MemberLite>, AppError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        let pattern = format!("%{}%", Self::escape_like(query));
+        let rows: Vec<(i64, String, String)> = sqlx::query_as(
+            "SELECT id, full_name, card_number
+             FROM members
+             WHERE deleted_at IS NULL
+               AND (full_name LIKE ?1 ESCAPE '\\' OR card_number LIKE ?1 ESCAPE '\\')
+             ORDER BY full_name COLLATE NOCASE ASC, id
+             LIMIT ?2",
         )
-        .bind(year)
+        .bind(pattern)
+        .bind(MEMBER_PICKER_LIMIT)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows
-            .iter()
-            .map(|r| {
-                let amount_str: String = r.get("amount");
-                ContributionWithMember {
-                    id:            r.get("id"),
-                    member_id:     r.get("member_id"),
-                    member_name:   r.get("member_name"),
-                    payment_date:  r.get("payment_date"),
-                    period:        r.get("period"),
-                    amount:        Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO),
-                    recorded_year: r.get("recorded_year"),
-                    audit_summary: r.get("audit_summary"),
-                }
-            })
+            .into_iter()
+            .map(|(id, full_name, card_number)| MemberLite { id, full_name, card_number })
             .collect())
     }
 
-    /// Toutes les cotisations toutes années confondues, triées par date ASC + résumé audit.
-    pub async fn get_all_contributions_with_member(
+    /// Membres actifs dont le nom ressemble à `full_name` (casse, accents, ordre des
+    /// mots et petites fautes ignorés), les plus proches d'abord, au plus
+    /// `MAX_SIMILAR_MEMBERS`. Sert d'avertissement avant une création : ne bloque rien.
+    pub async fn find_similar_members(&self, full_name: &str) -> Result<Vec<Member>, AppError> {
+        if full_name.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        let mut scored: Vec<(f64, Member)> = self
+            .get_members()
+            .await?
+            .into_iter()
+            .map(|m| (name_similarity(full_name, &m.full_name), m))
+            .filter(|(score, _)| *score >= SIMILAR_NAME_THRESHOLD)
+            .collect();
+        // get_members trie déjà par nom : tri stable sur le score seul
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(scored.into_iter().take(MAX_SIMILAR_MEMBERS).map(|(_, m)| m).collect())
+    }
+
+    /// Sommes exactes des cotisations de `member_ids` : (toutes années, année `year`).
+    /// Les montants sont relus en texte et additionnés en `Decimal` :
+    /// `SUM(CAST(amount AS REAL))` dérive dès qu'il y a des décimales.
+    async fn exact_member_totals(
         &self,
-    ) -> Result<Vec<ContributionWithMember>, AppError> {
-        let rows = sqlx::query(
-            "SELECT c.id, c.member_id, m.full_name AS member_name,
-                    c.payment_date, c.period, c.amount, c.recorded_year,
-                    (SELECT GROUP_CONCAT(summary, ' · ')
-                     FROM (SELECT CASE field
-                               WHEN 'amount' THEN old_value || ' Ar → ' || new_value || ' Ar'
-                               ELSE old_value || ' → ' || new_value
-                           END AS summary
-                           FROM contribution_audits
-                           WHERE contribution_id = c.id
-                           ORDER BY changed_at ASC)
-                    ) AS audit_summary
-             FROM contributions c
-             JOIN members m ON m.id = c.member_id
-             ORDER BY c.payment_date ASC",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        member_ids: &[i64],
+        year: i32,
+    ) -> Result<HashMap<i64, (Decimal, Decimal)>, AppError> {
+        let mut totals: HashMap<i64, (Decimal, Decimal)> = HashMap::new();
+        // Par paquets : limite du nombre de paramètres SQLite
+        for chunk in member_ids.chunks(500) {
+            let mut qb = QueryBuilder::new(
+                "SELECT member_id, recorded_year, amount FROM contributions WHERE member_id IN (",
+            );
+            let mut sep = qb.separated(", ");
+            for id in chunk {
+                sep.push_bind(*id);
+            }
+            qb.push(")");
+            let rows: Vec<(i64, i32, String)> = qb.build_query_as().fetch_all(&self.pool).await?;
+            for (member_id, recorded_year, amount) in rows {
+                let amount = Decimal::from_str(&amount).unwrap_or(Decimal::ZERO);
+                let entry = totals.entry(member_id).or_default();
+                entry.0 += amount;
+                if recorded_year == year {
+                    entry.1 += amount;
+                }
+            }
+        }
+        Ok(totals)
+    }
 
+    /// Lignes membre complétées par leurs totaux exacts (`exact_member_totals`).
+    async fn with_exact_totals(
+        &self,
+        rows: Vec<sqlx::sqlite::SqliteRow>,
+        year: i32,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        let ids: Vec<i64> = rows.iter().map(|r| r.get("id")).collect();
+        let totals = self.exact_member_totals(&ids, year).await?;
         Ok(rows
             .iter()
             .map(|r| {
-                let amount_str: String = r.get("amount");
-                ContributionWithMember {
-                    id:            r.get("id"),
-                    member_id:     r.get("member_id"),
-                    member_name:   r.get("member_name"),
-                    payment_date:  r.get("payment_date"),
-                    period:        r.get("period"),
-                    amount:        Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO),
-                    recorded_year: r.get("recorded_year"),
-                    audit_summary: r.get("audit_summary"),
-                }
+                let (total, year_total) = totals.get(&r.get("id")).copied().unwrap_or_default();
+                Self::map_member_with_total(r, total, year_total)
             })
             .collect())
     }
 
-    // ── PIN ───────────────────────────────────────────────────────────────────
-
-    /// Définit le code PIN admin (une seule fois). Erreur si déjà défini.
-    pub async fn set_pin(&self, pin: &str) -> Result<(), AppError> {
-        use sha2::{Digest, Sha256};
-
-        if pin.len() < 4 || !pin.chars().all(|c| c.is_ascii_digit()) {
-            return Err(AppError::Validation(
-                "Le code PIN doit contenir au moins 4 chiffres.".into(),
-            ));
-        }
-        let existing: Option<String> = sqlx::query_scalar(
-            "SELECT value FROM settings WHERE key = 'admin_pin'",
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-        if existing.is_some() {
-            return Err(AppError::Validation("Un code PIN est déjà configuré.".into()));
+    fn map_member_with_total(
+        r: &sqlx::sqlite::SqliteRow,
+        total: Decimal,
+        year_total: Decimal,
+    ) -> MemberWithTotal {
+        MemberWithTotal {
+            id:                  r.get("id"),
+            card_number:         r.get("card_number"),
+            full_name:           r.get("full_name"),
+            address:             r.get("address"),
+            phone:               r.get("phone"),
+            job:                 r.get("job"),
+            gender:              Self::read_gender(r),
+            member_type:         Self::read_member_type(r),
+            surname:             r.get("surname"),
+            given_name:          r.get("given_name"),
+            status:              Self::read_member_status(r),
+            birth_date:          r.get("birth_date"),
+            created_at:          r.get("created_at"),
+            total_contributions: total,
+            current_year_total:  year_total,
         }
-        let mut hasher = Sha256::new();
-        hasher.update(pin.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-        sqlx::query("INSERT INTO settings (key, value) VALUES ('admin_pin', ?)")
-            .bind(&hash)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
     }
 
-    /// Vérifie le code PIN. Retourne false si PIN incorrect, erreur si non configuré.
-    pub async fn verify_pin(&self, pin: &str) -> Result<bool, AppError> {
-        use sha2::{Digest, Sha256};
-        let stored: Option<String> = sqlx::query_scalar(
-            "SELECT value FROM settings WHERE key = 'admin_pin'",
-        )
-        .fetch_optional(&self.pool)
-        .await?;
-        let stored = match stored {
-            Some(h) => h,
-            None => return Err(AppError::Validation("Code PIN non configuré.".into())),
-        };
-        let mut hasher = Sha256::new();
-        hasher.update(pin.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-        Ok(hash == stored)
+    /// Page de lignes allégées : tri, filtres et pagination faits en SQL.
+    /// `page` commence à 0 ; `page_size` est borné à `MAX_PAGE_SIZE`.
+    pub async fn get_member_rows(
+        &self,
+        member_type: MemberType,
+        page: i64,
+        page_size: i64,
+        sort: MemberSort,
+        filter: &MemberFilter,
+    ) -> Result<Page<MemberRow>, AppError> {
+        let year = self.current_year().await?;
+        self.get_member_rows_for_year(member_type, page, page_size, sort, filter, year)
+            .await
     }
 
-    /// Modifie une contribution existante après vérification du PIN.
-    /// Crée une entrée d'audit pour chaque champ modifié.
-    /// Interdit si l'année est clôturée.
-    pub async fn update_contribution(
+    /// `current_year_total` (et le filtre `unpaid_only`) portent sur l'année `year`.
+    pub async fn get_member_rows_for_year(
         &self,
-        id: i64,
-        input: crate::db::ContributionEditInput,
-    ) -> Result<Contribution, AppError> {
-        // 1. Vérifier le PIN
-        if !self.verify_pin(&input.pin).await? {
-            return Err(AppError::Validation("Code PIN incorrect.".into()));
-        }
-        // 2. Valider le montant
-        let new_amount = Decimal::from_str(input.amount.trim())
-            .map_err(|_| AppError::Validation("Montant invalide.".into()))?;
-        if new_amount <= Decimal::ZERO {
-            return Err(AppError::Validation("Le montant doit être positif.".into()));
-        }
-        // 3. Valider la date
-        let new_recorded_year: i32 = NaiveDate::parse_from_str(&input.payment_date, "%Y-%m-%d")
-            .map(|d| d.year())
-            .map_err(|_| AppError::Validation(
-                format!("Date invalide : '{}'. Format YYYY-MM-DD.", input.payment_date),
-            ))?;
-
-        let mut tx = self.pool.begin().await?;
+        member_type: MemberType,
+        page: i64,
+        page_size: i64,
+        sort: MemberSort,
+        filter: &MemberFilter,
+        year: i32,
+    ) -> Result<Page<MemberRow>, AppError> {
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        let offset    = page.max(0) * page_size;
+
+        // SELECT … GROUP BY … HAVING commun au comptage et à la page
+        let push_filtered = |qb: &mut QueryBuilder<'_, sqlx::Sqlite>| {
+            qb.push(
+                "SELECT m.id, m.card_number, m.full_name, m.address, m.phone, m.job, m.gender,
+                        m.status, m.birth_date,
+                        COALESCE(SUM(CASE WHEN c.recorded_year = ",
+            );
+            qb.push_bind(year);
+            qb.push(
+                " THEN CAST(c.amount AS REAL) END), 0.0) AS current_year_total
+                 FROM members m
+                 LEFT JOIN contributions c ON c.member_id = m.id
+                 WHERE m.deleted_at IS NULL AND m.member_type = ",
+            );
+            qb.push_bind(member_type.as_str());
+            if let Some(g) = filter.gender {
+                qb.push(" AND m.gender = ");
+                qb.push_bind(g.as_str());
+            }
+            if let Some(st) = filter.status {
+                qb.push(" AND m.status = ");
+                qb.push_bind(st.as_str());
+            }
+            let search = filter.search.trim();
+            if !search.is_empty() {
+                let pattern = format!("%{}%", Self::escape_like(search));
+                qb.push(" AND (");
+                let mut sep = qb.separated(" OR ");
+                for col in ["m.card_number", "m.full_name", "m.address", "m.phone", "m.job"] {
+                    sep.push(format!("{col} LIKE "));
+                    sep.push_bind_unseparated(pattern.clone());
+                    sep.push_unseparated(" ESCAPE '\\'");
+                }
+                qb.push(")");
+            }
+            qb.push(" GROUP BY m.id");
+            if filter.unpaid_only {
+                qb.push(" HAVING current_year_total = 0");
+            }
+        };
 
-        // 4. Récupérer l'ancienne contribution
-        let old_row = sqlx::query(
-            "SELECT member_id, payment_date, period, amount, recorded_year
-             FROM contributions WHERE id = ?",
-        )
-        .bind(id)
-        .fetch_one(&mut *tx)
-        .await?;
+        let mut qb = QueryBuilder::new("SELECT COUNT(*) FROM (");
+        push_filtered(&mut qb);
+        qb.push(")");
+        let total_count: i64 = qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let order_expr = match sort.col {
+            MemberSortCol::CardNumber       => "m.card_number",
+            // Nom de famille d'abord quand il est connu : "RAKOTO Jean" et "Jean RAKOTO" voisinent
+            MemberSortCol::FullName         => "COALESCE(m.surname || ' ' || COALESCE(m.given_name, ''), m.full_name)",
+            MemberSortCol::Address          => "COALESCE(m.address, '')",
+            MemberSortCol::Phone            => "COALESCE(m.phone, '')",
+            MemberSortCol::Job              => "COALESCE(m.job, '')",
+            // Même ordre que l'enum côté frontend : M avant F
+            MemberSortCol::Gender           => "CASE m.gender WHEN 'M' THEN 0 ELSE 1 END",
+            MemberSortCol::CurrentYearTotal => "current_year_total",
+        };
+        let dir = if sort.desc { "DESC" } else { "ASC" };
+
+        let mut qb = QueryBuilder::new("");
+        push_filtered(&mut qb);
+        // m.id départage les ex æquo : pagination stable
+        qb.push(format!(" ORDER BY {order_expr} {dir}, m.id {dir} LIMIT "));
+        qb.push_bind(page_size);
+        qb.push(" OFFSET ");
+        qb.push_bind(offset);
+        let rows = qb.build().fetch_all(&self.pool).await?;
+
+        // Tri et filtre « non payé » en SQL (REAL suffit) ; totaux affichés exacts
+        let ids: Vec<i64> = rows.iter().map(|r| r.get("id")).collect();
+        let totals = self.exact_member_totals(&ids, year).await?;
+        let items = rows
+            .iter()
+            .map(|r| {
+                let year_total = totals.get(&r.get("id")).map_or(Decimal::ZERO, |t| t.1);
+                MemberRow {
+                    id:                 r.get("id"),
+                    card_number:        r.get("card_number"),
+                    full_name:          r.get("full_name"),
+                    address:            r.get("address"),
+                    phone:              r.get("phone"),
+                    job:                r.get("job"),
+                    gender:             Self::read_gender(r),
+                    status:             Self::read_member_status(r),
+                    birth_date:         r.get("birth_date"),
+                    current_year_total: year_total,
+                }
+            })
+            .collect();
 
-        let old_year: i32        = old_row.get("recorded_year");
-        let old_amount_str: String = old_row.get("amount");
-        let old_amount = Decimal::from_str(&old_amount_str).unwrap_or(Decimal::ZERO);
-        let old_period: String   = old_row.get("period");
-        let old_date: String     = old_row.get("payment_date");
-        let member_id: i64       = old_row.get("member_id");
+        Ok(Page { items, total_count })
+    }
 
-        // 5. Vérifier que l'année n'est pas clôturée
-        let closed: Option<Option<String>> = sqlx::query_scalar(
-            "SELECT closed_at FROM year_summaries WHERE year = ?",
-        )
-        .bind(old_year)
-        .fetch_optional(&mut *tx)
-        .await?;
-        if closed.flatten().is_some() {
-            return Err(AppError::Validation(
-                "Impossible de modifier une contribution d'une année clôturée.".into(),
-            ));
+    /// Échappe `%`, `_` et `\` pour un motif `LIKE … ESCAPE '\'`.
+    fn escape_like(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        for c in s.chars() {
+            if matches!(c, '%' | '_' | '\\') {
+                out.push('\\');
+            }
+            out.push(c);
         }
+        out
+    }
 
-        // 6. Mettre à jour la contribution
-        sqlx::query(
-            "UPDATE contributions
-             SET payment_date = ?, period = ?, amount = ?, recorded_year = ?
+    pub async fn get_member(&self, id: i64) -> Result<Member, AppError> {
+        let row = sqlx::query(
+            "SELECT id, card_number, full_name, address, phone, job,
+                    gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at
+             FROM members
              WHERE id = ?",
         )
-        .bind(&input.payment_date)
-        .bind(&input.period)
-        .bind(new_amount.to_string())
-        .bind(new_recorded_year)
         .bind(id)
-        .execute(&mut *tx)
-        .await?;
-
-        // 7. Recalculer les totaux annuels
-        Self::refresh_year_total_tx(&mut tx, old_year).await?;
-        if new_recorded_year != old_year {
-            Self::refresh_year_total_tx(&mut tx, new_recorded_year).await?;
-        }
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Membre #{id} introuvable.")))?;
 
-        // 8. Créer les entrées d'audit pour les champs modifiés
-        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
-        let reason_opt: Option<String> = if input.reason.trim().is_empty() {
-            None
-        } else {
-            Some(input.reason.trim().to_string())
-        };
+        Ok(Self::map_member(&row))
+    }
 
-        for (field, old_val, new_val) in [
-            ("amount",       old_amount.to_string(), new_amount.to_string()),
-            ("period",       old_period.clone(),     input.period.clone()),
-            ("payment_date", old_date.clone(),        input.payment_date.clone()),
-        ] {
-            if old_val != new_val {
-                sqlx::query(
-                    "INSERT INTO contribution_audits
-                         (contribution_id, field, old_value, new_value, changed_at, reason)
-                     VALUES (?, ?, ?, ?, ?, ?)",
-                )
-                .bind(id)
-                .bind(field)
-                .bind(&old_val)
-                .bind(&new_val)
-                .bind(&now)
-                .bind(&reason_opt)
-                .execute(&mut *tx)
-                .await?;
-            }
+    /// Recherche exacte par numéro de carte (lecteur de code-barres), sans tenir
+    /// compte de la casse ni des espaces autour. À casse près, deux cartes peuvent
+    /// coexister : la correspondance exacte l'emporte.
+    pub async fn get_member_by_card(&self, card_number: &str) -> Result<Option<Member>, AppError> {
+        let card = card_number.trim();
+        if card.is_empty() {
+            return Ok(None);
         }
+        let row = sqlx::query(
+            "SELECT id, card_number, full_name, address, phone, job,
+                    gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at
+             FROM members
+             WHERE TRIM(card_number) = ? COLLATE NOCASE AND deleted_at IS NULL
+             ORDER BY TRIM(card_number) = ? DESC, id
+             LIMIT 1",
+        )
+        .bind(card)
+        .bind(card)
+        .fetch_optional(&self.pool)
+        .await?;
 
-        tx.commit().await?;
-
-        Ok(Contribution {
-            id,
-            member_id,
-            payment_date: input.payment_date,
-            period:        input.period,
-            amount:        new_amount,
-            recorded_year: new_recorded_year,
-        })
+        Ok(row.as_ref().map(Self::map_member))
     }
 
-    /// Vérifie si l'année précédente est déjà clôturée.
-    /// Si non → calcule le total, génère une note et clôture automatiquement.
-    /// Retourne `Some(YearSummary)` si une clôture vient d'être effectuée, `None` sinon.
-    pub async fn check_and_close_previous_year(&self) -> Result<Option<YearSummary>, AppError> {
-        let prev_year = chrono::Utc::now().year() - 1;
-
-        // Déjà clôturé → rien à faire
-        if let Some(existing) = self.get_year_summary(prev_year).await? {
-            if existing.closed_at.is_some() {
-                return Ok(None);
-            }
-        }
-
-        // S'assurer que le résumé existe (même à 0) + recalculer le total
-        self.refresh_year_total(prev_year).await?;
+    pub async fn create_member(&self, mut input: MemberInput) -> Result<Member, AppError> {
+        input.phone = normalize_optional_phone(input.phone.as_deref())?;
+        Self::normalize_member_name(&mut input);
+        Self::validate_member_input(&input)?;
 
-        let total = self
-            .get_year_summary(prev_year)
-            .await?
-            .map(|s| s.total)
-            .unwrap_or(Decimal::ZERO);
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
 
-        let note = format!(
-            "CONTRIBUTIONS de l'année {} / TOTAL : {}",
-            prev_year,
-            Self::format_ariary_note(&total)
-        );
+        let (input_ref, now_ref) = (&input, &now);
+        let result = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let row = sqlx::query(
+                "INSERT INTO members
+                     (card_number, full_name, address, phone, job, gender, member_type,
+                      surname, given_name, status, birth_date, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 RETURNING id",
+            )
+            .bind(&input_ref.card_number)
+            .bind(&input_ref.full_name)
+            .bind(&input_ref.address)
+            .bind(&input_ref.phone)
+            .bind(&input_ref.job)
+            .bind(input_ref.gender.as_str())
+            .bind(input_ref.member_type.as_str())
+            .bind(&input_ref.surname)
+            .bind(&input_ref.given_name)
+            .bind(input_ref.status.as_str())
+            .bind(&input_ref.birth_date)
+            .bind(now_ref)
+            .fetch_one(&mut *tx)
+            .await?;
+            Self::audit_tx(&mut tx, "create", "member", row.get("id"), &serde_json::json!({
+                "card_number": input_ref.card_number,
+                "full_name":   input_ref.full_name,
+                "member_type": input_ref.member_type.as_str(),
+            }))
+            .await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(row)
+        })
+        .await;
+        let row = match result {
+            Err(AppError::Conflict(_)) => return Err(self.card_conflict(&input.card_number).await),
+            other => other?,
+        };
 
-        let closed = self.close_year(prev_year, Some(note)).await?;
-        Ok(Some(closed))
+        Ok(Member {
+            id:          row.get("id"),
+            card_number: input.card_number,
+            full_name:   input.full_name,
+            address:     input.address,
+            phone:       input.phone,
+            job:         input.job,
+            gender:      input.gender,
+            member_type: input.member_type,
+            surname:     input.surname,
+            given_name:  input.given_name,
+            status:      input.status,
+            birth_date:  input.birth_date,
+            created_at:  now,
+            transferred_at: None,
+        })
     }
 
-    pub async fn create_contribution(
-        &self,
-        input: ContributionInput,
-    ) -> Result<Contribution, AppError> {
-        // Valider la période
-        let period = input.period.trim();
-        if period.is_empty() {
-            return Err(AppError::Validation("La période est requise (ex : '2025').".into()));
-        }
-        if period.len() > 50 {
-            return Err(AppError::Validation("La période ne doit pas dépasser 50 caractères.".into()));
-        }
+    pub async fn update_member(&self, id: i64, mut input: MemberInput) -> Result<Member, AppError> {
+        input.phone = normalize_optional_phone(input.phone.as_deref())?;
+        Self::normalize_member_name(&mut input);
+        Self::validate_member_input(&input)?;
 
-        // Valider et parser le montant
-        let amount = Decimal::from_str(input.amount.trim())
-            .map_err(|_| AppError::Validation(
-                format!("Montant invalide : '{}'. Utilisez le format '15000.50'.", input.amount),
-            ))?;
+        let input = &input;
+        let result = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(
+                "UPDATE members
+                 SET card_number = ?, full_name = ?, address = ?, phone = ?,
+                     job = ?, gender = ?, member_type = ?, surname = ?, given_name = ?,
+                     status = ?, birth_date = ?
+                 WHERE id = ?",
+            )
+            .bind(&input.card_number)
+            .bind(&input.full_name)
+            .bind(&input.address)
+            .bind(&input.phone)
+            .bind(&input.job)
+            .bind(input.gender.as_str())
+            .bind(input.member_type.as_str())
+            .bind(&input.surname)
+            .bind(&input.given_name)
+            .bind(input.status.as_str())
+            .bind(&input.birth_date)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+            Self::audit_tx(&mut tx, "update", "member", id, &serde_json::json!({
+                "card_number": input.card_number,
+                "full_name":   input.full_name,
+                "member_type": input.member_type.as_str(),
+            }))
+            .await?;
 
-        if amount < Decimal::ZERO {
-            return Err(AppError::Validation("Le montant ne peut pas être négatif.".into()));
+            Self::refresh_open_years_of_members_tx(&mut tx, &[id]).await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(())
+        })
+        .await;
+        if let Err(AppError::Conflict(_)) = result {
+            return Err(self.card_conflict(&input.card_number).await);
         }
+        result?;
+        self.get_member(id).await
+    }
 
-        // Extraire l'année — recorded_year est automatique
-        let recorded_year = NaiveDate::parse_from_str(&input.payment_date, "%Y-%m-%d")
-            .map(|d| d.year())
-            .map_err(|_| AppError::Validation(
-                format!(
-                    "Date de paiement invalide : '{}'. Format attendu : YYYY-MM-DD.",
-                    input.payment_date
-                ),
-            ))?;
+    /// Prochain numéro de carte libre pour `member_type`, selon le modèle des
+    /// paramètres : plus grand numéro suivant le modèle + 1. Les membres à la
+    /// corbeille comptent (leur numéro reste pris) ; les anciens numéros d'un autre
+    /// format sont ignorés.
+    pub async fn next_card_number(&self, member_type: MemberType) -> Result<String, AppError> {
+        let settings = self.get_settings().await?;
+        let raw = match member_type {
+            MemberType::Communiant => &settings.card_pattern_communiant,
+            MemberType::Cathekomen => &settings.card_pattern_cathekomen,
+        };
+        let pattern = CardPattern::parse(raw)
+            .ok_or_else(|| AppError::validation("settings.card_pattern_invalid", &[raw]))?;
+        let cards: Vec<String> = sqlx::query_scalar("SELECT card_number FROM members")
+            .fetch_all(&self.pool)
+            .await?;
+        let last = cards.iter().filter_map(|c| pattern.number_of(c)).max().unwrap_or(0);
+        Ok(pattern.format(last + 1))
+    }
 
-        // Transaction : INSERT + refresh_year_total sont atomiques.
-        let mut tx = self.pool.begin().await?;
+    /// Numéro de carte déjà pris : message nommant le membre qui l'utilise.
+    async fn card_conflict(&self, card_number: &str) -> AppError {
+        let owner: Option<(String, Option<String>)> =
+            sqlx::query_as("SELECT full_name, deleted_at FROM members WHERE card_number = ?")
+                .bind(card_number)
+                .fetch_optional(&self.pool)
+                .await
+                .ok()
+                .flatten();
+        AppError::Conflict(match owner {
+            // Membre à la corbeille : le restaurer plutôt que d'en recréer un
+            Some((name, Some(_))) => format!(
+                "Efa an'i {name} (voafafa) ny laharana karatra '{card_number}'. \
+                 Avereno izy na mifidiana laharana hafa."
+            ),
+            Some((name, None)) => format!(
+                "Efa an'i {name} ny laharana karatra '{card_number}'. Mifidiana laharana hafa."
+            ),
+            None => "Ity laharana karatra ity dia efa misy. Mifidiana laharana hafa.".into(),
+        })
+    }
 
-        let row = sqlx::query(
-            "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
-             VALUES (?, ?, ?, ?, ?)
-             RETURNING id",
+    /// Met le membre à la corbeille : il disparaît des listes, mais ses cotisations
+    /// restent en base et dans les totaux annuels (l'argent a bien été reçu).
+    pub async fn delete_member(&self, id: i64) -> Result<(), AppError> {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let now = &now;
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let deleted = sqlx::query(
+                "UPDATE members SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL
+                 RETURNING card_number, full_name",
+            )
+            .bind(now)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(r) = deleted {
+                Self::audit_tx(&mut tx, "delete", "member", id, &serde_json::json!({
+                    "card_number": r.get::<String, _>("card_number"),
+                    "full_name":   r.get::<String, _>("full_name"),
+                }))
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, AppError>(())
+        })
+        .await
+    }
+
+    /// Membres à la corbeille, les plus récemment supprimés d'abord.
+    pub async fn get_deleted_members(&self) -> Result<Vec<DeletedMember>, AppError> {
+        let rows = sqlx::query(
+            "SELECT m.id, m.card_number, m.full_name, m.member_type, m.deleted_at,
+                    COUNT(c.id) AS contribution_count
+             FROM members m
+             LEFT JOIN contributions c ON c.member_id = m.id
+             WHERE m.deleted_at IS NOT NULL
+             GROUP BY m.id
+             ORDER BY m.deleted_at DESC, m.id DESC",
         )
-        .bind(input.member_id)
-        .bind(&input.payment_date)
-        .bind(&input.period)
-        .bind(amount.to_string())
-        .bind(recorded_year)
-        .fetch_one(&mut *tx)
+        .fetch_all(&self.pool)
         .await?;
 
-        Self::refresh_year_total_tx(&mut tx, recorded_year).await?;
+        Ok(rows
+            .iter()
+            .map(|r| DeletedMember {
+                id:                 r.get("id"),
+                card_number:        r.get("card_number"),
+                full_name:          r.get("full_name"),
+                member_type:        Self::read_member_type(r),
+                deleted_at:         r.get("deleted_at"),
+                contribution_count: r.get("contribution_count"),
+            })
+            .collect())
+    }
 
-        tx.commit().await?;
+    /// Sort un membre de la corbeille.
+    pub async fn restore_member(&self, id: i64) -> Result<Member, AppError> {
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let restored = sqlx::query(
+                "UPDATE members SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL
+                 RETURNING card_number, full_name",
+            )
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Membre #{id} absent de la corbeille.")))?;
+            Self::audit_tx(&mut tx, "restore", "member", id, &serde_json::json!({
+                "card_number": restored.get::<String, _>("card_number"),
+                "full_name":   restored.get::<String, _>("full_name"),
+            }))
+            .await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(())
+        })
+        .await?;
+        self.get_member(id).await
+    }
 
-        Ok(Contribution {
-            id:            row.get("id"),
-            member_id:     input.member_id,
-            payment_date:  input.payment_date,
-            period:        input.period,
-            amount,
-            recorded_year,
+    /// Efface définitivement un membre de la corbeille et ses cotisations (FK ON DELETE
+    /// CASCADE). Les totaux des années ouvertes sont recalculés ; ceux des années
+    /// clôturées restent figés. La fiche effacée est gardée au journal d'audit.
+    pub async fn purge_member(&self, id: i64) -> Result<(), AppError> {
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let row = sqlx::query("SELECT * FROM members WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Membre #{id} introuvable.")))?;
+            if row.get::<Option<String>, _>("deleted_at").is_none() {
+                return Err(AppError::Validation(
+                    "Seul un membre déjà supprimé peut être effacé définitivement.".into(),
+                ));
+            }
+            let contributions: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM contributions WHERE member_id = ?")
+                    .bind(id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            let years = Self::open_years_of_members_tx(&mut tx, &[id]).await?;
+            sqlx::query("DELETE FROM members WHERE id = ?").bind(id).execute(&mut *tx).await?;
+            Self::audit_tx(&mut tx, "purge", "member", id, &serde_json::json!({
+                "member":        Self::map_member(&row),
+                "contributions": contributions,
+            }))
+            .await?;
+            for year in years {
+                Self::refresh_year_total_tx(&mut tx, year).await?;
+            }
+            tx.commit().await?;
+            Ok::<_, AppError>(())
         })
+        .await
     }
 
-    pub async fn delete_contribution(&self, id: i64) -> Result<(), AppError> {
-        let mut tx = self.pool.begin().await?;
+    /// Fusionne le doublon `remove_id` dans `keep_id`, en une transaction : ses cotisations
+    /// sont réattribuées, l'adresse, le téléphone et le travail manquants de la fiche
+    /// conservée repris du doublon, puis le doublon est effacé. Les deux fiches doivent
+    /// être actives et de même type. Les totaux des années ouvertes concernées sont
+    /// recalculés et doivent rester identiques (sinon rien n'est écrit).
+    pub async fn merge_members(&self, keep_id: i64, remove_id: i64) -> Result<Member, AppError> {
+        if keep_id == remove_id {
+            return Err(AppError::validation("member.merge_same", &[]));
+        }
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let mut fetched = Vec::with_capacity(2);
+            for id in [keep_id, remove_id] {
+                let row = sqlx::query(
+                    "SELECT id, card_number, full_name, address, phone, job,
+                            gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at
+                     FROM members WHERE id = ? AND deleted_at IS NULL",
+                )
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Membre #{id} introuvable.")))?;
+                fetched.push(Self::map_member(&row));
+            }
+            let (keep, removed) = (&fetched[0], &fetched[1]);
+            if keep.member_type != removed.member_type {
+                return Err(AppError::validation("member.merge_type_mismatch", &[]));
+            }
 
-        let row = sqlx::query("SELECT recorded_year FROM contributions WHERE id = ?")
-            .bind(id)
-            .fetch_one(&mut *tx)
-            .await?;
-        let year: i32 = row.get("recorded_year");
+            // ── Totaux des années ouvertes avant la fusion ──
+            let years = Self::open_years_of_members_tx(&mut tx, &[keep_id, remove_id]).await?;
+            let mut expected: HashMap<i32, Decimal> = HashMap::new();
+            for year in &years {
+                let amounts: Vec<String> =
+                    sqlx::query_scalar("SELECT amount FROM contributions WHERE recorded_year = ?")
+                        .bind(year)
+                        .fetch_all(&mut *tx)
+                        .await?;
+                expected.insert(*year, amounts.iter().filter_map(|a| Decimal::from_str(a).ok()).sum());
+            }
 
-        sqlx::query("DELETE FROM contributions WHERE id = ?")
-            .bind(id)
+            let moved = sqlx::query("UPDATE contributions SET member_id = ? WHERE member_id = ?")
+                .bind(keep_id)
+                .bind(remove_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            sqlx::query("UPDATE member_notes SET member_id = ? WHERE member_id = ?")
+                .bind(keep_id)
+                .bind(remove_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                "UPDATE members
+                 SET address = COALESCE(NULLIF(TRIM(address), ''), ?),
+                     phone   = COALESCE(NULLIF(TRIM(phone), ''), ?),
+                     job     = COALESCE(NULLIF(TRIM(job), ''), ?)
+                 WHERE id = ?",
+            )
+            .bind(&removed.address)
+            .bind(&removed.phone)
+            .bind(&removed.job)
+            .bind(keep_id)
             .execute(&mut *tx)
             .await?;
+            sqlx::query("DELETE FROM members WHERE id = ?").bind(remove_id).execute(&mut *tx).await?;
+
+            for year in &years {
+                Self::refresh_year_total_tx(&mut tx, *year).await?;
+                let total: String = sqlx::query_scalar("SELECT total FROM year_summaries WHERE year = ?")
+                    .bind(year)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                if Decimal::from_str(&total).ok() != expected.get(year).copied() {
+                    return Err(AppError::Internal(format!(
+                        "Fusion annulée : le total {year} aurait changé ({total})."
+                    )));
+                }
+            }
+            Self::audit_tx(&mut tx, "merge", "member", keep_id, &serde_json::json!({
+                "removed_id":    remove_id,
+                "card_number":   removed.card_number,
+                "full_name":     removed.full_name,
+                "contributions": moved,
+            }))
+            .await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(())
+        })
+        .await?;
+        self.get_member(keep_id).await
+    }
 
-        Self::refresh_year_total_tx(&mut tx, year).await?;
+    /// Importe des membres depuis un CSV (voir `parse_member_csv`), en une seule transaction.
+    /// Chaque ligne est validée comme dans `create_member` ; une ligne invalide ou dont le
+    /// numéro de carte existe déjà est écartée sans interrompre l'import, et signalée avec
+    /// son numéro de ligne dans le rapport.
+    pub async fn import_members_csv(
+        &self,
+        csv: &str,
+        default_type: MemberType,
+    ) -> Result<ImportReport, AppError> {
+        let mut report = ImportReport::default();
+        let mut inputs = Vec::new();
+        for row in parse_member_csv(csv, default_type) {
+            let checked = row.input.and_then(|mut input| {
+                normalize_optional_phone(input.phone.as_deref())
+                    .and_then(|phone| {
+                        input.phone = phone;
+                        Self::normalize_member_name(&mut input);
+                        Self::validate_member_input(&input)
+                    })
+                    .map(|_| input)
+                    .map_err(|e| e.to_string())
+            });
+            match checked {
+                Ok(input) => inputs.push((row.line, input)),
+                Err(message) => report.errors.push(ImportRowError { line: row.line, message }),
+            }
+        }
 
-        tx.commit().await?;
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let (inputs, now) = (&inputs, &now);
+        let (inserted, duplicates) = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            // Carte → ligne qui l'a importée, pour distinguer un doublon interne au fichier
+            let mut seen: HashMap<&str, usize> = HashMap::new();
+            let mut duplicates = Vec::new();
+            for (line, input) in inputs {
+                let result = sqlx::query(
+                    "INSERT OR IGNORE INTO members
+                         (card_number, full_name, address, phone, job, gender, member_type,
+                          surname, given_name, status, birth_date, created_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(&input.card_number)
+                .bind(&input.full_name)
+                .bind(&input.address)
+                .bind(&input.phone)
+                .bind(&input.job)
+                .bind(input.gender.as_str())
+                .bind(input.member_type.as_str())
+                .bind(&input.surname)
+                .bind(&input.given_name)
+                .bind(input.status.as_str())
+                .bind(&input.birth_date)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+                if result.rows_affected() > 0 {
+                    seen.insert(&input.card_number, *line);
+                    continue;
+                }
+                let message = match seen.get(input.card_number.as_str()) {
+                    Some(first) => format!(
+                        "Numéro de carte '{}' en double : déjà importé à la ligne {first}.",
+                        input.card_number
+                    ),
+                    None => format!(
+                        "Numéro de carte '{}' déjà attribué à un membre existant.",
+                        input.card_number
+                    ),
+                };
+                duplicates.push(ImportRowError { line: *line, message });
+            }
+            tx.commit().await?;
+            Ok::<_, AppError>((seen.len(), duplicates))
+        })
+        .await?;
 
-        Ok(())
+        report.inserted = inserted;
+        report.errors.extend(duplicates);
+        report.errors.sort_by_key(|e| e.line);
+        report.skipped = report.errors.len();
+        Ok(report)
     }
 
-    // ── YearSummary ───────────────────────────────────────────────────────────
+    /// Transfère plusieurs membres vers un nouveau type (ex: "Cathekomen" → "Communiant")
+    /// et horodate `transferred_at`. Les contributions restent liées à leurs IDs — aucune
+    /// perte de données. Tout ou rien : un id inconnu ou à la corbeille annule le transfert.
+    /// Retourne les membres transférés, dans l'état enregistré.
+    pub async fn transfer_members(
+        &self,
+        ids: &[i64],
+        new_type: MemberType,
+    ) -> Result<Vec<Member>, AppError> {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-    pub async fn get_year_summaries(&self) -> Result<Vec<YearSummary>, AppError> {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let (ids, now) = (&ids, &now);
+        let rows = self.write(|| async move {
+            let mut qb: QueryBuilder<sqlx::Sqlite> =
+                QueryBuilder::new("UPDATE members SET member_type = ");
+            qb.push_bind(new_type.as_str());
+            qb.push(", transferred_at = ");
+            qb.push_bind(now);
+            qb.push(" WHERE deleted_at IS NULL AND id IN (");
+            let mut sep = qb.separated(", ");
+            for id in ids {
+                sep.push_bind(*id);
+            }
+            qb.push(
+                ") RETURNING id, card_number, full_name, address, phone, job,
+                             gender, member_type, surname, given_name, status, birth_date, created_at, transferred_at",
+            );
+
+            let mut tx = self.pool.begin().await?;
+            let rows = qb.build().fetch_all(&mut *tx).await?;
+            if rows.len() != ids.len() {
+                // Abandon de la transaction : aucun membre n'est transféré
+                let moved: HashSet<i64> = rows.iter().map(|r| r.get("id")).collect();
+                let missing = ids
+                    .iter()
+                    .filter(|id| !moved.contains(id))
+                    .map(i64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(AppError::Validation(format!(
+                    "Transfert annulé : membre(s) introuvable(s) ou supprimé(s) : {missing}."
+                ).into()));
+            }
+            for id in ids {
+                Self::audit_tx(&mut tx, "transfer", "member", *id, &serde_json::json!({
+                    "member_type": new_type.as_str(),
+                }))
+                .await?;
+            }
+            Self::refresh_open_years_of_members_tx(&mut tx, ids).await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(rows)
+        })
+        .await?;
+
+        let mut members: Vec<Member> = rows.iter().map(Self::map_member).collect();
+        members.sort_by_key(|m| m.id);
+        Ok(members)
+    }
+
+    // ── Notes membre ──────────────────────────────────────────────────────────
+
+    /// Ajoute une note au membre (actif ou à la corbeille). Texte sans espaces de
+    /// bord ; vide ou trop long refusé.
+    pub async fn add_member_note(&self, member_id: i64, text: String) -> Result<MemberNote, AppError> {
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return Err(AppError::validation("member.note_required", &[]));
+        }
+        if text.chars().count() > MAX_MEMBER_NOTE_LEN {
+            return Err(AppError::validation("member.note_too_long", &[&MAX_MEMBER_NOTE_LEN]));
+        }
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let (text, now) = (&text, &now);
+        // Insertion rejouée après `SQLITE_BUSY` : en transaction (voir `retry_busy`)
+        let id: Option<i64> = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let id = sqlx::query_scalar(
+                "INSERT INTO member_notes (member_id, created_at, text)
+                 SELECT id, ?, ? FROM members WHERE id = ?
+                 RETURNING id",
+            )
+            .bind(now)
+            .bind(text)
+            .bind(member_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(id)
+        })
+        .await?;
+        let id = id.ok_or_else(|| AppError::NotFound(format!("Membre #{member_id} introuvable.")))?;
+        Ok(MemberNote { id, member_id, created_at: now.clone(), text: text.clone() })
+    }
+
+    /// Notes du membre, les plus récentes d'abord.
+    pub async fn get_member_notes(&self, member_id: i64) -> Result<Vec<MemberNote>, AppError> {
         let rows = sqlx::query(
-            "SELECT year, total, closed_at, note
-             FROM year_summaries
-             ORDER BY year DESC",
+            "SELECT id, member_id, created_at, text FROM member_notes
+             WHERE member_id = ?
+             ORDER BY created_at DESC, id DESC",
         )
+        .bind(member_id)
         .fetch_all(&self.pool)
         .await?;
+        Ok(rows.iter().map(Self::map_member_note).collect())
+    }
 
-        Ok(rows.iter().map(Self::map_year_summary).collect())
+    fn map_member_note(r: &sqlx::sqlite::SqliteRow) -> MemberNote {
+        MemberNote {
+            id:         r.get("id"),
+            member_id:  r.get("member_id"),
+            created_at: r.get("created_at"),
+            text:       r.get("text"),
+        }
     }
 
-    pub async fn get_year_summary(&self, year: i32) -> Result<Option<YearSummary>, AppError> {
-        let row = sqlx::query(
-            "SELECT year, total, closed_at, note FROM year_summaries WHERE year = ?",
+    pub async fn delete_member_note(&self, id: i64) -> Result<(), AppError> {
+        // Une suppression rejouée hors transaction reviendrait en « introuvable »
+        let result = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let result = sqlx::query("DELETE FROM member_notes WHERE id = ?").bind(id).execute(&mut *tx).await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(result)
+        })
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Note #{id} introuvable.")));
+        }
+        Ok(())
+    }
+
+    // ── Contribution CRUD ─────────────────────────────────────────────────────
+
+    pub async fn get_contributions(&self, member_id: i64) -> Result<Vec<Contribution>, AppError> {
+        let rows = sqlx::query(
+            "SELECT id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                    recorded_by
+             FROM contributions
+             WHERE member_id = ?
+             ORDER BY payment_date DESC",
         )
-        .bind(year)
-        .fetch_optional(&self.pool)
+        .bind(member_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(row.as_ref().map(Self::map_year_summary))
+        Ok(rows.iter().map(Self::map_contribution).collect())
     }
 
-    /// Clôture une année : enregistre closed_at + note.
-    /// Tout est atomique : refresh_year_total + UPDATE closed_at + lecture finale.
-    pub async fn close_year(
-        &self,
-        year: i32,
-        note: Option<String>,
-    ) -> Result<YearSummary, AppError> {
-        let mut tx = self.pool.begin().await?;
+    /// Sous-totaux par année d'enregistrement des cotisations d'un membre, de la
+    /// plus récente à la plus ancienne. Erreur si le membre n'existe pas.
+    pub async fn get_member_year_totals(&self, member_id: i64) -> Result<Vec<MemberYearTotal>, AppError> {
+        self.get_member(member_id).await?;
+        let rows = sqlx::query("SELECT recorded_year, amount FROM contributions WHERE member_id = ?")
+            .bind(member_id)
+            .fetch_all(&self.pool)
+            .await?;
 
-        Self::refresh_year_total_tx(&mut tx, year).await?;
+        let mut years: BTreeMap<i32, (Decimal, i64)> = BTreeMap::new();
+        for r in &rows {
+            let Ok(amount) = Decimal::from_str(&r.get::<String, _>("amount")) else { continue };
+            let entry = years.entry(r.get("recorded_year")).or_default();
+            entry.0 += amount;
+            entry.1 += 1;
+        }
+        Ok(years
+            .into_iter()
+            .rev()
+            .map(|(year, (total, contribution_count))| MemberYearTotal { year, total, contribution_count })
+            .collect())
+    }
 
-        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
-        sqlx::query(
-            "UPDATE year_summaries SET closed_at = ?, note = ? WHERE year = ?",
+    /// Dernières cotisations d'un membre (formulaire d'édition : repérer un doublon).
+    pub async fn get_recent_contributions(
+        &self,
+        member_id: i64,
+        limit: i64,
+    ) -> Result<Vec<Contribution>, AppError> {
+        let rows = sqlx::query(
+            "SELECT id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                    recorded_by
+             FROM contributions
+             WHERE member_id = ?
+             ORDER BY payment_date DESC, id DESC
+             LIMIT ?",
         )
-        .bind(&now)
-        .bind(&note)
-        .bind(year)
-        .execute(&mut *tx)
+        .bind(member_id)
+        .bind(limit.clamp(1, MAX_PAGE_SIZE))
+        .fetch_all(&self.pool)
         .await?;
 
-        // Lire l'état final dans la même transaction
-        let row = sqlx::query(
-            "SELECT year, total, closed_at, note FROM year_summaries WHERE year = ?",
+        Ok(rows.iter().map(Self::map_contribution).collect())
+    }
+
+    pub async fn get_contributions_by_year(
+        &self,
+        year: i32,
+    ) -> Result<Vec<Contribution>, AppError> {
+        let rows = sqlx::query(
+            "SELECT id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                    recorded_by
+             FROM contributions
+             WHERE recorded_year = ?
+             ORDER BY payment_date DESC",
         )
         .bind(year)
-        .fetch_optional(&mut *tx)
+        .fetch_all(&self.pool)
         .await?;
 
-        tx.commit().await?;
-
-        row.as_ref()
-            .map(Self::map_year_summary)
-            .ok_or_else(|| AppError::Validation(format!("Résumé pour {year} introuvable.")))
+        Ok(rows.iter().map(Self::map_contribution).collect())
     }
 
-    /// Réouvre une année clôturée (supprime closed_at + note).
-    pub async fn reopen_year(&self, year: i32) -> Result<YearSummary, AppError> {
-        sqlx::query(
-            "UPDATE year_summaries SET closed_at = NULL, note = NULL WHERE year = ?",
+    /// Périodes déjà saisies pour une année (suggestions du formulaire), triées.
+    pub async fn get_distinct_periods(&self, year: i32) -> Result<Vec<String>, AppError> {
+        Ok(sqlx::query_scalar(
+            "SELECT DISTINCT period FROM contributions
+             WHERE recorded_year = ?
+             ORDER BY period",
         )
         .bind(year)
-        .execute(&self.pool)
-        .await?;
+        .fetch_all(&self.pool)
+        .await?)
+    }
 
-        self.get_year_summary(year)
-            .await?
-            .ok_or_else(|| AppError::Validation(format!("Résumé pour {year} introuvable.")))
+    /// Cotisations avec le nom du membre + résumé audit (JOIN), paginées (`limit` défaut
+    /// 50, max 500) et triées par date. `year` : `None` = toutes les années (recherche des
+    /// Archives). `name_filter` : nom du membre ou numéro de reçu, filtré dans le WHERE.
+    /// Les membres supprimés sont exclus de la liste, mais pas du total de l'année.
+    pub async fn get_contributions_by_year_with_member(
+        &self,
+        year: Option<i32>,
+        query: Option<&str>,
+        scope: ContributionSearchScope,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Page<ContributionWithMember>, AppError> {
+        use ContributionSearchScope as Scope;
+
+        let limit  = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let offset = offset.unwrap_or(0).max(0);
+        let query = query.map(str::trim).filter(|q| !q.is_empty());
+        let pattern = query.map(|q| format!("%{}%", Self::escape_like(q)));
+        // Montant : chiffres de la partie entière, comparés en préfixe
+        let amount_prefix = query
+            .filter(|_| matches!(scope, Scope::All | Scope::Amount))
+            .and_then(amount_search_digits)
+            .map(|digits| format!("{digits}%"));
+
+        // FROM … WHERE commun au comptage et à la page
+        let push_filtered = |qb: &mut QueryBuilder<'_, sqlx::Sqlite>| {
+            qb.push(
+                " FROM contributions c
+                 JOIN members m ON m.id = c.member_id
+                 WHERE m.deleted_at IS NULL",
+            );
+            if let Some(y) = year {
+                qb.push(" AND c.recorded_year = ");
+                qb.push_bind(y);
+            }
+            let Some(p) = &pattern else { return };
+            let mut columns: Vec<&str> = Vec::new();
+            if matches!(scope, Scope::All | Scope::Member) {
+                columns.extend(["m.full_name", "c.receipt_number"]);
+            }
+            if matches!(scope, Scope::All | Scope::Period) {
+                columns.extend(["c.period", "c.payment_date"]);
+            }
+            qb.push(" AND (0");
+            for col in columns {
+                qb.push(format!(" OR {col} LIKE "));
+                qb.push_bind(p.clone());
+                qb.push(" ESCAPE '\\'");
+            }
+            if let Some(prefix) = &amount_prefix {
+                qb.push(" OR substr(c.amount, 1, instr(c.amount || '.', '.') - 1) LIKE ");
+                qb.push_bind(prefix.clone());
+            }
+            qb.push(")");
+        };
+
+        let mut qb = QueryBuilder::new("SELECT COUNT(*)");
+        push_filtered(&mut qb);
+        let total_count: i64 = qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let mut qb = QueryBuilder::new(
+            "SELECT c.id, c.member_id, m.full_name AS member_name,
+                    c.payment_date, c.period, c.amount, c.recorded_year, c.receipt_number,
+                    c.recorded_by,
+                    (SELECT GROUP_CONCAT(summary, ' · ')
+                     FROM (SELECT CASE field
+                               WHEN 'amount' THEN old_value || ' Ar → ' || new_value || ' Ar'
+                               ELSE old_value || ' → ' || new_value
+                           END AS summary
+                           FROM contribution_audits
+                           WHERE contribution_id = c.id
+                           ORDER BY changed_at ASC)
+                    ) AS audit_summary",
+        );
+        push_filtered(&mut qb);
+        qb.push(" ORDER BY c.payment_date ASC, c.id ASC LIMIT ");
+        qb.push_bind(limit);
+        qb.push(" OFFSET ");
+        qb.push_bind(offset);
+        let rows = qb.build().fetch_all(&self.pool).await?;
+
+        let items = rows
+            .iter()
+            .map(|r| {
+                let amount_str: String = r.get("amount");
+                ContributionWithMember {
+                    id:            r.get("id"),
+                    member_id:     r.get("member_id"),
+                    member_name:   r.get("member_name"),
+                    payment_date:  r.get("payment_date"),
+                    period:        r.get("period"),
+                    amount:        Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO),
+                    recorded_year: r.get("recorded_year"),
+                    receipt_number: r.get("receipt_number"),
+                    recorded_by:   r.get("recorded_by"),
+                    audit_summary: r.get("audit_summary"),
+                }
+            })
+            .collect();
+
+        Ok(Page { items, total_count })
+    }
+
+    // ── PIN ───────────────────────────────────────────────────────────────────
+
+    /// Empreinte "sel$sha256(sel + pin)". Les anciennes empreintes sans sel
+    /// (sha256(pin) seul) restent acceptées et sont re-salées au prochain succès.
+    fn hash_pin(pin: &str, salt: &str) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(pin.as_bytes());
+        format!("{salt}${:x}", hasher.finalize())
+    }
+
+    fn pin_matches(pin: &str, stored: &str) -> bool {
+        match stored.split_once('$') {
+            Some((salt, _)) => Self::hash_pin(pin, salt) == stored,
+            None            => Self::hash_pin(pin, "").trim_start_matches('$') == stored,
+        }
+    }
+
+    /// PIN valide : `PIN_MIN_LEN` à `PIN_MAX_LEN` chiffres.
+    fn validate_pin_format(pin: &str) -> Result<(), AppError> {
+        if !(PIN_MIN_LEN..=PIN_MAX_LEN).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AppError::validation("pin.format", &[&PIN_MIN_LEN, &PIN_MAX_LEN]));
+        }
+        Ok(())
+    }
+
+    /// Enregistre l'empreinte d'un nouveau PIN (sel neuf) et invalide les jetons en cours.
+    async fn store_pin(&self, pin: &str) -> Result<(), AppError> {
+        let salt = uuid::Uuid::new_v4().simple().to_string();
+        self.set_setting("admin_pin", &Self::hash_pin(pin, &salt)).await?;
+        self.pin_tokens.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        Ok(())
+    }
+
+    /// Définit le code PIN admin (une seule fois). Erreur si déjà défini :
+    /// le remplacer passe par `change_pin`, qui exige l'ancien.
+    pub async fn set_pin(&self, pin: &str) -> Result<(), AppError> {
+        Self::validate_pin_format(pin)?;
+        if self.has_pin().await? {
+            return Err(AppError::validation("pin.already_set", &[]));
+        }
+        self.store_pin(pin).await
+    }
+
+    /// Remplace le PIN après vérification de l'ancien (compte dans les échecs
+    /// et le verrouillage comme `verify_pin`).
+    pub async fn change_pin(&self, old_pin: &str, new_pin: &str) -> Result<(), AppError> {
+        Self::validate_pin_format(new_pin)?;
+        self.check_pin_at(old_pin, chrono::Utc::now().timestamp()).await?;
+        self.store_pin(new_pin).await
+    }
+
+    /// Supprime le PIN après vérification : les actions destructrices redeviennent libres.
+    pub async fn remove_pin(&self, old_pin: &str) -> Result<(), AppError> {
+        self.check_pin_at(old_pin, chrono::Utc::now().timestamp()).await?;
+        self.write(|| {
+            sqlx::query("DELETE FROM settings WHERE key LIKE 'admin_pin%'").execute(&self.pool)
+        })
+        .await?;
+        self.pin_tokens.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        Ok(())
+    }
+
+    /// Un code PIN protège-t-il les actions destructrices ?
+    pub async fn has_pin(&self) -> Result<bool, AppError> {
+        Ok(self.get_setting("admin_pin").await?.is_some())
+    }
+
+    /// Vérifie le code PIN et délivre un jeton valable `PIN_TOKEN_TTL_SECS`,
+    /// exigé par les commandes destructrices (`require_pin_token`).
+    pub async fn verify_pin(&self, pin: &str) -> Result<String, AppError> {
+        self.verify_pin_at(pin, chrono::Utc::now().timestamp()).await
+    }
+
+    /// Comme `verify_pin`, avec l'heure (secondes Unix) injectée (tests).
+    pub(crate) async fn verify_pin_at(&self, pin: &str, now: i64) -> Result<String, AppError> {
+        self.check_pin_at(pin, now).await?;
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let mut tokens = self.pin_tokens.lock().unwrap_or_else(|e| e.into_inner());
+        tokens.retain(|_, expires| *expires > now);
+        tokens.insert(token.clone(), now + PIN_TOKEN_TTL_SECS);
+        Ok(token)
+    }
+
+    /// Compare le PIN saisi ; après `PIN_MAX_ATTEMPTS` échecs consécutifs,
+    /// toute tentative est refusée pendant `PIN_LOCKOUT_SECS` (horodatage en base).
+    /// Lecture et mise à jour des compteurs en une transaction sous le verrou
+    /// d'écriture : deux saisies simultanées ne comptent pas le même essai.
+    async fn check_pin_at(&self, pin: &str, now: i64) -> Result<(), AppError> {
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let state: HashMap<String, String> =
+                sqlx::query_as("SELECT key, value FROM settings WHERE key LIKE 'admin_pin%'")
+                    .fetch_all(&mut *tx)
+                    .await?
+                    .into_iter()
+                    .collect();
+            let get_i64 = |key: &str| state.get(key).and_then(|v| v.parse::<i64>().ok()).unwrap_or(0);
+            let stored = state
+                .get("admin_pin")
+                .ok_or_else(|| AppError::validation("pin.not_set", &[]))?;
+
+            let locked_until = get_i64("admin_pin_locked_until");
+            if now < locked_until {
+                let minutes = (locked_until - now + 59) / 60;
+                return Err(AppError::validation("pin.locked", &[&minutes]));
+            }
+            let attempts = get_i64("admin_pin_attempts");
+
+            let mut updates: Vec<(&str, String)> = Vec::new();
+            let outcome = if Self::pin_matches(pin, stored) {
+                if attempts > 0 {
+                    updates.push(("admin_pin_attempts", "0".into()));
+                }
+                if !stored.contains('$') {
+                    let salt = uuid::Uuid::new_v4().simple().to_string();
+                    updates.push(("admin_pin", Self::hash_pin(pin, &salt)));
+                }
+                Ok(())
+            } else if attempts + 1 >= PIN_MAX_ATTEMPTS {
+                updates.push(("admin_pin_attempts", "0".into()));
+                updates.push(("admin_pin_locked_until", (now + PIN_LOCKOUT_SECS).to_string()));
+                Err(AppError::validation("pin.locked_now", &[&(PIN_LOCKOUT_SECS / 60)]))
+            } else {
+                updates.push(("admin_pin_attempts", (attempts + 1).to_string()));
+                Err(AppError::validation("pin.wrong", &[&(PIN_MAX_ATTEMPTS - attempts - 1)]))
+            };
+
+            for (key, value) in updates {
+                sqlx::query(UPSERT_SETTING_SQL)
+                    .bind(key)
+                    .bind(value)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, AppError>(outcome)
+        })
+        .await?
+    }
+
+    /// Autorise une action destructrice : sans PIN configuré tout passe, sinon
+    /// il faut un jeton non expiré délivré par `verify_pin`.
+    pub async fn require_pin_token(&self, token: Option<&str>) -> Result<(), AppError> {
+        self.require_pin_token_at(token, chrono::Utc::now().timestamp()).await
+    }
+
+    pub(crate) async fn require_pin_token_at(
+        &self,
+        token: Option<&str>,
+        now: i64,
+    ) -> Result<(), AppError> {
+        if !self.has_pin().await? {
+            return Ok(());
+        }
+        let valid = token.is_some_and(|t| {
+            let tokens = self.pin_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            tokens.get(t).is_some_and(|expires| *expires > now)
+        });
+        if valid {
+            Ok(())
+        } else {
+            Err(AppError::validation("pin.required", &[]))
+        }
+    }
+
+    /// Modifie une contribution existante après vérification du PIN.
+    /// Crée une entrée d'audit pour chaque champ modifié.
+    /// Interdit si l'année est clôturée.
+    pub async fn update_contribution(
+        &self,
+        id: i64,
+        input: crate::db::ContributionEditInput,
+    ) -> Result<Contribution, AppError> {
+        // 1. Vérifier le PIN (avec verrouillage après trop d'échecs)
+        self.check_pin_at(&input.pin, chrono::Utc::now().timestamp()).await?;
+        // 2. Valider période, montant et date (mêmes règles que la création)
+        let rules = self.period_rules(input.allow_mismatch).await?;
+        let (new_amount, new_recorded_year, new_period) = Self::validate_contribution_fields(
+            &input.payment_date,
+            &input.period,
+            &input.amount,
+            rules,
+        )?;
+        // Une cotisation existante ne tombe pas à zéro : la supprimer plutôt
+        if new_amount.is_zero() {
+            return Err(AppError::validation("amount.zero", &[]));
+        }
+
+        let (input, new_period) = (&input, &new_period);
+        let (member_id, receipt_number, recorded_by) = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            // 3. Récupérer l'ancienne contribution
+            let old_row = sqlx::query(
+                "SELECT member_id, payment_date, period, amount, recorded_year, receipt_number,
+                        recorded_by
+                 FROM contributions WHERE id = ?",
+            )
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            let old_year: i32        = old_row.get("recorded_year");
+            let old_amount_str: String = old_row.get("amount");
+            let old_amount = Decimal::from_str(&old_amount_str).unwrap_or(Decimal::ZERO);
+            let old_period: String   = old_row.get("period");
+            let old_date: String     = old_row.get("payment_date");
+            let member_id: i64       = old_row.get("member_id");
+            let receipt_number: String = old_row.get("receipt_number");
+            let recorded_by: Option<String> = old_row.get("recorded_by");
+
+            // 4. Vérifier que ni l'ancienne ni la nouvelle année ne sont clôturées
+            Self::ensure_year_open_tx(&mut tx, old_year).await?;
+            if new_recorded_year != old_year {
+                Self::ensure_year_open_tx(&mut tx, new_recorded_year).await?;
+            }
+
+            // 5. Mettre à jour la contribution
+            sqlx::query(
+                "UPDATE contributions
+                 SET payment_date = ?, period = ?, amount = ?, recorded_year = ?
+                 WHERE id = ?",
+            )
+            .bind(&input.payment_date)
+            .bind(new_period)
+            .bind(new_amount.to_string())
+            .bind(new_recorded_year)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+            // 6. Recalculer les totaux annuels
+            Self::refresh_year_total_tx(&mut tx, old_year).await?;
+            if new_recorded_year != old_year {
+                Self::refresh_year_total_tx(&mut tx, new_recorded_year).await?;
+            }
+
+            // 7. Créer les entrées d'audit pour les champs modifiés
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+            let reason_opt: Option<String> = if input.reason.trim().is_empty() {
+                None
+            } else {
+                Some(input.reason.trim().to_string())
+            };
+
+            for (field, old_val, new_val) in [
+                ("amount",       old_amount.to_string(), new_amount.to_string()),
+                ("period",       old_period.clone(),     new_period.clone()),
+                ("payment_date", old_date.clone(),        input.payment_date.clone()),
+            ] {
+                if old_val != new_val {
+                    sqlx::query(
+                        "INSERT INTO contribution_audits
+                             (contribution_id, field, old_value, new_value, changed_at, reason)
+                         VALUES (?, ?, ?, ?, ?, ?)",
+                    )
+                    .bind(id)
+                    .bind(field)
+                    .bind(&old_val)
+                    .bind(&new_val)
+                    .bind(&now)
+                    .bind(&reason_opt)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+
+            tx.commit().await?;
+            Ok::<_, AppError>((member_id, receipt_number, recorded_by))
+        })
+        .await?;
+
+        Ok(Contribution {
+            id,
+            member_id,
+            payment_date:  input.payment_date.clone(),
+            period:        new_period.clone(),
+            amount:        new_amount,
+            recorded_year: new_recorded_year,
+            receipt_number,
+            recorded_by,
+        })
+    }
+
+    /// Vérifie si l'exercice précédent est déjà clôturé.
+    /// Si non → calcule le total ; pendant les `grace_days` premiers jours de l'exercice,
+    /// retourne `PendingUserAction` (saisies de décembre encore possibles), ensuite
+    /// génère une note et clôture automatiquement. `grace_days = Some(0)` clôture
+    /// immédiatement ; `None` suit les paramètres `auto_close_enabled` et
+    /// `auto_close_grace_days` (clôture automatique désactivée → `Disabled`).
+    pub async fn check_and_close_previous_year(
+        &self,
+        grace_days: Option<u32>,
+    ) -> Result<YearCloseStatus, AppError> {
+        self.check_and_close_previous_year_on(chrono::Local::now().date_naive(), grace_days)
+            .await
+    }
+
+    /// Comme `check_and_close_previous_year`, avec la date du jour injectée (tests).
+    pub(crate) async fn check_and_close_previous_year_on(
+        &self,
+        today: NaiveDate,
+        grace_days: Option<u32>,
+    ) -> Result<YearCloseStatus, AppError> {
+        let settings = self.get_settings().await?;
+        let current_year = fiscal_year_of(today, settings.fiscal_year_start_month);
+        let prev_year = current_year - 1;
+
+        // Déjà clôturé → rien à faire
+        if let Some(existing) = self.get_year_summary(prev_year).await? {
+            if existing.closed_at.is_some() {
+                return Ok(YearCloseStatus::AlreadyClosed);
+            }
+        }
+
+        // S'assurer que le résumé existe (même à 0) + recalculer le total, relu
+        // dans la même transaction qu'une saisie concurrente ne peut pas couper
+        let row = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            Self::refresh_year_total_tx(&mut tx, prev_year).await?;
+            let row = sqlx::query(&format!(
+                "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries WHERE year = ?"
+            ))
+            .bind(prev_year)
+            .fetch_optional(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(row)
+        })
+        .await?;
+        let summary = row.as_ref().map(Self::map_year_summary);
+        let total = summary.as_ref().map(|s| s.total).unwrap_or(Decimal::ZERO);
+
+        // Délai demandé explicitement, sinon celui des paramètres
+        let grace_days = match grace_days {
+            Some(days) => days,
+            None if !settings.auto_close_enabled => {
+                return Ok(YearCloseStatus::Disabled { year: prev_year, total });
+            }
+            None => settings.auto_close_grace_days,
+        };
+
+        // Début de l'exercice + grace_days : avec 15, clôture automatique à partir du 16 janvier
+        let auto_close_on = fiscal_year_start(current_year, settings.fiscal_year_start_month)
+            .and_then(|d| d.checked_add_days(chrono::Days::new(u64::from(grace_days))))
+            .ok_or_else(|| AppError::Validation("Délai de grâce invalide.".into()))?;
+        if today < auto_close_on {
+            return Ok(YearCloseStatus::PendingUserAction {
+                year: prev_year,
+                total,
+                auto_close_on: auto_close_on.format("%Y-%m-%d").to_string(),
+            });
+        }
+
+        let note = summary.as_ref().map(Self::default_close_note);
+        let summary = self.close_year(prev_year, note).await?;
+        Ok(YearCloseStatus::Closed { summary })
+    }
+
+    /// Aperçu de la clôture de `year`, sans rien écrire : total qui serait figé et
+    /// note proposée, à modifier avant `close_year`. Année déjà clôturée : total et
+    /// note enregistrés, `already_closed` à vrai.
+    pub async fn preview_year_close(&self, year: i32) -> Result<YearClosePreview, AppError> {
+        if let Some(s) = self.get_year_summary(year).await? {
+            if s.closed_at.is_some() {
+                return Ok(YearClosePreview {
+                    year,
+                    total:          s.total,
+                    default_note:   s.note.unwrap_or_default(),
+                    already_closed: true,
+                });
+            }
+        }
+        let rows = sqlx::query(YEAR_REFRESH_SQL).bind(year).fetch_all(&self.pool).await?;
+        let summary = Self::sum_year_rows(year, &rows);
+        Ok(YearClosePreview {
+            year,
+            total:          summary.total,
+            default_note:   Self::default_close_note(&summary),
+            already_closed: false,
+        })
+    }
+
+    pub async fn create_contribution(
+        &self,
+        input: ContributionInput,
+    ) -> Result<Contribution, AppError> {
+        let rules = self.period_rules(input.allow_mismatch).await?;
+        let (amount, recorded_year, period) = Self::validate_contribution_fields(
+            &input.payment_date,
+            &input.period,
+            &input.amount,
+            rules,
+        )?;
+        let recorded_by = Self::operator_name(input.recorded_by.as_deref());
+
+        // Transaction : numéro de reçu + INSERT + refresh_year_total sont atomiques ;
+        // deux saisies concurrentes ne peuvent pas obtenir le même numéro.
+        let (payment_date, period_ref, recorded_by_ref) =
+            (&input.payment_date, &period, &recorded_by);
+        let (id, receipt_number) = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            // Membre dans la corbeille : refusé, comme en saisie en série
+            let active: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM members WHERE id = ? AND deleted_at IS NULL)",
+            )
+            .bind(input.member_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            if !active {
+                return Err(AppError::validation("contribution.member_missing", &[&input.member_id]));
+            }
+
+            let receipt_number = Self::next_receipt_number_tx(&mut tx, recorded_year).await?;
+            let id: i64 = sqlx::query_scalar(
+                "INSERT INTO contributions
+                     (member_id, payment_date, period, amount, recorded_year, receipt_number,
+                      recorded_by)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)
+                 RETURNING id",
+            )
+            .bind(input.member_id)
+            .bind(payment_date)
+            .bind(period_ref)
+            .bind(amount.to_string())
+            .bind(recorded_year)
+            .bind(&receipt_number)
+            .bind(recorded_by_ref)
+            .fetch_one(&mut *tx)
+            .await?;
+            Self::audit_tx(&mut tx, "create", "contribution", id, &serde_json::json!({
+                "member_id":      input.member_id,
+                "receipt_number": receipt_number,
+                "amount":         amount.to_string(),
+                "period":         period_ref,
+            }))
+            .await?;
+
+            Self::refresh_year_total_tx(&mut tx, recorded_year).await?;
+
+            tx.commit().await?;
+            Ok::<_, AppError>((id, receipt_number))
+        })
+        .await?;
+
+        Ok(Contribution {
+            id,
+            member_id:     input.member_id,
+            payment_date:  input.payment_date,
+            period,
+            amount,
+            recorded_year,
+            receipt_number,
+            recorded_by,
+        })
+    }
+
+    /// Saisie en série (quête du dimanche) : toutes les lignes sont validées avant
+    /// toute écriture, puis insérées dans une seule transaction avec un recalcul par
+    /// année touchée. Tout ou rien : une ligne refusée (erreur préfixée de son
+    /// numéro, à partir de 1) n'enregistre aucune des autres.
+    pub async fn create_contributions_batch(
+        &self,
+        inputs: Vec<ContributionInput>,
+    ) -> Result<Vec<Contribution>, AppError> {
+        if inputs.len() > MAX_CONTRIBUTION_BATCH {
+            return Err(AppError::validation("contribution.batch_too_large", &[&MAX_CONTRIBUTION_BATCH]));
+        }
+        let rules = self.period_rules(false).await?;
+        let mut rows = Vec::with_capacity(inputs.len());
+        for (i, input) in inputs.into_iter().enumerate() {
+            let rules = PeriodRules { allow_mismatch: input.allow_mismatch, ..rules };
+            let (amount, recorded_year, period) = Self::validate_contribution_fields(
+                &input.payment_date,
+                &input.period,
+                &input.amount,
+                rules,
+            )
+            .map_err(|e| Self::batch_row_error(i, e))?;
+            // `id` et `receipt_number` sont attribués à l'insertion
+            rows.push(Contribution {
+                id:             0,
+                member_id:      input.member_id,
+                payment_date:   input.payment_date,
+                period,
+                amount,
+                recorded_year,
+                receipt_number: String::new(),
+                recorded_by:    Self::operator_name(input.recorded_by.as_deref()),
+            });
+        }
+        if rows.is_empty() {
+            return Ok(rows);
+        }
+
+        let rows_ref = &rows;
+        let saved = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            for (i, c) in rows_ref.iter().enumerate() {
+                let active: bool = sqlx::query_scalar(
+                    "SELECT EXISTS (SELECT 1 FROM members WHERE id = ? AND deleted_at IS NULL)",
+                )
+                .bind(c.member_id)
+                .fetch_one(&mut *tx)
+                .await?;
+                if !active {
+                    let e = AppError::validation("contribution.member_missing", &[&c.member_id]);
+                    return Err(Self::batch_row_error(i, e));
+                }
+            }
+
+            let mut saved = Vec::with_capacity(rows_ref.len());
+            for c in rows_ref {
+                let receipt_number = Self::next_receipt_number_tx(&mut tx, c.recorded_year).await?;
+                let id: i64 = sqlx::query_scalar(
+                    "INSERT INTO contributions
+                         (member_id, payment_date, period, amount, recorded_year, receipt_number,
+                          recorded_by)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)
+                     RETURNING id",
+                )
+                .bind(c.member_id)
+                .bind(&c.payment_date)
+                .bind(&c.period)
+                .bind(c.amount.to_string())
+                .bind(c.recorded_year)
+                .bind(&receipt_number)
+                .bind(&c.recorded_by)
+                .fetch_one(&mut *tx)
+                .await?;
+                Self::audit_tx(&mut tx, "create", "contribution", id, &serde_json::json!({
+                    "member_id":      c.member_id,
+                    "receipt_number": receipt_number,
+                    "amount":         c.amount.to_string(),
+                    "period":         c.period,
+                }))
+                .await?;
+                saved.push(Contribution { id, receipt_number, ..c.clone() });
+            }
+
+            let years: BTreeSet<i32> = rows_ref.iter().map(|c| c.recorded_year).collect();
+            for year in years {
+                Self::refresh_year_total_tx(&mut tx, year).await?;
+            }
+            tx.commit().await?;
+            Ok::<_, AppError>(saved)
+        })
+        .await?;
+        Ok(saved)
+    }
+
+    /// Erreur d'une ligne de `create_contributions_batch` : « Ligne 3 : … ».
+    fn batch_row_error(index: usize, e: AppError) -> AppError {
+        match e {
+            AppError::Validation(m) => AppError::validation(
+                "contribution.batch_row",
+                &[&(index + 1), &m.text(i18n::current())],
+            ),
+            other => other,
+        }
+    }
+
+    /// Nom d'opérateur nettoyé ; vide → `None`.
+    fn operator_name(raw: Option<&str>) -> Option<String> {
+        raw.map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    }
+
+    /// Prochain numéro de reçu de `year` ("2025-00124") : plus grande séquence déjà
+    /// attribuée pour cette année + 1. À appeler dans la transaction de l'INSERT.
+    async fn next_receipt_number_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        year: i32,
+    ) -> Result<String, AppError> {
+        let last: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(CAST(substr(receipt_number, instr(receipt_number, '-') + 1) AS INTEGER))
+             FROM contributions
+             WHERE receipt_number LIKE ? || '-%'",
+        )
+        .bind(year.to_string())
+        .fetch_one(&mut **tx)
+        .await?;
+        Ok(format!("{year}-{:05}", last.unwrap_or(0) + 1))
+    }
+
+    /// Cotisation correspondant à un reçu papier ("2025-00123"), avec le nom du membre.
+    pub async fn get_contribution_by_receipt(
+        &self,
+        receipt: &str,
+    ) -> Result<ContributionWithMember, AppError> {
+        let receipt = receipt.trim();
+        let row = sqlx::query(
+            "SELECT c.id, c.member_id, m.full_name AS member_name,
+                    c.payment_date, c.period, c.amount, c.recorded_year, c.receipt_number,
+                    c.recorded_by
+             FROM contributions c
+             JOIN members m ON m.id = c.member_id
+             WHERE c.receipt_number = ?",
+        )
+        .bind(receipt)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Reçu '{receipt}' introuvable.")))?;
+
+        let amount_str: String = row.get("amount");
+        Ok(ContributionWithMember {
+            id:            row.get("id"),
+            member_id:     row.get("member_id"),
+            member_name:   row.get("member_name"),
+            payment_date:  row.get("payment_date"),
+            period:        row.get("period"),
+            amount:        Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO),
+            recorded_year: row.get("recorded_year"),
+            receipt_number: row.get("receipt_number"),
+            recorded_by:   row.get("recorded_by"),
+            audit_summary: None,
+        })
+    }
+
+    pub async fn delete_contribution(&self, id: i64) -> Result<(), AppError> {
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            let row = sqlx::query(
+                "SELECT member_id, payment_date, recorded_year, receipt_number, amount, period,
+                        recorded_by
+                 FROM contributions WHERE id = ?",
+            )
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+            let year: i32 = row.get("recorded_year");
+
+            sqlx::query("DELETE FROM contributions WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            // Instantané complet : seule source de `restore_contribution`
+            Self::audit_tx(&mut tx, "delete", "contribution", id, &serde_json::json!({
+                "member_id":      row.get::<i64, _>("member_id"),
+                "payment_date":   row.get::<String, _>("payment_date"),
+                "receipt_number": row.get::<String, _>("receipt_number"),
+                "amount":         row.get::<String, _>("amount"),
+                "period":         row.get::<String, _>("period"),
+                "recorded_by":    row.get::<Option<String>, _>("recorded_by"),
+            }))
+            .await?;
+
+            Self::refresh_year_total_tx(&mut tx, year).await?;
+
+            tx.commit().await?;
+            Ok::<_, AppError>(())
+        })
+        .await
+    }
+
+    /// Annule une suppression : réinsère la contribution `id` d'après l'instantané que
+    /// `delete_contribution` a laissé dans le journal d'audit, sous son ancien id s'il
+    /// est libre (sinon un nouvel id), et recalcule le total de l'année. Seule une
+    /// suppression pas encore annulée est acceptée ; la ligne est revalidée comme une
+    /// saisie et son année doit être ouverte. L'historique des modifications, supprimé
+    /// avec la contribution, n'est pas restauré.
+    pub async fn restore_contribution(&self, id: i64) -> Result<Contribution, AppError> {
+        // Période d'une autre année admise : l'écart a pu être confirmé à la saisie
+        let rules = self.period_rules(true).await?;
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            let last: Option<(String, Option<String>)> = sqlx::query_as(
+                "SELECT action, details FROM audit_log
+                 WHERE entity = 'contribution' AND entity_id = ? AND action IN ('delete', 'restore')
+                 ORDER BY id DESC LIMIT 1",
+            )
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            let deleted = match last {
+                Some((action, Some(details))) if action == "delete" => {
+                    serde_json::from_str::<DeletedContribution>(&details).ok()
+                }
+                _ => None,
+            }
+            .ok_or_else(|| AppError::validation("contribution.not_restorable", &[&id]))?;
+            let (amount, recorded_year, period) = Self::validate_contribution_fields(
+                &deleted.payment_date,
+                &deleted.period,
+                &deleted.amount,
+                rules,
+            )?;
+
+            let member: Option<i64> =
+                sqlx::query_scalar("SELECT id FROM members WHERE id = ? AND deleted_at IS NULL")
+                    .bind(deleted.member_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if member.is_none() {
+                return Err(AppError::NotFound(format!("Membre {} introuvable.", deleted.member_id)));
+            }
+            Self::ensure_year_open_tx(&mut tx, recorded_year).await?;
+            let taken: Option<i64> = sqlx::query_scalar("SELECT id FROM contributions WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            // Reçu déjà réattribué entre-temps : un nouveau numéro plutôt qu'un doublon
+            let receipt_taken: Option<i64> =
+                sqlx::query_scalar("SELECT id FROM contributions WHERE receipt_number = ?")
+                    .bind(&deleted.receipt_number)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let receipt_number = if receipt_taken.is_none() && !deleted.receipt_number.is_empty() {
+                deleted.receipt_number.clone()
+            } else {
+                Self::next_receipt_number_tx(&mut tx, recorded_year).await?
+            };
+
+            // id NULL : SQLite en attribue un nouveau
+            let new_id: i64 = sqlx::query_scalar(
+                "INSERT INTO contributions
+                     (id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                      recorded_by)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                 RETURNING id",
+            )
+            .bind(taken.is_none().then_some(id))
+            .bind(deleted.member_id)
+            .bind(&deleted.payment_date)
+            .bind(&period)
+            .bind(amount.to_string())
+            .bind(recorded_year)
+            .bind(&receipt_number)
+            .bind(&deleted.recorded_by)
+            .fetch_one(&mut *tx)
+            .await?;
+            // Sous l'id supprimé : une seconde annulation de la même suppression est refusée
+            Self::audit_tx(&mut tx, "restore", "contribution", id, &serde_json::json!({
+                "restored_id":    new_id,
+                "member_id":      deleted.member_id,
+                "receipt_number": receipt_number,
+                "amount":         amount.to_string(),
+                "period":         period,
+            }))
+            .await?;
+
+            Self::refresh_year_total_tx(&mut tx, recorded_year).await?;
+
+            tx.commit().await?;
+            Ok::<_, AppError>(Contribution {
+                id:            new_id,
+                member_id:     deleted.member_id,
+                payment_date:  deleted.payment_date,
+                period,
+                amount,
+                recorded_year,
+                receipt_number,
+                recorded_by:   deleted.recorded_by,
+            })
+        })
+        .await
+    }
+
+    // ── YearSummary ───────────────────────────────────────────────────────────
+
+    pub async fn get_year_summaries(&self) -> Result<Vec<YearSummary>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries ORDER BY year DESC"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::map_year_summary).collect())
+    }
+
+    /// Années ayant au moins une cotisation ou une clôture, de la plus récente à la
+    /// plus ancienne. Un résumé vide (année future créée par erreur) n'y figure pas.
+    pub async fn get_years_with_contributions(&self) -> Result<Vec<i32>, AppError> {
+        Ok(sqlx::query_scalar(
+            "SELECT DISTINCT recorded_year FROM contributions
+             UNION
+             SELECT year FROM year_summaries WHERE closed_at IS NOT NULL
+             ORDER BY 1 DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Supprime le résumé d'une année sans aucune donnée : ni cotisation, ni
+    /// clôture, ni note. Sans effet si le résumé n'existe pas.
+    pub async fn delete_empty_year_summary(&self, year: i32) -> Result<(), AppError> {
+        self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let has_data: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM contributions WHERE recorded_year = ?1)
+                     OR EXISTS (SELECT 1 FROM year_summaries
+                                WHERE year = ?1 AND (closed_at IS NOT NULL OR note IS NOT NULL))",
+            )
+            .bind(year)
+            .fetch_one(&mut *tx)
+            .await?;
+            if has_data {
+                return Err(AppError::validation("year.not_empty", &[&year]));
+            }
+            sqlx::query("DELETE FROM year_summaries WHERE year = ?")
+                .bind(year)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn get_year_summary(&self, year: i32) -> Result<Option<YearSummary>, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries WHERE year = ?"
+        ))
+        .bind(year)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(Self::map_year_summary))
+    }
+
+    /// Rassemble le résumé, les totaux par membre et toutes les cotisations d'une année
+    /// pour le rapport d'archive imprimable.
+    pub async fn get_year_report(&self, year: i32) -> Result<YearReport, AppError> {
+        let summary = self
+            .get_year_summary(year)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Aucune cotisation pour l'année {year}.")))?;
+
+        let rows = sqlx::query(
+            "SELECT c.id, c.member_id, m.full_name AS member_name, m.card_number, m.member_type,
+                    c.payment_date, c.period, c.amount, c.recorded_year, c.receipt_number,
+                    c.recorded_by
+             FROM contributions c
+             JOIN members m ON m.id = c.member_id
+             WHERE c.recorded_year = ?
+             ORDER BY c.payment_date ASC, c.id ASC",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut members: HashMap<i64, YearMemberTotal> = HashMap::new();
+        let mut contributions = Vec::with_capacity(rows.len());
+        for r in &rows {
+            let amount_str: String = r.get("amount");
+            let amount = Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO);
+            let member_id: i64 = r.get("member_id");
+            let entry = members.entry(member_id).or_insert_with(|| YearMemberTotal {
+                member_id,
+                card_number: r.get("card_number"),
+                full_name:   r.get("member_name"),
+                member_type: Self::read_member_type(r),
+                count:       0,
+                total:       Decimal::ZERO,
+            });
+            entry.count += 1;
+            entry.total += amount;
+            contributions.push(ContributionWithMember {
+                id:            r.get("id"),
+                member_id,
+                member_name:   r.get("member_name"),
+                payment_date:  r.get("payment_date"),
+                period:        r.get("period"),
+                amount,
+                recorded_year: r.get("recorded_year"),
+                receipt_number: r.get("receipt_number"),
+                recorded_by:   r.get("recorded_by"),
+                audit_summary: None,
+            });
+        }
+
+        let mut members: Vec<YearMemberTotal> = members.into_values().collect();
+        // Communiants d'abord, puis Cathekomens — chacun par ordre alphabétique
+        members.sort_by_cached_key(|m| {
+            (m.member_type != MemberType::Communiant, m.full_name.to_lowercase())
+        });
+
+        Ok(YearReport { summary, members, contributions })
+    }
+
+    /// Relevé d'un membre pour une année d'enregistrement : cotisations par date de
+    /// paiement et total. Une année sans cotisation donne un relevé vide à 0.
+    pub async fn get_member_statement(
+        &self,
+        member_id: i64,
+        year: i32,
+    ) -> Result<MemberStatement, AppError> {
+        let member = self.get_member(member_id).await?;
+        let rows = sqlx::query(
+            "SELECT id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                    recorded_by
+             FROM contributions
+             WHERE member_id = ? AND recorded_year = ?
+             ORDER BY payment_date ASC, id ASC",
+        )
+        .bind(member_id)
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let contributions: Vec<Contribution> = rows.iter().map(Self::map_contribution).collect();
+        let total = contributions.iter().map(|c| c.amount).sum();
+        Ok(MemberStatement { member, year, contributions, total })
+    }
+
+    /// Clôture une année : enregistre closed_at + note (vide → NULL).
+    /// Tout est atomique : refresh_year_total + UPDATE closed_at + lecture finale.
+    pub async fn close_year(
+        &self,
+        year: i32,
+        note: Option<String>,
+    ) -> Result<YearSummary, AppError> {
+        let note = &Self::normalize_year_note(note)?;
+        let row = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            Self::refresh_year_total_tx(&mut tx, year).await?;
+            Self::snapshot_member_totals_tx(&mut tx, year).await?;
+
+            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+            sqlx::query(
+                "UPDATE year_summaries SET closed_at = ?, note = ? WHERE year = ?",
+            )
+            .bind(&now)
+            .bind(note)
+            .bind(year)
+            .execute(&mut *tx)
+            .await?;
+
+            // Lire l'état final dans la même transaction
+            let row = sqlx::query(&format!(
+                "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries WHERE year = ?"
+            ))
+            .bind(year)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(r) = &row {
+                Self::audit_tx(&mut tx, "close", "year", i64::from(year), &serde_json::json!({
+                    "total": r.get::<String, _>("total"),
+                    "note":  note,
+                }))
+                .await?;
+            }
+
+            tx.commit().await?;
+            Ok::<_, AppError>(row)
+        })
+        .await?;
+
+        row.as_ref()
+            .map(Self::map_year_summary)
+            .ok_or_else(|| AppError::NotFound(format!("Résumé pour {year} introuvable.")))
+    }
+
+    /// Fige le total de chaque membre pour `year` (nom et carte recopiés), en
+    /// remplaçant un éventuel instantané précédent.
+    async fn snapshot_member_totals_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        year: i32,
+    ) -> Result<(), AppError> {
+        let rows = sqlx::query(
+            "SELECT c.member_id, c.amount,
+                    COALESCE(m.full_name, '') AS member_name,
+                    COALESCE(m.card_number, '') AS card_number
+             FROM contributions c
+             LEFT JOIN members m ON m.id = c.member_id
+             WHERE c.recorded_year = ?",
+        )
+        .bind(year)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let mut totals: BTreeMap<i64, (String, String, Decimal)> = BTreeMap::new();
+        for r in &rows {
+            let Ok(amount) = Decimal::from_str(&r.get::<String, _>("amount")) else { continue };
+            totals
+                .entry(r.get("member_id"))
+                .or_insert_with(|| (r.get("member_name"), r.get("card_number"), Decimal::ZERO))
+                .2 += amount;
+        }
+
+        sqlx::query("DELETE FROM year_member_totals WHERE year = ?")
+            .bind(year)
+            .execute(&mut **tx)
+            .await?;
+        for (member_id, (name, card, total)) in totals {
+            sqlx::query(
+                "INSERT INTO year_member_totals (year, member_id, member_name, card_number, total)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(year)
+            .bind(member_id)
+            .bind(name)
+            .bind(card)
+            .bind(total.to_string())
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Totaux par membre figés à la clôture de `year`, du plus fort au plus faible.
+    /// Vide si l'année n'est pas clôturée.
+    pub async fn get_year_member_totals(&self, year: i32) -> Result<Vec<YearMemberSnapshot>, AppError> {
+        let rows = sqlx::query(
+            "SELECT year, member_id, member_name, card_number, total
+             FROM year_member_totals WHERE year = ?",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut totals: Vec<YearMemberSnapshot> =
+            rows.iter().map(Self::map_year_member_snapshot).collect();
+        totals.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.member_name.cmp(&b.member_name)));
+        Ok(totals)
+    }
+
+    /// Remplace la note d'une année clôturée sans toucher `closed_at` ni les totaux.
+    /// Une note vide ou blanche est enregistrée à NULL ; une année ouverte est refusée
+    /// (sa note se saisit à la clôture).
+    pub async fn update_year_note(
+        &self,
+        year: i32,
+        note: Option<String>,
+    ) -> Result<YearSummary, AppError> {
+        let note = Self::normalize_year_note(note)?;
+
+        let result = self.write(|| {
+            sqlx::query(
+                "UPDATE year_summaries SET note = ? WHERE year = ? AND closed_at IS NOT NULL",
+            )
+            .bind(&note)
+            .bind(year)
+            .execute(&self.pool)
+        })
+        .await?;
+
+        let summary = self
+            .get_year_summary(year)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Résumé pour {year} introuvable.")))?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::validation("year.not_closed", &[&year]));
+        }
+        Ok(summary)
+    }
+
+    /// Réouvre une année clôturée (supprime closed_at + note).
+    /// Les archives restent contiguës : si des années suivantes sont encore clôturées,
+    /// la réouverture est refusée, sauf `cascade` qui les rouvre toutes dans la même
+    /// transaction. Retourne les résumés rouverts, par année croissante.
+    pub async fn reopen_year(
+        &self,
+        year: i32,
+        cascade: bool,
+    ) -> Result<Vec<YearSummary>, AppError> {
+        let rows = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+
+            let closed_at: Option<Option<String>> =
+                sqlx::query_scalar("SELECT closed_at FROM year_summaries WHERE year = ?")
+                    .bind(year)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if !matches!(closed_at, Some(Some(_))) {
+                return Err(AppError::validation("year.not_closed", &[&year]));
+            }
+
+            let later: Vec<i32> = sqlx::query_scalar(
+                "SELECT year FROM year_summaries
+                 WHERE year > ? AND closed_at IS NOT NULL
+                 ORDER BY year ASC",
+            )
+            .bind(year)
+            .fetch_all(&mut *tx)
+            .await?;
+            if !later.is_empty() && !cascade {
+                let list = later.iter().map(i32::to_string).collect::<Vec<_>>().join(", ");
+                return Err(AppError::validation("year.later_closed", &[&year, &list]));
+            }
+
+            sqlx::query(
+                "UPDATE year_summaries SET closed_at = NULL, note = NULL
+                 WHERE year >= ? AND closed_at IS NOT NULL",
+            )
+            .bind(year)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("DELETE FROM year_member_totals WHERE year >= ?")
+                .bind(year)
+                .execute(&mut *tx)
+                .await?;
+            for y in std::iter::once(year).chain(later.iter().copied()) {
+                Self::audit_tx(&mut tx, "reopen", "year", i64::from(y), &serde_json::json!({
+                    "requested_year": year,
+                    "cascade":        cascade,
+                }))
+                .await?;
+            }
+
+            let mut rows = Vec::with_capacity(later.len() + 1);
+            for y in std::iter::once(year).chain(later) {
+                rows.push(
+                    sqlx::query(&format!(
+                        "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries WHERE year = ?"
+                    ))
+                    .bind(y)
+                    .fetch_one(&mut *tx)
+                    .await?,
+                );
+            }
+
+            tx.commit().await?;
+            Ok::<_, AppError>(rows)
+        })
+        .await?;
+
+        Ok(rows.iter().map(Self::map_year_summary).collect())
+    }
+    /// Variation en % de `previous` à `current`, arrondie à 1 décimale.
+    /// `None` si l'année précédente est à 0 (pourcentage non défini).
+    fn variation_pct(previous: Decimal, current: Decimal) -> Option<f64> {
+        if previous.is_zero() {
+            return None;
+        }
+        ((current - previous) / previous * Decimal::ONE_HUNDRED)
+            .round_dp(1)
+            .to_f64()
+    }
+
+    /// Comparatif annuel : total + sous-totaux par type de membre pour chaque année demandée.
+    /// Les années sans contribution apparaissent avec des totaux à 0. Sommes en Decimal comme
+    /// `sum_year_rows` : une cotisation orpheline compte dans le total, pas dans les sous-totaux.
+    /// La variation est calculée par rapport à l'année civile précédente (même si non demandée).
+    pub async fn get_year_comparison(
+        &self,
+        years: Vec<i32>,
+    ) -> Result<Vec<YearComparison>, AppError> {
+        let mut years = years;
+        years.sort_unstable();
+        years.dedup();
+        if years.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut qb: QueryBuilder<sqlx::Sqlite> = QueryBuilder::new(
+            "SELECT c.recorded_year AS year, m.member_type AS member_type, c.amount AS amount
+             FROM contributions c
+             LEFT JOIN members m ON m.id = c.member_id
+             WHERE c.recorded_year IN (",
+        );
+        let mut sep = qb.separated(", ");
+        for y in &years {
+            sep.push_bind(*y);
+            sep.push_bind(*y - 1);
+        }
+        qb.push(")");
+        let rows = qb.build().fetch_all(&self.pool).await?;
+
+        // Année → (total, communiants, cathekomens)
+        let mut sums: HashMap<i32, (Decimal, Decimal, Decimal)> = HashMap::new();
+        for r in &rows {
+            let Ok(amount) = Decimal::from_str(&r.get::<String, _>("amount")) else { continue };
+            let entry = sums.entry(r.get("year")).or_default();
+            entry.0 += amount;
+            match r.get::<Option<String>, _>("member_type").as_deref().map(MemberType::from_str) {
+                Some(Ok(MemberType::Communiant)) => entry.1 += amount,
+                Some(Ok(MemberType::Cathekomen)) => entry.2 += amount,
+                _ => {}
+            }
+        }
+        let sums_of = |year: i32| sums.get(&year).copied().unwrap_or_default();
+
+        Ok(years
+            .iter()
+            .map(|&year| {
+                let (total, communiants, cathekomens) = sums_of(year);
+                YearComparison {
+                    year,
+                    total,
+                    communiants,
+                    cathekomens,
+                    variation_pct: Self::variation_pct(sums_of(year - 1).0, total),
+                }
+            })
+            .collect())
+    }
+
+    /// Comparatif mois par mois de `year_a` et `year_b` (mois de `payment_date`, comme
+    /// `get_monthly_totals`) avec l'écart cumulé A − B : « sommes-nous en avance sur
+    /// l'an dernier à cette date ? ». SQL regroupe par année et mois, les sommes
+    /// restent en Decimal.
+    pub async fn compare_years(
+        &self,
+        year_a: i32,
+        year_b: i32,
+    ) -> Result<MonthlyComparison, AppError> {
+        let rows = sqlx::query(
+            "SELECT CAST(substr(payment_date, 1, 4) AS INTEGER) AS year,
+                    CAST(substr(payment_date, 6, 2) AS INTEGER) AS month,
+                    amount
+             FROM contributions
+             WHERE substr(payment_date, 1, 4) IN (?, ?)",
+        )
+        .bind(format!("{year_a:04}"))
+        .bind(format!("{year_b:04}"))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut totals: HashMap<(i32, i64), Decimal> = HashMap::new();
+        for r in &rows {
+            let amount_str: String = r.get("amount");
+            let Ok(amount) = Decimal::from_str(&amount_str) else { continue };
+            *totals.entry((r.get("year"), r.get("month"))).or_default() += amount;
+        }
+        let total = |year: i32, month: u32| {
+            totals.get(&(year, i64::from(month))).copied().unwrap_or(Decimal::ZERO)
+        };
+
+        let mut cumulative_diff = Decimal::ZERO;
+        let months = (1..=12)
+            .map(|month| {
+                let (total_a, total_b) = (total(year_a, month), total(year_b, month));
+                cumulative_diff += total_a - total_b;
+                MonthComparison { month, total_a, total_b, cumulative_diff }
+            })
+            .collect();
+        Ok(MonthlyComparison { year_a, year_b, months })
+    }
+
+    /// Totaux mois par mois des cotisations payées en `year` (d'après `payment_date`) :
+    /// toujours 12 entrées, à 0 pour un mois sans cotisation. Les paiements de janvier
+    /// rattachés à l'année précédente comptent dans leur mois de paiement.
+    pub async fn get_monthly_totals(&self, year: i32) -> Result<Vec<MonthlyTotal>, AppError> {
+        let rows = sqlx::query(
+            "SELECT CAST(substr(payment_date, 6, 2) AS INTEGER) AS month, amount
+             FROM contributions
+             WHERE substr(payment_date, 1, 4) = ?",
+        )
+        .bind(format!("{year:04}"))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut months: Vec<MonthlyTotal> = (1..=12)
+            .map(|month| MonthlyTotal { month, count: 0, total: Decimal::ZERO })
+            .collect();
+        for r in &rows {
+            let month: i64 = r.get("month");
+            let Some(entry) = months.get_mut((month - 1) as usize) else { continue };
+            let amount_str: String = r.get("amount");
+            let Ok(amount) = Decimal::from_str(&amount_str) else { continue };
+            entry.count += 1;
+            entry.total += amount;
+        }
+        Ok(months)
+    }
+
+    /// Totaux par semaine ISO de l'année ISO `year` (du lundi de la semaine 1 au
+    /// dimanche de la dernière) : une entrée par semaine, à zéro si rien n'a été payé,
+    /// pour qu'un graphique ne masque pas les creux.
+    pub async fn get_weekly_totals(&self, year: i32) -> Result<Vec<WeekTotal>, AppError> {
+        let Some(first) = NaiveDate::from_isoywd_opt(year, 1, Weekday::Mon) else {
+            return Err(AppError::validation("year.invalid", &[&year]));
+        };
+        let weeks: u32 = if NaiveDate::from_isoywd_opt(year, 53, Weekday::Mon).is_some() { 53 } else { 52 };
+        let last = first + chrono::Duration::days(i64::from(weeks) * 7 - 1);
+
+        let rows = sqlx::query(
+            "SELECT payment_date, amount
+             FROM contributions
+             WHERE payment_date BETWEEN ? AND ?",
+        )
+        .bind(first.format("%Y-%m-%d").to_string())
+        .bind(last.format("%Y-%m-%d").to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut totals: Vec<WeekTotal> = (1..=weeks)
+            .map(|week| WeekTotal { week, count: 0, total: Decimal::ZERO })
+            .collect();
+        for r in &rows {
+            let date: String = r.get("payment_date");
+            let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") else { continue };
+            let Some(entry) = totals.get_mut(date.iso_week().week0() as usize) else { continue };
+            let amount_str: String = r.get("amount");
+            let Ok(amount) = Decimal::from_str(&amount_str) else { continue };
+            entry.count += 1;
+            entry.total += amount;
+        }
+        Ok(totals)
+    }
+
+    /// Répartition des cotisations de `year` par genre et par type de membre.
+    /// LEFT JOIN : une cotisation dont le membre n'existe plus tombe dans "Inconnu".
+    pub async fn get_year_breakdown(&self, year: i32) -> Result<YearBreakdown, AppError> {
+        let rows = sqlx::query(
+            "SELECT COALESCE(m.gender, 'Inconnu') AS gender,
+                    COALESCE(m.member_type, 'Inconnu') AS member_type,
+                    c.amount
+             FROM contributions c
+             LEFT JOIN members m ON m.id = c.member_id
+             WHERE c.recorded_year = ?",
+        )
+        .bind(year)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let bucket = |key: &str| BreakdownEntry { key: key.to_string(), count: 0, total: Decimal::ZERO };
+        let mut by_gender: Vec<BreakdownEntry> =
+            [Gender::M.as_str(), Gender::F.as_str()].into_iter().map(bucket).collect();
+        let mut by_type: Vec<BreakdownEntry> =
+            [MemberType::Communiant.as_str(), MemberType::Cathekomen.as_str()]
+                .into_iter()
+                .map(bucket)
+                .collect();
+
+        fn add(entries: &mut Vec<BreakdownEntry>, key: &str, amount: Decimal) {
+            match entries.iter_mut().find(|e| e.key == key) {
+                Some(e) => {
+                    e.count += 1;
+                    e.total += amount;
+                }
+                None => entries.push(BreakdownEntry { key: key.to_string(), count: 1, total: amount }),
+            }
+        }
+
+        for r in &rows {
+            let amount_str: String = r.get("amount");
+            let amount = Decimal::from_str(&amount_str).unwrap_or(Decimal::ZERO);
+            add(&mut by_gender, r.get("gender"), amount);
+            add(&mut by_type, r.get("member_type"), amount);
+        }
+        Ok(YearBreakdown { year, by_gender, by_type })
+    }
+
+    // ── Statistiques ──────────────────────────────────────────────────────────
+
+    /// Séries agrégées pour la page Statistiques :
+    /// membres par genre et type, nouveaux membres par année, totaux par année.
+    pub async fn get_statistics(&self) -> Result<Statistics, AppError> {
+        let gender_rows = sqlx::query(
+            "SELECT member_type, gender, COUNT(*) AS count
+             FROM members
+             WHERE deleted_at IS NULL
+             GROUP BY member_type, gender
+             ORDER BY member_type ASC, gender ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let new_rows = sqlx::query(
+            "SELECT CAST(substr(created_at, 1, 4) AS INTEGER) AS year, COUNT(*) AS count
+             FROM members
+             WHERE deleted_at IS NULL
+             GROUP BY year
+             ORDER BY year ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let totals_rows = sqlx::query(
+            "SELECT year, total FROM year_summaries ORDER BY year ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Statistics {
+            gender_by_type: gender_rows
+                .iter()
+                .map(|r| GenderCount {
+                    member_type: r.get("member_type"),
+                    gender:      r.get("gender"),
+                    count:       r.get("count"),
+                })
+                .collect(),
+            new_members_by_year: new_rows
+                .iter()
+                .map(|r| YearCount { year: r.get("year"), count: r.get("count") })
+                .collect(),
+            totals_by_year: totals_rows
+                .iter()
+                .map(|r| {
+                    let total_str: String = r.get("total");
+                    YearTotal {
+                        year:  r.get("year"),
+                        total: Decimal::from_str(&total_str).unwrap_or(Decimal::ZERO),
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Chiffres de l'accueil pour l'année courante.
+    pub async fn get_dashboard_stats(&self) -> Result<DashboardStats, AppError> {
+        self.get_dashboard_stats_for_year(self.current_year().await?).await
+    }
+
+    /// `get_dashboard_stats` pour une année donnée (testable) : une seule requête
+    /// de sous-requêtes scalaires, sans charger aucune liste.
+    pub async fn get_dashboard_stats_for_year(&self, year: i32) -> Result<DashboardStats, AppError> {
+        let row = sqlx::query(
+            "SELECT
+                 (SELECT COUNT(*) FROM members
+                  WHERE deleted_at IS NULL AND status = ?4 AND member_type = ?2) AS communiants,
+                 (SELECT COUNT(*) FROM members
+                  WHERE deleted_at IS NULL AND status = ?4 AND member_type = ?3) AS cathekomens,
+                 (SELECT COUNT(*) FROM members
+                  WHERE deleted_at IS NULL AND status = ?4 AND gender = 'M') AS men,
+                 (SELECT COUNT(*) FROM members
+                  WHERE deleted_at IS NULL AND status = ?4 AND gender = 'F') AS women,
+                 (SELECT total FROM year_summaries WHERE year = ?1) AS current_year_total,
+                 (SELECT total FROM year_summaries WHERE year = ?1 - 1) AS previous_year_total,
+                 (SELECT COUNT(*) FROM contributions
+                  WHERE recorded_year = ?1) AS contributions_this_year,
+                 (SELECT MAX(payment_date) FROM contributions) AS last_contribution_date",
+        )
+        .bind(year)
+        .bind(MemberType::Communiant.as_str())
+        .bind(MemberType::Cathekomen.as_str())
+        .bind(MemberStatus::Actif.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let decimal = |col: &str| {
+            let s: Option<String> = row.get(col);
+            s.and_then(|s| Decimal::from_str(&s).ok()).unwrap_or(Decimal::ZERO)
+        };
+        Ok(DashboardStats {
+            year,
+            communiants:             row.get("communiants"),
+            cathekomens:             row.get("cathekomens"),
+            men:                     row.get("men"),
+            women:                   row.get("women"),
+            current_year_total:      decimal("current_year_total"),
+            previous_year_total:     decimal("previous_year_total"),
+            contributions_this_year: row.get("contributions_this_year"),
+            last_contribution_date:  row.get("last_contribution_date"),
+        })
+    }
+
+    // ── Sauvegarde / Restauration ─────────────────────────────────────────────
+
+    /// Tables jamais écrasées par une restauration : historique des migrations
+    /// et journal applicatif (il doit garder la trace de la restauration elle-même).
+    const RESTORE_SKIPPED_TABLES: [&'static str; 2] = ["_sqlx_migrations", "app_log"];
+
+    /// Copie cohérente de la base dans `path` via `VACUUM INTO` (le fichier ne doit pas exister).
+    pub async fn backup_into(&self, path: &str) -> Result<(), AppError> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Compacte le fichier de la base (`VACUUM`) : récupère l'espace laissé
+    /// par les suppressions. Bloque les écritures le temps de l'opération.
+    pub async fn vacuum(&self) -> Result<(), AppError> {
+        sqlx::query("VACUUM").execute(&self.pool).await?;
+        // En WAL, les pages compactées restent dans le journal jusqu'au checkpoint
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Remplace le contenu de la base par celui de la sauvegarde `path`.
+    ///
+    /// La sauvegarde est attachée sur une connexion dédiée puis recopiée table par table
+    /// dans une transaction : le pool reste ouvert (serveur API compris).
+    /// Refusé si la sauvegarde ne provient pas de la même version de schéma.
+    pub async fn restore_from(&self, path: &str) -> Result<(), AppError> {
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("ATTACH DATABASE ? AS bak")
+            .bind(path)
+            .execute(&mut *conn)
+            .await?;
+
+        let result = Self::copy_from_attached(&mut conn).await;
+
+        let _ = sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await;
+        let _ = sqlx::query("DETACH DATABASE bak").execute(&mut *conn).await;
+        result
+    }
+
+    async fn copy_from_attached(conn: &mut sqlx::SqliteConnection) -> Result<(), AppError> {
+        let version = "SELECT COALESCE(MAX(version), 0) FROM {db}._sqlx_migrations WHERE success = 1";
+        let main_v: i64 = sqlx::query_scalar(&version.replace("{db}", "main"))
+            .fetch_one(&mut *conn)
+            .await?;
+        let bak_v: i64 = sqlx::query_scalar(&version.replace("{db}", "bak"))
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|_| AppError::Validation("Ce fichier n'est pas une sauvegarde valide.".into()))?;
+        if main_v != bak_v {
+            return Err(AppError::Validation(format!(
+                "Sauvegarde incompatible (schéma {bak_v}, attendu {main_v})."
+            ).into()));
+        }
+
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM main.sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        // Hors transaction : PRAGMA foreign_keys est sans effet à l'intérieur d'une transaction
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+        let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+        for t in tables.iter().filter(|t| !Self::RESTORE_SKIPPED_TABLES.contains(&t.as_str())) {
+            sqlx::query(&format!("DELETE FROM main.\"{t}\"")).execute(&mut *tx).await?;
+            sqlx::query(&format!("INSERT INTO main.\"{t}\" SELECT * FROM bak.\"{t}\""))
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    // ── Archive JSON ──────────────────────────────────────────────────────────
+
+    /// Base complète au format JSON portable (`DatabaseArchive`), lue dans une seule
+    /// transaction : membres (corbeille comprise), notes, cotisations, résumés annuels,
+    /// totaux figés à la clôture et paramètres, sans l'état du PIN.
+    pub async fn export_archive(&self) -> Result<String, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let members = sqlx::query("SELECT * FROM members ORDER BY id")
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(|r| ArchivedMember { member: Self::map_member(r), deleted_at: r.get("deleted_at") })
+            .collect();
+        let contributions = sqlx::query("SELECT * FROM contributions ORDER BY id")
+            .fetch_all(&mut *tx)
+            .await?
+            .iter()
+            .map(Self::map_contribution)
+            .collect();
+        let year_summaries = sqlx::query(&format!(
+            "SELECT {YEAR_SUMMARY_COLUMNS} FROM year_summaries ORDER BY year"
+        ))
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(Self::map_year_summary)
+        .collect();
+        let year_member_totals = sqlx::query(
+            "SELECT year, member_id, member_name, card_number, total
+             FROM year_member_totals ORDER BY year, member_id",
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(Self::map_year_member_snapshot)
+        .collect();
+        let member_notes = sqlx::query(
+            "SELECT id, member_id, created_at, text FROM member_notes ORDER BY id",
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .iter()
+        .map(Self::map_member_note)
+        .collect();
+        let settings: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM settings WHERE key NOT LIKE 'admin_pin%'")
+                .fetch_all(&mut *tx)
+                .await?;
+        tx.commit().await?;
+
+        let archive = DatabaseArchive {
+            schema_version: ARCHIVE_SCHEMA_VERSION,
+            exported_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+            members,
+            contributions,
+            year_summaries,
+            year_member_totals,
+            settings: settings.into_iter().collect(),
+            member_notes,
+        };
+        serde_json::to_string_pretty(&archive)
+            .map_err(|e| AppError::Internal(format!("Sérialisation de l'archive : {e}")))
+    }
+
+    /// Importe une archive produite par `export_archive`, en une seule transaction.
+    /// La version et la cohérence de l'archive sont vérifiées avant toute écriture.
+    ///
+    /// - `Replace` : membres, notes, cotisations, résumés, totaux figés et paramètres (hors
+    ///   PIN) sont remplacés ; identifiants et numéros de reçu sont conservés.
+    /// - `Merge` : un membre dont la carte existe est ignoré et ses notes et cotisations
+    ///   rattachées à la fiche locale (une note identique déjà présente est écartée) ; une cotisation déjà présente (même reçu, même contenu) ou
+    ///   d'une année clôturée ici est écartée, un reçu pris par une autre cotisation est
+    ///   renuméroté. Les paramètres locaux l'emportent.
+    pub async fn import_archive(
+        &self,
+        json: &str,
+        mode: ImportMode,
+    ) -> Result<ArchiveImportReport, AppError> {
+        let archive = Self::parse_archive(json)?;
+        let archive = &archive;
+        let report = self.write(|| async move {
+            let mut tx = self.pool.begin().await?;
+            let report = match mode {
+                ImportMode::Replace => Self::replace_from_archive(&mut tx, archive).await?,
+                ImportMode::Merge   => Self::merge_from_archive(&mut tx, archive).await?,
+            };
+            tx.commit().await?;
+            Ok::<_, AppError>(report)
+        })
+        .await?;
+        if mode == ImportMode::Replace {
+            i18n::set_current(self.get_language().await?);
+        }
+        Ok(report)
+    }
+
+    /// Lit et vérifie une archive : version d'abord (un format futur peut ne plus se
+    /// désérialiser), puis cartes uniques, cotisations et notes rattachées à un membre présent.
+    fn parse_archive(json: &str) -> Result<DatabaseArchive, AppError> {
+        let invalid = |e: serde_json::Error| AppError::validation("archive.invalid", &[&e]);
+        let value: serde_json::Value = serde_json::from_str(json).map_err(invalid)?;
+        let version = value.get("schema_version").and_then(serde_json::Value::as_u64);
+        if !version.is_some_and(|v| (1..=u64::from(ARCHIVE_SCHEMA_VERSION)).contains(&v)) {
+            let found = version.map_or_else(|| "?".to_string(), |v| v.to_string());
+            return Err(AppError::validation("archive.version", &[&found, &ARCHIVE_SCHEMA_VERSION]));
+        }
+        let archive: DatabaseArchive = serde_json::from_value(value).map_err(invalid)?;
+
+        let mut cards = HashSet::new();
+        if let Some(dup) = archive.members.iter().find(|a| !cards.insert(&a.member.card_number)) {
+            return Err(AppError::validation("archive.duplicate_card", &[&dup.member.card_number]));
+        }
+        let ids: HashSet<i64> = archive.members.iter().map(|a| a.member.id).collect();
+        if let Some(c) = archive.contributions.iter().find(|c| !ids.contains(&c.member_id)) {
+            return Err(AppError::validation("archive.unknown_member", &[&c.receipt_number, &c.member_id]));
+        }
+        if let Some(n) = archive.member_notes.iter().find(|n| !ids.contains(&n.member_id)) {
+            return Err(AppError::validation("archive.unknown_note_member", &[&n.id, &n.member_id]));
+        }
+        Ok(archive)
+    }
+
+    /// Insère un membre de l'archive ; `id = None` laisse SQLite attribuer l'identifiant.
+    async fn insert_archived_member(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        archived: &ArchivedMember,
+        id: Option<i64>,
+    ) -> Result<i64, AppError> {
+        let m = &archived.member;
+        // Archive antérieure aux parties du nom : déduites comme à la migration
+        let (surname, given_name) = match (&m.surname, &m.given_name) {
+            (None, None) => split_full_name(&m.full_name),
+            (s, g) => (s.clone(), g.clone()),
+        };
+        Ok(sqlx::query_scalar(
+            "INSERT INTO members
+                 (id, card_number, full_name, address, phone, job, gender, member_type,
+                  surname, given_name, status, birth_date, created_at, transferred_at, deleted_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             RETURNING id",
+        )
+        .bind(id)
+        .bind(&m.card_number)
+        .bind(&m.full_name)
+        .bind(&m.address)
+        .bind(&m.phone)
+        .bind(&m.job)
+        .bind(m.gender.as_str())
+        .bind(m.member_type.as_str())
+        .bind(surname)
+        .bind(given_name)
+        .bind(m.status.as_str())
+        .bind(&m.birth_date)
+        .bind(&m.created_at)
+        .bind(&m.transferred_at)
+        .bind(&archived.deleted_at)
+        .fetch_one(&mut **tx)
+        .await?)
+    }
+
+    async fn insert_archived_contribution(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        c: &Contribution,
+        id: Option<i64>,
+        member_id: i64,
+        receipt_number: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO contributions
+                 (id, member_id, payment_date, period, amount, recorded_year, receipt_number,
+                  recorded_by)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(member_id)
+        .bind(&c.payment_date)
+        .bind(&c.period)
+        .bind(c.amount.to_string())
+        .bind(c.recorded_year)
+        .bind(receipt_number)
+        .bind(&c.recorded_by)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_archived_note(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        n: &MemberNote,
+        id: Option<i64>,
+        member_id: i64,
+    ) -> Result<(), AppError> {
+        sqlx::query("INSERT INTO member_notes (id, member_id, created_at, text) VALUES (?, ?, ?, ?)")
+            .bind(id)
+            .bind(member_id)
+            .bind(&n.created_at)
+            .bind(&n.text)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// `or_ignore` : une année ou un instantané déjà présent est conservé (fusion).
+    async fn insert_archived_summaries(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        archive: &DatabaseArchive,
+        member_ids: &HashMap<i64, i64>,
+        skipped_years: &HashSet<i32>,
+        or_ignore: bool,
+    ) -> Result<(), AppError> {
+        let verb = if or_ignore { "INSERT OR IGNORE" } else { "INSERT" };
+        for s in archive.year_summaries.iter().filter(|s| !skipped_years.contains(&s.year)) {
+            sqlx::query(&format!(
+                "{verb} INTO year_summaries ({YEAR_SUMMARY_COLUMNS})
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+            ))
+            .bind(s.year)
+            .bind(s.total.to_string())
+            .bind(s.total_communiant.to_string())
+            .bind(s.total_cathekomen.to_string())
+            .bind(s.contributor_count)
+            .bind(s.contribution_count)
+            .bind(&s.closed_at)
+            .bind(&s.note)
+            .execute(&mut **tx)
+            .await?;
+        }
+        for t in archive.year_member_totals.iter().filter(|t| !skipped_years.contains(&t.year)) {
+            // Instantané d'un membre absent de l'archive : identifiant gardé tel quel
+            let member_id = member_ids.get(&t.member_id).copied().unwrap_or(t.member_id);
+            sqlx::query(&format!(
+                "{verb} INTO year_member_totals (year, member_id, member_name, card_number, total)
+                 VALUES (?, ?, ?, ?, ?)"
+            ))
+            .bind(t.year)
+            .bind(member_id)
+            .bind(&t.member_name)
+            .bind(&t.card_number)
+            .bind(t.total.to_string())
+            .execute(&mut **tx)
+            .await?;
+        }
+        let settings_sql = if or_ignore {
+            "INSERT OR IGNORE INTO settings (key, value) VALUES (?, ?)"
+        } else {
+            UPSERT_SETTING_SQL
+        };
+        for (key, value) in archive.settings.iter().filter(|(k, _)| !k.starts_with("admin_pin")) {
+            sqlx::query(settings_sql).bind(key).bind(value).execute(&mut **tx).await?;
+        }
+        Ok(())
+    }
+
+    async fn replace_from_archive(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        archive: &DatabaseArchive,
+    ) -> Result<ArchiveImportReport, AppError> {
+        let tables =
+            ["contribution_audits", "contributions", "year_member_totals", "year_summaries", "member_notes", "members"];
+        for table in tables {
+            sqlx::query(&format!("DELETE FROM {table}")).execute(&mut **tx).await?;
+        }
+        sqlx::query("DELETE FROM settings WHERE key NOT LIKE 'admin_pin%'")
+            .execute(&mut **tx)
+            .await?;
+
+        for a in &archive.members {
+            Self::insert_archived_member(tx, a, Some(a.member.id)).await?;
+        }
+        for n in &archive.member_notes {
+            Self::insert_archived_note(tx, n, Some(n.id), n.member_id).await?;
+        }
+        for c in &archive.contributions {
+            Self::insert_archived_contribution(tx, c, Some(c.id), c.member_id, &c.receipt_number).await?;
+        }
+        Self::insert_archived_summaries(tx, archive, &HashMap::new(), &HashSet::new(), false).await?;
+
+        Ok(ArchiveImportReport {
+            members_inserted:       archive.members.len(),
+            contributions_inserted: archive.contributions.len(),
+            ..Default::default()
+        })
+    }
+
+    async fn merge_from_archive(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        archive: &DatabaseArchive,
+    ) -> Result<ArchiveImportReport, AppError> {
+        let mut report = ArchiveImportReport::default();
+        let closed: HashSet<i32> =
+            sqlx::query_scalar("SELECT year FROM year_summaries WHERE closed_at IS NOT NULL")
+                .fetch_all(&mut **tx)
+                .await?
+                .into_iter()
+                .collect();
+
+        // Identifiant dans l'archive → identifiant local
+        let mut member_ids: HashMap<i64, i64> = HashMap::new();
+        for a in &archive.members {
+            let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM members WHERE card_number = ?")
+                .bind(&a.member.card_number)
+                .fetch_optional(&mut **tx)
+                .await?;
+            let id = match existing {
+                Some(id) => {
+                    report.members_skipped += 1;
+                    id
+                }
+                None => {
+                    report.members_inserted += 1;
+                    Self::insert_archived_member(tx, a, None).await?
+                }
+            };
+            member_ids.insert(a.member.id, id);
+        }
+
+        for n in &archive.member_notes {
+            let member_id = member_ids[&n.member_id];
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM member_notes
+                               WHERE member_id = ? AND created_at = ? AND text = ?)",
+            )
+            .bind(member_id)
+            .bind(&n.created_at)
+            .bind(&n.text)
+            .fetch_one(&mut **tx)
+            .await?;
+            if !exists {
+                Self::insert_archived_note(tx, n, None, member_id).await?;
+            }
+        }
+
+        let mut years = BTreeSet::new();
+        for c in &archive.contributions {
+            let member_id = member_ids[&c.member_id];
+            if closed.contains(&c.recorded_year) {
+                report.contributions_skipped += 1;
+                continue;
+            }
+            let existing = sqlx::query(
+                "SELECT member_id, payment_date, period, amount FROM contributions
+                 WHERE receipt_number = ?",
+            )
+            .bind(&c.receipt_number)
+            .fetch_optional(&mut **tx)
+            .await?;
+            let receipt_number = match existing {
+                None => c.receipt_number.clone(),
+                Some(r) => {
+                    let same = r.get::<i64, _>("member_id") == member_id
+                        && r.get::<String, _>("payment_date") == c.payment_date
+                        && r.get::<String, _>("period") == c.period
+                        && Decimal::from_str(&r.get::<String, _>("amount")).ok() == Some(c.amount);
+                    if same {
+                        report.contributions_skipped += 1;
+                        continue;
+                    }
+                    Self::next_receipt_number_tx(tx, c.recorded_year).await?
+                }
+            };
+            Self::insert_archived_contribution(tx, c, None, member_id, &receipt_number).await?;
+            report.contributions_inserted += 1;
+            years.insert(c.recorded_year);
+        }
+
+        Self::insert_archived_summaries(tx, archive, &member_ids, &closed, true).await?;
+        for year in years {
+            Self::refresh_year_total_tx(tx, year).await?;
+        }
+        Ok(report)
+    }
+
+    // ── Tâches en attente ─────────────────────────────────────────────────────
+
+    /// Rappels affichés sous la cloche de la Navbar. Une requête par type de
+    /// tâche : en ajouter une = une méthode `pending_*` de plus. N'écrit rien.
+    pub async fn get_pending_tasks(&self) -> Result<Vec<PendingTask>, AppError> {
+        self.get_pending_tasks_on(chrono::Local::now().date_naive()).await
+    }
+
+    /// `get_pending_tasks` à une date donnée (testable).
+    pub(crate) async fn get_pending_tasks_on(&self, today: NaiveDate) -> Result<Vec<PendingTask>, AppError> {
+        let mut tasks = Vec::new();
+        let year = fiscal_year_of(today, self.fiscal_start_month().await?);
+        tasks.extend(self.pending_unclosed_year(year - 1).await?);
+        tasks.extend(self.pending_incomplete_members().await?);
+        tasks.extend(self.pending_future_contributions(today).await?);
+        Ok(tasks)
+    }
+
+    async fn pending_unclosed_year(&self, year: i32) -> Result<Option<PendingTask>, AppError> {
+        let contributions: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM contributions
+             WHERE recorded_year = ?
+               AND NOT EXISTS (SELECT 1 FROM year_summaries
+                               WHERE year = ? AND closed_at IS NOT NULL)",
+        )
+        .bind(year)
+        .bind(year)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((contributions > 0).then_some(PendingTask::UnclosedYear { year, contributions }))
+    }
+
+    async fn pending_incomplete_members(&self) -> Result<Option<PendingTask>, AppError> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM members
+             WHERE deleted_at IS NULL
+               AND TRIM(COALESCE(phone, '')) = ''
+               AND TRIM(COALESCE(address, '')) = ''",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((count > 0).then_some(PendingTask::IncompleteMembers { count }))
+    }
+
+    async fn pending_future_contributions(&self, today: NaiveDate) -> Result<Option<PendingTask>, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contributions WHERE payment_date > ?")
+            .bind(today.format("%Y-%m-%d").to_string())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok((count > 0).then_some(PendingTask::FutureContributions { count }))
+    }
+
+    // ── Contrôle d'intégrité ──────────────────────────────────────────────────
+
+    /// Vérifie le fichier SQLite puis la cohérence des données : membres des
+    /// contributions, `recorded_year` et totaux annuels. N'écrit rien.
+    pub async fn run_integrity_check(&self) -> Result<IntegrityReport, AppError> {
+        let mut issues = Vec::new();
+
+        let pragma: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await?;
+        issues.extend(
+            pragma
+                .into_iter()
+                .filter(|m| m != "ok")
+                .map(|message| IntegrityIssue::Sqlite { message }),
+        );
+
+        let orphans = sqlx::query(
+            "SELECT c.id, c.member_id
+             FROM contributions c
+             LEFT JOIN members m ON m.id = c.member_id
+             WHERE m.id IS NULL
+             ORDER BY c.id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        issues.extend(orphans.iter().map(|r| IntegrityIssue::OrphanContribution {
+            contribution_id: r.get("id"),
+            member_id:       r.get("member_id"),
+        }));
+
+        let start_month = self.fiscal_start_month().await?;
+        let mut conn = self.pool.acquire().await?;
+        issues.extend(
+            Self::recorded_year_mismatches(&mut conn, start_month)
+                .await?
+                .into_iter()
+                .map(|(contribution_id, recorded_year, payment_year)| {
+                    IntegrityIssue::RecordedYearMismatch { contribution_id, recorded_year, payment_year }
+                }),
+        );
+
+        issues.extend(
+            Self::year_total_mismatches(&mut conn)
+                .await?
+                .into_iter()
+                .map(|r| IntegrityIssue::YearTotalMismatch {
+                    year:     r.year,
+                    stored:   r.stored,
+                    computed: r.computed,
+                }),
+        );
+
+        Ok(IntegrityReport {
+            checked_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+            issues,
+        })
+    }
+
+    /// Contributions dont `recorded_year` n'est pas l'exercice de `payment_date`
+    /// (`fiscal_year_of`, calculé ici : SQLite ne connaît pas le mois de début),
+    /// sous forme `(id, recorded_year, exercice attendu)`. Dates illisibles ignorées.
+    async fn recorded_year_mismatches(
+        conn: &mut SqliteConnection,
+        start_month: u32,
+    ) -> Result<Vec<(i64, i32, i32)>, AppError> {
+        let rows = sqlx::query("SELECT id, recorded_year, payment_date FROM contributions ORDER BY id")
+            .fetch_all(&mut *conn)
+            .await?;
+        Ok(rows
+            .iter()
+            .filter_map(|r| {
+                let date = NaiveDate::parse_from_str(&r.get::<String, _>("payment_date"), "%Y-%m-%d").ok()?;
+                let expected = fiscal_year_of(date, start_month);
+                let recorded: i32 = r.get("recorded_year");
+                (recorded != expected).then(|| (r.get("id"), recorded, expected))
+            })
+            .collect())
+    }
+
+    /// Années ouvertes dont `year_summaries.total` diffère de la somme des
+    /// contributions (ligne absente = 0), par année croissante. Les années clôturées
+    /// sont ignorées : leur total reste figé, même après une purge (`purge_member`).
+    async fn year_total_mismatches(conn: &mut SqliteConnection) -> Result<Vec<RepairedYear>, AppError> {
+        // Sommes en Decimal (montants stockés en TEXT) plutôt qu'en REAL SQL
+        let mut computed: HashMap<i32, Decimal> = HashMap::new();
+        for r in sqlx::query("SELECT recorded_year, amount FROM contributions")
+            .fetch_all(&mut *conn)
+            .await?
+        {
+            let amount = Decimal::from_str(&r.get::<String, _>("amount")).unwrap_or(Decimal::ZERO);
+            *computed.entry(r.get("recorded_year")).or_default() += amount;
+        }
+        let mut stored: HashMap<i32, Decimal> = HashMap::new();
+        let mut closed: HashSet<i32> = HashSet::new();
+        for r in sqlx::query("SELECT year, total, closed_at FROM year_summaries")
+            .fetch_all(&mut *conn)
+            .await?
+        {
+            let year: i32 = r.get("year");
+            if r.get::<Option<String>, _>("closed_at").is_some() {
+                closed.insert(year);
+                continue;
+            }
+            let total = Decimal::from_str(&r.get::<String, _>("total")).unwrap_or(Decimal::ZERO);
+            stored.insert(year, total);
+        }
+        let mut all_years: Vec<i32> = computed.keys().chain(stored.keys()).copied().collect();
+        all_years.sort_unstable();
+        all_years.dedup();
+        Ok(all_years
+            .into_iter()
+            .filter(|year| !closed.contains(year))
+            .map(|year| RepairedYear {
+                year,
+                stored:   stored.get(&year).copied().unwrap_or(Decimal::ZERO),
+                computed: computed.get(&year).copied().unwrap_or(Decimal::ZERO),
+            })
+            .filter(|r| r.stored != r.computed)
+            .collect())
+    }
+
+    /// Recalcule les résumés des seules années ouvertes dont le total ne correspond
+    /// plus aux contributions (écriture interrompue) et renvoie ce qui a été corrigé.
+    /// Contrairement à `repair_integrity`, `recorded_year` n'est pas touché.
+    pub async fn verify_and_repair_summaries(&self) -> Result<Vec<RepairedYear>, AppError> {
+        let _writer = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await?;
+        let repaired = Self::year_total_mismatches(&mut tx).await?;
+        for r in &repaired {
+            Self::refresh_year_total_tx(&mut tx, r.year).await?;
+        }
+        tx.commit().await?;
+        Ok(repaired)
+    }
+
+    /// Réécrit une fois pour toutes les périodes saisies librement avant leur validation
+    /// ("Janvier 2025" → "2025-01", "2025-t1" → "2025-T1", voir `parse_legacy_period`).
+    /// Les valeurs illisibles restent telles quelles et sont listées dans le rapport.
+    /// `None` si la normalisation a déjà eu lieu sur cette base.
+    pub async fn normalize_legacy_periods(&self) -> Result<Option<PeriodNormalization>, AppError> {
+        if self.get_setting("periods_normalized").await?.is_some() {
+            return Ok(None);
+        }
+        let _writer = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await?;
+        let periods: Vec<String> =
+            sqlx::query_scalar("SELECT DISTINCT period FROM contributions ORDER BY period")
+                .fetch_all(&mut *tx)
+                .await?;
+
+        let mut report = PeriodNormalization::default();
+        for period in periods {
+            let Some(canonical) = parse_legacy_period(&period).map(|p| p.to_string()) else {
+                report.unparseable.push(period);
+                continue;
+            };
+            if canonical == period {
+                continue;
+            }
+            let updated = sqlx::query("UPDATE contributions SET period = ? WHERE period = ?")
+                .bind(&canonical)
+                .bind(&period)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            report.rewritten.push((period, canonical, updated));
+        }
+
+        sqlx::query(UPSERT_SETTING_SQL)
+            .bind("periods_normalized")
+            .bind(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some(report))
+    }
+
+    /// Corrige ce qui peut l'être sans arbitrage humain : `recorded_year` réaligné
+    /// sur l'exercice de `payment_date`, puis les totaux des années ouvertes recalculés. Les
+    /// contributions orphelines, celles dont l'année actuelle ou attendue est clôturée
+    /// (à rouvrir d'abord) et les erreurs SQLite restent dans le rapport renvoyé.
+    pub async fn repair_integrity(&self) -> Result<IntegrityReport, AppError> {
+        let start_month = self.fiscal_start_month().await?;
+        let writer = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await?;
+        let closed: HashSet<i32> =
+            sqlx::query_scalar("SELECT year FROM year_summaries WHERE closed_at IS NOT NULL")
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .collect();
+        for (id, recorded, expected) in Self::recorded_year_mismatches(&mut tx, start_month).await? {
+            if closed.contains(&recorded) || closed.contains(&expected) {
+                continue;
+            }
+            sqlx::query("UPDATE contributions SET recorded_year = ? WHERE id = ?")
+                .bind(expected)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let years: Vec<i32> = sqlx::query_scalar(
+            "SELECT c.recorded_year FROM contributions c
+             LEFT JOIN year_summaries ys ON ys.year = c.recorded_year
+             WHERE ys.closed_at IS NULL
+             UNION
+             SELECT year FROM year_summaries WHERE closed_at IS NULL",
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        for year in years {
+            Self::refresh_year_total_tx(&mut tx, year).await?;
+        }
+        tx.commit().await?;
+        drop(writer);
+
+        self.run_integrity_check().await
+    }
+
+    /// Réécrit au format "+261 3X XX XXX XX" les téléphones déjà enregistrés (saisis
+    /// avant la validation, ou importés d'une ancienne base). Les numéros vides
+    /// deviennent NULL ; ceux qui ne ressemblent à aucun numéro malgache restent
+    /// inchangés et sont listés dans le rapport, à corriger à la main.
+    pub async fn normalize_all_phones(&self) -> Result<PhoneFixReport, AppError> {
+        let _writer = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await?;
+        let rows = sqlx::query(
+            "SELECT id, card_number, phone FROM members WHERE phone IS NOT NULL ORDER BY id",
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut report = PhoneFixReport::default();
+        for r in &rows {
+            let phone: String = r.get("phone");
+            let normalized = match normalize_optional_phone(Some(&phone)) {
+                Ok(n) => n,
+                Err(_) => {
+                    report.invalid.push(InvalidPhone {
+                        member_id:   r.get("id"),
+                        card_number: r.get("card_number"),
+                        phone,
+                    });
+                    continue;
+                }
+            };
+            if normalized.as_deref() == Some(phone.as_str()) {
+                continue;
+            }
+            sqlx::query("UPDATE members SET phone = ? WHERE id = ?")
+                .bind(&normalized)
+                .bind(r.get::<i64, _>("id"))
+                .execute(&mut *tx)
+                .await?;
+            report.updated += 1;
+        }
+        tx.commit().await?;
+        Ok(report)
+    }
+
+    // ── État de la base ───────────────────────────────────────────────────────
+
+    /// Dernière migration réussie ; 0 pour une base jamais migrée.
+    pub async fn schema_version(&self) -> Result<i64, AppError> {
+        let version = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(version)
+    }
+
+    /// Version du schéma, lignes par table et taille de la base (Paramètres).
+    pub async fn health(&self) -> Result<DbHealth, AppError> {
+        let schema_version = self.schema_version().await?;
+
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != '_sqlx_migrations'
+             ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut tables = Vec::with_capacity(names.len());
+        for table in names {
+            // Noms issus de sqlite_master, pas d'une saisie
+            let rows = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{table}\""))
+                .fetch_one(&self.pool)
+                .await?;
+            tables.push(TableRowCount { table, rows });
+        }
+
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(&self.pool).await?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(&self.pool).await?;
+
+        Ok(DbHealth { schema_version, tables, size_bytes: page_count * page_size })
+    }
+
+    // ── Journal d'audit ───────────────────────────────────────────────────────
+
+    /// Ajoute une ligne au journal d'audit, dans la transaction de l'opération.
+    /// Au mieux : des détails non sérialisables sont enregistrés à NULL plutôt que
+    /// de faire échouer l'opération.
+    async fn audit_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        action: &str,
+        entity: &str,
+        entity_id: i64,
+        details: &impl serde::Serialize,
+    ) -> Result<(), AppError> {
+        let details = serde_json::to_string(details).ok();
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        sqlx::query(
+            "INSERT INTO audit_log (timestamp, action, entity, entity_id, details)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&now)
+        .bind(action)
+        .bind(entity)
+        .bind(entity_id)
+        .bind(details)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Journal d'audit, le plus récent d'abord, paginé (`limit` défaut 50, max 500).
+    /// `entity` : 'member' | 'contribution' | 'year' ; `None` ou vide = tout.
+    pub async fn get_audit_log(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        entity: Option<&str>,
+    ) -> Result<Page<AuditEntry>, AppError> {
+        let limit  = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let offset = offset.unwrap_or(0).max(0);
+        let entity = entity.map(str::trim).filter(|e| !e.is_empty());
+
+        let total_count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM audit_log WHERE ?1 IS NULL OR entity = ?1",
+        )
+        .bind(entity)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rows = sqlx::query(
+            "SELECT id, timestamp, action, entity, entity_id, details
+             FROM audit_log
+             WHERE ?1 IS NULL OR entity = ?1
+             ORDER BY id DESC
+             LIMIT ?2 OFFSET ?3",
+        )
+        .bind(entity)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let items = rows
+            .iter()
+            .map(|r| AuditEntry {
+                id:        r.get("id"),
+                timestamp: r.get("timestamp"),
+                action:    r.get("action"),
+                entity:    r.get("entity"),
+                entity_id: r.get("entity_id"),
+                details:   r.get("details"),
+            })
+            .collect();
+
+        Ok(Page { items, total_count })
+    }
+
+    // ── Journal applicatif ────────────────────────────────────────────────────
+
+    /// Ajoute une entrée au journal (`level` : 'info' | 'error').
+    pub async fn log_event(&self, level: &str, source: &str, message: &str) -> Result<(), AppError> {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        // Insertion rejouée après `SQLITE_BUSY` : en transaction (voir `retry_busy`)
+        self.write(|| async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(
+                "INSERT INTO app_log (level, source, message, created_at) VALUES (?, ?, ?, ?)",
+            )
+            .bind(level)
+            .bind(source)
+            .bind(message)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await
+        })
+        .await
+    }
+
+    /// Dernières entrées du journal, les plus récentes d'abord.
+    pub async fn get_app_logs(&self, limit: i64) -> Result<Vec<AppLog>, AppError> {
+        let rows = sqlx::query(
+            "SELECT id, level, source, message, created_at
+             FROM app_log
+             ORDER BY id DESC
+             LIMIT ?",
+        )
+        .bind(limit.clamp(1, MAX_PAGE_SIZE))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| AppLog {
+                id:         r.get("id"),
+                level:      r.get("level"),
+                source:     r.get("source"),
+                message:    r.get("message"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
+
+    // ── Paramètres ────────────────────────────────────────────────────────────
+
+    /// Valeur brute d'un paramètre (`None` si la clé n'existe pas).
+    pub async fn get_setting(&self, key: &str) -> Result<Option<String>, AppError> {
+        Ok(sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Crée ou remplace un paramètre.
+    pub async fn set_setting(&self, key: &str, value: &str) -> Result<(), AppError> {
+        if key.trim().is_empty() {
+            return Err(AppError::Validation("La clé du paramètre est obligatoire.".into()));
+        }
+        self.write(|| sqlx::query(UPSERT_SETTING_SQL).bind(key).bind(value).execute(&self.pool))
+            .await?;
+        Ok(())
+    }
+
+    /// Tous les paramètres (clé → valeur), sans l'état du PIN (hash, verrouillage).
+    pub async fn get_all_settings(&self) -> Result<HashMap<String, String>, AppError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM settings WHERE key NOT LIKE 'admin_pin%'")
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Langue de l'interface et des messages d'erreur (malgache par défaut).
+    pub async fn get_language(&self) -> Result<Lang, AppError> {
+        Ok(Lang::parse(self.get_setting("language").await?.as_deref().unwrap_or("")))
+    }
+
+    /// Enregistre la langue et l'applique aux messages renvoyés par ce processus.
+    pub async fn set_language(&self, lang: Lang) -> Result<Lang, AppError> {
+        self.set_setting("language", lang.as_str()).await?;
+        i18n::set_current(lang);
+        Ok(lang)
+    }
+
+    /// Paramètres typés ; les clés absentes ou illisibles prennent leur valeur par défaut.
+    pub async fn get_settings(&self) -> Result<Settings, AppError> {
+        let all = self.get_all_settings().await?;
+        let get = |key: &str| all.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+        Ok(Settings {
+            church_name:      get("church_name").unwrap_or(DEFAULT_CHURCH_NAME).to_string(),
+            church_address:   get("church_address").unwrap_or_default().to_string(),
+            treasurer_name:   get("treasurer_name").unwrap_or_default().to_string(),
+            currency_label:   get("currency_label").unwrap_or(DEFAULT_CURRENCY_LABEL).to_string(),
+            default_pledge:   get("default_pledge")
+                .and_then(|v| Decimal::from_str(v).ok())
+                .unwrap_or(Decimal::ZERO),
+            backup_retention: get("backup_retention")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_BACKUP_RETENTION),
+            quarter_periods:  get("quarter_periods") != Some("0"),
+            startup_integrity_check: get("startup_integrity_check") != Some("0"),
+            auto_close_enabled: get("auto_close_enabled") != Some("0"),
+            auto_close_grace_days: get("auto_close_grace_days")
+                .and_then(|v| v.parse().ok())
+                .filter(|d| *d <= MAX_GRACE_DAYS)
+                .unwrap_or(DEFAULT_GRACE_DAYS),
+            max_future_days:  get("max_future_days")
+                .and_then(|v| v.parse().ok())
+                .filter(|d| *d <= MAX_FUTURE_DAYS)
+                .unwrap_or(DEFAULT_MAX_FUTURE_DAYS),
+            renewal_window_days: get("renewal_window_days")
+                .and_then(|v| v.parse().ok())
+                .filter(|d| *d <= MAX_RENEWAL_WINDOW_DAYS)
+                .unwrap_or(DEFAULT_RENEWAL_WINDOW_DAYS),
+            fiscal_year_start_month: get("fiscal_year_start_month")
+                .and_then(|v| v.parse().ok())
+                .filter(|m| (1..=12).contains(m))
+                .unwrap_or(1),
+            default_period:   get("default_period").unwrap_or_default().to_string(),
+            // Clé absente : montants par défaut ; valeur vide : aucun raccourci
+            quick_amounts:    match all.get("quick_amounts") {
+                Some(v) => v
+                    .split(',')
+                    .filter_map(|a| Decimal::from_str(a.trim()).ok())
+                    .filter(|a| a.is_sign_positive() && !a.is_zero())
+                    .collect(),
+                None => DEFAULT_QUICK_AMOUNTS.iter().map(|&a| Decimal::from(a)).collect(),
+            },
+            card_pattern_communiant: get("card_pattern_communiant")
+                .unwrap_or(DEFAULT_CARD_PATTERN_COMMUNIANT)
+                .to_string(),
+            card_pattern_cathekomen: get("card_pattern_cathekomen")
+                .unwrap_or(DEFAULT_CARD_PATTERN_CATHEKOMEN)
+                .to_string(),
+            operators:        Self::operator_list(get("operators").unwrap_or_default()),
+        })
+    }
+
+    /// Valide puis enregistre tous les paramètres dans une seule transaction.
+    pub async fn update_settings(&self, settings: Settings) -> Result<Settings, AppError> {
+        let church_name = settings.church_name.trim();
+        if church_name.is_empty() {
+            return Err(AppError::validation("settings.church_required", &[]));
+        }
+        if settings.default_pledge.is_sign_negative() {
+            return Err(AppError::validation("settings.pledge_negative", &[]));
+        }
+        Self::validate_backup_retention(settings.backup_retention)?;
+        if settings.auto_close_grace_days > MAX_GRACE_DAYS {
+            return Err(AppError::validation("settings.grace_days_max", &[&MAX_GRACE_DAYS]));
+        }
+        if settings.max_future_days > MAX_FUTURE_DAYS {
+            return Err(AppError::validation("settings.future_days_max", &[&MAX_FUTURE_DAYS]));
+        }
+        if settings.renewal_window_days > MAX_RENEWAL_WINDOW_DAYS {
+            return Err(AppError::validation("settings.renewal_days_max", &[&MAX_RENEWAL_WINDOW_DAYS]));
+        }
+        if !(1..=12).contains(&settings.fiscal_year_start_month) {
+            return Err(AppError::validation(
+                "settings.fiscal_month_invalid",
+                &[&settings.fiscal_year_start_month],
+            ));
+        }
+        let default_period = match settings.default_period.trim() {
+            "" => String::new(),
+            raw => Self::parse_allowed_period(raw, settings.quarter_periods)
+                .ok_or_else(|| AppError::validation("settings.period_invalid", &[&raw]))?
+                .to_string(),
+        };
+        if settings.quick_amounts.len() > MAX_QUICK_AMOUNTS {
+            return Err(AppError::validation("settings.quick_amounts_max", &[&MAX_QUICK_AMOUNTS]));
+        }
+        if settings.quick_amounts.iter().any(|a| a.is_sign_negative() || a.is_zero()) {
+            return Err(AppError::validation("settings.quick_amounts_positive", &[]));
+        }
+        let card_patterns = [
+            settings.card_pattern_communiant.trim(),
+            settings.card_pattern_cathekomen.trim(),
+        ];
+        if let Some(bad) = card_patterns.iter().find(|p| CardPattern::parse(p).is_none()) {
+            return Err(AppError::validation("settings.card_pattern_invalid", &[bad]));
+        }
+        let operators = Self::operator_list(&settings.operators.join(",")).join(",");
+        let quick_amounts = settings
+            .quick_amounts
+            .iter()
+            .map(|a| a.normalize().to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let values = [
+            ("church_name",      church_name.to_string()),
+            ("church_address",   settings.church_address.trim().to_string()),
+            ("treasurer_name",   settings.treasurer_name.trim().to_string()),
+            ("currency_label",   settings.currency_label.trim().to_string()),
+            ("default_pledge",   settings.default_pledge.normalize().to_string()),
+            ("backup_retention", settings.backup_retention.to_string()),
+            ("quarter_periods",  if settings.quarter_periods { "1" } else { "0" }.to_string()),
+            ("startup_integrity_check",
+             if settings.startup_integrity_check { "1" } else { "0" }.to_string()),
+            ("auto_close_enabled",
+             if settings.auto_close_enabled { "1" } else { "0" }.to_string()),
+            ("auto_close_grace_days", settings.auto_close_grace_days.to_string()),
+            ("max_future_days",  settings.max_future_days.to_string()),
+            ("renewal_window_days", settings.renewal_window_days.to_string()),
+            ("fiscal_year_start_month", settings.fiscal_year_start_month.to_string()),
+            ("default_period",   default_period),
+            ("quick_amounts",    quick_amounts),
+            ("card_pattern_communiant", card_patterns[0].to_string()),
+            ("card_pattern_cathekomen", card_patterns[1].to_string()),
+            ("operators",        operators),
+        ];
+        let writer = self.write_lock.lock().await;
+        let mut tx = self.pool.begin().await?;
+        // `recorded_year` dépend du mois de début : le changer une fois des cotisations
+        // saisies décalerait leurs exercices (et ceux des années clôturées)
+        let current_month = sqlx::query_scalar::<_, String>(
+            "SELECT value FROM settings WHERE key = 'fiscal_year_start_month'",
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|m| (1..=12).contains(m))
+        .unwrap_or(1);
+        if settings.fiscal_year_start_month != current_month {
+            let has_contributions: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contributions)")
+                    .fetch_one(&mut *tx)
+                    .await?;
+            if has_contributions {
+                return Err(AppError::validation("settings.fiscal_month_locked", &[]));
+            }
+        }
+        for (key, value) in values {
+            sqlx::query(UPSERT_SETTING_SQL)
+                .bind(key)
+                .bind(value)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        drop(writer);
+        self.get_settings().await
+    }
+
+    /// Liste d'opérateurs séparés par des virgules : noms nettoyés, vides et doublons
+    /// écartés, ordre conservé.
+    fn operator_list(raw: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in raw.split(',').filter_map(|n| Self::operator_name(Some(n))) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    fn validate_backup_retention(keep: usize) -> Result<(), AppError> {
+        if !(1..=365).contains(&keep) {
+            return Err(AppError::Validation(
+                "Le nombre de sauvegardes conservées doit être compris entre 1 et 365.".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Nombre de sauvegardes automatiques conservées (`settings.backup_retention`).
+    pub async fn get_backup_retention(&self) -> Result<usize, AppError> {
+        Ok(self
+            .get_setting("backup_retention")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_BACKUP_RETENTION))
+    }
+
+    pub async fn set_backup_retention(&self, keep: usize) -> Result<(), AppError> {
+        Self::validate_backup_retention(keep)?;
+        self.set_setting("backup_retention", &keep.to_string()).await
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ContributionSearchScope as Scope;
+
+    /// Crée une DB SQLite en mémoire avec migrations appliquées.
+    async fn make_repo() -> Repository {
+        Repository::new(":memory:").await.expect("DB en mémoire")
+    }
+
+    fn member_input(card: &str, name: &str, mtype: &str) -> MemberInput {
+        MemberInput {
+            card_number: card.into(),
+            full_name:   name.into(),
+            address:     None,
+            phone:       None,
+            job:         None,
+            gender:      Gender::M,
+            member_type: mtype.parse().unwrap(),
+            surname:     None,
+            given_name:  None,
+            status:      MemberStatus::Actif,
+            birth_date:  None,
+        }
+    }
+
+    fn contribution_input(member_id: i64, date: &str, period: &str, amount: &str) -> ContributionInput {
+        ContributionInput {
+            member_id,
+            payment_date: date.into(),
+            period:       period.into(),
+            amount:       amount.into(),
+            allow_mismatch: false,
+            recorded_by:  None,
+        }
+    }
+
+    // ── Membres ───────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_create_member_ok() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Jean Dupont", "Communiant")).await.unwrap();
+        assert_eq!(m.card_number, "C001");
+        assert_eq!(m.full_name, "Jean Dupont");
+        assert_eq!(m.member_type, MemberType::Communiant);
+        assert!(m.id > 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_member_carte_vide() {
+        let repo = make_repo().await;
+        let err = repo.create_member(member_input("", "Jean", "Communiant")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_member_nom_vide() {
+        let repo = make_repo().await;
+        let err = repo.create_member(member_input("C001", "  ", "Communiant")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_search_members_lite() {
+        let repo = make_repo().await;
+        let rabe = repo.create_member(member_input("C010", "Rabe Paul", "Communiant")).await.unwrap();
+        let rasoa = repo.create_member(member_input("K020", "Rasoa_Marie", "Cathekomen")).await.unwrap();
+        let supprime = repo.create_member(member_input("C030", "Rabe Supprimé", "Communiant")).await.unwrap();
+        repo.delete_member(supprime.id).await.unwrap();
+
+        let found = repo.search_members_lite(" rabe ").await.unwrap();
+        assert_eq!(found, vec![MemberLite { id: rabe.id, full_name: "Rabe Paul".into(), card_number: "C010".into() }]);
+        // Carte, et `_` pris littéralement
+        assert_eq!(repo.search_members_lite("k02").await.unwrap()[0].id, rasoa.id);
+        assert_eq!(repo.search_members_lite("a_m").await.unwrap().len(), 1);
+        assert!(repo.search_members_lite("  ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_members() {
+        let repo = make_repo().await;
+        let rakoto = repo.create_member(member_input("C001", "Rakoto Jean", "Communiant")).await.unwrap();
+        let rakotto = repo.create_member(member_input("K001", "RAKOTTO Jean", "Cathekomen")).await.unwrap();
+        repo.create_member(member_input("C002", "Rakotomalala Jean", "Communiant")).await.unwrap();
+        let supprime = repo.create_member(member_input("C003", "Jean Rakoto", "Communiant")).await.unwrap();
+        repo.delete_member(supprime.id).await.unwrap();
+
+        let found = repo.find_similar_members("  jean RAKOTO ").await.unwrap();
+        // Exact (une fois réduit) avant la faute de frappe ; supprimés et noms éloignés écartés
+        assert_eq!(found.iter().map(|m| m.id).collect::<Vec<_>>(), vec![rakoto.id, rakotto.id]);
+        assert!(repo.find_similar_members("Rasoa Marie").await.unwrap().is_empty());
+        assert!(repo.find_similar_members("   ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_member_carte_duplicate() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C001", "Jean", "Communiant")).await.unwrap();
+        let err = repo.create_member(member_input("C001", "Pierre", "Communiant")).await.unwrap_err();
+        assert!(matches!(err, AppError::Conflict(_)));
+        assert_eq!(
+            err.to_string(),
+            "Efa an'i Jean ny laharana karatra 'C001'. Mifidiana laharana hafa."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_member_carte_duplicate() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C-0042", "Jean Rakoto", "Communiant")).await.unwrap();
+        let m = repo.create_member(member_input("C-0043", "Pierre", "Cathekomen")).await.unwrap();
+
+        let err = repo
+            .update_member(m.id, member_input("C-0042", "Pierre", "Cathekomen"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Conflict(_)));
+        assert_eq!(
+            err.to_string(),
+            "Efa an'i Jean Rakoto ny laharana karatra 'C-0042'. Mifidiana laharana hafa."
+        );
+        // Membre inchangé
+        assert_eq!(repo.get_member(m.id).await.unwrap().card_number, "C-0043");
+        // Garder son propre numéro n'est pas un conflit
+        repo.update_member(m.id, member_input("C-0043", "Pierre R.", "Cathekomen")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_member_introuvable() {
+        let repo = make_repo().await;
+        let err = repo.get_member(42).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_member_by_card() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C-0042", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K-7", "Bob", "Cathekomen")).await.unwrap();
+
+        // Casse et espaces du lecteur ignorés
+        let found = repo.get_member_by_card("  c-0042\t").await.unwrap().unwrap();
+        assert_eq!(found.id, a.id);
+        assert_eq!(repo.get_member_by_card("K-7").await.unwrap().unwrap().id, b.id);
+
+        // Correspondance exacte seulement : ni préfixe ni vide
+        assert!(repo.get_member_by_card("C-004").await.unwrap().is_none());
+        assert!(repo.get_member_by_card("   ").await.unwrap().is_none());
+
+        // Deux cartes identiques à la casse près : l'exacte l'emporte
+        let c = repo.create_member(member_input("k-7", "Claire", "Communiant")).await.unwrap();
+        assert_eq!(repo.get_member_by_card("k-7").await.unwrap().unwrap().id, c.id);
+        assert_eq!(repo.get_member_by_card("K-7").await.unwrap().unwrap().id, b.id);
+    }
+
+    #[test]
+    fn test_app_error_serialisation() {
+        let json = serde_json::to_value(AppError::Validation("Nom vide".into())).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "validation", "message": "Nom vide" }));
+        let json = serde_json::to_value(AppError::Db).unwrap();
+        assert_eq!(json["kind"], "db");
+        // Relecture côté RemoteClient : le kind est conservé
+        let back: AppError = serde_json::from_str(r#"{"kind":"conflict","message":"Déjà pris"}"#).unwrap();
+        assert!(matches!(back, AppError::Conflict(m) if m == "Déjà pris"));
+    }
+
+    #[tokio::test]
+    async fn test_search_members_tous_types() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C-0042", "Jean Rakoto", "Communiant")).await.unwrap();
+        repo.create_member(MemberInput {
+            phone:   Some("+261341234567".into()),
+            address: Some("Ambalavao Centre".into()),
+            ..member_input("K-0007", "Rasoa Be", "Cathekomen")
+        })
+        .await
+        .unwrap();
+        repo.create_member(member_input("C-0100", "Rabe_50%", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000")).await.unwrap();
+
+        let names =
+            |list: Vec<MemberWithTotal>| list.into_iter().map(|m| m.full_name).collect::<Vec<_>>();
+        // Nom, insensible à la casse, dans les deux types
+        assert_eq!(
+            names(repo.search_members("RA", None).await.unwrap()),
+            ["Jean Rakoto", "Rabe_50%", "Rasoa Be"]
+        );
+        // Carte, téléphone, adresse
+        assert_eq!(names(repo.search_members("c-0042", None).await.unwrap()), ["Jean Rakoto"]);
+        assert_eq!(names(repo.search_members("3412345", None).await.unwrap()), ["Rasoa Be"]);
+        assert_eq!(names(repo.search_members("34 12 345", None).await.unwrap()), ["Rasoa Be"]);
+        assert_eq!(names(repo.search_members("ambalavao", None).await.unwrap()), ["Rasoa Be"]);
+        // Jokers LIKE pris littéralement
+        assert_eq!(names(repo.search_members("_50%", None).await.unwrap()), ["Rabe_50%"]);
+        // Limite et requête vide
+        assert_eq!(repo.search_members("ra", Some(2)).await.unwrap().len(), 2);
+        assert!(repo.search_members("  ", None).await.unwrap().is_empty());
+
+        let jean = &repo.search_members("jean", None).await.unwrap()[0];
+        assert_eq!(jean.total_contributions, Decimal::from(5000));
+        assert_eq!(jean.member_type, MemberType::Communiant);
+    }
+
+    #[tokio::test]
+    async fn test_member_phone_normalise() {
+        let repo = make_repo().await;
+        let m = repo
+            .create_member(MemberInput {
+                phone: Some("034 12 345 67".into()),
+                ..member_input("C001", "Alice", "Communiant")
+            })
+            .await
+            .unwrap();
+        assert_eq!(m.phone.as_deref(), Some("+261 34 12 345 67"));
+        assert_eq!(repo.get_member(m.id).await.unwrap().phone.as_deref(), Some("+261 34 12 345 67"));
+
+        // Champ laissé à l'indicatif seul → pas de téléphone
+        let m = repo
+            .update_member(m.id, MemberInput {
+                phone: Some("+261 ".into()),
+                ..member_input("C001", "Alice", "Communiant")
+            })
+            .await
+            .unwrap();
+        assert_eq!(m.phone, None);
+
+        let err = repo
+            .update_member(m.id, MemberInput {
+                phone: Some("034 12 AB 67".into()),
+                ..member_input("C001", "Alice", "Communiant")
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let err = repo
+            .create_member(MemberInput {
+                phone: Some("034 12 345".into()),
+                ..member_input("C002", "Bob", "Communiant")
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_parties_du_nom() {
+        let repo = make_repo().await;
+        // Ancien client : seul full_name, parties déduites
+        let jean = repo.create_member(member_input("C001", "Jean RAKOTO", "Communiant")).await.unwrap();
+        assert_eq!((jean.surname.as_deref(), jean.given_name.as_deref()), (Some("RAKOTO"), Some("Jean")));
+        // full_name vide : composé à partir des parties
+        let marie = repo
+            .create_member(MemberInput {
+                surname:    Some(" RASOA ".into()),
+                given_name: Some("Marie".into()),
+                ..member_input("C002", "", "Communiant")
+            })
+            .await
+            .unwrap();
+        assert_eq!(marie.full_name, "RASOA Marie");
+        assert_eq!(repo.get_member(marie.id).await.unwrap().surname.as_deref(), Some("RASOA"));
+        // Rien à déduire : tri sur full_name
+        let bako = repo.create_member(member_input("C003", "Bako Randria", "Communiant")).await.unwrap();
+        assert_eq!((bako.surname, bako.given_name), (None, None));
+
+        let err = repo
+            .create_member(MemberInput {
+                surname:    Some("  ".into()),
+                given_name: None,
+                ..member_input("C004", " ", "Communiant")
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let err = repo
+            .update_member(jean.id, MemberInput {
+                given_name: Some("x".repeat(201)),
+                ..member_input("C001", "Jean RAKOTO", "Communiant")
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        // Tri par nom de famille quand il est connu : "Andry RATSIMBA" après RASOA
+        repo.create_member(member_input("C005", "Andry RATSIMBA", "Communiant")).await.unwrap();
+        let page = repo
+            .get_member_rows_for_year(
+                MemberType::Communiant,
+                0,
+                10,
+                MemberSort { col: MemberSortCol::FullName, desc: false },
+                &MemberFilter::default(),
+                2024,
+            )
+            .await
+            .unwrap();
+        let names: Vec<String> = page.items.into_iter().map(|m| m.full_name).collect();
+        assert_eq!(names, ["Bako Randria", "Jean RAKOTO", "RASOA Marie", "Andry RATSIMBA"]);
+    }
+
+    #[tokio::test]
+    async fn test_statut_membre() {
+        let repo = make_repo().await;
+        let alice = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        assert_eq!(alice.status, MemberStatus::Actif);
+        let bob = repo
+            .create_member(MemberInput { status: MemberStatus::Inactif, ..member_input("C002", "Bob", "Communiant") })
+            .await
+            .unwrap();
+        let koto = repo.create_member(member_input("C003", "Koto", "Communiant")).await.unwrap();
+        repo.update_member(koto.id, MemberInput { status: MemberStatus::Decede, ..member_input("C003", "Koto", "Communiant") })
+            .await
+            .unwrap();
+        assert_eq!(repo.get_member(koto.id).await.unwrap().status, MemberStatus::Decede);
+        repo.create_contribution(contribution_input(bob.id, "2024-03-01", "2024", "2000")).await.unwrap();
+
+        let names = |list: Vec<MemberWithTotal>| list.into_iter().map(|m| m.full_name).collect::<Vec<_>>();
+        let all = repo.get_members_by_type_with_total_for_year(MemberType::Communiant, None, 2024).await.unwrap();
+        assert_eq!(names(all), ["Alice", "Bob", "Koto"]);
+        let inactive = repo
+            .get_members_by_type_with_total_for_year(MemberType::Communiant, Some(MemberStatus::Inactif), 2024)
+            .await
+            .unwrap();
+        assert_eq!(inactive[0].current_year_total, Decimal::from(2000));
+        assert_eq!(names(inactive), ["Bob"]);
+
+        let filter = MemberFilter { status: Some(MemberStatus::Decede), ..MemberFilter::default() };
+        let page = repo
+            .get_member_rows_for_year(MemberType::Communiant, 0, 10, MemberSort::default(), &filter, 2024)
+            .await
+            .unwrap();
+        assert_eq!((page.total_count, page.items[0].status), (1, MemberStatus::Decede));
+
+        // Effectifs : actifs seulement ; la cotisation du membre inactif reste comptée
+        let stats = repo.get_dashboard_stats_for_year(2024).await.unwrap();
+        assert_eq!((stats.communiants, stats.men), (1, 1));
+        assert_eq!(stats.current_year_total, Decimal::from(2000));
+
+        assert!(matches!("Mort".parse::<MemberStatus>(), Err(AppError::Validation(_))));
+        assert_eq!("Décédé".parse::<MemberStatus>().unwrap(), MemberStatus::Decede);
+    }
+
+    #[tokio::test]
+    async fn test_totaux_par_annee() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        let k = repo.create_member(member_input("K001", "Koto", "Cathekomen")).await.unwrap();
+        for (id, date, amount) in [
+            (a.id, "2023-05-01", "1000.5"),
+            (a.id, "2023-09-01", "2000"),
+            (a.id, "2024-02-01", "500"),
+            (a.id, "2022-02-01", "9999"),
+            (k.id, "2024-02-01", "700"),
+        ] {
+            repo.create_contribution(contribution_input(id, date, &date[..4], amount)).await.unwrap();
+        }
+
+        let list = repo.get_members_with_year_totals(MemberType::Communiant, &[2023, 2024]).await.unwrap();
+        assert_eq!(list.iter().map(|m| m.member.id).collect::<Vec<_>>(), [a.id, b.id]);
+        let alice = &list[0].year_totals;
+        assert_eq!(alice.get(&2023), Some(&Decimal::from_str("3000.5").unwrap()));
+        assert_eq!(alice.get(&2024), Some(&Decimal::from(500)));
+        // Années non demandées absentes ; rien versé : table vide
+        assert!(!alice.contains_key(&2022));
+        assert!(list[1].year_totals.is_empty());
+        assert_eq!(list[0].member.total_contributions, Decimal::from_str("13499.5").unwrap());
+
+        let none = repo.get_members_with_year_totals(MemberType::Communiant, &[]).await.unwrap();
+        assert!(none.iter().all(|m| m.year_totals.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_membres_par_adresse() {
+        let repo = make_repo().await;
+        let with_address = |card, name, address: Option<&str>| MemberInput {
+            address: address.map(Into::into),
+            ..member_input(card, name, "Communiant")
+        };
+        for (card, name, address) in [
+            ("C001", "Koto",  Some("Tanambao II")),
+            ("C002", "Bako",  None),
+            ("C003", "Alice", Some("  tanambao   ii ")),
+            ("C004", "Hery",  Some("Ambohimena")),
+        ] {
+            repo.create_member(with_address(card, name, address)).await.unwrap();
+        }
+        repo.create_member(MemberInput { address: Some("Ambohimena".into()), ..member_input("K001", "Lova", "Cathekomen") })
+            .await
+            .unwrap();
+
+        let groups = repo.get_members_grouped_by_address(MemberType::Communiant).await.unwrap();
+        let summary: Vec<(Option<&str>, Vec<&str>)> = groups
+            .iter()
+            .map(|g| (g.address.as_deref(), g.members.iter().map(|m| m.full_name.as_str()).collect()))
+            .collect();
+        assert_eq!(summary, [
+            (Some("ambohimena"),  vec!["Hery"]),
+            (Some("tanambao ii"), vec!["Alice", "Koto"]),
+            (None,                vec!["Bako"]),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_normalize_all_phones() {
+        let repo = make_repo().await;
+        let ids = [
+            repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap().id,
+            repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap().id,
+            repo.create_member(member_input("C003", "Carl", "Communiant")).await.unwrap().id,
+            repo.create_member(member_input("C004", "Dina", "Communiant")).await.unwrap().id,
+        ];
+        // Numéros saisis avant la validation
+        for (id, phone) in ids.iter().zip(["0341234567", "+261 32 11 222 33", "  ", "tsy misy"]) {
+            sqlx::query("UPDATE members SET phone = ? WHERE id = ?")
+                .bind(phone)
+                .bind(id)
+                .execute(&repo.pool)
+                .await
+                .unwrap();
+        }
+
+        let report = repo.normalize_all_phones().await.unwrap();
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].card_number, "C004");
+        assert_eq!(report.invalid[0].phone, "tsy misy");
+
+        let phone = |i: usize| {
+            let repo = &repo;
+            async move { repo.get_member(ids[i]).await.unwrap().phone }
+        };
+        assert_eq!(phone(0).await.as_deref(), Some("+261 34 12 345 67"));
+        assert_eq!(phone(1).await.as_deref(), Some("+261 32 11 222 33"));
+        assert_eq!(phone(2).await, None);
+        assert_eq!(phone(3).await.as_deref(), Some("tsy misy"));
+
+        // Deuxième passage : plus rien à réécrire
+        assert_eq!(repo.normalize_all_phones().await.unwrap().updated, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_members_vide() {
+        let repo = make_repo().await;
+        let list = repo.get_members().await.unwrap();
+        assert!(list.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_members() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        let list = repo.get_members().await.unwrap();
+        assert_eq!(list.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_members_by_type() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_member(member_input("C003", "Carol", "Communiant")).await.unwrap();
+
+        let comm = repo.get_members_by_type(MemberType::Communiant).await.unwrap();
+        assert_eq!(comm.len(), 2);
+
+        let cath = repo.get_members_by_type(MemberType::Cathekomen).await.unwrap();
+        assert_eq!(cath.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_member() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let updated = repo.update_member(m.id, member_input("C001-U", "Alice Martin", "Communiant")).await.unwrap();
+        assert_eq!(updated.card_number, "C001-U");
+        assert_eq!(updated.full_name, "Alice Martin");
+    }
+
+    #[tokio::test]
+    async fn test_delete_member_cascade() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        repo.delete_member(m.id).await.unwrap();
+        let list = repo.get_members().await.unwrap();
+        assert!(list.is_empty());
+        // Purge : les cotisations partent en cascade
+        repo.purge_member(m.id).await.unwrap();
+        let contribs = repo.get_contributions(m.id).await.unwrap();
+        assert!(contribs.is_empty());
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::ZERO);
+        assert!(repo.get_deleted_members().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_member_puis_restauration() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-04-01", "2024", "2000")).await.unwrap();
+
+        repo.delete_member(a.id).await.unwrap();
+
+        // Absent des listes, recherches et effectifs…
+        assert_eq!(repo.get_members().await.unwrap().len(), 1);
+        assert!(repo.get_members_by_type(MemberType::Communiant).await.unwrap().is_empty());
+        assert!(repo
+            .get_members_by_type_with_total(MemberType::Communiant, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(repo.get_member_by_card("C001").await.unwrap().is_none());
+        assert!(repo.search_members("alice", None).await.unwrap().is_empty());
+        assert_eq!(repo.get_member_counts().await.unwrap().communiants, 0);
+        let page = repo.get_contributions_by_year_with_member(Some(2024), None, Scope::All, None, None).await.unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].member_name, "Bob");
+
+        // … mais ses cotisations comptent toujours
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from_str("7000").unwrap());
+        assert_eq!(summary.total_communiant, Decimal::from_str("5000").unwrap());
+        assert_eq!(summary.contributor_count, 2);
+        assert_eq!(repo.get_contributions(a.id).await.unwrap().len(), 1);
+
+        let trash = repo.get_deleted_members().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!((trash[0].id, trash[0].contribution_count), (a.id, 1));
+
+        // Carte toujours réservée
+        let err = repo.create_member(member_input("C001", "Autre", "Communiant")).await.unwrap_err();
+        assert!(matches!(err, AppError::Conflict(m) if m.contains("(voafafa)")));
+
+        // Plus de nouvelle cotisation tant qu'il est dans la corbeille
+        let err = repo
+            .create_contribution(contribution_input(a.id, "2024-05-01", "2024", "1000"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.key == "contribution.member_missing"));
+        assert_eq!(repo.get_contributions(a.id).await.unwrap().len(), 1);
+
+        // Membre actif : ni restauration ni purge
+        assert!(matches!(repo.restore_member(b.id).await, Err(AppError::NotFound(_))));
+        assert!(matches!(repo.purge_member(b.id).await, Err(AppError::Validation(_))));
+
+        let restored = repo.restore_member(a.id).await.unwrap();
+        assert_eq!(restored.full_name, "Alice");
+        assert_eq!(repo.get_members().await.unwrap().len(), 2);
+        assert!(repo.get_deleted_members().await.unwrap().is_empty());
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from_str("7000").unwrap());
+        repo.create_contribution(contribution_input(a.id, "2024-05-01", "2024", "1000")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_merge_members() {
+        let repo = make_repo().await;
+        let keep = repo.create_member(member_input("C001", "Rakoto Jean", "Communiant")).await.unwrap();
+        let mut dup = member_input("C002", "RAKOTO Jean", "Communiant");
+        dup.phone = Some("0341234567".into());
+        dup.job   = Some("Mpamboly".into());
+        let dup = repo.create_member(dup).await.unwrap();
+        let other = repo.create_member(member_input("K001", "Soa", "Cathekomen")).await.unwrap();
+        for (id, date, amount) in [
+            (keep.id, "2024-02-10", "1000"),
+            (dup.id,  "2024-03-10", "2500"),
+            (dup.id,  "2025-01-05", "3000"),
+            (other.id, "2024-04-01", "500"),
+        ] {
+            let period = &date[..7];
+            repo.create_contribution(contribution_input(id, date, period, amount)).await.unwrap();
+        }
+        let before_2024 = repo.get_year_summary(2024).await.unwrap().unwrap();
+
+        // Refus : même fiche, types différents, fiche absente
+        assert!(matches!(repo.merge_members(keep.id, keep.id).await, Err(AppError::Validation(_))));
+        assert!(matches!(repo.merge_members(keep.id, other.id).await, Err(AppError::Validation(_))));
+        assert!(matches!(repo.merge_members(keep.id, 999).await, Err(AppError::NotFound(_))));
+
+        let merged = repo.merge_members(keep.id, dup.id).await.unwrap();
+        assert_eq!(merged.card_number, "C001");
+        assert_eq!(merged.phone.as_deref(), dup.phone.as_deref());
+        assert_eq!(merged.job.as_deref(), Some("Mpamboly"));
+        assert!(matches!(repo.get_member(dup.id).await, Err(AppError::NotFound(_))));
+        assert_eq!(repo.get_contributions(keep.id).await.unwrap().len(), 3);
+
+        let after_2024 = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(after_2024.total, before_2024.total);
+        assert_eq!(after_2024.total_communiant, Decimal::from(3500));
+        assert_eq!(after_2024.total_cathekomen, Decimal::from(500));
+        assert_eq!(after_2024.contribution_count, 3);
+        assert_eq!(after_2024.contributor_count, 2);
+        let after_2025 = repo.get_year_summary(2025).await.unwrap().unwrap();
+        assert_eq!(after_2025.total, Decimal::from(3000));
+
+        let log = repo.get_audit_log(None, None, Some("member")).await.unwrap();
+        assert!(log.items.iter().any(|e| e.action == "merge" && e.entity_id == keep.id));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_members() {
+        let repo = make_repo().await;
+        let m1 = repo.create_member(member_input("C001", "Alice", "Cathekomen")).await.unwrap();
+        let m2 = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        assert_eq!(m1.transferred_at, None);
+        let moved = repo.transfer_members(&[m2.id, m1.id, m2.id], MemberType::Communiant).await.unwrap();
+        assert_eq!(moved.iter().map(|m| m.id).collect::<Vec<_>>(), [m1.id, m2.id]);
+        assert!(moved.iter().all(|m| m.member_type == MemberType::Communiant));
+        let at = moved[0].transferred_at.clone().expect("transferred_at renseigné");
+        assert_eq!(repo.get_member(m1.id).await.unwrap().transferred_at, Some(at));
+        let comm = repo.get_members_by_type(MemberType::Communiant).await.unwrap();
+        assert_eq!(comm.len(), 2);
+        let cath = repo.get_members_by_type(MemberType::Cathekomen).await.unwrap();
+        assert!(cath.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ids_introuvables() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("K001", "Alice", "Cathekomen")).await.unwrap();
+        let b = repo.create_member(member_input("K002", "Bob", "Cathekomen")).await.unwrap();
+        repo.delete_member(b.id).await.unwrap();
+
+        let err = repo.transfer_members(&[a.id, b.id, 9999], MemberType::Communiant).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(err.to_string().ends_with(&format!("{}, 9999.", b.id)), "{err}");
+        // Tout ou rien : le membre valide n'a pas bougé
+        let a = repo.get_member(a.id).await.unwrap();
+        assert_eq!(a.member_type, MemberType::Cathekomen);
+        assert_eq!(a.transferred_at, None);
+        assert_eq!(repo.get_audit_log(None, None, None).await.unwrap().items[0].action, "delete");
+    }
+
+    #[tokio::test]
+    async fn test_transfer_ids_vides() {
+        let repo = make_repo().await;
+        let moved = repo.transfer_members(&[], MemberType::Communiant).await.unwrap();
+        assert!(moved.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_members_csv_rapport() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C001", "Existant", "Communiant")).await.unwrap();
+
+        let csv = "\u{FEFF}numero_carte,nom_complet,adresse,telephone,travail,genre,type\n\
+                   C001,Doublon base,,,,M\n\
+                   C002,\"Rabe, Jean\",Ambalavao,0341234567,Mpamboly,m\n\
+                   \n\
+                   C003,,,,,F\n\
+                   C004,Rasoa,,,,F,Cathekomen\n\
+                   C002,Doublon fichier,,,,M\n\
+                   C005,Genre inconnu,,,,X\n\
+                   C006,Trop court\n\
+                   C007,Rakoto,,,,M,Mpitondra\n";
+        let report = repo.import_members_csv(csv, MemberType::Communiant).await.unwrap();
+
+        assert_eq!(report.inserted, 2);
+        assert_eq!(report.skipped, 6);
+        let lines: Vec<usize> = report.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 5, 7, 8, 9, 10]);
+        assert!(report.errors[0].message.contains("membre existant"));
+        assert!(report.errors[1].message.contains("nom complet"));
+        assert!(report.errors[2].message.contains("ligne 3"));
+
+        let rabe = repo.get_member_by_card("C002").await.unwrap().unwrap();
+        assert_eq!(rabe.full_name, "Rabe, Jean");
+        assert_eq!(rabe.gender, Gender::M);
+        assert_eq!(rabe.member_type, MemberType::Communiant);
+        let rasoa = repo.get_member_by_card("C004").await.unwrap().unwrap();
+        assert_eq!(rasoa.member_type, MemberType::Cathekomen);
+        assert_eq!(repo.get_member_by_card("C001").await.unwrap().unwrap().full_name, "Existant");
+        assert_eq!(repo.get_members().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_members_csv_sans_entete() {
+        let repo = make_repo().await;
+        let report = repo
+            .import_members_csv("K01,Rabe,,,,M\r\nK02,Rasoa,,,,F\r\n", MemberType::Cathekomen)
+            .await
+            .unwrap();
+        assert_eq!((report.inserted, report.skipped), (2, 0));
+        assert!(report.errors.is_empty());
+        assert_eq!(repo.get_members_by_type(MemberType::Cathekomen).await.unwrap().len(), 2);
+    }
+
+    // ── Gender / MemberType ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_enums_serde_aller_retour() {
+        for g in [Gender::M, Gender::F] {
+            let json = serde_json::to_string(&g).unwrap();
+            assert_eq!(json, format!("\"{}\"", g.as_str()));
+            assert_eq!(serde_json::from_str::<Gender>(&json).unwrap(), g);
+        }
+        for t in [MemberType::Communiant, MemberType::Cathekomen] {
+            let json = serde_json::to_string(&t).unwrap();
+            assert_eq!(json, format!("\"{}\"", t.as_str()));
+            assert_eq!(serde_json::from_str::<MemberType>(&json).unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn test_member_input_valeurs_invalides() {
+        let json = r#"{"card_number":"C001","full_name":"Alice","address":null,"phone":null,
+                       "job":null,"gender":"X","member_type":"Communiant"}"#;
+        let err = serde_json::from_str::<MemberInput>(json).unwrap_err().to_string();
+        assert!(err.contains("'M', 'F'"), "{err}");
+
+        let json = json.replace("\"X\"", "\"F\"").replace("Communiant", "Communant");
+        let err = serde_json::from_str::<MemberInput>(&json).unwrap_err().to_string();
+        assert!(err.contains("'Communiant', 'Cathekomen'"), "{err}");
+    }
+
+    #[test]
+    fn test_member_type_from_str_invalide() {
+        let err = "communiant".parse::<MemberType>().unwrap_err();
+        assert!(err.to_string().contains("Valeurs acceptées"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_notes_membre() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Alice R.", "Communiant")).await.unwrap();
+
+        let first = repo.add_member_note(a.id, "  Nifindra tany Antananarivo 2024 ".into()).await.unwrap();
+        assert_eq!(first.text, "Nifindra tany Antananarivo 2024");
+        let second = repo.add_member_note(a.id, "Tian'ny hotsidihina ao an-trano".into()).await.unwrap();
+        let notes = repo.get_member_notes(a.id).await.unwrap();
+        assert_eq!(notes, [second.clone(), first.clone()]);
+
+        let err = repo.add_member_note(a.id, " \n ".into()).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let err = repo.add_member_note(a.id, "x".repeat(MAX_MEMBER_NOTE_LEN + 1)).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let err = repo.add_member_note(9999, "Note".into()).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        repo.delete_member_note(first.id).await.unwrap();
+        assert_eq!(repo.get_member_notes(a.id).await.unwrap(), [second]);
+        let err = repo.delete_member_note(first.id).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        // Fusion : les notes suivent la fiche conservée
+        repo.add_member_note(b.id, "Doublon".into()).await.unwrap();
+        repo.merge_members(a.id, b.id).await.unwrap();
+        assert_eq!(repo.get_member_notes(a.id).await.unwrap().len(), 2);
+
+        // Purge : notes effacées avec le membre
+        repo.delete_member(a.id).await.unwrap();
+        repo.purge_member(a.id).await.unwrap();
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM member_notes")
+            .fetch_one(&repo.pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+
+    // ── Total contributions membre ─────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_total_contributions_zero() {
+        let repo = make_repo().await;
+        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let list = repo.get_members_by_type_with_total(MemberType::Communiant, None).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].total_contributions, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_total_contributions_somme() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-01-15", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-06-01", "2024", "5000.50")).await.unwrap();
+        for _ in 0..3 {
+            repo.create_contribution(contribution_input(m.id, "2024-07-01", "2024", "0.10")).await.unwrap();
+        }
+        let list = repo.get_members_by_type_with_total(MemberType::Communiant, None).await.unwrap();
+        assert_eq!(list[0].total_contributions, Decimal::from_str("15000.80").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_member_counts() {
+        let repo = make_repo().await;
+        assert_eq!(repo.get_member_counts().await.unwrap(), MemberCounts::default());
+        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        repo.create_member(member_input("K001", "Koto", "Cathekomen")).await.unwrap();
+        let counts = repo.get_member_counts().await.unwrap();
+        assert_eq!(counts, MemberCounts { communiants: 2, cathekomens: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_total_annee_courante() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-05-10", "2023", "8000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "3000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2023-07-01", "2023", "4000")).await.unwrap();
+
+        let list = repo
+            .get_members_by_type_with_total_for_year(MemberType::Communiant, None, 2024)
+            .await
+            .unwrap();
+        assert_eq!(list[0].full_name, "Alice");
+        assert_eq!(list[0].total_contributions, Decimal::from(11000));
+        assert_eq!(list[0].current_year_total, Decimal::from(3000));
+        // Bob a cotisé, mais pas cette année
+        assert_eq!(list[1].total_contributions, Decimal::from(4000));
+        assert_eq!(list[1].current_year_total, Decimal::ZERO);
+    }
+
+    /// 7 Communiants (dont 3 femmes) + 1 Cathekomen ; cotisations 2024 pour C001 et C005.
+    async fn repo_member_rows() -> Repository {
+        let repo = make_repo().await;
+        let mut ids = Vec::new();
+        for (card, name, gender, job) in [
+            ("C001", "Andry",    Gender::M, Some("Mpampianatra")),
+            ("C002", "Bako",     Gender::F, None),
+            ("C003", "Faly",     Gender::M, Some("Mpamboly")),
+            ("C004", "Hery",     Gender::M, None),
+            ("C005", "Lalao",    Gender::F, Some("Mpampianatra")),
+            ("C006", "Nirina",   Gender::F, None),
+            ("C007", "Rivo_50%", Gender::M, None),
+        ] {
+            let mut input = member_input(card, name, "Communiant");
+            input.gender = gender;
+            input.job    = job.map(Into::into);
+            ids.push(repo.create_member(input).await.unwrap().id);
+        }
+        repo.create_member(member_input("K001", "Koto", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(ids[0], "2024-01-05", "2024", "3000")).await.unwrap();
+        repo.create_contribution(contribution_input(ids[4], "2024-02-05", "2024", "9000")).await.unwrap();
+        repo.create_contribution(contribution_input(ids[1], "2023-03-05", "2023", "1000")).await.unwrap();
+        repo
+    }
+
+    async fn rows(
+        repo: &Repository,
+        page: i64,
+        page_size: i64,
+        sort: MemberSort,
+        filter: MemberFilter,
+    ) -> (Vec<String>, i64) {
+        let p = repo
+            .get_member_rows_for_year(MemberType::Communiant, page, page_size, sort, &filter, 2024)
+            .await
+            .unwrap();
+        (p.items.into_iter().map(|m| m.full_name).collect(), p.total_count)
+    }
+
+    fn sort(col: MemberSortCol, desc: bool) -> MemberSort {
+        MemberSort { col, desc }
+    }
+
+    #[tokio::test]
+    async fn test_member_rows_pagination_et_tri() {
+        let repo = repo_member_rows().await;
+        let by_name = sort(MemberSortCol::FullName, false);
+
+        let (p0, total) = rows(&repo, 0, 3, by_name, MemberFilter::default()).await;
+        assert_eq!(total, 7);
+        assert_eq!(p0, ["Andry", "Bako", "Faly"]);
+        let (p2, _) = rows(&repo, 2, 3, by_name, MemberFilter::default()).await;
+        assert_eq!(p2, ["Rivo_50%"]);
+        let (hors, total) = rows(&repo, 9, 3, by_name, MemberFilter::default()).await;
+        assert!(hors.is_empty());
+        assert_eq!(total, 7);
+
+        let (desc, _) = rows(&repo, 0, 2, sort(MemberSortCol::FullName, true), MemberFilter::default()).await;
+        assert_eq!(desc, ["Rivo_50%", "Nirina"]);
+
+        // Total de l'année, ex æquo départagés par id
+        let (top, _) = rows(&repo, 0, 3, sort(MemberSortCol::CurrentYearTotal, true), MemberFilter::default()).await;
+        assert_eq!(top, ["Lalao", "Andry", "Rivo_50%"]);
+
+        // M avant F, comme l'enum côté frontend
+        let (genre, _) = rows(&repo, 0, 7, sort(MemberSortCol::Gender, false), MemberFilter::default()).await;
+        assert_eq!(genre, ["Andry", "Faly", "Hery", "Rivo_50%", "Bako", "Lalao", "Nirina"]);
+
+        // Valeurs NULL triées comme des chaînes vides
+        let (job, _) = rows(&repo, 0, 7, sort(MemberSortCol::Job, true), MemberFilter::default()).await;
+        assert_eq!(job[..3], ["Lalao", "Andry", "Faly"]);
+    }
+
+    #[tokio::test]
+    async fn test_member_rows_filtres() {
+        let repo = repo_member_rows().await;
+        let by_name = sort(MemberSortCol::FullName, false);
+
+        let femmes = MemberFilter { gender: Some(Gender::F), ..Default::default() };
+        let (list, total) = rows(&repo, 0, 2, by_name, femmes).await;
+        assert_eq!(total, 3);
+        assert_eq!(list, ["Bako", "Lalao"]);
+
+        // Recherche insensible à la casse, sur le travail aussi
+        let search = MemberFilter { search: "mpampianatra".into(), ..Default::default() };
+        let (list, _) = rows(&repo, 0, 10, by_name, search).await;
+        assert_eq!(list, ["Andry", "Lalao"]);
+
+        // `%` et `_` recherchés littéralement
+        let search = MemberFilter { search: "_50%".into(), ..Default::default() };
+        assert_eq!(rows(&repo, 0, 10, by_name, search).await.0, ["Rivo_50%"]);
+        let search = MemberFilter { search: "%".into(), ..Default::default() };
+        assert_eq!(rows(&repo, 0, 10, by_name, search).await.1, 1);
+
+        // Sans cotisation en 2024 (Bako a cotisé en 2023 seulement), combiné au genre
+        let unpaid = MemberFilter { unpaid_only: true, ..Default::default() };
+        let (list, total) = rows(&repo, 0, 10, by_name, unpaid).await;
+        assert_eq!(total, 5);
+        assert!(!list.contains(&"Andry".to_string()));
+        let unpaid_f = MemberFilter { unpaid_only: true, gender: Some(Gender::F), ..Default::default() };
+        let (list, _) = rows(&repo, 1, 1, sort(MemberSortCol::FullName, true), unpaid_f).await;
+        assert_eq!(list, ["Bako"]);
+
+        // Même ensemble par la requête dédiée, tous types ou un seul
+        let names = |list: Vec<Member>| list.into_iter().map(|m| m.full_name).collect::<Vec<_>>();
+        let unpaid = repo.get_members_without_contribution(2024, Some("Communiant")).await.unwrap();
+        assert_eq!(unpaid.len(), 5);
+        assert!(!names(unpaid).contains(&"Andry".to_string()));
+        let all = names(repo.get_members_without_contribution(2023, None).await.unwrap());
+        assert!(all.contains(&"Koto".to_string()) && !all.contains(&"Bako".to_string()));
+        let err = repo.get_members_without_contribution(2024, Some("Diakona")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        // Les lignes ne portent que le total de l'année demandée
+        let p = repo
+            .get_member_rows_for_year(MemberType::Communiant, 0, 1, by_name, &MemberFilter::default(), 2024)
+            .await
+            .unwrap();
+        assert_eq!(p.items[0].current_year_total, Decimal::from(3000));
+    }
+
+    // ── Contributions ─────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_create_contribution_ok() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "12000")).await.unwrap();
+        assert_eq!(c.member_id, m.id);
+        assert_eq!(c.period, "2024");
+        assert_eq!(c.recorded_year, 2024);
+        assert_eq!(c.amount.to_string(), "12000");
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_contributions() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        for date in ["2024-01-10", "2024-06-01", "2023-12-24", "2024-06-01"] {
+            repo.create_contribution(contribution_input(a.id, date, &date[..4], "1000")).await.unwrap();
+        }
+        repo.create_contribution(contribution_input(b.id, "2024-12-01", "2024", "500")).await.unwrap();
+
+        let recent = repo.get_recent_contributions(a.id, 3).await.unwrap();
+        let dates: Vec<&str> = recent.iter().map(|c| c.payment_date.as_str()).collect();
+        assert_eq!(dates, ["2024-06-01", "2024-06-01", "2024-01-10"]);
+        // Même date : la dernière saisie d'abord
+        assert!(recent[0].id > recent[1].id);
+        assert!(recent.iter().all(|c| c.member_id == a.id));
+        // Limite bornée à 1 au minimum
+        assert_eq!(repo.get_recent_contributions(a.id, 0).await.unwrap().len(), 1);
+        assert!(repo.get_recent_contributions(9999, 5).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_contribution_montant_invalide() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let err = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "abc")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_contribution_montant_negatif() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let err = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "-500")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_contribution_date_invalide() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let err = repo.create_contribution(contribution_input(m.id, "15-03-2024", "2024", "1000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_create_contribution_periode_trimestre() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-05-02", " 2024-T2 ", "3000")).await.unwrap();
+        assert_eq!(c.period, "2024-T2");
+        // Forme canonique enregistrée
+        let c = repo.create_contribution(contribution_input(m.id, "2024-05-02", "2024-t3", "3000")).await.unwrap();
+        assert_eq!(c.period, "2024-T3");
+        let c = repo.create_contribution(contribution_input(m.id, "2024-05-02", "2024-5", "3000")).await.unwrap();
+        assert_eq!(c.period, "2024-05");
+
+        for period in ["2024-T5", "24", "2024-13", "Taona 2024", "Mai 2024"] {
+            let err = repo.create_contribution(contribution_input(m.id, "2024-05-02", period, "3000")).await.unwrap_err();
+            assert!(matches!(err, AppError::Validation(_)), "{period}");
+        }
+        let err = repo.create_contribution(contribution_input(m.id, "2024-05-02", "Mai 2024", "3000")).await.unwrap_err();
+        assert!(err.to_string().contains("'2025-03'"), "{err}");
+
+        // Trimestres désactivés dans les paramètres : année et mois seulement
+        repo.set_setting("quarter_periods", "0").await.unwrap();
+        let err = repo.create_contribution(contribution_input(m.id, "2024-05-02", "2024-T2", "3000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        repo.create_contribution(contribution_input(m.id, "2024-05-02", "2024-05", "3000")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_normalize_legacy_periods() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        // Saisies antérieures à la validation, écrites directement
+        for period in ["Janvier 2024", "2024-t1", "2024-t1", "Fety 2024", "2024"] {
+            let c = repo.create_contribution(contribution_input(m.id, "2024-02-01", "2024", "1000")).await.unwrap();
+            sqlx::query("UPDATE contributions SET period = ? WHERE id = ?")
+                .bind(period)
+                .bind(c.id)
+                .execute(&repo.pool)
+                .await
+                .unwrap();
+        }
+
+        let report = repo.normalize_legacy_periods().await.unwrap().unwrap();
+        assert_eq!(report.rewritten, vec![
+            ("2024-t1".to_string(), "2024-T1".to_string(), 2),
+            ("Janvier 2024".to_string(), "2024-01".to_string(), 1),
+        ]);
+        assert_eq!(report.unparseable, vec!["Fety 2024".to_string()]);
+        assert_eq!(
+            repo.get_distinct_periods(2024).await.unwrap(),
+            vec!["2024", "2024-01", "2024-T1", "Fety 2024"]
+        );
+
+        // Une seule fois par base
+        assert_eq!(repo.normalize_legacy_periods().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_contribution_periode_autre_annee() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let err = repo.create_contribution(contribution_input(m.id, "2025-01-05", "2024", "5000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        // Dérogation explicite : arriéré 2024 payé en janvier 2025
+        let mut input = contribution_input(m.id, "2025-01-05", "2024", "5000");
+        input.allow_mismatch = true;
+        let c = repo.create_contribution(input).await.unwrap();
+        assert_eq!(c.period, "2024");
+        assert_eq!(c.recorded_year, 2025);
+    }
+
+    #[test]
+    fn test_exercice_de_la_date() {
+        // Année civile : coupure au 1er janvier ; mois hors de 2..=12 traité comme janvier
+        for start in [0, 1, 13] {
+            assert_eq!(fiscal_year_of(date("2024-12-31"), start), 2024);
+            assert_eq!(fiscal_year_of(date("2025-01-01"), start), 2025);
+        }
+        // Juillet → juin : coupure entre le 30 juin et le 1er juillet
+        assert_eq!(fiscal_year_of(date("2025-06-30"), 7), 2024);
+        assert_eq!(fiscal_year_of(date("2025-07-01"), 7), 2025);
+        assert_eq!(fiscal_year_of(date("2024-12-31"), 7), 2024);
+        assert_eq!(fiscal_year_of(date("2025-01-01"), 7), 2024);
+        // Décembre → novembre
+        assert_eq!(fiscal_year_of(date("2024-11-30"), 12), 2023);
+        assert_eq!(fiscal_year_of(date("2024-12-01"), 12), 2024);
+
+        assert_eq!(fiscal_year_start(2024, 7), Some(date("2024-07-01")));
+        assert_eq!(fiscal_year_start(2024, 1), Some(date("2024-01-01")));
+        assert_eq!(fiscal_year_start(2024, 0), Some(date("2024-01-01")));
+    }
+
+    #[test]
+    fn test_dates_de_paiement_bornees() {
+        let rules = |fiscal_start| PeriodRules {
+            quarters:       true,
+            allow_mismatch: false,
+            latest_date:    Some(date("2025-03-10")),
+            fiscal_start,
+        };
+        let check = |d: &str, period: &str, fiscal_start| {
+            Repository::validate_contribution_fields(d, period, "1000", rules(fiscal_start))
+                .map(|(_, year, _)| year)
+        };
+
+        // Dernier jour admis inclus, lendemain refusé
+        assert_eq!(check("2025-03-10", "2025", 1).unwrap(), 2025);
+        let err = check("2025-03-11", "2025", 1).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(err.to_string().contains("2025-03-10"), "{err}");
+        assert!(check("2099-01-01", "2099", 1).is_err());
+        // Plancher : 1990 accepté, 1989 refusé
+        assert_eq!(check("1990-01-01", "1990", 1).unwrap(), 1990);
+        let err = check("1989-12-31", "1989", 1).unwrap_err();
+        assert!(err.to_string().contains(&MIN_PAYMENT_YEAR.to_string()), "{err}");
+        // Sans limite (restauration) : date lointaine admise
+        let sans_limite = PeriodRules { latest_date: None, ..rules(1) };
+        assert!(Repository::validate_contribution_fields("2099-01-01", "2099", "1", sans_limite).is_ok());
+
+        // Exercice juillet → juin : l'année enregistrée suit l'exercice
+        assert_eq!(check("2024-06-30", "2024-06", 7).unwrap(), 2023);
+        assert_eq!(check("2024-07-01", "2024-07", 7).unwrap(), 2024);
+        assert_eq!(check("2025-01-15", "2025-01", 7).unwrap(), 2024);
+        // Période de l'année civile ou de l'exercice, pas d'une autre
+        assert_eq!(check("2025-01-15", "2024", 7).unwrap(), 2024);
+        assert!(check("2025-01-15", "2023", 7).is_err());
+        // Année civile : l'exercice n'élargit rien
+        assert!(check("2025-01-15", "2024", 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_date_future_sans_resume_annuel() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let err = repo
+            .create_contribution(contribution_input(m.id, "2099-01-01", "2099", "5000"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(repo.get_year_summary(2099).await.unwrap().is_none());
+
+        // Avance réglable : aujourd'hui + 3 jours passe, + 4 non
+        repo.set_setting("max_future_days", "3").await.unwrap();
+        let today = chrono::Local::now().date_naive();
+        let plus = |days| (today + chrono::Days::new(days)).format("%Y-%m-%d").to_string();
+        let mut ok = contribution_input(m.id, &plus(3), "2000", "5000");
+        ok.allow_mismatch = true;
+        repo.create_contribution(ok).await.unwrap();
+        let mut trop = contribution_input(m.id, &plus(4), "2000", "5000");
+        trop.allow_mismatch = true;
+        assert!(repo.create_contribution(trop).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exercice_juillet_juin() {
+        let repo = make_repo().await;
+        repo.set_setting("fiscal_year_start_month", "7").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+
+        let juin = repo.create_contribution(contribution_input(m.id, "2024-06-30", "2024-06", "1000")).await.unwrap();
+        let juillet = repo.create_contribution(contribution_input(m.id, "2024-07-01", "2024-07", "2000")).await.unwrap();
+        let janvier = repo.create_contribution(contribution_input(m.id, "2025-01-10", "2025-01", "4000")).await.unwrap();
+        assert_eq!((juin.recorded_year, juillet.recorded_year, janvier.recorded_year), (2023, 2024, 2024));
+        assert_eq!(juillet.receipt_number, "2024-00001");
+        assert_eq!(repo.get_year_summary(2023).await.unwrap().unwrap().total, Decimal::from(1000));
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(6000));
+
+        // Exercice 2024–2025 en cours jusqu'au 30 juin 2025 : l'exercice précédent est 2023
+        let status = repo.check_and_close_previous_year_on(date("2025-06-30"), Some(15)).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::Closed { ref summary } if summary.year == 2023));
+        // Au 1er juillet, 2024 devient l'exercice précédent ; délai compté depuis juillet
+        let status = repo.check_and_close_previous_year_on(date("2025-07-10"), Some(15)).await.unwrap();
+        assert!(matches!(
+            status,
+            YearCloseStatus::PendingUserAction { year: 2024, ref auto_close_on, .. }
+                if auto_close_on == "2025-07-16"
+        ));
+        let status = repo.check_and_close_previous_year_on(date("2025-07-16"), Some(15)).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::Closed { ref summary } if summary.year == 2024));
+
+        // Retour à l'année civile : les saisies suivantes reprennent l'année de la date
+        repo.set_setting("fiscal_year_start_month", "1").await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2025-08-01", "2025-08", "500")).await.unwrap();
+        assert_eq!(c.recorded_year, 2025);
+    }
+
+    #[tokio::test]
+    async fn test_get_distinct_periods() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-08-01", "2024-T3", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-02-01", "2024", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-03-01", "2023", "1000")).await.unwrap();
+
+        assert_eq!(repo.get_distinct_periods(2024).await.unwrap(), vec!["2024", "2024-T3"]);
+        assert!(repo.get_distinct_periods(2022).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_contribution_recalcule_total() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c1 = repo.create_contribution(contribution_input(m.id, "2024-01-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-06-01", "2024", "5000")).await.unwrap();
+
+        let s = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s.total, Decimal::from_str("15000").unwrap());
+
+        repo.delete_contribution(c1.id).await.unwrap();
+        let s2 = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s2.total, Decimal::from_str("5000").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_receipt_numbers() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Rakoto", "Communiant")).await.unwrap();
+        let a = repo.create_contribution(contribution_input(m.id, "2025-01-10", "2025", "1000")).await.unwrap();
+        let b = repo.create_contribution(contribution_input(m.id, "2025-02-10", "2025", "2000")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-12-30", "2024", "3000")).await.unwrap();
+        // Consécutifs dans une même année, recommence à 1 pour une autre année
+        assert_eq!(a.receipt_number, "2025-00001");
+        assert_eq!(b.receipt_number, "2025-00002");
+        assert_eq!(c.receipt_number, "2024-00001");
+
+        let found = repo.get_contribution_by_receipt(" 2025-00002 ").await.unwrap();
+        assert_eq!((found.id, found.member_name.as_str()), (b.id, "Rakoto"));
+        let settings = Settings {
+            church_name:    "FJKM <Isotry>".into(),
+            treasurer_name: "Rasoa".into(),
+            ..repo.get_settings().await.unwrap()
+        };
+        let html = crate::export::build_receipt_html(&found, &settings);
+        assert!(html.contains("Rosia n° 2025-00002"));
+        assert!(html.contains("FJKM &lt;Isotry&gt;"));
+        assert!(html.contains("Rasoa"));
+        assert!(html.contains("Vola (Ariary)"));
+        assert!(matches!(
+            repo.get_contribution_by_receipt("2025-00099").await,
+            Err(AppError::NotFound(_))
+        ));
+
+        // Le numéro ne change pas quand la cotisation change d'année
+        repo.set_pin("1234").await.unwrap();
+        let moved = repo.update_contribution(a.id, edit_input("2024-12-31", "2024", "1000")).await.unwrap();
+        assert_eq!(moved.receipt_number, "2025-00001");
+        let d = repo.create_contribution(contribution_input(m.id, "2025-03-01", "2025", "500")).await.unwrap();
+        assert_eq!(d.receipt_number, "2025-00003");
+
+        // Restauration après suppression : le reçu d'origine est conservé
+        repo.delete_contribution(b.id).await.unwrap();
+        let restored = repo.restore_contribution(b.id).await.unwrap();
+        assert_eq!(restored.receipt_number, "2025-00002");
+    }
+
+    #[tokio::test]
+    async fn test_contribution_recorded_by() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Rakoto", "Communiant")).await.unwrap();
+        let mut input = contribution_input(m.id, "2025-01-10", "2025", "1000");
+        input.recorded_by = Some("  Naina ".into());
+        let a = repo.create_contribution(input).await.unwrap();
+        assert_eq!(a.recorded_by.as_deref(), Some("Naina"));
+        let mut blank = contribution_input(m.id, "2025-02-10", "2025", "2000");
+        blank.recorded_by = Some(" ".into());
+        assert_eq!(repo.create_contribution(blank).await.unwrap().recorded_by, None);
+
+        // Ligne antérieure à la colonne : NULL relu comme None
+        sqlx::query(
+            "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+             VALUES (?, '2025-03-01', '2025', '500', 2025)",
+        )
+        .bind(m.id)
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+
+        let page = repo.get_contributions_by_year_with_member(Some(2025), None, Scope::All, None, None).await.unwrap();
+        let by: Vec<Option<&str>> = page.items.iter().map(|c| c.recorded_by.as_deref()).collect();
+        assert_eq!(by, vec![Some("Naina"), None, None]);
+
+        // Conservé par la modification et par la restauration
+        repo.set_pin("1234").await.unwrap();
+        let edited = repo.update_contribution(a.id, edit_input("2025-01-11", "2025", "1500")).await.unwrap();
+        assert_eq!(edited.recorded_by.as_deref(), Some("Naina"));
+        repo.delete_contribution(a.id).await.unwrap();
+        let restored = repo.restore_contribution(edited.id).await.unwrap();
+        assert_eq!(
+            repo.get_contribution_by_receipt(&restored.receipt_number).await.unwrap().recorded_by.as_deref(),
+            Some("Naina"),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_saisie_en_serie() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-01-05", "2025", "1000")).await.unwrap();
+
+        let saved = repo
+            .create_contributions_batch(vec![
+                contribution_input(a.id, "2025-03-02", "2025-03", "5000"),
+                contribution_input(b.id, "2025-03-02", "2025-3", "2000"),
+                contribution_input(a.id, "2024-12-29", "2024", "500"),
+            ])
+            .await
+            .unwrap();
+        let receipts: Vec<&str> = saved.iter().map(|c| c.receipt_number.as_str()).collect();
+        assert_eq!(receipts, vec!["2025-00002", "2025-00003", "2024-00001"]);
+        assert_eq!(saved[1].period, "2025-03");
+        assert!(saved.iter().all(|c| c.id > 0));
+
+        // Un total recalculé par année touchée
+        let y2025 = repo.get_year_summary(2025).await.unwrap().unwrap();
+        assert_eq!((y2025.total, y2025.contribution_count), (Decimal::from(8000), 3));
+        assert_eq!(y2025.total_cathekomen, Decimal::from(2000));
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(500));
+        assert!(repo.create_contributions_batch(vec![]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_saisie_en_serie_tout_ou_rien() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let gone = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        repo.delete_member(gone.id).await.unwrap();
+        let ok = contribution_input(a.id, "2025-03-02", "2025", "5000");
+
+        // Montant invalide en 2ᵉ ligne, puis membre supprimé en 3ᵉ : rien n'est écrit
+        let bad_amount = vec![ok.clone(), contribution_input(a.id, "2025-03-02", "2025", "abc"), ok.clone()];
+        let bad_member = vec![ok.clone(), ok.clone(), contribution_input(gone.id, "2025-03-02", "2025", "100")];
+        for (batch, row) in [(bad_amount, "2"), (bad_member, "3")] {
+            let err = repo.create_contributions_batch(batch).await.unwrap_err();
+            let AppError::Validation(m) = &err else { panic!("{err:?}") };
+            assert_eq!((m.key.as_ref(), m.params[0].as_str()), ("contribution.batch_row", row));
+            // Message de la ligne déjà traduit, pas une clé brute
+            assert!(!m.params[1].starts_with("contribution."), "{err:?}");
+        }
+        assert!(repo.get_contributions(a.id).await.unwrap().is_empty());
+        assert!(repo.get_year_summary(2025).await.unwrap().is_none());
+
+        let too_many = vec![ok; MAX_CONTRIBUTION_BATCH + 1];
+        assert!(matches!(
+            repo.create_contributions_batch(too_many).await.unwrap_err(),
+            AppError::Validation(ref m) if m.key == "contribution.batch_too_large"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_contribution_apres_suppression() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c1 = repo.create_contribution(contribution_input(m.id, "2024-01-01", "2024-T1", "10000")).await.unwrap();
+        let c2 = repo.create_contribution(contribution_input(m.id, "2024-06-01", "2024", "5000")).await.unwrap();
+
+        let not_restorable = |e: AppError| matches!(e, AppError::Validation(ref m) if m.key == "contribution.not_restorable");
+
+        // Jamais supprimée : rien à annuler
+        assert!(not_restorable(repo.restore_contribution(c1.id).await.unwrap_err()));
+
+        // Ancien id libre : réutilisé, total de l'année recalculé
+        repo.delete_contribution(c1.id).await.unwrap();
+        let restored = repo.restore_contribution(c1.id).await.unwrap();
+        assert_eq!((restored.id, restored.period.as_str()), (c1.id, "2024-T1"));
+        assert_eq!(restored.receipt_number, c1.receipt_number);
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(15_000));
+        // Une même suppression ne s'annule qu'une fois
+        assert!(not_restorable(repo.restore_contribution(c1.id).await.unwrap_err()));
+        let audit = repo.get_audit_log(Some(1), None, Some("contribution")).await.unwrap();
+        assert_eq!((audit.items[0].action.as_str(), audit.items[0].entity_id), ("restore", c1.id));
+
+        // Ancien id repris entre-temps (ligne importée) : nouvel id, reçu conservé
+        repo.delete_contribution(c2.id).await.unwrap();
+        sqlx::query(
+            "INSERT INTO contributions (id, member_id, payment_date, period, amount, recorded_year, receipt_number)
+             VALUES (?, ?, '2024-07-01', '2024', '1000', 2024, '2024-09999')",
+        )
+        .bind(c2.id)
+        .bind(m.id)
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+        let again = repo.restore_contribution(c2.id).await.unwrap();
+        assert_ne!(again.id, c2.id);
+        assert_eq!(again.receipt_number, c2.receipt_number);
+        assert_eq!(repo.get_contributions(m.id).await.unwrap().len(), 3);
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(16_000));
+
+        // Année clôturée depuis la suppression : refusé
+        let c3 = repo.create_contribution(contribution_input(m.id, "2023-05-01", "2023", "700")).await.unwrap();
+        repo.delete_contribution(c3.id).await.unwrap();
+        repo.close_year(2023, None).await.unwrap();
+        assert!(matches!(
+            repo.restore_contribution(c3.id).await.unwrap_err(),
+            AppError::Validation(ref m) if m.key == "year.closed"
+        ));
+
+        // Membre purgé : rien à rattacher
+        repo.delete_contribution(again.id).await.unwrap();
+        repo.delete_member(m.id).await.unwrap();
+        repo.purge_member(m.id).await.unwrap();
+        let err = repo.restore_contribution(again.id).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_contributions_by_year_with_member() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice Rakoto", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-04-10", "2024", "8000")).await.unwrap();
+        let page = repo.get_contributions_by_year_with_member(Some(2024), None, Scope::All, None, None).await.unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].member_name, "Alice Rakoto");
+        assert_eq!(page.items[0].recorded_year, 2024);
+    }
+
+    #[tokio::test]
+    async fn test_get_contributions_by_year_with_member_pagination() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        for day in 1..=12 {
+            let date = format!("2024-03-{day:02}");
+            repo.create_contribution(contribution_input(m.id, &date, "2024", "1000")).await.unwrap();
+        }
+        repo.create_contribution(contribution_input(m.id, "2023-03-01", "2023", "1000")).await.unwrap();
+
+        let p1 = repo.get_contributions_by_year_with_member(Some(2024), None, Scope::All, Some(5), Some(0)).await.unwrap();
+        assert_eq!(p1.total_count, 12);
+        assert_eq!(p1.items.len(), 5);
+        assert_eq!(p1.items[0].payment_date, "2024-03-01");
+
+        let p3 = repo.get_contributions_by_year_with_member(Some(2024), None, Scope::All, Some(5), Some(10)).await.unwrap();
+        assert_eq!(p3.items.len(), 2);
+        assert_eq!(p3.items[1].payment_date, "2024-03-12");
+
+        // Offset au-delà de la fin : page vide mais total_count conservé
+        let vide = repo.get_contributions_by_year_with_member(Some(2024), None, Scope::All, Some(5), Some(50)).await.unwrap();
+        assert!(vide.items.is_empty());
+        assert_eq!(vide.total_count, 12);
+    }
+
+    #[tokio::test]
+    async fn test_get_contributions_with_member_filtre() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice Rakoto", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob 100%", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-03-01", "2025", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2025-04-01", "2025", "1000")).await.unwrap();
+
+        // Filtre par nom, insensible à la casse, limité à l'année
+        let p = repo.get_contributions_by_year_with_member(Some(2025), Some(" rakoto "), Scope::All, None, None).await.unwrap();
+        assert_eq!(p.total_count, 1);
+        assert_eq!(p.items[0].member_id, a.id);
+        // Toutes années
+        let p = repo.get_contributions_by_year_with_member(None, Some("alice"), Scope::All, None, None).await.unwrap();
+        assert_eq!(p.total_count, 2);
+        assert_eq!(p.items[0].recorded_year, 2024);
+        // Numéro de reçu, jokers LIKE pris littéralement
+        let p = repo.get_contributions_by_year_with_member(None, Some("2025-00002"), Scope::All, None, None).await.unwrap();
+        assert_eq!((p.total_count, p.items[0].member_id), (1, b.id));
+        let p = repo.get_contributions_by_year_with_member(None, Some("%"), Scope::All, None, None).await.unwrap();
+        assert_eq!((p.total_count, p.items[0].member_id), (1, b.id));
+        // Filtre vide : aucun filtre
+        let p = repo.get_contributions_by_year_with_member(None, Some("  "), Scope::All, Some(2), None).await.unwrap();
+        assert_eq!((p.total_count, p.items.len()), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn test_get_contributions_with_member_portee() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-12", "2024-T1", "50000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-05-02", "2024-T2", "5000.50")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-11-20", "2024-T4", "150000")).await.unwrap();
+        let search = |q: &'static str, scope| {
+            let repo = repo.clone();
+            async move {
+                let p = repo.get_contributions_by_year_with_member(None, Some(q), scope, None, None).await.unwrap();
+                p.items.iter().map(|c| c.amount.to_string()).collect::<Vec<_>>()
+            }
+        };
+
+        // Montant : préfixe de la partie entière, séparateurs ignorés
+        assert_eq!(search("50 000", Scope::Amount).await, ["50000"]);
+        assert_eq!(search("50.000", Scope::All).await, ["50000"]);
+        assert_eq!(search("50", Scope::Amount).await, ["50000", "5000.50"]);
+        // Période et date de paiement
+        assert_eq!(search("2024-T2", Scope::Period).await, ["5000.50"]);
+        assert_eq!(search("2024-03", Scope::All).await, ["50000"]);
+        // Portée restreinte : le nom ne compte plus, ni le montant
+        assert!(search("alice", Scope::Period).await.is_empty());
+        assert!(search("150", Scope::Member).await.is_empty());
+        assert_eq!(search("alice", Scope::Member).await.len(), 3);
+        assert_eq!(search("1", Scope::Amount).await, ["150000"]);
+    }
+
+    fn edit_input(date: &str, period: &str, amount: &str) -> crate::db::ContributionEditInput {
+        crate::db::ContributionEditInput {
+            payment_date: date.into(),
+            period:       period.into(),
+            amount:       amount.into(),
+            pin:          "1234".into(),
+            reason:       String::new(),
+            allow_mismatch: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_montant() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "10000")).await.unwrap();
+
+        let updated = repo.update_contribution(c.id, edit_input("2024-03-01", "2024", "12500")).await.unwrap();
+        assert_eq!(updated.amount, Decimal::from_str("12500").unwrap());
+
+        let s = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s.total, Decimal::from_str("12500").unwrap());
+
+        // Montant nul refusé en modification, rien n'est écrit
+        for zero in ["0", "0,00"] {
+            let err = repo.update_contribution(c.id, edit_input("2024-03-01", "2024", zero)).await.unwrap_err();
+            assert!(matches!(err, AppError::Validation(ref m) if m.key == "amount.zero"), "{err:?}");
+        }
+        assert_eq!(repo.get_contributions(m.id).await.unwrap()[0].amount, Decimal::from(12500));
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_change_annee() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2023-12-31", "2023", "8000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-06-01", "2023", "2000")).await.unwrap();
+
+        let updated = repo.update_contribution(c.id, edit_input("2024-01-02", "2024", "8000")).await.unwrap();
+        assert_eq!(updated.recorded_year, 2024);
+
+        let s2023 = repo.get_year_summary(2023).await.unwrap().unwrap();
+        assert_eq!(s2023.total, Decimal::from_str("2000").unwrap());
+        let s2024 = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s2024.total, Decimal::from_str("8000").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_change_annee_sous_totaux() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(b.id, "2023-11-15", "2023", "3000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "5000")).await.unwrap();
+
+        // Seule cotisation de 2023 déplacée en 2024 : 2023 retombe à zéro
+        repo.update_contribution(c.id, edit_input("2024-04-10", "2024", "3500")).await.unwrap();
+
+        let s2023 = repo.get_year_summary(2023).await.unwrap().unwrap();
+        assert_eq!(s2023.total, Decimal::ZERO);
+        assert_eq!(s2023.total_cathekomen, Decimal::ZERO);
+        assert_eq!(s2023.contributor_count, 0);
+        assert_eq!(s2023.contribution_count, 0);
+
+        let s2024 = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s2024.total, Decimal::from_str("8500").unwrap());
+        assert_eq!(s2024.total_communiant, Decimal::from_str("5000").unwrap());
+        assert_eq!(s2024.total_cathekomen, Decimal::from_str("3500").unwrap());
+        assert_eq!(s2024.contributor_count, 2);
+        assert_eq!(s2024.contribution_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_vers_annee_cloturee() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-05-01", "2022", "5000")).await.unwrap();
+        repo.close_year(2022, None).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "7000")).await.unwrap();
+
+        // Déplacement refusé : aucune des deux années n'est modifiée
+        let err = repo.update_contribution(c.id, edit_input("2022-06-01", "2022", "7000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let s2022 = repo.get_year_summary(2022).await.unwrap().unwrap();
+        assert_eq!(s2022.total, Decimal::from_str("5000").unwrap());
+        let s2024 = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s2024.total, Decimal::from_str("7000").unwrap());
+        let list = repo.get_contributions(m.id).await.unwrap();
+        assert!(list.iter().any(|x| x.id == c.id && x.recorded_year == 2024));
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_annee_cloturee() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2022-05-01", "2022", "5000")).await.unwrap();
+        repo.close_year(2022, None).await.unwrap();
+
+        let err = repo.update_contribution(c.id, edit_input("2022-05-01", "2022", "6000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        let s = repo.get_year_summary(2022).await.unwrap().unwrap();
+        assert_eq!(s.total, Decimal::from_str("5000").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_periode_vide() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "1000")).await.unwrap();
+        let err = repo.update_contribution(c.id, edit_input("2024-03-01", "  ", "1000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_update_contribution_periode_autre_annee() {
+        let repo = make_repo().await;
+        repo.set_pin("1234").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let c = repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "1000")).await.unwrap();
+        let err = repo.update_contribution(c.id, edit_input("2024-03-01", "2023", "1000")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        let mut input = edit_input("2024-03-01", "2023", "1000");
+        input.allow_mismatch = true;
+        let updated = repo.update_contribution(c.id, input).await.unwrap();
+        assert_eq!(updated.period, "2023");
+    }
+
+    // ── Résumés annuels ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_year_summary_auto_cree() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-05-01", "2023", "20000")).await.unwrap();
+        let s = repo.get_year_summary(2023).await.unwrap().unwrap();
+        assert_eq!(s.year, 2023);
+        assert_eq!(s.total, Decimal::from_str("20000").unwrap());
+        assert!(s.closed_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sous_totaux_par_type() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-01-10", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-10", "2024", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-03-10", "2024", "1000")).await.unwrap();
+
+        let s = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(s.total, Decimal::from_str("8500.50").unwrap());
+        assert_eq!(s.total_communiant, Decimal::from_str("7500.50").unwrap());
+        assert_eq!(s.total_cathekomen, Decimal::from_str("1000").unwrap());
+        assert_eq!(s.contributor_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sous_totaux_apres_transfert() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-05-01", "2023", "3000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-05-01", "2024", "4000")).await.unwrap();
+        repo.close_year(2023, None).await.unwrap();
+
+        repo.transfer_members(&[a.id], MemberType::Communiant).await.unwrap();
+
+        // Année ouverte : recalculée selon le nouveau type
+        let open = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(open.total_communiant, Decimal::from_str("4000").unwrap());
+        assert_eq!(open.total_cathekomen, Decimal::ZERO);
+        assert_eq!(open.contributor_count, 1);
+
+        // Année clôturée : répartition figée
+        let closed = repo.get_year_summary(2023).await.unwrap().unwrap();
+        assert_eq!(closed.total_communiant, Decimal::ZERO);
+        assert_eq!(closed.total_cathekomen, Decimal::from_str("3000").unwrap());
+
+        // Même chose via la modification d'un membre
+        repo.update_member(a.id, member_input("C001", "Alice", "Cathekomen")).await.unwrap();
+        let open = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(open.total_cathekomen, Decimal::from_str("4000").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Bob", "Cathekomen")).await.unwrap();
+        repo.update_member(a.id, member_input("C001", "Alice R.", "Communiant")).await.unwrap();
+        repo.transfer_members(&[b.id], MemberType::Communiant).await.unwrap();
+        let c = repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        repo.delete_contribution(c.id).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-04-01", "2024", "2000")).await.unwrap();
+        repo.close_year(2024, Some("Vita".into())).await.unwrap();
+        repo.reopen_year(2024, false).await.unwrap();
+        repo.delete_member(a.id).await.unwrap();
+        // Déjà à la corbeille : rien de plus au journal
+        repo.delete_member(a.id).await.unwrap();
+        repo.restore_member(a.id).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-01-10", "2025", "1000")).await.unwrap();
+        repo.delete_member(a.id).await.unwrap();
+        repo.purge_member(a.id).await.unwrap();
+
+        let log = repo.get_audit_log(None, None, None).await.unwrap();
+        assert_eq!(log.total_count, 14);
+        let actions: Vec<(&str, &str)> =
+            log.items.iter().map(|e| (e.action.as_str(), e.entity.as_str())).collect();
+        assert_eq!(actions, [
+            ("purge", "member"),
+            ("delete", "member"),
+            ("create", "contribution"),
+            ("restore", "member"),
+            ("delete", "member"),
+            ("reopen", "year"),
+            ("close", "year"),
+            ("create", "contribution"),
+            ("delete", "contribution"),
+            ("create", "contribution"),
+            ("transfer", "member"),
+            ("update", "member"),
+            ("create", "member"),
+            ("create", "member"),
+        ]);
+        assert_eq!(log.items[0].entity_id, a.id);
+        assert_eq!(log.items[5].entity_id, 2024);
+        // Fiche effacée et nombre de cotisations emportées, gardés au journal
+        let purged: serde_json::Value =
+            serde_json::from_str(log.items[0].details.as_deref().unwrap()).unwrap();
+        assert_eq!(purged["member"]["full_name"], "Alice R.");
+        assert_eq!(purged["member"]["card_number"], "C001");
+        assert_eq!(purged["contributions"], 1);
+
+        // Filtre par entité, pagination
+        let members = repo.get_audit_log(Some(2), Some(4), Some("member")).await.unwrap();
+        assert_eq!(members.total_count, 8);
+        assert_eq!(members.items.len(), 2);
+        assert_eq!(members.items[0].action, "transfer");
+        assert_eq!(members.items[0].entity_id, b.id);
+        assert_eq!(members.items[1].action, "update");
+
+        let deleted = repo.get_audit_log(None, None, Some("contribution")).await.unwrap();
+        assert_eq!(deleted.total_count, 4);
+        let details: serde_json::Value =
+            serde_json::from_str(deleted.items[2].details.as_deref().unwrap()).unwrap();
+        assert_eq!(details["receipt_number"], "2024-00001");
+        assert_eq!(details["amount"], "5000");
+    }
+
+    #[tokio::test]
+    async fn test_close_and_reopen_year() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "50000")).await.unwrap();
+
+        let closed = repo.close_year(2022, Some("Test note".into())).await.unwrap();
+        assert!(closed.closed_at.is_some());
+        assert_eq!(closed.note.as_deref(), Some("Test note"));
+
+        let reopened = repo.reopen_year(2022, false).await.unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(reopened[0].closed_at.is_none());
+        assert!(reopened[0].note.is_none());
+
+        // Déjà ouverte : rien à rouvrir
+        let err = repo.reopen_year(2022, false).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert_eq!(err.to_string(), "L'année 2022 n'est pas clôturée.");
+    }
+
+    #[tokio::test]
+    async fn test_apercu_cloture_sans_ecriture() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2022-03-01", "2022", "50000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2022-04-01", "2022", "2500.50")).await.unwrap();
+        let before = repo.get_year_summary(2022).await.unwrap().unwrap();
+
+        let preview = repo.preview_year_close(2022).await.unwrap();
+        assert!(!preview.already_closed);
+        assert_eq!(preview.total, Decimal::from_str("52500.50").unwrap());
+        assert!(preview.default_note.starts_with("CONTRIBUTIONS de l'année 2022 / TOTAL : "));
+        assert!(preview.default_note.ends_with("2 cotisation(s) · 2 membre(s)"), "{}", preview.default_note);
+        // Rien d'écrit : année toujours ouverte, aucun résumé créé pour une année vide
+        let after = repo.get_year_summary(2022).await.unwrap().unwrap();
+        assert!(after.closed_at.is_none());
+        assert_eq!(after.total, before.total);
+        assert_eq!(repo.preview_year_close(2019).await.unwrap().total, Decimal::ZERO);
+        assert!(repo.get_year_summary(2019).await.unwrap().is_none());
+
+        // Clôture avec la note modifiée, puis l'aperçu la renvoie telle quelle
+        let closed = repo.close_year(2022, Some("  Fanamarihana vaovao ".into())).await.unwrap();
+        assert_eq!(closed.note.as_deref(), Some("Fanamarihana vaovao"));
+        let preview = repo.preview_year_close(2022).await.unwrap();
+        assert!(preview.already_closed);
+        assert_eq!(preview.default_note, "Fanamarihana vaovao");
+
+        let err = repo.close_year(2023, Some("x".repeat(MAX_YEAR_NOTE_LEN + 1))).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(repo.get_year_summary(2023).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_year_annees_suivantes_cloturees() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        for year in 2021..=2024 {
+            let date = format!("{year}-03-01");
+            repo.create_contribution(contribution_input(m.id, &date, &year.to_string(), "1000")).await.unwrap();
+        }
+        for year in 2021..=2023 {
+            repo.close_year(year, None).await.unwrap();
+        }
+
+        // Sans cascade : refusé tant que 2022 et 2023 sont clôturées, rien ne change
+        let err = repo.reopen_year(2021, false).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(err.to_string().contains("2022, 2023"), "{err}");
+        assert!(repo.get_year_summary(2021).await.unwrap().unwrap().closed_at.is_some());
+
+        // La dernière année clôturée se rouvre seule
+        let last = repo.reopen_year(2023, false).await.unwrap();
+        assert_eq!(last.iter().map(|s| s.year).collect::<Vec<_>>(), vec![2023]);
+
+        // Cascade : 2021 et 2022 rouvertes ensemble, 2024 (jamais clôturée) non listée
+        repo.close_year(2023, None).await.unwrap();
+        let reopened = repo.reopen_year(2021, true).await.unwrap();
+        assert_eq!(reopened.iter().map(|s| s.year).collect::<Vec<_>>(), vec![2021, 2022, 2023]);
+        assert!(reopened.iter().all(|s| s.closed_at.is_none()));
+        let summaries = repo.get_year_summaries().await.unwrap();
+        assert!(summaries.iter().all(|s| s.closed_at.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_update_year_note_annee_cloturee() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "50000")).await.unwrap();
+        let closed = repo.close_year(2022, Some("Note auto".into())).await.unwrap();
+
+        let edited = repo.update_year_note(2022, Some("  Fety faha-50 taona  ".into())).await.unwrap();
+        assert_eq!(edited.note.as_deref(), Some("Fety faha-50 taona"));
+        assert_eq!(edited.closed_at, closed.closed_at);
+        assert_eq!(edited.total, closed.total);
+        assert_eq!(edited.total_communiant, closed.total_communiant);
+        assert_eq!(edited.contributor_count, closed.contributor_count);
+
+        // Note vide → NULL, année toujours clôturée
+        let cleared = repo.update_year_note(2022, Some("   ".into())).await.unwrap();
+        assert!(cleared.note.is_none());
+        assert_eq!(cleared.closed_at, closed.closed_at);
+        assert_eq!(cleared.total, closed.total);
+
+        let err = repo.update_year_note(2022, Some("x".repeat(MAX_YEAR_NOTE_LEN + 1))).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        let err = repo.update_year_note(1990, Some("?".into())).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        // Année ouverte : refusée, rien n'est écrit
+        repo.create_contribution(contribution_input(m.id, "2023-01-01", "2023", "1000")).await.unwrap();
+        let err = repo.update_year_note(2023, Some("Aloha".into())).await.unwrap_err();
+        assert_eq!(err.to_string(), "L'année 2023 n'est pas clôturée.");
+        assert!(repo.get_year_summary(2023).await.unwrap().unwrap().note.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_annees_avec_donnees() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "50000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "1000")).await.unwrap();
+        repo.close_year(2022, None).await.unwrap();
+        // Résumé vide d'une année future saisie par erreur, et année vide mais annotée
+        sqlx::query("INSERT INTO year_summaries (year) VALUES (2099)").execute(&repo.pool).await.unwrap();
+        sqlx::query("INSERT INTO year_summaries (year, note) VALUES (2023, 'Asa')").execute(&repo.pool).await.unwrap();
+
+        assert_eq!(repo.get_years_with_contributions().await.unwrap(), [2024, 2022]);
+
+        for year in [2022, 2023, 2024] {
+            let err = repo.delete_empty_year_summary(year).await.unwrap_err();
+            assert!(matches!(err, AppError::Validation(_)), "{year}");
+        }
+        repo.delete_empty_year_summary(2099).await.unwrap();
+        assert!(repo.get_year_summary(2099).await.unwrap().is_none());
+        // Déjà supprimé : sans effet
+        repo.delete_empty_year_summary(2099).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_close_year_sans_contributions() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2021-01-01", "2021", "0")).await.unwrap();
+        let closed = repo.close_year(2021, None).await.unwrap();
+        assert!(closed.closed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_year_member_totals_figes_a_la_cloture() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        for (id, date, amount) in [
+            (a.id, "2021-01-10", "1000"),
+            (a.id, "2021-06-10", "1500.50"),
+            (b.id, "2021-03-01", "4000"),
+            (b.id, "2022-03-01", "9000"),
+        ] {
+            repo.create_contribution(contribution_input(id, date, &date[..4], amount)).await.unwrap();
+        }
+        // Année ouverte : pas d'instantané
+        assert!(repo.get_year_member_totals(2021).await.unwrap().is_empty());
+
+        repo.close_year(2021, None).await.unwrap();
+        let totals = repo.get_year_member_totals(2021).await.unwrap();
+        let rows: Vec<_> = totals.iter().map(|t| (t.card_number.as_str(), t.total)).collect();
+        assert_eq!(rows, vec![("C002", Decimal::from(4000)), ("C001", Decimal::from_str("2500.50").unwrap())]);
+
+        // Renommage et suppression ultérieurs : l'archive ne bouge pas
+        let mut input = member_input("C001", "Alice Renamed", "Communiant");
+        input.gender = a.gender;
+        repo.update_member(a.id, input).await.unwrap();
+        repo.delete_member(b.id).await.unwrap();
+        let totals = repo.get_year_member_totals(2021).await.unwrap();
+        assert_eq!(totals[0].member_name, "Bob");
+        assert_eq!(totals[1].member_name, "Alice");
+
+        // Réouverture : instantané effacé
+        repo.reopen_year(2021, false).await.unwrap();
+        assert!(repo.get_year_member_totals(2021).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_annee_sans_cotisation() {
+        // Onglet d'une année « trou » des Archives : aucune erreur, page vide
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2021-03-01", "2021", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-03-01", "2023", "1000")).await.unwrap();
+
+        let page = repo.get_contributions_by_year_with_member(Some(2022), None, Scope::All, None, None).await.unwrap();
+        assert!(page.items.is_empty());
+        assert_eq!(page.total_count, 0);
+        assert!(repo.get_year_summary(2022).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reopen_year_inconnue() {
+        let repo = make_repo().await;
+        let err = repo.reopen_year(1990, true).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_year_summaries_ordre_desc() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2021-01-01", "2021", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-01-01", "2023", "2000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "3000")).await.unwrap();
+        let list = repo.get_year_summaries().await.unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[0].year, 2023);
+        assert_eq!(list[1].year, 2022);
+        assert_eq!(list[2].year, 2021);
+    }
+
+    #[tokio::test]
+    async fn test_year_comparison_sous_totaux() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-02-01", "2023", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2023-03-01", "2023", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "25000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-03-01", "2024", "5000")).await.unwrap();
+
+        let list = repo.get_year_comparison(vec![2024, 2023]).await.unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].year, 2023);
+        assert_eq!(list[1].year, 2024);
+        assert_eq!(list[1].communiants, Decimal::from_str("25000").unwrap());
+        assert_eq!(list[1].cathekomens, Decimal::from_str("5000").unwrap());
+        assert_eq!(list[1].total, Decimal::from_str("30000").unwrap());
+        assert_eq!(list[1].variation_pct, Some(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_year_comparison_decimal_et_orphelines() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        for _ in 0..10 {
+            repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "0.10")).await.unwrap();
+        }
+        // Cotisation dont le membre n'existe plus
+        let mut conn = repo.pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+        sqlx::query(
+            "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+             VALUES (999, '2024-05-01', '2024', '300', 2024)",
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+        drop(conn);
+
+        let list = repo.get_year_comparison(vec![2024]).await.unwrap();
+        assert_eq!(list[0].communiants, Decimal::ONE);
+        assert_eq!(list[0].cathekomens, Decimal::ZERO);
+        assert_eq!(list[0].total, Decimal::from(301));
+    }
+
+    #[tokio::test]
+    async fn test_year_comparison_annee_precedente_a_zero() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "12000")).await.unwrap();
+
+        // 2022 et 2023 sans données : lignes à zéro, pas de pourcentage infini
+        let list = repo.get_year_comparison(vec![2022, 2023, 2024]).await.unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[0].total, Decimal::ZERO);
+        assert_eq!(list[0].variation_pct, None);
+        assert_eq!(list[1].variation_pct, None);
+        assert_eq!(list[2].total, Decimal::from_str("12000").unwrap());
+        assert_eq!(list[2].variation_pct, None);
+    }
+
+    #[tokio::test]
+    async fn test_year_comparison_baisse() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2022-02-01", "2022", "30000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-02-01", "2023", "10000")).await.unwrap();
+
+        // 2022 non demandée mais utilisée comme référence pour 2023
+        let list = repo.get_year_comparison(vec![2023]).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].variation_pct, Some(-66.7));
+    }
+
+    #[tokio::test]
+    async fn test_index_des_totaux_annuels() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Bob", "Cathekomen")).await.unwrap();
+        // 3000 contributions réparties sur 2023..2025, alternativement Alice et Bob
+        sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i < 2999)
+             INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+             SELECT CASE i % 2 WHEN 0 THEN ? ELSE ? END,
+                    (2023 + i % 3) || '-06-01', (2023 + i % 3) || '', '100', 2023 + i % 3
+             FROM n",
+        )
+        .bind(a.id)
+        .bind(b.id)
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+        let mut tx = repo.pool.begin().await.unwrap();
+        Repository::refresh_year_total_tx(&mut tx, 2024).await.unwrap();
+        tx.commit().await.unwrap();
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from(100_000));
+        assert_eq!(summary.contribution_count, 1000);
+
+        let plan = |sql: &str| {
+            let pool = repo.pool.clone();
+            let sql = format!("EXPLAIN QUERY PLAN {sql}");
+            async move {
+                let rows = sqlx::query(&sql).bind(1_i64).fetch_all(&pool).await.unwrap();
+                rows.iter().map(|r| r.get::<String, _>("detail")).collect::<Vec<_>>().join(" / ")
+            }
+        };
+        let year_plan = plan(YEAR_REFRESH_SQL).await;
+        assert!(
+            year_plan.contains("USING COVERING INDEX idx_contributions_year_member_amount"),
+            "{year_plan}"
+        );
+        let member_plan = plan("SELECT amount FROM contributions WHERE member_id = ?").await;
+        assert!(member_plan.contains("idx_contributions_member_id"), "{member_plan}");
+    }
+
+    // ── Totaux mensuels ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_monthly_totals_plusieurs_mois() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-01-15", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-01-31", "2024", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-06-01", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-12-31", "2024", "7000")).await.unwrap();
+        // Autre année : ignorée
+        repo.create_contribution(contribution_input(a.id, "2023-06-10", "2023", "99999")).await.unwrap();
+
+        let months = repo.get_monthly_totals(2024).await.unwrap();
+        assert_eq!(months.len(), 12);
+        assert_eq!(months.iter().map(|m| m.month).collect::<Vec<_>>(), (1..=12).collect::<Vec<_>>());
+        assert_eq!(months[0].total, Decimal::from_str("12500.50").unwrap());
+        assert_eq!(months[0].count, 2);
+        assert_eq!(months[5].total, Decimal::from_str("5000").unwrap());
+        assert_eq!(months[11].total, Decimal::from_str("7000").unwrap());
+        let sum: Decimal = months.iter().map(|m| m.total).sum();
+        assert_eq!(sum, Decimal::from_str("24500.50").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_weekly_totals() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        // Lundi 30/12/2024 : semaine 1 de 2025 (ISO)
+        repo.create_contribution(contribution_input(a.id, "2024-12-30", "2024", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-01-05", "2025", "2000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-03-10", "2025", "500.50")).await.unwrap();
+        // Lundi 29/12/2025 : semaine 1 de 2026, exclue
+        repo.create_contribution(contribution_input(a.id, "2025-12-29", "2025", "9999")).await.unwrap();
+
+        let weeks = repo.get_weekly_totals(2025).await.unwrap();
+        assert_eq!(weeks.len(), 52);
+        assert_eq!(weeks.iter().map(|w| w.week).collect::<Vec<_>>(), (1..=52).collect::<Vec<_>>());
+        assert_eq!((weeks[0].count, weeks[0].total), (2, Decimal::from(3000)));
+        assert_eq!(weeks[10].total, Decimal::from_str("500.50").unwrap());
+        // Semaines sans cotisation présentes, à zéro
+        assert_eq!(weeks.iter().filter(|w| w.count == 0).count(), 50);
+        let sum: Decimal = weeks.iter().map(|w| w.total).sum();
+        assert_eq!(sum, Decimal::from_str("3500.50").unwrap());
+
+        // 2020 compte 53 semaines ISO
+        assert_eq!(repo.get_weekly_totals(2020).await.unwrap().len(), 53);
+    }
+
+    #[tokio::test]
+    async fn test_monthly_totals_mois_vides() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-05", "2024", "4000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-09-20", "2024", "6000")).await.unwrap();
+
+        let months = repo.get_monthly_totals(2024).await.unwrap();
+        assert_eq!(months.len(), 12);
+        for m in &months {
+            let expected = match m.month {
+                3 => "4000",
+                9 => "6000",
+                _ => "0",
+            };
+            assert_eq!(m.total, Decimal::from_str(expected).unwrap(), "mois {}", m.month);
+            assert_eq!(m.count, i64::from(m.month == 3 || m.month == 9));
+        }
+
+        // Année sans aucune cotisation : 12 mois à zéro
+        let empty = repo.get_monthly_totals(2020).await.unwrap();
+        assert_eq!(empty.len(), 12);
+        assert!(empty.iter().all(|m| m.total.is_zero() && m.count == 0));
+    }
+
+    #[tokio::test]
+    async fn test_compare_years() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        for (date, period, amount) in [
+            ("2024-01-15", "2024", "1000"),
+            ("2024-03-02", "2024", "2000.50"),
+            ("2025-01-10", "2025", "500"),
+            ("2025-01-20", "2024", "700"), // arriéré : compte dans janvier 2025
+            ("2025-02-01", "2025", "3000"),
+        ] {
+            let mut input = contribution_input(a.id, date, period, amount);
+            input.allow_mismatch = true;
+            repo.create_contribution(input).await.unwrap();
+        }
+
+        let cmp = repo.compare_years(2025, 2024).await.unwrap();
+        assert_eq!((cmp.year_a, cmp.year_b, cmp.months.len()), (2025, 2024, 12));
+        let jan = &cmp.months[0];
+        assert_eq!((jan.total_a, jan.total_b), (Decimal::from(1200), Decimal::from(1000)));
+        assert_eq!(jan.cumulative_diff, Decimal::from(200));
+        assert_eq!(cmp.months[1].cumulative_diff, Decimal::from(3200));
+        // Mars : l'avance se réduit (3 200 − 2 000,50)
+        assert_eq!(cmp.months[2].cumulative_diff, Decimal::from_str("1199.50").unwrap());
+        assert_eq!(cmp.months[11].cumulative_diff, Decimal::from_str("1199.50").unwrap());
+    }
+
+    // ── Statistiques ──────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_year_breakdown() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Rabe", "Communiant")).await.unwrap();
+        let mut input = member_input("K001", "Rasoa", "Cathekomen");
+        input.gender = Gender::F;
+        let b = repo.create_member(input).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-03-01", "2024", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-04-01", "2024", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-05-01", "2023", "999")).await.unwrap();
+        // Cotisation dont le membre n'existe plus
+        let mut conn = repo.pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+        sqlx::query(
+            "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+             VALUES (999, '2024-05-01', '2024', '300', 2024)",
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+        drop(conn);
+
+        let bd = repo.get_year_breakdown(2024).await.unwrap();
+        let flat = |entries: &[BreakdownEntry]| {
+            entries.iter().map(|e| (e.key.clone(), e.count, e.total.to_string())).collect::<Vec<_>>()
+        };
+        assert_eq!(flat(&bd.by_gender), vec![
+            ("M".to_string(), 1, "10000".to_string()),
+            ("F".to_string(), 2, "3500.50".to_string()),
+            ("Inconnu".to_string(), 1, "300".to_string()),
+        ]);
+        assert_eq!(flat(&bd.by_type), vec![
+            ("Communiant".to_string(), 1, "10000".to_string()),
+            ("Cathekomen".to_string(), 2, "3500.50".to_string()),
+            ("Inconnu".to_string(), 1, "300".to_string()),
+        ]);
+
+        // Année vide : les quatre groupes à 0
+        let empty = repo.get_year_breakdown(2020).await.unwrap();
+        assert_eq!(empty.by_gender.len() + empty.by_type.len(), 4);
+        assert!(empty.by_gender.iter().chain(&empty.by_type).all(|e| e.count == 0 && e.total.is_zero()));
+    }
+
+    #[tokio::test]
+    async fn test_get_statistics() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        let mut f = member_input("C003", "Carol", "Cathekomen");
+        f.gender = Gender::F;
+        repo.create_member(f).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-05-01", "2023", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-05-01", "2024", "2500")).await.unwrap();
+
+        let stats = repo.get_statistics().await.unwrap();
+
+        let comm_m = stats.gender_by_type.iter()
+            .find(|g| g.member_type == "Communiant" && g.gender == "M")
+            .unwrap();
+        assert_eq!(comm_m.count, 2);
+        let cath_f = stats.gender_by_type.iter()
+            .find(|g| g.member_type == "Cathekomen" && g.gender == "F")
+            .unwrap();
+        assert_eq!(cath_f.count, 1);
+
+        // Tous créés maintenant → une seule année d'inscription
+        assert_eq!(stats.new_members_by_year.len(), 1);
+        assert_eq!(stats.new_members_by_year[0].count, 3);
+
+        assert_eq!(stats.totals_by_year.len(), 2);
+        assert_eq!(stats.totals_by_year[0].year, 2023);
+        assert_eq!(stats.totals_by_year[1].total, Decimal::from_str("2500").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dashboard_stats() {
+        let repo = make_repo().await;
+        let empty = repo.get_dashboard_stats_for_year(2024).await.unwrap();
+        assert_eq!((empty.communiants, empty.cathekomens, empty.men, empty.women), (0, 0, 0, 0));
+        assert_eq!(empty.current_year_total, Decimal::ZERO);
+        assert_eq!(empty.last_contribution_date, None);
+
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        let mut f = member_input("C003", "Carol", "Cathekomen");
+        f.gender = Gender::F;
+        let c = repo.create_member(f).await.unwrap();
+        let gone = repo.create_member(member_input("C004", "Dera", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2023-05-01", "2023", "1000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-05-01", "2024", "2500")).await.unwrap();
+        repo.create_contribution(contribution_input(c.id, "2024-02-10", "2024", "500")).await.unwrap();
+        repo.create_contribution(contribution_input(gone.id, "2024-06-30", "2024", "300")).await.unwrap();
+        repo.delete_member(gone.id).await.unwrap();
+
+        let stats = repo.get_dashboard_stats_for_year(2024).await.unwrap();
+        assert_eq!(stats.year, 2024);
+        // Membre supprimé : hors effectifs, mais sa cotisation compte
+        assert_eq!((stats.communiants, stats.cathekomens), (2, 1));
+        assert_eq!((stats.men, stats.women), (2, 1));
+        assert_eq!(stats.current_year_total, Decimal::from_str("3300").unwrap());
+        assert_eq!(stats.previous_year_total, Decimal::from_str("1000").unwrap());
+        assert_eq!(stats.contributions_this_year, 3);
+        assert_eq!(stats.last_contribution_date.as_deref(), Some("2024-06-30"));
+    }
+
+    #[tokio::test]
+    async fn test_format_ariary_note() {
+        let d = Decimal::from_str("1234567").unwrap();
+        assert_eq!(Repository::format_ariary_note(&d), "1 234 567 Ariary");
+        let z = Decimal::ZERO;
+        assert_eq!(Repository::format_ariary_note(&z), "0 Ariary");
+        let c = Decimal::from_str("1234.5").unwrap();
+        assert_eq!(Repository::format_ariary_note(&c), "1 234 Ariary");
+        assert_eq!(format_ariary(&c), "1 234,50 Ariary");
+    }
+
+    #[test]
+    fn test_parse_amount_valides() {
+        let cases = [
+            ("15000",                "15000"),
+            ("  15000  ",            "15000"),
+            ("15 000",               "15000"),
+            ("15\u{202f}000",        "15000"),
+            ("1\u{a0}500\u{a0}000",  "1500000"),
+            ("15000.50",             "15000.50"),
+            ("15000,50",             "15000.50"),
+            ("15 000,5",             "15000.5"),
+            ("0",                    "0"),
+            ("0,01",                 "0.01"),
+            ("1000000000",           "1000000000"),
+            ("1 000 000 000,00",     "1000000000.00"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(parse_amount(raw).unwrap().to_string(), expected, "entrée : {raw:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_amount_invalides() {
+        let message = |raw: &str| match parse_amount(raw) {
+            Err(e @ AppError::Validation(_)) => e.to_string(),
+            other => panic!("{raw:?} accepté : {other:?}"),
+        };
+        assert!(message("").contains("requis"));
+        assert!(message(" \u{202f} ").contains("requis"));
+        assert!(message("-500").contains("négatif"));
+        assert!(message("- 500").contains("négatif"));
+        assert!(message("15,000.50").contains("Un seul séparateur"));
+        assert!(message("15.000,50").contains("Un seul séparateur"));
+        assert!(message("15000.505").contains("2 décimales"));
+        assert!(message("0,001").contains("2 décimales"));
+        assert!(message("1000000000.01").contains("dépasser 1 000 000 000 Ariary"));
+        assert!(message("99999999999").contains("dépasser"));
+        for raw in ["abc", "15k", "+500", "1.2.3", "15,", ".50", "15_000", "Ar 5000", "5e3"] {
+            assert!(message(raw).contains("Montant invalide"), "entrée : {raw:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_year_report_html() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Rabe <Jean> & fils", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Rasoa", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-06-01", "2024", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-03-01", "2024", "1000000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2023-03-01", "2023", "999")).await.unwrap();
+        repo.close_year(2024, Some("Tahiry farany".into())).await.unwrap();
+
+        let report = repo.get_year_report(2024).await.unwrap();
+        assert_eq!(report.contributions.len(), 3);
+        assert_eq!(report.members.len(), 2);
+        assert_eq!(report.members[0].member_id, a.id);
+        assert_eq!(report.members[0].count, 2);
+        assert_eq!(report.members[0].total, Decimal::from_str("12500.50").unwrap());
+
+        let html = crate::export::build_year_report_html(&report, "FJKM <Isotry>");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("1 012 500,50 Ariary"));
+        assert!(html.contains("12 500,50 Ariary"));
+        assert!(html.contains("1 000 000 Ariary"));
+        assert!(!html.contains("999 Ariary"));
+        assert!(html.contains("Tahiry farany"));
+        assert!(html.contains("Rabe &lt;Jean&gt; &amp; fils"));
+        assert!(html.contains("FJKM &lt;Isotry&gt;"));
+        assert!(!html.contains("<Jean>"));
+    }
+
+    #[tokio::test]
+    async fn test_export_year_report_pdf_pagine() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Rakotô (Jean)", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Rasoa", "Cathekomen")).await.unwrap();
+        for day in 0..150 {
+            let date = (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Duration::days(day))
+                .format("%Y-%m-%d")
+                .to_string();
+            let id = if day % 2 == 0 { a.id } else { b.id };
+            repo.create_contribution(contribution_input(id, &date, "2024", "1000")).await.unwrap();
+        }
+        repo.close_year(2024, Some("Voamarina".into())).await.unwrap();
+
+        let report = repo.get_year_report(2024).await.unwrap();
+        let pdf = crate::export::build_year_report_pdf(&report, "FJKM Isotry");
+        let text = String::from_utf8_lossy(&pdf);
+        let count = |needle: &[u8]| pdf.windows(needle.len()).filter(|w| *w == needle).count();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        // 150 lignes à ~50 par page : au moins 4 pages, en-tête du tableau répété
+        let pages = count(b"/Type /Page ");
+        assert!(pages >= 4, "{pages} pages");
+        assert!(text.contains(&format!("/Count {pages}")));
+        assert!(count(b"(Daty) Tj") >= 3);
+        assert!(text.contains(&format!("(Pejy {pages} / {pages}) Tj")));
+        assert!(text.contains("(150 000 Ariary) Tj"));
+        assert!(text.contains("(Voamarina) Tj"));
+        // Parenthèses échappées, "ô" encodé en WinAnsi
+        assert_eq!(count(b"(Rakot\xF4 \\(Jean\\)) Tj"), 76);
+        // La table xref est bien à l'offset annoncé
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap()
+            .lines().next().unwrap().parse().unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref"));
+    }
+
+    #[tokio::test]
+    async fn test_export_contributions_csv() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Rabe, Jean \"Bebe\"", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Rasoa", "Cathekomen")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-02-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-03-01", "2024-T1", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2023-03-01", "2023", "999")).await.unwrap();
+
+        let csv = crate::export::build_contributions_csv(&repo.get_year_report(2024).await.unwrap());
+        assert_eq!(
+            csv,
+            "\u{FEFF}date,card_number,member_name,member_type,period,amount\r\n\
+             2024-02-01,C001,\"Rabe, Jean \"\"Bebe\"\"\",Communiant,2024,10000\r\n\
+             2024-03-01,K001,Rasoa,Cathekomen,2024-T1,2500.50\r\n\
+             TOTAL,,,,,12500.50\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_member_statement() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Rabe", "Communiant")).await.unwrap();
+        let other = repo.create_member(member_input("C002", "Rasoa", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-05-01", "2024-T2", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-02-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2023-03-01", "2023", "999")).await.unwrap();
+        repo.create_contribution(contribution_input(other.id, "2024-02-01", "2024", "500")).await.unwrap();
+
+        let s = repo.get_member_statement(m.id, 2024).await.unwrap();
+        assert_eq!(s.member.card_number, "C001");
+        assert_eq!(s.contributions.len(), 2);
+        assert_eq!(s.contributions[0].payment_date, "2024-02-01");
+        assert_eq!(s.total, Decimal::from_str("12500.50").unwrap());
+
+        let csv = crate::export::build_member_statement_csv(&s);
+        assert!(csv.starts_with("\u{FEFF}card_number,member_name,member_type,year\r\n\
+                                 C001,Rabe,Communiant,2024\r\n"));
+        assert!(csv.ends_with("2024-05-01,2024-00001,2024-T2,2500.50\r\nTOTAL,,,12500.50\r\n"));
+
+        // Année sans cotisation : relevé valide, total à 0
+        let empty = repo.get_member_statement(m.id, 2022).await.unwrap();
+        assert!(empty.contributions.is_empty());
+        assert_eq!(empty.total, Decimal::ZERO);
+        assert!(crate::export::build_member_statement_csv(&empty).ends_with("TOTAL,,,0\r\n"));
+
+        let err = repo.get_member_statement(9999, 2024).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_ecriture_csv_selection() {
+        let dir = std::env::temp_dir().join(format!("fjkm-test-{}", uuid::Uuid::new_v4()));
+        let dest = dir.join("sous-dossier").join("selection.csv");
+        let headers = vec!["Mpikambana".to_string(), "Vola".to_string()];
+        let rows = vec![
+            vec!["RABE, \"Jean\"".to_string(), "2500.50".to_string()],
+            vec!["Rasoa".to_string(), "1000".to_string()],
+        ];
+        crate::export::write_csv_file(&dest, &headers, &rows, false).unwrap();
+        let csv = std::fs::read_to_string(&dest).unwrap();
+        assert_eq!(csv, "\u{FEFF}Mpikambana,Vola\r\n\"RABE, \"\"Jean\"\"\",2500.50\r\nRasoa,1000\r\n");
+
+        // Fichier existant : refusé sans `overwrite`, remplacé avec
+        let err = crate::export::write_csv_file(&dest, &headers, &[], false).unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        crate::export::write_csv_file(&dest, &headers, &[], true).unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "\u{FEFF}Mpikambana,Vola\r\n");
+
+        let err = crate::export::write_csv_file(&dir.join("selection.txt"), &headers, &rows, true)
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_member_year_totals() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Rabe", "Communiant")).await.unwrap();
+        let other = repo.create_member(member_input("C002", "Rasoa", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-05-01", "2024-T2", "2500.50")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-02-01", "2024", "10000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2022-03-01", "2022", "999")).await.unwrap();
+        repo.create_contribution(contribution_input(other.id, "2024-02-01", "2024", "500")).await.unwrap();
+
+        let totals = repo.get_member_year_totals(m.id).await.unwrap();
+        assert_eq!(
+            totals,
+            vec![
+                MemberYearTotal {
+                    year:               2024,
+                    total:              Decimal::from_str("12500.50").unwrap(),
+                    contribution_count: 2,
+                },
+                MemberYearTotal { year: 2022, total: Decimal::from(999), contribution_count: 1 },
+            ]
+        );
+        let none = repo.create_member(member_input("C003", "Vao", "Cathekomen")).await.unwrap();
+        assert!(repo.get_member_year_totals(none.id).await.unwrap().is_empty());
+        let err = repo.get_member_year_totals(9999).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_export_year_report_annee_inconnue() {
+        let repo = make_repo().await;
+        let err = repo.get_year_report(1999).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    // ── Sauvegarde / Restauration ─────────────────────────────────────────────
+
+    fn temp_db_path() -> String {
+        std::env::temp_dir()
+            .join(format!("fjkm-test-{}.db", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_backup_puis_restauration() {
+        // Base sur disque : avec `:memory:`, VACUUM INTO écrirait dans le VFS mémoire
+        let db_path = temp_db_path();
+        let repo = Repository::new(&db_path).await.unwrap();
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000")).await.unwrap();
+
+        let path = temp_db_path();
+        repo.backup_into(&path).await.unwrap();
+
+        // Modifications postérieures à la sauvegarde
+        repo.delete_member(a.id).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.log_event("info", "test", "avant restauration").await.unwrap();
+
+        repo.restore_from(&path).await.unwrap();
+
+        let members = repo.get_members().await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].card_number, "C001");
+        assert_eq!(repo.get_contributions(a.id).await.unwrap().len(), 1);
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from_str("5000").unwrap());
+        // Le journal n'est pas écrasé par la restauration
+        assert_eq!(repo.get_app_logs(10).await.unwrap().len(), 1);
+
+        repo.pool.close().await;
+        for f in [path, db_path] {
+            let _ = std::fs::remove_file(f);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restauration_fichier_invalide() {
+        let repo = make_repo().await;
+        let path = temp_db_path();
+        // Base SQLite vide : pas de table _sqlx_migrations
+        sqlx::SqlitePool::connect_with(
+            SqliteConnectOptions::new().filename(&path).create_if_missing(true),
+        )
+        .await
+        .unwrap()
+        .close()
+        .await;
+
+        let err = repo.restore_from(&path).await.unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_conserve_les_donnees() {
+        let db_path = temp_db_path();
+        let repo = Repository::new(&db_path).await.unwrap();
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
+        repo.delete_member(a.id).await.unwrap();
+
+        repo.vacuum().await.unwrap();
+
+        let members = repo.get_members().await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].card_number, "C002");
+        assert!(repo.run_integrity_check().await.unwrap().issues.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backup_retention() {
+        let repo = make_repo().await;
+        assert_eq!(repo.get_backup_retention().await.unwrap(), DEFAULT_BACKUP_RETENTION);
+        repo.set_backup_retention(7).await.unwrap();
+        assert_eq!(repo.get_backup_retention().await.unwrap(), 7);
+        let err = repo.set_backup_retention(0).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    // ── Archive JSON ──────────────────────────────────────────────────────────
+
+    /// Archive relue en JSON, sans l'horodatage de l'export.
+    fn archive_value(json: &str) -> serde_json::Value {
+        let mut v: serde_json::Value = serde_json::from_str(json).unwrap();
+        v.as_object_mut().unwrap().remove("exported_at");
+        v
+    }
+
+    #[tokio::test]
+    async fn test_archive_aller_retour() {
+        let repo = make_repo().await;
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("K001", "Bob", "Cathekomen")).await.unwrap();
+        let c = repo.create_member(member_input("C002", "Carl", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000.10")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-06-01", "2024", "2000")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2025-01-20", "2025", "3000")).await.unwrap();
+        repo.close_year(2024, Some("Taona 2024".into())).await.unwrap();
+        repo.delete_member(c.id).await.unwrap();
+        repo.add_member_note(a.id, "Niova adiresy".into()).await.unwrap();
+        repo.add_member_note(a.id, "Nandoa mialoha".into()).await.unwrap();
+        repo.add_member_note(c.id, "Nifindra".into()).await.unwrap();
+        repo.set_setting("church_name", "FJKM Isotry").await.unwrap();
+        repo.set_pin("1234").await.unwrap();
+
+        let json = repo.export_archive().await.unwrap();
+        assert!(!json.contains("admin_pin"));
+
+        // Base cible avec des données qui doivent disparaître
+        let target = make_repo().await;
+        let x = target.create_member(member_input("X999", "Tsy ho hita", "Communiant")).await.unwrap();
+        target.add_member_note(x.id, "Tsy ho hita".into()).await.unwrap();
+        let report = target.import_archive(&json, ImportMode::Replace).await.unwrap();
+        assert_eq!(
+            report,
+            ArchiveImportReport { members_inserted: 3, contributions_inserted: 3, ..Default::default() }
+        );
+
+        // Ré-export identique : identifiants, reçus, corbeille, clôture, totaux figés, paramètres
+        assert_eq!(archive_value(&target.export_archive().await.unwrap()), archive_value(&json));
+        assert_eq!(target.get_members().await.unwrap().len(), 2);
+        assert_eq!(target.get_deleted_members().await.unwrap().len(), 1);
+        let summary = target.get_year_summary(2024).await.unwrap().unwrap();
+        assert!(summary.closed_at.is_some());
+        assert_eq!(summary.total, Decimal::from_str("7000.10").unwrap());
+        assert_eq!(target.get_year_member_totals(2024).await.unwrap().len(), 2);
+        assert_eq!(target.get_member_notes(a.id).await.unwrap().len(), 2);
+        assert_eq!(target.get_member_notes(c.id).await.unwrap().len(), 1);
+        let notes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM member_notes")
+            .fetch_one(&target.pool)
+            .await
+            .unwrap();
+        assert_eq!(notes, 3);
+        assert_eq!(target.get_settings().await.unwrap().church_name, "FJKM Isotry");
+        // Le PIN de la base cible n'est pas touché
+        assert!(!target.has_pin().await.unwrap());
+        // La numérotation des reçus reprend après ceux de l'archive
+        let d = target.create_contribution(contribution_input(a.id, "2025-02-01", "2025", "100")).await.unwrap();
+        assert_eq!(d.receipt_number, "2025-00002");
+    }
+
+    #[tokio::test]
+    async fn test_archive_fusion() {
+        let source = make_repo().await;
+        let alice = source.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let dina = source.create_member(member_input("C005", "Dina", "Communiant")).await.unwrap();
+        source.create_contribution(contribution_input(alice.id, "2024-05-01", "2024", "2000")).await.unwrap();
+        source.create_contribution(contribution_input(alice.id, "2025-01-10", "2025", "5000")).await.unwrap();
+        source.create_contribution(contribution_input(dina.id, "2025-01-11", "2025", "1000")).await.unwrap();
+        source.add_member_note(alice.id, "Mpihira".into()).await.unwrap();
+        source.add_member_note(dina.id, "Vao tonga".into()).await.unwrap();
+        let json = source.export_archive().await.unwrap();
+
+        // Cible : identifiants différents, 2024 déjà clôturée, reçu 2025-00001 déjà pris
+        let target = make_repo().await;
+        let zo = target.create_member(member_input("C009", "Zo", "Communiant")).await.unwrap();
+        let alice_local = target.create_member(member_input("C001", "Alice R.", "Communiant")).await.unwrap();
+        target.create_contribution(contribution_input(zo.id, "2024-02-01", "2024", "400")).await.unwrap();
+        target.close_year(2024, None).await.unwrap();
+        target.create_contribution(contribution_input(alice_local.id, "2025-01-05", "2025", "700")).await.unwrap();
+        target.set_setting("church_name", "FJKM Local").await.unwrap();
+
+        let report = target.import_archive(&json, ImportMode::Merge).await.unwrap();
+        assert_eq!(
+            report,
+            ArchiveImportReport {
+                members_inserted:       1,
+                members_skipped:        1,
+                contributions_inserted: 2,
+                contributions_skipped:  1,
+            }
+        );
+
+        // Fiche locale conservée, cotisation de l'archive rattachée et renumérotée
+        assert_eq!(target.get_member(alice_local.id).await.unwrap().full_name, "Alice R.");
+        let mut receipts: Vec<String> = target
+            .get_contributions(alice_local.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.receipt_number)
+            .collect();
+        receipts.sort();
+        assert_eq!(receipts, vec!["2025-00001", "2025-00002"]);
+        let summary = target.get_year_summary(2025).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from(6700));
+        assert_eq!(summary.contributor_count, 2);
+        // Année clôturée ici : inchangée
+        assert_eq!(target.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(400));
+        // Paramètres locaux conservés
+        assert_eq!(target.get_settings().await.unwrap().church_name, "FJKM Local");
+        assert!(target.run_integrity_check().await.unwrap().issues.is_empty());
+
+        // Notes rattachées aux fiches locales ; une seconde fusion ne les double pas
+        target.import_archive(&json, ImportMode::Merge).await.unwrap();
+        let notes = target.get_member_notes(alice_local.id).await.unwrap();
+        assert_eq!(notes.iter().map(|n| n.text.as_str()).collect::<Vec<_>>(), vec!["Mpihira"]);
+        let dina_local = target.get_member_by_card("C005").await.unwrap().unwrap();
+        assert_eq!(target.get_member_notes(dina_local.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_archive_refusee_avant_ecriture() {
+        let source = make_repo().await;
+        let a = source.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        source.create_contribution(contribution_input(a.id, "2025-01-10", "2025", "5000")).await.unwrap();
+        let json = source.export_archive().await.unwrap();
+
+        let target = make_repo().await;
+        target.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+
+        let mut future = archive_value(&json);
+        future["schema_version"] = serde_json::json!(ARCHIVE_SCHEMA_VERSION + 1);
+        let mut orphan = archive_value(&json);
+        orphan["contributions"][0]["member_id"] = serde_json::json!(999);
+        let mut unversioned = archive_value(&json);
+        unversioned.as_object_mut().unwrap().remove("schema_version");
+        let mut orphan_note = archive_value(&json);
+        orphan_note["member_notes"] =
+            serde_json::json!([{ "id": 1, "member_id": 999, "created_at": "2025-01-10T08:00:00", "text": "?" }]);
+
+        for bad in [
+            future.to_string(),
+            orphan.to_string(),
+            unversioned.to_string(),
+            orphan_note.to_string(),
+            "{ pas du JSON".to_string(),
+        ] {
+            for mode in [ImportMode::Replace, ImportMode::Merge] {
+                let err = target.import_archive(&bad, mode).await.unwrap_err();
+                assert!(matches!(err, AppError::Validation(_)), "{err:?}");
+            }
+        }
+        let members = target.get_members().await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].card_number, "C002");
+        assert!(target.get_year_summary(2025).await.unwrap().is_none());
+    }
+
+    // ── Échecs d'ouverture / démonstration ────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_ouverture_fichier_endommage() {
+        let db_path = temp_db_path();
+        std::fs::write(&db_path, vec![0x42u8; 8192]).unwrap();
+        let err = Repository::new(&db_path).await.err().expect("fichier illisible");
+        assert!(matches!(err, AppError::Corrupt(_)), "{err:?}");
+        assert_eq!(err.kind(), "corrupt");
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    async fn test_ouverture_dossier_absent() {
+        let db_path = std::env::temp_dir()
+            .join(format!("fjkm-test-{}", uuid::Uuid::new_v4()))
+            .join("fjkm.db");
+        let err = Repository::new(db_path.to_str().unwrap()).await.err().expect("dossier absent");
+        assert!(matches!(err, AppError::Io(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_demo_lecture_seule() {
+        let repo = Repository::demo().await.unwrap();
+        assert_eq!(repo.get_members().await.unwrap().len(), 4);
+        let err = repo.create_member(member_input("C-0100", "Vao", "Communiant")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.key == "db.read_only"), "{err:?}");
+        // Toujours lisible après l'écriture refusée : la connexion unique est conservée
+        assert!(!repo.get_year_summaries().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_donnees_de_demonstration() {
+        let repo = make_repo().await;
+        let report = repo.seed_demo_data(40, vec![2024, 2023, 2024], 7).await.unwrap();
+        assert_eq!(report.years, vec![2023, 2024]);
+        assert_eq!(
+            (report.members_inserted, report.contributions_inserted, report.total.to_string()),
+            (40, 132, "1739000".to_string())
+        );
+
+        // Totaux annuels recalculés, cartes au format des paramètres
+        let summed: Decimal = repo.get_year_summaries().await.unwrap().iter().map(|s| s.total).sum();
+        assert_eq!(summed, report.total);
+        let members = repo.get_members().await.unwrap();
+        assert!(members.iter().all(|m| m.card_number.len() == 6));
+
+        // Même graine, autre base : mêmes données
+        let other = make_repo().await;
+        assert_eq!(other.seed_demo_data(40, vec![2023, 2024], 7).await.unwrap(), report);
+
+        // Base non vide : refusé, rien n'est ajouté
+        let err = repo.seed_demo_data(5, vec![2024], 8).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.key == "seed.not_empty"), "{err:?}");
+        assert_eq!(repo.get_members().await.unwrap().len(), 40);
+
+        let empty = make_repo().await;
+        for (count, years) in [(0, vec![2024]), (MAX_SEED_MEMBERS + 1, vec![2024]), (5, vec![]), (5, vec![1999])] {
+            assert!(matches!(
+                empty.seed_demo_data(count, years, 1).await.unwrap_err(),
+                AppError::Validation(_)
+            ));
+        }
+    }
+
+    // ── Tâches en attente ─────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_taches_en_attente() {
+        let repo = make_repo().await;
+        let today = date("2025-02-01");
+        assert!(repo.get_pending_tasks_on(today).await.unwrap().is_empty());
+
+        let alice = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let bob = repo
+            .create_member(MemberInput { address: Some("Ambalavao".into()), ..member_input("C002", "Bob", "Communiant") })
+            .await
+            .unwrap();
+        let chloe = repo.create_member(member_input("C003", "Chloé", "Cathekomen")).await.unwrap();
+        repo.delete_member(chloe.id).await.unwrap();
+        repo.create_contribution(contribution_input(alice.id, "2024-11-10", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(bob.id, "2024-12-01", "2024", "3000")).await.unwrap();
+        repo.create_contribution(contribution_input(bob.id, "2025-03-01", "2025", "3000")).await.unwrap();
+
+        // Bob a une adresse, Chloé est à la corbeille : seule Alice est incomplète
+        assert_eq!(
+            repo.get_pending_tasks_on(today).await.unwrap(),
+            vec![
+                PendingTask::UnclosedYear { year: 2024, contributions: 2 },
+                PendingTask::IncompleteMembers { count: 1 },
+                PendingTask::FutureContributions { count: 1 },
+            ]
+        );
+
+        repo.close_year(2024, None).await.unwrap();
+        assert_eq!(
+            repo.get_pending_tasks_on(date("2025-03-01")).await.unwrap(),
+            vec![PendingTask::IncompleteMembers { count: 1 }]
+        );
+    }
+
+    // ── État de la base ───────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_etat_base() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
+
+        let health = repo.health().await.unwrap();
+        let migrations = sqlx::migrate!("./migrations").iter().map(|m| m.version).max().unwrap();
+        assert_eq!(health.schema_version, migrations);
+        assert_eq!(repo.schema_version().await.unwrap(), migrations);
+        let rows = |t: &str| health.tables.iter().find(|c| c.table == t).map(|c| c.rows);
+        assert_eq!(rows("members"), Some(1));
+        assert_eq!(rows("contributions"), Some(1));
+        assert_eq!(rows("_sqlx_migrations"), None);
+        assert!(health.tables.windows(2).all(|w| w[0].table < w[1].table));
+        assert!(health.size_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn test_chemin_avec_espaces() {
+        // Chemin Windows typique (« Mes Documents ») : ouvert tel quel, sans URL sqlite://
+        let dir = std::env::temp_dir().join(format!("fjkm-test {} dossier", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fiangonana #1.db");
+
+        let repo = Repository::new(&path.to_string_lossy()).await.unwrap();
+        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        assert!(repo.health().await.unwrap().schema_version > 0);
+        repo.pool.close().await;
+        assert!(path.exists());
+
+        // Réouverture : même fichier, mêmes données
+        let reopened = Repository::new(&path.to_string_lossy()).await.unwrap();
+        assert_eq!(reopened.get_members().await.unwrap().len(), 1);
+        reopened.pool.close().await;
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    /// Base sur disque migrée jusqu'à `version` incluse, comme avant une mise à jour.
+    async fn pool_at_version(path: &str, version: i64) -> SqlitePool {
+        let pool = SqlitePool::connect_with(
+            SqliteConnectOptions::new().filename(path).create_if_missing(true),
+        )
+        .await
+        .unwrap();
+        let mut migrator = sqlx::migrate!("./migrations");
+        migrator.migrations = migrator
+            .migrations
+            .iter()
+            .filter(|m| m.version <= version)
+            .cloned()
+            .collect::<Vec<_>>()
+            .into();
+        migrator.run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_migration_totaux_membres_exacts() {
+        let path = temp_db_path();
+        let pool = pool_at_version(&path, 16).await;
+        sqlx::query(
+            "INSERT INTO members (id, card_number, full_name, created_at)
+             VALUES (1, 'C001', 'Alice', '2024-01-01')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for amount in ["1500", "3500"] {
+            sqlx::query(
+                "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+                 VALUES (1, '2024-03-01', '2024', ?, 2024)",
+            )
+            .bind(amount)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("INSERT INTO year_summaries (year, total, closed_at) VALUES (2024, '5000', '2025-01-05')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        // Mise à jour : 0017 remplit l'instantané, recalculé aussitôt en Decimal
+        let repo = Repository::new(&path).await.unwrap();
+        let total: String = sqlx::query_scalar(
+            "SELECT total FROM year_member_totals WHERE year = 2024 AND member_id = 1",
+        )
+        .fetch_one(&repo.pool)
+        .await
+        .unwrap();
+        // Le SQL seul donnerait "5000.0"
+        assert_eq!(total, "5000");
+        repo.pool.close().await;
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_migration_sous_totaux_exacts() {
+        let path = temp_db_path();
+        let pool = pool_at_version(&path, 4).await;
+        sqlx::query(
+            "INSERT INTO members (id, card_number, full_name, member_type, created_at)
+             VALUES (1, 'C001', 'Alice', 'Communiant', '2024-01-01'),
+                    (2, 'K001', 'Bob', 'Cathekomen', '2024-01-01')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (member_id, amount) in [(1, "1500"), (1, "3500"), (2, "0.10"), (2, "0.20")] {
+            sqlx::query(
+                "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+                 VALUES (?, '2024-03-01', '2024', ?, 2024)",
+            )
+            .bind(member_id)
+            .bind(amount)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("INSERT INTO year_summaries (year, total, closed_at) VALUES (2024, '5000.30', '2025-01-05')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        let repo = Repository::new(&path).await.unwrap();
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        let stored: (String, String) = sqlx::query_as(
+            "SELECT total_communiant, total_cathekomen FROM year_summaries WHERE year = 2024",
+        )
+        .fetch_one(&repo.pool)
+        .await
+        .unwrap();
+        // Le SQL seul donnerait "5000.0" et "0.3"
+        assert_eq!(stored, ("5000".to_string(), "0.30".to_string()));
+        assert_eq!(summary.total_communiant + summary.total_cathekomen, summary.total);
+        assert!(summary.closed_at.is_some());
+        repo.pool.close().await;
+        let _ = std::fs::remove_file(path);
+    }
+
+    // ── Contrôle d'intégrité ──────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_integrite_base_saine() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        let report = repo.run_integrity_check().await.unwrap();
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
     }
-}
 
-// ─── Tests ────────────────────────────────────────────────────────────────────
+    #[tokio::test]
+    async fn test_integrite_detection_et_reparation() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        let c2 = repo.create_contribution(contribution_input(m.id, "2025-01-10", "2025", "2000")).await.unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Corruptions : année d'enregistrement fausse, total faussé, contribution orpheline
+        sqlx::query("UPDATE contributions SET recorded_year = 2023 WHERE id = ?")
+            .bind(c2.id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE year_summaries SET total = '999' WHERE year = 2024")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        let mut conn = repo.pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.unwrap();
+        let orphan_id: i64 = sqlx::query_scalar(
+            "INSERT INTO contributions (member_id, payment_date, period, amount, recorded_year)
+             VALUES (999, '2024-06-01', '2024', '100', 2024) RETURNING id",
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await.unwrap();
+        drop(conn);
+
+        let issues = repo.run_integrity_check().await.unwrap().issues;
+        assert!(issues.contains(&IntegrityIssue::OrphanContribution {
+            contribution_id: orphan_id,
+            member_id:       999,
+        }));
+        assert!(issues.contains(&IntegrityIssue::RecordedYearMismatch {
+            contribution_id: c2.id,
+            recorded_year:   2023,
+            payment_year:    2025,
+        }));
+        // 2023 : 2000 comptés sans ligne de résumé ; 2024 : 999 stockés pour 5100 ; 2025 : 2000 stockés pour 0
+        let totals: Vec<(i32, String, String)> = issues
+            .iter()
+            .filter_map(|i| match i {
+                IntegrityIssue::YearTotalMismatch { year, stored, computed } => {
+                    Some((*year, stored.to_string(), computed.to_string()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                (2023, "0".into(), "2000".into()),
+                (2024, "999".into(), "5100".into()),
+                (2025, "2000".into(), "0".into()),
+            ]
+        );
 
-    /// Crée une DB SQLite en mémoire avec migrations appliquées.
-    async fn make_repo() -> Repository {
-        Repository::new(":memory:").await.expect("DB en mémoire")
+        let after = repo.repair_integrity().await.unwrap();
+        // Seule l'orpheline reste : elle demande une décision humaine
+        assert_eq!(after.issues.len(), 1);
+        assert!(matches!(after.issues[0], IntegrityIssue::OrphanContribution { .. }));
+        assert!(!after.issues[0].is_repairable());
+        assert_eq!(repo.get_contributions(m.id).await.unwrap().iter()
+            .find(|c| c.id == c2.id).unwrap().recorded_year, 2025);
+        let total_2024 = repo.get_year_summary(2024).await.unwrap().unwrap().total;
+        assert_eq!(total_2024, Decimal::from(5100));
     }
 
-    fn member_input(card: &str, name: &str, mtype: &str) -> MemberInput {
-        MemberInput {
-            card_number: card.into(),
-            full_name:   name.into(),
-            address:     None,
-            phone:       None,
-            job:         None,
-            gender:      "M".into(),
-            member_type: mtype.into(),
-        }
+    #[tokio::test]
+    async fn test_integrite_exercice_juillet_juin() {
+        let repo = make_repo().await;
+        repo.set_setting("fiscal_year_start_month", "7").await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        // Mars 2025 relève de l'exercice 2024–2025 : pas une incohérence
+        let mars = repo.create_contribution(contribution_input(m.id, "2025-03-01", "2025-03", "3000")).await.unwrap();
+        let aout = repo.create_contribution(contribution_input(m.id, "2025-08-01", "2025-08", "1000")).await.unwrap();
+        assert_eq!(mars.recorded_year, 2024);
+        assert!(repo.run_integrity_check().await.unwrap().issues.is_empty());
+
+        sqlx::query("UPDATE contributions SET recorded_year = 2024 WHERE id = ?")
+            .bind(aout.id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        let issues = repo.run_integrity_check().await.unwrap().issues;
+        assert!(issues.contains(&IntegrityIssue::RecordedYearMismatch {
+            contribution_id: aout.id,
+            recorded_year:   2024,
+            payment_year:    2025,
+        }));
+        assert!(!issues.iter().any(|i| matches!(
+            i,
+            IntegrityIssue::RecordedYearMismatch { contribution_id, .. } if *contribution_id == mars.id
+        )));
+
+        assert!(repo.repair_integrity().await.unwrap().issues.is_empty());
+        let years: Vec<(i64, i32)> = repo.get_contributions(m.id).await.unwrap()
+            .iter()
+            .map(|c| (c.id, c.recorded_year))
+            .collect();
+        assert!(years.contains(&(mars.id, 2024)));
+        assert!(years.contains(&(aout.id, 2025)));
     }
 
-    fn contribution_input(member_id: i64, date: &str, period: &str, amount: &str) -> ContributionInput {
-        ContributionInput {
-            member_id,
-            payment_date: date.into(),
-            period:       period.into(),
-            amount:       amount.into(),
+    #[tokio::test]
+    async fn test_reparation_annee_cloturee() {
+        let repo = make_repo().await;
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let a = repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "3000")).await.unwrap();
+        let b = repo.create_contribution(contribution_input(m.id, "2025-02-01", "2025", "1000")).await.unwrap();
+        repo.close_year(2024, None).await.unwrap();
+
+        // Sortie de 2024 clôturée et entrée dans 2024 clôturée : rien à déplacer
+        sqlx::query("UPDATE contributions SET recorded_year = 2025 WHERE id = ?")
+            .bind(a.id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE contributions SET recorded_year = 2024 WHERE id = ?")
+            .bind(b.id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+
+        let issues = repo.repair_integrity().await.unwrap().issues;
+        for (id, recorded_year, payment_year) in [(a.id, 2025, 2024), (b.id, 2024, 2025)] {
+            assert!(issues.contains(&IntegrityIssue::RecordedYearMismatch {
+                contribution_id: id,
+                recorded_year,
+                payment_year,
+            }));
         }
+        let years: Vec<(i64, i32)> = repo.get_contributions(m.id).await.unwrap()
+            .iter()
+            .map(|c| (c.id, c.recorded_year))
+            .collect();
+        assert!(years.contains(&(a.id, 2025)));
+        assert!(years.contains(&(b.id, 2024)));
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(3000));
     }
 
-    // ── Membres ───────────────────────────────────────────────────────────────
-
     #[tokio::test]
-    async fn test_create_member_ok() {
+    async fn test_verify_and_repair_summaries() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Jean Dupont", "Communiant")).await.unwrap();
-        assert_eq!(m.card_number, "C001");
-        assert_eq!(m.full_name, "Jean Dupont");
-        assert_eq!(m.member_type, "Communiant");
-        assert!(m.id > 0);
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2025-01-10", "2025", "2000")).await.unwrap();
+        assert!(repo.verify_and_repair_summaries().await.unwrap().is_empty());
+
+        // Écriture interrompue : total 2024 faussé, résumé 2025 perdu
+        sqlx::query("UPDATE year_summaries SET total = '999' WHERE year = 2024")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM year_summaries WHERE year = 2025")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+
+        let repaired = repo.verify_and_repair_summaries().await.unwrap();
+        assert_eq!(
+            repaired,
+            vec![
+                RepairedYear { year: 2024, stored: Decimal::from(999), computed: Decimal::from(5000) },
+                RepairedYear { year: 2025, stored: Decimal::ZERO, computed: Decimal::from(2000) },
+            ]
+        );
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(5000));
+        let summary_2025 = repo.get_year_summary(2025).await.unwrap().unwrap();
+        assert_eq!(summary_2025.total, Decimal::from(2000));
+        assert_eq!(summary_2025.contribution_count, 1);
+        // Plus rien à corriger
+        assert!(repo.verify_and_repair_summaries().await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn test_create_member_carte_vide() {
+    async fn test_verify_summaries_annee_close_figee_apres_purge() {
+        let db_path = temp_db_path();
+        let repo = Repository::new(&db_path).await.unwrap();
+        let a = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        let b = repo.create_member(member_input("C002", "Bob", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(a.id, "2024-03-01", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(b.id, "2024-04-01", "2024", "2000")).await.unwrap();
+        repo.close_year(2024, None).await.unwrap();
+
+        // La purge retire les cotisations de Bob mais laisse le total clôturé intact
+        repo.delete_member(b.id).await.unwrap();
+        repo.purge_member(b.id).await.unwrap();
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().total, Decimal::from(7000));
+        repo.pool.close().await;
+
+        // Redémarrage : la vérification des totaux ne touche pas à l'année close
+        let repo = Repository::new(&db_path).await.unwrap();
+        assert!(repo.verify_and_repair_summaries().await.unwrap().is_empty());
+        assert!(repo.run_integrity_check().await.unwrap().issues.is_empty());
+        repo.repair_integrity().await.unwrap();
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, Decimal::from(7000));
+        assert!(summary.closed_at.is_some());
+
+        repo.pool.close().await;
+        let _ = std::fs::remove_file(db_path);
+    }
+
+    // ── Paramètres ────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_settings_valeurs_initiales() {
         let repo = make_repo().await;
-        let err = repo.create_member(member_input("", "Jean", "Communiant")).await.unwrap_err();
-        assert!(matches!(err, AppError::Validation(_)));
+        let settings = repo.get_settings().await.unwrap();
+        assert_eq!(settings.church_name, DEFAULT_CHURCH_NAME);
+        assert_eq!(settings.church_address, "");
+        assert_eq!(settings.treasurer_name, "");
+        assert_eq!(settings.currency_label, DEFAULT_CURRENCY_LABEL);
+        assert_eq!(settings.default_pledge, Decimal::ZERO);
+        assert_eq!(settings.backup_retention, DEFAULT_BACKUP_RETENTION);
+        assert!(settings.quarter_periods);
+        assert!(settings.startup_integrity_check);
+        assert!(settings.auto_close_enabled);
+        assert_eq!(settings.auto_close_grace_days, DEFAULT_GRACE_DAYS);
+        assert_eq!(settings.max_future_days, DEFAULT_MAX_FUTURE_DAYS);
+        assert_eq!(settings.renewal_window_days, DEFAULT_RENEWAL_WINDOW_DAYS);
+        assert_eq!(settings.fiscal_year_start_month, 1);
+        assert_eq!(settings.default_period, "");
+        assert!(settings.operators.is_empty());
+        assert_eq!(
+            settings.quick_amounts,
+            DEFAULT_QUICK_AMOUNTS.map(Decimal::from).to_vec(),
+        );
     }
 
     #[tokio::test]
-    async fn test_create_member_nom_vide() {
+    async fn test_update_settings() {
         let repo = make_repo().await;
-        let err = repo.create_member(member_input("C001", "  ", "Communiant")).await.unwrap_err();
-        assert!(matches!(err, AppError::Validation(_)));
+        let saved = repo
+            .update_settings(Settings {
+                church_name:      "  FJKM Analakely ".into(),
+                church_address:   "Lalana Rainandriamampandry".into(),
+                treasurer_name:   " Rasoa ".into(),
+                currency_label:   "  ".into(),
+                default_pledge:   Decimal::from(12_000),
+                backup_retention: 30,
+                quarter_periods:  false,
+                startup_integrity_check: false,
+                auto_close_enabled: false,
+                auto_close_grace_days: 45,
+                max_future_days:  0,
+                renewal_window_days: 60,
+                fiscal_year_start_month: 7,
+                default_period:   " 2025 ".into(),
+                quick_amounts:    vec![Decimal::from(2_000), Decimal::from_str("7500.50").unwrap()],
+                card_pattern_communiant: " M%d/A ".into(),
+                card_pattern_cathekomen: "K-%04d".into(),
+                operators:        vec![" Naina ".into(), "".into(), "Tiana".into(), "Naina".into()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(saved.church_name, "FJKM Analakely");
+        assert_eq!(saved.treasurer_name, "Rasoa");
+        // Libellé vide : retour à la valeur par défaut
+        assert_eq!(saved.currency_label, DEFAULT_CURRENCY_LABEL);
+        assert_eq!(saved.operators, vec!["Naina".to_string(), "Tiana".to_string()]);
+        assert_eq!(repo.get_setting("operators").await.unwrap().as_deref(), Some("Naina,Tiana"));
+        assert_eq!(saved.default_period, "2025");
+        assert_eq!(saved.quick_amounts, vec![Decimal::from(2_000), Decimal::from_str("7500.5").unwrap()]);
+        assert_eq!(repo.get_setting("quick_amounts").await.unwrap().as_deref(), Some("2000,7500.5"));
+        assert_eq!(saved.card_pattern_communiant, "M%d/A");
+        assert!(!saved.startup_integrity_check);
+        assert!(!saved.auto_close_enabled);
+        assert_eq!(repo.get_setting("auto_close_grace_days").await.unwrap().as_deref(), Some("45"));
+        assert_eq!(saved.max_future_days, 0);
+        assert_eq!(saved.renewal_window_days, 60);
+        assert_eq!(saved.fiscal_year_start_month, 7);
+        assert_eq!(saved.default_pledge, Decimal::from(12_000));
+        assert_eq!(repo.get_backup_retention().await.unwrap(), 30);
+        assert_eq!(
+            repo.get_setting("church_address").await.unwrap().as_deref(),
+            Some("Lalana Rainandriamampandry"),
+        );
     }
 
     #[tokio::test]
-    async fn test_create_member_carte_duplicate() {
+    async fn test_mois_exercice_fige_apres_cotisations() {
         let repo = make_repo().await;
-        repo.create_member(member_input("C001", "Jean", "Communiant")).await.unwrap();
-        let err = repo.create_member(member_input("C001", "Pierre", "Communiant")).await.unwrap_err();
-        assert!(matches!(err, AppError::Db));
+        let base = repo.get_settings().await.unwrap();
+        let juillet = Settings { fiscal_year_start_month: 7, ..base.clone() };
+        let juillet = repo.update_settings(juillet).await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2025-03-01", "2025-03", "3000")).await.unwrap();
+
+        let janvier = Settings { fiscal_year_start_month: 1, ..juillet.clone() };
+        assert!(matches!(repo.update_settings(janvier).await, Err(AppError::Validation(_))));
+        assert_eq!(repo.get_settings().await.unwrap().fiscal_year_start_month, 7);
+        // Les autres paramètres restent modifiables tant que le mois ne change pas
+        let nom = Settings { church_name: "FJKM Isotry".into(), ..juillet };
+        assert_eq!(repo.update_settings(nom).await.unwrap().church_name, "FJKM Isotry");
     }
 
     #[tokio::test]
-    async fn test_get_members_vide() {
+    async fn test_update_settings_invalides() {
         let repo = make_repo().await;
-        let list = repo.get_members().await.unwrap();
-        assert!(list.is_empty());
+        let base = repo.get_settings().await.unwrap();
+        let vide = Settings { church_name: " ".into(), ..base.clone() };
+        assert!(matches!(repo.update_settings(vide).await, Err(AppError::Validation(_))));
+        let negatif = Settings { default_pledge: Decimal::from(-1), ..base.clone() };
+        assert!(matches!(repo.update_settings(negatif).await, Err(AppError::Validation(_))));
+        // Trimestres désactivés : "2025-T1" refusé comme période par défaut
+        let periode = Settings {
+            default_period:  "2025-T1".into(),
+            quarter_periods: false,
+            ..base.clone()
+        };
+        assert!(matches!(repo.update_settings(periode).await, Err(AppError::Validation(_))));
+        let delai = Settings { auto_close_grace_days: MAX_GRACE_DAYS + 1, ..base.clone() };
+        assert!(matches!(repo.update_settings(delai).await, Err(AppError::Validation(_))));
+        let avance = Settings { max_future_days: MAX_FUTURE_DAYS + 1, ..base.clone() };
+        assert!(matches!(repo.update_settings(avance).await, Err(AppError::Validation(_))));
+        let renouvellement = Settings { renewal_window_days: MAX_RENEWAL_WINDOW_DAYS + 1, ..base.clone() };
+        assert!(matches!(repo.update_settings(renouvellement).await, Err(AppError::Validation(_))));
+        for mois in [0, 13] {
+            let exercice = Settings { fiscal_year_start_month: mois, ..base.clone() };
+            assert!(matches!(repo.update_settings(exercice).await, Err(AppError::Validation(_))));
+        }
+        let montant_nul = Settings { quick_amounts: vec![Decimal::ZERO], ..base.clone() };
+        assert!(matches!(repo.update_settings(montant_nul).await, Err(AppError::Validation(_))));
+        let trop = Settings {
+            quick_amounts: vec![Decimal::ONE; MAX_QUICK_AMOUNTS + 1],
+            ..base.clone()
+        };
+        assert!(matches!(repo.update_settings(trop).await, Err(AppError::Validation(_))));
+        for bad in ["C-", "C-%s", "C-%4d", "%d-%d"] {
+            let modele = Settings { card_pattern_cathekomen: bad.into(), ..base.clone() };
+            assert!(matches!(repo.update_settings(modele).await, Err(AppError::Validation(_))));
+        }
+        // Rien n'a été écrit
+        assert_eq!(repo.get_settings().await.unwrap(), base);
     }
 
     #[tokio::test]
-    async fn test_get_members() {
+    async fn test_language() {
         let repo = make_repo().await;
-        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
-        let list = repo.get_members().await.unwrap();
-        assert_eq!(list.len(), 2);
+        assert_eq!(repo.get_language().await.unwrap(), Lang::Mg);
+        assert_eq!(repo.set_language(Lang::Fr).await.unwrap(), Lang::Fr);
+        assert_eq!(repo.get_language().await.unwrap(), Lang::Fr);
+        // Message de validation traduit selon la langue demandée
+        let err = parse_amount("-5").unwrap_err();
+        assert_eq!(err.message(Lang::Fr), "Le montant ne peut pas être négatif.");
+        assert_eq!(err.message(Lang::Mg), "Tsy azo atao latsaky ny aotra ny vola.");
+        repo.set_language(Lang::Mg).await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_get_members_by_type() {
+    async fn test_next_card_number() {
         let repo = make_repo().await;
-        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
-        repo.create_member(member_input("C003", "Carol", "Communiant")).await.unwrap();
+        assert_eq!(repo.next_card_number(MemberType::Communiant).await.unwrap(), "C-0001");
+        for (card, mtype) in [
+            ("C-0001", "Communiant"),
+            ("C-0007", "Communiant"),
+            ("C-12", "Communiant"),      // sans zéros : suit quand même le modèle
+            ("C-0009X", "Communiant"),   // ancien format : ignoré
+            ("2019/453", "Communiant"),  // ancien format : ignoré
+            ("K-0003", "Cathekomen"),
+        ] {
+            repo.create_member(member_input(card, card, mtype)).await.unwrap();
+        }
+        // Les trous ne sont pas réutilisés : max + 1
+        assert_eq!(repo.next_card_number(MemberType::Communiant).await.unwrap(), "C-0013");
+        assert_eq!(repo.next_card_number(MemberType::Cathekomen).await.unwrap(), "K-0004");
 
-        let comm = repo.get_members_by_type("Communiant").await.unwrap();
-        assert_eq!(comm.len(), 2);
+        // Membre à la corbeille : son numéro reste pris
+        let m = repo.create_member(member_input("C-0020", "Rabe", "Communiant")).await.unwrap();
+        repo.delete_member(m.id).await.unwrap();
+        assert_eq!(repo.next_card_number(MemberType::Communiant).await.unwrap(), "C-0021");
 
-        let cath = repo.get_members_by_type("Cathekomen").await.unwrap();
-        assert_eq!(cath.len(), 1);
+        // Modèle personnalisé, largeur dépassée sans troncature
+        repo.set_setting("card_pattern_cathekomen", "K%02d").await.unwrap();
+        repo.create_member(member_input("K123", "Rasoa", "Cathekomen")).await.unwrap();
+        assert_eq!(repo.next_card_number(MemberType::Cathekomen).await.unwrap(), "K124");
     }
 
     #[tokio::test]
-    async fn test_update_member() {
+    async fn test_get_all_settings_sans_pin() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let updated = repo.update_member(m.id, member_input("C001-U", "Alice Martin", "Communiant")).await.unwrap();
-        assert_eq!(updated.card_number, "C001-U");
-        assert_eq!(updated.full_name, "Alice Martin");
+        repo.set_pin("1234").await.unwrap();
+        repo.set_setting("custom", "x").await.unwrap();
+        let all = repo.get_all_settings().await.unwrap();
+        assert_eq!(all.get("custom").map(String::as_str), Some("x"));
+        assert!(!all.contains_key("admin_pin"));
     }
 
+    // ── PIN ───────────────────────────────────────────────────────────────────
+
     #[tokio::test]
-    async fn test_delete_member_cascade() {
+    async fn test_pin_hash_sale() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2024-03-01", "2024", "5000")).await.unwrap();
-        repo.delete_member(m.id).await.unwrap();
-        let list = repo.get_members().await.unwrap();
-        assert!(list.is_empty());
-        let contribs = repo.get_contributions(m.id).await.unwrap();
-        assert!(contribs.is_empty());
+        assert!(!repo.has_pin().await.unwrap());
+        repo.set_pin("1234").await.unwrap();
+        assert!(repo.has_pin().await.unwrap());
+
+        let stored = repo.get_setting("admin_pin").await.unwrap().unwrap();
+        let (salt, _) = stored.split_once('$').expect("empreinte salée");
+        assert!(!salt.is_empty());
+        assert!(!stored.contains("1234"));
+        assert!(Repository::pin_matches("1234", &stored));
+        assert!(!Repository::pin_matches("4321", &stored));
+
+        assert!(repo.verify_pin("1234").await.is_ok());
+        assert!(matches!(repo.verify_pin("0000").await, Err(AppError::Validation(_))));
     }
 
     #[tokio::test]
-    async fn test_transfer_members() {
+    async fn test_pin_ancienne_empreinte_resalee() {
+        use sha2::{Digest, Sha256};
         let repo = make_repo().await;
-        let m1 = repo.create_member(member_input("C001", "Alice", "Cathekomen")).await.unwrap();
-        let m2 = repo.create_member(member_input("C002", "Bob", "Cathekomen")).await.unwrap();
-        let n = repo.transfer_members(&[m1.id, m2.id], "Communiant").await.unwrap();
-        assert_eq!(n, 2);
-        let comm = repo.get_members_by_type("Communiant").await.unwrap();
-        assert_eq!(comm.len(), 2);
-        let cath = repo.get_members_by_type("Cathekomen").await.unwrap();
-        assert!(cath.is_empty());
+        let legacy = format!("{:x}", Sha256::digest(b"1234"));
+        repo.set_setting("admin_pin", &legacy).await.unwrap();
+
+        repo.verify_pin("1234").await.unwrap();
+        let stored = repo.get_setting("admin_pin").await.unwrap().unwrap();
+        assert!(stored.contains('$'));
+        assert!(Repository::pin_matches("1234", &stored));
     }
 
     #[tokio::test]
-    async fn test_transfer_ids_vides() {
+    async fn test_pin_jeton_expire() {
         let repo = make_repo().await;
-        let n = repo.transfer_members(&[], "Communiant").await.unwrap();
-        assert_eq!(n, 0);
+        // Sans PIN configuré, les actions destructrices restent libres
+        repo.require_pin_token(None).await.unwrap();
+
+        repo.set_pin("1234").await.unwrap();
+        let now = 1_700_000_000;
+        assert!(repo.require_pin_token_at(None, now).await.is_err());
+        assert!(repo.require_pin_token_at(Some("inconnu"), now).await.is_err());
+
+        let token = repo.verify_pin_at("1234", now).await.unwrap();
+        repo.require_pin_token_at(Some(&token), now + PIN_TOKEN_TTL_SECS - 1).await.unwrap();
+        let err = repo
+            .require_pin_token_at(Some(&token), now + PIN_TOKEN_TTL_SECS)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Code PIN requis pour cette action.");
     }
 
-    // ── Total contributions membre ─────────────────────────────────────────────
-
     #[tokio::test]
-    async fn test_total_contributions_zero() {
+    async fn test_pin_verrouillage() {
         let repo = make_repo().await;
-        repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let list = repo.get_members_by_type_with_total("Communiant").await.unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].total_contributions, "0");
+        repo.set_pin("1234").await.unwrap();
+        let now = 1_700_000_000;
+
+        for _ in 0..PIN_MAX_ATTEMPTS {
+            assert!(repo.verify_pin_at("0000", now).await.is_err());
+        }
+        // Verrouillé : même le bon PIN est refusé
+        let err = repo.verify_pin_at("1234", now + 10).await.unwrap_err();
+        assert!(err.to_string().contains("Réessayez"), "{err}");
+        assert!(repo.verify_pin_at("1234", now + PIN_LOCKOUT_SECS - 1).await.is_err());
+
+        // Fin du verrouillage
+        repo.verify_pin_at("1234", now + PIN_LOCKOUT_SECS).await.unwrap();
+        let err = repo.verify_pin_at("0000", now + PIN_LOCKOUT_SECS).await.unwrap_err();
+        assert!(err.to_string().contains("restant"), "{err}");
     }
 
     #[tokio::test]
-    async fn test_total_contributions_somme() {
+    async fn test_pin_succes_remet_compteur_a_zero() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2024-01-15", "2024", "10000")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2024-06-01", "2024", "5000.50")).await.unwrap();
-        let list = repo.get_members_by_type_with_total("Communiant").await.unwrap();
-        let total: f64 = list[0].total_contributions.parse().unwrap();
-        assert!((total - 15000.0).abs() < 2.0);
+        repo.set_pin("1234").await.unwrap();
+        let now = 1_700_000_000;
+        for _ in 0..PIN_MAX_ATTEMPTS - 1 {
+            assert!(repo.verify_pin_at("0000", now).await.is_err());
+        }
+        repo.verify_pin_at("1234", now).await.unwrap();
+        // Le compteur repart de zéro : un nouvel échec ne verrouille pas
+        assert!(repo.verify_pin_at("0000", now).await.is_err());
+        repo.verify_pin_at("1234", now).await.unwrap();
     }
 
-    // ── Contributions ─────────────────────────────────────────────────────────
-
     #[tokio::test]
-    async fn test_create_contribution_ok() {
+    async fn test_pin_format() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let c = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "12000")).await.unwrap();
-        assert_eq!(c.member_id, m.id);
-        assert_eq!(c.period, "2024");
-        assert_eq!(c.recorded_year, 2024);
-        assert_eq!(c.amount.to_string(), "12000");
+        for bad in ["123", "1234567", "12a4", ""] {
+            let err = repo.set_pin(bad).await.unwrap_err();
+            assert_eq!(err.to_string(), "Le code PIN doit contenir de 4 à 6 chiffres.");
+        }
+        repo.set_pin("123456").await.unwrap();
+        assert!(repo.set_pin("1234").await.is_err());
     }
 
     #[tokio::test]
-    async fn test_create_contribution_montant_invalide() {
+    async fn test_pin_modification_exige_l_ancien() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let err = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "abc")).await.unwrap_err();
-        assert!(matches!(err, AppError::Validation(_)));
+        repo.set_pin("1234").await.unwrap();
+        let token = repo.verify_pin("1234").await.unwrap();
+
+        assert!(repo.change_pin("0000", "5678").await.is_err());
+        assert!(repo.change_pin("1234", "56").await.is_err());
+        repo.change_pin("1234", "5678").await.unwrap();
+        assert!(repo.verify_pin("1234").await.is_err());
+        repo.verify_pin("5678").await.unwrap();
+        // Les jetons délivrés avec l'ancien PIN ne valent plus rien
+        assert!(repo.require_pin_token(Some(&token)).await.is_err());
     }
 
     #[tokio::test]
-    async fn test_create_contribution_montant_negatif() {
+    async fn test_pin_suppression() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let err = repo.create_contribution(contribution_input(m.id, "2024-03-15", "2024", "-500")).await.unwrap_err();
-        assert!(matches!(err, AppError::Validation(_)));
+        repo.set_pin("1234").await.unwrap();
+        assert!(repo.verify_pin("0000").await.is_err());
+
+        assert!(repo.remove_pin("0000").await.is_err());
+        assert!(repo.has_pin().await.unwrap());
+        repo.remove_pin("1234").await.unwrap();
+        assert!(!repo.has_pin().await.unwrap());
+        assert_eq!(repo.get_setting("admin_pin_attempts").await.unwrap(), None);
+        repo.require_pin_token(None).await.unwrap();
+        // Un nouveau PIN peut ensuite être défini
+        repo.set_pin("4321").await.unwrap();
+    }
+
+    // ── Clôture automatique / délai de grâce ──────────────────────────────────
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
     }
 
     #[tokio::test]
-    async fn test_create_contribution_date_invalide() {
+    async fn test_cloture_auto_pendant_delai_de_grace() {
         let repo = make_repo().await;
         let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let err = repo.create_contribution(contribution_input(m.id, "15-03-2024", "2024", "1000")).await.unwrap_err();
-        assert!(matches!(err, AppError::Validation(_)));
+        repo.create_contribution(contribution_input(m.id, "2024-12-20", "2024", "5000")).await.unwrap();
+
+        let status = repo
+            .check_and_close_previous_year_on(date("2025-01-15"), Some(DEFAULT_GRACE_DAYS))
+            .await
+            .unwrap();
+        match status {
+            YearCloseStatus::PendingUserAction { year, total, auto_close_on } => {
+                assert_eq!(year, 2024);
+                assert_eq!(total, Decimal::from_str("5000").unwrap());
+                assert_eq!(auto_close_on, "2025-01-16");
+            }
+            other => panic!("attendu PendingUserAction, obtenu {other:?}"),
+        }
+        // Rien n'est clôturé : une saisie de décembre reste possible
+        assert!(repo.get_year_summary(2024).await.unwrap().unwrap().closed_at.is_none());
+        repo.create_contribution(contribution_input(m.id, "2024-12-28", "2024", "1000")).await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_delete_contribution_recalcule_total() {
+    async fn test_cloture_auto_apres_delai_de_grace() {
         let repo = make_repo().await;
         let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        let c1 = repo.create_contribution(contribution_input(m.id, "2024-01-01", "2024", "10000")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2024-06-01", "2024", "5000")).await.unwrap();
+        repo.create_contribution(contribution_input(m.id, "2024-12-20", "2024", "5000")).await.unwrap();
 
-        let s = repo.get_year_summary(2024).await.unwrap().unwrap();
-        assert_eq!(s.total, Decimal::from_str("15000").unwrap());
+        let status = repo
+            .check_and_close_previous_year_on(date("2025-01-16"), Some(DEFAULT_GRACE_DAYS))
+            .await
+            .unwrap();
+        let YearCloseStatus::Closed { summary } = status else {
+            panic!("attendu Closed, obtenu {status:?}");
+        };
+        assert_eq!(summary.year, 2024);
+        assert!(summary.closed_at.is_some());
+        assert_eq!(summary.total_communiant, Decimal::from_str("5000").unwrap());
+        let note = summary.note.unwrap();
+        assert!(note.contains("5 000 Ariary"));
+        assert!(note.contains("Communiants : 5 000 Ariary / Cathekomens : 0 Ariary / 1 cotisation(s) · 1 membre(s)"));
+
+        // Deuxième appel : déjà clôturée
+        let again = repo
+            .check_and_close_previous_year_on(date("2025-01-17"), Some(DEFAULT_GRACE_DAYS))
+            .await
+            .unwrap();
+        assert!(matches!(again, YearCloseStatus::AlreadyClosed));
+    }
 
-        repo.delete_contribution(c1.id).await.unwrap();
-        let s2 = repo.get_year_summary(2024).await.unwrap().unwrap();
-        assert_eq!(s2.total, Decimal::from_str("5000").unwrap());
+    #[tokio::test]
+    async fn test_cloture_immediate_sans_delai() {
+        let repo = make_repo().await;
+        let status = repo.check_and_close_previous_year_on(date("2025-01-02"), Some(0)).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::Closed { ref summary } if summary.year == 2024));
     }
 
     #[tokio::test]
-    async fn test_get_contributions_by_year_with_member() {
+    async fn test_cloture_auto_selon_parametres() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice Rakoto", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2024-04-10", "2024", "8000")).await.unwrap();
-        let list = repo.get_contributions_by_year_with_member(2024).await.unwrap();
-        assert_eq!(list.len(), 1);
-        assert_eq!(list[0].member_name, "Alice Rakoto");
-        assert_eq!(list[0].recorded_year, 2024);
+        repo.set_setting("auto_close_grace_days", "45").await.unwrap();
+
+        // Délai des paramètres : clôture prévue le 15 février
+        let status = repo.check_and_close_previous_year_on(date("2025-02-14"), None).await.unwrap();
+        assert!(matches!(
+            status,
+            YearCloseStatus::PendingUserAction { year: 2024, ref auto_close_on, .. }
+                if auto_close_on == "2025-02-15"
+        ));
+
+        // Clôture automatique désactivée : rien n'est clôturé, même après le délai
+        repo.set_setting("auto_close_enabled", "0").await.unwrap();
+        let status = repo.check_and_close_previous_year_on(date("2025-03-01"), None).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::Disabled { year: 2024, .. }));
+        assert!(repo.get_year_summary(2024).await.unwrap().unwrap().closed_at.is_none());
+
+        // « Hikatona izao » reste possible
+        let status = repo.check_and_close_previous_year_on(date("2025-03-01"), Some(0)).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::Closed { .. }));
+
+        repo.set_setting("auto_close_enabled", "1").await.unwrap();
+        let status = repo.check_and_close_previous_year_on(date("2025-03-01"), None).await.unwrap();
+        assert!(matches!(status, YearCloseStatus::AlreadyClosed));
     }
 
-    // ── Résumés annuels ───────────────────────────────────────────────────────
+    // ── Anniversaires ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_prochain_anniversaire_bornes() {
+        let next = |birth: &str, today: &str| {
+            Repository::next_birthday(date(birth), date(today)).unwrap().to_string()
+        };
+        // Aujourd'hui même : pas reporté à l'an prochain
+        assert_eq!(next("1990-06-15", "2025-06-15"), "2025-06-15");
+        // Passé de la veille : l'an prochain
+        assert_eq!(next("1990-06-14", "2025-06-15"), "2026-06-14");
+        // Fin décembre : janvier de l'année suivante
+        assert_eq!(next("1980-01-03", "2025-12-28"), "2026-01-03");
+        assert_eq!(next("1980-12-31", "2025-12-31"), "2025-12-31");
+        // 29 février : le 28 les années non bissextiles, le 29 sinon
+        assert_eq!(next("2000-02-29", "2025-02-01"), "2025-02-28");
+        assert_eq!(next("2000-02-29", "2027-03-01"), "2028-02-29");
+        assert_eq!(next("2000-02-29", "2025-03-01"), "2026-02-28");
+    }
+
+    #[test]
+    fn test_age_et_validation_naissance() {
+        assert_eq!(Repository::age_on(date("1990-06-15"), date("2025-06-14")), 34);
+        assert_eq!(Repository::age_on(date("1990-06-15"), date("2025-06-15")), 35);
+        assert_eq!(Repository::age_on(date("2000-02-29"), date("2025-02-28")), 24);
+
+        let today = date("2025-06-15");
+        assert!(Repository::validate_birth_date("1990-06-15", today).is_ok());
+        assert!(Repository::validate_birth_date("2025-06-15", today).is_ok());
+        for bad in ["15/06/1990", "1990-13-01", "2023-02-29", "", "2025-06-16", "1905-06-15"] {
+            let err = Repository::validate_birth_date(bad, today).unwrap_err();
+            assert!(matches!(err, AppError::Validation(_)), "{bad}");
+        }
+        // Juste sous la limite
+        assert!(Repository::validate_birth_date("1905-06-16", today).is_ok());
+    }
 
     #[tokio::test]
-    async fn test_year_summary_auto_cree() {
+    async fn test_anniversaires_a_venir() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2023-05-01", "2023", "20000")).await.unwrap();
-        let s = repo.get_year_summary(2023).await.unwrap().unwrap();
-        assert_eq!(s.year, 2023);
-        assert_eq!(s.total, Decimal::from_str("20000").unwrap());
-        assert!(s.closed_at.is_none());
+        let with_birth = |card: &str, name: &str, birth: Option<&str>| MemberInput {
+            birth_date: birth.map(str::to_string),
+            ..member_input(card, name, "Communiant")
+        };
+        repo.create_member(with_birth("C001", "Alice", Some("1990-01-02"))).await.unwrap();
+        repo.create_member(with_birth("C002", "Bako", Some("2000-02-29"))).await.unwrap();
+        repo.create_member(with_birth("C003", "Koto", Some("1975-12-30"))).await.unwrap();
+        repo.create_member(with_birth("C004", "Lova", None)).await.unwrap();
+        repo.create_member(with_birth("C005", "Noro", Some("1985-06-01"))).await.unwrap();
+
+        // 28 décembre, fenêtre de 7 jours : chevauche le Nouvel An
+        let list = repo.get_upcoming_birthdays_on(date("2025-12-28"), 7).await.unwrap();
+        let got: Vec<_> = list.iter().map(|b| (b.full_name.as_str(), b.days_until, b.age)).collect();
+        assert_eq!(got, vec![("Koto", 2, 50), ("Alice", 5, 36)]);
+        assert_eq!(list[1].date, "2026-01-02");
+
+        // 29 février fêté le 28 en 2026
+        let list = repo.get_upcoming_birthdays_on(date("2026-02-27"), 1).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!((list[0].full_name.as_str(), list[0].date.as_str(), list[0].age), ("Bako", "2026-02-28", 26));
+
+        // Fenêtre 0 : seulement aujourd'hui
+        let list = repo.get_upcoming_birthdays_on(date("2026-06-01"), 0).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].days_until, 0);
+
+        // Saisie refusée : date future
+        let err = repo
+            .create_member(with_birth("C006", "Vola", Some("2999-01-01")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        // Lue et modifiée avec le reste de la fiche
+        let m = repo.get_members().await.unwrap().into_iter().find(|m| m.card_number == "C004").unwrap();
+        assert_eq!(m.birth_date, None);
+        let updated = repo
+            .update_member(m.id, with_birth("C004", "Lova", Some("1999-09-09")))
+            .await
+            .unwrap();
+        assert_eq!(updated.birth_date.as_deref(), Some("1999-09-09"));
     }
 
     #[tokio::test]
-    async fn test_close_and_reopen_year() {
+    async fn test_renouvellements_a_venir() {
         let repo = make_repo().await;
-        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "50000")).await.unwrap();
+        let registered = |card: &'static str, name: &'static str, created_at: &'static str| {
+            let repo = &repo;
+            async move {
+                let m = repo.create_member(member_input(card, name, "Communiant")).await.unwrap();
+                sqlx::query("UPDATE members SET created_at = ? WHERE id = ?")
+                    .bind(created_at)
+                    .bind(m.id)
+                    .execute(&repo.pool)
+                    .await
+                    .unwrap();
+                m
+            }
+        };
+        registered("C001", "Alice", "2023-01-02T09:00:00").await;
+        registered("C002", "Bako", "2024-02-29T10:30:00").await;
+        registered("C003", "Koto", "2020-12-30T08:00:00").await;
+        // Inscrit cette année : pas encore de renouvellement
+        registered("C004", "Lova", "2025-12-29T08:00:00").await;
+        let noro = registered("C005", "Noro", "2021-12-31T08:00:00").await;
+        let vola = registered("C006", "Vola", "2022-12-31T08:00:00").await;
+        sqlx::query("UPDATE members SET status = 'Inactif' WHERE id = ?")
+            .bind(noro.id)
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        repo.delete_member(vola.id).await.unwrap();
 
-        let closed = repo.close_year(2022, Some("Test note".into())).await.unwrap();
-        assert!(closed.closed_at.is_some());
-        assert_eq!(closed.note.as_deref(), Some("Test note"));
+        // 28 décembre, fenêtre de 7 jours : chevauche le Nouvel An
+        let list = repo.get_upcoming_renewals_on(date("2025-12-28"), 7).await.unwrap();
+        let got: Vec<_> = list.iter().map(|r| (r.card_number.as_str(), r.days_until, r.years)).collect();
+        assert_eq!(got, vec![("C003", 2, 5), ("C001", 5, 3)]);
+        assert_eq!((list[1].registered_on.as_str(), list[1].date.as_str()), ("2023-01-02", "2026-01-02"));
 
-        let reopened = repo.reopen_year(2022).await.unwrap();
-        assert!(reopened.closed_at.is_none());
-        assert!(reopened.note.is_none());
+        // Inscrit un 29 février : renouvelé le 28 en 2026
+        let list = repo.get_upcoming_renewals_on(date("2026-02-27"), 1).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!((list[0].full_name.as_str(), list[0].date.as_str(), list[0].years), ("Bako", "2026-02-28", 2));
+        // … et le 29 les années bissextiles
+        let list = repo.get_upcoming_renewals_on(date("2028-02-28"), 1).await.unwrap();
+        assert_eq!(list[0].date, "2028-02-29");
+
+        // Fenêtre 0 : seulement aujourd'hui
+        let list = repo.get_upcoming_renewals_on(date("2026-12-30"), 0).await.unwrap();
+        assert_eq!(list.iter().map(|r| r.days_until).collect::<Vec<_>>(), vec![0]);
+        assert!(repo.get_upcoming_renewals_on(date("2026-06-01"), 30).await.unwrap().is_empty());
     }
 
+    // ── Base verrouillée ──────────────────────────────────────────────────────
+
     #[tokio::test]
-    async fn test_close_year_sans_contributions() {
-        let repo = make_repo().await;
+    async fn test_base_verrouillee_reessais() {
+        let db_path = temp_db_path();
+        let repo = Repository::with_busy_timeout(&db_path, Duration::from_millis(20)).await.unwrap();
+
+        // Second pool tenant un verrou d'écriture, comme un outil de sauvegarde
+        let other = SqlitePool::connect_with(SqliteConnectOptions::new().filename(&db_path))
+            .await
+            .unwrap();
+        let mut locker = other.acquire().await.unwrap();
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *locker).await.unwrap();
+
+        // Verrou jamais relâché : message clair après les tentatives
+        let err = repo
+            .create_member(member_input("C001", "Alice", "Communiant"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Busy));
+        assert_eq!(err.kind(), "validation");
+        assert_eq!(err.to_string(), "La base est momentanément occupée, réessayez.");
+
+        // Verrou relâché pendant les tentatives : l'écriture finit par passer
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            sqlx::query("COMMIT").execute(&mut *locker).await.unwrap();
+        });
         let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2021-01-01", "2021", "0")).await.unwrap();
-        let closed = repo.close_year(2021, None).await.unwrap();
-        assert!(closed.closed_at.is_some());
+        release.await.unwrap();
+        assert_eq!(repo.get_member(m.id).await.unwrap().full_name, "Alice");
+
+        other.close().await;
+        repo.pool.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{db_path}{suffix}"));
+        }
     }
 
-    #[tokio::test]
-    async fn test_get_year_summaries_ordre_desc() {
-        let repo = make_repo().await;
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_saisies_concurrentes_meme_annee() {
+        let db_path = temp_db_path();
+        let repo = Repository::new(&db_path).await.unwrap();
         let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2021-01-01", "2021", "1000")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2023-01-01", "2023", "2000")).await.unwrap();
-        repo.create_contribution(contribution_input(m.id, "2022-01-01", "2022", "3000")).await.unwrap();
-        let list = repo.get_year_summaries().await.unwrap();
-        assert_eq!(list.len(), 3);
-        assert_eq!(list[0].year, 2023);
-        assert_eq!(list[1].year, 2022);
-        assert_eq!(list[2].year, 2021);
+
+        // 50 saisies simultanées sur 2024, montants à centimes distincts
+        let tasks: Vec<_> = (1..=50)
+            .map(|i| {
+                let repo = repo.clone();
+                let amount = format!("{i}.{:02}", i % 100);
+                tokio::spawn(async move {
+                    repo.create_contribution(contribution_input(m.id, "2024-05-01", "2024", &amount)).await
+                })
+            })
+            .collect();
+        let mut receipts = BTreeSet::new();
+        for task in tasks {
+            let c = task.await.unwrap().unwrap();
+            receipts.insert(c.receipt_number);
+        }
+        assert_eq!(receipts.len(), 50);
+
+        let expected: Decimal = (1..=50)
+            .map(|i| Decimal::from_str(&format!("{i}.{:02}", i % 100)).unwrap())
+            .sum();
+        let summary = repo.get_year_summary(2024).await.unwrap().unwrap();
+        assert_eq!(summary.total, expected);
+        assert_eq!(summary.contribution_count, 50);
+
+        repo.pool.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{db_path}{suffix}"));
+        }
     }
 
-    #[tokio::test]
-    async fn test_format_ariary_note() {
-        let d = Decimal::from_str("1234567").unwrap();
-        assert_eq!(Repository::format_ariary_note(&d), "1 234 567 Ariary");
-        let z = Decimal::ZERO;
-        assert_eq!(Repository::format_ariary_note(&z), "0 Ariary");
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parametres_et_saisies_concurrents() {
+        // Attente courte : une écriture hors du verrou échouerait en `Busy`
+        let db_path = temp_db_path();
+        let repo = Repository::with_busy_timeout(&db_path, Duration::from_millis(20)).await.unwrap();
+        let m = repo.create_member(member_input("C001", "Alice", "Communiant")).await.unwrap();
+        repo.set_pin("1234").await.unwrap();
+
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            let repo = repo.clone();
+            tasks.push(tokio::spawn(async move {
+                repo.create_contribution(contribution_input(m.id, "2024-05-01", "2024", "100")).await?;
+                repo.set_setting("church_address", &format!("Adiresy {i}")).await?;
+                repo.log_event("info", "test", &format!("saisie {i}")).await
+            }));
+        }
+        // Deux essais de PIN erronés simultanés : deux échecs comptés
+        let wrong: Vec<_> = (0..2)
+            .map(|_| {
+                let repo = repo.clone();
+                tokio::spawn(async move { repo.verify_pin("9999").await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        for task in wrong {
+            let err = task.await.unwrap().unwrap_err();
+            assert!(matches!(err, AppError::Validation(ref m) if m.key == "pin.wrong"));
+        }
+
+        assert_eq!(repo.get_year_summary(2024).await.unwrap().unwrap().contribution_count, 20);
+        assert!(repo.get_setting("church_address").await.unwrap().unwrap().starts_with("Adiresy "));
+        assert_eq!(repo.get_app_logs(50).await.unwrap().len(), 20);
+        assert_eq!(repo.get_setting("admin_pin_attempts").await.unwrap().as_deref(), Some("2"));
+
+        repo.pool.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{db_path}{suffix}"));
+        }
     }
 }
@@ -0,0 +1,150 @@
+//! Données de démonstration (`Repository::seed_demo_data`) : noms malgaches tirés
+//! de listes intégrées, téléphones et adresses plausibles, montants variés.
+//!
+//! Générateur SplitMix64 maison, sans dépendance : une même graine donne
+//! toujours les mêmes membres et les mêmes cotisations (tests, démos répétables).
+
+use chrono::{Datelike, NaiveDate};
+
+use super::models::Gender;
+
+const SURNAMES: &[&str] = &[
+    "RAKOTOARISOA", "RANDRIANARISOA", "RAZAFINDRAKOTO", "RABEMANANJARA", "RASOANAIVO",
+    "ANDRIAMAHEFA", "RAKOTONIRINA", "RAMANANTSOA", "RANDRIAMAMPIONONA", "RAZANAMPARANY",
+    "RAJAONARISON", "RAVELOJAONA", "RAHARISON", "ANDRIANTSOA", "RATSIMBAZAFY", "RAMAROSON",
+];
+
+const MALE_NAMES: &[&str] = &[
+    "Hery", "Tiana", "Mamy", "Faly", "Rivo", "Toky", "Haja", "Njaka", "Solofo", "Andry",
+    "Tojo", "Fidy", "Jean", "Paul",
+];
+
+const FEMALE_NAMES: &[&str] = &[
+    "Voahangy", "Lalao", "Hanitra", "Nirina", "Fara", "Vola", "Miora", "Lova", "Tahina",
+    "Onja", "Fitiavana", "Marie", "Hasina", "Noro",
+];
+
+const ADDRESSES: &[&str] = &[
+    "Ambalavao Isotry", "Isotry", "Andavamamba", "Anosibe", "Ampefiloha", "Ankadifotsy",
+    "67 Ha", "Anosy",
+];
+
+const JOBS: &[&str] = &[
+    "Mpampianatra", "Mpivarotra", "Mpamboly", "Mpanjaitra", "Mpitsabo", "Mpianatra",
+    "Mpiasam-panjakana", "Mpamily",
+];
+
+/// Montants usuels d'une cotisation (Ariary).
+const AMOUNTS: &[i64] = &[1_000, 2_000, 5_000, 5_000, 10_000, 10_000, 20_000, 50_000];
+
+/// SplitMix64 : rapide, reproductible, largement suffisant pour des données factices.
+pub struct SeedRng(u64);
+
+impl SeedRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Entier dans `0..n` (`n` > 0).
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Vrai avec une probabilité de `percent` %.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+pub fn gender(rng: &mut SeedRng) -> Gender {
+    if rng.chance(50) { Gender::M } else { Gender::F }
+}
+
+/// "RAKOTOARISOA Hery" : nom de famille en capitales, prénom selon le genre.
+pub fn full_name(rng: &mut SeedRng, gender: Gender) -> String {
+    let first = match gender {
+        Gender::M => rng.pick(MALE_NAMES),
+        Gender::F => rng.pick(FEMALE_NAMES),
+    };
+    format!("{} {first}", rng.pick(SURNAMES))
+}
+
+pub fn address(rng: &mut SeedRng) -> String {
+    rng.pick(ADDRESSES).to_string()
+}
+
+pub fn job(rng: &mut SeedRng) -> String {
+    rng.pick(JOBS).to_string()
+}
+
+/// Numéro mobile au format enregistré par l'application : "+261 34 12 345 67".
+pub fn phone(rng: &mut SeedRng) -> String {
+    let operator = rng.pick(&["32", "33", "34", "38"]);
+    format!(
+        "+261 {operator} {:02} {:03} {:02}",
+        rng.below(100),
+        rng.below(1000),
+        rng.below(100)
+    )
+}
+
+pub fn amount(rng: &mut SeedRng) -> i64 {
+    *rng.pick(AMOUNTS)
+}
+
+/// Date de paiement de l'année `year`, jamais après `last` (année en cours).
+pub fn payment_date(rng: &mut SeedRng, year: i32, last: NaiveDate) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    let end = NaiveDate::from_ymd_opt(year, 12, 31)?.min(last);
+    let span = (end - first).num_days();
+    if span < 0 {
+        return None;
+    }
+    first.checked_add_days(chrono::Days::new(rng.below(span as u64 + 1)))
+}
+
+/// Période mensuelle de la date : "2024-03".
+pub fn month_period(date: NaiveDate) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meme_graine_meme_suite() {
+        let (mut a, mut b) = (SeedRng::new(42), SeedRng::new(42));
+        let tirage = |rng: &mut SeedRng| {
+            let g = gender(rng);
+            (full_name(rng, g), phone(rng), amount(rng))
+        };
+        assert_eq!(tirage(&mut a), tirage(&mut b));
+        assert_ne!(tirage(&mut a), tirage(&mut SeedRng::new(43)));
+    }
+
+    #[test]
+    fn test_dates_et_telephones_plausibles() {
+        let mut rng = SeedRng::new(7);
+        let last = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        for _ in 0..200 {
+            let d = payment_date(&mut rng, 2025, last).unwrap();
+            assert!(d.year() == 2025 && d <= last);
+            assert!(crate::db::validate::normalize_phone(&phone(&mut rng)).is_ok());
+        }
+        assert!(payment_date(&mut rng, 2026, last).is_none());
+        assert_eq!(month_period(last), "2025-03");
+    }
+}
@@ -0,0 +1,93 @@
+//! Rapprochement approximatif des noms de membres, pour signaler les doublons
+//! probables ("RAKOTO Jean" / "Rakoto Jean" / "Jean Rakoto") à la création.
+//!
+//! Les noms sont réduits (minuscules, accents retirés, ponctuation ignorée, mots
+//! triés) puis comparés par distance de Levenshtein rapportée à leur longueur.
+
+/// Similarité minimale (0..1) pour considérer deux noms comme proches.
+pub const SIMILAR_NAME_THRESHOLD: f64 = 0.8;
+
+/// Lettre sans accent (alphabet latin usuel, y compris ô/à/ì du malgache).
+fn strip_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç'                               => 'c',
+        'è' | 'é' | 'ê' | 'ë'             => 'e',
+        'ì' | 'í' | 'î' | 'ï'             => 'i',
+        'ñ'                               => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö'       => 'o',
+        'ù' | 'ú' | 'û' | 'ü'             => 'u',
+        'ý' | 'ÿ'                         => 'y',
+        _                                 => c,
+    }
+}
+
+/// Forme de comparaison d'un nom : "  RAKOTO-Jean  Hervé " → "herve jean rakoto".
+pub fn fold_name(name: &str) -> String {
+    let folded: String = name
+        .to_lowercase()
+        .chars()
+        .map(strip_accent)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut words: Vec<&str> = folded.split_whitespace().collect();
+    words.sort_unstable();
+    words.join(" ")
+}
+
+/// Nombre minimal d'insertions, suppressions et substitutions de caractères.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Similarité de deux noms entre 0 (rien en commun) et 1 (identiques une fois
+/// réduits par `fold_name`). Un nom vide n'est proche de rien.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = fold_name(a).chars().collect();
+    let b: Vec<char> = fold_name(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_name() {
+        assert_eq!(fold_name("RAKOTO Jean"), "jean rakoto");
+        assert_eq!(fold_name("  Jean   rakoto "), "jean rakoto");
+        assert_eq!(fold_name("Randrianarisôa Hervé-Noël"), "herve noel randrianarisoa");
+        assert_eq!(fold_name("R. Jean"), "jean r");
+        assert_eq!(fold_name("   "), "");
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        // Casse, accents, ordre des mots : identiques
+        assert_eq!(name_similarity("RAKOTO Jean", "Rakoto Jean"), 1.0);
+        assert_eq!(name_similarity("Jean Rakoto", "rakoto jean"), 1.0);
+        assert_eq!(name_similarity("Rasoa Hélène", "RASOA Helene"), 1.0);
+        // Faute de frappe : proche
+        assert!(name_similarity("Rakoto Jean", "Rakotto Jean") >= SIMILAR_NAME_THRESHOLD);
+        assert!(name_similarity("Rabe Paul", "Rabe Paula") >= SIMILAR_NAME_THRESHOLD);
+        // Noms différents : éloignés
+        assert!(name_similarity("Rakoto Jean", "Rakotomalala Jean") < SIMILAR_NAME_THRESHOLD);
+        assert!(name_similarity("Rakoto Jean", "Rasoa Marie") < SIMILAR_NAME_THRESHOLD);
+        assert_eq!(name_similarity("", "Rakoto"), 0.0);
+        assert_eq!(name_similarity(" - ", " - "), 0.0);
+    }
+}
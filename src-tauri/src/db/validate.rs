@@ -0,0 +1,434 @@
+/// Normalisation des saisies libres avant écriture en base.
+///
+/// Téléphones : numéros malgaches uniquement, stockés sous la forme
+/// "+261 3X XX XXX XX" (même présentation que le champ `PhoneInput` du frontend).
+///
+/// Périodes de cotisation : année ("2025"), mois ("2025-03") ou trimestre
+/// ("2025-T1"), toujours stockées sous cette forme canonique.
+///
+/// Recherche par montant (Archives) : "50 000", "50.000" ou "50000" désignent
+/// le même montant (même règle que `utils::amount::amount_matches` côté frontend).
+///
+/// Noms : nom de famille et prénom(s) déduits au mieux du nom complet, pour le tri.
+use std::fmt;
+
+use super::{error::AppError, similarity::fold_name};
+
+/// Indicatif de Madagascar.
+const COUNTRY_CODE: &str = "261";
+
+/// Chiffres du numéro d'abonné, indicatif et 0 initial retirés.
+const SUBSCRIBER_DIGITS: usize = 9;
+
+/// Accepte "034 12 345 67", "0341234567", "+261 34 12 345 67", "261341234567",
+/// "00261341234567" ou "341234567" ; espaces, tirets et points sont ignorés.
+/// Retourne "+261 34 12 345 67".
+pub fn normalize_phone(raw: &str) -> Result<String, AppError> {
+    let invalid = || AppError::validation("member.phone_invalid", &[&raw.trim()]);
+
+    let compact: String = raw
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '.'))
+        .collect();
+    let (plus, digits) = match compact.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, compact.as_str()),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let subscriber = if plus {
+        digits.strip_prefix(COUNTRY_CODE)
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.strip_prefix(COUNTRY_CODE)
+    } else if digits.len() == SUBSCRIBER_DIGITS + 1 {
+        digits.strip_prefix('0')
+    } else if digits.len() == SUBSCRIBER_DIGITS + COUNTRY_CODE.len() {
+        digits.strip_prefix(COUNTRY_CODE)
+    } else {
+        Some(digits)
+    }
+    .ok_or_else(invalid)?;
+
+    // "+261 034…" : 0 de tête toléré après l'indicatif
+    let subscriber = match subscriber.strip_prefix('0') {
+        Some(rest) if subscriber.len() == SUBSCRIBER_DIGITS + 1 => rest,
+        _ => subscriber,
+    };
+    if subscriber.len() != SUBSCRIBER_DIGITS || !subscriber.starts_with(['2', '3']) {
+        return Err(invalid());
+    }
+
+    Ok(format!(
+        "+{COUNTRY_CODE} {} {} {} {}",
+        &subscriber[0..2],
+        &subscriber[2..4],
+        &subscriber[4..7],
+        &subscriber[7..9],
+    ))
+}
+
+/// Téléphone facultatif : absent, vide ou réduit à l'indicatif ("+261 ", laissé
+/// par le champ du frontend) → `None` ; sinon `normalize_phone`.
+pub fn normalize_optional_phone(raw: Option<&str>) -> Result<Option<String>, AppError> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed == format!("+{COUNTRY_CODE}") {
+        return Ok(None);
+    }
+    normalize_phone(trimmed).map(Some)
+}
+
+// ─── Périodes ─────────────────────────────────────────────────────────────────
+
+/// Subdivision de l'année couverte par une cotisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodPart {
+    Year,
+    /// 1 = janvier … 12 = décembre
+    Month(u32),
+    /// 1..=4
+    Quarter(u32),
+}
+
+/// Période de cotisation ; `Display` donne la forme canonique stockée en base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub year: i32,
+    pub part: PeriodPart,
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.part {
+            PeriodPart::Year       => write!(f, "{:04}", self.year),
+            PeriodPart::Month(m)   => write!(f, "{:04}-{m:02}", self.year),
+            PeriodPart::Quarter(q) => write!(f, "{:04}-T{q}", self.year),
+        }
+    }
+}
+
+/// Noms de mois reconnus dans les anciennes saisies (forme `fold_name` : minuscules, sans accents).
+const MONTH_NAMES: [[&str; 2]; 12] = [
+    ["janvier", "janoary"],
+    ["fevrier", "febroary"],
+    ["mars", "martsa"],
+    ["avril", "aprily"],
+    ["mai", "mey"],
+    ["juin", "jona"],
+    ["juillet", "jolay"],
+    ["aout", "aogositra"],
+    ["septembre", "septambra"],
+    ["octobre", "oktobra"],
+    ["novembre", "novambra"],
+    ["decembre", "desambra"],
+];
+
+fn parse_year(s: &str) -> Option<i32> {
+    (s.len() == 4 && s.bytes().all(|b| b.is_ascii_digit())).then(|| s.parse().ok()).flatten()
+}
+
+/// Nombre de 1 ou 2 chiffres compris dans `1..=max`.
+fn parse_small(s: &str, max: u32) -> Option<u32> {
+    if !(1..=2).contains(&s.len()) || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok().filter(|n| (1..=max).contains(n))
+}
+
+/// Saisie d'une période : "2025", "2025-03" (ou "2025-3") et "2025-T1".."2025-T4"
+/// (T minuscule admis). Tout le reste est refusé.
+pub fn parse_period(raw: &str) -> Option<Period> {
+    let raw = raw.trim();
+    let (year, rest) = match raw.split_once('-') {
+        Some((y, r)) => (y, Some(r)),
+        None         => (raw, None),
+    };
+    let year = parse_year(year)?;
+    let part = match rest {
+        None => PeriodPart::Year,
+        Some(r) => match r.strip_prefix(['T', 't']) {
+            Some(q) => PeriodPart::Quarter(parse_small(q, 4)?),
+            None    => PeriodPart::Month(parse_small(r, 12)?),
+        },
+    };
+    Some(Period { year, part })
+}
+
+/// Lecture tolérante des périodes saisies avant la validation : en plus de
+/// `parse_period`, "Janvier 2025", "Martsa 2025", "03/2025", "T1 2025" ou
+/// "1er trimestre 2025". `None` si la valeur reste ambiguë.
+pub fn parse_legacy_period(raw: &str) -> Option<Period> {
+    if let Some(period) = parse_period(raw) {
+        return Some(period);
+    }
+    let folded = fold_name(raw);
+    let (years, rest): (Vec<&str>, Vec<&str>) =
+        folded.split_whitespace().partition(|w| parse_year(w).is_some());
+    let [year] = years.as_slice() else {
+        return None;
+    };
+    let year = parse_year(year)?;
+
+    let month_name = |w: &str| {
+        MONTH_NAMES.iter().position(|names| names.contains(&w)).map(|i| i as u32 + 1)
+    };
+    let ordinal = |w: &str| {
+        let digits = w.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let suffix = &w[digits.len()..];
+        matches!(suffix, "" | "er" | "ere" | "e" | "eme" | "nd" | "nde")
+            .then(|| parse_small(digits, 4))
+            .flatten()
+            .or_else(|| (w == "premier").then_some(1))
+    };
+    let part = match rest.as_slice() {
+        [] => PeriodPart::Year,
+        [w] => match w.strip_prefix('t').and_then(|q| parse_small(q, 4)) {
+            Some(q) => PeriodPart::Quarter(q),
+            None    => PeriodPart::Month(month_name(w).or_else(|| parse_small(w, 12))?),
+        },
+        // Mots triés par `fold_name` : l'ordinal précède "trimestre"
+        [n, "trimestre"] => PeriodPart::Quarter(ordinal(n)?),
+        _ => return None,
+    };
+    Some(Period { year, part })
+}
+
+// ─── Nom de famille / prénom(s) ───────────────────────────────────────────────
+
+/// Mot en capitales : au moins une majuscule ASCII, aucune minuscule.
+fn is_caps(word: &str) -> bool {
+    word.bytes().any(|b| b.is_ascii_uppercase()) && !word.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+fn has_lowercase(s: &str) -> bool {
+    s.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+/// (nom de famille, prénom(s)) devinés depuis le nom complet, même règle que la
+/// migration 0020 : premier mot en capitales ("RAKOTO Jean"), sinon dernier
+/// ("Jean RAKOTO"), pourvu que le reste contienne une minuscule ; un mot unique
+/// en capitales est un nom de famille. Rien de deviné sinon.
+pub fn split_full_name(full_name: &str) -> (Option<String>, Option<String>) {
+    let t = full_name.trim();
+    let Some((first, rest)) = t.split_once(' ') else {
+        return if is_caps(t) { (Some(t.to_string()), None) } else { (None, None) };
+    };
+    let rest = rest.trim();
+    if is_caps(first) && has_lowercase(rest) {
+        return (Some(first.to_string()), Some(rest.to_string()));
+    }
+    let (head, last) = t.rsplit_once(' ').unwrap_or((t, ""));
+    let head = head.trim();
+    if is_caps(last) && has_lowercase(head) {
+        return (Some(last.to_string()), Some(head.to_string()));
+    }
+    (None, None)
+}
+
+/// Nom affiché composé des parties : "RAKOTO Jean", ou la seule partie remplie.
+pub fn compose_full_name(surname: Option<&str>, given_name: Option<&str>) -> String {
+    [surname, given_name]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ─── Recherche par montant ────────────────────────────────────────────────────
+
+/// Chiffres de la partie entière d'un montant recherché : "50 000", "50.000",
+/// "50,000" et "50000" donnent "50000" ; les décimales ("5000,50") sont ignorées,
+/// tout comme un suffixe "Ar". `None` si la saisie n'est pas un montant.
+pub fn amount_search_digits(query: &str) -> Option<String> {
+    let compact: String = query
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{202f}')
+        .collect();
+    let compact = compact
+        .strip_suffix("Ar")
+        .or_else(|| compact.strip_suffix("ar"))
+        .unwrap_or(&compact);
+    if compact.is_empty() || !compact.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return None;
+    }
+    // Dernier séparateur suivi d'un ou deux chiffres : décimales
+    let int_part = match compact.rfind(['.', ',']) {
+        Some(i) if (1..=2).contains(&(compact.len() - i - 1)) => &compact[..i],
+        _ => compact,
+    };
+    let digits: String = int_part.chars().filter(char::is_ascii_digit).collect();
+    (!digits.is_empty()).then_some(digits)
+}
+
+// ─── Adresses ─────────────────────────────────────────────────────────────────
+
+/// Clé de regroupement d'une adresse : minuscules, espaces en trop retirés, de
+/// sorte que "Tanambao  II" et " tanambao ii" tombent dans le même quartier.
+/// `None` pour une adresse absente ou blanche.
+pub fn address_key(address: Option<&str>) -> Option<String> {
+    let key = address?.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    (!key.is_empty()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_full_name() {
+        let split = |n: &str| {
+            let (s, g) = split_full_name(n);
+            (s.unwrap_or_default(), g.unwrap_or_default())
+        };
+        assert_eq!(split("RAKOTOARISOA Hery"), ("RAKOTOARISOA".into(), "Hery".into()));
+        assert_eq!(split(" Jean  RAKOTO "), ("RAKOTO".into(), "Jean".into()));
+        assert_eq!(split("RAKOTO ANDRIA Jean"), ("RAKOTO".into(), "ANDRIA Jean".into()));
+        assert_eq!(split("Marie Hanitra RASOA"), ("RASOA".into(), "Marie Hanitra".into()));
+        assert_eq!(split("RABE"), ("RABE".into(), String::new()));
+        // Rien à deviner : tout en minuscules, tout en capitales, un seul prénom
+        for n in ["Jean Rakoto", "RAKOTO JEAN", "Hery", "", "O'NEIL 2"] {
+            assert_eq!(split_full_name(n), (None, None), "entrée : {n:?}");
+        }
+        assert_eq!(compose_full_name(Some(" RAKOTO "), Some("Jean")), "RAKOTO Jean");
+        assert_eq!(compose_full_name(None, Some("Jean")), "Jean");
+        assert_eq!(compose_full_name(Some(""), None), "");
+    }
+
+    #[test]
+    fn test_address_key() {
+        assert_eq!(address_key(Some(" Tanambao  II ")).as_deref(), Some("tanambao ii"));
+        assert_eq!(address_key(Some("TANAMBAO II")), address_key(Some("tanambao ii")));
+        assert_eq!(address_key(Some("   ")), None);
+        assert_eq!(address_key(None), None);
+    }
+
+    #[test]
+    fn test_amount_search_digits() {
+        for raw in ["50000", "50 000", "50.000", "50,000", "50\u{202f}000 Ar", "50000,00"] {
+            assert_eq!(amount_search_digits(raw).as_deref(), Some("50000"), "entrée : {raw:?}");
+        }
+        assert_eq!(amount_search_digits("5000.5").as_deref(), Some("5000"));
+        assert_eq!(amount_search_digits("1.234.567").as_deref(), Some("1234567"));
+        for raw in ["", "  ", "Rakoto", "2024-T1", "Ar", "-500"] {
+            assert_eq!(amount_search_digits(raw), None, "entrée : {raw:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_phone_valides() {
+        let cases = [
+            ("0341234567",          "+261 34 12 345 67"),
+            ("034 12 345 67",       "+261 34 12 345 67"),
+            (" 032-12-345-67 ",     "+261 32 12 345 67"),
+            ("033.12.345.67",       "+261 33 12 345 67"),
+            ("+261341234567",       "+261 34 12 345 67"),
+            ("+261 34 12 345 67",   "+261 34 12 345 67"),
+            ("+261 034 12 345 67",  "+261 34 12 345 67"),
+            ("261381234567",        "+261 38 12 345 67"),
+            ("00261341234567",      "+261 34 12 345 67"),
+            ("341234567",           "+261 34 12 345 67"),
+            ("020 22 123 45",       "+261 20 22 123 45"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(normalize_phone(raw).unwrap(), expected, "entrée : {raw:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_phone_invalides() {
+        let cases = [
+            "",
+            "   ",
+            "+",
+            "034 12 345 6",
+            "034 12 345 678",
+            "03412345a7",
+            "tel: 0341234567",
+            "+33 6 12 34 56 78",
+            "+2613412345",
+            "0041234567",
+            "0541234567",
+            "12345",
+            "++261341234567",
+            "034/12/345/67",
+        ];
+        for raw in cases {
+            assert!(
+                matches!(normalize_phone(raw), Err(AppError::Validation(_))),
+                "entrée acceptée à tort : {raw:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_optional_phone() {
+        assert_eq!(normalize_optional_phone(None).unwrap(), None);
+        assert_eq!(normalize_optional_phone(Some("")).unwrap(), None);
+        assert_eq!(normalize_optional_phone(Some("  ")).unwrap(), None);
+        assert_eq!(normalize_optional_phone(Some("+261 ")).unwrap(), None);
+        assert_eq!(
+            normalize_optional_phone(Some("0341234567")).unwrap().as_deref(),
+            Some("+261 34 12 345 67")
+        );
+        assert!(normalize_optional_phone(Some("abc")).is_err());
+    }
+
+    #[test]
+    fn test_parse_period_formes_canoniques() {
+        let cases = [
+            ("2025",      "2025"),
+            (" 2025 ",    "2025"),
+            ("2025-03",   "2025-03"),
+            ("2025-3",    "2025-03"),
+            ("2025-12",   "2025-12"),
+            ("2025-T1",   "2025-T1"),
+            ("2025-t4",   "2025-T4"),
+        ];
+        for (raw, expected) in cases {
+            let period = parse_period(raw).unwrap_or_else(|| panic!("refusé : {raw:?}"));
+            assert_eq!(period.to_string(), expected, "entrée : {raw:?}");
+            assert_eq!(period.year, 2025);
+        }
+    }
+
+    #[test]
+    fn test_parse_period_refus() {
+        let cases = [
+            "", "25", "20255", "2025-", "2025-0", "2025-13", "2025-123", "2025-T0",
+            "2025-T5", "2025-Q1", "Janvier 2025", "1er trimestre", "2025/03", "2025-03-01",
+        ];
+        for raw in cases {
+            assert_eq!(parse_period(raw), None, "entrée acceptée à tort : {raw:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_period() {
+        let cases = [
+            ("2025-t2",              "2025-T2"),
+            ("Janvier 2025",         "2025-01"),
+            ("février 2025",         "2025-02"),
+            ("AOÛT 2025",            "2025-08"),
+            ("Martsa 2025",          "2025-03"),
+            ("03/2025",              "2025-03"),
+            ("T3 2025",              "2025-T3"),
+            ("1er trimestre 2025",   "2025-T1"),
+            ("4ème trimestre 2025",  "2025-T4"),
+            ("Premier trimestre 2025", "2025-T1"),
+            (" 2025 ",               "2025"),
+        ];
+        for (raw, expected) in cases {
+            let period = parse_legacy_period(raw).unwrap_or_else(|| panic!("illisible : {raw:?}"));
+            assert_eq!(period.to_string(), expected, "entrée : {raw:?}");
+        }
+        for raw in ["1er trimestre", "2024-2025", "Noël 2025", "13/2025", "adidy"] {
+            assert_eq!(parse_legacy_period(raw), None, "entrée acceptée à tort : {raw:?}");
+        }
+    }
+}
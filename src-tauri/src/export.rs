@@ -1,7 +1,14 @@
-/// Fonctions partagées d'export/import CSV et Excel.
+/// Fonctions partagées d'export/import CSV et Excel, rapport annuel HTML et PDF, reçus.
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+use rust_decimal::Decimal;
 use rust_xlsxwriter::{Color, Format, Workbook};
 
-use crate::db::{MemberInput, MemberWithTotal};
+use crate::db::{
+    format_ariary, AppError, ContributionWithMember, MemberInput, MemberStatement, MemberStatus,
+    MemberType, MemberWithTotal, Settings, YearMemberTotal, YearReport,
+};
+use crate::pdf::{fit_text, wrap_text, PdfDocument, PdfFont, PAGE_HEIGHT, PAGE_WIDTH};
 
 // ── CSV ───────────────────────────────────────────────────────────────────────
 
@@ -61,7 +68,7 @@ pub fn build_csv_from_members(members: &[crate::db::Member]) -> String {
         out.push(',');
         out.push_str(&csv_escape(m.job.as_deref().unwrap_or("")));
         out.push(',');
-        out.push_str(&csv_escape(&m.gender));
+        out.push_str(m.gender.as_str());
         out.push('\n');
     }
     out
@@ -97,51 +104,546 @@ pub fn build_excel_bytes(members: &[MemberWithTotal], sheet_name: &str) -> Resul
         worksheet.write(r, 3, m.phone.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
         worksheet.write(r, 4, m.job.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
         worksheet.write(r, 5, m.gender.as_str()).map_err(|e| e.to_string())?;
-        worksheet.write(r, 6, m.total_contributions.as_str()).map_err(|e| e.to_string())?;
+        worksheet.write(r, 6, m.total_contributions.to_string()).map_err(|e| e.to_string())?;
     }
 
     worksheet.autofit();
     workbook.save_to_buffer().map_err(|e| e.to_string())
 }
 
-/// Parse le CSV importé en liste de MemberInput.
-pub fn parse_csv_to_members(csv_content: &str, member_type: &str) -> Vec<MemberInput> {
-    let mut inputs = Vec::new();
-    let mut lines = csv_content.lines();
+/// CSV brut des cotisations d'une année pour le comptable : BOM UTF-8 et fins de
+/// ligne CRLF (ouverture directe dans Excel), montants en nombre simple ("2500.50"),
+/// ligne TOTAL finale.
+pub fn build_contributions_csv(report: &YearReport) -> String {
+    let members: HashMap<i64, &YearMemberTotal> =
+        report.members.iter().map(|m| (m.member_id, m)).collect();
 
-    if let Some(header) = lines.next() {
-        let h = header.trim().to_lowercase();
-        if !h.contains("carte") && !h.contains("nom") {
-            let fields = parse_csv_line(header);
-            if fields.len() >= 6 {
-                inputs.push(make_member_input(&fields, member_type));
-            }
-        }
+    let mut out = String::from("\u{FEFF}");
+    out.push_str("date,card_number,member_name,member_type,period,amount\r\n");
+    let mut total = Decimal::ZERO;
+    for c in &report.contributions {
+        let member = members.get(&c.member_id);
+        total += c.amount;
+        out.push_str(&csv_escape(&c.payment_date));
+        out.push(',');
+        out.push_str(&csv_escape(member.map(|m| m.card_number.as_str()).unwrap_or("")));
+        out.push(',');
+        out.push_str(&csv_escape(&c.member_name));
+        out.push(',');
+        out.push_str(member.map(|m| m.member_type.as_str()).unwrap_or(""));
+        out.push(',');
+        out.push_str(&csv_escape(&c.period));
+        out.push(',');
+        out.push_str(&c.amount.to_string());
+        out.push_str("\r\n");
+    }
+    out.push_str(&format!("TOTAL,,,,,{total}\r\n"));
+    out
+}
+
+/// CSV du relevé annuel d'un membre : bloc d'identification (carte, nom, type,
+/// année), ligne vide, puis ses cotisations et la ligne TOTAL. Même format que
+/// `build_contributions_csv` (BOM, CRLF, montants bruts).
+pub fn build_member_statement_csv(s: &MemberStatement) -> String {
+    let mut out = String::from("\u{FEFF}");
+    out.push_str("card_number,member_name,member_type,year\r\n");
+    out.push_str(&format!(
+        "{},{},{},{}\r\n\r\n",
+        csv_escape(&s.member.card_number),
+        csv_escape(&s.member.full_name),
+        s.member.member_type.as_str(),
+        s.year,
+    ));
+    out.push_str("date,receipt_number,period,amount\r\n");
+    for c in &s.contributions {
+        out.push_str(&format!(
+            "{},{},{},{}\r\n",
+            csv_escape(&c.payment_date),
+            csv_escape(&c.receipt_number),
+            csv_escape(&c.period),
+            c.amount,
+        ));
+    }
+    out.push_str(&format!("TOTAL,,,{}\r\n", s.total));
+    out
+}
+
+/// CSV générique (en-têtes + lignes déjà formatées par l'appelant), même format
+/// que les autres exports : BOM UTF-8, CRLF, champs échappés.
+pub fn build_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = String::from("\u{FEFF}");
+    for line in std::iter::once(headers).chain(rows.iter().map(Vec::as_slice)) {
+        let fields: Vec<String> = line.iter().map(|f| csv_escape(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Écrit `build_csv` dans `dest` : extension `.csv` exigée, dossiers parents créés,
+/// fichier existant conservé sauf si `overwrite`.
+pub fn write_csv_file(
+    dest: &Path,
+    headers: &[String],
+    rows: &[Vec<String>],
+    overwrite: bool,
+) -> Result<(), AppError> {
+    let shown = dest.display().to_string();
+    let is_csv = dest
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+    if !is_csv {
+        return Err(AppError::validation("export.csv_extension", &[&shown]));
     }
+    if dest.exists() && !overwrite {
+        return Err(AppError::validation("export.file_exists", &[&shown]));
+    }
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Internal(e.to_string()))?;
+    }
+    std::fs::write(dest, build_csv(headers, rows)).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Ligne de données du CSV importé : numéro de ligne dans le fichier (1 = première
+/// ligne) et membre lu, ou la raison pour laquelle la ligne est inexploitable.
+#[derive(Debug)]
+pub struct CsvMemberRow {
+    pub line:  usize,
+    pub input: Result<MemberInput, String>,
+}
+
+/// Parse le CSV importé. Colonnes : numéro de carte, nom complet, adresse, téléphone,
+/// travail, genre, puis type de membre facultatif (`member_type` si absent ou vide).
+/// La première ligne est un en-tête si elle mentionne la carte ou le nom ; les lignes
+/// vides sont ignorées.
+pub fn parse_member_csv(csv_content: &str, member_type: MemberType) -> Vec<CsvMemberRow> {
+    let csv_content = csv_content.trim_start_matches('\u{FEFF}');
+    let mut rows = Vec::new();
 
-    for line in lines {
+    for (i, line) in csv_content.lines().enumerate() {
         let line = line.trim();
         if line.is_empty() {
             continue;
         }
-        let fields = parse_csv_line(line);
-        if fields.len() < 6 {
-            continue;
+        if i == 0 {
+            let h = line.to_lowercase();
+            if ["carte", "nom", "card", "name"].iter().any(|k| h.contains(k)) {
+                continue;
+            }
         }
-        inputs.push(make_member_input(&fields, member_type));
+        let fields = parse_csv_line(line);
+        rows.push(CsvMemberRow { line: i + 1, input: make_member_input(&fields, member_type) });
     }
 
-    inputs
+    rows
 }
 
-fn make_member_input(fields: &[String], member_type: &str) -> MemberInput {
-    MemberInput {
+fn make_member_input(fields: &[String], member_type: MemberType) -> Result<MemberInput, String> {
+    if fields.len() < 6 {
+        return Err(format!("Colonnes insuffisantes : {} au lieu de 6 au moins.", fields.len()));
+    }
+    let optional = |f: &String| if f.is_empty() { None } else { Some(f.clone()) };
+    let member_type = match fields.get(6).filter(|t| !t.is_empty()) {
+        Some(t) => t.parse().map_err(|e: AppError| e.to_string())?,
+        None => member_type,
+    };
+    Ok(MemberInput {
         card_number: fields[0].clone(),
         full_name:   fields[1].clone(),
-        address:     if fields[2].is_empty() { None } else { Some(fields[2].clone()) },
-        phone:       if fields[3].is_empty() { None } else { Some(fields[3].clone()) },
-        job:         if fields[4].is_empty() { None } else { Some(fields[4].clone()) },
-        gender:      fields[5].clone(),
-        member_type: member_type.to_string(),
+        address:     optional(&fields[2]),
+        phone:       optional(&fields[3]),
+        job:         optional(&fields[4]),
+        gender:      fields[5].to_uppercase().parse().map_err(|e: AppError| e.to_string())?,
+        member_type,
+        surname:     None,
+        given_name:  None,
+        status:      MemberStatus::Actif,
+        birth_date:  None,
+    })
+}
+
+// ── Rapport annuel HTML ───────────────────────────────────────────────────────
+
+/// Styles embarqués : pas de Tailwind dans la fenêtre d'impression, lisible en noir et blanc.
+const REPORT_CSS: &str = "\
+body{font-family:Georgia,'Times New Roman',serif;color:#000;background:#fff;margin:2rem;font-size:11pt}\
+h1{font-size:18pt;margin:0}\
+h2{font-size:13pt;margin:1.5rem 0 .5rem;border-bottom:1px solid #000;padding-bottom:.2rem}\
+.sub{margin:.2rem 0 0;color:#333}\
+table{width:100%;border-collapse:collapse;margin-top:.3rem}\
+th,td{border:1px solid #555;padding:.25rem .4rem;text-align:left;vertical-align:top}\
+th{background:#eee}\
+td.num,th.num{text-align:right;font-variant-numeric:tabular-nums;white-space:nowrap}\
+tfoot td{font-weight:bold}\
+dl{display:grid;grid-template-columns:max-content 1fr;gap:.2rem 1rem;margin:0}\
+dt{font-weight:bold}dd{margin:0}\
+tr{page-break-inside:avoid}\
+@media print{body{margin:0}thead{display:table-header-group}}";
+
+/// Échappe le texte inséré dans le HTML (noms, notes, périodes).
+pub fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&'  => out.push_str("&amp;"),
+            '<'  => out.push_str("&lt;"),
+            '>'  => out.push_str("&gt;"),
+            '"'  => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _    => out.push(c),
+        }
+    }
+    out
+}
+
+/// Construit le rapport d'archive d'une année : document HTML autonome (CSS inline)
+/// prêt à imprimer ou à enregistrer en PDF depuis la fenêtre d'impression.
+pub fn build_year_report_html(report: &YearReport, church_name: &str) -> String {
+    let s = &report.summary;
+    let year = s.year;
+    let church = html_escape(church_name);
+    let status = match s.closed_at.as_deref() {
+        Some(dt) => format!("Voakatona ny {}", html_escape(dt.get(..10).unwrap_or(dt))),
+        None     => "Mbola misokatra".to_string(),
+    };
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html><html lang=\"mg\"><head><meta charset=\"utf-8\">\
+         <title>{church} — Tahiry {year}</title><style>{REPORT_CSS}</style></head><body>\
+         <h1>{church}</h1><p class=\"sub\">Tahirin'ny rakitra — taona {year}</p>"
+    );
+
+    // ── Résumé ──
+    let _ = write!(
+        out,
+        "<h2>Famintinana</h2><dl>\
+         <dt>Fitambarana</dt><dd>{}</dd>\
+         <dt>Toe-javatra</dt><dd>{status}</dd>\
+         <dt>Mpikambana nandoa</dt><dd>{}</dd>\
+         <dt>Fandoavana</dt><dd>{}</dd>",
+        format_ariary(&s.total),
+        report.members.len(),
+        report.contributions.len(),
+    );
+    if let Some(note) = s.note.as_deref().filter(|n| !n.is_empty()) {
+        let _ = write!(out, "<dt>Fanamarihana</dt><dd>{}</dd>", html_escape(note));
     }
+    out.push_str("</dl>");
+
+    // ── Totaux par membre ──
+    out.push_str(
+        "<h2>Isaky ny mpikambana</h2><table><thead><tr>\
+         <th>Karatra</th><th>Anarana</th><th>Karazana</th>\
+         <th class=\"num\">Isa</th><th class=\"num\">Fitambarana</th>\
+         </tr></thead><tbody>",
+    );
+    for m in &report.members {
+        let _ = write!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            html_escape(&m.card_number),
+            html_escape(&m.full_name),
+            m.member_type.as_str(),
+            m.count,
+            format_ariary(&m.total),
+        );
+    }
+    let _ = write!(
+        out,
+        "</tbody><tfoot><tr><td colspan=\"4\">Fitambarana</td><td class=\"num\">{}</td></tr></tfoot></table>",
+        format_ariary(&s.total),
+    );
+
+    // ── Liste complète des cotisations ──
+    out.push_str(
+        "<h2>Fandoavana rehetra</h2><table><thead><tr>\
+         <th>Daty</th><th>Anarana</th><th>Vanim-potoana</th><th class=\"num\">Vola</th>\
+         </tr></thead><tbody>",
+    );
+    for c in &report.contributions {
+        let _ = write!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+            html_escape(&c.payment_date),
+            html_escape(&c.member_name),
+            html_escape(&c.period),
+            format_ariary(&c.amount),
+        );
+    }
+    out.push_str("</tbody></table></body></html>");
+    out
+}
+
+// ── Reçu de cotisation ────────────────────────────────────────────────────────
+
+/// Reçu imprimable d'une cotisation, même style que le rapport annuel : numéro de
+/// reçu, membre, date, période et montant, avec une ligne de signature au nom du
+/// trésorier (`settings.treasurer_name`) s'il est renseigné.
+pub fn build_receipt_html(c: &ContributionWithMember, settings: &Settings) -> String {
+    let church = html_escape(&settings.church_name);
+    let receipt = html_escape(&c.receipt_number);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html><html lang=\"mg\"><head><meta charset=\"utf-8\">\
+         <title>{church} — Rosia {receipt}</title><style>{REPORT_CSS}\
+         body{{max-width:32rem}}.sign{{margin-top:3rem;text-align:right}}</style></head><body>\
+         <h1>{church}</h1>"
+    );
+    if !settings.church_address.trim().is_empty() {
+        let _ = write!(out, "<p class=\"sub\">{}</p>", html_escape(&settings.church_address));
+    }
+    let treasurer = match settings.treasurer_name.trim() {
+        "" => String::new(),
+        name => format!("<br>{}", html_escape(name)),
+    };
+    let _ = write!(
+        out,
+        "<h2>Rosia n° {receipt}</h2><dl>\
+         <dt>Anarana</dt><dd>{}</dd>\
+         <dt>Daty</dt><dd>{}</dd>\
+         <dt>Vanim-potoana</dt><dd>{}</dd>\
+         <dt>Vola ({})</dt><dd>{}</dd>\
+         </dl><p class=\"sign\">Ny mpitahiry vola{treasurer}</p></body></html>",
+        html_escape(&c.member_name),
+        html_escape(&c.payment_date),
+        html_escape(&c.period),
+        html_escape(&settings.currency_label),
+        format_ariary(&c.amount),
+    );
+    out
+}
+
+// ── Rapport annuel PDF ────────────────────────────────────────────────────────
+
+const PDF_MARGIN: f32 = 40.0;
+const PDF_ROW_H: f32 = 14.0;
+const PDF_BODY_SIZE: f32 = 9.0;
+
+/// Colonne d'un tableau du rapport PDF.
+struct PdfColumn {
+    title: &'static str,
+    width: f32,
+    /// Aligné à droite (nombres, montants)
+    right: bool,
+}
+
+const PDF_MEMBER_COLUMNS: [PdfColumn; 5] = [
+    PdfColumn { title: "Karatra",     width: 70.0,  right: false },
+    PdfColumn { title: "Anarana",     width: 205.0, right: false },
+    PdfColumn { title: "Karazana",    width: 90.0,  right: false },
+    PdfColumn { title: "Isa",         width: 40.0,  right: true },
+    PdfColumn { title: "Fitambarana", width: 110.0, right: true },
+];
+
+const PDF_CONTRIBUTION_COLUMNS: [PdfColumn; 4] = [
+    PdfColumn { title: "Daty",          width: 70.0,  right: false },
+    PdfColumn { title: "Anarana",       width: 235.0, right: false },
+    PdfColumn { title: "Vanim-potoana", width: 90.0,  right: false },
+    PdfColumn { title: "Vola",          width: 120.0, right: true },
+];
+
+/// Curseur de mise en page : page courante et position verticale.
+struct PdfLayout {
+    doc:     PdfDocument,
+    page:    usize,
+    y:       f32,
+    /// Rappel en haut des pages suivantes : "Église — Tahiry 2024"
+    running: String,
+}
+
+impl PdfLayout {
+    fn new(running: String) -> Self {
+        let mut doc = PdfDocument::new();
+        let page = doc.add_page();
+        Self { doc, page, y: PAGE_HEIGHT - PDF_MARGIN, running }
+    }
+
+    fn new_page(&mut self) {
+        self.page = self.doc.add_page();
+        let top = PAGE_HEIGHT - PDF_MARGIN;
+        self.doc.text(self.page, PDF_MARGIN, top - 8.0, 8.0, PdfFont::Regular, &self.running);
+        self.doc.line(self.page, PDF_MARGIN, top - 12.0, PAGE_WIDTH - PDF_MARGIN, top - 12.0, 0.5);
+        self.y = top - 24.0;
+    }
+
+    /// Passe à la page suivante si `height` ne tient plus ; vrai si saut de page.
+    fn ensure(&mut self, height: f32) -> bool {
+        // Réserve le bas de page pour la numérotation
+        if self.y - height < PDF_MARGIN + 16.0 {
+            self.new_page();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn heading(&mut self, title: &str) {
+        self.ensure(28.0 + 2.0 * PDF_ROW_H);
+        self.y -= 16.0;
+        self.doc.text(self.page, PDF_MARGIN, self.y, 12.0, PdfFont::Bold, title);
+        self.y -= 8.0;
+    }
+
+    fn row(&mut self, columns: &[PdfColumn], cells: &[String], font: PdfFont) {
+        self.y -= PDF_ROW_H;
+        let mut x = PDF_MARGIN;
+        for (col, cell) in columns.iter().zip(cells) {
+            let text = fit_text(cell, col.width - 6.0, PDF_BODY_SIZE, font);
+            let baseline = self.y + 4.0;
+            if col.right {
+                self.doc.text_right(self.page, x + col.width - 3.0, baseline, PDF_BODY_SIZE, font, &text);
+            } else {
+                self.doc.text(self.page, x + 3.0, baseline, PDF_BODY_SIZE, font, &text);
+            }
+            x += col.width;
+        }
+    }
+
+    fn table_header(&mut self, columns: &[PdfColumn]) {
+        let width: f32 = columns.iter().map(|c| c.width).sum();
+        self.doc.fill_rect(self.page, PDF_MARGIN, self.y - PDF_ROW_H, width, PDF_ROW_H, 0.88);
+        let titles: Vec<String> = columns.iter().map(|c| c.title.to_string()).collect();
+        self.row(columns, &titles, PdfFont::Bold);
+    }
+
+    /// Tableau paginé : l'en-tête est répété en haut de chaque nouvelle page.
+    fn table(&mut self, columns: &[PdfColumn], rows: Vec<Vec<String>>, footer: Option<Vec<String>>) {
+        self.table_header(columns);
+        let width: f32 = columns.iter().map(|c| c.width).sum();
+        for cells in rows {
+            if self.ensure(PDF_ROW_H) {
+                self.table_header(columns);
+            }
+            self.row(columns, &cells, PdfFont::Regular);
+            self.doc.line(self.page, PDF_MARGIN, self.y, PDF_MARGIN + width, self.y, 0.2);
+        }
+        if let Some(cells) = footer {
+            if self.ensure(PDF_ROW_H) {
+                self.table_header(columns);
+            }
+            self.doc.line(self.page, PDF_MARGIN, self.y, PDF_MARGIN + width, self.y, 0.8);
+            self.row(columns, &cells, PdfFont::Bold);
+        }
+    }
+
+    /// Numérotation "Pejy n / total" en bas de chaque page.
+    fn finish(mut self) -> Vec<u8> {
+        let total = self.doc.page_count();
+        for page in 0..total {
+            self.doc.text_right(
+                page,
+                PAGE_WIDTH - PDF_MARGIN,
+                PDF_MARGIN - 12.0,
+                8.0,
+                PdfFont::Regular,
+                &format!("Pejy {} / {total}", page + 1),
+            );
+        }
+        self.doc.to_bytes()
+    }
+}
+
+/// Rapport d'archive d'une année en PDF, à signer après la clôture : en-tête
+/// (église, date de clôture), résumé et note, totaux par membre, liste complète
+/// des cotisations, emplacement des signatures. Les tableaux longs sont répartis
+/// sur plusieurs pages.
+pub fn build_year_report_pdf(report: &YearReport, church_name: &str) -> Vec<u8> {
+    let s = &report.summary;
+    let year = s.year;
+    let mut layout = PdfLayout::new(format!("{church_name} — Tahiry {year}"));
+    let text_width_max = PAGE_WIDTH - 2.0 * PDF_MARGIN;
+
+    // ── En-tête ──
+    layout.y -= 16.0;
+    layout.doc.text(layout.page, PDF_MARGIN, layout.y, 16.0, PdfFont::Bold, church_name);
+    layout.y -= 16.0;
+    layout.doc.text(
+        layout.page,
+        PDF_MARGIN,
+        layout.y,
+        11.0,
+        PdfFont::Regular,
+        &format!("Tahirin'ny rakitra — taona {year}"),
+    );
+    let status = match s.closed_at.as_deref() {
+        Some(dt) => format!("Voakatona ny {}", dt.get(..10).unwrap_or(dt)),
+        None     => "Mbola misokatra".to_string(),
+    };
+    layout.y -= 14.0;
+    layout.doc.text(layout.page, PDF_MARGIN, layout.y, 9.0, PdfFont::Regular, &status);
+    layout.y -= 8.0;
+    layout.doc.line(layout.page, PDF_MARGIN, layout.y, PAGE_WIDTH - PDF_MARGIN, layout.y, 0.8);
+
+    // ── Résumé ──
+    layout.heading("Famintinana");
+    let summary = [
+        ("Fitambarana", format_ariary(&s.total)),
+        ("Mpikambana nandoa", report.members.len().to_string()),
+        ("Fandoavana", report.contributions.len().to_string()),
+    ];
+    for (label, value) in summary {
+        layout.y -= PDF_ROW_H;
+        layout.doc.text(layout.page, PDF_MARGIN, layout.y, 10.0, PdfFont::Bold, label);
+        layout.doc.text(layout.page, PDF_MARGIN + 130.0, layout.y, 10.0, PdfFont::Regular, &value);
+    }
+    if let Some(note) = s.note.as_deref().filter(|n| !n.trim().is_empty()) {
+        layout.y -= PDF_ROW_H;
+        layout.doc.text(layout.page, PDF_MARGIN, layout.y, 10.0, PdfFont::Bold, "Fanamarihana");
+        for line in wrap_text(note, text_width_max - 130.0, 10.0, PdfFont::Regular) {
+            layout.doc.text(layout.page, PDF_MARGIN + 130.0, layout.y, 10.0, PdfFont::Regular, &line);
+            layout.y -= 12.0;
+            layout.ensure(12.0);
+        }
+    }
+
+    // ── Totaux par membre ──
+    layout.heading("Isaky ny mpikambana");
+    let members = report
+        .members
+        .iter()
+        .map(|m| vec![
+            m.card_number.clone(),
+            m.full_name.clone(),
+            m.member_type.as_str().to_string(),
+            m.count.to_string(),
+            format_ariary(&m.total),
+        ])
+        .collect();
+    let footer = vec![
+        "Fitambarana".to_string(),
+        String::new(),
+        String::new(),
+        report.contributions.len().to_string(),
+        format_ariary(&s.total),
+    ];
+    layout.table(&PDF_MEMBER_COLUMNS, members, Some(footer));
+
+    // ── Liste complète des cotisations ──
+    layout.heading("Fandoavana rehetra");
+    let contributions = report
+        .contributions
+        .iter()
+        .map(|c| vec![
+            c.payment_date.clone(),
+            c.member_name.clone(),
+            c.period.clone(),
+            format_ariary(&c.amount),
+        ])
+        .collect();
+    layout.table(&PDF_CONTRIBUTION_COLUMNS, contributions, None);
+
+    // ── Signatures ──
+    layout.ensure(80.0);
+    layout.y -= 50.0;
+    let half = (PAGE_WIDTH - 2.0 * PDF_MARGIN) / 2.0;
+    for (i, label) in ["Ny Mpitahiry vola", "Ny Mpitandrina"].into_iter().enumerate() {
+        let x = PDF_MARGIN + i as f32 * half;
+        layout.doc.line(layout.page, x, layout.y, x + half - 30.0, layout.y, 0.5);
+        layout.doc.text(layout.page, x, layout.y - 12.0, 9.0, PdfFont::Regular, label);
+    }
+
+    layout.finish()
 }
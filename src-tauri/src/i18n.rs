@@ -0,0 +1,381 @@
+//! Traduction des messages d'erreur renvoyés au frontend (français / malgache).
+//!
+//! Les erreurs de validation sont construites à partir d'une clé et de paramètres
+//! (`Message`), puis traduites au moment de la sérialisation dans la langue
+//! choisie dans les paramètres (`current`). Une clé absente de `MESSAGES` est
+//! affichée telle quelle : les messages encore rédigés en clair restent lisibles.
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Langue de l'interface — stockée dans `app_settings` (clé `language`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    Mg,
+    Fr,
+}
+
+impl Lang {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Lang::Mg => "mg",
+            Lang::Fr => "fr",
+        }
+    }
+
+    /// Valeur stockée → langue ; inconnue ou absente → malgache.
+    pub fn parse(s: &str) -> Lang {
+        match s.trim() {
+            "fr" => Lang::Fr,
+            _    => Lang::Mg,
+        }
+    }
+}
+
+/// Langue des messages sérialisés. Globale au processus : les erreurs sont
+/// sérialisées par Tauri (ou Axum) après le retour des commandes.
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn current() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::Fr,
+        _ => Lang::Mg,
+    }
+}
+
+pub fn set_current(lang: Lang) {
+    CURRENT.store(matches!(lang, Lang::Fr) as u8, Ordering::Relaxed);
+}
+
+// ─── Message ───────────────────────────────────────────────────────────────────
+
+/// Message d'erreur non traduit : clé de `MESSAGES` et valeurs de `{0}`, `{1}`…
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub key:    Cow<'static, str>,
+    pub params: Vec<String>,
+}
+
+impl Message {
+    pub fn new(key: &'static str, params: &[&dyn Display]) -> Self {
+        Message {
+            key:    Cow::Borrowed(key),
+            params: params.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    pub fn text(&self, lang: Lang) -> String {
+        translate(lang, &self.key, &self.params)
+    }
+}
+
+/// Message déjà rédigé (non encore converti en clé, ou relu depuis le serveur).
+impl From<&str> for Message {
+    fn from(s: &str) -> Self {
+        Message { key: Cow::Owned(s.to_owned()), params: vec![] }
+    }
+}
+
+impl From<String> for Message {
+    fn from(s: String) -> Self {
+        Message { key: Cow::Owned(s), params: vec![] }
+    }
+}
+
+/// Texte de `key` dans `lang`, `{i}` remplacé par `params[i]`.
+pub fn translate(lang: Lang, key: &str, params: &[String]) -> String {
+    let template = MESSAGES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, fr, mg)| match lang {
+            Lang::Fr => *fr,
+            Lang::Mg => *mg,
+        })
+        .unwrap_or(key);
+    params
+        .iter()
+        .enumerate()
+        .fold(template.to_string(), |text, (i, p)| text.replace(&format!("{{{i}}}"), p))
+}
+
+// ─── Table ─────────────────────────────────────────────────────────────────────
+
+/// (clé, français, malgache).
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // Montants
+    ("amount.required",
+     "Le montant est requis.",
+     "Ilaina ny vola."),
+    ("amount.negative",
+     "Le montant ne peut pas être négatif.",
+     "Tsy azo atao latsaky ny aotra ny vola."),
+    ("amount.zero",
+     "Le montant doit être supérieur à zéro.",
+     "Tsy maintsy mihoatra ny aotra ny vola."),
+    ("amount.separators",
+     "Montant invalide : '{0}'. Un seul séparateur décimal, ',' ou '.', sans séparateur de \
+      milliers autre que l'espace.",
+     "Vola tsy mety : '{0}'. Mpanasaraka decimal iray ihany, ',' na '.', ary elanelana fotsiny \
+      eo amin'ny arivo."),
+    ("amount.format",
+     "Montant invalide : '{0}'. Utilisez le format '15000' ou '15000,50'.",
+     "Vola tsy mety : '{0}'. Soraty toy izao : '15000' na '15000,50'."),
+    ("amount.decimals",
+     "Montant invalide : '{0}'. 2 décimales au plus.",
+     "Vola tsy mety : '{0}'. Isa 2 farafahabetsany aorian'ny faingo."),
+    ("amount.invalid",
+     "Montant invalide : '{0}'.",
+     "Vola tsy mety : '{0}'."),
+    ("amount.too_large",
+     "Le montant ne peut pas dépasser {0}.",
+     "Tsy tokony hihoatra ny {0} ny vola."),
+    // Membres
+    ("member.card_required",
+     "Le numéro de carte est requis.",
+     "Ilaina ny laharan'ny karatra."),
+    ("member.card_too_long",
+     "Le numéro de carte ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny laharan'ny karatra."),
+    ("member.name_required",
+     "Le nom complet est requis.",
+     "Ilaina ny anarana feno."),
+    ("member.name_too_long",
+     "Le nom complet ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny anarana feno."),
+    ("member.address_too_long",
+     "L'adresse ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny adiresy."),
+    ("member.phone_too_long",
+     "Le téléphone ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny laharan'ny finday."),
+    ("member.job_too_long",
+     "Le travail ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny asa."),
+    ("member.birth_date_invalid",
+     "Date de naissance invalide : '{0}'. Format attendu : AAAA-MM-JJ.",
+     "Daty nahaterahana tsy mety : '{0}'. Soraty toy izao : TTTT-VV-AA."),
+    ("member.birth_date_future",
+     "La date de naissance ne peut pas être dans le futur.",
+     "Tsy mety ho amin'ny ho avy ny daty nahaterahana."),
+    ("member.birth_date_implausible",
+     "Date de naissance invraisemblable : âge de {0} ans ou plus.",
+     "Daty nahaterahana tsy azo inoana : {0} taona na mihoatra."),
+    ("member.phone_invalid",
+     "Numéro de téléphone invalide : '{0}'. Format attendu : 03X XX XXX XX.",
+     "Laharana finday tsy mety : '{0}'. Soraty toy izao : 03X XX XXX XX."),
+    ("member.gender_invalid",
+     "Genre invalide : '{0}'. Valeurs acceptées : 'M', 'F'.",
+     "Lahy na vavy tsy mety : '{0}'. Azo ekena : 'M', 'F'."),
+    ("member.note_required",
+     "La note est vide.",
+     "Tsy misy soratra ilay fanamarihana."),
+    ("member.note_too_long",
+     "La note ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny fanamarihana."),
+    ("member.merge_same",
+     "Choisissez deux fiches différentes à fusionner.",
+     "Mifidiana taratasy roa samy hafa hatambatra."),
+    ("member.merge_type_mismatch",
+     "Seules deux fiches du même type peuvent être fusionnées.",
+     "Taratasy roa mitovy karazana ihany no azo atambatra."),
+    ("member.type_invalid",
+     "Type de membre invalide : '{0}'. Valeurs acceptées : 'Communiant', 'Cathekomen'.",
+     "Karazana mpikambana tsy mety : '{0}'. Azo ekena : 'Communiant', 'Cathekomen'."),
+    ("member.status_invalid",
+     "Statut de membre invalide : '{0}'. Valeurs acceptées : 'Actif', 'Inactif', 'Décédé'.",
+     "Toetran'ny mpikambana tsy mety : '{0}'. Azo ekena : 'Actif', 'Inactif', 'Décédé'."),
+    // Cotisations
+    ("contribution.period_required",
+     "La période est requise (ex : '2025').",
+     "Ilaina ny fe-potoana (ohatra : '2025')."),
+    ("contribution.payment_date_invalid",
+     "Date de paiement invalide : '{0}'. Format attendu : YYYY-MM-DD.",
+     "Daty nandoavana tsy mety : '{0}'. Soraty toy izao : TTTT-VV-AA."),
+    ("contribution.payment_date_too_old",
+     "Date de paiement invalide : '{0}'. Les paiements antérieurs à {1} sont refusés.",
+     "Daty nandoavana tsy mety : '{0}'. Tsy ekena ny fandoavana talohan'ny {1}."),
+    ("contribution.payment_date_future",
+     "Date de paiement trop lointaine : '{0}'. Dernière date acceptée : {1}.",
+     "Lavitra loatra ny daty nandoavana : '{0}'. Daty farany azo ekena : {1}."),
+    ("contribution.period_invalid",
+     "Période invalide : '{0}'. Formats acceptés : année '2025' ou mois '2025-03'.",
+     "Fe-potoana tsy mety : '{0}'. Azo ekena : taona '2025' na volana '2025-03'."),
+    ("contribution.period_invalid_quarters",
+     "Période invalide : '{0}'. Formats acceptés : année '2025', mois '2025-03' ou trimestre \
+      '2025-T1' à '2025-T4'.",
+     "Fe-potoana tsy mety : '{0}'. Azo ekena : taona '2025', volana '2025-03' na telovolana \
+      '2025-T1' ka hatramin'ny '2025-T4'."),
+    ("contribution.period_mismatch",
+     "La période '{0}' ne correspond pas à l'année du paiement ({1}).",
+     "Tsy mifanaraka amin'ny taona nandoavana ({1}) ny fe-potoana '{0}'."),
+    ("contribution.member_missing",
+     "Membre #{0} introuvable ou supprimé.",
+     "Tsy hita na voafafa ny mpikambana #{0}."),
+    ("contribution.not_restorable",
+     "Aucune suppression à annuler pour la contribution #{0}.",
+     "Tsy misy famafana azo averina ho an'ny rakitra #{0}."),
+    ("contribution.batch_row",
+     "Ligne {0} : {1}",
+     "Andalana {0} : {1}"),
+    ("contribution.batch_too_large",
+     "{0} contributions au maximum par envoi.",
+     "Fandoavana {0} farafahabetsany isaky ny fandefasana."),
+    // Années
+    ("year.invalid",
+     "Année invalide : {0}.",
+     "Taona tsy mety : {0}."),
+    ("year.closed",
+     "Impossible de modifier une contribution de l'année {0} : elle est clôturée.",
+     "Tsy azo ovaina ny rakitra amin'ny taona {0} : efa mihidy io taona io."),
+    ("year.not_closed",
+     "L'année {0} n'est pas clôturée.",
+     "Tsy mbola mihidy ny taona {0}."),
+    ("year.later_closed",
+     "Impossible de rouvrir {0} : {1} encore clôturée(s). Rouvrez-les d'abord ou rouvrez en \
+      cascade.",
+     "Tsy azo sokafana indray ny {0} : mbola mihidy ny {1}. Sokafy aloha ireo, na sokafy \
+      misesy."),
+    ("year.note_too_long",
+     "La note de clôture ne doit pas dépasser {0} caractères.",
+     "Tsy tokony hihoatra ny litera {0} ny fanamarihana amin'ny fanakatonana."),
+    ("year.not_empty",
+     "L'année {0} contient des données (cotisations, clôture ou note) : elle ne peut pas être masquée.",
+     "Misy raki-tsoratra ny taona {0} (fandoavana, fanakatonana na fanamarihana) : tsy azo afenina."),
+    // Code PIN
+    ("pin.format",
+     "Le code PIN doit contenir de {0} à {1} chiffres.",
+     "Tokony ho isa {0} ka hatramin'ny {1} ny kaody PIN."),
+    ("pin.already_set",
+     "Un code PIN est déjà configuré.",
+     "Efa misy kaody PIN voapetraka."),
+    ("pin.not_set",
+     "Code PIN non configuré.",
+     "Mbola tsy misy kaody PIN voapetraka."),
+    ("pin.locked",
+     "Trop de tentatives incorrectes. Réessayez dans {0} min.",
+     "Diso imbetsaka loatra. Andramo indray afaka {0} min."),
+    ("pin.locked_now",
+     "Code PIN incorrect. Verrouillé pendant {0} min.",
+     "Diso ny kaody PIN. Mihidy mandritra ny {0} min."),
+    ("pin.wrong",
+     "Code PIN incorrect ({0} essai(s) restant(s)).",
+     "Diso ny kaody PIN (andrana {0} sisa)."),
+    ("pin.required",
+     "Code PIN requis pour cette action.",
+     "Ilaina ny kaody PIN amin'ity asa ity."),
+    // Paramètres
+    ("settings.church_required",
+     "Le nom de l'église est obligatoire.",
+     "Ilaina ny anaran'ny fiangonana."),
+    ("settings.pledge_negative",
+     "La cotisation par défaut ne peut pas être négative.",
+     "Tsy azo atao latsaky ny aotra ny adidy mahazatra."),
+    ("settings.period_invalid",
+     "Période par défaut invalide : '{0}'.",
+     "Fe-potoana mahazatra tsy mety : '{0}'."),
+    ("settings.quick_amounts_max",
+     "{0} montants rapides au maximum.",
+     "Vola haingana {0} farafahabetsany."),
+    ("settings.quick_amounts_positive",
+     "Les montants rapides doivent être positifs.",
+     "Tsy maintsy mihoatra ny aotra ny vola haingana."),
+    ("settings.grace_days_max",
+     "Le délai avant clôture automatique ne peut pas dépasser {0} jours.",
+     "Tsy azo mihoatra ny {0} andro ny fe-potoana alohan'ny fanakatonana ho azy."),
+    ("settings.future_days_max",
+     "L'avance admise sur une date de paiement ne peut pas dépasser {0} jours.",
+     "Tsy azo mihoatra ny {0} andro ny fandrosoana azo ekena amin'ny daty nandoavana."),
+    ("settings.renewal_days_max",
+     "La fenêtre des renouvellements de carte ne peut pas dépasser {0} jours.",
+     "Tsy azo mihoatra ny {0} andro ny fe-potoana fanavaozana karatra."),
+    ("settings.fiscal_month_invalid",
+     "Mois de début d'exercice invalide : {0}. Valeurs acceptées : 1 à 12.",
+     "Volana fanombohan'ny taom-piasana tsy mety : {0}. Azo ekena : 1 ka hatramin'ny 12."),
+    ("settings.fiscal_month_locked",
+     "Le mois de début d'exercice ne peut plus changer : des cotisations sont déjà enregistrées.",
+     "Tsy azo ovaina intsony ny volana fanombohan'ny taom-piasana : efa misy fandoavana voarakitra."),
+    ("settings.card_pattern_invalid",
+     "Modèle de carte invalide : '{0}'. Exemple : 'C-%04d'.",
+     "Endrika karatra tsy mety : '{0}'. Ohatra : 'C-%04d'."),
+    // Export CSV
+    ("export.csv_extension",
+     "Le fichier d'export doit se terminer par .csv : {0}",
+     "Tsy maintsy mifarana amin'ny .csv ny rakitra avoaka : {0}"),
+    ("export.file_exists",
+     "Le fichier existe déjà : {0}",
+     "Efa misy ilay rakitra : {0}"),
+    // Archive JSON
+    ("archive.invalid",
+     "Ce fichier n'est pas une archive valide : {0}",
+     "Tsy rakitra fitehirizana mety ity : {0}"),
+    ("archive.version",
+     "Archive de version {0} non prise en charge (version attendue : {1}).",
+     "Tsy voaray ny fitehirizana dikan-teny {0} (andrasana : {1})."),
+    ("archive.duplicate_card",
+     "Archive incohérente : carte '{0}' présente plusieurs fois.",
+     "Fitehirizana tsy mirindra : karatra '{0}' miverimberina."),
+    ("archive.unknown_member",
+     "Archive incohérente : le reçu '{0}' désigne un membre absent (#{1}).",
+     "Fitehirizana tsy mirindra : ny rosia '{0}' dia an'olona tsy ao (#{1})."),
+    ("archive.unknown_note_member",
+     "Archive incohérente : la note #{0} désigne un membre absent (#{1}).",
+     "Fitehirizana tsy mirindra : ny fanamarihana #{0} dia an'olona tsy ao (#{1})."),
+    // Données de démonstration
+    ("seed.not_empty",
+     "La base contient déjà des données : données de démonstration refusées.",
+     "Efa misy angona ny tahiry : tsy ampidirina ny angona fanandramana."),
+    ("seed.member_count",
+     "Nombre de membres de démonstration : entre 1 et {0}.",
+     "Isan'ny mpikambana fanandramana : 1 ka hatramin'ny {0}."),
+    ("seed.years",
+     "Années de démonstration invalides : de {0} à {1} uniquement.",
+     "Taona fanandramana tsy mety : {0} ka hatramin'ny {1} ihany."),
+    // Base
+    ("db.read_only",
+     "Mode démonstration : les modifications ne sont pas enregistrées.",
+     "Fanandramana ihany : tsy voatahiry ny fanovana."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let params = vec!["2022".to_string(), "2023".to_string()];
+        assert_eq!(
+            translate(Lang::Fr, "year.later_closed", &params),
+            "Impossible de rouvrir 2022 : 2023 encore clôturée(s). Rouvrez-les d'abord ou \
+             rouvrez en cascade."
+        );
+        assert_eq!(translate(Lang::Mg, "year.not_closed", &params[..1]), "Tsy mbola mihidy ny taona 2022.");
+        // Clé inconnue : affichée telle quelle
+        assert_eq!(translate(Lang::Mg, "Nom vide", &[]), "Nom vide");
+        assert_eq!(Message::from("Déjà pris").text(Lang::Fr), "Déjà pris");
+        assert_eq!(Message::new("amount.invalid", &[&"abc"]).text(Lang::Mg), "Vola tsy mety : 'abc'.");
+    }
+
+    #[test]
+    fn test_lang_parse() {
+        assert_eq!(Lang::parse("fr"), Lang::Fr);
+        assert_eq!(Lang::parse("mg"), Lang::Mg);
+        assert_eq!(Lang::parse(""), Lang::Mg);
+        assert_eq!(Lang::parse(Lang::Fr.as_str()), Lang::Fr);
+    }
+
+    /// Chaque clé est unique et ses deux traductions ont les mêmes paramètres.
+    #[test]
+    fn test_messages_complets() {
+        for (i, (key, fr, mg)) in MESSAGES.iter().enumerate() {
+            assert!(!fr.is_empty() && !mg.is_empty(), "{key}");
+            assert!(MESSAGES[i + 1..].iter().all(|(k, _, _)| k != key), "clé en double : {key}");
+            for p in 0..4 {
+                let placeholder = format!("{{{p}}}");
+                assert_eq!(fr.contains(&placeholder), mg.contains(&placeholder), "{key} {placeholder}");
+            }
+        }
+    }
+}
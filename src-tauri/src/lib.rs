@@ -1,7 +1,11 @@
 mod api_server;
+mod backup;
+mod cli;
 mod config;
 mod db;
 mod export;
+mod i18n;
+mod pdf;
 mod remote_client;
 
 static MOCK_SERVER_PORT: std::sync::OnceLock<u16> = std::sync::OnceLock::new();
@@ -9,11 +13,21 @@ static MOCK_SERVER_PORT: std::sync::OnceLock<u16> = std::sync::OnceLock::new();
 static API_SERVER_PORT: std::sync::OnceLock<u16> = std::sync::OnceLock::new();
 
 use config::{load_config, save_config_to_disk, AppConfig, AppMode};
+use backup::BackupInfo;
 use db::{
-    Contribution, ContributionEditInput, ContributionInput, ContributionWithMember, Member,
-    MemberInput, MemberWithTotal, Repository, YearSummary,
+    AddressGroup, AppError, AppLog, ArchiveImportReport, AuditEntry, Contribution, ContributionEditInput, ContributionInput,
+    ContributionSearchScope, ContributionWithMember, DashboardStats, DbHealth, DeletedMember, ImportMode, ImportReport,
+    IntegrityReport, Member, MemberCounts, MemberFilter, MemberInput, MemberLite, MemberNote, MemberRow, MemberSort, MemberStatus, MemberType,
+    MemberWithTotal, MemberYearTotal, MemberYearTotals, MonthlyComparison, MonthlyTotal, Page, PendingTask, PhoneFixReport,
+    Repository, SeedReport, Settings,
+    RenewalDue, Statistics, UpcomingBirthday, WeekTotal, YearBreakdown, YearClosePreview, YearCloseStatus, YearComparison,
+    YearMemberSnapshot, YearSummary,
 };
-use export::{build_csv_from_members, build_excel_bytes, parse_csv_to_members};
+use export::{
+    build_contributions_csv, build_csv_from_members, build_excel_bytes, build_member_statement_csv,
+    build_receipt_html, build_year_report_html, build_year_report_pdf, write_csv_file,
+};
+use i18n::Lang;
 use remote_client::RemoteClient;
 use std::{path::PathBuf, sync::Arc};
 use tauri::Manager;
@@ -24,157 +38,525 @@ use tokio::sync::RwLock;
 /// Abstraction sur la source de données :
 /// - Local  : SQLite sur ce PC (mode serveur)
 /// - Remote : API HTTP sur le PC serveur (mode client)
+/// - Demo : base en mémoire, lecture seule (`start_demo_mode`, après un échec d'ouverture)
 /// - Unconfigured : premier lancement, aucune config
+/// - Initializing : ouverture de la base / migrations en cours (tâche de fond du setup)
+/// - Failed : échec de l'initialisation, remonté au frontend par `is_ready`
 pub enum DataSource {
     Local(Repository),
     Remote(RemoteClient),
+    Demo(Repository),
     Unconfigured,
+    Initializing,
+    Failed(AppError),
 }
 
 macro_rules! dispatch {
     ($self:expr, $method:ident $(, $arg:expr)*) => {
         match $self {
-            DataSource::Local(r)     => r.$method($($arg),*).await.map_err(|e| e.to_string()),
-            DataSource::Remote(c)    => c.$method($($arg),*).await.map_err(|e| e.to_string()),
-            DataSource::Unconfigured => Err(Self::not_configured()),
+            DataSource::Local(r)
+            | DataSource::Demo(r)    => r.$method($($arg),*).await,
+            DataSource::Remote(c)    => c.$method($($arg),*).await,
+            other                    => Err(other.unavailable()),
+        }
+    };
+}
+
+/// Comme `dispatch!` pour les actions destructrices : le jeton PIN est contrôlé ici
+/// en mode local, ou transmis au serveur qui le contrôle lui-même.
+macro_rules! dispatch_guarded {
+    ($self:expr, $token:expr, $method:ident $(, $arg:expr)*) => {
+        match $self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                r.require_pin_token($token).await?;
+                r.$method($($arg),*).await
+            }
+            DataSource::Remote(c) => c.$method($($arg,)* $token).await,
+            other                 => Err(other.unavailable()),
         }
     };
 }
 
 impl DataSource {
-    fn not_configured() -> String {
-        "non_configure".to_string()
+    /// Erreur renvoyée par les commandes tant qu'aucune source n'est utilisable.
+    fn unavailable(&self) -> AppError {
+        AppError::Internal(match self {
+            DataSource::Initializing => "Initialisation de la base en cours…".to_string(),
+            DataSource::Failed(e)    => format!("Échec de l'initialisation de la base : {e}"),
+            _                        => "non_configure".to_string(),
+        })
+    }
+
+    /// Repository local — les opérations de maintenance (sauvegardes, journal)
+    /// ne sont disponibles que sur le PC serveur.
+    fn local_repo(&self) -> Result<&Repository, AppError> {
+        match self {
+            DataSource::Local(r)     => Ok(r),
+            DataSource::Remote(_)    => Err(AppError::Internal("Disponible uniquement sur le PC serveur.".into())),
+            DataSource::Demo(_)      => Err(AppError::validation("db.read_only", &[])),
+            other                    => Err(other.unavailable()),
+        }
     }
 
     // ── Members ───────────────────────────────────────────────────────────────
 
-    async fn get_members(&self) -> Result<Vec<Member>, String> {
+    async fn get_members(&self) -> Result<Vec<Member>, AppError> {
         dispatch!(self, get_members)
     }
 
-    async fn get_members_by_type(&self, t: &str) -> Result<Vec<Member>, String> {
+    async fn get_members_by_type(&self, t: MemberType) -> Result<Vec<Member>, AppError> {
         dispatch!(self, get_members_by_type, t)
     }
 
-    async fn get_members_by_type_with_total(&self, t: &str) -> Result<Vec<MemberWithTotal>, String> {
-        dispatch!(self, get_members_by_type_with_total, t)
+    async fn get_member_counts(&self) -> Result<MemberCounts, AppError> {
+        dispatch!(self, get_member_counts)
+    }
+
+    async fn get_upcoming_birthdays(&self, days: u32) -> Result<Vec<UpcomingBirthday>, AppError> {
+        dispatch!(self, get_upcoming_birthdays, days)
+    }
+
+    async fn get_upcoming_renewals(&self, within_days: u32) -> Result<Vec<RenewalDue>, AppError> {
+        dispatch!(self, get_upcoming_renewals, within_days)
+    }
+
+    async fn get_members_by_type_with_total(
+        &self,
+        t: MemberType,
+        status: Option<MemberStatus>,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        dispatch!(self, get_members_by_type_with_total, t, status)
+    }
+
+    async fn get_members_with_year_totals(
+        &self,
+        t: MemberType,
+        years: &[i32],
+    ) -> Result<Vec<MemberYearTotals>, AppError> {
+        dispatch!(self, get_members_with_year_totals, t, years)
+    }
+
+    async fn get_members_grouped_by_address(&self, t: MemberType) -> Result<Vec<AddressGroup>, AppError> {
+        dispatch!(self, get_members_grouped_by_address, t)
+    }
+
+    async fn get_members_without_contribution(
+        &self,
+        year: i32,
+        member_type: Option<&str>,
+    ) -> Result<Vec<Member>, AppError> {
+        dispatch!(self, get_members_without_contribution, year, member_type)
+    }
+
+    async fn get_member_rows(
+        &self,
+        t: MemberType,
+        page: i64,
+        page_size: i64,
+        sort: MemberSort,
+        filter: &MemberFilter,
+    ) -> Result<Page<MemberRow>, AppError> {
+        dispatch!(self, get_member_rows, t, page, page_size, sort, filter)
     }
 
-    async fn get_member(&self, id: i64) -> Result<Member, String> {
+    async fn get_member(&self, id: i64) -> Result<Member, AppError> {
         dispatch!(self, get_member, id)
     }
 
-    async fn create_member(&self, input: MemberInput) -> Result<Member, String> {
+    async fn get_member_year_totals(&self, member_id: i64) -> Result<Vec<MemberYearTotal>, AppError> {
+        dispatch!(self, get_member_year_totals, member_id)
+    }
+
+    async fn add_member_note(&self, member_id: i64, text: String) -> Result<MemberNote, AppError> {
+        dispatch!(self, add_member_note, member_id, text)
+    }
+
+    async fn get_member_notes(&self, member_id: i64) -> Result<Vec<MemberNote>, AppError> {
+        dispatch!(self, get_member_notes, member_id)
+    }
+
+    async fn delete_member_note(&self, id: i64) -> Result<(), AppError> {
+        dispatch!(self, delete_member_note, id)
+    }
+
+    async fn get_member_by_card(&self, card_number: &str) -> Result<Option<Member>, AppError> {
+        dispatch!(self, get_member_by_card, card_number)
+    }
+
+    async fn search_members(
+        &self,
+        query: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        dispatch!(self, search_members, query, limit)
+    }
+
+    async fn search_members_lite(&self, query: &str) -> Result<Vec<MemberLite>, AppError> {
+        dispatch!(self, search_members_lite, query)
+    }
+
+    async fn find_similar_members(&self, full_name: &str) -> Result<Vec<Member>, AppError> {
+        dispatch!(self, find_similar_members, full_name)
+    }
+
+    async fn create_member(&self, input: MemberInput) -> Result<Member, AppError> {
         dispatch!(self, create_member, input)
     }
 
-    async fn update_member(&self, id: i64, input: MemberInput) -> Result<Member, String> {
+    async fn update_member(&self, id: i64, input: MemberInput) -> Result<Member, AppError> {
         dispatch!(self, update_member, id, input)
     }
 
-    async fn delete_member(&self, id: i64) -> Result<(), String> {
-        dispatch!(self, delete_member, id)
+    async fn delete_member(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        dispatch_guarded!(self, pin_token, delete_member, id)
+    }
+
+    async fn get_deleted_members(&self) -> Result<Vec<DeletedMember>, AppError> {
+        dispatch!(self, get_deleted_members)
+    }
+
+    async fn restore_member(&self, id: i64) -> Result<Member, AppError> {
+        dispatch!(self, restore_member, id)
+    }
+
+    async fn purge_member(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        dispatch_guarded!(self, pin_token, purge_member, id)
     }
 
-    async fn transfer_members(&self, ids: &[i64], new_type: &str) -> Result<usize, String> {
+    async fn next_card_number(&self, member_type: MemberType) -> Result<String, AppError> {
+        dispatch!(self, next_card_number, member_type)
+    }
+
+    async fn transfer_members(
+        &self,
+        ids: &[i64],
+        new_type: MemberType,
+    ) -> Result<Vec<Member>, AppError> {
         dispatch!(self, transfer_members, ids, new_type)
     }
 
+    async fn merge_members(
+        &self,
+        keep_id: i64,
+        remove_id: i64,
+        pin_token: Option<&str>,
+    ) -> Result<Member, AppError> {
+        dispatch_guarded!(self, pin_token, merge_members, keep_id, remove_id)
+    }
+
     // ── Contributions ─────────────────────────────────────────────────────────
 
-    async fn get_contributions(&self, member_id: i64) -> Result<Vec<Contribution>, String> {
+    async fn get_contributions(&self, member_id: i64) -> Result<Vec<Contribution>, AppError> {
         dispatch!(self, get_contributions, member_id)
     }
 
-    async fn get_contributions_by_year(&self, year: i32) -> Result<Vec<Contribution>, String> {
+    async fn get_recent_contributions(
+        &self,
+        member_id: i64,
+        limit: i64,
+    ) -> Result<Vec<Contribution>, AppError> {
+        dispatch!(self, get_recent_contributions, member_id, limit)
+    }
+
+    async fn get_contributions_by_year(&self, year: i32) -> Result<Vec<Contribution>, AppError> {
         dispatch!(self, get_contributions_by_year, year)
     }
 
-    async fn create_contribution(&self, input: ContributionInput) -> Result<Contribution, String> {
+    async fn get_distinct_periods(&self, year: i32) -> Result<Vec<String>, AppError> {
+        dispatch!(self, get_distinct_periods, year)
+    }
+
+    async fn create_contribution(&self, input: ContributionInput) -> Result<Contribution, AppError> {
         dispatch!(self, create_contribution, input)
     }
 
-    async fn delete_contribution(&self, id: i64) -> Result<(), String> {
-        dispatch!(self, delete_contribution, id)
+    async fn create_contributions_batch(
+        &self,
+        inputs: Vec<ContributionInput>,
+    ) -> Result<Vec<Contribution>, AppError> {
+        dispatch!(self, create_contributions_batch, inputs)
+    }
+
+    async fn delete_contribution(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        dispatch_guarded!(self, pin_token, delete_contribution, id)
     }
 
-    async fn get_contributions_by_year_with_member(
+    async fn restore_contribution(&self, id: i64) -> Result<Contribution, AppError> {
+        dispatch!(self, restore_contribution, id)
+    }
+
+    async fn get_contribution_by_receipt(
         &self,
-        year: i32,
-    ) -> Result<Vec<ContributionWithMember>, String> {
-        dispatch!(self, get_contributions_by_year_with_member, year)
+        receipt: &str,
+    ) -> Result<ContributionWithMember, AppError> {
+        dispatch!(self, get_contribution_by_receipt, receipt)
     }
 
-    async fn get_all_contributions_with_member(
+    async fn get_contributions_by_year_with_member(
         &self,
-    ) -> Result<Vec<ContributionWithMember>, String> {
-        dispatch!(self, get_all_contributions_with_member)
+        year: Option<i32>,
+        query: Option<&str>,
+        scope: ContributionSearchScope,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Page<ContributionWithMember>, AppError> {
+        dispatch!(self, get_contributions_by_year_with_member, year, query, scope, limit, offset)
     }
 
     // ── Year Summaries ────────────────────────────────────────────────────────
 
-    async fn get_year_summaries(&self) -> Result<Vec<YearSummary>, String> {
+    async fn get_year_summaries(&self) -> Result<Vec<YearSummary>, AppError> {
         dispatch!(self, get_year_summaries)
     }
 
-    async fn get_year_summary(&self, year: i32) -> Result<Option<YearSummary>, String> {
+    async fn get_year_summary(&self, year: i32) -> Result<Option<YearSummary>, AppError> {
         dispatch!(self, get_year_summary, year)
     }
 
-    async fn close_year(&self, year: i32, note: Option<String>) -> Result<YearSummary, String> {
+    async fn get_years_with_contributions(&self) -> Result<Vec<i32>, AppError> {
+        dispatch!(self, get_years_with_contributions)
+    }
+
+    async fn delete_empty_year_summary(&self, year: i32) -> Result<(), AppError> {
+        dispatch!(self, delete_empty_year_summary, year)
+    }
+
+    async fn close_year(&self, year: i32, note: Option<String>) -> Result<YearSummary, AppError> {
         dispatch!(self, close_year, year, note)
     }
 
-    async fn reopen_year(&self, year: i32) -> Result<YearSummary, String> {
-        dispatch!(self, reopen_year, year)
+    async fn reopen_year(
+        &self,
+        year: i32,
+        cascade: bool,
+        pin_token: Option<&str>,
+    ) -> Result<Vec<YearSummary>, AppError> {
+        dispatch_guarded!(self, pin_token, reopen_year, year, cascade)
+    }
+
+    async fn update_year_note(
+        &self,
+        year: i32,
+        note: Option<String>,
+        pin_token: Option<&str>,
+    ) -> Result<YearSummary, AppError> {
+        dispatch_guarded!(self, pin_token, update_year_note, year, note)
+    }
+
+    async fn check_and_close_previous_year(&self, grace_days: Option<u32>) -> Result<YearCloseStatus, AppError> {
+        dispatch!(self, check_and_close_previous_year, grace_days)
+    }
+
+    async fn preview_year_close(&self, year: i32) -> Result<YearClosePreview, AppError> {
+        dispatch!(self, preview_year_close, year)
+    }
+
+    async fn get_year_comparison(&self, years: Vec<i32>) -> Result<Vec<YearComparison>, AppError> {
+        dispatch!(self, get_year_comparison, years)
+    }
+
+    async fn get_monthly_totals(&self, year: i32) -> Result<Vec<MonthlyTotal>, AppError> {
+        dispatch!(self, get_monthly_totals, year)
+    }
+
+    async fn get_year_member_totals(&self, year: i32) -> Result<Vec<YearMemberSnapshot>, AppError> {
+        dispatch!(self, get_year_member_totals, year)
     }
 
-    async fn check_and_close_previous_year(&self) -> Result<Option<YearSummary>, String> {
-        dispatch!(self, check_and_close_previous_year)
+    async fn get_weekly_totals(&self, year: i32) -> Result<Vec<WeekTotal>, AppError> {
+        dispatch!(self, get_weekly_totals, year)
+    }
+
+    async fn compare_years(
+        &self,
+        year_a: i32,
+        year_b: i32,
+    ) -> Result<MonthlyComparison, AppError> {
+        dispatch!(self, compare_years, year_a, year_b)
+    }
+
+    async fn get_year_breakdown(&self, year: i32) -> Result<YearBreakdown, AppError> {
+        dispatch!(self, get_year_breakdown, year)
+    }
+
+    // ── Statistiques ──────────────────────────────────────────────────────────
+
+    async fn get_statistics(&self) -> Result<Statistics, AppError> {
+        dispatch!(self, get_statistics)
+    }
+
+    async fn get_dashboard_stats(&self) -> Result<DashboardStats, AppError> {
+        dispatch!(self, get_dashboard_stats)
+    }
+
+    // ── Paramètres ────────────────────────────────────────────────────────────
+
+    async fn get_settings(&self) -> Result<Settings, AppError> {
+        dispatch!(self, get_settings)
+    }
+
+    async fn update_settings(&self, settings: Settings) -> Result<Settings, AppError> {
+        dispatch!(self, update_settings, settings)
+    }
+
+    async fn get_language(&self) -> Result<Lang, AppError> {
+        dispatch!(self, get_language)
+    }
+
+    async fn set_language(&self, lang: Lang) -> Result<Lang, AppError> {
+        dispatch!(self, set_language, lang)
+    }
+
+    // ── Intégrité ─────────────────────────────────────────────────────────────
+
+    async fn run_integrity_check(&self) -> Result<IntegrityReport, AppError> {
+        dispatch!(self, run_integrity_check)
+    }
+
+    async fn repair_integrity(&self) -> Result<IntegrityReport, AppError> {
+        dispatch!(self, repair_integrity)
+    }
+
+    async fn health(&self) -> Result<DbHealth, AppError> {
+        dispatch!(self, health)
+    }
+
+    async fn get_pending_tasks(&self) -> Result<Vec<PendingTask>, AppError> {
+        dispatch!(self, get_pending_tasks)
+    }
+
+    async fn normalize_all_phones(&self) -> Result<PhoneFixReport, AppError> {
+        dispatch!(self, normalize_all_phones)
+    }
+
+    // ── Journal d'audit ───────────────────────────────────────────────────────
+
+    async fn get_audit_log(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        entity: Option<&str>,
+    ) -> Result<Page<AuditEntry>, AppError> {
+        dispatch!(self, get_audit_log, limit, offset, entity)
     }
 
     // ── Export / Import ───────────────────────────────────────────────────────
 
-    async fn export_members_csv(&self, member_type: &str) -> Result<String, String> {
+    async fn export_members_csv(&self, member_type: MemberType) -> Result<String, AppError> {
         match self {
-            DataSource::Local(r) => {
-                let members = r.get_members_by_type(member_type).await.map_err(|e| e.to_string())?;
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                let members = r.get_members_by_type(member_type).await?;
                 Ok(build_csv_from_members(&members))
             }
-            DataSource::Remote(c) => c.export_members_csv(member_type).await.map_err(|e| e.to_string()),
-            DataSource::Unconfigured => Err(Self::not_configured()),
+            DataSource::Remote(c) => c.export_members_csv(member_type).await,
+            other => Err(other.unavailable()),
+        }
+    }
+
+    async fn export_members_excel(&self, member_type: MemberType) -> Result<Vec<u8>, AppError> {
+        match self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                let members = r.get_members_by_type_with_total(member_type, None).await?;
+                Ok(build_excel_bytes(&members, member_type.as_str())?)
+            }
+            DataSource::Remote(c) => c.export_members_excel(member_type).await,
+            other => Err(other.unavailable()),
         }
     }
 
-    async fn export_members_excel(&self, member_type: &str) -> Result<Vec<u8>, String> {
+    async fn export_year_report(&self, year: i32) -> Result<String, AppError> {
         match self {
-            DataSource::Local(r) => {
-                let members = r.get_members_by_type_with_total(member_type).await.map_err(|e| e.to_string())?;
-                build_excel_bytes(&members, member_type)
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                let report = r.get_year_report(year).await?;
+                let settings = r.get_settings().await?;
+                Ok(build_year_report_html(&report, &settings.church_name))
             }
-            DataSource::Remote(c) => c.export_members_excel(member_type).await.map_err(|e| e.to_string()),
-            DataSource::Unconfigured => Err(Self::not_configured()),
+            DataSource::Remote(c) => c.export_year_report(year).await,
+            other => Err(other.unavailable()),
         }
     }
 
-    async fn import_members_csv(&self, csv_content: String, member_type: &str) -> Result<usize, String> {
+    async fn export_receipt(&self, receipt: &str) -> Result<String, AppError> {
+        match self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                let contribution = r.get_contribution_by_receipt(receipt).await?;
+                let s = r.get_settings().await?;
+                Ok(build_receipt_html(&contribution, &s))
+            }
+            DataSource::Remote(c) => c.export_receipt(receipt).await,
+            other => Err(other.unavailable()),
+        }
+    }
+
+    async fn export_year_report_pdf(&self, year: i32) -> Result<Vec<u8>, AppError> {
+        match self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                let report = r.get_year_report(year).await?;
+                let settings = r.get_settings().await?;
+                Ok(build_year_report_pdf(&report, &settings.church_name))
+            }
+            DataSource::Remote(c) => c.export_year_report_pdf(year).await,
+            other => Err(other.unavailable()),
+        }
+    }
+
+    async fn export_contributions_csv(&self, year: i32) -> Result<String, AppError> {
+        match self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                Ok(build_contributions_csv(&r.get_year_report(year).await?))
+            }
+            DataSource::Remote(c) => c.export_contributions_csv(year).await,
+            other => Err(other.unavailable()),
+        }
+    }
+
+    async fn export_member_statement_csv(
+        &self,
+        member_id: i64,
+        year: i32,
+    ) -> Result<String, AppError> {
+        match self {
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                Ok(build_member_statement_csv(&r.get_member_statement(member_id, year).await?))
+            }
+            DataSource::Remote(c) => c.export_member_statement_csv(member_id, year).await,
+            other => Err(other.unavailable()),
+        }
+    }
+
+    async fn import_members_csv(
+        &self,
+        csv_content: String,
+        member_type: MemberType,
+    ) -> Result<ImportReport, AppError> {
         match self {
-            DataSource::Local(r) => {
-                let inputs = parse_csv_to_members(&csv_content, member_type);
-                r.import_members(inputs).await.map_err(|e| e.to_string())
+            DataSource::Local(r) | DataSource::Demo(r) => {
+                r.import_members_csv(&csv_content, member_type).await
             }
-            DataSource::Remote(c) => c.import_members_csv(csv_content, member_type).await.map_err(|e| e.to_string()),
-            DataSource::Unconfigured => Err(Self::not_configured()),
+            DataSource::Remote(c) => c.import_members_csv(csv_content, member_type).await,
+            other => Err(other.unavailable()),
         }
     }
 
     // ── PIN ───────────────────────────────────────────────────────────────────
 
-    async fn set_pin(&self, pin: String) -> Result<(), String> {
+    async fn set_pin(&self, pin: String) -> Result<(), AppError> {
         dispatch!(self, set_pin, &pin)
     }
 
-    async fn verify_pin(&self, pin: String) -> Result<bool, String> {
+    async fn change_pin(&self, old_pin: String, new_pin: String) -> Result<(), AppError> {
+        dispatch!(self, change_pin, &old_pin, &new_pin)
+    }
+
+    async fn remove_pin(&self, old_pin: String) -> Result<(), AppError> {
+        dispatch!(self, remove_pin, &old_pin)
+    }
+
+    async fn has_pin(&self) -> Result<bool, AppError> {
+        dispatch!(self, has_pin)
+    }
+
+    async fn verify_pin(&self, pin: String) -> Result<String, AppError> {
         dispatch!(self, verify_pin, &pin)
     }
 
@@ -182,7 +564,7 @@ impl DataSource {
         &self,
         id: i64,
         input: ContributionEditInput,
-    ) -> Result<Contribution, String> {
+    ) -> Result<Contribution, AppError> {
         dispatch!(self, update_contribution, id, input)
     }
 }
@@ -196,15 +578,59 @@ pub struct AppState {
 
 // ─── Initialisation de la source ──────────────────────────────────────────────
 
-async fn init_source(app_data_dir: &PathBuf, cfg: &AppConfig) -> Result<DataSource, String> {
+/// Totaux annuels faussés par une écriture interrompue : corrigés au démarrage
+/// et consignés dans `app_log`. Un échec n'empêche pas l'application de s'ouvrir.
+async fn repair_summaries_at_startup(repo: &Repository) {
+    let messages = match repo.verify_and_repair_summaries().await {
+        Ok(repaired) => repaired
+            .iter()
+            .map(|r| format!("Total {} corrigé : {} → {}", r.year, r.stored, r.computed))
+            .collect(),
+        Err(e) => vec![format!("Vérification des totaux annuels impossible : {e}")],
+    };
+    for message in messages {
+        if let Err(e) = repo.log_event("warn", "integrity", &message).await {
+            eprintln!("[Integrity] {message} (journal indisponible : {e})");
+        }
+    }
+}
+
+/// Normalisation unique des périodes saisies en texte libre (voir
+/// `Repository::normalize_legacy_periods`) ; le rapport va dans `app_log`.
+async fn normalize_periods_at_startup(repo: &Repository) {
+    let messages: Vec<(&str, String)> = match repo.normalize_legacy_periods().await {
+        Ok(None) => return,
+        Ok(Some(report)) => report
+            .rewritten
+            .iter()
+            .map(|(from, to, count)| {
+                ("info", format!("Période « {from} » → « {to} » ({count} cotisation(s))"))
+            })
+            .chain(report.unparseable.iter().map(|p| {
+                ("warn", format!("Période non reconnue, laissée telle quelle : « {p} »"))
+            }))
+            .collect(),
+        Err(e) => vec![("warn", format!("Normalisation des périodes impossible : {e}"))],
+    };
+    for (level, message) in messages {
+        if let Err(e) = repo.log_event(level, "period", &message).await {
+            eprintln!("[Period] {message} (journal indisponible : {e})");
+        }
+    }
+}
+
+async fn init_source(app_data_dir: &PathBuf, cfg: &AppConfig) -> Result<DataSource, AppError> {
     match &cfg.mode {
         AppMode::Server => {
-            let db_path = app_data_dir
-                .join("fjkm.db")
+            let db_path = cfg
+                .db_path(app_data_dir)
                 .to_str()
-                .ok_or("Chemin DB invalide")?
+                .ok_or_else(|| AppError::Io("Chemin DB invalide".into()))?
                 .to_owned();
-            let repo = Repository::new(&db_path).await.map_err(|e| e.to_string())?;
+            let repo = Repository::new(&db_path).await?;
+            i18n::set_current(repo.get_language().await.unwrap_or_default());
+            repair_summaries_at_startup(&repo).await;
+            normalize_periods_at_startup(&repo).await;
             let port = cfg.server_port;
             // Ne démarrer le serveur qu'une seule fois (idempotent)
             if API_SERVER_PORT.get().is_none() {
@@ -224,6 +650,18 @@ async fn init_source(app_data_dir: &PathBuf, cfg: &AppConfig) -> Result<DataSour
 
 // ─── Commandes config ──────────────────────────────────────────────────────────
 
+/// `Ok(false)` tant que la base s'initialise, `Err` si l'initialisation a échoué :
+/// son `kind` (`io`, `migration`, `corrupt`…) oriente l'écran d'erreur du frontend.
+/// Une app non configurée est « prête » (le frontend affiche alors le Setup).
+#[tauri::command]
+async fn is_ready(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    match &*state.source.read().await {
+        DataSource::Initializing => Ok(false),
+        DataSource::Failed(e)    => Err(e.clone()),
+        _                        => Ok(true),
+    }
+}
+
 #[tauri::command]
 async fn get_config(state: tauri::State<'_, AppState>) -> Result<Option<AppConfig>, String> {
     Ok(load_config(&state.app_data_dir))
@@ -233,13 +671,51 @@ async fn get_config(state: tauri::State<'_, AppState>) -> Result<Option<AppConfi
 async fn save_config(
     state: tauri::State<'_, AppState>,
     config: AppConfig,
-) -> Result<(), String> {
-    save_config_to_disk(&state.app_data_dir, &config)?;
+) -> Result<(), AppError> {
+    // Le dossier a pu manquer au démarrage (`resolve_app_data_dir`)
+    std::fs::create_dir_all(&state.app_data_dir).map_err(|e| AppError::Io(e.to_string()))?;
+    save_config_to_disk(&state.app_data_dir, &config).map_err(AppError::Io)?;
     let new_source = init_source(&state.app_data_dir, &config).await?;
     *state.source.write().await = new_source;
     Ok(())
 }
 
+/// Ouvre (ou crée) la base dans `path` au lieu du dossier de données habituel, puis
+/// retient ce choix dans la configuration. `Ok(false)` : base ouverte, mais choix non
+/// enregistré (dossier de configuration en lecture seule) — à refaire au prochain lancement.
+#[tauri::command]
+async fn use_data_dir(state: tauri::State<'_, AppState>, path: String) -> Result<bool, AppError> {
+    let dir = PathBuf::from(path.trim());
+    if !dir.is_absolute() {
+        return Err(AppError::Io(format!("Chemin absolu attendu : {}", dir.display())));
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::Io(format!("{} : {e}", dir.display())))?;
+    let config = AppConfig {
+        data_dir: Some(dir),
+        ..load_config(&state.app_data_dir).unwrap_or(AppConfig {
+            mode:        AppMode::Server,
+            server_ip:   "0.0.0.0".into(),
+            server_port: config::DEFAULT_SERVER_PORT,
+            data_dir:    None,
+        })
+    };
+    let new_source = init_source(&state.app_data_dir, &config).await?;
+    *state.source.write().await = new_source;
+    let saved = save_config_to_disk(&state.app_data_dir, &config)
+        .inspect_err(|e| eprintln!("[Setup] Dossier de données non enregistré : {e}"))
+        .is_ok();
+    Ok(saved)
+}
+
+/// Base de démonstration en mémoire, en lecture seule, quand la vraie base ne
+/// s'ouvre pas. Rien n'est écrit sur le disque ; le mode cesse au redémarrage.
+#[tauri::command]
+async fn start_demo_mode(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    let repo = Repository::demo().await?;
+    *state.source.write().await = DataSource::Demo(repo);
+    Ok(())
+}
+
 #[tauri::command]
 async fn reset_config(state: tauri::State<'_, AppState>) -> Result<(), String> {
     let config_file = config::config_path(&state.app_data_dir);
@@ -288,36 +764,181 @@ async fn test_server_connection(ip: String, port: u16) -> Result<bool, String> {
 // ─── Commandes Member ──────────────────────────────────────────────────────────
 
 #[tauri::command]
-async fn get_members(state: tauri::State<'_, AppState>) -> Result<Vec<Member>, String> {
+async fn get_members(state: tauri::State<'_, AppState>) -> Result<Vec<Member>, AppError> {
     state.source.read().await.get_members().await
 }
 
 #[tauri::command]
 async fn get_members_by_type(
     state: tauri::State<'_, AppState>,
-    member_type: String,
-) -> Result<Vec<Member>, String> {
-    state.source.read().await.get_members_by_type(&member_type).await
+    member_type: MemberType,
+) -> Result<Vec<Member>, AppError> {
+    state.source.read().await.get_members_by_type(member_type).await
+}
+
+#[tauri::command]
+async fn get_member_counts(state: tauri::State<'_, AppState>) -> Result<MemberCounts, AppError> {
+    state.source.read().await.get_member_counts().await
+}
+
+/// Carte « Tsingerin-taona » de l'accueil.
+#[tauri::command]
+async fn get_upcoming_birthdays(
+    state: tauri::State<'_, AppState>,
+    days: u32,
+) -> Result<Vec<UpcomingBirthday>, AppError> {
+    state.source.read().await.get_upcoming_birthdays(days).await
+}
+
+/// Carte « Fanavaozana karatra » de l'accueil (`settings.renewal_window_days`).
+#[tauri::command]
+async fn get_upcoming_renewals(
+    state: tauri::State<'_, AppState>,
+    within_days: u32,
+) -> Result<Vec<RenewalDue>, AppError> {
+    state.source.read().await.get_upcoming_renewals(within_days).await
 }
 
 #[tauri::command]
 async fn get_members_by_type_with_total(
     state: tauri::State<'_, AppState>,
-    member_type: String,
-) -> Result<Vec<MemberWithTotal>, String> {
-    state.source.read().await.get_members_by_type_with_total(&member_type).await
+    member_type: MemberType,
+    status: Option<MemberStatus>,
+) -> Result<Vec<MemberWithTotal>, AppError> {
+    state.source.read().await.get_members_by_type_with_total(member_type, status).await
+}
+
+/// Colonnes « par année » des tableaux : totaux de chaque membre pour `years`.
+#[tauri::command]
+async fn get_members_with_year_totals(
+    state: tauri::State<'_, AppState>,
+    member_type: MemberType,
+    years: Vec<i32>,
+) -> Result<Vec<MemberYearTotals>, AppError> {
+    state.source.read().await.get_members_with_year_totals(member_type, &years).await
 }
 
+/// Membres d'un type regroupés par adresse normalisée (visites à domicile).
 #[tauri::command]
-async fn get_member(state: tauri::State<'_, AppState>, id: i64) -> Result<Member, String> {
+async fn get_members_grouped_by_address(
+    state: tauri::State<'_, AppState>,
+    member_type: MemberType,
+) -> Result<Vec<AddressGroup>, AppError> {
+    state.source.read().await.get_members_grouped_by_address(member_type).await
+}
+
+/// Membres sans cotisation en `year` (filtre « Tsy nandoa » des tableaux).
+#[tauri::command]
+async fn get_members_without_contribution(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+    member_type: Option<String>,
+) -> Result<Vec<Member>, AppError> {
+    state
+        .source
+        .read()
+        .await
+        .get_members_without_contribution(year, member_type.as_deref())
+        .await
+}
+
+/// Liste paginée côté SQL des tableaux Mpandray / Tsy Mpandray.
+#[tauri::command]
+async fn get_member_rows(
+    state: tauri::State<'_, AppState>,
+    member_type: MemberType,
+    page: i64,
+    page_size: i64,
+    sort: MemberSort,
+    filter: MemberFilter,
+) -> Result<Page<MemberRow>, AppError> {
+    state
+        .source
+        .read()
+        .await
+        .get_member_rows(member_type, page, page_size, sort, &filter)
+        .await
+}
+
+#[tauri::command]
+async fn get_member(state: tauri::State<'_, AppState>, id: i64) -> Result<Member, AppError> {
     state.source.read().await.get_member(id).await
 }
 
+/// Sous-totaux annuels des cotisations d'un membre (fiche `/membre/:id`).
+#[tauri::command]
+async fn get_member_year_totals(
+    state: tauri::State<'_, AppState>,
+    member_id: i64,
+) -> Result<Vec<MemberYearTotal>, AppError> {
+    state.source.read().await.get_member_year_totals(member_id).await
+}
+
+/// Ajoute une note libre au membre ; texte vide refusé.
+#[tauri::command]
+async fn add_member_note(
+    state: tauri::State<'_, AppState>,
+    member_id: i64,
+    text: String,
+) -> Result<MemberNote, AppError> {
+    state.source.read().await.add_member_note(member_id, text).await
+}
+
+/// Notes du membre, les plus récentes d'abord.
+#[tauri::command]
+async fn get_member_notes(
+    state: tauri::State<'_, AppState>,
+    member_id: i64,
+) -> Result<Vec<MemberNote>, AppError> {
+    state.source.read().await.get_member_notes(member_id).await
+}
+
+#[tauri::command]
+async fn delete_member_note(state: tauri::State<'_, AppState>, id: i64) -> Result<(), AppError> {
+    state.source.read().await.delete_member_note(id).await
+}
+
+#[tauri::command]
+async fn get_member_by_card(
+    state: tauri::State<'_, AppState>,
+    card_number: String,
+) -> Result<Option<Member>, AppError> {
+    state.source.read().await.get_member_by_card(&card_number).await
+}
+
+/// Recherche globale (barre de navigation) : Communiants et Cathekomens confondus.
+#[tauri::command]
+async fn search_members(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<MemberWithTotal>, AppError> {
+    state.source.read().await.search_members(&query, limit).await
+}
+
+/// Sélecteur de membre (saisie depuis les Archives) : id, nom et carte.
+#[tauri::command]
+async fn search_members_lite(
+    state: tauri::State<'_, AppState>,
+    query: String,
+) -> Result<Vec<MemberLite>, AppError> {
+    state.source.read().await.search_members_lite(&query).await
+}
+
+/// Membres au nom proche de `full_name` : avertissement de doublon à la création.
+#[tauri::command]
+async fn find_similar_members(
+    state: tauri::State<'_, AppState>,
+    full_name: String,
+) -> Result<Vec<Member>, AppError> {
+    state.source.read().await.find_similar_members(&full_name).await
+}
+
 #[tauri::command]
 async fn create_member(
     state: tauri::State<'_, AppState>,
     member: MemberInput,
-) -> Result<Member, String> {
+) -> Result<Member, AppError> {
     state.source.read().await.create_member(member).await
 }
 
@@ -326,13 +947,49 @@ async fn update_member(
     state: tauri::State<'_, AppState>,
     id: i64,
     member: MemberInput,
-) -> Result<Member, String> {
+) -> Result<Member, AppError> {
     state.source.read().await.update_member(id, member).await
 }
 
 #[tauri::command]
-async fn delete_member(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
-    state.source.read().await.delete_member(id).await
+async fn delete_member(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    pin_token: Option<String>,
+) -> Result<(), AppError> {
+    state.source.read().await.delete_member(id, pin_token.as_deref()).await
+}
+
+/// Corbeille : membres supprimés, restaurables ou purgeables.
+#[tauri::command]
+async fn get_deleted_members(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<DeletedMember>, AppError> {
+    state.source.read().await.get_deleted_members().await
+}
+
+#[tauri::command]
+async fn restore_member(state: tauri::State<'_, AppState>, id: i64) -> Result<Member, AppError> {
+    state.source.read().await.restore_member(id).await
+}
+
+/// Effacement définitif (membre et cotisations) — protégé par le PIN.
+#[tauri::command]
+async fn purge_member(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    pin_token: Option<String>,
+) -> Result<(), AppError> {
+    state.source.read().await.purge_member(id, pin_token.as_deref()).await
+}
+
+/// Numéro de carte proposé à la création d'un membre (bouton « Hamorona »).
+#[tauri::command]
+async fn next_card_number(
+    state: tauri::State<'_, AppState>,
+    member_type: MemberType,
+) -> Result<String, AppError> {
+    state.source.read().await.next_card_number(member_type).await
 }
 
 // ─── Commandes Contribution ────────────────────────────────────────────────────
@@ -341,35 +998,83 @@ async fn delete_member(state: tauri::State<'_, AppState>, id: i64) -> Result<(),
 async fn get_contributions(
     state: tauri::State<'_, AppState>,
     member_id: i64,
-) -> Result<Vec<Contribution>, String> {
+) -> Result<Vec<Contribution>, AppError> {
     state.source.read().await.get_contributions(member_id).await
 }
 
+#[tauri::command]
+async fn get_recent_contributions(
+    state: tauri::State<'_, AppState>,
+    member_id: i64,
+    limit: i64,
+) -> Result<Vec<Contribution>, AppError> {
+    state.source.read().await.get_recent_contributions(member_id, limit).await
+}
+
 #[tauri::command]
 async fn get_contributions_by_year(
     state: tauri::State<'_, AppState>,
     year: i32,
-) -> Result<Vec<Contribution>, String> {
+) -> Result<Vec<Contribution>, AppError> {
     state.source.read().await.get_contributions_by_year(year).await
 }
 
+#[tauri::command]
+async fn get_distinct_periods(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<Vec<String>, AppError> {
+    state.source.read().await.get_distinct_periods(year).await
+}
+
 #[tauri::command]
 async fn create_contribution(
     state: tauri::State<'_, AppState>,
     contribution: ContributionInput,
-) -> Result<Contribution, String> {
+) -> Result<Contribution, AppError> {
     state.source.read().await.create_contribution(contribution).await
 }
 
+/// Saisie en série : tout ou rien, erreur préfixée du numéro de ligne.
+#[tauri::command]
+async fn create_contributions_batch(
+    state: tauri::State<'_, AppState>,
+    contributions: Vec<ContributionInput>,
+) -> Result<Vec<Contribution>, AppError> {
+    state.source.read().await.create_contributions_batch(contributions).await
+}
+
 #[tauri::command]
-async fn delete_contribution(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
-    state.source.read().await.delete_contribution(id).await
+async fn delete_contribution(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    pin_token: Option<String>,
+) -> Result<(), AppError> {
+    state.source.read().await.delete_contribution(id, pin_token.as_deref()).await
+}
+
+/// Annulation d'une suppression (toast « Averina »).
+#[tauri::command]
+async fn restore_contribution(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<Contribution, AppError> {
+    state.source.read().await.restore_contribution(id).await
+}
+
+/// Retrouve une cotisation (et son membre) à partir du numéro d'un reçu papier.
+#[tauri::command]
+async fn get_contribution_by_receipt(
+    state: tauri::State<'_, AppState>,
+    receipt: String,
+) -> Result<ContributionWithMember, AppError> {
+    state.source.read().await.get_contribution_by_receipt(&receipt).await
 }
 
 // ─── Commandes YearSummary ────────────────────────────────────────────────────
 
 #[tauri::command]
-async fn get_year_summaries(state: tauri::State<'_, AppState>) -> Result<Vec<YearSummary>, String> {
+async fn get_year_summaries(state: tauri::State<'_, AppState>) -> Result<Vec<YearSummary>, AppError> {
     state.source.read().await.get_year_summaries().await
 }
 
@@ -377,63 +1082,258 @@ async fn get_year_summaries(state: tauri::State<'_, AppState>) -> Result<Vec<Yea
 async fn get_year_summary(
     state: tauri::State<'_, AppState>,
     year: i32,
-) -> Result<Option<YearSummary>, String> {
+) -> Result<Option<YearSummary>, AppError> {
     state.source.read().await.get_year_summary(year).await
 }
 
+/// Années qui ont des données (onglets des Archives).
+#[tauri::command]
+async fn get_years_with_contributions(state: tauri::State<'_, AppState>) -> Result<Vec<i32>, AppError> {
+    state.source.read().await.get_years_with_contributions().await
+}
+
+/// Efface le résumé d'une année restée vide (« Afeno » dans les Archives).
+#[tauri::command]
+async fn delete_empty_year_summary(state: tauri::State<'_, AppState>, year: i32) -> Result<(), AppError> {
+    state.source.read().await.delete_empty_year_summary(year).await
+}
+
 #[tauri::command]
 async fn close_year(
     state: tauri::State<'_, AppState>,
     year: i32,
     note: Option<String>,
-) -> Result<YearSummary, String> {
+) -> Result<YearSummary, AppError> {
     state.source.read().await.close_year(year, note).await
 }
 
+/// Réouvre `year` ; `cascade` rouvre aussi les années suivantes encore clôturées.
 #[tauri::command]
 async fn reopen_year(
     state: tauri::State<'_, AppState>,
     year: i32,
-) -> Result<YearSummary, String> {
-    state.source.read().await.reopen_year(year).await
+    cascade: bool,
+    pin_token: Option<String>,
+) -> Result<Vec<YearSummary>, AppError> {
+    state.source.read().await.reopen_year(year, cascade, pin_token.as_deref()).await
+}
+
+/// Note d'une année clôturée, modifiable sans la rouvrir.
+#[tauri::command]
+async fn update_year_note(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+    note: Option<String>,
+    pin_token: Option<String>,
+) -> Result<YearSummary, AppError> {
+    state.source.read().await.update_year_note(year, note, pin_token.as_deref()).await
 }
 
 #[tauri::command]
 async fn transfer_members(
     state: tauri::State<'_, AppState>,
     ids: Vec<i64>,
-    new_type: String,
-) -> Result<usize, String> {
-    state.source.read().await.transfer_members(&ids, &new_type).await
+    new_type: MemberType,
+) -> Result<Vec<Member>, AppError> {
+    state.source.read().await.transfer_members(&ids, new_type).await
+}
+
+/// Fusionne le doublon `remove_id` dans `keep_id` (cotisations réattribuées, doublon effacé).
+#[tauri::command]
+async fn merge_members(
+    state: tauri::State<'_, AppState>,
+    keep_id: i64,
+    remove_id: i64,
+    pin_token: Option<String>,
+) -> Result<Member, AppError> {
+    state.source.read().await.merge_members(keep_id, remove_id, pin_token.as_deref()).await
 }
 
 // ─── Commandes Archives ────────────────────────────────────────────────────────
 
+/// Page de cotisations des Archives ; `year` absent = recherche sur toutes les années,
+/// limitée aux champs de `scope`.
 #[tauri::command]
 async fn get_contributions_by_year_with_member(
     state: tauri::State<'_, AppState>,
-    year: i32,
-) -> Result<Vec<ContributionWithMember>, String> {
+    year: Option<i32>,
+    query: Option<String>,
+    scope: Option<ContributionSearchScope>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<Page<ContributionWithMember>, AppError> {
     state
         .source
         .read()
         .await
-        .get_contributions_by_year_with_member(year)
+        .get_contributions_by_year_with_member(
+            year,
+            query.as_deref(),
+            scope.unwrap_or_default(),
+            limit,
+            offset,
+        )
         .await
 }
 
 #[tauri::command]
-async fn get_all_contributions_with_member(
+async fn check_and_close_previous_year(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<ContributionWithMember>, String> {
-    state.source.read().await.get_all_contributions_with_member().await
+    grace_days: Option<u32>,
+) -> Result<YearCloseStatus, AppError> {
+    state
+        .source
+        .read()
+        .await
+        .check_and_close_previous_year(grace_days)
+        .await
 }
 
+/// Aperçu de la clôture de `year` (total, note proposée), sans rien écrire.
 #[tauri::command]
-async fn check_and_close_previous_year(
+async fn preview_year_close(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<YearClosePreview, AppError> {
+    state.source.read().await.preview_year_close(year).await
+}
+
+#[tauri::command]
+async fn get_year_comparison(
+    state: tauri::State<'_, AppState>,
+    years: Vec<i32>,
+) -> Result<Vec<YearComparison>, AppError> {
+    state.source.read().await.get_year_comparison(years).await
+}
+
+#[tauri::command]
+async fn get_monthly_totals(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<Vec<MonthlyTotal>, AppError> {
+    state.source.read().await.get_monthly_totals(year).await
+}
+
+/// Totaux par membre figés à la clôture de `year` (vide si l'année est ouverte).
+#[tauri::command]
+async fn get_year_member_totals(
     state: tauri::State<'_, AppState>,
-) -> Result<Option<YearSummary>, String> {
-    state.source.read().await.check_and_close_previous_year().await
+    year: i32,
+) -> Result<Vec<YearMemberSnapshot>, AppError> {
+    state.source.read().await.get_year_member_totals(year).await
+}
+
+/// Totaux par semaine ISO (graphique de l'accueil), semaines vides à zéro.
+#[tauri::command]
+async fn get_weekly_totals(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<Vec<WeekTotal>, AppError> {
+    state.source.read().await.get_weekly_totals(year).await
+}
+
+/// Comparatif mois par mois de deux années, avec l'écart cumulé.
+#[tauri::command]
+async fn compare_years(
+    state: tauri::State<'_, AppState>,
+    year_a: i32,
+    year_b: i32,
+) -> Result<MonthlyComparison, AppError> {
+    state.source.read().await.compare_years(year_a, year_b).await
+}
+
+/// Cotisations d'une année réparties par genre et par type de membre.
+#[tauri::command]
+async fn get_year_breakdown(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<YearBreakdown, AppError> {
+    state.source.read().await.get_year_breakdown(year).await
+}
+
+// ─── Commandes Statistiques ────────────────────────────────────────────────────
+
+#[tauri::command]
+async fn get_statistics(state: tauri::State<'_, AppState>) -> Result<Statistics, AppError> {
+    state.source.read().await.get_statistics().await
+}
+
+#[tauri::command]
+async fn get_dashboard_stats(state: tauri::State<'_, AppState>) -> Result<DashboardStats, AppError> {
+    state.source.read().await.get_dashboard_stats().await
+}
+
+// ─── Commandes Paramètres ──────────────────────────────────────────────────────
+
+#[tauri::command]
+async fn get_settings(state: tauri::State<'_, AppState>) -> Result<Settings, AppError> {
+    state.source.read().await.get_settings().await
+}
+
+#[tauri::command]
+async fn update_settings(
+    state: tauri::State<'_, AppState>,
+    settings: Settings,
+) -> Result<Settings, AppError> {
+    state.source.read().await.update_settings(settings).await
+}
+
+/// Langue enregistrée ; appliquée aussi aux messages produits par ce processus
+/// (en mode client, le serveur traduit les siens).
+#[tauri::command]
+async fn get_language(state: tauri::State<'_, AppState>) -> Result<Lang, AppError> {
+    let lang = state.source.read().await.get_language().await?;
+    i18n::set_current(lang);
+    Ok(lang)
+}
+
+#[tauri::command]
+async fn set_language(state: tauri::State<'_, AppState>, lang: Lang) -> Result<Lang, AppError> {
+    let lang = state.source.read().await.set_language(lang).await?;
+    i18n::set_current(lang);
+    Ok(lang)
+}
+
+// ─── Commandes Intégrité ───────────────────────────────────────────────────────
+
+#[tauri::command]
+async fn run_integrity_check(state: tauri::State<'_, AppState>) -> Result<IntegrityReport, AppError> {
+    state.source.read().await.run_integrity_check().await
+}
+
+#[tauri::command]
+async fn repair_integrity(state: tauri::State<'_, AppState>) -> Result<IntegrityReport, AppError> {
+    state.source.read().await.repair_integrity().await
+}
+
+/// Version du schéma, lignes par table et taille (« À propos de la base »).
+#[tauri::command]
+async fn get_db_health(state: tauri::State<'_, AppState>) -> Result<DbHealth, AppError> {
+    state.source.read().await.health().await
+}
+
+/// Rappels de la cloche de la Navbar (année à clôturer, fiches incomplètes…).
+#[tauri::command]
+async fn get_pending_tasks(state: tauri::State<'_, AppState>) -> Result<Vec<PendingTask>, AppError> {
+    state.source.read().await.get_pending_tasks().await
+}
+
+/// Réécrit les téléphones existants au format "+261 3X XX XXX XX".
+#[tauri::command]
+async fn normalize_all_phones(state: tauri::State<'_, AppState>) -> Result<PhoneFixReport, AppError> {
+    state.source.read().await.normalize_all_phones().await
+}
+
+// ─── Commandes Journal d'audit ─────────────────────────────────────────────────
+
+#[tauri::command]
+async fn get_audit_log(
+    state: tauri::State<'_, AppState>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    entity: Option<String>,
+) -> Result<Page<AuditEntry>, AppError> {
+    state.source.read().await.get_audit_log(limit, offset, entity.as_deref()).await
 }
 
 // ─── Commandes Import / Export ─────────────────────────────────────────────────
@@ -441,42 +1341,156 @@ async fn check_and_close_previous_year(
 #[tauri::command]
 async fn export_members_csv(
     state: tauri::State<'_, AppState>,
-    member_type: String,
-) -> Result<String, String> {
-    state.source.read().await.export_members_csv(&member_type).await
+    member_type: MemberType,
+) -> Result<String, AppError> {
+    state.source.read().await.export_members_csv(member_type).await
 }
 
 #[tauri::command]
 async fn export_members_excel(
     state: tauri::State<'_, AppState>,
-    member_type: String,
-) -> Result<Vec<u8>, String> {
-    state.source.read().await.export_members_excel(&member_type).await
+    member_type: MemberType,
+) -> Result<Vec<u8>, AppError> {
+    state.source.read().await.export_members_excel(member_type).await
+}
+
+/// Rapport d'archive imprimable d'une année (HTML autonome).
+#[tauri::command]
+async fn export_year_report(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<String, AppError> {
+    state.source.read().await.export_year_report(year).await
+}
+
+/// Reçu imprimable d'une cotisation (HTML autonome).
+#[tauri::command]
+async fn export_receipt(state: tauri::State<'_, AppState>, receipt: String) -> Result<String, AppError> {
+    state.source.read().await.export_receipt(&receipt).await
+}
+
+/// Rapport d'archive d'une année en PDF (octets, enregistrés par le webview).
+#[tauri::command]
+async fn export_year_report_pdf(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<Vec<u8>, AppError> {
+    state.source.read().await.export_year_report_pdf(year).await
+}
+
+/// Cotisations d'une année au format CSV (tableur du comptable).
+#[tauri::command]
+async fn export_contributions_csv(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+) -> Result<String, AppError> {
+    state.source.read().await.export_contributions_csv(year).await
+}
+
+/// Relevé annuel d'un membre au format CSV, même sans cotisation (total à 0).
+#[tauri::command]
+async fn export_member_statement_csv(
+    state: tauri::State<'_, AppState>,
+    member_id: i64,
+    year: i32,
+) -> Result<String, AppError> {
+    state.source.read().await.export_member_statement_csv(member_id, year).await
 }
 
 #[tauri::command]
 async fn import_members_csv(
     state: tauri::State<'_, AppState>,
     csv_content: String,
-    member_type: String,
-) -> Result<usize, String> {
+    member_type: MemberType,
+) -> Result<ImportReport, AppError> {
     state
         .source
         .read()
         .await
-        .import_members_csv(csv_content, &member_type)
+        .import_members_csv(csv_content, member_type)
         .await
 }
 
+/// Écrit sur ce poste un CSV préparé par l'interface (lignes affichées telles
+/// quelles). Un chemin relatif part du dossier Téléchargements, à défaut de
+/// `app_data_dir/exports`. Retourne le chemin complet du fichier écrit.
+#[tauri::command]
+async fn write_csv(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    dest_path: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    overwrite: bool,
+) -> Result<String, AppError> {
+    let mut dest = PathBuf::from(dest_path.trim());
+    if dest.is_relative() {
+        let base = app
+            .path()
+            .download_dir()
+            .unwrap_or_else(|_| state.app_data_dir.join("exports"));
+        dest = base.join(dest);
+    }
+    write_csv_file(&dest, &headers, &rows, overwrite)?;
+    Ok(dest.to_string_lossy().into_owned())
+}
+
+/// Ouvre le gestionnaire de fichiers du système sur `path` : fichier sélectionné
+/// sous Windows et macOS, dossier parent ailleurs.
+#[tauri::command]
+async fn reveal_in_file_manager(path: String) -> Result<(), AppError> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = std::process::Command::new("explorer");
+        c.arg(format!("/select,{}", path.display()));
+        c
+    } else if cfg!(target_os = "macos") {
+        let mut c = std::process::Command::new("open");
+        c.arg("-R").arg(&path);
+        c
+    } else {
+        let dir = if path.is_dir() { path.as_path() } else { path.parent().unwrap_or(&path) };
+        let mut c = std::process::Command::new("xdg-open");
+        c.arg(dir);
+        c
+    };
+    cmd.spawn().map(drop).map_err(|e| AppError::Internal(e.to_string()))
+}
+
 // ─── Commandes PIN + édition contribution ─────────────────────────────────────
 
 #[tauri::command]
-async fn set_pin(state: tauri::State<'_, AppState>, pin: String) -> Result<(), String> {
+async fn set_pin(state: tauri::State<'_, AppState>, pin: String) -> Result<(), AppError> {
     state.source.read().await.set_pin(pin).await
 }
 
+/// Remplace le PIN ; l'ancien est exigé.
 #[tauri::command]
-async fn verify_pin(state: tauri::State<'_, AppState>, pin: String) -> Result<bool, String> {
+async fn change_pin(
+    state: tauri::State<'_, AppState>,
+    old_pin: String,
+    new_pin: String,
+) -> Result<(), AppError> {
+    state.source.read().await.change_pin(old_pin, new_pin).await
+}
+
+/// Supprime le PIN ; l'ancien est exigé.
+#[tauri::command]
+async fn remove_pin(state: tauri::State<'_, AppState>, old_pin: String) -> Result<(), AppError> {
+    state.source.read().await.remove_pin(old_pin).await
+}
+
+#[tauri::command]
+async fn has_pin(state: tauri::State<'_, AppState>) -> Result<bool, AppError> {
+    state.source.read().await.has_pin().await
+}
+
+/// Jeton court exigé par les commandes destructrices (`pin_token`).
+#[tauri::command]
+async fn verify_pin(state: tauri::State<'_, AppState>, pin: String) -> Result<String, AppError> {
     state.source.read().await.verify_pin(pin).await
 }
 
@@ -485,10 +1499,112 @@ async fn update_contribution(
     state: tauri::State<'_, AppState>,
     id: i64,
     input: ContributionEditInput,
-) -> Result<Contribution, String> {
+) -> Result<Contribution, AppError> {
     state.source.read().await.update_contribution(id, input).await
 }
 
+// ─── Commandes Sauvegardes / Maintenance ──────────────────────────────────────
+
+#[tauri::command]
+async fn list_backups(state: tauri::State<'_, AppState>) -> Result<Vec<BackupInfo>, AppError> {
+    backup::list_backups(&backup::backups_dir(&state.app_data_dir)).map_err(AppError::Internal)
+}
+
+#[tauri::command]
+async fn backup_now(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    let repo = state.source.read().await.local_repo()?.clone();
+    backup::backup_now(&repo, &state.app_data_dir).await.map_err(AppError::Internal)
+}
+
+#[tauri::command]
+async fn restore_backup(
+    state: tauri::State<'_, AppState>,
+    file_name: String,
+    pin_token: Option<String>,
+) -> Result<(), AppError> {
+    let repo = state.source.read().await.local_repo()?.clone();
+    repo.require_pin_token(pin_token.as_deref()).await?;
+    backup::restore_backup(&repo, &state.app_data_dir, &file_name)
+        .await
+        .map_err(AppError::Internal)
+}
+
+/// Base complète en JSON portable, pour passer d'un ordinateur à l'autre.
+#[tauri::command]
+async fn export_archive(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    let repo = state.source.read().await.local_repo()?.clone();
+    repo.export_archive().await
+}
+
+/// Importe une archive JSON après une sauvegarde de l'état courant (PIN exigé).
+#[tauri::command]
+async fn import_archive(
+    state: tauri::State<'_, AppState>,
+    json: String,
+    mode: ImportMode,
+    pin_token: Option<String>,
+) -> Result<ArchiveImportReport, AppError> {
+    let repo = state.source.read().await.local_repo()?.clone();
+    repo.require_pin_token(pin_token.as_deref()).await?;
+    backup::backup_now(&repo, &state.app_data_dir)
+        .await
+        .map_err(|e| AppError::Internal(format!("Import annulé, sauvegarde préalable impossible : {e}")))?;
+    let result = repo.import_archive(&json, mode).await;
+    let (level, message) = match &result {
+        Ok(r) => ("info", format!(
+            "Archive importée ({mode:?}) : {} membre(s), {} cotisation(s)",
+            r.members_inserted, r.contributions_inserted,
+        )),
+        Err(e) => ("error", format!("Échec de l'import d'archive : {e}")),
+    };
+    let _ = repo.log_event(level, "archive", &message).await;
+    result
+}
+
+/// Données fictives pour une démonstration (raccourci caché de l'interface).
+/// Base locale uniquement ; refusé si elle contient déjà des données.
+#[tauri::command]
+async fn seed_demo_data(
+    state: tauri::State<'_, AppState>,
+    member_count: usize,
+    years: Vec<i32>,
+    seed: u64,
+) -> Result<SeedReport, AppError> {
+    let repo = state.source.read().await.local_repo()?.clone();
+    let report = repo.seed_demo_data(member_count, years, seed).await?;
+    let _ = repo
+        .log_event("info", "seed", &format!(
+            "Données de démonstration : {} membre(s), {} cotisation(s), graine {seed}",
+            report.members_inserted, report.contributions_inserted,
+        ))
+        .await;
+    Ok(report)
+}
+
+#[tauri::command]
+async fn get_backup_retention(state: tauri::State<'_, AppState>) -> Result<usize, AppError> {
+    let source = state.source.read().await;
+    source.local_repo()?.get_backup_retention().await
+}
+
+#[tauri::command]
+async fn set_backup_retention(
+    state: tauri::State<'_, AppState>,
+    keep: usize,
+) -> Result<(), AppError> {
+    let source = state.source.read().await;
+    source.local_repo()?.set_backup_retention(keep).await
+}
+
+#[tauri::command]
+async fn get_app_logs(
+    state: tauri::State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<AppLog>, AppError> {
+    let source = state.source.read().await;
+    source.local_repo()?.get_app_logs(limit.unwrap_or(50)).await
+}
+
 // ─── Commandes fenêtre ─────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -499,16 +1615,27 @@ async fn minimize_window(app: tauri::AppHandle) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Bascule maximisé / restauré et retourne le nouvel état réel de la fenêtre.
 #[tauri::command]
-async fn toggle_maximize(app: tauri::AppHandle) -> Result<(), String> {
+async fn toggle_maximize(app: tauri::AppHandle) -> Result<bool, String> {
     let win = app
         .get_webview_window("main")
         .ok_or_else(|| "Fenêtre introuvable".to_string())?;
     if win.is_maximized().map_err(|e| e.to_string())? {
-        win.unmaximize().map_err(|e| e.to_string())
+        win.unmaximize().map_err(|e| e.to_string())?;
     } else {
-        win.maximize().map_err(|e| e.to_string())
+        win.maximize().map_err(|e| e.to_string())?;
     }
+    win.is_maximized().map_err(|e| e.to_string())
+}
+
+/// État maximisé courant — lu par la barre de titre au démarrage.
+#[tauri::command]
+async fn is_window_maximized(app: tauri::AppHandle) -> Result<bool, String> {
+    app.get_webview_window("main")
+        .ok_or_else(|| "Fenêtre introuvable".to_string())?
+        .is_maximized()
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -521,35 +1648,66 @@ async fn close_window(app: tauri::AppHandle) -> Result<(), String> {
 
 // ─── Point d'entrée ────────────────────────────────────────────────────────────
 
+/// Dossier de données de Tauri, ou à défaut celui que calcule la CLI. Un dossier
+/// introuvable ou impossible à créer n'arrête pas l'application : l'erreur est
+/// renvoyée pour être affichée, le chemin retenu servant encore à lire une
+/// éventuelle configuration.
+fn resolve_app_data_dir(app: &tauri::App) -> (PathBuf, Option<AppError>) {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .or_else(config::default_app_data_dir)
+        .unwrap_or_else(|| std::env::temp_dir().join(config::APP_IDENTIFIER));
+    match std::fs::create_dir_all(&dir) {
+        Ok(()) => (dir, None),
+        Err(e) => {
+            eprintln!("[Setup] Dossier de données {} : {e}", dir.display());
+            let error = AppError::Io(format!("{} : {e}", dir.display()));
+            (dir, Some(error))
+        }
+    }
+}
+
 pub fn run() {
+    // Options de maintenance (`--stats`, `--backup`…) : exécutées sans fenêtre
+    if let Some(code) = cli::run_from_args(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
         .setup(|app| {
-            let app_dir = app
-                .path()
-                .app_data_dir()
-                .expect("Impossible d'obtenir app_data_dir");
-            std::fs::create_dir_all(&app_dir).expect("Impossible de créer app_data_dir");
-
+            let (app_dir, dir_error) = resolve_app_data_dir(app);
             let config = load_config(&app_dir);
 
-            let source = match config {
-                None => DataSource::Unconfigured,
-                Some(cfg) => {
-                    let rt = tokio::runtime::Runtime::new()
-                        .expect("Impossible de créer le runtime Tokio");
-                    match rt.block_on(init_source(&app_dir, &cfg)) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            eprintln!("[Setup] Erreur init source: {e} — démarrage sans config");
-                            DataSource::Unconfigured
-                        }
+            // La fenêtre s'affiche tout de suite : ouverture de la base et migrations
+            // tournent en tâche de fond, le frontend attend via `is_ready`. Sans dossier
+            // de données, l'échec s'affiche d'emblée (réessayer, autre dossier, démo).
+            let source = Arc::new(RwLock::new(match (dir_error, &config) {
+                (Some(e), None) => DataSource::Failed(e),
+                (_, None)       => DataSource::Unconfigured,
+                (_, Some(_))    => DataSource::Initializing,
+            }));
+            if let Some(cfg) = config {
+                let (dir, source) = (app_dir.clone(), source.clone());
+                tauri::async_runtime::spawn(async move {
+                    let ready = init_source(&dir, &cfg).await.unwrap_or_else(|e| {
+                        eprintln!("[Setup] Erreur init source: {e}");
+                        DataSource::Failed(e)
+                    });
+                    // Une reconfiguration faite entre-temps (save_config / reset_config) prime
+                    let mut current = source.write().await;
+                    if matches!(*current, DataSource::Initializing) {
+                        *current = ready;
                     }
-                }
-            };
+                });
+            }
+
+            tauri::async_runtime::spawn(backup::run_daily_backups(app_dir.clone(), source.clone()));
 
             app.manage(AppState {
                 app_data_dir: app_dir,
-                source: Arc::new(RwLock::new(source)),
+                source,
             });
 
             Ok(())
@@ -557,45 +1715,117 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Config
             get_config,
+            is_ready,
             save_config,
             reset_config,
+            use_data_dir,
+            start_demo_mode,
             test_server_connection,
             start_mock_server,
             // Member
             get_members,
             get_members_by_type,
+            get_member_counts,
+            get_upcoming_birthdays,
+            get_upcoming_renewals,
             get_members_by_type_with_total,
+            get_members_with_year_totals,
+            get_members_grouped_by_address,
+            get_members_without_contribution,
+            get_member_rows,
             get_member,
+            get_member_year_totals,
+            add_member_note,
+            get_member_notes,
+            delete_member_note,
+            get_member_by_card,
+            search_members,
+            search_members_lite,
+            find_similar_members,
             create_member,
             update_member,
             delete_member,
+            get_deleted_members,
+            restore_member,
+            purge_member,
+            next_card_number,
             // Contribution
             get_contributions,
+            get_recent_contributions,
             get_contributions_by_year,
+            get_distinct_periods,
             create_contribution,
+            create_contributions_batch,
             delete_contribution,
+            restore_contribution,
+            get_contribution_by_receipt,
             // YearSummary
             get_year_summaries,
             get_year_summary,
+            get_years_with_contributions,
+            delete_empty_year_summary,
             close_year,
             reopen_year,
+            update_year_note,
             // Transfer
             transfer_members,
+            merge_members,
             // Archives
             get_contributions_by_year_with_member,
-            get_all_contributions_with_member,
             check_and_close_previous_year,
+            preview_year_close,
+            get_year_comparison,
+            get_monthly_totals,
+            get_year_member_totals,
+            get_weekly_totals,
+            compare_years,
+            get_year_breakdown,
+            // Statistiques
+            get_statistics,
+            get_dashboard_stats,
+            // Paramètres
+            get_settings,
+            update_settings,
+            get_language,
+            set_language,
+            run_integrity_check,
+            repair_integrity,
+            get_db_health,
+            get_pending_tasks,
+            normalize_all_phones,
+            get_audit_log,
             // Import / Export
             export_members_csv,
             export_members_excel,
+            export_year_report,
+            export_receipt,
+            export_year_report_pdf,
+            export_contributions_csv,
+            export_member_statement_csv,
+            write_csv,
+            reveal_in_file_manager,
             import_members_csv,
             // PIN + édition contribution
             set_pin,
+            change_pin,
+            remove_pin,
+            has_pin,
             verify_pin,
             update_contribution,
+            // Sauvegardes / Maintenance
+            list_backups,
+            backup_now,
+            restore_backup,
+            export_archive,
+            import_archive,
+            seed_demo_data,
+            get_backup_retention,
+            set_backup_retention,
+            get_app_logs,
             // Fenêtre
             minimize_window,
             toggle_maximize,
+            is_window_maximized,
             close_window,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,232 @@
+/// Écriture PDF minimale pour les rapports : pages A4, polices standard Helvetica
+/// (non incorporées, présentes dans tout lecteur PDF), texte encodé en WinAnsi.
+///
+/// Pas de mise en page automatique : l'appelant place chaque texte et gère les
+/// sauts de page (voir `export::build_year_report_pdf`).
+use std::fmt::Write;
+
+/// Largeur d'une page A4, en points.
+pub const PAGE_WIDTH: f32 = 595.0;
+/// Hauteur d'une page A4, en points.
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFont {
+    Regular,
+    Bold,
+}
+
+impl PdfFont {
+    fn resource(self) -> &'static str {
+        match self {
+            PdfFont::Regular => "F1",
+            PdfFont::Bold    => "F2",
+        }
+    }
+}
+
+// ─── Métriques Helvetica ──────────────────────────────────────────────────────
+
+/// Chasse des caractères ASCII 32..=126 (millièmes de corps), Helvetica.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Idem, Helvetica-Bold.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Caractère → octet WinAnsi ; `?` si le caractère n'existe pas dans l'encodage.
+fn winansi_byte(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{202f}' | '\u{a0}' => 0xA0,
+        '\u{a1}'..='\u{ff}' => c as u32 as u8,
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        _ => b'?',
+    }
+}
+
+/// Largeur de `text` en points ; les lettres accentuées prennent la chasse moyenne.
+pub fn text_width(text: &str, size: f32, font: PdfFont) -> f32 {
+    let widths = match font {
+        PdfFont::Regular => &HELVETICA_WIDTHS,
+        PdfFont::Bold    => &HELVETICA_BOLD_WIDTHS,
+    };
+    let units: u32 = text
+        .chars()
+        .map(|c| match winansi_byte(c) {
+            b @ 32..=126 => u32::from(widths[usize::from(b - 32)]),
+            0xA0 => 278,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Tronque `text` (avec "…") pour qu'il tienne dans `max_width`.
+pub fn fit_text(text: &str, max_width: f32, size: f32, font: PdfFont) -> String {
+    if text_width(text, size, font) <= max_width {
+        return text.to_string();
+    }
+    let mut out: String = text.to_string();
+    while !out.is_empty() && text_width(&format!("{out}…"), size, font) > max_width {
+        out.pop();
+    }
+    format!("{}…", out.trim_end())
+}
+
+/// Découpe `text` en lignes de `max_width` au plus, aux espaces.
+pub fn wrap_text(text: &str, max_width: f32, size: f32, font: PdfFont) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{line} {word}") };
+            if !line.is_empty() && text_width(&candidate, size, font) > max_width {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            } else {
+                line = candidate;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// ─── Document ─────────────────────────────────────────────────────────────────
+
+/// Document en cours de construction : un flux de contenu par page.
+#[derive(Debug, Default)]
+pub struct PdfDocument {
+    pages: Vec<Vec<u8>>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute une page vierge et retourne son index.
+    pub fn add_page(&mut self) -> usize {
+        self.pages.push(Vec::new());
+        self.pages.len() - 1
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Texte sur une ligne, `(x, y)` = début de la ligne de base.
+    pub fn text(&mut self, page: usize, x: f32, y: f32, size: f32, font: PdfFont, text: &str) {
+        let content = &mut self.pages[page];
+        content.extend_from_slice(
+            format!("BT /{} {size:.1} Tf {x:.2} {y:.2} Td (", font.resource()).as_bytes(),
+        );
+        for c in text.chars() {
+            let b = winansi_byte(c);
+            if matches!(b, b'(' | b')' | b'\\') {
+                content.push(b'\\');
+            }
+            content.push(b);
+        }
+        content.extend_from_slice(b") Tj ET\n");
+    }
+
+    /// Texte aligné à droite sur `right`.
+    pub fn text_right(&mut self, page: usize, right: f32, y: f32, size: f32, font: PdfFont, text: &str) {
+        let x = right - text_width(text, size, font);
+        self.text(page, x, y, size, font, text);
+    }
+
+    /// Trait de `(x1, y1)` à `(x2, y2)`.
+    pub fn line(&mut self, page: usize, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        let mut op = String::new();
+        let _ = writeln!(op, "{width:.2} w {x1:.2} {y1:.2} m {x2:.2} {y2:.2} l S");
+        self.pages[page].extend_from_slice(op.as_bytes());
+    }
+
+    /// Rectangle plein en niveau de gris (0 = noir, 1 = blanc).
+    pub fn fill_rect(&mut self, page: usize, x: f32, y: f32, w: f32, h: f32, gray: f32) {
+        let mut op = String::new();
+        let _ = writeln!(op, "{gray:.2} g {x:.2} {y:.2} {w:.2} {h:.2} re f 0 g");
+        self.pages[page].extend_from_slice(op.as_bytes());
+    }
+
+    /// Fichier PDF complet (catalogue, polices, pages, table xref).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objets : 1 catalogue, 2 arbre des pages, 3-4 polices, puis (page, contenu) par page
+        let page_obj = |i: usize| 5 + 2 * i;
+        let mut objects: Vec<Vec<u8>> = Vec::with_capacity(4 + 2 * self.pages.len());
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids = (0..self.pages.len())
+            .map(|i| format!("{} 0 R", page_obj(i)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        objects.push(
+            format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", self.pages.len()).into_bytes(),
+        );
+        for base in ["Helvetica", "Helvetica-Bold"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{base} /Encoding /WinAnsiEncoding >>"
+                )
+                .into_bytes(),
+            );
+        }
+        for (i, content) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page_obj(i) + 1,
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, obj) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(obj);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_at = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_at}\n%%EOF\n",
+            objects.len() + 1,
+        );
+        out.extend_from_slice(xref.as_bytes());
+        out
+    }
+}
@@ -3,10 +3,17 @@ use reqwest::{Client, Response};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::api_server::PIN_TOKEN_HEADER;
 use crate::db::{
-    AppError, Contribution, ContributionEditInput, ContributionInput, ContributionWithMember,
-    Member, MemberInput, MemberWithTotal, YearSummary,
+    AddressGroup, AppError, AuditEntry, Contribution, ContributionEditInput, ContributionInput,
+    ContributionSearchScope, ContributionWithMember,
+    DashboardStats, DbHealth, DeletedMember, ImportReport, IntegrityReport, Member, MemberCounts,
+    MemberFilter, MemberInput, MemberLite, MemberNote, MemberRow, MemberSort, MemberStatus, MemberType, MemberWithTotal,
+    MemberYearTotal, MemberYearTotals, MonthlyComparison, MonthlyTotal, Page, PendingTask, PhoneFixReport, RenewalDue, Settings, Statistics, UpcomingBirthday,
+    WeekTotal, YearBreakdown, YearComparison, YearClosePreview, YearCloseStatus, YearMemberSnapshot,
+    YearSummary,
 };
+use crate::i18n::Lang;
 
 pub struct RemoteClient {
     pub base_url: String,
@@ -29,7 +36,9 @@ impl RemoteClient {
             Ok(resp)
         } else {
             let msg = resp.text().await.unwrap_or_else(|_| "Erreur inconnue".to_string());
-            Err(AppError::Network(format!("Erreur serveur : {msg}")))
+            // Erreur structurée du serveur : on garde son `kind` (validation, conflit…)
+            Err(serde_json::from_str::<AppError>(&msg)
+                .unwrap_or_else(|_| AppError::Network(format!("Erreur serveur : {msg}"))))
         }
     }
 
@@ -88,10 +97,13 @@ impl RemoteClient {
             .map_err(|e| AppError::Network(format!("Réponse invalide : {e}")))
     }
 
-    async fn delete_req(&self, path: &str) -> Result<(), AppError> {
-        let resp = self
-            .client
-            .delete(self.url(path))
+    /// DELETE d'une action protégée : le jeton PIN voyage dans `PIN_TOKEN_HEADER`.
+    async fn delete_req(&self, path: &str, pin_token: Option<&str>) -> Result<(), AppError> {
+        let mut req = self.client.delete(self.url(path));
+        if let Some(t) = pin_token {
+            req = req.header(PIN_TOKEN_HEADER, t);
+        }
+        let resp = req
             .send()
             .await
             .map_err(|e| AppError::Network(format!("Connexion échouée : {e}")))?;
@@ -121,21 +133,127 @@ impl RemoteClient {
         self.get_json("/api/members").await
     }
 
-    pub async fn get_members_by_type(&self, member_type: &str) -> Result<Vec<Member>, AppError> {
+    pub async fn get_members_by_type(&self, member_type: MemberType) -> Result<Vec<Member>, AppError> {
         self.get_json(&format!("/api/members/by-type/{member_type}")).await
     }
 
+    pub async fn get_member_counts(&self) -> Result<MemberCounts, AppError> {
+        self.get_json("/api/members/counts").await
+    }
+
     pub async fn get_members_by_type_with_total(
         &self,
-        member_type: &str,
+        member_type: MemberType,
+        status: Option<MemberStatus>,
     ) -> Result<Vec<MemberWithTotal>, AppError> {
-        self.get_json(&format!("/api/members/by-type/{member_type}/totals")).await
+        #[derive(Serialize)]
+        struct Body { status: Option<MemberStatus> }
+        self.post_json(&format!("/api/members/by-type/{member_type}/totals"), &Body { status }).await
+    }
+
+    pub async fn get_members_with_year_totals(
+        &self,
+        member_type: MemberType,
+        years: &[i32],
+    ) -> Result<Vec<MemberYearTotals>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { years: &'a [i32] }
+        self.post_json(&format!("/api/members/by-type/{member_type}/year-totals"), &Body { years }).await
+    }
+
+    pub async fn get_members_grouped_by_address(
+        &self,
+        member_type: MemberType,
+    ) -> Result<Vec<AddressGroup>, AppError> {
+        self.get_json(&format!("/api/members/by-type/{member_type}/by-address")).await
+    }
+
+    pub async fn get_members_without_contribution(
+        &self,
+        year: i32,
+        member_type: Option<&str>,
+    ) -> Result<Vec<Member>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { year: i32, member_type: Option<&'a str> }
+        self.post_json("/api/members/without-contribution", &Body { year, member_type }).await
+    }
+
+    pub async fn get_member_rows(
+        &self,
+        member_type: MemberType,
+        page: i64,
+        page_size: i64,
+        sort: MemberSort,
+        filter: &MemberFilter,
+    ) -> Result<Page<MemberRow>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { page: i64, page_size: i64, sort: MemberSort, filter: &'a MemberFilter }
+        self.post_json(
+            &format!("/api/members/by-type/{member_type}/rows"),
+            &Body { page, page_size, sort, filter },
+        )
+        .await
+    }
+
+    pub async fn get_upcoming_birthdays(&self, days: u32) -> Result<Vec<UpcomingBirthday>, AppError> {
+        self.get_json(&format!("/api/members/birthdays/{days}")).await
+    }
+
+    pub async fn get_upcoming_renewals(&self, within_days: u32) -> Result<Vec<RenewalDue>, AppError> {
+        self.get_json(&format!("/api/members/renewals/{within_days}")).await
     }
 
     pub async fn get_member(&self, id: i64) -> Result<Member, AppError> {
         self.get_json(&format!("/api/members/{id}")).await
     }
 
+    pub async fn get_member_year_totals(&self, member_id: i64) -> Result<Vec<MemberYearTotal>, AppError> {
+        self.get_json(&format!("/api/members/{member_id}/year-totals")).await
+    }
+
+    pub async fn add_member_note(&self, member_id: i64, text: String) -> Result<MemberNote, AppError> {
+        #[derive(Serialize)]
+        struct Body { text: String }
+        self.post_json(&format!("/api/members/{member_id}/notes"), &Body { text }).await
+    }
+
+    pub async fn get_member_notes(&self, member_id: i64) -> Result<Vec<MemberNote>, AppError> {
+        self.get_json(&format!("/api/members/{member_id}/notes")).await
+    }
+
+    pub async fn delete_member_note(&self, id: i64) -> Result<(), AppError> {
+        self.delete_req(&format!("/api/member-notes/{id}"), None).await
+    }
+
+    /// POST : le numéro de carte n'a pas à être encodé dans l'URL.
+    pub async fn get_member_by_card(&self, card_number: &str) -> Result<Option<Member>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { card_number: &'a str }
+        self.post_json("/api/members/by-card", &Body { card_number }).await
+    }
+
+    pub async fn search_members(
+        &self,
+        query: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<MemberWithTotal>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { query: &'a str, limit: Option<i64> }
+        self.post_json("/api/members/search", &Body { query, limit }).await
+    }
+
+    pub async fn search_members_lite(&self, query: &str) -> Result<Vec<MemberLite>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { query: &'a str }
+        self.post_json("/api/members/lite", &Body { query }).await
+    }
+
+    pub async fn find_similar_members(&self, full_name: &str) -> Result<Vec<Member>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> { full_name: &'a str }
+        self.post_json("/api/members/similar", &Body { full_name }).await
+    }
+
     pub async fn create_member(&self, input: MemberInput) -> Result<Member, AppError> {
         self.post_json("/api/members", &input).await
     }
@@ -144,45 +262,125 @@ impl RemoteClient {
         self.put_json(&format!("/api/members/{id}"), &input).await
     }
 
-    pub async fn delete_member(&self, id: i64) -> Result<(), AppError> {
-        self.delete_req(&format!("/api/members/{id}")).await
+    pub async fn delete_member(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        self.delete_req(&format!("/api/members/{id}"), pin_token).await
+    }
+
+    pub async fn get_deleted_members(&self) -> Result<Vec<DeletedMember>, AppError> {
+        self.get_json("/api/members/deleted").await
+    }
+
+    pub async fn restore_member(&self, id: i64) -> Result<Member, AppError> {
+        self.post_json(&format!("/api/members/{id}/restore"), &serde_json::json!({})).await
+    }
+
+    pub async fn purge_member(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        self.delete_req(&format!("/api/members/{id}/purge"), pin_token).await
     }
 
-    pub async fn transfer_members(&self, ids: &[i64], new_type: &str) -> Result<usize, AppError> {
+    pub async fn next_card_number(&self, member_type: MemberType) -> Result<String, AppError> {
+        self.get_json(&format!("/api/members/next-card/{member_type}")).await
+    }
+
+    pub async fn transfer_members(
+        &self,
+        ids: &[i64],
+        new_type: MemberType,
+    ) -> Result<Vec<Member>, AppError> {
         #[derive(Serialize)]
-        struct Body<'a> { ids: &'a [i64], new_type: &'a str }
+        struct Body<'a> { ids: &'a [i64], new_type: MemberType }
         self.post_json("/api/transfer-members", &Body { ids, new_type }).await
     }
 
+    pub async fn merge_members(
+        &self,
+        keep_id: i64,
+        remove_id: i64,
+        pin_token: Option<&str>,
+    ) -> Result<Member, AppError> {
+        let mut req = self.client.post(self.url("/api/members/merge"));
+        if let Some(t) = pin_token {
+            req = req.header(PIN_TOKEN_HEADER, t);
+        }
+        let resp = req
+            .json(&serde_json::json!({ "keep_id": keep_id, "remove_id": remove_id }))
+            .send()
+            .await
+            .map_err(|e| AppError::Network(format!("Connexion échouée : {e}")))?;
+
+        Self::check_response(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::Network(format!("Réponse invalide : {e}")))
+    }
+
     // ── Contributions ─────────────────────────────────────────────────────────
 
     pub async fn get_contributions(&self, member_id: i64) -> Result<Vec<Contribution>, AppError> {
         self.get_json(&format!("/api/contributions/by-member/{member_id}")).await
     }
 
+    pub async fn get_recent_contributions(
+        &self,
+        member_id: i64,
+        limit: i64,
+    ) -> Result<Vec<Contribution>, AppError> {
+        self.get_json(&format!("/api/contributions/by-member/{member_id}/recent/{limit}")).await
+    }
+
     pub async fn get_contributions_by_year(&self, year: i32) -> Result<Vec<Contribution>, AppError> {
         self.get_json(&format!("/api/contributions/by-year/{year}")).await
     }
 
+    pub async fn get_distinct_periods(&self, year: i32) -> Result<Vec<String>, AppError> {
+        self.get_json(&format!("/api/contributions/periods/{year}")).await
+    }
+
     pub async fn create_contribution(&self, input: ContributionInput) -> Result<Contribution, AppError> {
         self.post_json("/api/contributions", &input).await
     }
 
-    pub async fn delete_contribution(&self, id: i64) -> Result<(), AppError> {
-        self.delete_req(&format!("/api/contributions/{id}")).await
+    pub async fn create_contributions_batch(
+        &self,
+        inputs: Vec<ContributionInput>,
+    ) -> Result<Vec<Contribution>, AppError> {
+        self.post_json("/api/contributions/batch", &inputs).await
     }
 
-    pub async fn get_contributions_by_year_with_member(
+    pub async fn delete_contribution(&self, id: i64, pin_token: Option<&str>) -> Result<(), AppError> {
+        self.delete_req(&format!("/api/contributions/{id}"), pin_token).await
+    }
+
+    pub async fn restore_contribution(&self, id: i64) -> Result<Contribution, AppError> {
+        self.post_json(&format!("/api/contributions/{id}/restore"), &serde_json::json!({})).await
+    }
+
+    pub async fn get_contribution_by_receipt(
         &self,
-        year: i32,
-    ) -> Result<Vec<ContributionWithMember>, AppError> {
-        self.get_json(&format!("/api/contributions/by-year/{year}/with-member")).await
+        receipt: &str,
+    ) -> Result<ContributionWithMember, AppError> {
+        self.get_json(&format!("/api/contributions/by-receipt/{receipt}")).await
     }
 
-    pub async fn get_all_contributions_with_member(
+    pub async fn get_contributions_by_year_with_member(
         &self,
-    ) -> Result<Vec<ContributionWithMember>, AppError> {
-        self.get_json("/api/contributions/all/with-member").await
+        year: Option<i32>,
+        query: Option<&str>,
+        scope: ContributionSearchScope,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Page<ContributionWithMember>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            year:        Option<i32>,
+            name_filter: Option<&'a str>,
+            scope:       ContributionSearchScope,
+            limit:       Option<i64>,
+            offset:      Option<i64>,
+        }
+        let body = Body { year, name_filter: query, scope, limit, offset };
+        self.post_json("/api/contributions/with-member", &body).await
     }
 
     // ── Year Summaries ────────────────────────────────────────────────────────
@@ -195,18 +393,176 @@ impl RemoteClient {
         self.get_json(&format!("/api/year-summaries/{year}")).await
     }
 
+    pub async fn get_years_with_contributions(&self) -> Result<Vec<i32>, AppError> {
+        self.get_json("/api/years-with-contributions").await
+    }
+
+    pub async fn delete_empty_year_summary(&self, year: i32) -> Result<(), AppError> {
+        self.delete_req(&format!("/api/year-summaries/{year}"), None).await
+    }
+
     pub async fn close_year(&self, year: i32, note: Option<String>) -> Result<YearSummary, AppError> {
         #[derive(Serialize)]
         struct Body { note: Option<String> }
         self.post_json(&format!("/api/year-summaries/{year}/close"), &Body { note }).await
     }
 
-    pub async fn reopen_year(&self, year: i32) -> Result<YearSummary, AppError> {
-        self.post_json(&format!("/api/year-summaries/{year}/reopen"), &serde_json::json!({})).await
+    pub async fn reopen_year(
+        &self,
+        year: i32,
+        cascade: bool,
+        pin_token: Option<&str>,
+    ) -> Result<Vec<YearSummary>, AppError> {
+        let mut req = self.client.post(self.url(&format!("/api/year-summaries/{year}/reopen")));
+        if let Some(t) = pin_token {
+            req = req.header(PIN_TOKEN_HEADER, t);
+        }
+        let resp = req
+            .json(&serde_json::json!({ "cascade": cascade }))
+            .send()
+            .await
+            .map_err(|e| AppError::Network(format!("Connexion échouée : {e}")))?;
+
+        Self::check_response(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::Network(format!("Réponse invalide : {e}")))
+    }
+
+    pub async fn update_year_note(
+        &self,
+        year: i32,
+        note: Option<String>,
+        pin_token: Option<&str>,
+    ) -> Result<YearSummary, AppError> {
+        #[derive(Serialize)]
+        struct Body { note: Option<String> }
+        let mut req = self.client.put(self.url(&format!("/api/year-summaries/{year}/note")));
+        if let Some(t) = pin_token {
+            req = req.header(PIN_TOKEN_HEADER, t);
+        }
+        let resp = req
+            .json(&Body { note })
+            .send()
+            .await
+            .map_err(|e| AppError::Network(format!("Connexion échouée : {e}")))?;
+
+        Self::check_response(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AppError::Network(format!("Réponse invalide : {e}")))
+    }
+
+    pub async fn check_and_close_previous_year(
+        &self,
+        grace_days: Option<u32>,
+    ) -> Result<YearCloseStatus, AppError> {
+        #[derive(Serialize)]
+        struct Body { grace_days: Option<u32> }
+        self.post_json("/api/year/check-close", &Body { grace_days }).await
+    }
+
+    pub async fn preview_year_close(&self, year: i32) -> Result<YearClosePreview, AppError> {
+        self.get_json(&format!("/api/year-summaries/{year}/close-preview")).await
+    }
+
+    pub async fn get_year_comparison(&self, years: Vec<i32>) -> Result<Vec<YearComparison>, AppError> {
+        #[derive(Serialize)]
+        struct Body { years: Vec<i32> }
+        self.post_json("/api/year-comparison", &Body { years }).await
+    }
+
+    pub async fn get_monthly_totals(&self, year: i32) -> Result<Vec<MonthlyTotal>, AppError> {
+        self.get_json(&format!("/api/monthly-totals/{year}")).await
     }
 
-    pub async fn check_and_close_previous_year(&self) -> Result<Option<YearSummary>, AppError> {
-        self.post_json("/api/year/check-close", &serde_json::json!({})).await
+    pub async fn get_year_member_totals(&self, year: i32) -> Result<Vec<YearMemberSnapshot>, AppError> {
+        self.get_json(&format!("/api/year-summaries/{year}/member-totals")).await
+    }
+
+    pub async fn get_weekly_totals(&self, year: i32) -> Result<Vec<WeekTotal>, AppError> {
+        self.get_json(&format!("/api/weekly-totals/{year}")).await
+    }
+
+    pub async fn compare_years(
+        &self,
+        year_a: i32,
+        year_b: i32,
+    ) -> Result<MonthlyComparison, AppError> {
+        self.get_json(&format!("/api/compare-years/{year_a}/{year_b}")).await
+    }
+
+    pub async fn get_year_breakdown(&self, year: i32) -> Result<YearBreakdown, AppError> {
+        self.get_json(&format!("/api/year-breakdown/{year}")).await
+    }
+
+    // ── Statistiques ──────────────────────────────────────────────────────────
+
+    pub async fn get_statistics(&self) -> Result<Statistics, AppError> {
+        self.get_json("/api/statistics").await
+    }
+
+    pub async fn get_dashboard_stats(&self) -> Result<DashboardStats, AppError> {
+        self.get_json("/api/dashboard-stats").await
+    }
+
+    // ── Paramètres ────────────────────────────────────────────────────────────
+
+    pub async fn get_settings(&self) -> Result<Settings, AppError> {
+        self.get_json("/api/settings").await
+    }
+
+    pub async fn update_settings(&self, settings: Settings) -> Result<Settings, AppError> {
+        self.put_json("/api/settings", &settings).await
+    }
+
+    pub async fn get_language(&self) -> Result<Lang, AppError> {
+        self.get_json("/api/language").await
+    }
+
+    pub async fn set_language(&self, lang: Lang) -> Result<Lang, AppError> {
+        self.put_json("/api/language", &lang).await
+    }
+
+    // ── Intégrité ─────────────────────────────────────────────────────────────
+
+    pub async fn run_integrity_check(&self) -> Result<IntegrityReport, AppError> {
+        self.get_json("/api/integrity").await
+    }
+
+    pub async fn repair_integrity(&self) -> Result<IntegrityReport, AppError> {
+        self.post_json("/api/integrity/repair", &serde_json::json!({})).await
+    }
+
+    pub async fn health(&self) -> Result<DbHealth, AppError> {
+        self.get_json("/api/db-health").await
+    }
+
+    pub async fn get_pending_tasks(&self) -> Result<Vec<PendingTask>, AppError> {
+        self.get_json("/api/pending-tasks").await
+    }
+
+    pub async fn normalize_all_phones(&self) -> Result<PhoneFixReport, AppError> {
+        self.post_json("/api/maintenance/phones", &serde_json::json!({})).await
+    }
+
+    // ── Journal d'audit ───────────────────────────────────────────────────────
+
+    pub async fn get_audit_log(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        entity: Option<&str>,
+    ) -> Result<Page<AuditEntry>, AppError> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            limit:  Option<i64>,
+            offset: Option<i64>,
+            entity: Option<&'a str>,
+        }
+        self.post_json("/api/audit-log", &Body { limit, offset, entity }).await
     }
 
     // ── PIN ───────────────────────────────────────────────────────────────────
@@ -216,7 +572,19 @@ impl RemoteClient {
         Err(AppError::Validation("Le PIN ne peut être configuré que sur le serveur.".into()))
     }
 
-    pub async fn verify_pin(&self, pin: &str) -> Result<bool, AppError> {
+    pub async fn change_pin(&self, _old_pin: &str, _new_pin: &str) -> Result<(), AppError> {
+        self.set_pin("").await
+    }
+
+    pub async fn remove_pin(&self, _old_pin: &str) -> Result<(), AppError> {
+        self.set_pin("").await
+    }
+
+    pub async fn has_pin(&self) -> Result<bool, AppError> {
+        self.get_json("/api/has-pin").await
+    }
+
+    pub async fn verify_pin(&self, pin: &str) -> Result<String, AppError> {
         #[derive(Serialize)]
         struct Body<'a> { pin: &'a str }
         self.post_json("/api/verify-pin", &Body { pin }).await
@@ -232,19 +600,43 @@ impl RemoteClient {
 
     // ── Export / Import ───────────────────────────────────────────────────────
 
-    pub async fn export_members_csv(&self, member_type: &str) -> Result<String, AppError> {
+    pub async fn export_members_csv(&self, member_type: MemberType) -> Result<String, AppError> {
         self.get_json::<String>(&format!("/api/export/csv/{member_type}")).await
     }
 
-    pub async fn export_members_excel(&self, member_type: &str) -> Result<Vec<u8>, AppError> {
+    pub async fn export_members_excel(&self, member_type: MemberType) -> Result<Vec<u8>, AppError> {
         self.get_bytes(&format!("/api/export/excel/{member_type}")).await
     }
 
+    pub async fn export_year_report(&self, year: i32) -> Result<String, AppError> {
+        self.get_json::<String>(&format!("/api/export/year/{year}")).await
+    }
+
+    pub async fn export_receipt(&self, receipt: &str) -> Result<String, AppError> {
+        self.get_json::<String>(&format!("/api/export/receipt/{receipt}")).await
+    }
+
+    pub async fn export_year_report_pdf(&self, year: i32) -> Result<Vec<u8>, AppError> {
+        self.get_bytes(&format!("/api/export/year/{year}/pdf")).await
+    }
+
+    pub async fn export_contributions_csv(&self, year: i32) -> Result<String, AppError> {
+        self.get_json::<String>(&format!("/api/export/contributions/{year}")).await
+    }
+
+    pub async fn export_member_statement_csv(
+        &self,
+        member_id: i64,
+        year: i32,
+    ) -> Result<String, AppError> {
+        self.get_json::<String>(&format!("/api/export/statement/{member_id}/{year}")).await
+    }
+
     pub async fn import_members_csv(
         &self,
         csv_content: String,
-        member_type: &str,
-    ) -> Result<usize, AppError> {
+        member_type: MemberType,
+    ) -> Result<ImportReport, AppError> {
         #[derive(Serialize)]
         struct Body { content: String }
         self.post_json(&format!("/api/import/csv/{member_type}"), &Body { content: csv_content }).await
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use leptos::prelude::*;
 use leptos_router::{
     components::{Route, Router, Routes},
@@ -5,17 +7,44 @@ use leptos_router::{
 };
 
 use crate::{
-    components::{navbar::Navbar, sky_canvas::SkyCanvas, titlebar::TitleBar, year_toast::YearToast},
-    models::year_summary::YearSummary,
+    components::{
+        confirm_dialog::{ConfirmCtx, ConfirmDialog},
+        demo_seed::DemoSeedShortcut,
+        member_page::CLIENT_SIDE_MAX_MEMBERS,
+        navbar::Navbar, pin_prompt_modal::PinPromptModal, sky_canvas::SkyCanvas,
+        titlebar::TitleBar,
+        toast_stack::{
+            ToastAction, ToastKind, ToastQueue, ToastStack, RETRY_LABEL, TOAST_EXIT_MS, UNDO_TOAST_MS,
+        },
+        year_toast::YearToast,
+    },
+    models::{
+        integrity::IntegrityReport,
+        member::{MemberCounts, MemberType, MemberWithTotal},
+        settings::{current_fiscal_year, Settings},
+        task::PendingTask,
+        year_summary::{YearClosePreview, YearCloseStatus},
+    },
     pages::{
         accueil::Accueil, archives::Archives, cathekomens::Cathekomens,
-        communiants::Communiants, setup::SetupPage,
+        communiants::Communiants, impression::ImpressionMembres, journal::Journal,
+        member_detail::MemberDetail, parametres::Parametres, recherche::Recherche, saisie::SaisieSerie, setup::SetupPage, statistiques::Statistiques,
+    },
+    i18n::{tr, Lang, LangCtx},
+    services::{config_service::{self, StartupError}, db_service::{self, BackendMode}},
+    theme::{
+        apply_motion_to_dom, apply_theme_to_dom, load_motion_choice, load_theme,
+        save_motion_choice, save_theme, MotionCtx, ThemeCtx, ToastCtx,
+    },
+    utils::{
+        motion::{prefers_reduced_motion, watch_system_motion},
+        sleep_ms,
     },
-    services::{config_service, db_service},
-    theme::{apply_theme_to_dom, load_theme, save_theme, ThemeCtx, ToastCtx},
-    utils::sleep_ms,
 };
 
+/// Intervalle de vérification de `is_ready` pendant l'initialisation de la base.
+const READY_POLL_MS: u32 = 150;
+
 // ─── Contexte de configuration ───────────────────────────────────────────────
 
 /// Partagé via provide_context pour que la Navbar puisse déclencher
@@ -26,30 +55,350 @@ pub struct ConfigCtx {
     pub is_configured: RwSignal<Option<bool>>,
 }
 
+// ─── Contexte des paramètres ─────────────────────────────────────────────────
+
+/// Nom affiché tant que les paramètres ne sont pas chargés.
+pub const DEFAULT_CHURCH_NAME: &str = "FJKM Ambalavao Isotry";
+
+/// Paramètres de l'église, chargés une fois la source de données configurée
+/// et mis à jour par la page Paramètres.
+#[derive(Clone, Copy)]
+pub struct SettingsCtx {
+    pub settings: RwSignal<Option<Settings>>,
+}
+
+impl SettingsCtx {
+    /// Nom de l'église (réactif), valeur par défaut si non chargé.
+    pub fn church_name(&self) -> String {
+        self.settings
+            .get()
+            .map(|s| s.church_name)
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_CHURCH_NAME.to_string())
+    }
+}
+
+// ─── Contexte intégrité ──────────────────────────────────────────────────────
+
+/// Anomalies relevées au démarrage (`startup_integrity_check`), affichées sur l'accueil
+/// jusqu'à réparation ou fermeture de la bannière. `None` : rien à signaler.
+#[derive(Clone, Copy)]
+pub struct IntegrityCtx {
+    pub report: RwSignal<Option<IntegrityReport>>,
+}
+
+/// Page imprimable affichée (`/imprimer/…`) : ciel, barre de titre et Navbar ne
+/// sont pas rendus. Fourni par `App`, hors du `Router` : `SkyCanvas` et `TitleBar`
+/// ne peuvent pas lire la route eux-mêmes.
+#[derive(Clone, Copy)]
+pub struct PrintModeCtx {
+    pub active: RwSignal<bool>,
+}
+
+// ─── Notifications ───────────────────────────────────────────────────────────
+
+/// Toasts de retour d'action (succès, erreurs hors formulaire), affichés par `ToastStack`.
+/// Les erreurs de validation restent affichées dans les formulaires.
+#[derive(Clone, Copy)]
+pub struct ToastProviderCtx {
+    pub toasts: RwSignal<ToastQueue>,
+}
+
+impl ToastProviderCtx {
+    pub fn push_toast(self, kind: ToastKind, message: impl Into<String>, duration_ms: u32) {
+        let message = message.into();
+        self.toasts.update(|q| {
+            q.push(kind, message, duration_ms);
+        });
+    }
+
+    pub fn success(self, message: impl Into<String>) {
+        self.push_toast(ToastKind::Success, message, ToastKind::Success.default_duration_ms());
+    }
+
+    pub fn error(self, message: impl Into<String>) {
+        self.push_toast(ToastKind::Error, message, ToastKind::Error.default_duration_ms());
+    }
+
+    pub fn info(self, message: impl Into<String>) {
+        self.push_toast(ToastKind::Info, message, ToastKind::Info.default_duration_ms());
+    }
+
+    /// Suppression effectuée : toast « Averina » pendant `UNDO_TOAST_MS` qui lance `undo`.
+    pub fn undoable(self, message: impl Into<String>, undo: impl Fn() + Send + Sync + 'static) {
+        let message = message.into();
+        let action = ToastAction { label: "Averina", run: Callback::new(move |()| undo()) };
+        self.toasts.update(|q| {
+            q.push_with_action(ToastKind::Info, message, UNDO_TOAST_MS, Some(action));
+        });
+    }
+
+    /// Échec passager (base occupée…) : toast d'erreur « Avereno » qui relance `retry`.
+    pub fn error_with_retry(self, message: impl Into<String>, retry: impl Fn() + Send + Sync + 'static) {
+        let message = message.into();
+        let action = ToastAction { label: RETRY_LABEL, run: Callback::new(move |()| retry()) };
+        self.toasts.update(|q| {
+            q.push_with_action(ToastKind::Error, message, ToastKind::Error.default_duration_ms(), Some(action));
+        });
+    }
+
+    /// Ferme un toast après son animation de sortie ; sans effet s'il est déjà parti.
+    pub fn dismiss(self, id: u32) {
+        let mut started = false;
+        self.toasts.update(|q| started = q.mark_leaving(id));
+        if started {
+            leptos::task::spawn_local(async move {
+                sleep_ms(TOAST_EXIT_MS).await;
+                self.toasts.update(|q| q.remove(id));
+            });
+        }
+    }
+}
+
+// ─── Version des données / cache des membres ─────────────────────────────────
+
+/// Incrémentée après toute écriture touchant les membres ou leurs totaux
+/// (sert de `refresh_ctr` aux pages membres) : listes en cache et badges sont rechargés.
+#[derive(Clone, Copy)]
+pub struct DataVersionCtx {
+    pub version: RwSignal<u32>,
+}
+
+impl DataVersionCtx {
+    pub fn bump(self) {
+        self.version.update(|v| *v = v.wrapping_add(1));
+    }
+}
+
+/// Effectifs affichés dans la Navbar et listes préchargées au survol des onglets.
+#[derive(Clone, Copy)]
+pub struct MemberDataCtx {
+    pub counts: RwSignal<Option<MemberCounts>>,
+    /// Liste par type, avec la version des données au moment du chargement
+    lists:      RwSignal<HashMap<MemberType, (u32, Vec<MemberWithTotal>)>>,
+    /// Préchargements en cours (survols répétés)
+    pending:    StoredValue<Vec<MemberType>>,
+    version:    RwSignal<u32>,
+    /// Quartiers repliés de la vue « par adresse », gardés d'une page à l'autre
+    collapsed:  StoredValue<HashMap<MemberType, HashSet<String>>>,
+}
+
+impl MemberDataCtx {
+    fn new(version: RwSignal<u32>) -> Self {
+        Self {
+            counts:    RwSignal::new(None),
+            lists:     RwSignal::new(HashMap::new()),
+            pending:   StoredValue::new(Vec::new()),
+            version,
+            collapsed: StoredValue::new(HashMap::new()),
+        }
+    }
+
+    /// Liste en cache si elle date de la version courante.
+    pub fn cached(self, member_type: MemberType) -> Option<Vec<MemberWithTotal>> {
+        let version = self.version.get_untracked();
+        self.lists.with_untracked(|l| {
+            l.get(&member_type).filter(|(v, _)| *v == version).map(|(_, list)| list.clone())
+        })
+    }
+
+    /// Mémorise une liste chargée pendant la version `version`.
+    pub fn store(self, member_type: MemberType, version: u32, list: Vec<MemberWithTotal>) {
+        if version == self.version.get_untracked() {
+            self.lists.update(|l| {
+                l.insert(member_type, (version, list));
+            });
+        }
+    }
+
+    /// Charge la liste en arrière-plan pour que la page s'affiche immédiatement.
+    /// Rien à précharger pour un type paginé côté serveur.
+    pub fn prefetch(self, member_type: MemberType) {
+        let server_mode = self
+            .counts
+            .get_untracked()
+            .is_none_or(|c| c.of(member_type) > CLIENT_SIDE_MAX_MEMBERS);
+        if server_mode
+            || self.cached(member_type).is_some()
+            || self.pending.with_value(|p| p.contains(&member_type))
+        {
+            return;
+        }
+        self.pending.update_value(|p| p.push(member_type));
+        let version = self.version.get_untracked();
+        leptos::task::spawn_local(async move {
+            if let Ok(list) = db_service::get_members_by_type_with_total(member_type, None).await {
+                self.store(member_type, version, list);
+            }
+            self.pending.update_value(|p| p.retain(|t| *t != member_type));
+        });
+    }
+
+    pub fn collapsed_addresses(self, member_type: MemberType) -> HashSet<String> {
+        self.collapsed.with_value(|c| c.get(&member_type).cloned().unwrap_or_default())
+    }
+
+    pub fn remember_collapsed(self, member_type: MemberType, keys: HashSet<String>) {
+        self.collapsed.update_value(|c| {
+            c.insert(member_type, keys);
+        });
+    }
+
+    /// Nouvelle version : cache vidé, effectifs relus.
+    fn invalidate(self) {
+        self.lists.update(HashMap::clear);
+        leptos::task::spawn_local(async move {
+            if let Ok(counts) = db_service::get_member_counts().await {
+                self.counts.set(Some(counts));
+            }
+        });
+    }
+}
+
+// ─── Tâches en attente ───────────────────────────────────────────────────────
+
+/// Rappels de la cloche de la Navbar (`get_pending_tasks`), relus au démarrage,
+/// à chaque nouvelle version des données et après une clôture d'année.
+#[derive(Clone, Copy)]
+pub struct TasksCtx {
+    pub tasks: RwSignal<Vec<PendingTask>>,
+}
+
+impl TasksCtx {
+    /// En cas d'erreur, la liste précédente reste affichée.
+    fn refresh(self) {
+        leptos::task::spawn_local(async move {
+            if let Ok(tasks) = db_service::get_pending_tasks().await {
+                self.tasks.set(tasks);
+            }
+        });
+    }
+}
+
+// ─── Contexte PIN (actions destructrices) ────────────────────────────────────
+
+type PinAction = Box<dyn FnOnce(Option<String>)>;
+
+/// Garde des actions destructrices (suppression, réouverture, restauration, note d'année) :
+/// si un PIN admin est configuré, `PinPromptModal` le demande avant d'exécuter
+/// l'action avec le jeton obtenu ; sinon l'action part directement sans jeton.
+#[derive(Clone, Copy)]
+pub struct PinCtx {
+    /// `None` tant que `has_pin` n'a pas répondu.
+    configured: RwSignal<Option<bool>>,
+    pending:    StoredValue<Option<PinAction>, LocalStorage>,
+    /// Modal de saisie affiché.
+    pub open:   RwSignal<bool>,
+}
+
+impl PinCtx {
+    fn new() -> Self {
+        Self {
+            configured: RwSignal::new(None),
+            pending:    StoredValue::new_local(None),
+            open:       RwSignal::new(false),
+        }
+    }
+
+    /// Exécute `action` après saisie du PIN (ou immédiatement si aucun PIN n'est configuré).
+    pub fn guard(self, action: impl FnOnce(Option<String>) + 'static) {
+        match self.configured.get_untracked() {
+            Some(false) => action(None),
+            Some(true) => {
+                self.pending.set_value(Some(Box::new(action)));
+                self.open.set(true);
+            }
+            None => leptos::task::spawn_local(async move {
+                // Inconnu ou erreur : on demande le PIN, le backend tranchera
+                let has_pin = db_service::has_pin().await.unwrap_or(true);
+                self.configured.set(Some(has_pin));
+                self.guard(action);
+            }),
+        }
+    }
+
+    /// PIN défini, changé ou supprimé depuis les paramètres.
+    pub fn set_configured(self, configured: bool) {
+        self.configured.set(Some(configured));
+    }
+
+    /// PIN accepté : ferme le modal et lance l'action en attente.
+    pub fn confirm(self, token: String) {
+        self.open.set(false);
+        let mut action = None;
+        self.pending.update_value(|p| action = p.take());
+        if let Some(action) = action {
+            action(Some(token));
+        }
+    }
+
+    pub fn cancel(self) {
+        self.open.set(false);
+        self.pending.set_value(None);
+    }
+}
+
 // ─── Application principale (après configuration) ────────────────────────────
 
 #[component]
 fn MainApp() -> impl IntoView {
-    let toast_data: RwSignal<Option<YearSummary>> = RwSignal::new(None);
-    provide_context(ToastCtx { data: toast_data });
+    let toast_data:    RwSignal<Option<YearCloseStatus>>  = RwSignal::new(None);
+    let toast_preview: RwSignal<Option<YearClosePreview>> = RwSignal::new(None);
+    provide_context(ToastCtx { data: toast_data, preview: toast_preview });
+
+    let pin_ctx = PinCtx::new();
+    provide_context(pin_ctx);
+
+    let confirm_ctx = ConfirmCtx::new();
+    provide_context(confirm_ctx);
+
+    provide_context(ToastProviderCtx { toasts: RwSignal::new(ToastQueue::default()) });
+
+    let print_mode = use_context::<PrintModeCtx>().expect("PrintModeCtx manquant");
+
+    let data_version = DataVersionCtx { version: RwSignal::new(0) };
+    provide_context(data_version);
+    let member_data = MemberDataCtx::new(data_version.version);
+    provide_context(member_data);
+    Effect::new(move |_| {
+        let _ = data_version.version.get();
+        member_data.invalidate();
+    });
+    let tasks = TasksCtx { tasks: RwSignal::new(Vec::new()) };
+    provide_context(tasks);
+    Effect::new(move |_| {
+        let _ = data_version.version.get();
+        toast_data.track();
+        tasks.refresh();
+    });
 
+    // Au démarrage puis chaque jour : année précédente encore ouverte → aperçu de
+    // la clôture ; seul « Hikatona » dans le toast la clôture
     leptos::task::spawn_local(async move {
-        if let Ok(Some(s)) = db_service::check_and_close_previous_year().await {
-            toast_data.set(Some(s));
-        }
         loop {
-            sleep_ms(86_400_000).await;
-            if let Ok(Some(s)) = db_service::check_and_close_previous_year().await {
-                toast_data.set(Some(s));
+            let start = db_service::get_settings().await.map_or(1, |s| s.fiscal_year_start_month);
+            let prev_year = current_fiscal_year(start) - 1;
+            match db_service::preview_year_close(prev_year).await {
+                Ok(p) if !p.already_closed => toast_preview.set(Some(p)),
+                _ => {}
             }
+            sleep_ms(86_400_000).await;
         }
     });
 
     view! {
         <Router>
-            <div style="position:fixed;top:36px;left:0;right:0;bottom:0;z-index:10;overflow-y:auto;"
+            // Impression : conteneur dans le flux (sinon seule la partie visible est
+            // imprimée) et fond blanc à la place du ciel
+            <div style=move || if print_mode.active.get() {
+                     "background:#fff;"
+                 } else {
+                     "position:fixed;top:36px;left:0;right:0;bottom:0;z-index:10;overflow-y:auto;"
+                 }
                  class="flex flex-col min-h-full">
-                <Navbar />
+                <Show when=move || !print_mode.active.get()>
+                    <Navbar />
+                </Show>
                 <main class="flex-1 container mx-auto px-3 sm:px-4 py-4 sm:py-8 max-w-6xl w-full">
                     <Routes fallback=|| {
                         view! {
@@ -62,10 +411,21 @@ fn MainApp() -> impl IntoView {
                         <Route path=path!("/communiants")  view=Communiants />
                         <Route path=path!("/cathekomens")  view=Cathekomens />
                         <Route path=path!("/archives")     view=Archives />
+                        <Route path=path!("/statistiques") view=Statistiques />
+                        <Route path=path!("/parametres")   view=Parametres />
+                        <Route path=path!("/recherche")    view=Recherche />
+                        <Route path=path!("/journal")      view=Journal />
+                        <Route path=path!("/saisie")       view=SaisieSerie />
+                        <Route path=path!("/membre/:id")   view=MemberDetail />
+                        <Route path=path!("/imprimer/:member_type") view=ImpressionMembres />
                     </Routes>
                 </main>
             </div>
             <YearToast />
+            <ToastStack />
+            <DemoSeedShortcut />
+            {move || pin_ctx.open.get().then(|| view! { <PinPromptModal /> })}
+            {move || confirm_ctx.request.get().map(|opts| view! { <ConfirmDialog opts=opts /> })}
         </Router>
     }
 }
@@ -86,27 +446,265 @@ pub fn App() -> impl IntoView {
         apply_theme_to_dom(t, old.is_some());
     });
 
+    let motion = MotionCtx {
+        choice: RwSignal::new(load_motion_choice()),
+        system: RwSignal::new(prefers_reduced_motion()),
+    };
+    watch_system_motion(motion.system);
+    provide_context(motion);
+
+    // Enregistré seulement après un choix explicite : sinon le système fait foi
+    Effect::new(move |old: Option<()>| {
+        let choice = motion.choice.get();
+        if old.is_some() {
+            save_motion_choice(choice);
+        }
+    });
+    Effect::new(move |_| apply_motion_to_dom(motion.reduced()));
+
+    // Détecté une fois : sans pont Tauri, `db_service` répond avec des données simulées
+    let backend = db_service::backend_mode();
+    provide_context(backend);
+
     // None = chargement, Some(false) = non configuré, Some(true) = configuré
     let is_configured: RwSignal<Option<bool>> = RwSignal::new(None);
 
     // Fournir le signal au reste de l'arbre (Navbar en a besoin)
     provide_context(ConfigCtx { is_configured });
 
+    let settings: RwSignal<Option<Settings>> = RwSignal::new(None);
+    provide_context(SettingsCtx { settings });
+
+    let lang: RwSignal<Lang> = RwSignal::new(Lang::default());
+    provide_context(LangCtx { lang });
+
+    let integrity: RwSignal<Option<IntegrityReport>> = RwSignal::new(None);
+    provide_context(IntegrityCtx { report: integrity });
+
+    provide_context(PrintModeCtx { active: RwSignal::new(false) });
+
+    // Paramètres et langue lus dès que la source est configurée (y compris juste
+    // après le Setup), puis contrôle d'intégrité si activé
     Effect::new(move |_| {
+        if is_configured.get() == Some(true) {
+            leptos::task::spawn_local(async move {
+                if let Ok(l) = db_service::get_language().await {
+                    lang.set(l);
+                }
+                let Ok(s) = db_service::get_settings().await else { return };
+                let check = s.startup_integrity_check;
+                settings.set(Some(s));
+                if check {
+                    if let Ok(report) = db_service::run_integrity_check().await {
+                        integrity.set((!report.issues.is_empty()).then_some(report));
+                    }
+                }
+            });
+        }
+    });
+
+    // Échec d'ouverture de la base au démarrage (migrations, disque…)
+    let init_error: RwSignal<Option<StartupError>> = RwSignal::new(None);
+    // Autre dossier de données saisi sur l'écran d'erreur
+    let other_dir:  RwSignal<String>               = RwSignal::new(String::new());
+    let recovering: RwSignal<bool>                 = RwSignal::new(false);
+    // Bandeau permanent : mode démonstration, dossier choisi non retenu
+    let banner:     RwSignal<Option<&'static str>> = RwSignal::new(None);
+
+    // La base s'ouvre en tâche de fond côté Tauri : on reste sur l'écran de
+    // chargement jusqu'à ce qu'elle soit prête plutôt que d'afficher des tables vides.
+    let wait_ready = move || {
+        leptos::task::spawn_local(async move {
+            loop {
+                match config_service::is_ready().await {
+                    Ok(true)  => break is_configured.set(Some(true)),
+                    Ok(false) => sleep_ms(READY_POLL_MS).await,
+                    Err(e)    => break init_error.set(Some(e)),
+                }
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        // Navigateur seul : ni configuration à lire ni base à attendre
+        if backend == BackendMode::Mock {
+            banner.set(Some("Mode démo — données non persistées"));
+            is_configured.set(Some(true));
+            return;
+        }
         leptos::task::spawn_local(async move {
             match config_service::get_config().await {
-                Ok(Some(_)) => is_configured.set(Some(true)),
+                Ok(Some(_)) => wait_ready(),
                 _           => is_configured.set(Some(false)),
             }
         });
     });
 
+    let show_error = move |message: String| {
+        init_error.set(Some(StartupError { kind: "internal".into(), message }));
+    };
+
+    let reconfigure = move || {
+        leptos::task::spawn_local(async move {
+            match config_service::reset_config().await {
+                Ok(()) => {
+                    init_error.set(None);
+                    is_configured.set(Some(false));
+                }
+                Err(e) => show_error(e),
+            }
+        });
+    };
+
+    // Réessaie avec la configuration enregistrée (`save_config` ré-initialise la
+    // source) ; sans configuration, retour au Setup. L'erreur détaillée est relue
+    // par `is_ready`.
+    let retry = move |_| {
+        init_error.set(None);
+        leptos::task::spawn_local(async move {
+            match config_service::get_config().await {
+                Ok(Some(cfg)) => {
+                    let _ = config_service::save_config(&cfg).await;
+                    wait_ready();
+                }
+                Ok(None) => reconfigure(),
+                Err(e)   => show_error(e),
+            }
+        });
+    };
+
+    let use_other_dir = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let path = other_dir.get_untracked();
+        if path.trim().is_empty() {
+            return;
+        }
+        recovering.set(true);
+        leptos::task::spawn_local(async move {
+            match config_service::use_data_dir(&path).await {
+                Ok(saved) => {
+                    if !saved {
+                        banner.set(Some(
+                            "Tsy voatahiry ny toerana nofidiana : hangatahina indray \
+                             amin'ny fanokafana manaraka.",
+                        ));
+                    }
+                    init_error.set(None);
+                    wait_ready();
+                }
+                Err(e) => show_error(e),
+            }
+            recovering.set(false);
+        });
+    };
+
+    let demo = move |_| {
+        recovering.set(true);
+        leptos::task::spawn_local(async move {
+            match config_service::start_demo_mode().await {
+                Ok(()) => {
+                    banner.set(Some("Fanandramana : tsy voatahiry ny fanovana."));
+                    init_error.set(None);
+                    is_configured.set(Some(true));
+                }
+                Err(e) => show_error(e),
+            }
+            recovering.set(false);
+        });
+    };
+
     view! {
         <SkyCanvas />
         <TitleBar />
 
-        {move || match is_configured.get() {
-            None => view! {
+        {move || match (init_error.get(), is_configured.get()) {
+            (Some(e), _) => view! {
+                <div class="fixed inset-0 flex items-center justify-center z-20 p-4">
+                    <div class="max-w-md w-full p-6 space-y-4 rounded-2xl shadow-2xl \
+                                bg-white dark:bg-gray-800 \
+                                border border-red-200 dark:border-red-700/50">
+                        <h2 class="text-base font-bold text-red-700 dark:text-red-300">
+                            "Tsy voasokatra ny angona"
+                        </h2>
+                        <p class="text-sm text-gray-700 dark:text-gray-200">{e.hint()}</p>
+                        <p class="text-xs font-mono text-gray-500 dark:text-gray-400 break-words">
+                            {e.message.clone()}
+                        </p>
+
+                        <form on:submit=use_other_dir class="space-y-2">
+                            <label
+                                for="init-data-dir"
+                                class="block text-xs font-semibold text-gray-600 dark:text-gray-400"
+                            >
+                                "Toerana hafa hitahirizana ny angona"
+                            </label>
+                            <div class="flex gap-2">
+                                <input
+                                    id="init-data-dir"
+                                    type="text"
+                                    placeholder="D:\\FJKM"
+                                    class="flex-1 min-w-0 px-3 py-2 text-sm font-mono \
+                                           bg-gray-50 dark:bg-gray-700/60 \
+                                           border border-gray-200 dark:border-gray-600 \
+                                           rounded-xl text-gray-800 dark:text-white \
+                                           focus:outline-none focus:ring-2 focus:ring-blue-400"
+                                    prop:value=move || other_dir.get()
+                                    on:input=move |ev| other_dir.set(event_target_value(&ev))
+                                />
+                                <button
+                                    type="submit"
+                                    disabled=move || recovering.get() || other_dir.get().trim().is_empty()
+                                    class="btn-ripple px-3 py-2 text-sm font-medium \
+                                           text-blue-700 dark:text-blue-300 \
+                                           bg-blue-50 dark:bg-blue-900/30 \
+                                           hover:bg-blue-100 dark:hover:bg-blue-900/50 \
+                                           disabled:opacity-50 rounded-xl transition-colors"
+                                >
+                                    "Ampiasao"
+                                </button>
+                            </div>
+                        </form>
+
+                        <div class="flex flex-wrap gap-3">
+                            <button
+                                type="button"
+                                on:click=move |_| reconfigure()
+                                class="btn-ripple flex-1 px-4 py-2.5 text-sm font-medium \
+                                       text-gray-600 dark:text-gray-300 \
+                                       bg-gray-100 dark:bg-gray-700 \
+                                       hover:bg-gray-200 dark:hover:bg-gray-600 \
+                                       rounded-xl transition-colors"
+                            >
+                                "Hanova ny fikirana"
+                            </button>
+                            <button
+                                type="button"
+                                on:click=demo
+                                disabled=move || recovering.get()
+                                title="Angona ohatra, tsy voatahiry ny fanovana"
+                                class="btn-ripple flex-1 px-4 py-2.5 text-sm font-medium \
+                                       text-amber-700 dark:text-amber-300 \
+                                       bg-amber-50 dark:bg-amber-900/30 \
+                                       hover:bg-amber-100 dark:hover:bg-amber-900/50 \
+                                       disabled:opacity-50 rounded-xl transition-colors"
+                            >
+                                "Fanandramana"
+                            </button>
+                            <button
+                                type="button"
+                                on:click=retry
+                                class="btn-ripple flex-1 px-4 py-2.5 text-sm font-semibold \
+                                       text-white bg-blue-600 hover:bg-blue-700 \
+                                       rounded-xl transition-colors shadow-sm"
+                            >
+                                {move || tr("action.retry")}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            }.into_any(),
+
+            (None, None) => view! {
                 <div class="fixed inset-0 flex items-center justify-center z-20">
                     <p class="text-blue-900 dark:text-blue-100 text-lg font-medium animate-pulse">
                         "Chargement…"
@@ -114,11 +712,19 @@ pub fn App() -> impl IntoView {
                 </div>
             }.into_any(),
 
-            Some(false) => view! {
+            (None, Some(false)) => view! {
                 <SetupPage is_configured />
             }.into_any(),
 
-            Some(true) => view! {
+            (None, Some(true)) => view! {
+                {move || banner.get().map(|text| view! {
+                    <div class="fixed bottom-3 left-1/2 -translate-x-1/2 z-40 px-4 py-1.5 \
+                                text-xs font-medium rounded-full shadow \
+                                bg-amber-100 dark:bg-amber-900/60 \
+                                text-amber-800 dark:text-amber-200">
+                        {text}
+                    </div>
+                })}
                 <MainApp />
             }.into_any(),
         }}
@@ -0,0 +1,199 @@
+/// Graphique en barres SVG — sans bibliothèque JS.
+///
+/// Chaque groupe (ex : une année) affiche une barre par série (ex : Lahy / Vavy).
+/// Les couleurs suivent `ThemeCtx` : palette claire ou sombre selon le thème effectif.
+use leptos::prelude::*;
+
+use crate::theme::ThemeCtx;
+
+// ─── Données ──────────────────────────────────────────────────────────────────
+
+/// Série affichée dans le graphique (une couleur par série).
+#[derive(Clone, Copy, PartialEq)]
+pub struct BarSeries {
+    pub name:  &'static str,
+    /// Couleur en mode clair
+    pub light: &'static str,
+    /// Couleur en mode sombre
+    pub dark:  &'static str,
+}
+
+/// Groupe de barres : une valeur par série, dans l'ordre des séries.
+#[derive(Clone, PartialEq)]
+pub struct BarGroup {
+    pub label:  String,
+    pub values: Vec<f64>,
+}
+
+// ─── Géométrie ────────────────────────────────────────────────────────────────
+
+const WIDTH:    f64 = 600.0;
+const HEIGHT:   f64 = 220.0;
+const PAD_TOP:  f64 = 20.0;
+const PAD_BOT:  f64 = 28.0;
+const GROUP_GAP: f64 = 0.3; // fraction de la largeur d'un groupe laissée vide
+
+/// Arrondit le maximum de l'axe à une valeur "ronde" (1, 2, 5 × 10ⁿ).
+pub fn nice_max(max: f64) -> f64 {
+    if max <= 0.0 || !max.is_finite() {
+        return 1.0;
+    }
+    let exp = 10f64.powf(max.log10().floor());
+    let f = max / exp;
+    let nice = if f <= 1.0 {
+        1.0
+    } else if f <= 2.0 {
+        2.0
+    } else if f <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * exp
+}
+
+/// Hauteur en pixels d'une barre de valeur `value` pour un axe allant jusqu'à `axis_max`.
+pub fn bar_height(value: f64, axis_max: f64) -> f64 {
+    let plot_h = HEIGHT - PAD_TOP - PAD_BOT;
+    if axis_max <= 0.0 {
+        return 0.0;
+    }
+    (value.max(0.0) / axis_max * plot_h).min(plot_h)
+}
+
+// ─── Composant ────────────────────────────────────────────────────────────────
+
+#[component]
+pub fn BarChart(
+    series: Vec<BarSeries>,
+    groups: Vec<BarGroup>,
+    /// Formatage de la valeur affichée au-dessus de chaque barre.
+    format_value: fn(f64) -> String,
+) -> impl IntoView {
+    let theme_ctx = use_context::<ThemeCtx>().expect("ThemeCtx manquant");
+    let is_dark = Memo::new(move |_| theme_ctx.theme.get().is_dark());
+
+    let max = groups
+        .iter()
+        .flat_map(|g| g.values.iter().copied())
+        .fold(0.0_f64, f64::max);
+    let axis_max = nice_max(max);
+
+    let n_groups = groups.len().max(1) as f64;
+    let n_series = series.len().max(1) as f64;
+    let group_w  = WIDTH / n_groups;
+    let bar_w    = group_w * (1.0 - GROUP_GAP) / n_series;
+    let baseline = HEIGHT - PAD_BOT;
+
+    let legend = series.clone();
+
+    view! {
+        <div class="space-y-2">
+            <svg
+                viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+                class="w-full h-auto"
+                role="img"
+            >
+                // Ligne de base
+                <line
+                    x1="0" x2=WIDTH.to_string()
+                    y1=baseline.to_string() y2=baseline.to_string()
+                    stroke=move || if is_dark.get() { "#4b5563" } else { "#d1d5db" }
+                    stroke-width="1"
+                />
+                {groups.into_iter().enumerate().map(|(gi, g)| {
+                    let gx = gi as f64 * group_w + group_w * GROUP_GAP / 2.0;
+                    let label_x = gi as f64 * group_w + group_w / 2.0;
+                    let bars = g.values.iter().copied().enumerate().map(|(si, v)| {
+                        let s  = series.get(si).copied();
+                        let h  = bar_height(v, axis_max);
+                        let x  = gx + si as f64 * bar_w;
+                        let y  = baseline - h;
+                        view! {
+                            <g>
+                                <rect
+                                    x=x.to_string() y=y.to_string()
+                                    width=(bar_w * 0.9).to_string() height=h.to_string()
+                                    rx="3"
+                                    fill=move || match s {
+                                        Some(s) if is_dark.get() => s.dark,
+                                        Some(s)                  => s.light,
+                                        None                     => "#9ca3af",
+                                    }
+                                >
+                                    <title>{format!("{} : {}", s.map(|s| s.name).unwrap_or(""), format_value(v))}</title>
+                                </rect>
+                                <text
+                                    x=(x + bar_w * 0.45).to_string() y=(y - 4.0).to_string()
+                                    text-anchor="middle" font-size="10"
+                                    fill=move || if is_dark.get() { "#d1d5db" } else { "#374151" }
+                                >
+                                    {format_value(v)}
+                                </text>
+                            </g>
+                        }
+                    }).collect_view();
+                    view! {
+                        <g>
+                            {bars}
+                            <text
+                                x=label_x.to_string() y=(HEIGHT - 8.0).to_string()
+                                text-anchor="middle" font-size="11" font-weight="600"
+                                fill=move || if is_dark.get() { "#9ca3af" } else { "#4b5563" }
+                            >
+                                {g.label.clone()}
+                            </text>
+                        </g>
+                    }
+                }).collect_view()}
+            </svg>
+
+            // Légende (masquée s'il n'y a qu'une série)
+            {(legend.len() > 1).then(|| view! {
+                <div class="flex flex-wrap justify-center gap-4 text-xs \
+                            text-gray-600 dark:text-gray-400">
+                    {legend.into_iter().map(|s| view! {
+                        <span class="flex items-center gap-1.5">
+                            <span
+                                class="inline-block w-3 h-3 rounded-sm"
+                                style=move || format!(
+                                    "background:{};",
+                                    if is_dark.get() { s.dark } else { s.light },
+                                )
+                            />
+                            {s.name}
+                        </span>
+                    }).collect_view()}
+                </div>
+            })}
+        </div>
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_max() {
+        assert_eq!(nice_max(0.0), 1.0);
+        assert_eq!(nice_max(-5.0), 1.0);
+        assert_eq!(nice_max(7.0), 10.0);
+        assert_eq!(nice_max(13.0), 20.0);
+        assert_eq!(nice_max(45.0), 50.0);
+        assert_eq!(nice_max(100.0), 100.0);
+        assert_eq!(nice_max(1_250_000.0), 2_000_000.0);
+    }
+
+    #[test]
+    fn test_bar_height() {
+        let plot_h = HEIGHT - PAD_TOP - PAD_BOT;
+        assert_eq!(bar_height(0.0, 10.0), 0.0);
+        assert_eq!(bar_height(10.0, 10.0), plot_h);
+        assert_eq!(bar_height(5.0, 10.0), plot_h / 2.0);
+        assert_eq!(bar_height(-3.0, 10.0), 0.0);
+        assert_eq!(bar_height(5.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,181 @@
+/// Boîte de confirmation intégrée, remplaçant `window.confirm()`.
+///
+/// `use_confirm().ask(..)` renvoie un futur résolu à `true` seulement sur clic
+/// (ou Entrée) du bouton de confirmation ; Échap, « Foana » et le fond annulent.
+use js_sys::{Function, Promise};
+use leptos::prelude::*;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::components::{icons::IconAlertTriangle, modal_wrapper::ModalWrapper};
+use crate::i18n::tr;
+
+// ─── Contexte ─────────────────────────────────────────────────────────────────
+
+/// Contenu d'une demande de confirmation.
+#[derive(Clone)]
+pub struct ConfirmOptions {
+    pub title:         String,
+    pub message:       String,
+    pub confirm_label: String,
+    /// Action destructrice : bouton rouge, focus initial sur « Foana ».
+    pub danger:        bool,
+}
+
+/// Fourni par `MainApp`, qui affiche `ConfirmDialog` tant qu'une demande est ouverte.
+#[derive(Clone, Copy)]
+pub struct ConfirmCtx {
+    pub request: RwSignal<Option<ConfirmOptions>>,
+    /// `resolve` de la promesse attendue par l'appelant.
+    resolve:     StoredValue<Option<Function>, LocalStorage>,
+}
+
+impl ConfirmCtx {
+    pub fn new() -> Self {
+        Self {
+            request: RwSignal::new(None),
+            resolve: StoredValue::new_local(None),
+        }
+    }
+
+    /// Ouvre la boîte et attend la réponse. Une demande encore ouverte est annulée.
+    pub async fn ask(self, opts: ConfirmOptions) -> bool {
+        self.answer(false);
+        let promise = Promise::new(&mut |resolve, _reject| {
+            self.resolve.set_value(Some(resolve));
+        });
+        self.request.set(Some(opts));
+        JsFuture::from(promise)
+            .await
+            .ok()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Ferme la boîte et débloque `ask` avec `ok`.
+    pub fn answer(self, ok: bool) {
+        self.request.set(None);
+        let mut resolve = None;
+        self.resolve.update_value(|r| resolve = r.take());
+        if let Some(resolve) = resolve {
+            let _ = resolve.call1(&JsValue::NULL, &JsValue::from_bool(ok));
+        }
+    }
+}
+
+impl Default for ConfirmCtx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn use_confirm() -> ConfirmCtx {
+    use_context::<ConfirmCtx>().expect("ConfirmCtx manquant")
+}
+
+// ─── Composant ────────────────────────────────────────────────────────────────
+
+#[component]
+pub fn ConfirmDialog(opts: ConfirmOptions) -> impl IntoView {
+    let ctx = use_confirm();
+
+    let cancel_ref:  NodeRef<leptos::html::Button> = NodeRef::new();
+    let confirm_ref: NodeRef<leptos::html::Button> = NodeRef::new();
+
+    // Focus initial : « Foana » pour une action destructrice
+    let danger = opts.danger;
+    Effect::new(move |_| {
+        let target = if danger { cancel_ref.get() } else { confirm_ref.get() };
+        if let Some(el) = target {
+            let _ = el.focus();
+        }
+    });
+
+    // Échap annule ; Tab reste entre les deux boutons
+    let on_keydown = move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+        "Escape" => {
+            ev.prevent_default();
+            ctx.answer(false);
+        }
+        "Tab" => {
+            ev.prevent_default();
+            let (Some(cancel), Some(confirm)) = (cancel_ref.get(), confirm_ref.get()) else {
+                return;
+            };
+            let active = web_sys::window()
+                .and_then(|w| w.document())
+                .and_then(|d| d.active_element());
+            let on_cancel = active.is_some_and(|a| cancel.is_same_node(Some(a.as_ref())));
+            let _ = if on_cancel { confirm.focus() } else { cancel.focus() };
+        }
+        _ => {}
+    };
+
+    let confirm_class = if danger {
+        "bg-red-600 hover:bg-red-700 focus:ring-red-400"
+    } else {
+        "bg-blue-600 hover:bg-blue-700 focus:ring-blue-400"
+    };
+
+    view! {
+        <ModalWrapper
+            on_close=Callback::new(move |()| ctx.answer(false))
+            card_class="max-w-sm overflow-hidden"
+        >
+            <div
+                role="alertdialog"
+                aria-modal="true"
+                aria-labelledby="confirm-dialog-title"
+                aria-describedby="confirm-dialog-message"
+                class="px-6 py-5 space-y-4"
+                on:keydown=on_keydown
+            >
+                <div class="flex items-start gap-3">
+                    {danger.then(|| view! {
+                        <div class="shrink-0 p-2 rounded-full bg-red-50 dark:bg-red-900/30">
+                            <IconAlertTriangle class="w-5 h-5 text-red-500 dark:text-red-400" />
+                        </div>
+                    })}
+                    <div>
+                        <h2 id="confirm-dialog-title"
+                            class="text-base font-bold text-gray-800 dark:text-white">
+                            {opts.title.clone()}
+                        </h2>
+                        <p id="confirm-dialog-message"
+                           class="text-sm text-gray-600 dark:text-gray-300 mt-1">
+                            {opts.message.clone()}
+                        </p>
+                    </div>
+                </div>
+
+                <div class="flex gap-3">
+                    <button
+                        type="button"
+                        node_ref=cancel_ref
+                        on:click=move |_| ctx.answer(false)
+                        class="btn-ripple flex-1 px-4 py-2 text-sm font-medium \
+                               text-gray-600 dark:text-gray-300 \
+                               bg-gray-100 dark:bg-gray-700 \
+                               hover:bg-gray-200 dark:hover:bg-gray-600 \
+                               focus:outline-none focus:ring-2 focus:ring-gray-400 \
+                               rounded-xl transition-colors"
+                    >
+                        {move || tr("action.cancel")}
+                    </button>
+                    <button
+                        type="button"
+                        node_ref=confirm_ref
+                        on:click=move |_| ctx.answer(true)
+                        class=format!(
+                            "btn-ripple flex-1 px-4 py-2 text-sm font-semibold text-white \
+                             focus:outline-none focus:ring-2 {confirm_class} \
+                             rounded-xl transition-colors shadow-sm"
+                        )
+                    >
+                        {opts.confirm_label.clone()}
+                    </button>
+                </div>
+            </div>
+        </ModalWrapper>
+    }
+}
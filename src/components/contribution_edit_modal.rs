@@ -4,11 +4,13 @@ use leptos::prelude::*;
 use crate::{
     components::{
         icons::{IconAlertTriangle, IconX},
+        contribution_modal::period_mismatch,
         modal_wrapper::ModalWrapper,
     },
+    i18n::tr,
     models::contribution::{Contribution, ContributionEditInput, ContributionWithMember},
     services::db_service,
-    utils::format_ariary,
+    utils::{format_ariary_str, MoneyFormat},
 };
 
 #[component]
@@ -27,6 +29,11 @@ pub fn ContributionEditModal(
     let amount_val = RwSignal::new(contrib.get_value().amount.clone());
     let pin_val    = RwSignal::new(String::new());
     let reason_val = RwSignal::new(String::new());
+    // Une cotisation déjà saisie avec dérogation la conserve par défaut
+    let mismatch   = RwSignal::new({
+        let c = contrib.get_value();
+        period_mismatch(&c.payment_date, &c.period)
+    });
 
     let saving  = RwSignal::new(false);
     let erreur: RwSignal<Option<String>> = RwSignal::new(None);
@@ -39,6 +46,7 @@ pub fn ContributionEditModal(
             amount:       amount_val.get(),
             pin:          pin_val.get(),
             reason:       reason_val.get(),
+            allow_mismatch: mismatch.get(),
         };
         saving.set(true);
         erreur.set(None);
@@ -92,7 +100,7 @@ pub fn ContributionEditModal(
                     <div>
                         <span class="text-xs text-gray-400">"Vola"</span>
                         <p class="font-mono font-semibold">
-                            {format_ariary(&contrib.get_value().amount)}
+                            {format_ariary_str(&contrib.get_value().amount, MoneyFormat::with_decimals(2))}
                         </p>
                     </div>
                 </div>
@@ -146,6 +154,17 @@ pub fn ContributionEditModal(
                         prop:value=move || period_val.get()
                         on:input=move |ev| period_val.set(event_target_value(&ev))
                     />
+                    {move || period_mismatch(&date_val.get(), &period_val.get()).then(|| view! {
+                        <label class="mt-2 flex items-start gap-2 text-xs                                       text-amber-700 dark:text-amber-300 cursor-pointer select-none">
+                            <input
+                                type="checkbox"
+                                class="mt-0.5 accent-amber-500"
+                                prop:checked=move || mismatch.get()
+                                on:change=move |ev| mismatch.set(event_target_checked(&ev))
+                            />
+                            "Vanim-potoana tamin'ny taona hafa"
+                        </label>
+                    })}
                 </div>
 
                 // Nouvelle vola
@@ -222,7 +241,7 @@ pub fn ContributionEditModal(
                     on:click=move |_| on_cancel.run(())
                     disabled=move || saving.get()
                 >
-                    "Foana"
+                    {move || tr("action.cancel")}
                 </button>
                 <button
                     class="px-4 py-2 text-sm font-semibold rounded-xl \
@@ -232,7 +251,7 @@ pub fn ContributionEditModal(
                     on:click=on_submit
                     disabled=move || saving.get()
                 >
-                    {move || if saving.get() { "Mitahiry…" } else { "Tehirizina" }}
+                    {move || tr(if saving.get() { "action.saving" } else { "action.save" })}
                 </button>
             </div>
 
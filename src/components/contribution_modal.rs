@@ -1,15 +1,27 @@
 /// Modal d'ajout de cotisation + couche confetti.
+/// Le pied du modal propose aussi le relevé annuel du membre (CSV).
+/// Sans membre imposé (page Archives), un sélecteur de membre précède les champs.
 use js_sys::{Date, Math};
 use leptos::prelude::*;
 
 use crate::{
+    app::{SettingsCtx, ToastProviderCtx},
     components::{
-        icons::{IconAlertTriangle, IconSave, IconX},
+        icons::{IconAlertTriangle, IconFileText, IconSave, IconSearch, IconX},
         modal_wrapper::ModalWrapper,
     },
-    models::contribution::ContributionInput,
+    i18n::tr,
+    models::{
+        contribution::ContributionInput, member::MemberLite, year_summary::MONTH_NAMES,
+    },
     services::db_service,
-    utils::sleep_ms,
+    theme::ThemeCtx,
+    utils::{
+        amount::{amount_to_backend, parse_amount_input},
+        download::download_text,
+        format_ariary_str, sleep_ms, use_debounced_signal, use_reduced_motion, MoneyFormat,
+        RequestGen, SEARCH_DEBOUNCE_MS,
+    },
 };
 
 // ─── Palette confetti ─────────────────────────────────────────────────────────
@@ -20,6 +32,13 @@ const CONFETTI_COLORS: &[&str] = &[
     "#fbbf24", "#34d399", "#60a5fa", "#f87171", "#a78bfa",
 ];
 
+/// Thème sombre : teintes claires, lisibles sur le ciel de nuit.
+const CONFETTI_COLORS_DARK: &[&str] = &[
+    "#fcd34d", "#6ee7b7", "#93c5fd", "#fca5a5", "#c4b5fd",
+    "#f9a8d4", "#5eead4", "#fdba74", "#67e8f9", "#bef264",
+    "#fde68a", "#a7f3d0", "#bfdbfe", "#fecaca", "#ddd6fe",
+];
+
 struct Piece {
     x:        f64,   // left en %
     color:    &'static str,
@@ -29,11 +48,11 @@ struct Piece {
 }
 
 impl Piece {
-    fn random() -> Self {
-        let ci = (Math::random() * CONFETTI_COLORS.len() as f64) as usize;
+    fn random(palette: &'static [&'static str]) -> Self {
+        let ci = (Math::random() * palette.len() as f64) as usize;
         Self {
             x:        Math::random() * 98.0,
-            color:    CONFETTI_COLORS[ci],
+            color:    palette[ci],
             size:     (Math::random() * 7.0 + 5.0) as u32,
             delay:    (Math::random() * 700.0) as u32,
             duration: (Math::random() * 900.0 + 1600.0) as u32,
@@ -60,57 +79,10 @@ impl Piece {
     }
 }
 
-// ─── Formatage du montant ─────────────────────────────────────────────────────
-
-/// Insère des espaces fins comme séparateurs de milliers.
-fn fmt_thousands(s: &str) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    let len = chars.len();
-    let mut r = String::new();
-    for (i, &c) in chars.iter().enumerate() {
-        if i > 0 && (len - i) % 3 == 0 {
-            r.push('\u{202f}'); // espace fine insécable
-        }
-        r.push(c);
-    }
-    r
-}
-
-/// Formate la saisie brute en "1 234,50".
-///
-/// Accepte uniquement chiffres + virgule ; virgule unique ; 2 décimales max.
-pub fn fmt_amount(raw: &str) -> String {
-    let mut int_s = String::new();
-    let mut dec_s = String::new();
-    let mut has_comma = false;
-
-    for c in raw.chars() {
-        if c.is_ascii_digit() {
-            if has_comma {
-                if dec_s.len() < 2 { dec_s.push(c); }
-            } else {
-                int_s.push(c);
-            }
-        } else if c == ',' && !has_comma {
-            has_comma = true;
-        }
-    }
-
-    let int_fmt = fmt_thousands(&int_s);
-    if has_comma { format!("{},{}", int_fmt, dec_s) } else { int_fmt }
-}
-
-/// "1 234,50" (espace fine) → "1234.50" pour le backend.
-fn amount_to_backend(display: &str) -> String {
-    display
-        .chars()
-        .filter(|&c| c.is_ascii_digit() || c == ',')
-        .collect::<String>()
-        .replace(',', ".")
-}
+// ─── Helpers ──────────────────────────────────────────────────────────────────
 
 /// Date d'aujourd'hui au format "YYYY-MM-DD".
-fn today() -> String {
+pub fn today() -> String {
     let d = Date::new_0();
     format!(
         "{:04}-{:02}-{:02}",
@@ -120,20 +92,80 @@ fn today() -> String {
     )
 }
 
+/// Date proposée à l'ouverture : aujourd'hui, ou le 1er janvier de `year` quand
+/// l'année consultée n'est pas celle de `today` (rattrapage d'une année passée).
+pub fn default_payment_date(today: &str, year: Option<i32>) -> String {
+    match year {
+        Some(y) if today.get(..4) != Some(&format!("{y:04}")) => format!("{y:04}-01-01"),
+        _ => today.to_string(),
+    }
+}
+
+/// Période canonique composée par les deux listes du formulaire : `part` vaut ""
+/// (année entière), "01".."12" (mois) ou "T1".."T4" (trimestre).
+pub fn compose_period(year: i32, part: &str) -> String {
+    if part.is_empty() {
+        format!("{year:04}")
+    } else {
+        format!("{year:04}-{part}")
+    }
+}
+
+/// Inverse de `compose_period` ("2025-03" → (2025, "03")) ; `None` si la période
+/// n'est pas sous forme canonique.
+pub fn split_period(period: &str) -> Option<(i32, String)> {
+    let period = period.trim();
+    let (year, part) = period.split_once('-').unwrap_or((period, ""));
+    if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let valid = part.is_empty()
+        || matches!(part, "T1" | "T2" | "T3" | "T4")
+        || (part.len() == 2 && part.parse::<u32>().is_ok_and(|m| (1..=12).contains(&m)));
+    valid.then(|| (year.parse().unwrap_or_default(), part.to_string()))
+}
+
+/// Vrai si la période commence par une autre année que `payment_date`
+/// ("2024" ou "2024-T4" payé le "2025-01-05") : le backend exige alors `allow_mismatch`.
+pub fn period_mismatch(payment_date: &str, period: &str) -> bool {
+    match (payment_date.get(..4), period.trim().get(..4)) {
+        (Some(d), Some(p)) if p.bytes().all(|b| b.is_ascii_digit()) => d != p,
+        _ => false,
+    }
+}
+
 // ─── Couche Confetti ──────────────────────────────────────────────────────────
 
 /// Couche fixe qui affiche les confettis quand `active` passe à `true`.
-/// Se désactive automatiquement après l'animation.
+/// Se désactive automatiquement après l'animation. Animations réduites : simple
+/// message fixe, sans pièce animée.
 #[component]
 pub fn ConfettiLayer(active: RwSignal<bool>) -> impl IntoView {
     let pieces: RwSignal<Vec<String>> = RwSignal::new(vec![]);
+    let flash:  RwSignal<bool>        = RwSignal::new(false);
+    let theme = use_context::<ThemeCtx>().expect("ThemeCtx manquant");
+    let reduced = use_reduced_motion();
 
     Effect::new(move |_| {
         if !active.get() {
             return;
         }
-        // Génère 60 pièces
-        let styles: Vec<String> = (0..60).map(|_| Piece::random().style_str()).collect();
+        if reduced.get_untracked() {
+            flash.set(true);
+            leptos::task::spawn_local(async move {
+                sleep_ms(1500).await;
+                active.set(false);
+                flash.set(false);
+            });
+            return;
+        }
+        // Génère 60 pièces aux couleurs du thème
+        let palette = if theme.theme.get_untracked().is_dark() {
+            CONFETTI_COLORS_DARK
+        } else {
+            CONFETTI_COLORS
+        };
+        let styles: Vec<String> = (0..60).map(|_| Piece::random(palette).style_str()).collect();
         pieces.set(styles);
 
         // Efface après la dernière animation (max ~2.4 s + 0.7 s délai = 3.1 s)
@@ -149,12 +181,55 @@ pub fn ConfettiLayer(active: RwSignal<bool>) -> impl IntoView {
             {move || pieces.get().into_iter().map(|css_str| {
                 view! { <div style={css_str} /> }
             }).collect_view()}
+            {move || flash.get().then(|| view! {
+                <div class="absolute top-6 left-1/2 -translate-x-1/2 px-4 py-2 rounded-xl \
+                            text-sm font-semibold shadow-lg \
+                            bg-emerald-600 text-white dark:bg-emerald-500">
+                    "✓ Voatahiry ny fandoavana"
+                </div>
+            })}
         </div>
     }
 }
 
 // ─── Modal Cotisation ─────────────────────────────────────────────────────────
 
+/// Signaux du modal touchés par un enregistrement.
+#[derive(Clone, Copy)]
+struct SaveTarget {
+    open:            RwSignal<bool>,
+    refresh_ctr:     RwSignal<u32>,
+    confetti_active: RwSignal<bool>,
+    f_erreur:        RwSignal<Option<String>>,
+    f_loading:       RwSignal<bool>,
+    toasts:          ToastProviderCtx,
+}
+
+/// Enregistre la cotisation. Échec passager (base occupée…) : « Avereno » renvoie
+/// la même saisie tant que le modal est ouvert.
+fn save_contribution(input: ContributionInput, nom: String, t: SaveTarget) {
+    t.f_loading.set(true);
+    t.f_erreur.set(None);
+    leptos::task::spawn_local(async move {
+        match db_service::create_contribution(&input).await {
+            Ok(_) => {
+                t.open.set(false);
+                t.refresh_ctr.update(|n| *n += 1);
+                t.confetti_active.set(true);
+                t.toasts.success(format!("Voatahiry ny rakitra an'i {nom}"));
+            }
+            // Saisie refusée : le message reste dans le formulaire
+            Err(e) if e.is_input_error() => t.f_erreur.set(Some(e.to_string())),
+            Err(e) => t.toasts.error_with_retry(e.to_string(), move || {
+                if t.open.try_get_untracked() == Some(true) {
+                    save_contribution(input.clone(), nom.clone(), t);
+                }
+            }),
+        }
+        t.f_loading.set(false);
+    });
+}
+
 const LABEL: &str = "block text-xs font-semibold text-gray-600 dark:text-gray-400 mb-1";
 const INPUT: &str = "w-full px-3 py-2 text-sm \
                      bg-gray-50 dark:bg-gray-700/60 \
@@ -166,10 +241,16 @@ const INPUT: &str = "w-full px-3 py-2 text-sm \
 /// Modal d'ajout de cotisation pour un membre.
 #[component]
 pub fn ContributionModal(
-    /// ID du membre concerné.
-    membre_id:       i64,
+    /// ID du membre concerné ; absent : le membre est choisi dans le modal.
+    #[prop(optional)]
+    membre_id:       Option<i64>,
     /// Nom affiché dans le titre du modal.
+    #[prop(optional)]
     membre_nom:      String,
+    /// Année consultée (Archives) : date proposée au 1er janvier si ce n'est
+    /// pas l'année en cours.
+    #[prop(optional)]
+    year:            Option<i32>,
     /// Signal d'ouverture — ferme le modal quand `false`.
     open:            RwSignal<bool>,
     /// Incrémenter pour rafraîchir la liste des membres.
@@ -177,66 +258,159 @@ pub fn ContributionModal(
     /// Passe à `true` pour déclencher les confettis.
     confetti_active: RwSignal<bool>,
 ) -> impl IntoView {
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant").settings;
+
+    // Période par défaut des Paramètres, sinon l'année du jour
+    let (initial_year, initial_part) = settings
+        .get_untracked()
+        .and_then(|s| split_period(&s.default_period))
+        .unwrap_or_else(|| (today()[..4].parse().unwrap_or_default(), String::new()));
+
     // ── Champs du formulaire ──────────────────────────────────────────────────
-    let f_date:    RwSignal<String>         = RwSignal::new(today());
-    let f_period:  RwSignal<String>         = RwSignal::new(String::new());
+    let f_date:    RwSignal<String>         = RwSignal::new(default_payment_date(&today(), year));
+    // Période : année + mois/trimestre facultatif, composés en forme canonique
+    let f_period_year: RwSignal<i32>        = RwSignal::new(initial_year);
+    let f_period_part: RwSignal<String>     = RwSignal::new(initial_part);
+    let f_period = Memo::new(move |_| compose_period(f_period_year.get(), &f_period_part.get()));
+    let f_mismatch: RwSignal<bool>          = RwSignal::new(false);
+    let f_operator: RwSignal<String>        = RwSignal::new(String::new());
     let f_erreur:  RwSignal<Option<String>> = RwSignal::new(None);
     let f_loading: RwSignal<bool>           = RwSignal::new(false);
 
     // Montant : stocke la chaîne formatée "1 234,50" directement
     let f_amount:    RwSignal<String>              = RwSignal::new(String::new());
+    // Des caractères de la dernière saisie ont été ignorés
+    let f_amount_dropped: RwSignal<bool>           = RwSignal::new(false);
     let amount_node: NodeRef<leptos::html::Input>  = NodeRef::new();
 
+    // ── Sélecteur de membre (sans `membre_id`) ────────────────────────────────
+    let picked:       RwSignal<Option<MemberLite>> = RwSignal::new(None);
+    let pick_query:   RwSignal<String>             = RwSignal::new(String::new());
+    let pick_results: RwSignal<Vec<MemberLite>>    = RwSignal::new(vec![]);
+    let pick_q = use_debounced_signal(pick_query, SEARCH_DEBOUNCE_MS);
+    let pick_gen = RequestGen::new();
+    Effect::new(move |_| {
+        let q = pick_q.get();
+        let g = pick_gen.next();
+        if membre_id.is_some() || q.trim().is_empty() {
+            pick_results.set(vec![]);
+            return;
+        }
+        leptos::task::spawn_local(async move {
+            let res = db_service::search_members_lite(&q).await;
+            if pick_gen.is_current(g) {
+                pick_results.set(res.unwrap_or_default());
+            }
+        });
+    });
+
+    // ── Période : suit l'année de la date ─────────────────────────────────────
+    let date_year = Memo::new(move |_| {
+        f_date.get().get(..4).and_then(|y| y.parse::<i32>().ok()).unwrap_or(initial_year)
+    });
+    Effect::new(move |prev: Option<i32>| {
+        let year = date_year.get();
+        // Suit la date tant que la période reprend l'ancienne année
+        if prev.is_some_and(|prev| f_period_year.get_untracked() == prev) {
+            f_period_year.set(year);
+        }
+        year
+    });
+    // Années proposées : autour de la date, plus l'année choisie si elle en sort
+    let period_years = Memo::new(move |_| {
+        let (base, chosen) = (date_year.get(), f_period_year.get());
+        (base.min(chosen).min(base - 3)..=base.max(chosen).max(base + 1)).rev().collect::<Vec<_>>()
+    });
+    let quarters = Memo::new(move |_| {
+        settings.with(|s| s.as_ref().is_none_or(|s| s.quarter_periods))
+    });
+
     // ── Gestion du montant ────────────────────────────────────────────────────
     let on_amount_input = move |_| {
         let el = match amount_node.get() { Some(e) => e, None => return };
         let raw = el.value();
-        let formatted = fmt_amount(&raw);
+        let parsed    = parse_amount_input(&raw);
+        let formatted = parsed.display();
+        f_amount_dropped.set(parsed.dropped);
         f_amount.set(formatted.clone());
         el.set_value(&formatted);
         let pos = formatted.len() as u32;
         let _ = el.set_selection_range(pos, pos);
     };
 
-    // Empêche toute saisie autre que chiffres et virgule
+    // Montant rapide : même mise en forme que la saisie au clavier
+    let pick_amount = move |backend: &str| {
+        f_amount_dropped.set(false);
+        f_amount.set(parse_amount_input(backend).display());
+    };
+    let quick_amounts = Memo::new(move |_| {
+        settings.with(|s| s.as_ref().map(|s| s.quick_amounts.clone()).unwrap_or_default())
+    });
+    // Opérateurs des Paramètres ; liste vide = saisie libre
+    let operators = Memo::new(move |_| {
+        settings.with(|s| s.as_ref().map(|s| s.operators.clone()).unwrap_or_default())
+    });
+
+    // Au clavier : chiffres et séparateur décimal seulement (le collage passe par `parse_amount_input`)
     let on_amount_keydown = move |ev: web_sys::KeyboardEvent| {
         let k = ev.key();
         let allowed = k.len() > 1  // touches de contrôle (Backspace, ArrowLeft…)
             || k.chars().all(|c| c.is_ascii_digit())
-            || k == ",";
+            || k == ","
+            || k == ".";
         if !allowed { ev.prevent_default(); }
     };
 
     // ── Soumission ────────────────────────────────────────────────────────────
+    let toast_nom = StoredValue::new(membre_nom);
     let soumettre = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
 
+        let Some(member_id) = membre_id.or_else(|| picked.get().map(|m| m.id)) else {
+            f_erreur.set(Some("Mifidiana mpikambana.".into()));
+            return;
+        };
         let amount_backend = amount_to_backend(&f_amount.get());
-        if amount_backend.is_empty() || amount_backend == "." {
+        if amount_backend.is_empty() {
             f_erreur.set(Some("Ampidiro ny vola marina.".into()));
             return;
         }
 
         let input = ContributionInput {
-            member_id:    membre_id,
+            member_id,
             payment_date: f_date.get(),
-            period:       f_period.get().trim().to_string(),
+            period:       f_period.get(),
             amount:       amount_backend,
+            allow_mismatch: f_mismatch.get(),
+            recorded_by:  Some(f_operator.get().trim().to_string()).filter(|o| !o.is_empty()),
         };
 
-        f_loading.set(true);
-        f_erreur.set(None);
+        let nom = picked.get().map(|m| m.full_name).unwrap_or_else(|| toast_nom.get_value());
+        let target = SaveTarget { open, refresh_ctr, confetti_active, f_erreur, f_loading, toasts };
+        save_contribution(input, nom, target);
+    };
 
+    // ── Relevé annuel ─────────────────────────────────────────────────────────
+    let f_stmt_year: RwSignal<String> = RwSignal::new(today()[..4].to_string());
+    let stmt_loading: RwSignal<bool>  = RwSignal::new(false);
+    let export_statement = move |_| {
+        let Some(membre_id) = membre_id else { return };
+        let Ok(year) = f_stmt_year.get().trim().parse::<i32>() else {
+            toasts.error("Taona tsy mety.");
+            return;
+        };
+        stmt_loading.set(true);
         leptos::task::spawn_local(async move {
-            match db_service::create_contribution(&input).await {
-                Ok(_) => {
-                    open.set(false);
-                    refresh_ctr.update(|n| *n += 1);
-                    confetti_active.set(true);
-                }
-                Err(e) => f_erreur.set(Some(e)),
+            let filename = format!("tatitra-{membre_id}-{year}.csv");
+            let res = match db_service::export_member_statement_csv(membre_id, year).await {
+                Ok(csv) => download_text(&csv, "text/csv;charset=utf-8", &filename),
+                Err(e)  => Err(e),
+            };
+            if let Err(e) = res {
+                toasts.error(e);
             }
-            f_loading.set(false);
+            stmt_loading.set(false);
         });
     };
 
@@ -255,7 +429,11 @@ pub fn ContributionModal(
                             "Rakitra vaovao"
                         </h2>
                         <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5">
-                            {membre_nom.clone()}
+                            {move || match (membre_id, picked.get()) {
+                                (None, Some(m)) => m.full_name,
+                                (None, None)    => "Mifidiana mpikambana".to_string(),
+                                (Some(_), _)    => toast_nom.get_value(),
+                            }}
                         </p>
                     </div>
                     <button
@@ -271,6 +449,73 @@ pub fn ContributionModal(
                 // ── Formulaire ───────────────────────────────────────────────
                 <form on:submit=soumettre class="px-6 py-5 space-y-4">
 
+                    // Membre (Archives) : recherche par nom ou carte
+                    {membre_id.is_none().then(|| view! {
+                        <div>
+                            <label class=LABEL>"Mpikambana *"</label>
+                            {move || match picked.get() {
+                                Some(m) => view! {
+                                    <div class="flex items-center justify-between gap-2 px-3 py-2 text-sm \
+                                                rounded-xl bg-emerald-50 dark:bg-emerald-900/30 \
+                                                text-emerald-800 dark:text-emerald-200">
+                                        <span class="truncate">
+                                            {m.full_name}
+                                            <span class="font-mono text-xs opacity-75">
+                                                {format!(" · {}", m.card_number)}
+                                            </span>
+                                        </span>
+                                        <button
+                                            type="button"
+                                            on:click=move |_| picked.set(None)
+                                            class="shrink-0 text-xs font-semibold \
+                                                   text-emerald-700 dark:text-emerald-300 hover:underline"
+                                        >
+                                            "Hanova"
+                                        </button>
+                                    </div>
+                                }.into_any(),
+                                None => view! {
+                                    <div class="relative">
+                                        <span class="absolute left-3 top-1/2 -translate-y-1/2 \
+                                                     text-gray-400 pointer-events-none">
+                                            <IconSearch class="w-4 h-4" />
+                                        </span>
+                                        <input
+                                            type="text"
+                                            placeholder="Anarana na laharana karatra…"
+                                            class=format!("{INPUT} pl-9")
+                                            prop:value=move || pick_query.get()
+                                            on:input=move |ev| pick_query.set(event_target_value(&ev))
+                                        />
+                                    </div>
+                                    <ul class="mt-1 max-h-48 overflow-y-auto rounded-xl empty:hidden \
+                                               border border-gray-100 dark:border-gray-700 \
+                                               divide-y divide-gray-100 dark:divide-gray-700/60">
+                                        {move || pick_results.get().into_iter().map(|m| {
+                                            let label = format!("{} · {}", m.full_name, m.card_number);
+                                            view! {
+                                                <li>
+                                                    <button
+                                                        type="button"
+                                                        on:click=move |_| {
+                                                            picked.set(Some(m.clone()));
+                                                            pick_query.set(String::new());
+                                                        }
+                                                        class="w-full text-left px-3 py-1.5 text-sm \
+                                                               text-gray-700 dark:text-gray-200 \
+                                                               hover:bg-emerald-50 dark:hover:bg-emerald-900/30"
+                                                    >
+                                                        {label}
+                                                    </button>
+                                                </li>
+                                            }
+                                        }).collect_view()}
+                                    </ul>
+                                }.into_any(),
+                            }}
+                        </div>
+                    })}
+
                     // Date + Période côte à côte
                     <div class="grid grid-cols-2 gap-3">
                         <div>
@@ -284,16 +529,81 @@ pub fn ContributionModal(
                         </div>
                         <div>
                             <label class=LABEL>"Vanim-potoana *"</label>
-                            <input
-                                type="text" required
-                                placeholder="ohatra : 2025"
-                                class=INPUT
-                                prop:value=move || f_period.get()
-                                on:input=move |ev| f_period.set(event_target_value(&ev))
-                            />
+                            <div class="flex gap-2">
+                                <select
+                                    class=INPUT
+                                    aria-label="Taona"
+                                    on:change=move |ev| {
+                                        if let Ok(y) = event_target_value(&ev).parse() {
+                                            f_period_year.set(y);
+                                        }
+                                    }
+                                >
+                                    {move || period_years.get().into_iter().map(|y| view! {
+                                        <option
+                                            value=y.to_string()
+                                            selected=move || f_period_year.get() == y
+                                        >
+                                            {y}
+                                        </option>
+                                    }).collect_view()}
+                                </select>
+                                <select
+                                    class=INPUT
+                                    aria-label="Volana na telovolana"
+                                    on:change=move |ev| f_period_part.set(event_target_value(&ev))
+                                >
+                                    <option value="" selected=move || f_period_part.get().is_empty()>
+                                        "Taona manontolo"
+                                    </option>
+                                    <optgroup label="Volana">
+                                        {MONTH_NAMES.iter().enumerate().map(|(i, name)| {
+                                            let value = format!("{:02}", i + 1);
+                                            let current = value.clone();
+                                            view! {
+                                                <option
+                                                    value=value
+                                                    selected=move || f_period_part.get() == current
+                                                >
+                                                    {*name}
+                                                </option>
+                                            }
+                                        }).collect_view()}
+                                    </optgroup>
+                                    {move || quarters.get().then(|| view! {
+                                        <optgroup label="Telovolana">
+                                            {(1..=4).map(|q| {
+                                                let value = format!("T{q}");
+                                                let current = value.clone();
+                                                view! {
+                                                    <option
+                                                        value=value
+                                                        selected=move || f_period_part.get() == current
+                                                    >
+                                                        {format!("Telovolana {q}")}
+                                                    </option>
+                                                }
+                                            }).collect_view()}
+                                        </optgroup>
+                                    })}
+                                </select>
+                            </div>
                         </div>
                     </div>
 
+                    // Période d'une autre année : dérogation explicite
+                    {move || period_mismatch(&f_date.get(), &f_period.get()).then(|| view! {
+                        <label class="flex items-start gap-2 text-xs text-amber-700 dark:text-amber-300                                       cursor-pointer select-none">
+                            <input
+                                type="checkbox"
+                                class="mt-0.5 accent-amber-500"
+                                prop:checked=move || f_mismatch.get()
+                                on:change=move |ev| f_mismatch.set(event_target_checked(&ev))
+                            />
+                            "Vanim-potoana tamin'ny taona hafa (trosa taloha na aloa mialoha)"
+                        </label>
+                    })}
+
                     // Montant
                     <div>
                         <label class=LABEL>"Vola (Ariary) *"</label>
@@ -319,6 +629,30 @@ pub fn ContributionModal(
                                 "Ar"
                             </span>
                         </div>
+                        // Montants rapides (Paramètres)
+                        {move || {
+                            let amounts = quick_amounts.get();
+                            (!amounts.is_empty()).then(|| view! {
+                                <div class="mt-2 flex flex-wrap gap-1.5">
+                                    {amounts.into_iter().map(|a| {
+                                        let label = format_ariary_str(&a, MoneyFormat::default());
+                                        view! {
+                                            <button
+                                                type="button"
+                                                on:click=move |_| pick_amount(&a)
+                                                class="btn-ripple px-2.5 py-1 text-xs font-mono font-semibold \
+                                                       rounded-full transition-colors \
+                                                       text-emerald-700 dark:text-emerald-300 \
+                                                       bg-emerald-50 dark:bg-emerald-900/30 \
+                                                       hover:bg-emerald-100 dark:hover:bg-emerald-800/40"
+                                            >
+                                                {label}
+                                            </button>
+                                        }
+                                    }).collect_view()}
+                                </div>
+                            })
+                        }}
                         // Aperçu du montant formaté
                         {move || {
                             let v = f_amount.get();
@@ -329,6 +663,51 @@ pub fn ContributionModal(
                                 </p>
                             })
                         }}
+                        {move || f_amount_dropped.get().then(|| view! {
+                            <p class="mt-1 flex items-center gap-1 text-xs text-amber-600 dark:text-amber-400">
+                                <IconAlertTriangle class="w-3.5 h-3.5 shrink-0" />
+                                "Nisy soratra tsy voaray : hamarino ny vola."
+                            </p>
+                        })}
+                    </div>
+
+                    // Opérateur de saisie
+                    <div>
+                        <label class=LABEL>"Nampiditra"</label>
+                        {move || {
+                            let ops = operators.get();
+                            if ops.is_empty() {
+                                view! {
+                                    <input
+                                        type="text"
+                                        placeholder="Anaran'ny mpampiditra"
+                                        class=INPUT
+                                        prop:value=move || f_operator.get()
+                                        on:input=move |ev| f_operator.set(event_target_value(&ev))
+                                    />
+                                }.into_any()
+                            } else {
+                                view! {
+                                    <select
+                                        class=INPUT
+                                        on:change=move |ev| f_operator.set(event_target_value(&ev))
+                                    >
+                                        <option value="">"—"</option>
+                                        {ops.into_iter().map(|o| {
+                                            let (value, current) = (o.clone(), o.clone());
+                                            view! {
+                                                <option
+                                                    value=value
+                                                    selected=move || f_operator.get() == current
+                                                >
+                                                    {o}
+                                                </option>
+                                            }
+                                        }).collect_view()}
+                                    </select>
+                                }.into_any()
+                            }
+                        }}
                     </div>
 
                     // Erreur
@@ -353,7 +732,7 @@ pub fn ContributionModal(
                                    hover:bg-gray-200 dark:hover:bg-gray-600 \
                                    rounded-xl transition-colors"
                         >
-                            "Foana"
+                            {move || tr("action.cancel")}
                         </button>
                         <button
                             type="submit"
@@ -369,13 +748,86 @@ pub fn ContributionModal(
                             view! {
                                 <span class="flex items-center gap-1.5">
                                     <IconSave class="w-4 h-4" />
-                                    "Tehirizina"
+                                    {move || tr("action.save")}
                                 </span>
                             }.into_any()
                         }}
                         </button>
                     </div>
                 </form>
+
+                // ── Relevé annuel (membre imposé uniquement) ─────────────────
+                {membre_id.is_some().then(|| view! {
+                <div class="flex items-center justify-between gap-3 px-6 py-3 \
+                            border-t border-gray-100 dark:border-gray-700 \
+                            bg-gray-50/60 dark:bg-gray-800/40">
+                    <span class="text-xs font-semibold text-gray-600 dark:text-gray-400">
+                        "Tatitra isan-taona…"
+                    </span>
+                    <div class="flex items-center gap-2">
+                        <input
+                            type="number" min="2000" max="2100"
+                            aria-label="Taona"
+                            class="w-20 px-2 py-1 text-xs font-mono \
+                                   bg-white dark:bg-gray-700/60 \
+                                   border border-gray-200 dark:border-gray-600 \
+                                   rounded-lg text-gray-800 dark:text-white \
+                                   focus:outline-none focus:ring-2 focus:ring-emerald-400"
+                            prop:value=move || f_stmt_year.get()
+                            on:input=move |ev| f_stmt_year.set(event_target_value(&ev))
+                        />
+                        <button
+                            type="button"
+                            disabled=move || stmt_loading.get()
+                            on:click=export_statement
+                            class="btn-ripple flex items-center gap-1 px-2.5 py-1 text-xs \
+                                   font-semibold rounded-lg transition-colors \
+                                   text-emerald-700 dark:text-emerald-300 \
+                                   hover:bg-emerald-50 dark:hover:bg-emerald-900/30 \
+                                   disabled:opacity-60 disabled:cursor-wait"
+                        >
+                            <IconFileText class="w-3.5 h-3.5" />
+                            {move || if stmt_loading.get() { "Manondrana…" } else { "CSV" }}
+                        </button>
+                    </div>
+                </div>
+                })}
         </ModalWrapper>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periode_composee() {
+        assert_eq!(compose_period(2025, ""), "2025");
+        assert_eq!(compose_period(2025, "03"), "2025-03");
+        assert_eq!(compose_period(2025, "T2"), "2025-T2");
+
+        for period in ["2025", "2025-03", "2025-12", "2025-T4"] {
+            let (year, part) = split_period(period).unwrap();
+            assert_eq!(compose_period(year, &part), period);
+        }
+        assert_eq!(split_period(" 2024-T1 "), Some((2024, "T1".into())));
+        for period in ["", "25", "2025-3", "2025-13", "2025-T5", "Janvier 2025"] {
+            assert_eq!(split_period(period), None, "{period:?}");
+        }
+    }
+
+    #[test]
+    fn periode_autre_annee() {
+        assert!(!period_mismatch("2025-03-01", "2025"));
+        assert!(!period_mismatch("2025-03-01", " 2025-T1"));
+        assert!(period_mismatch("2025-01-05", "2024-T4"));
+        assert!(!period_mismatch("2025-01-05", ""));
+    }
+
+    #[test]
+    fn date_par_defaut() {
+        assert_eq!(default_payment_date("2025-06-15", None), "2025-06-15");
+        assert_eq!(default_payment_date("2025-06-15", Some(2025)), "2025-06-15");
+        assert_eq!(default_payment_date("2025-06-15", Some(2023)), "2023-01-01");
+    }
+}
@@ -0,0 +1,61 @@
+/// « À propos de la base » — page Paramètres.
+///
+/// Version du schéma (dernière migration appliquée), nombre de lignes par table
+/// et taille du fichier : de quoi répondre à « quelle version ? » lors d'un
+/// dépannage, sans ouvrir la base à la main. Rechargé après chaque écriture.
+use leptos::prelude::*;
+
+use crate::{
+    app::{DataVersionCtx, ToastProviderCtx},
+    components::{icons::IconDatabase, maintenance_panel::format_size},
+    models::integrity::DbHealth,
+    services::db_service,
+};
+
+#[component]
+pub fn DbHealthPanel() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+
+    let health: RwSignal<Option<DbHealth>> = RwSignal::new(None);
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        leptos::task::spawn_local(async move {
+            match db_service::get_db_health().await {
+                Ok(h) => health.set(Some(h)),
+                Err(e) => toasts.error_with_retry(e, move || data_version.bump()),
+            }
+        });
+    });
+
+    move || health.get().map(|h| view! {
+        <section class="max-w-xl p-4 sm:p-5 \
+                        bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+            <h2 class="text-sm font-semibold text-gray-800 dark:text-white flex items-center gap-2">
+                <IconDatabase class="w-4 h-4 text-gray-500 dark:text-gray-400" />
+                "Momba ny tahiry"
+            </h2>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5 mb-3">
+                {format!(
+                    "Rafitra v{} · {}",
+                    h.schema_version,
+                    format_size(h.size_bytes),
+                )}
+            </p>
+            <dl class="grid grid-cols-2 gap-x-6 gap-y-1 text-sm">
+                {h.tables.into_iter().map(|t| view! {
+                    <div class="flex items-center justify-between gap-3 py-0.5">
+                        <dt class="font-mono text-xs text-gray-500 dark:text-gray-400 truncate">
+                            {t.table}
+                        </dt>
+                        <dd class="font-semibold text-gray-800 dark:text-white tabular-nums">
+                            {t.rows}
+                        </dd>
+                    </div>
+                }).collect_view()}
+            </dl>
+        </section>
+    })
+}
@@ -0,0 +1,66 @@
+/// Raccourci caché Ctrl+Alt+Maj+D : remplit une base vide de données fictives
+/// (`seed_demo_data`) pour présenter l'application sans montrer de vraies fiches.
+///
+/// Aucun bouton visible ; le backend refuse si la base contient déjà des données.
+use leptos::prelude::*;
+
+use crate::{
+    app::{DataVersionCtx, ToastProviderCtx},
+    components::confirm_dialog::{use_confirm, ConfirmOptions},
+    i18n::tr,
+    services::db_service,
+    utils::{format_ariary_str, MoneyFormat},
+};
+
+/// Membres générés par le raccourci.
+const DEMO_MEMBERS: usize = 60;
+/// Années couvertes : l'année en cours et les deux précédentes.
+const DEMO_YEARS: i32 = 3;
+/// Graine fixe : d'une base vide à l'autre, la même démonstration.
+const DEMO_SEED: u64 = 2024;
+
+fn is_shortcut(ev: &web_sys::KeyboardEvent) -> bool {
+    ev.ctrl_key() && ev.alt_key() && ev.shift_key() && ev.key().eq_ignore_ascii_case("d")
+}
+
+#[component]
+pub fn DemoSeedShortcut() -> impl IntoView {
+    let toasts       = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let confirm = use_confirm();
+
+    let listener = window_event_listener(leptos::ev::keydown, move |ev| {
+        if !is_shortcut(&ev) || confirm.request.with_untracked(Option::is_some) {
+            return;
+        }
+        ev.prevent_default();
+        leptos::task::spawn_local(async move {
+            let ok = confirm
+                .ask(ConfirmOptions {
+                    title:         tr("demo.confirm_title"),
+                    message:       tr("demo.confirm_message").replace("{n}", &DEMO_MEMBERS.to_string()),
+                    confirm_label: tr("demo.confirm"),
+                    danger:        false,
+                })
+                .await;
+            if !ok {
+                return;
+            }
+            let year = js_sys::Date::new_0().get_full_year() as i32;
+            let years: Vec<i32> = (year + 1 - DEMO_YEARS..=year).collect();
+            match db_service::seed_demo_data(DEMO_MEMBERS, &years, DEMO_SEED).await {
+                Ok(r) => {
+                    data_version.bump();
+                    toasts.success(
+                        tr("demo.done")
+                            .replace("{members}", &r.members_inserted.to_string())
+                            .replace("{contributions}", &r.contributions_inserted.to_string())
+                            .replace("{total}", &format_ariary_str(&r.total, MoneyFormat::default())),
+                    );
+                }
+                Err(e) => toasts.error(e),
+            }
+        });
+    });
+    on_cleanup(move || listener.remove());
+}
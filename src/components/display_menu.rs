@@ -0,0 +1,65 @@
+/// Petit menu d'affichage de la Navbar : réduction des animations.
+///
+/// Le choix est enregistré dans localStorage (voir `MotionCtx`) ; sans choix,
+/// la préférence `prefers-reduced-motion` du système s'applique.
+use leptos::prelude::*;
+
+use crate::components::icons::IconSliders;
+use crate::theme::MotionCtx;
+use crate::utils::use_reduced_motion;
+
+#[component]
+pub fn DisplayMenu() -> impl IntoView {
+    let motion = use_context::<MotionCtx>().expect("MotionCtx manquant");
+    let reduced = use_reduced_motion();
+    let open = RwSignal::new(false);
+
+    view! {
+        <div class="relative">
+            <button
+                title="Fampisehoana"
+                aria-haspopup="true"
+                aria-expanded=move || open.get().to_string()
+                class="p-2 rounded-lg text-gray-500 dark:text-gray-400 \
+                       hover:text-blue-600 dark:hover:text-blue-400 \
+                       hover:bg-gray-100 dark:hover:bg-gray-800 \
+                       transition-colors"
+                on:click=move |_| open.update(|o| *o = !*o)
+            >
+                <IconSliders class="w-4 h-4" />
+            </button>
+            {move || open.get().then(|| view! {
+                // Fond transparent : un clic ailleurs ferme le menu
+                <div class="fixed inset-0 z-40" on:click=move |_| open.set(false) />
+                <div
+                    class="absolute right-0 mt-2 z-50 w-64 p-3 \
+                           bg-white dark:bg-gray-800 \
+                           border border-gray-200 dark:border-gray-700 \
+                           rounded-xl shadow-xl animate-fade-in"
+                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                        if ev.key() == "Escape" {
+                            open.set(false);
+                        }
+                    }
+                >
+                    <label class="flex items-start gap-2 cursor-pointer select-none">
+                        <input
+                            type="checkbox"
+                            class="custom-check mt-0.5"
+                            prop:checked=move || reduced.get()
+                            on:change=move |ev| motion.choice.set(Some(event_target_checked(&ev)))
+                        />
+                        <span>
+                            <span class="block text-sm font-medium text-gray-800 dark:text-gray-100">
+                                "Ahena ny sary mihetsika"
+                            </span>
+                            <span class="block text-xs text-gray-500 dark:text-gray-400 mt-0.5">
+                                "Lanitra tsy mihetsika, tsy misy confetti."
+                            </span>
+                        </span>
+                    </label>
+                </div>
+            })}
+        </div>
+    }
+}
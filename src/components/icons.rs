@@ -36,6 +36,17 @@ lucide!(IconHome,
      <polyline points='9 22 9 12 15 12 15 22'/>"
 );
 
+lucide!(IconSettings,
+    "<path d='M12.22 2h-.44a2 2 0 0 0-2 2v.18a2 2 0 0 1-1 1.73l-.43.25a2 2 0 0 1-2 0l-.15-.08\
+     a2 2 0 0 0-2.73.73l-.22.38a2 2 0 0 0 .73 2.73l.15.1a2 2 0 0 1 1 1.72v.51a2 2 0 0 1-1 1.74\
+     l-.15.09a2 2 0 0 0-.73 2.73l.22.38a2 2 0 0 0 2.73.73l.15-.08a2 2 0 0 1 2 0l.43.25\
+     a2 2 0 0 1 1 1.73V20a2 2 0 0 0 2 2h.44a2 2 0 0 0 2-2v-.18a2 2 0 0 1 1-1.73l.43-.25\
+     a2 2 0 0 1 2 0l.15.08a2 2 0 0 0 2.73-.73l.22-.39a2 2 0 0 0-.73-2.73l-.15-.08\
+     a2 2 0 0 1-1-1.74v-.5a2 2 0 0 1 1-1.74l.15-.09a2 2 0 0 0 .73-2.73l-.22-.38\
+     a2 2 0 0 0-2.73-.73l-.15.08a2 2 0 0 1-2 0l-.43-.25a2 2 0 0 1-1-1.73V4a2 2 0 0 0-2-2z'/>\
+     <circle cx='12' cy='12' r='3'/>"
+);
+
 // Croix chrétienne — icône des Communiants.
 lucide!(IconCross,
     "<path d='M11 2v7H4a1 1 0 0 0 0 2h7v11a1 1 0 0 0 2 0V11h7a1 1 0 0 0 0-2h-7V2a1 1 0 0 0-2 0Z'/>"
@@ -54,6 +65,14 @@ lucide!(IconArchive,
      <path d='M10 12h4'/>"
 );
 
+// Graphique en barres — page Statistiques.
+lucide!(IconBarChart,
+    "<path d='M3 3v18h18'/>\
+     <path d='M18 17V9'/>\
+     <path d='M13 17V5'/>\
+     <path d='M8 17v-3'/>"
+);
+
 // Bâtiment église (logo navbar).
 lucide!(IconChurch,
     "<path d='m18 7 4 2v11a2 2 0 0 1-2 2H4a2 2 0 0 1-2-2V9l4-2'/>\
@@ -84,6 +103,14 @@ lucide!(IconMonitor,
 
 // ── Actions ───────────────────────────────────────────────────────────────────
 
+lucide!(IconSliders,
+    "<line x1='21' x2='14' y1='4' y2='4'/><line x1='10' x2='3' y1='4' y2='4'/>\
+     <line x1='21' x2='12' y1='12' y2='12'/><line x1='8' x2='3' y1='12' y2='12'/>\
+     <line x1='21' x2='16' y1='20' y2='20'/><line x1='12' x2='3' y1='20' y2='20'/>\
+     <line x1='14' x2='14' y1='2' y2='6'/><line x1='8' x2='8' y1='10' y2='14'/>\
+     <line x1='16' x2='16' y1='18' y2='22'/>"
+);
+
 lucide!(IconSearch,
     "<circle cx='11' cy='11' r='8'/>\
      <path d='m21 21-4.35-4.35'/>"
@@ -125,6 +152,12 @@ lucide!(IconDownload,
      <line x1='12' y1='15' x2='12' y2='3'/>"
 );
 
+lucide!(IconPrinter,
+    "<path d='M6 18H4a2 2 0 0 1-2-2v-5a2 2 0 0 1 2-2h16a2 2 0 0 1 2 2v5a2 2 0 0 1-2 2h-2'/>\
+     <path d='M6 9V3a1 1 0 0 1 1-1h10a1 1 0 0 1 1 1v6'/>\
+     <rect x='6' y='14' width='12' height='8' rx='1'/>"
+);
+
 lucide!(IconUpload,
     "<path d='M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4'/>\
      <polyline points='17 8 12 3 7 8'/>\
@@ -139,6 +172,19 @@ lucide!(IconTransfer,
      <path d='M4 17h16'/>"
 );
 
+// Fusion (deux branches qui se rejoignent) — fusion de deux fiches membre.
+lucide!(IconMerge,
+    "<path d='m8 6 4-4 4 4'/>\
+     <path d='M12 2v10.3a4 4 0 0 1-1.172 2.872L4 22'/>\
+     <path d='m20 22-5-5'/>"
+);
+
+lucide!(IconDatabase,
+    "<ellipse cx='12' cy='5' rx='9' ry='3'/>\
+     <path d='M3 5V19A9 3 0 0 0 21 19V5'/>\
+     <path d='M3 12A9 3 0 0 0 21 12'/>"
+);
+
 lucide!(IconX,
     "<path d='M18 6 6 18'/><path d='m6 6 12 12'/>"
 );
@@ -150,6 +196,13 @@ lucide!(IconBell,
      <path d='M10.3 21a1.94 1.94 0 0 0 3.4 0'/>"
 );
 
+lucide!(IconCake,
+    "<path d='M20 21v-8a2 2 0 0 0-2-2H6a2 2 0 0 0-2 2v8'/>\
+     <path d='M4 16s.5-1 2-1 2.5 2 4 2 2.5-2 4-2 2.5 2 4 2 2-1 2-1'/>\
+     <path d='M2 21h20'/><path d='M7 8v3'/><path d='M12 8v3'/><path d='M17 8v3'/>\
+     <path d='M7 4h.01'/><path d='M12 4h.01'/><path d='M17 4h.01'/>"
+);
+
 lucide!(IconLock,
     "<rect width='18' height='11' x='3' y='11' rx='2' ry='2'/>\
      <path d='M7 11V7a5 5 0 0 1 10 0v4'/>"
@@ -0,0 +1,37 @@
+use leptos::prelude::*;
+
+use crate::i18n::{tr, LangCtx};
+use crate::services::db_service;
+
+/// Bascule malgache ↔ français ; la langue est enregistrée dans les paramètres
+/// (l'affichage change aussitôt, rétabli si l'enregistrement échoue).
+#[component]
+pub fn LanguageSwitcher() -> impl IntoView {
+    let ctx = use_context::<LangCtx>().expect("LangCtx manquant");
+
+    let toggle = move |_| {
+        let previous = ctx.lang.get_untracked();
+        let next = previous.toggled();
+        ctx.lang.set(next);
+        leptos::task::spawn_local(async move {
+            if db_service::set_language(next).await.is_err() {
+                ctx.lang.set(previous);
+            }
+        });
+    };
+
+    view! {
+        <button
+            on:click=toggle
+            title=move || tr("nav.language")
+            class="btn-ripple flex items-center px-2.5 py-1.5 rounded-lg \
+                   bg-white/60 dark:bg-gray-700/60 backdrop-blur \
+                   border border-gray-200 dark:border-gray-600 \
+                   text-gray-700 dark:text-gray-200 \
+                   hover:bg-white dark:hover:bg-gray-700 \
+                   text-xs font-semibold tabular-nums select-none"
+        >
+            {move || ctx.lang.get().code()}
+        </button>
+    }
+}
@@ -0,0 +1,634 @@
+/// Modal de maintenance : sauvegardes locales (liste, sauvegarde immédiate,
+/// restauration, rétention), archive JSON portable (export, import en remplacement
+/// ou en fusion), remise en forme des téléphones, contrôle d'intégrité et dernières
+/// entrées du journal applicatif.
+use leptos::prelude::*;
+
+use crate::{
+    app::{DataVersionCtx, PinCtx},
+    components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
+        icons::{IconAlertTriangle, IconDatabase, IconDownload, IconRefresh, IconSave, IconUpload, IconX},
+        modal_wrapper::ModalWrapper,
+    },
+    i18n::tr,
+    models::{
+        backup::{AppLog, BackupInfo, ImportMode},
+        integrity::IntegrityReport,
+        member::InvalidPhone,
+    },
+    services::db_service,
+    utils::download::download_text,
+};
+
+/// Nombre d'entrées du journal affichées.
+const LOG_LIMIT: i64 = 10;
+
+/// Taille lisible : "512 o", "12,4 Ko", "3,1 Mo".
+pub fn format_size(bytes: u64) -> String {
+    const KO: f64 = 1024.0;
+    let b = bytes as f64;
+    if b < KO {
+        format!("{bytes} o")
+    } else if b < KO * KO {
+        format!("{:.1} Ko", b / KO).replace('.', ",")
+    } else {
+        format!("{:.1} Mo", b / (KO * KO)).replace('.', ",")
+    }
+}
+
+/// "2025-03-14T02:00:05" → "14/03/2025 02:00".
+pub fn format_datetime(iso: &str) -> String {
+    match (iso.get(0..4), iso.get(5..7), iso.get(8..10), iso.get(11..16)) {
+        (Some(y), Some(m), Some(d), Some(hm)) => format!("{d}/{m}/{y} {hm}"),
+        _ => iso.to_string(),
+    }
+}
+
+#[component]
+pub fn MaintenancePanel(
+    /// Signal d'ouverture du modal.
+    open: RwSignal<bool>,
+) -> impl IntoView {
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let confirm_ctx = use_confirm();
+    let backups:   RwSignal<Vec<BackupInfo>> = RwSignal::new(vec![]);
+    let logs:      RwSignal<Vec<AppLog>>     = RwSignal::new(vec![]);
+    let retention: RwSignal<String>          = RwSignal::new(String::new());
+    let busy:      RwSignal<bool>            = RwSignal::new(false);
+    let error:     RwSignal<Option<String>>  = RwSignal::new(None);
+    let info:      RwSignal<Option<String>>  = RwSignal::new(None);
+    // Fichier en attente de confirmation de restauration
+    let confirm:   RwSignal<Option<String>>  = RwSignal::new(None);
+    // Téléphones non reconnus au dernier passage de `normalize_all_phones`
+    let bad_phones: RwSignal<Vec<InvalidPhone>> = RwSignal::new(vec![]);
+    // Dernier rapport de `run_integrity_check` / `repair_integrity`
+    let integrity: RwSignal<Option<IntegrityReport>> = RwSignal::new(None);
+    let archive_mode: RwSignal<ImportMode> = RwSignal::new(ImportMode::Merge);
+    let archive_input: NodeRef<leptos::html::Input> = NodeRef::new();
+
+    let reload = move || {
+        leptos::task::spawn_local(async move {
+            match db_service::list_backups().await {
+                Ok(list) => backups.set(list),
+                Err(e)   => error.set(Some(e)),
+            }
+            if let Ok(list) = db_service::get_app_logs(LOG_LIMIT).await {
+                logs.set(list);
+            }
+        });
+    };
+
+    // Chargement initial (rétention lue une seule fois pour ne pas écraser la saisie)
+    reload();
+    leptos::task::spawn_local(async move {
+        if let Ok(n) = db_service::get_backup_retention().await {
+            retention.set(n.to_string());
+        }
+    });
+
+    let do_backup = move |_| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::backup_now().await {
+                Ok(name) => info.set(Some(format!("Voatahiry : {name}"))),
+                Err(e)   => error.set(Some(e)),
+            }
+            busy.set(false);
+            reload();
+        });
+    };
+
+    let do_restore = move |file_name: String| pin.guard(move |token| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::restore_backup(&file_name, token.as_deref()).await {
+                // Toutes les pages ont des données en cache : rechargement complet
+                Ok(()) => {
+                    let _ = web_sys::window().map(|w| w.location().reload());
+                }
+                Err(e) => {
+                    error.set(Some(e));
+                    busy.set(false);
+                    confirm.set(None);
+                    reload();
+                }
+            }
+        });
+    });
+
+    let save_retention = move |_| {
+        let Ok(keep) = retention.get().trim().parse::<usize>() else {
+            error.set(Some("Isa tsy mety.".into()));
+            return;
+        };
+        error.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::set_backup_retention(keep).await {
+                Ok(()) => info.set(Some(format!("Tahiry {keep} farany no tazonina."))),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let export_archive = move |_| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            let d = js_sys::Date::new_0();
+            let filename = format!(
+                "fitehirizana-{:04}-{:02}-{:02}.json",
+                d.get_full_year(), d.get_month() + 1, d.get_date(),
+            );
+            let res = db_service::export_archive()
+                .await
+                .and_then(|json| download_text(&json, "application/json", &filename));
+            match res {
+                Ok(()) => info.set(Some(format!("Voatahiry : {filename}"))),
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let run_import = move |json: String, mode: ImportMode| pin.guard(move |token| {
+        busy.set(true);
+        leptos::task::spawn_local(async move {
+            match db_service::import_archive(&json, mode, token.as_deref()).await {
+                // Base remplacée : toutes les pages ont des données en cache
+                Ok(_) if mode == ImportMode::Replace => {
+                    let _ = web_sys::window().map(|w| w.location().reload());
+                }
+                Ok(report) => {
+                    info.set(Some(report.summary()));
+                    data_version.bump();
+                    busy.set(false);
+                    reload();
+                }
+                Err(e) => {
+                    error.set(Some(e));
+                    busy.set(false);
+                    reload();
+                }
+            }
+        });
+    });
+
+    let on_archive_selected = move |_| {
+        let Some(el)    = archive_input.get() else { return; };
+        let Some(files) = el.files() else { return; };
+        let Some(file)  = files.get(0) else { return; };
+        let text_promise = file.text();
+        el.set_value("");
+        error.set(None);
+        info.set(None);
+        let mode = archive_mode.get_untracked();
+        leptos::task::spawn_local(async move {
+            let json = match wasm_bindgen_futures::JsFuture::from(text_promise).await {
+                Ok(val) => val.as_string().unwrap_or_default(),
+                Err(e) => {
+                    error.set(Some(e.as_string().unwrap_or_else(|| "Hadisoana famakiana ny rakitra".into())));
+                    return;
+                }
+            };
+            if mode == ImportMode::Replace {
+                let ok = confirm_ctx
+                    .ask(ConfirmOptions {
+                        title:         "Hosoloina ny angona rehetra ?".into(),
+                        message:       "Hofafana ny mpikambana sy ny fandoavana ankehitriny ary \
+                                        hosoloina ny ao amin'ny rakitra. Hisy tahiry atao aloha."
+                            .into(),
+                        confirm_label: "Soloina".into(),
+                        danger:        true,
+                    })
+                    .await;
+                if !ok {
+                    return;
+                }
+            }
+            run_import(json, mode);
+        });
+    };
+
+    let fix_phones = move |_| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::normalize_all_phones().await {
+                Ok(report) => {
+                    info.set(Some(report.summary()));
+                    bad_phones.set(report.invalid);
+                    if report.updated > 0 {
+                        data_version.bump();
+                    }
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let check_integrity = move |_| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::run_integrity_check().await {
+                Ok(report) => integrity.set(Some(report)),
+                Err(e)     => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let repair = move |_| {
+        busy.set(true);
+        error.set(None);
+        info.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::repair_integrity().await {
+                Ok(report) => {
+                    integrity.set(Some(report));
+                    data_version.bump();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+            reload();
+        });
+    };
+
+    view! {
+        <ModalWrapper
+            on_close=Callback::new(move |()| if !busy.get_untracked() { open.set(false) })
+            card_class="max-w-lg max-h-[90vh] overflow-y-auto"
+        >
+            // ── En-tête ────────────────────────────────────────────────────────
+            <div class="flex items-center justify-between px-6 pt-5 pb-4 \
+                        border-b border-gray-100 dark:border-gray-700">
+                <h2 class="flex items-center gap-2 text-base font-bold text-gray-800 dark:text-white">
+                    <IconDatabase class="w-5 h-5 text-blue-500" />
+                    "Fikojakojana"
+                </h2>
+                <button
+                    on:click=move |_| open.set(false)
+                    disabled=move || busy.get()
+                    class="text-gray-400 hover:text-gray-600 \
+                           dark:hover:text-gray-200 transition-colors \
+                           p-1 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-700"
+                >
+                    <IconX class="w-4 h-4" />
+                </button>
+            </div>
+
+            <div class="px-6 py-5 space-y-5">
+                // ── Messages ───────────────────────────────────────────────────
+                {move || error.get().map(|e| view! {
+                    <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                                bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                                border border-red-200 dark:border-red-700/50">
+                        <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                        <span>{e}</span>
+                    </div>
+                })}
+                {move || info.get().map(|m| view! {
+                    <div class="p-3 text-xs rounded-xl \
+                                bg-emerald-50 dark:bg-emerald-900/20 text-emerald-700 dark:text-emerald-300 \
+                                border border-emerald-200 dark:border-emerald-700/50">
+                        {m}
+                    </div>
+                })}
+
+                // ── Sauvegardes ────────────────────────────────────────────────
+                <section class="space-y-2">
+                    <div class="flex items-center justify-between gap-2">
+                        <div>
+                            <h3 class="text-sm font-semibold text-gray-700 dark:text-gray-200">"Tahiry"</h3>
+                            <p class="text-xs text-gray-500 dark:text-gray-400">
+                                "Tahiry mandeha ho azy isan'andro"
+                            </p>
+                        </div>
+                        <button
+                            type="button"
+                            disabled=move || busy.get()
+                            on:click=do_backup
+                            class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                   text-white bg-blue-600 hover:bg-blue-700 \
+                                   disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                        >
+                            <IconSave class="w-4 h-4" />
+                            "Hanao tahiry izao"
+                        </button>
+                    </div>
+
+                    <ul class="divide-y divide-gray-100 dark:divide-gray-700 \
+                               border border-gray-100 dark:border-gray-700 rounded-xl overflow-hidden">
+                        {move || {
+                            let list = backups.get();
+                            if list.is_empty() {
+                                return view! {
+                                    <li class="px-3 py-4 text-xs text-center text-gray-400">
+                                        "Mbola tsy misy tahiry"
+                                    </li>
+                                }.into_any();
+                            }
+                            list.into_iter().map(|b| {
+                                let name_restore = b.file_name.clone();
+                                let name_confirm = b.file_name.clone();
+                                let name_check   = b.file_name.clone();
+                                let is_pending = move || confirm.get().as_deref() == Some(name_check.as_str());
+                                view! {
+                                    <li class="flex items-center justify-between gap-2 px-3 py-2 text-xs">
+                                        <div class="min-w-0">
+                                            <p class="font-medium text-gray-700 dark:text-gray-200 truncate">
+                                                {b.file_name.clone()}
+                                            </p>
+                                            <p class="text-gray-400">
+                                                {format!("{} · {}", format_datetime(&b.created_at), format_size(b.size_bytes))}
+                                            </p>
+                                        </div>
+                                        {move || if is_pending() {
+                                            let name = name_restore.clone();
+                                            view! {
+                                                <div class="flex items-center gap-1 shrink-0">
+                                                    <button
+                                                        type="button"
+                                                        disabled=move || busy.get()
+                                                        on:click=move |_| confirm.set(None)
+                                                        class="px-2 py-1 rounded-lg text-gray-500 \
+                                                               hover:bg-gray-100 dark:hover:bg-gray-700"
+                                                    >
+                                                        {move || tr("action.cancel")}
+                                                    </button>
+                                                    <button
+                                                        type="button"
+                                                        disabled=move || busy.get()
+                                                        on:click=move |_| do_restore(name.clone())
+                                                        class="px-2 py-1 rounded-lg font-semibold text-white \
+                                                               bg-amber-500 hover:bg-amber-600 \
+                                                               disabled:opacity-60 disabled:cursor-wait"
+                                                    >
+                                                        {move || if busy.get() { "Mamerina…" } else { "Hekena" }}
+                                                    </button>
+                                                </div>
+                                            }.into_any()
+                                        } else {
+                                            let name = name_confirm.clone();
+                                            view! {
+                                                <button
+                                                    type="button"
+                                                    disabled=move || busy.get()
+                                                    on:click=move |_| confirm.set(Some(name.clone()))
+                                                    title="Hamerina ny angona amin'ity tahiry ity \
+                                                           (voatahiry aloha ny angona ankehitriny)"
+                                                    class="flex items-center gap-1 shrink-0 px-2 py-1 rounded-lg \
+                                                           text-amber-600 dark:text-amber-400 \
+                                                           hover:bg-amber-50 dark:hover:bg-amber-900/20"
+                                                >
+                                                    <IconRefresh class="w-3.5 h-3.5" />
+                                                    "Averina"
+                                                </button>
+                                            }.into_any()
+                                        }}
+                                    </li>
+                                }
+                            }).collect_view().into_any()
+                        }}
+                    </ul>
+
+                    <div class="flex items-center gap-2 text-xs text-gray-600 dark:text-gray-300">
+                        <label for="backup-retention">"Tahiry tazonina :"</label>
+                        <input
+                            id="backup-retention"
+                            type="number" min="1" max="365"
+                            class="w-20 px-2 py-1 rounded-lg \
+                                   bg-gray-50 dark:bg-gray-700/60 \
+                                   border border-gray-200 dark:border-gray-600 \
+                                   focus:outline-none focus:ring-2 focus:ring-blue-400"
+                            prop:value=move || retention.get()
+                            on:input=move |ev| retention.set(event_target_value(&ev))
+                        />
+                        <button
+                            type="button"
+                            on:click=save_retention
+                            class="px-2 py-1 rounded-lg text-blue-600 dark:text-blue-400 \
+                                   hover:bg-blue-50 dark:hover:bg-blue-900/20"
+                        >
+                            {move || tr("action.save")}
+                        </button>
+                    </div>
+                </section>
+
+                // ── Archive JSON ───────────────────────────────────────────────
+                <section class="space-y-2">
+                    <div class="flex items-center justify-between gap-2">
+                        <div>
+                            <h3 class="text-sm font-semibold text-gray-700 dark:text-gray-200">
+                                "Fitehirizana JSON"
+                            </h3>
+                            <p class="text-xs text-gray-500 dark:text-gray-400">
+                                "Hamindrana ny angona rehetra amin'ny solosaina hafa"
+                            </p>
+                        </div>
+                        <button
+                            type="button"
+                            disabled=move || busy.get()
+                            on:click=export_archive
+                            class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                   text-blue-600 dark:text-blue-400 \
+                                   hover:bg-blue-50 dark:hover:bg-blue-900/30 \
+                                   disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                        >
+                            <IconDownload class="w-4 h-4" />
+                            "Haondrana"
+                        </button>
+                    </div>
+                    <div class="flex items-center gap-2 text-xs text-gray-600 dark:text-gray-300">
+                        <select
+                            aria-label="Fomba fampidirana"
+                            class="flex-1 px-2 py-1.5 rounded-lg \
+                                   bg-gray-50 dark:bg-gray-700/60 \
+                                   border border-gray-200 dark:border-gray-600 \
+                                   focus:outline-none focus:ring-2 focus:ring-blue-400"
+                            prop:value=move || match archive_mode.get() {
+                                ImportMode::Merge   => "merge",
+                                ImportMode::Replace => "replace",
+                            }
+                            on:change=move |ev| archive_mode.set(
+                                if event_target_value(&ev) == "replace" { ImportMode::Replace } else { ImportMode::Merge }
+                            )
+                        >
+                            <option value="merge">"Ampiana ny angona misy"</option>
+                            <option value="replace">"Soloina ny angona rehetra"</option>
+                        </select>
+                        <input
+                            type="file"
+                            accept=".json,application/json"
+                            style="display:none"
+                            node_ref=archive_input
+                            on:change=on_archive_selected
+                        />
+                        <button
+                            type="button"
+                            disabled=move || busy.get()
+                            on:click=move |_| if let Some(el) = archive_input.get() { el.click() }
+                            class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                   text-amber-600 dark:text-amber-400 \
+                                   hover:bg-amber-50 dark:hover:bg-amber-900/20 \
+                                   disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                        >
+                            <IconUpload class="w-4 h-4" />
+                            "Hampiditra"
+                        </button>
+                    </div>
+                </section>
+
+                // ── Téléphones ─────────────────────────────────────────────────
+                <section class="space-y-2">
+                    <div class="flex items-center justify-between gap-2">
+                        <div>
+                            <h3 class="text-sm font-semibold text-gray-700 dark:text-gray-200">"Laharana finday"</h3>
+                            <p class="text-xs text-gray-500 dark:text-gray-400">
+                                "Averina amin'ny endrika +261 3X XX XXX XX"
+                            </p>
+                        </div>
+                        <button
+                            type="button"
+                            disabled=move || busy.get()
+                            on:click=fix_phones
+                            class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                   text-blue-600 dark:text-blue-400 \
+                                   hover:bg-blue-50 dark:hover:bg-blue-900/30 \
+                                   disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                        >
+                            <IconRefresh class="w-4 h-4" />
+                            "Hamboarina"
+                        </button>
+                    </div>
+                    {move || {
+                        let list = bad_phones.get();
+                        (!list.is_empty()).then(|| view! {
+                            <ul class="space-y-1 text-xs text-amber-700 dark:text-amber-300">
+                                {list.into_iter().map(|p| view! {
+                                    <li class="flex gap-2">
+                                        <span class="shrink-0 font-mono">{p.card_number}</span>
+                                        <span class="break-all">{format!("« {} »", p.phone)}</span>
+                                    </li>
+                                }).collect_view()}
+                            </ul>
+                        })
+                    }}
+                </section>
+
+                // ── Intégrité ──────────────────────────────────────────────────
+                <section class="space-y-2">
+                    <div class="flex items-center justify-between gap-2">
+                        <div>
+                            <h3 class="text-sm font-semibold text-gray-700 dark:text-gray-200">
+                                "Fahamarinan'ny angona"
+                            </h3>
+                            <p class="text-xs text-gray-500 dark:text-gray-400">
+                                "Rakitra SQLite sy totaly isan-taona"
+                            </p>
+                        </div>
+                        <button
+                            type="button"
+                            disabled=move || busy.get()
+                            on:click=check_integrity
+                            class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                   text-blue-600 dark:text-blue-400 \
+                                   hover:bg-blue-50 dark:hover:bg-blue-900/30 \
+                                   disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                        >
+                            <IconDatabase class="w-4 h-4" />
+                            "Hamarino"
+                        </button>
+                    </div>
+                    {move || integrity.get().map(|report| {
+                        if report.issues.is_empty() {
+                            return view! {
+                                <p class="text-xs text-emerald-700 dark:text-emerald-300">
+                                    {format!("Tsy misy olana ({})", format_datetime(&report.checked_at))}
+                                </p>
+                            }.into_any();
+                        }
+                        let repairable = report.has_repairable();
+                        view! {
+                            <ul class="space-y-1 text-xs text-amber-700 dark:text-amber-300">
+                                {report.issues.iter().map(|i| view! {
+                                    <li class="break-all">{i.describe()}</li>
+                                }).collect_view()}
+                            </ul>
+                            {repairable.then(|| view! {
+                                <button
+                                    type="button"
+                                    disabled=move || busy.get()
+                                    on:click=repair
+                                    class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs font-semibold \
+                                           text-white bg-amber-500 hover:bg-amber-600 \
+                                           disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors"
+                                >
+                                    <IconRefresh class="w-4 h-4" />
+                                    "Amboary"
+                                </button>
+                            })}
+                        }.into_any()
+                    })}
+                </section>
+
+                // ── Journal ────────────────────────────────────────────────────
+                <section class="space-y-2">
+                    <h3 class="text-sm font-semibold text-gray-700 dark:text-gray-200">"Tantaran'ny asa"</h3>
+                    <ul class="space-y-1 text-xs">
+                        {move || {
+                            let list = logs.get();
+                            if list.is_empty() {
+                                return view! {
+                                    <li class="text-gray-400">"Tsy misy"</li>
+                                }.into_any();
+                            }
+                            list.into_iter().map(|l| {
+                                let color = if l.level == "error" {
+                                    "text-red-600 dark:text-red-400"
+                                } else {
+                                    "text-gray-600 dark:text-gray-300"
+                                };
+                                view! {
+                                    <li class=format!("flex gap-2 {color}")>
+                                        <span class="shrink-0 text-gray-400">{format_datetime(&l.created_at)}</span>
+                                        <span class="break-all">{l.message}</span>
+                                    </li>
+                                }
+                            }).collect_view().into_any()
+                        }}
+                    </ul>
+                </section>
+            </div>
+        </ModalWrapper>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taille_lisible() {
+        assert_eq!(format_size(512), "512 o");
+        assert_eq!(format_size(12_698), "12,4 Ko");
+        assert_eq!(format_size(3_250_586), "3,1 Mo");
+    }
+
+    #[test]
+    fn date_heure_lisible() {
+        assert_eq!(format_datetime("2025-03-14T02:00:05"), "14/03/2025 02:00");
+        assert_eq!(format_datetime("invalide"), "invalide");
+    }
+}
@@ -1,14 +1,32 @@
 /// Modal formulaire — créer ou modifier un membre.
+///
+/// En édition, les dernières cotisations du membre s'affichent sous les champs
+/// (lecture seule) pour repérer une saisie en double. En création, les membres au
+/// nom proche sont signalés à la sortie du champ nom, sans bloquer l'enregistrement.
+///
+/// Le nom se saisit en deux champs (anarana / fanampiny) qui composent le nom
+/// complet affiché ; le backend les garde à part pour le tri par nom de famille.
+///
+/// Une saisie commencée n'est pas perdue : le clic sur le fond demande confirmation,
+/// et un brouillon (`use_form_draft`) est proposé à la prochaine ouverture.
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    app::ToastProviderCtx,
     components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
         icons::IconX,
         modal_wrapper::ModalWrapper,
         phone_input::PhoneInput,
     },
-    models::member::MemberInput,
-    services::db_service,
+    i18n::tr,
+    models::{
+        contribution::Contribution,
+        member::{Gender, Member, MemberInput, MemberStatus, MemberType},
+    },
+    services::db_service::{self, ApiError},
+    utils::{format_ariary_str, use_form_draft, MoneyFormat, RequestGen},
 };
 
 const LABEL: &str = "block text-xs font-semibold text-gray-600 dark:text-gray-400 mb-1";
@@ -19,6 +37,40 @@ const INPUT: &str = "w-full px-3 py-2 text-sm \
                      placeholder-gray-400 dark:placeholder-gray-500 \
                      focus:outline-none focus:ring-2 focus:ring-blue-400 transition";
 
+/// Nombre de cotisations affichées en mode édition.
+const RECENT_LIMIT: i64 = 5;
+
+/// Nom complet affiché : "RAKOTO Jean", ou la seule partie remplie (même règle
+/// que `compose_full_name` côté backend).
+fn compose_name(surname: &str, given_name: &str) -> String {
+    [surname.trim(), given_name.trim()]
+        .into_iter()
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Champs du formulaire gardés en brouillon, tels que saisis.
+#[derive(Clone, Serialize, Deserialize)]
+struct MemberDraft {
+    card_number: String,
+    surname:     String,
+    given_name:  String,
+    full_name:   String,
+    address:     String,
+    phone:       String,
+    job:         String,
+    gender:      Gender,
+    birth_date:  String,
+    status:      MemberStatus,
+}
+
+/// Date du jour "YYYY-MM-DD" (borne `max` du champ date de naissance).
+fn today_iso() -> String {
+    let d = js_sys::Date::new_0();
+    format!("{:04}-{:02}-{:02}", d.get_full_year(), d.get_month() + 1, d.get_date())
+}
+
 /// Modal formulaire de création / modification d'un membre.
 ///
 /// Les signaux du formulaire (`f_*`) sont définis dans `MemberPage` et passés ici
@@ -29,23 +81,162 @@ pub fn MemberForm(
     open:        RwSignal<bool>,
     /// `Some(id)` en mode édition, `None` en création.
     edit_id:     RwSignal<Option<i64>>,
-    /// Type de membre ("Communiant" | "Cathekomen").
-    member_type: &'static str,
+    /// Type de membre des fiches créées / modifiées.
+    member_type: MemberType,
     /// Classes Tailwind du bouton de soumission (couleur principale).
     btn_class:   &'static str,
     /// Incrémenter pour déclencher un rechargement de liste.
     refresh_ctr: RwSignal<u32>,
-    /// Signal d'erreur flottante.
-    notif_error: RwSignal<Option<String>>,
+    /// Création prise en charge par le parent (insertion optimiste) : s'il
+    /// retourne `true`, le formulaire se ferme sans attendre le backend.
+    #[prop(optional)]
+    on_create:   Option<Callback<MemberInput, bool>>,
     // ── Signaux de champs ────────────────────────────────────────────────────
     f_carte:     RwSignal<String>,
     f_nom:       RwSignal<String>,
     f_adresse:   RwSignal<String>,
     f_telephone: RwSignal<String>,
     f_travail:   RwSignal<String>,
-    f_genre:     RwSignal<String>,
+    f_genre:     RwSignal<Gender>,
+    f_naissance: RwSignal<String>,
+    f_statut:    RwSignal<MemberStatus>,
     f_loading:   RwSignal<bool>,
 ) -> impl IntoView {
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    // Erreur de validation / conflit renvoyée par le backend
+    let form_error: RwSignal<Option<String>> = RwSignal::new(None);
+    // Numéro de carte déjà pris : affiché sous le champ, qui passe en rouge
+    let card_error: RwSignal<Option<String>> = RwSignal::new(None);
+
+    // ── Dernières cotisations (édition) ──────────────────────────────────────
+    // Chargées en arrière-plan : le formulaire reste utilisable, et une erreur
+    // ici n'empêche pas l'enregistrement.
+    let recent:         RwSignal<Vec<Contribution>> = RwSignal::new(vec![]);
+    let recent_loading: RwSignal<bool>              = RwSignal::new(false);
+    let recent_error:   RwSignal<Option<String>>    = RwSignal::new(None);
+    let recent_gen = RequestGen::new();
+    Effect::new(move |_| {
+        let Some(id) = edit_id.get() else {
+            recent.set(vec![]);
+            return;
+        };
+        let g = recent_gen.next();
+        recent_loading.set(true);
+        recent_error.set(None);
+        leptos::task::spawn_local(async move {
+            let res = db_service::get_recent_contributions(id, RECENT_LIMIT).await;
+            if !recent_gen.is_current(g) {
+                return;
+            }
+            match res {
+                Ok(list) => recent.set(list),
+                Err(e)   => recent_error.set(Some(e)),
+            }
+            recent_loading.set(false);
+        });
+    });
+
+    // ── Anarana / fanampiny ──────────────────────────────────────────────────
+    // Un membre sans nom de famille connu garde tout son nom dans « fanampiny » ;
+    // `f_nom` n'est recomposé que si l'on touche à l'un des deux champs, pour ne
+    // pas réordonner un nom complet laissé tel quel.
+    let f_anarana:   RwSignal<String> = RwSignal::new(String::new());
+    let f_fanampiny: RwSignal<String> = RwSignal::new(f_nom.get_untracked());
+    let parts_touched = RwSignal::new(false);
+    if let Some(id) = edit_id.get_untracked() {
+        leptos::task::spawn_local(async move {
+            let Ok(m) = db_service::get_member(id).await else { return };
+            if m.surname.is_some() && !parts_touched.get_untracked() {
+                f_anarana.set(m.surname.unwrap_or_default());
+                f_fanampiny.set(m.given_name.unwrap_or_default());
+            }
+        });
+    }
+    let set_part = move |part: RwSignal<String>, value: String| {
+        part.set(value);
+        parts_touched.set(true);
+        f_nom.set(compose_name(&f_anarana.get_untracked(), &f_fanampiny.get_untracked()));
+    };
+
+    // ── Brouillon ────────────────────────────────────────────────────────────
+    // `dirty` passe à vrai à la première saisie (événements `input` du formulaire) :
+    // seul un formulaire modifié est gardé en brouillon ou protégé du clic sur le fond.
+    let dirty = RwSignal::new(false);
+    let draft_key = format!(
+        "member_{}_{}",
+        member_type.as_str(),
+        edit_id.get_untracked().map_or_else(|| "new".to_string(), |id| id.to_string()),
+    );
+    let draft = use_form_draft(draft_key, move || {
+        dirty.get().then(|| MemberDraft {
+            card_number: f_carte.get(),
+            surname:     f_anarana.get(),
+            given_name:  f_fanampiny.get(),
+            full_name:   f_nom.get(),
+            address:     f_adresse.get(),
+            phone:       f_telephone.get(),
+            job:         f_travail.get(),
+            gender:      f_genre.get(),
+            birth_date:  f_naissance.get(),
+            status:      f_statut.get(),
+        })
+    });
+    let reprendre_brouillon = move |_| {
+        let Some(d) = draft.take_offer() else { return };
+        parts_touched.set(true);
+        f_carte.set(d.card_number);
+        f_anarana.set(d.surname);
+        f_fanampiny.set(d.given_name);
+        f_nom.set(d.full_name);
+        f_adresse.set(d.address);
+        f_telephone.set(d.phone);
+        f_travail.set(d.job);
+        f_genre.set(d.gender);
+        f_naissance.set(d.birth_date);
+        f_statut.set(d.status);
+        dirty.set(true);
+    };
+
+    // Clic sur le fond : fermeture directe si rien n'a été saisi
+    let confirm = use_confirm();
+    let on_overlay = Callback::new(move |()| {
+        if !dirty.get_untracked() {
+            open.set(false);
+            return;
+        }
+        leptos::task::spawn_local(async move {
+            let ok = confirm.ask(ConfirmOptions {
+                title:         "Hakatona ny taratasy ?".into(),
+                message:       "Tsy voatahiry ny zavatra nosoratana. Hatolotra indray izy \
+                                rehefa sokafana ity taratasy ity."
+                    .into(),
+                confirm_label: "Akatona".into(),
+                danger:        false,
+            }).await;
+            if ok {
+                open.set(false);
+            }
+        });
+    });
+
+    // ── Noms proches (création) ──────────────────────────────────────────────
+    // Simple avertissement : une erreur de recherche est ignorée.
+    let similar:     RwSignal<Vec<Member>> = RwSignal::new(vec![]);
+    let similar_gen = RequestGen::new();
+    let chercher_similaires = move |_| {
+        let nom = f_nom.get_untracked().trim().to_string();
+        let g = similar_gen.next();
+        if edit_id.get_untracked().is_some() || nom.is_empty() {
+            similar.set(vec![]);
+            return;
+        }
+        leptos::task::spawn_local(async move {
+            let res = db_service::find_similar_members(&nom).await;
+            if similar_gen.is_current(g) {
+                similar.set(res.unwrap_or_default());
+            }
+        });
+    };
 
     let soumettre = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
@@ -56,17 +247,42 @@ pub fn MemberForm(
             Some(phone_val.trim().to_string())
         };
 
+        let full_name = f_nom.get().trim().to_string();
+        if full_name.is_empty() {
+            form_error.set(Some("Ilaina ny anarana na ny fanampiny".into()));
+            return;
+        }
+        // Sans nom de famille, le backend déduit les parties du nom complet
+        let surname = f_anarana.get().trim().to_string();
+        let (surname, given_name) = if surname.is_empty() {
+            (None, None)
+        } else {
+            let given = f_fanampiny.get().trim().to_string();
+            (Some(surname), (!given.is_empty()).then_some(given))
+        };
+
         let input = MemberInput {
             card_number: f_carte.get().trim().to_string(),
-            full_name:   f_nom.get().trim().to_string(),
+            full_name,
             address:     { let t = f_adresse.get().trim().to_string(); if t.is_empty() { None } else { Some(t) } },
             phone,
             job:         { let t = f_travail.get().trim().to_string(); if t.is_empty() { None } else { Some(t) } },
             gender:      f_genre.get(),
-            member_type: member_type.to_string(),
+            member_type,
+            surname,
+            given_name,
+            status:      f_statut.get(),
+            birth_date:  { let t = f_naissance.get(); if t.is_empty() { None } else { Some(t) } },
         };
-        f_loading.set(true);
         let eid = edit_id.get();
+        if eid.is_none() && on_create.is_some_and(|cb| cb.run(input.clone())) {
+            draft.clear();
+            open.set(false);
+            return;
+        }
+        f_loading.set(true);
+        form_error.set(None);
+        card_error.set(None);
         leptos::task::spawn_local(async move {
             let res = if let Some(id) = eid {
                 db_service::update_member(id, &input).await.map(|_| ())
@@ -75,21 +291,47 @@ pub fn MemberForm(
             };
             match res {
                 Ok(_) => {
+                    draft.clear();
                     open.set(false);
                     refresh_ctr.update(|n| *n += 1);
+                    toasts.success(if eid.is_some() {
+                        format!("Voaova ny mombamomba an'i {}", input.full_name)
+                    } else {
+                        format!("Voasoratra ny mpikambana vaovao : {}", input.full_name)
+                    });
                 }
-                Err(e) => notif_error.set(Some(e)),
+                // Saisie refusée : le message reste dans le formulaire
+                Err(ApiError::Conflict(m)) => card_error.set(Some(m)),
+                Err(e) if e.is_input_error() => form_error.set(Some(e.to_string())),
+                Err(e) => toasts.error(e.to_string()),
             }
             f_loading.set(false);
         });
     };
 
     let is_edit    = move || edit_id.get().is_some();
+
+    // Création : propose le prochain numéro libre selon le modèle des paramètres
+    let generating: RwSignal<bool> = RwSignal::new(false);
+    let generer_carte = move |_| {
+        generating.set(true);
+        leptos::task::spawn_local(async move {
+            match db_service::next_card_number(member_type).await {
+                Ok(card) => {
+                    card_error.set(None);
+                    f_carte.set(card);
+                    dirty.set(true);
+                }
+                Err(e) => toasts.error(e),
+            }
+            generating.set(false);
+        });
+    };
     let modal_title = move || if is_edit() { "Hanova ny mpikambana" } else { "Mpikambana vaovao" };
 
     view! {
         <ModalWrapper
-            on_close=Callback::new(move |()| open.set(false))
+            on_close=on_overlay
             card_class="max-w-lg max-h-[90vh] overflow-y-auto"
         >
 
@@ -108,40 +350,168 @@ pub fn MemberForm(
                     </button>
                 </div>
 
-                <form on:submit=soumettre class="px-6 py-5 space-y-4">
+                <form
+                    on:submit=soumettre
+                    on:input=move |_| dirty.set(true)
+                    class="px-6 py-5 space-y-4"
+                >
+                    {move || (draft.offered.with(Option::is_some) && !dirty.get()).then(|| view! {
+                        <div class="flex flex-wrap items-center gap-2 p-2.5 text-xs rounded-xl \
+                                    bg-blue-50 dark:bg-blue-900/20 \
+                                    text-blue-800 dark:text-blue-300 \
+                                    border border-blue-200 dark:border-blue-700/50">
+                            <p class="flex-1 min-w-[10rem]">
+                                "Misy drafitra tsy voatahiry. Haverina ve ?"
+                            </p>
+                            <button
+                                type="button"
+                                on:click=reprendre_brouillon
+                                class="btn-ripple px-2.5 py-1 font-semibold rounded-lg \
+                                       bg-blue-600 hover:bg-blue-700 text-white transition-colors"
+                            >
+                                "Averina"
+                            </button>
+                            <button
+                                type="button"
+                                on:click=move |_| draft.discard()
+                                class="btn-ripple px-2.5 py-1 font-medium rounded-lg \
+                                       hover:bg-blue-100 dark:hover:bg-blue-900/40 transition-colors"
+                            >
+                                "Ario"
+                            </button>
+                        </div>
+                    })}
                     <div class="grid grid-cols-2 gap-3">
                         <div>
                             <label class=LABEL>"N° karatra *"</label>
-                            <input
-                                type="text" required
-                                placeholder="ohatra : C-0042"
-                                class=INPUT
-                                prop:value=move || f_carte.get()
-                                on:input=move |ev| f_carte.set(event_target_value(&ev))
-                            />
+                            <div class="flex gap-1.5">
+                                <input
+                                    type="text" required
+                                    placeholder="ohatra : C-0042"
+                                    class=move || if card_error.get().is_some() {
+                                        INPUT.replace("border-gray-200 dark:border-gray-600",
+                                                      "border-red-400 dark:border-red-500")
+                                    } else {
+                                        INPUT.to_string()
+                                    }
+                                    aria-invalid=move || card_error.get().is_some().to_string()
+                                    prop:value=move || f_carte.get()
+                                    on:input=move |ev| {
+                                        card_error.set(None);
+                                        f_carte.set(event_target_value(&ev));
+                                    }
+                                />
+                                {move || (!is_edit()).then(|| view! {
+                                    <button
+                                        type="button"
+                                        title="Hamorona laharana karatra manaraka"
+                                        disabled=move || generating.get()
+                                        on:click=generer_carte
+                                        class="btn-ripple shrink-0 px-2.5 text-xs font-semibold                                                rounded-xl transition-colors disabled:opacity-50                                                text-blue-600 dark:text-blue-400                                                bg-blue-50 dark:bg-blue-900/30                                                hover:bg-blue-100 dark:hover:bg-blue-900/50"
+                                    >
+                                        "Hamorona"
+                                    </button>
+                                })}
+                            </div>
+                            {move || card_error.get().map(|e| view! {
+                                <p class="mt-1 text-xs text-red-600 dark:text-red-400">{e}</p>
+                            })}
                         </div>
                         <div>
                             <label class=LABEL>"Lahy/Vavy *"</label>
                             <select
                                 class=INPUT
-                                prop:value=move || f_genre.get()
-                                on:change=move |ev| f_genre.set(event_target_value(&ev))
+                                prop:value=move || f_genre.get().as_str()
+                                on:change=move |ev| {
+                                    if let Some(g) = Gender::from_value(&event_target_value(&ev)) {
+                                        f_genre.set(g);
+                                    }
+                                }
                             >
-                                <option value="M">"Lahy"</option>
-                                <option value="F">"Vavy"</option>
+                                <option value=Gender::M.as_str()>{Gender::M.label()}</option>
+                                <option value=Gender::F.as_str()>{Gender::F.label()}</option>
                             </select>
                         </div>
                     </div>
 
                     <div>
-                        <label class=LABEL>"Anarana sy fanampiny *"</label>
-                        <input
-                            type="text" required
-                            placeholder="Anarana Fianakaviana"
-                            class=INPUT
-                            prop:value=move || f_nom.get()
-                            on:input=move |ev| f_nom.set(event_target_value(&ev))
-                        />
+                        <div class="grid grid-cols-2 gap-3">
+                            <div>
+                                <label class=LABEL>"Anarana"</label>
+                                <input
+                                    type="text"
+                                    placeholder="RAKOTO"
+                                    class=INPUT
+                                    prop:value=move || f_anarana.get()
+                                    on:input=move |ev| set_part(f_anarana, event_target_value(&ev))
+                                    on:blur=chercher_similaires
+                                />
+                            </div>
+                            <div>
+                                <label class=LABEL>"Fanampiny"</label>
+                                <input
+                                    type="text"
+                                    placeholder="Jean"
+                                    class=INPUT
+                                    prop:value=move || f_fanampiny.get()
+                                    on:input=move |ev| set_part(f_fanampiny, event_target_value(&ev))
+                                    on:blur=chercher_similaires
+                                />
+                            </div>
+                        </div>
+                        {move || {
+                            let list = similar.get();
+                            (!list.is_empty()).then(|| view! {
+                                <div class="mt-2 p-2.5 text-xs rounded-xl \
+                                            bg-amber-50 dark:bg-amber-900/20 \
+                                            text-amber-800 dark:text-amber-300 \
+                                            border border-amber-200 dark:border-amber-700/50">
+                                    <p class="font-semibold">"Efa misy mpikambana mitovy anarana :"</p>
+                                    <ul class="mt-1 space-y-0.5">
+                                        {list.into_iter().map(|m| view! {
+                                            <li>
+                                                {m.full_name}
+                                                <span class="font-mono opacity-75">
+                                                    {format!(" · {} · {}", m.card_number, m.member_type.label())}
+                                                </span>
+                                            </li>
+                                        }).collect_view()}
+                                    </ul>
+                                    <p class="mt-1 opacity-75">
+                                        "Azo tohizana ihany raha olona hafa izy."
+                                    </p>
+                                </div>
+                            })
+                        }}
+                    </div>
+
+                    <div class="grid grid-cols-2 gap-3">
+                        <div>
+                            <label class=LABEL>"Daty nahaterahana"</label>
+                            <input
+                                type="date"
+                                max=today_iso()
+                                class=INPUT
+                                prop:value=move || f_naissance.get()
+                                on:input=move |ev| f_naissance.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <div>
+                            <label class=LABEL>"Toetra"</label>
+                            <select
+                                class=INPUT
+                                prop:value=move || f_statut.get().as_str()
+                                on:change=move |ev| {
+                                    if let Some(s) = MemberStatus::from_value(&event_target_value(&ev)) {
+                                        f_statut.set(s);
+                                    }
+                                }
+                            >
+                                {MemberStatus::ALL.map(|s| view! {
+                                    <option value=s.as_str()>{s.label()}</option>
+                                })}
+                            </select>
+                        </div>
                     </div>
 
                     <div>
@@ -171,6 +541,52 @@ pub fn MemberForm(
                         />
                     </div>
 
+                    {move || is_edit().then(|| view! {
+                        <div>
+                            <p class=LABEL>"Latsakemboka farany"</p>
+                            {move || {
+                                if recent_loading.get() {
+                                    return view! {
+                                        <div class="flex justify-center py-2">
+                                            <div class="w-4 h-4 border-2 border-blue-400 \
+                                                        border-t-transparent rounded-full animate-spin" />
+                                        </div>
+                                    }.into_any();
+                                }
+                                if let Some(e) = recent_error.get() {
+                                    return view! {
+                                        <p class="text-xs text-amber-600 dark:text-amber-400">{e}</p>
+                                    }.into_any();
+                                }
+                                let list = recent.get();
+                                if list.is_empty() {
+                                    return view! {
+                                        <p class="text-xs text-gray-400">"Mbola tsy nandoa"</p>
+                                    }.into_any();
+                                }
+                                view! {
+                                    <ul class="divide-y divide-gray-100 dark:divide-gray-700/60 text-xs \
+                                               border border-gray-100 dark:border-gray-700 rounded-xl">
+                                        {list.into_iter().map(|c| view! {
+                                            <li class="flex items-center justify-between gap-3 px-3 py-1.5 \
+                                                       text-gray-600 dark:text-gray-300">
+                                                <span class="font-mono">{c.payment_date}</span>
+                                                <span class="truncate">{c.period}</span>
+                                                <span class="font-mono text-gray-800 dark:text-white">
+                                                    {format_ariary_str(&c.amount, MoneyFormat::default())}
+                                                </span>
+                                            </li>
+                                        }).collect_view()}
+                                    </ul>
+                                }.into_any()
+                            }}
+                        </div>
+                    })}
+
+                    {move || form_error.get().map(|e| view! {
+                        <p class="text-xs text-red-600 dark:text-red-400">{e}</p>
+                    })}
+
                     <div class="flex gap-3 justify-end pt-1">
                         <button
                             type="button"
@@ -181,7 +597,7 @@ pub fn MemberForm(
                                    hover:bg-gray-200 dark:hover:bg-gray-600 \
                                    rounded-xl transition-colors"
                         >
-                            "Foana"
+                            {move || tr("action.cancel")}
                         </button>
                         <button
                             type="submit"
@@ -191,7 +607,7 @@ pub fn MemberForm(
                                            disabled:opacity-60 disabled:cursor-wait \
                                            transition-colors shadow-sm", btn_class)
                         >
-                            {move || if f_loading.get() { "Tehirizina…" } else { "Tehirizina" }}
+                            {move || tr(if f_loading.get() { "action.saving" } else { "action.save" })}
                         </button>
                     </div>
                 </form>
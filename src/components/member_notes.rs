@@ -0,0 +1,133 @@
+/// Notes libres d'un membre (fiche `/membre/:id`) : saisie d'une nouvelle note,
+/// historique de la plus récente à la plus ancienne, suppression une à une.
+use leptos::prelude::*;
+
+use crate::{
+    components::{
+        icons::{IconAlertTriangle, IconPlus, IconTrash},
+        maintenance_panel::format_datetime,
+    },
+    models::member::MemberNote,
+    services::db_service,
+};
+
+#[component]
+pub fn MemberNotes(member_id: i64) -> impl IntoView {
+    let notes:  RwSignal<Vec<MemberNote>> = RwSignal::new(vec![]);
+    let draft:  RwSignal<String>          = RwSignal::new(String::new());
+    let saving: RwSignal<bool>            = RwSignal::new(false);
+    let erreur: RwSignal<Option<String>>  = RwSignal::new(None);
+
+    leptos::task::spawn_local(async move {
+        match db_service::get_member_notes(member_id).await {
+            Ok(list) => notes.set(list),
+            Err(e)   => erreur.set(Some(e)),
+        }
+    });
+
+    let ajouter = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let text = draft.get_untracked();
+        if text.trim().is_empty() {
+            return;
+        }
+        erreur.set(None);
+        saving.set(true);
+        leptos::task::spawn_local(async move {
+            match db_service::add_member_note(member_id, &text).await {
+                Ok(note) => {
+                    notes.update(|l| l.insert(0, note));
+                    draft.set(String::new());
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let supprimer = move |id: i64| {
+        erreur.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::delete_member_note(id).await {
+                Ok(()) => notes.update(|l| l.retain(|n| n.id != id)),
+                Err(e) => erreur.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        <div class="px-4 py-4 space-y-3 \
+                    bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                    rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+            <h2 class="text-sm font-semibold text-gray-700 dark:text-gray-200">"Fanamarihana"</h2>
+            {move || erreur.get().map(|e| view! {
+                <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                            bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                            border border-red-200 dark:border-red-700/50">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+            <form on:submit=ajouter class="flex items-start gap-2">
+                <textarea
+                    rows="2"
+                    placeholder="Ohatra : nifindra tany Antananarivo tamin'ny 2024"
+                    prop:value=move || draft.get()
+                    on:input=move |ev| draft.set(event_target_value(&ev))
+                    class="flex-1 px-3 py-2 text-sm resize-y \
+                           bg-gray-50 dark:bg-gray-700/60 \
+                           border border-gray-200 dark:border-gray-600 \
+                           rounded-xl text-gray-800 dark:text-white \
+                           placeholder-gray-400 dark:placeholder-gray-500 \
+                           focus:outline-none focus:ring-2 focus:ring-blue-400 transition"
+                />
+                <button
+                    type="submit"
+                    title="Hanampy fanamarihana"
+                    disabled=move || saving.get() || draft.with(|d| d.trim().is_empty())
+                    class="btn-ripple p-2.5 rounded-xl text-white bg-blue-600 hover:bg-blue-700 \
+                           disabled:opacity-50 transition-colors shadow-sm"
+                >
+                    <IconPlus class="w-4 h-4" />
+                </button>
+            </form>
+            {move || {
+                let list = notes.get();
+                if list.is_empty() {
+                    return view! {
+                        <p class="text-xs text-gray-400 dark:text-gray-500">"Mbola tsy misy fanamarihana"</p>
+                    }.into_any();
+                }
+                view! {
+                    <ul class="divide-y divide-gray-100 dark:divide-gray-700/50">
+                        {list.into_iter().map(|n| {
+                            let id = n.id;
+                            view! {
+                                <li class="flex items-start gap-3 py-2">
+                                    <div class="flex-1 min-w-0">
+                                        <p class="text-sm text-gray-800 dark:text-gray-100 \
+                                                  whitespace-pre-line break-words">
+                                            {n.text}
+                                        </p>
+                                        <p class="text-xs text-gray-400 dark:text-gray-500 mt-0.5">
+                                            {format_datetime(&n.created_at)}
+                                        </p>
+                                    </div>
+                                    <button
+                                        type="button"
+                                        title="Hamafa"
+                                        on:click=move |_| supprimer(id)
+                                        class="p-1.5 rounded-lg text-gray-400 hover:text-red-600 \
+                                               hover:bg-red-50 dark:hover:bg-red-900/20 transition-colors"
+                                    >
+                                        <IconTrash class="w-3.5 h-3.5" />
+                                    </button>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
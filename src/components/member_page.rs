@@ -1,25 +1,41 @@
 /// Composant générique pour Communiants et Cathécomènes.
 ///
 /// Orchestre la liste, les filtres, la pagination, le formulaire CRUD
-/// et la modale de transfert. Délègue le rendu aux sous-composants :
-/// `MemberTable`, `MemberForm`, `TransferModal`, `ContributionModal`.
+/// et les modales de transfert et de fusion. Délègue le rendu aux sous-composants :
+/// `MemberTable`, `MemberForm`, `TransferModal`, `MergeModal`, `ContributionModal`.
+use std::collections::HashSet;
+
 use leptos::prelude::*;
+use leptos_router::components::A;
 
 use js_sys::{Array, Uint8Array};
 use wasm_bindgen::JsCast;
 use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
 use crate::{
+    app::{DataVersionCtx, MemberDataCtx, PinCtx, ToastProviderCtx},
     components::{
+        confirm_dialog::use_confirm,
         contribution_modal::{ConfettiLayer, ContributionModal},
-        icons::{IconAlertTriangle, IconDownload, IconPlus, IconSearch, IconTransfer, IconUpload, PageIcon},
+        icons::{
+            IconAlertTriangle, IconDownload, IconMerge, IconPlus, IconPrinter, IconSearch,
+            IconTransfer, IconUpload, IconX, PageIcon,
+        },
         member_form::MemberForm,
-        member_table::{MemberTable, SortCol, SortDir, PAGE_SIZE},
+        member_table::{AddressSection, MemberTable, SortCol, SortDir, PAGE_SIZE},
+        merge_modal::MergeModal,
         transfer_modal::TransferModal,
     },
-    models::member::MemberWithTotal,
+    i18n::tr,
+    models::member::{
+        Gender, ImportRowError, MemberFilter, MemberInput, MemberRow, MemberSort, MemberStatus,
+        MemberType, MemberWithTotal,
+    },
     services::db_service,
-    utils::sleep_ms,
+    utils::{
+        member_index::MemberIndex, optimistic, scanner::ScanBuffer, sleep_ms, use_debounced_signal,
+        RequestGen, SEARCH_DEBOUNCE_MS,
+    },
 };
 
 // ─── Helper : déclenche le téléchargement d'un fichier texte dans le navigateur ──
@@ -55,60 +71,28 @@ fn trigger_xlsx_download(bytes: &[u8], filename: &str) -> Result<(), String> {
 
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
-const NOTIF_DISMISS_MS: u32 = 4000;
-
-fn filter_and_sort(
-    membres: Vec<MemberWithTotal>,
-    query: &str,
-    genre: &str,
-    col: SortCol,
-    dir: SortDir,
-) -> Vec<MemberWithTotal> {
-    let q = query.to_lowercase();
-    let mut list: Vec<MemberWithTotal> = membres
-        .into_iter()
-        .filter(|m| {
-            (genre == "Rehetra" || m.gender == genre)
-                && (q.is_empty()
-                    || m.full_name.to_lowercase().contains(&q)
-                    || m.card_number.to_lowercase().contains(&q)
-                    || m.address.as_deref().unwrap_or("").to_lowercase().contains(&q)
-                    || m.phone.as_deref().unwrap_or("").to_lowercase().contains(&q)
-                    || m.job.as_deref().unwrap_or("").to_lowercase().contains(&q))
-        })
-        .collect();
-
-    list.sort_by(|a, b| {
-        use std::cmp::Ordering;
-        let ord: Ordering = match col {
-            SortCol::Carte     => a.card_number.cmp(&b.card_number),
-            SortCol::Nom       => a.full_name.cmp(&b.full_name),
-            SortCol::Adresse   => a.address.as_deref().unwrap_or("").cmp(b.address.as_deref().unwrap_or("")),
-            SortCol::Telephone => a.phone.as_deref().unwrap_or("").cmp(b.phone.as_deref().unwrap_or("")),
-            SortCol::Travail   => a.job.as_deref().unwrap_or("").cmp(b.job.as_deref().unwrap_or("")),
-            SortCol::Genre     => a.gender.cmp(&b.gender),
-        };
-        if dir == SortDir::Desc { ord.reverse() } else { ord }
-    });
-    list
-}
+/// Au-delà de cet effectif, tri, filtres et pagination passent par SQL
+/// (`get_member_rows`) ; en dessous, la liste complète est chargée une fois,
+/// mise en cache et filtrée côté client.
+pub const CLIENT_SIDE_MAX_MEMBERS: i64 = 300;
 
-fn auto_dismiss(signal: RwSignal<Option<String>>) {
-    Effect::new(move |_| {
-        if signal.get().is_some() {
-            leptos::task::spawn_local(async move {
-                sleep_ms(NOTIF_DISMISS_MS).await;
-                signal.set(None);
-            });
-        }
-    });
+/// Durée de l'animation `row-sliding-out` (style/main.css), arrondie.
+const TRANSFER_ANIM_MS: u32 = 400;
+
+/// Frappe dans un champ de saisie : jamais interprétée comme un scan.
+fn is_editable_target(ev: &web_sys::KeyboardEvent) -> bool {
+    ev.target()
+        .and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|el| {
+            matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") || el.is_content_editable()
+        })
 }
 
 // ─── Composant principal ──────────────────────────────────────────────────────
 
 #[component]
 pub fn MemberPage(
-    member_type: &'static str,
+    member_type: MemberType,
     icon:        &'static str,
     title:       &'static str,
     subtitle:    &'static str,
@@ -120,31 +104,66 @@ pub fn MemberPage(
     link_class:  &'static str,
     /// Couleur du spinner
     spin_class:  &'static str,
-    /// Si `Some("Communiant")` : active la multi-sélection + bouton "Transférer"
+    /// Si `Some(MemberType::Communiant)` : ajoute le bouton "Transférer" à la sélection
     #[prop(optional)]
-    transfer_to: Option<&'static str>,
+    transfer_to: Option<MemberType>,
 ) -> impl IntoView {
 
     // ── Données ────────────────────────────────────────────────────────────────
-    let membres: RwSignal<Vec<MemberWithTotal>> = RwSignal::new(vec![]);
+    // Mode client : liste complète ; mode serveur : page courante + effectif filtré
+    let membres:      RwSignal<Vec<MemberWithTotal>> = RwSignal::new(vec![]);
+    let server_rows:  RwSignal<Vec<MemberRow>>       = RwSignal::new(vec![]);
+    let server_total: RwSignal<usize>                = RwSignal::new(0);
     let loading   = RwSignal::new(true);
 
-    // ── Notifications flottantes (auto-dismiss 4 s) ────────────────────────────
-    let notif_error:   RwSignal<Option<String>> = RwSignal::new(None);
-    let notif_success: RwSignal<Option<String>> = RwSignal::new(None);
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
 
-    auto_dismiss(notif_error);
-    auto_dismiss(notif_success);
+    // Version globale des données : incrémentée seulement après une écriture réussie
+    // (un « Foana » ne recharge rien), elle invalide aussi le cache et les badges de la Navbar
+    let refresh_ctr: RwSignal<u32> = use_context::<DataVersionCtx>()
+        .expect("DataVersionCtx manquant")
+        .version;
+    let member_data = use_context::<MemberDataCtx>().expect("MemberDataCtx manquant");
+    let load_gen = RequestGen::new();
+    // « Avereno » d'un chargement échoué : relance les chargements de la page
+    let reload = move || refresh_ctr.update(|n| *n += 1);
 
-    let refresh_ctr: RwSignal<u32> = RwSignal::new(0);
+    // Effectif pas encore connu : mode serveur, exact quelle que soit la taille
+    let server_mode = Memo::new(move |_| {
+        member_data
+            .counts
+            .get()
+            .is_none_or(|c| c.of(member_type) > CLIENT_SIDE_MAX_MEMBERS)
+    });
 
     Effect::new(move |_| {
-        let _ = refresh_ctr.get();
-        loading.set(true);
+        let version = refresh_ctr.get();
+        if server_mode.get() {
+            return;
+        }
+        let gen = load_gen.next();
+        // Liste préchargée au survol de l'onglet : affichage immédiat
+        if let Some(liste) = member_data.cached(member_type) {
+            membres.set(liste);
+            loading.set(false);
+            return;
+        }
+        // Spinner au premier chargement seulement : un rechargement garde la liste affichée
+        if membres.get_untracked().is_empty() {
+            loading.set(true);
+        }
         leptos::task::spawn_local(async move {
-            match db_service::get_members_by_type_with_total(member_type).await {
-                Ok(liste) => membres.set(liste),
-                Err(e)    => notif_error.set(Some(e)),
+            let res = db_service::get_members_by_type_with_total(member_type, None).await;
+            // Réponse d'un chargement dépassé : ignorée
+            if !load_gen.is_current(gen) {
+                return;
+            }
+            match res {
+                Ok(liste) => {
+                    member_data.store(member_type, version, liste.clone());
+                    membres.set(liste);
+                }
+                Err(e) => toasts.error_with_retry(e, reload),
             }
             loading.set(false);
         });
@@ -152,71 +171,289 @@ pub fn MemberPage(
 
     // ── Recherche / Filtres / Tri / Pagination ─────────────────────────────────
     let recherche:    RwSignal<String>  = RwSignal::new(String::new());
-    let filtre_genre: RwSignal<String>  = RwSignal::new("Rehetra".into());
+    let filtre_genre: RwSignal<Option<Gender>> = RwSignal::new(None);
+    // Membres actifs par défaut ; `None` : tous les statuts
+    let filtre_statut: RwSignal<Option<MemberStatus>> = RwSignal::new(Some(MemberStatus::Actif));
+    // « Tsy nandoa » : liste chargée par `get_members_without_contribution`,
+    // filtrée en mémoire quel que soit l'effectif
+    let filtre_unpaid: RwSignal<bool>   = RwSignal::new(false);
+    let unpaid_membres: RwSignal<Vec<MemberWithTotal>> = RwSignal::new(vec![]);
+    let current_year = js_sys::Date::new_0().get_full_year() as i32;
     let sort_col:     RwSignal<SortCol> = RwSignal::new(SortCol::Nom);
     let sort_dir:     RwSignal<SortDir> = RwSignal::new(SortDir::Asc);
     let page:         RwSignal<usize>   = RwSignal::new(0);
 
+    let recherche_debounced = use_debounced_signal(recherche, SEARCH_DEBOUNCE_MS);
+
     let selected: RwSignal<Vec<i64>> = RwSignal::new(vec![]);
     Effect::new(move |_| {
-        let _ = recherche.get();
+        let _ = recherche_debounced.get();
         let _ = filtre_genre.get();
+        let _ = filtre_statut.get();
+        let _ = filtre_unpaid.get();
         page.set(0);
         selected.set(vec![]);
     });
 
+    let unpaid_gen = RequestGen::new();
+    Effect::new(move |_| {
+        let _ = refresh_ctr.get();
+        if !filtre_unpaid.get() {
+            return;
+        }
+        let gen = unpaid_gen.next();
+        if unpaid_membres.get_untracked().is_empty() {
+            loading.set(true);
+        }
+        leptos::task::spawn_local(async move {
+            let res = db_service::get_members_without_contribution(current_year, Some(member_type)).await;
+            if !unpaid_gen.is_current(gen) {
+                return;
+            }
+            match res {
+                Ok(liste) => unpaid_membres.set(liste.into_iter().map(MemberWithTotal::from).collect()),
+                Err(e) => toasts.error_with_retry(e, reload),
+            }
+            loading.set(false);
+        });
+    });
+
+    // ── Vue « par année » : totaux de l'année précédente et de l'année courante ──
+    // Mode client seulement ; la liste « Tsy nandoa » garde sa colonne unique
+    let by_year: RwSignal<bool> = RwSignal::new(false);
+    let year_view = Memo::new(move |_| {
+        by_year.get() && !server_mode.get() && !filtre_unpaid.get()
+    });
+    let year_membres: RwSignal<Vec<MemberWithTotal>> = RwSignal::new(vec![]);
+    let year_gen = RequestGen::new();
+    Effect::new(move |_| {
+        let _ = refresh_ctr.get();
+        if !year_view.get() {
+            return;
+        }
+        let gen = year_gen.next();
+        if year_membres.get_untracked().is_empty() {
+            loading.set(true);
+        }
+        leptos::task::spawn_local(async move {
+            let years = [current_year - 1, current_year];
+            let res = db_service::get_members_with_year_totals(member_type, &years).await;
+            if !year_gen.is_current(gen) {
+                return;
+            }
+            match res {
+                Ok(liste) => year_membres.set(
+                    liste.into_iter().map(|m| m.into_year_view(current_year)).collect(),
+                ),
+                Err(e) => toasts.error_with_retry(e, reload),
+            }
+            loading.set(false);
+        });
+    });
+    // La colonne de l'année précédente disparaît : le tri revient au nom
+    Effect::new(move |_| {
+        if !year_view.get() && sort_col.get_untracked() == SortCol::AnneePrecedente {
+            sort_col.set(SortCol::Nom);
+            sort_dir.set(SortDir::Asc);
+        }
+    });
+
+    // Pagination en mémoire : petite liste complète ou liste « Tsy nandoa »
+    let client_side = Memo::new(move |_| !server_mode.get() || filtre_unpaid.get());
+
+    // ── Mode serveur : une page à la fois ──────────────────────────────────────
+    let rows_gen = RequestGen::new();
+    Effect::new(move |_| {
+        let _ = refresh_ctr.get();
+        if client_side.get() {
+            return;
+        }
+        let filter = MemberFilter {
+            search: recherche_debounced.get(),
+            gender: filtre_genre.get(),
+            status: filtre_statut.get(),
+            ..Default::default()
+        };
+        let sort = MemberSort {
+            col:  sort_col.get().backend(),
+            desc: sort_dir.get() == SortDir::Desc,
+        };
+        let p   = page.get();
+        let gen = rows_gen.next();
+        if server_rows.get_untracked().is_empty() {
+            loading.set(true);
+        }
+        leptos::task::spawn_local(async move {
+            let res = db_service::get_member_rows(member_type, p, PAGE_SIZE, sort, &filter).await;
+            if !rows_gen.is_current(gen) {
+                return;
+            }
+            match res {
+                Ok(rows) => {
+                    server_total.set(rows.total_count.max(0) as usize);
+                    server_rows.set(rows.items);
+                }
+                Err(e) => toasts.error_with_retry(e, reload),
+            }
+            loading.set(false);
+        });
+    });
+
+    // ── Mode client : filtrage et tri en mémoire ───────────────────────────────
+    // Index reconstruit au chargement de la liste seulement ; une frappe ne fait
+    // que recalculer des positions, et seule la page affichée est clonée
+    let index = Memo::new_with_compare(
+        move |_| {
+            let source = if filtre_unpaid.get() {
+                unpaid_membres
+            } else if year_view.get() {
+                year_membres
+            } else {
+                membres
+            };
+            source.with(|l| MemberIndex::build(l))
+        },
+        |_, _| true,
+    );
     let sorted_filtered = Memo::new(move |_| {
-        filter_and_sort(
-            membres.get(),
-            &recherche.get(),
-            &filtre_genre.get(),
-            sort_col.get(),
-            sort_dir.get(),
-        )
+        index.with(|ix| {
+            ix.filter_sorted(
+                &recherche_debounced.get(),
+                filtre_genre.get(),
+                filtre_statut.get(),
+                sort_col.get(),
+                sort_dir.get(),
+            )
+        })
+    });
+
+    // ── Vue « par adresse » : mode client seulement (liste complète en mémoire) ──
+    let group_by_address: RwSignal<bool> = RwSignal::new(false);
+    let grouped = Memo::new(move |_| group_by_address.get() && client_side.get());
+    let sections = Memo::new(move |_| {
+        if !grouped.get() {
+            return None;
+        }
+        index.with(|ix| {
+            sorted_filtered.with(|positions| {
+                let groups = ix.group_by_address(positions);
+                Some(
+                    groups
+                        .into_iter()
+                        .map(|(address, group)| AddressSection {
+                            address,
+                            rows: group
+                                .iter()
+                                .filter_map(|&pos| ix.get(pos).cloned().map(MemberRow::from))
+                                .collect(),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+        })
+    });
+    // Sections repliées : retrouvées en revenant sur la page
+    let collapsed: RwSignal<HashSet<String>> =
+        RwSignal::new(member_data.collapsed_addresses(member_type));
+    Effect::new(move |_| member_data.remember_collapsed(member_type, collapsed.get()));
+
+    let filtered_count = Memo::new(move |_| {
+        if client_side.get() { sorted_filtered.with(Vec::len) } else { server_total.get() }
+    });
+
+    let has_members = Memo::new(move |_| {
+        if filtre_unpaid.get() {
+            true
+        } else if server_mode.get() {
+            server_total.get() > 0
+                || !recherche_debounced.get().is_empty()
+                || filtre_genre.get().is_some()
+                || filtre_statut.get().is_some()
+        } else {
+            !membres.with(Vec::is_empty)
+        }
     });
 
     let total_pages = Memo::new(move |_| {
-        ((sorted_filtered.get().len() + PAGE_SIZE - 1) / PAGE_SIZE).max(1)
+        if grouped.get() { 1 } else { filtered_count.get().div_ceil(PAGE_SIZE).max(1) }
+    });
+
+    // Page devenue hors limites (suppression, transfert) : retour à la dernière
+    Effect::new(move |_| {
+        let last = total_pages.get() - 1;
+        if page.get() > last {
+            page.set(last);
+        }
     });
 
     let page_items = Memo::new(move |_| {
-        sorted_filtered
-            .get()
-            .into_iter()
-            .skip(page.get() * PAGE_SIZE)
-            .take(PAGE_SIZE)
-            .collect::<Vec<_>>()
+        if !client_side.get() {
+            return server_rows.get();
+        }
+        // Vue par adresse : toutes les lignes des sections (sélection « tout cocher »)
+        if let Some(rows) = sections.with(|s| {
+            s.as_ref().map(|list| list.iter().flat_map(|s| s.rows.iter().cloned()).collect::<Vec<_>>())
+        }) {
+            return rows;
+        }
+        let p = page.get();
+        index.with(|ix| {
+            sorted_filtered.with(|positions| {
+                positions
+                    .iter()
+                    .skip(p * PAGE_SIZE)
+                    .take(PAGE_SIZE)
+                    .filter_map(|&pos| ix.get(pos).cloned().map(MemberRow::from))
+                    .collect::<Vec<_>>()
+            })
+        })
     });
 
     let all_page_selected = Memo::new(move |_| {
-        let items = page_items.get();
-        !items.is_empty() && items.iter().all(|m| selected.get().contains(&m.id))
+        page_items.with(|items| {
+            !items.is_empty() && selected.with(|s| items.iter().all(|m| s.contains(&m.id)))
+        })
     });
 
     // ── Transfert ──────────────────────────────────────────────────────────────
     let transferring_ids: RwSignal<Vec<i64>> = RwSignal::new(vec![]);
     let transfer_modal:   RwSignal<bool> = RwSignal::new(false);
     let transfer_loading: RwSignal<bool> = RwSignal::new(false);
+    // Fusion : proposée quand exactement deux fiches sont cochées
+    let merge_modal:      RwSignal<bool> = RwSignal::new(false);
 
+    // Les lignes glissent dès la confirmation ; la liste n'est rechargée qu'une fois
+    // l'animation terminée. En cas d'échec, les lignes reviennent en place.
     let do_transfer = Callback::new(move |()| {
         let ids = selected.get();
-        if ids.is_empty() { return; }
+        if ids.is_empty() || transfer_loading.get_untracked() { return; }
         let target = match transfer_to { Some(t) => t, None => return };
         transfer_loading.set(true);
+        transfer_modal.set(false);
         transferring_ids.set(ids.clone());
+        let started = js_sys::Date::now();
         leptos::task::spawn_local(async move {
-            let result = db_service::transfer_members(&ids, target).await;
-            sleep_ms(400).await;
-            match result {
-                Ok(_) => {
-                    transfer_modal.set(false);
+            match db_service::transfer_members(&ids, target).await {
+                Ok(moved) => {
+                    // Lignes réellement transférées (doublons de sélection écartés)
+                    transferring_ids.set(moved.iter().map(|m| m.id).collect());
+                    let elapsed = (js_sys::Date::now() - started) as u32;
+                    sleep_ms(TRANSFER_ANIM_MS.saturating_sub(elapsed)).await;
                     selected.set(vec![]);
                     transferring_ids.set(vec![]);
                     refresh_ctr.update(|n| *n += 1);
+                    toasts.success(match moved.as_slice() {
+                        [m] => format!("Nafindra any amin'ny {} i {}", target.label(), m.full_name),
+                        _ => format!(
+                            "Vita ny famindrana : {} mpikambana any amin'ny {}",
+                            moved.len(),
+                            target.label(),
+                        ),
+                    });
                 }
                 Err(e) => {
-                    notif_error.set(Some(e));
                     transferring_ids.set(vec![]);
+                    toasts.error(e);
                 }
             }
             transfer_loading.set(false);
@@ -231,16 +468,50 @@ pub fn MemberPage(
     let f_adresse:   RwSignal<String> = RwSignal::new(String::new());
     let f_telephone: RwSignal<String> = RwSignal::new(String::new());
     let f_travail:   RwSignal<String> = RwSignal::new(String::new());
-    let f_genre:     RwSignal<String> = RwSignal::new("M".into());
+    let f_genre:     RwSignal<Gender> = RwSignal::new(Gender::M);
+    let f_naissance: RwSignal<String> = RwSignal::new(String::new());
+    let f_statut:    RwSignal<MemberStatus> = RwSignal::new(MemberStatus::Actif);
     let f_loading:   RwSignal<bool>   = RwSignal::new(false);
 
+    // Création optimiste (liste complète en mémoire seulement) : ligne provisoire
+    // aussitôt, remplacée par la fiche enregistrée ou retirée en cas d'échec.
+    // Une saisie refusée rouvre le formulaire, encore rempli.
+    let create_optimistic = Callback::new(move |input: MemberInput| {
+        if server_mode.get_untracked() {
+            return false;
+        }
+        let temp = optimistic::temp_id(&membres.get_untracked());
+        membres.update(|l| {
+            optimistic::apply_optimistic(l, MemberWithTotal::provisional(temp, &input));
+        });
+        leptos::task::spawn_local(async move {
+            match db_service::create_member(&input).await {
+                Ok(m) => {
+                    membres.update(|l| optimistic::confirm(l, temp, m.into()));
+                    refresh_ctr.update(|n| *n += 1);
+                    toasts.success(format!("Voasoratra ny mpikambana vaovao : {}", input.full_name));
+                }
+                Err(e) => {
+                    membres.update(|l| optimistic::rollback(l, temp));
+                    if e.is_input_error() {
+                        modal_ouvert.set(true);
+                    }
+                    toasts.error(e.to_string());
+                }
+            }
+        });
+        true
+    });
+
     let reset_form = move || {
         f_carte.set(String::new());
         f_nom.set(String::new());
         f_adresse.set(String::new());
         f_telephone.set(String::new());
         f_travail.set(String::new());
-        f_genre.set("M".into());
+        f_genre.set(Gender::M);
+        f_naissance.set(String::new());
+        f_statut.set(MemberStatus::Actif);
         edit_id.set(None);
     };
 
@@ -250,6 +521,39 @@ pub fn MemberPage(
     let contrib_membre_nom: RwSignal<String> = RwSignal::new(String::new());
     let confetti_active:    RwSignal<bool>   = RwSignal::new(false);
 
+    // ── Lecteur de code-barres : scan d'une carte → cotisation du membre ─────
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let confirm = use_confirm();
+    let scan_buffer = StoredValue::new_local(ScanBuffer::default());
+    let scan_listener = window_event_listener(leptos::ev::keydown, move |ev| {
+        let busy = modal_ouvert.get_untracked()
+            || contrib_open.get_untracked()
+            || transfer_modal.get_untracked()
+            || merge_modal.get_untracked()
+            || pin.open.get_untracked()
+            || confirm.request.with_untracked(Option::is_some);
+        if busy || is_editable_target(&ev) {
+            return;
+        }
+        let now = js_sys::Date::now();
+        let Some(card) = scan_buffer.try_update_value(|b| b.feed(&ev.key(), now)).flatten() else {
+            return;
+        };
+        ev.prevent_default();
+        leptos::task::spawn_local(async move {
+            match db_service::get_member_by_card(&card).await {
+                Ok(Some(m)) => {
+                    contrib_membre_id.set(m.id);
+                    contrib_membre_nom.set(m.full_name);
+                    contrib_open.set(true);
+                }
+                Ok(None) => toasts.error(format!("Tsy misy mpikambana manana ny karatra {card}")),
+                Err(e) => toasts.error(e),
+            }
+        });
+    });
+    on_cleanup(move || scan_listener.remove());
+
     // ── Export CSV ────────────────────────────────────────────────────────────
     let export_loading: RwSignal<bool> = RwSignal::new(false);
 
@@ -258,12 +562,13 @@ pub fn MemberPage(
         leptos::task::spawn_local(async move {
             match db_service::export_members_excel(member_type).await {
                 Ok(bytes) => {
-                    let filename = format!("{}.xlsx", member_type.to_lowercase());
-                    if let Err(e) = trigger_xlsx_download(&bytes, &filename) {
-                        notif_error.set(Some(e));
+                    let filename = format!("{}.xlsx", member_type.as_str().to_lowercase());
+                    match trigger_xlsx_download(&bytes, &filename) {
+                        Ok(()) => toasts.info(format!("Voaalefa : {filename}")),
+                        Err(e) => toasts.error(e),
                     }
                 }
-                Err(e) => notif_error.set(Some(e)),
+                Err(e) => toasts.error(e),
             }
             export_loading.set(false);
         });
@@ -272,6 +577,8 @@ pub fn MemberPage(
     // ── Import CSV ────────────────────────────────────────────────────────────
     let file_input_ref: NodeRef<leptos::html::Input> = NodeRef::new();
     let import_loading: RwSignal<bool> = RwSignal::new(false);
+    // Lignes écartées par le dernier import, à corriger dans le fichier
+    let import_errors: RwSignal<Vec<ImportRowError>> = RwSignal::new(vec![]);
 
     let trigger_import = move |_| {
         if let Some(el) = file_input_ref.get() {
@@ -291,18 +598,21 @@ pub fn MemberPage(
                 Ok(val) => {
                     let text: String = val.as_string().unwrap_or_default();
                     match db_service::import_members_csv(&text, member_type).await {
-                        Ok(count) => {
-                            notif_success.set(Some(format!(
-                                "{count} mpikambana nampidirina",
-                            )));
+                        Ok(report) => {
+                            if report.skipped == 0 {
+                                toasts.success(report.summary());
+                            } else {
+                                toasts.info(report.summary());
+                            }
+                            import_errors.set(report.errors);
                             refresh_ctr.update(|n| *n += 1);
                         }
-                        Err(e) => notif_error.set(Some(e)),
+                        Err(e) => toasts.error(e),
                     }
                 }
-                Err(e) => notif_error.set(Some(
+                Err(e) => toasts.error(
                     e.as_string().unwrap_or_else(|| "Hadisoana famakiana ny rakitra".into())
-                )),
+                ),
             }
             import_loading.set(false);
             // Réinitialiser pour permettre de re-sélectionner le même fichier
@@ -316,44 +626,6 @@ pub fn MemberPage(
     view! {
         <div class="animate-fade-in space-y-4 sm:space-y-5">
 
-            // ── Notification d'erreur flottante ────────────────────────────────
-            {move || notif_error.get().map(|msg| view! {
-                <div class="fixed top-5 right-5 z-[100] flex items-start gap-3 \
-                            px-4 py-3 rounded-2xl shadow-2xl border \
-                            bg-white dark:bg-gray-800 \
-                            border-red-200 dark:border-red-700 \
-                            max-w-xs w-full animate-fade-in">
-                    <IconAlertTriangle class="w-5 h-5 text-red-500 dark:text-red-400 shrink-0 mt-0.5" />
-                    <p class="text-sm text-red-700 dark:text-red-300 flex-1 leading-snug">{msg}</p>
-                    <button
-                        on:click=move |_| notif_error.set(None)
-                        class="btn-ripple text-red-400 hover:text-red-600 \
-                               dark:hover:text-red-200 rounded p-0.5 transition-colors"
-                    >
-                        "✕"
-                    </button>
-                </div>
-            })}
-
-            // ── Notification de succès flottante ───────────────────────────────
-            {move || notif_success.get().map(|msg| view! {
-                <div class="fixed top-5 right-5 z-[100] flex items-start gap-3 \
-                            px-4 py-3 rounded-2xl shadow-2xl border \
-                            bg-white dark:bg-gray-800 \
-                            border-green-200 dark:border-green-700 \
-                            max-w-xs w-full animate-fade-in">
-                    <span class="text-green-500 dark:text-green-400 shrink-0 mt-0.5 text-lg leading-none">"✓"</span>
-                    <p class="text-sm text-green-700 dark:text-green-300 flex-1 leading-snug">{msg}</p>
-                    <button
-                        on:click=move |_| notif_success.set(None)
-                        class="btn-ripple text-green-400 hover:text-green-600 \
-                               dark:hover:text-green-200 rounded p-0.5 transition-colors"
-                    >
-                        "✕"
-                    </button>
-                </div>
-            })}
-
             // ── En-tête ────────────────────────────────────────────────────────
             <div class="flex flex-wrap items-start sm:items-center justify-between gap-3">
                 <div>
@@ -367,24 +639,6 @@ pub fn MemberPage(
                     </p>
                 </div>
                 <div class="flex items-center gap-2 flex-wrap shrink-0">
-                    {move || {
-                        if transfer_to.is_none() { return None; }
-                        let n = selected.get().len();
-                        if n == 0 { return None; }
-                        Some(view! {
-                            <button
-                                on:click=move |_| transfer_modal.set(true)
-                                class="btn-ripple px-3 py-2 text-xs sm:text-sm font-semibold text-white \
-                                       bg-amber-500 hover:bg-amber-600 \
-                                       rounded-xl transition-colors duration-200 \
-                                       flex items-center gap-1.5 shadow-sm"
-                            >
-                                <IconTransfer class="w-4 h-4" />
-                                {format!("Famindra ({n})")}
-                            </button>
-                        })
-                    }}
-
                     // ── Bouton Exporter ──────────────────────────────────────
                     <button
                         on:click=do_export
@@ -421,6 +675,22 @@ pub fn MemberPage(
                         {move || if import_loading.get() { "Ampiditra…" } else { "Ampiditra" }}
                     </button>
 
+                    // ── Bouton Imprimer (page dédiée, liste complète) ─────────
+                    <A
+                        href=member_type.print_path()
+                        attr:class="btn-ripple px-3 py-2 text-xs sm:text-sm font-semibold \
+                                    text-gray-700 dark:text-gray-200 \
+                                    bg-white/80 dark:bg-gray-700/80 \
+                                    border border-gray-200 dark:border-gray-600 \
+                                    hover:bg-gray-50 dark:hover:bg-gray-600 \
+                                    rounded-xl transition-colors duration-200 \
+                                    flex items-center gap-1.5 shadow-sm"
+                        attr:title="Hanonta ny lisitra manontolo"
+                    >
+                        <IconPrinter class="w-4 h-4" />
+                        "Hanonta"
+                    </A>
+
                     // ── Input fichier caché ──────────────────────────────────
                     <input
                         type="file"
@@ -443,6 +713,45 @@ pub fn MemberPage(
                 </div>
             </div>
 
+            // ── Lignes écartées par l'import ───────────────────────────────────
+            {move || {
+                let errors = import_errors.get();
+                (!errors.is_empty()).then(|| view! {
+                    <div class="bg-amber-50/90 dark:bg-amber-900/30 \
+                                border border-amber-200 dark:border-amber-700 \
+                                rounded-2xl px-4 py-3 text-sm">
+                        <div class="flex items-start gap-2">
+                            <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5 \
+                                                      text-amber-600 dark:text-amber-400" />
+                            <p class="flex-1 font-semibold text-amber-800 dark:text-amber-200">
+                                {format!("Andalana {} tsy nampidirina", errors.len())}
+                            </p>
+                            <button
+                                type="button"
+                                aria-label="Akatona"
+                                on:click=move |_| import_errors.set(vec![])
+                                class="btn-ripple rounded p-0.5 text-amber-500 \
+                                       hover:text-amber-700 dark:hover:text-amber-200 \
+                                       transition-colors"
+                            >
+                                <IconX class="w-4 h-4" />
+                            </button>
+                        </div>
+                        <ul class="mt-2 ml-6 max-h-40 overflow-y-auto space-y-0.5 \
+                                   text-xs text-amber-800 dark:text-amber-200">
+                            {errors.into_iter().map(|e| view! {
+                                <li>
+                                    <span class="font-mono font-semibold">
+                                        {format!("Andalana {} :", e.line)}
+                                    </span>
+                                    " "{e.message}
+                                </li>
+                            }).collect_view()}
+                        </ul>
+                    </div>
+                })
+            }}
+
             // ── Barre de recherche + filtres ───────────────────────────────────
             <div class="flex flex-wrap gap-2 sm:gap-3 items-center">
                 <div class="relative flex-1 min-w-[180px]">
@@ -469,21 +778,102 @@ pub fn MemberPage(
                            border border-gray-200 dark:border-gray-600 \
                            rounded-xl text-gray-800 dark:text-white \
                            focus:outline-none focus:ring-2 focus:ring-blue-400 transition"
-                    prop:value=move || filtre_genre.get()
-                    on:change=move |ev| filtre_genre.set(event_target_value(&ev))
+                    prop:value=move || filtre_genre.get().map_or("Rehetra", Gender::as_str)
+                    on:change=move |ev| filtre_genre.set(Gender::from_value(&event_target_value(&ev)))
                 >
                     <option value="Rehetra">"Rehetra"</option>
-                    <option value="M">"Lehilahy"</option>
-                    <option value="F">"Vehivavy"</option>
+                    <option value=Gender::M.as_str()>"Lehilahy"</option>
+                    <option value=Gender::F.as_str()>"Vehivavy"</option>
                 </select>
+                <select
+                    class="px-3 py-2 text-sm \
+                           bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                           border border-gray-200 dark:border-gray-600 \
+                           rounded-xl text-gray-800 dark:text-white \
+                           focus:outline-none focus:ring-2 focus:ring-blue-400 transition"
+                    prop:value=move || filtre_statut.get().map_or("Rehetra", MemberStatus::as_str)
+                    on:change=move |ev| filtre_statut.set(MemberStatus::from_value(&event_target_value(&ev)))
+                >
+                    {MemberStatus::ALL.map(|s| view! {
+                        <option value=s.as_str()>{s.label()}</option>
+                    })}
+                    <option value="Rehetra">"Toetra rehetra"</option>
+                </select>
+                <button
+                    type="button"
+                    aria-pressed=move || filtre_unpaid.get().to_string()
+                    on:click=move |_| filtre_unpaid.update(|on| *on = !*on)
+                    class=move || format!(
+                        "btn-ripple px-3 py-2 text-sm rounded-xl border transition-colors \
+                         duration-200 whitespace-nowrap {}",
+                        if filtre_unpaid.get() {
+                            "bg-amber-50 dark:bg-amber-900/30 border-amber-300 \
+                             dark:border-amber-700 text-amber-700 dark:text-amber-300 font-semibold"
+                        } else {
+                            "bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                             border-gray-200 dark:border-gray-600 \
+                             text-gray-800 dark:text-white"
+                        }
+                    )
+                >
+                    {move || format!("{} {current_year}", tr("filter.unpaid"))}
+                </button>
+                <button
+                    type="button"
+                    aria-pressed=move || grouped.get().to_string()
+                    disabled=move || !client_side.get()
+                    title=move || if client_side.get() {
+                        "Vondrona isaky ny fonenana, ho an'ny fitsidihana an-tokantrano"
+                    } else {
+                        "Lisitra lava loatra : tsy azo avondrona araka ny adiresy"
+                    }
+                    on:click=move |_| group_by_address.update(|on| *on = !*on)
+                    class=move || format!(
+                        "btn-ripple px-3 py-2 text-sm rounded-xl border transition-colors \
+                         duration-200 whitespace-nowrap disabled:opacity-50 \
+                         disabled:cursor-not-allowed {}",
+                        if grouped.get() {
+                            "bg-blue-50 dark:bg-blue-900/30 border-blue-300 \
+                             dark:border-blue-700 text-blue-700 dark:text-blue-300 font-semibold"
+                        } else {
+                            "bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                             border-gray-200 dark:border-gray-600 \
+                             text-gray-800 dark:text-white"
+                        }
+                    )
+                >
+                    "Araka ny adiresy"
+                </button>
+                <button
+                    type="button"
+                    aria-pressed=move || year_view.get().to_string()
+                    disabled=move || server_mode.get() || filtre_unpaid.get()
+                    title=move || if server_mode.get() {
+                        "Lisitra lava loatra : tsy azo asehoana isan-taona"
+                    } else {
+                        "Rakitra tamin'ny taona teo aloha sy tamin'ity taona ity"
+                    }
+                    on:click=move |_| by_year.update(|on| *on = !*on)
+                    class=move || format!(
+                        "btn-ripple px-3 py-2 text-sm rounded-xl border transition-colors \
+                         duration-200 whitespace-nowrap disabled:opacity-50 \
+                         disabled:cursor-not-allowed {}",
+                        if year_view.get() {
+                            "bg-blue-50 dark:bg-blue-900/30 border-blue-300 \
+                             dark:border-blue-700 text-blue-700 dark:text-blue-300 font-semibold"
+                        } else {
+                            "bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                             border-gray-200 dark:border-gray-600 \
+                             text-gray-800 dark:text-white"
+                        }
+                    )
+                >
+                    {move || tr("filter.by_year")}
+                </button>
                 <span class="text-xs text-gray-500 dark:text-gray-400 whitespace-nowrap">
-                    {move || {
-                        let n = sorted_filtered.get().len();
-                        format!("{n} mpikambana")
-                    }}
+                    {move || format!("{} mpikambana", filtered_count.get())}
                 </span>
                 {move || {
-                    if transfer_to.is_none() { return None; }
                     let n = selected.get().len();
                     if n == 0 { return None; }
                     Some(view! {
@@ -498,24 +888,27 @@ pub fn MemberPage(
 
             // ── Tableau ────────────────────────────────────────────────────────
             <MemberTable
-                membres=membres
-                sorted_filtered=sorted_filtered
+                has_members=has_members
+                filtered_count=filtered_count
                 page=page
                 total_pages=total_pages
                 sort_col=sort_col
                 sort_dir=sort_dir
-                transfer_to=transfer_to
                 selected=selected
                 all_page_selected=all_page_selected
                 page_items=page_items
+                sections=sections
+                collapsed=collapsed
                 transferring_ids=transferring_ids
                 icon=icon
+                current_year=current_year
+                unpaid_mode=filtre_unpaid
+                year_columns=year_view
                 row_hover=row_hover
                 link_class=link_class
                 spin_class=spin_class
                 loading=loading
                 refresh_ctr=refresh_ctr
-                notif_error=notif_error
                 modal_ouvert=modal_ouvert
                 edit_id=edit_id
                 f_carte=f_carte
@@ -524,6 +917,8 @@ pub fn MemberPage(
                 f_telephone=f_telephone
                 f_travail=f_travail
                 f_genre=f_genre
+                f_naissance=f_naissance
+                f_statut=f_statut
                 contrib_membre_id=contrib_membre_id
                 contrib_membre_nom=contrib_membre_nom
                 contrib_open=contrib_open
@@ -537,17 +932,81 @@ pub fn MemberPage(
                     member_type=member_type
                     btn_class=btn_class
                     refresh_ctr=refresh_ctr
-                    notif_error=notif_error
+                    on_create=create_optimistic
                     f_carte=f_carte
                     f_nom=f_nom
                     f_adresse=f_adresse
                     f_telephone=f_telephone
                     f_travail=f_travail
                     f_genre=f_genre
+                    f_naissance=f_naissance
+                    f_statut=f_statut
                     f_loading=f_loading
                 />
             })}
 
+            // ── Barre d'action flottante (sélection → transfert, fusion) ───────
+            {move || {
+                let n = selected.get().len();
+                (n > 0).then(|| view! {
+                    <div class="fixed bottom-6 left-1/2 -translate-x-1/2 z-40 \
+                                flex items-center gap-3 pl-4 pr-2 py-2 \
+                                rounded-2xl shadow-2xl border \
+                                bg-white dark:bg-gray-800 \
+                                border-amber-200 dark:border-amber-700 animate-fade-in">
+                        <span class="text-sm font-semibold text-amber-600 dark:text-amber-400 whitespace-nowrap">
+                            {format!("{n} voafidy")}
+                        </span>
+                        <button
+                            type="button"
+                            disabled=move || transfer_loading.get()
+                            on:click=move |_| selected.set(vec![])
+                            class="btn-ripple px-3 py-2 text-xs font-medium \
+                                   text-gray-600 dark:text-gray-300 \
+                                   hover:bg-gray-100 dark:hover:bg-gray-700 \
+                                   disabled:opacity-50 rounded-xl transition-colors"
+                        >
+                            {move || tr("action.cancel")}
+                        </button>
+                        {(n == 2).then(|| view! {
+                            <button
+                                type="button"
+                                disabled=move || transfer_loading.get()
+                                on:click=move |_| merge_modal.set(true)
+                                class="btn-ripple px-3 py-2 text-xs sm:text-sm font-semibold \
+                                       text-amber-700 dark:text-amber-300 \
+                                       bg-amber-50 dark:bg-amber-900/30 \
+                                       hover:bg-amber-100 dark:hover:bg-amber-900/50 \
+                                       disabled:opacity-60 rounded-xl transition-colors duration-200 \
+                                       flex items-center gap-1.5"
+                            >
+                                <IconMerge class="w-4 h-4" />
+                                "Hampiraisina…"
+                            </button>
+                        })}
+                        {transfer_to.map(|tt| view! {
+                            <button
+                                type="button"
+                                disabled=move || transfer_loading.get()
+                                on:click=move |_| transfer_modal.set(true)
+                                class="btn-ripple px-3 py-2 text-xs sm:text-sm font-semibold text-white \
+                                       bg-amber-500 hover:bg-amber-600 \
+                                       disabled:opacity-60 disabled:cursor-wait \
+                                       rounded-xl transition-colors duration-200 \
+                                       flex items-center gap-1.5 shadow-sm"
+                            >
+                                <IconTransfer class="w-4 h-4" />
+                                {move || if transfer_loading.get() {
+                                    "Mamindra…".to_string()
+                                } else {
+                                    format!("Afindra any amin'ny {}", tt.label())
+                                }}
+                            </button>
+                        })}
+                    </div>
+                })
+            }}
+
             // ── Modal de transfert ─────────────────────────────────────────────
             {move || {
                 let tt = transfer_to?;
@@ -556,12 +1015,17 @@ pub fn MemberPage(
                         open=transfer_modal
                         loading=transfer_loading
                         selected=selected
-                        _transfer_to=tt
+                        transfer_to=tt
                         on_confirm=do_transfer
                     />
                 })
             }}
 
+            // ── Modal de fusion ────────────────────────────────────────────────
+            {move || merge_modal.get().then(|| view! {
+                <MergeModal open=merge_modal selected=selected />
+            })}
+
             // ── Modal cotisation ───────────────────────────────────────────────
             {move || {
                 if !contrib_open.get() { return None; }
@@ -1,14 +1,23 @@
 /// Tableau des membres avec tri par colonne.
+use std::collections::HashSet;
+
 use leptos::prelude::*;
+use leptos_router::components::A;
 use wasm_bindgen::JsCast;
 
 use crate::{
-    components::icons::{
-        IconChevronLeft, IconChevronRight, IconCoins, IconPencil, IconSearch,
-        IconTrash, PageIcon,
+    app::{PinCtx, ToastProviderCtx},
+    components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
+        icons::{
+            IconChevronLeft, IconChevronRight, IconCoins, IconPencil, IconSearch,
+            IconTrash, PageIcon,
+        },
     },
-    models::member::MemberWithTotal,
+    i18n::tr,
+    models::member::{Gender, MemberRow, MemberSortCol, MemberStatus},
     services::db_service,
+    utils::{format_ariary_str, money::parse_decimal, MoneyFormat},
 };
 
 pub const PAGE_SIZE: usize = 15;
@@ -16,7 +25,24 @@ pub const PAGE_SIZE: usize = 15;
 // ─── Tri ──────────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Copy, PartialEq)]
-pub enum SortCol { Carte, Nom, Adresse, Telephone, Travail, Genre }
+pub enum SortCol { Carte, Nom, Adresse, Telephone, Travail, Genre, AnneeCourante, AnneePrecedente }
+
+impl SortCol {
+    /// Colonne équivalente pour le tri SQL de `get_member_rows`.
+    pub fn backend(self) -> MemberSortCol {
+        match self {
+            Self::Carte         => MemberSortCol::CardNumber,
+            Self::Nom           => MemberSortCol::FullName,
+            Self::Adresse       => MemberSortCol::Address,
+            Self::Telephone     => MemberSortCol::Phone,
+            Self::Travail       => MemberSortCol::Job,
+            Self::Genre         => MemberSortCol::Gender,
+            Self::AnneeCourante => MemberSortCol::CurrentYearTotal,
+            // Colonne de la vue « par année », en mémoire seulement
+            Self::AnneePrecedente => MemberSortCol::CurrentYearTotal,
+        }
+    }
+}
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum SortDir { Asc, Desc }
@@ -30,6 +56,16 @@ impl SortDir {
     }
 }
 
+// ─── Vue par adresse ──────────────────────────────────────────────────────────
+
+/// Membres d'un quartier, dans l'ordre de tri du tableau.
+#[derive(Clone, PartialEq)]
+pub struct AddressSection {
+    /// Adresse normalisée ; `None` : sans adresse
+    pub address: Option<String>,
+    pub rows:    Vec<MemberRow>,
+}
+
 // ─── Helper interne ───────────────────────────────────────────────────────────
 
 fn checked_from_event(ev: web_sys::Event) -> bool {
@@ -39,16 +75,61 @@ fn checked_from_event(ev: web_sys::Event) -> bool {
         .unwrap_or(false)
 }
 
+/// Cellule d'une colonne annuelle : « — » quand rien n'a été versé.
+fn year_cell(total: &str) -> String {
+    if parse_decimal(total).is_zero() {
+        "—".into()
+    } else {
+        format_ariary_str(total, MoneyFormat::default())
+    }
+}
+
+/// Suppression d'une ligne après le PIN : toast « Averina » en cas de succès,
+/// « Avereno » si la base a refusé l'écriture (verrou passager…).
+fn delete_member_row(
+    mid: i64,
+    nom: String,
+    pin: PinCtx,
+    refresh_ctr: RwSignal<u32>,
+    toasts: ToastProviderCtx,
+) {
+    pin.guard(move |token| leptos::task::spawn_local(async move {
+        match db_service::delete_member(mid, token.as_deref()).await {
+            Ok(_) => {
+                refresh_ctr.update(|n| *n += 1);
+                toasts.undoable(format!("Voafafa i {nom}"), move || {
+                    leptos::task::spawn_local(async move {
+                        match db_service::restore_member(mid).await {
+                            Ok(m)  => {
+                                refresh_ctr.update(|n| *n += 1);
+                                toasts.success(format!("Naverina i {}", m.full_name));
+                            }
+                            Err(e) => toasts.error(e),
+                        }
+                    });
+                });
+            }
+            Err(e) => toasts.error_with_retry(e, move || {
+                delete_member_row(mid, nom.clone(), pin, refresh_ctr, toasts);
+            }),
+        }
+    }));
+}
+
 // ─── Composant Th ─────────────────────────────────────────────────────────────
 
 #[component]
 pub fn Th(
+    /// Clé de traduction du libellé (`i18n::tr`)
     label:       &'static str,
     col:         SortCol,
     sort_col:    RwSignal<SortCol>,
     sort_dir:    RwSignal<SortDir>,
     #[prop(optional)]
     extra_class: &'static str,
+    /// Libellé brut à la place de `label` (colonnes d'années)
+    #[prop(optional, into)]
+    text:        Option<String>,
 ) -> impl IntoView {
     view! {
         <th
@@ -64,7 +145,7 @@ pub fn Th(
                 }
             }
         >
-            {label}
+            {move || text.clone().unwrap_or_else(|| tr(label))}
             {move || if sort_col.get() == col { sort_dir.get().arrow() } else { "" }}
         </th>
     }
@@ -75,28 +156,39 @@ pub fn Th(
 #[component]
 pub fn MemberTable(
     // ── Données et pagination ────────────────────────────────────────────────
-    membres:          RwSignal<Vec<MemberWithTotal>>,
-    sorted_filtered:  Memo<Vec<MemberWithTotal>>,
+    /// Au moins un membre de ce type existe (sinon : état vide plutôt que « Tsy misy vokatra »)
+    has_members:      Memo<bool>,
+    /// Nombre de membres après recherche et filtres, toutes pages confondues
+    filtered_count:   Memo<usize>,
     page:             RwSignal<usize>,
     total_pages:      Memo<usize>,
     // ── Tri ──────────────────────────────────────────────────────────────────
     sort_col:         RwSignal<SortCol>,
     sort_dir:         RwSignal<SortDir>,
-    // ── Sélection / transfert ─────────────────────────────────────────────
-    transfer_to:      Option<&'static str>,
+    // ── Sélection (transfert, fusion) ─────────────────────────────────────
     selected:         RwSignal<Vec<i64>>,
     all_page_selected: Memo<bool>,
-    page_items:       Memo<Vec<MemberWithTotal>>,
+    page_items:       Memo<Vec<MemberRow>>,
+    // ── Vue « par adresse » ──────────────────────────────────────────────────
+    /// Sections repliables à la place des pages (`None` : tableau paginé)
+    sections:         Memo<Option<Vec<AddressSection>>>,
+    /// Clés des sections repliées (`address_key`, "" : sans adresse)
+    collapsed:        RwSignal<HashSet<String>>,
     transferring_ids: RwSignal<Vec<i64>>,
     // ── Style paramétrable ────────────────────────────────────────────────
     icon:             &'static str,
+    /// Année civile affichée dans la colonne et l'infobulle « sans cotisation »
+    current_year:     i32,
+    /// Liste « Tsy nandoa » : la colonne de l'année n'affiche que « — »
+    unpaid_mode:      RwSignal<bool>,
+    /// Vue « par année » : colonnes année précédente et année courante
+    year_columns:     Memo<bool>,
     row_hover:        &'static str,
     link_class:       &'static str,
     spin_class:       &'static str,
     // ── Signaux partagés ──────────────────────────────────────────────────
     loading:          RwSignal<bool>,
     refresh_ctr:      RwSignal<u32>,
-    notif_error:      RwSignal<Option<String>>,
     // ── Ouverture modale édition ──────────────────────────────────────────
     modal_ouvert:     RwSignal<bool>,
     edit_id:          RwSignal<Option<i64>>,
@@ -105,12 +197,197 @@ pub fn MemberTable(
     f_adresse:        RwSignal<String>,
     f_telephone:      RwSignal<String>,
     f_travail:        RwSignal<String>,
-    f_genre:          RwSignal<String>,
+    f_genre:          RwSignal<Gender>,
+    f_naissance:      RwSignal<String>,
+    f_statut:         RwSignal<MemberStatus>,
     // ── Ouverture modale cotisation ───────────────────────────────────────
     contrib_membre_id:  RwSignal<i64>,
     contrib_membre_nom: RwSignal<String>,
     contrib_open:       RwSignal<bool>,
 ) -> impl IntoView {
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let confirm = use_confirm();
+
+    // Ligne d'un membre, commune au tableau paginé et aux sections par adresse
+    let row_view = move |m: MemberRow| {
+        let m_edit = m.clone();
+        let nom_delete = m.full_name.clone();
+        let nom_link   = m.full_name.clone();
+        let mid    = m.id;
+        let genre_label = match m.gender { Gender::M => "♂ Lahy", Gender::F => "♀ Vavy" };
+        let unpaid      = m.unpaid_this_year();
+        let year_total  = format_ariary_str(&m.current_year_total, MoneyFormat::default());
+        let prev_total  = year_cell(m.previous_year_total.as_deref().unwrap_or("0"));
+
+        view! {
+            <tr class=move || {
+                let sliding = transferring_ids.get().contains(&mid);
+                format!(
+                    "tr-hover border-b border-gray-50 \
+                     dark:border-gray-700/50 \
+                     {} transition-colors duration-150{}{}",
+                    row_hover,
+                    if sliding { " row-sliding-out" } else { "" },
+                    // Ligne provisoire (création en cours) : pas d'action
+                    if mid < 0 { " opacity-60 pointer-events-none" } else { "" }
+                )
+            }>
+                <td class="pl-4 pr-2 py-2.5">
+                    <input
+                        type="checkbox"
+                        class="custom-check"
+                        prop:checked=move || selected.get().contains(&mid)
+                        on:change=move |ev: web_sys::Event| {
+                            let checked = checked_from_event(ev);
+                            selected.update(|s| {
+                                if checked {
+                                    if !s.contains(&mid) { s.push(mid); }
+                                } else {
+                                    s.retain(|&id| id != mid);
+                                }
+                            });
+                        }
+                    />
+                </td>
+                <td class="hidden sm:table-cell px-3 py-2.5 \
+                           font-mono text-xs \
+                           text-gray-500 dark:text-gray-400 \
+                           whitespace-nowrap">
+                    {m.card_number.clone()}
+                </td>
+                <td class="px-3 py-2.5 font-semibold \
+                           text-gray-800 dark:text-white \
+                           whitespace-nowrap">
+                    <A href=format!("/membre/{mid}") attr:class="hover:underline">
+                        {nom_link.clone()}
+                    </A>
+                    // Statut visible quand le filtre laisse passer les non-actifs
+                    {(m.status != MemberStatus::Actif).then(|| view! {
+                        <span class="ml-2 px-1.5 py-0.5 text-[10px] font-medium rounded \
+                                     bg-gray-100 dark:bg-gray-700 \
+                                     text-gray-500 dark:text-gray-400">
+                            {m.status.label()}
+                        </span>
+                    })}
+                </td>
+                <td class="hidden md:table-cell px-3 py-2.5 \
+                           text-gray-600 dark:text-gray-300 \
+                           max-w-[140px] truncate">
+                    {m.address.clone().unwrap_or_else(|| "—".into())}
+                </td>
+                <td class="hidden lg:table-cell px-3 py-2.5 \
+                           text-gray-600 dark:text-gray-300 \
+                           whitespace-nowrap">
+                    {m.phone.clone().unwrap_or_else(|| "—".into())}
+                </td>
+                <td class="hidden md:table-cell px-3 py-2.5 \
+                           text-gray-600 dark:text-gray-300 \
+                           max-w-[120px] truncate">
+                    {m.job.clone().unwrap_or_else(|| "—".into())}
+                </td>
+                <td class="hidden sm:table-cell px-3 py-2.5 \
+                           text-gray-600 dark:text-gray-300 \
+                           whitespace-nowrap">
+                    {genre_label}
+                </td>
+                {move || year_columns.get().then(|| view! {
+                    <td class="hidden sm:table-cell px-3 py-2.5 whitespace-nowrap \
+                               text-xs text-gray-600 dark:text-gray-300">
+                        {prev_total.clone()}
+                    </td>
+                })}
+                <td class="hidden sm:table-cell px-3 py-2.5 whitespace-nowrap">
+                    {move || if unpaid_mode.get() || (year_columns.get() && unpaid) {
+                        view! {
+                            <span class="text-xs text-gray-400 dark:text-gray-500">"—"</span>
+                        }.into_any()
+                    } else if unpaid {
+                        view! {
+                            <span
+                                title=format!("Tsy mbola nandoa rakitra tamin'ny {current_year}")
+                                class="inline-flex items-center gap-1.5 px-2 py-0.5 \
+                                       rounded-full text-xs font-medium \
+                                       bg-amber-50 dark:bg-amber-900/30 \
+                                       text-amber-700 dark:text-amber-300"
+                            >
+                                <span class="w-1.5 h-1.5 rounded-full bg-amber-500" />
+                                "Tsy mbola nandoa"
+                            </span>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <span class="text-xs text-gray-600 dark:text-gray-300">
+                                {year_total.clone()}
+                            </span>
+                        }.into_any()
+                    }}
+                </td>
+                <td class="px-3 py-2.5 pr-4 text-right whitespace-nowrap">
+                    <button
+                        title="Rakitra"
+                        class="btn-ripple mr-2 text-xs text-amber-500 \
+                               dark:text-amber-400 rounded \
+                               hover:scale-125 transition-transform \
+                               duration-150 font-medium"
+                        on:click=move |_| {
+                            contrib_membre_id.set(mid);
+                            contrib_membre_nom.set(m.full_name.clone());
+                            contrib_open.set(true);
+                        }
+                    >
+                        <IconCoins class="w-4 h-4" />
+                    </button>
+                    <button
+                        title="Hanova"
+                        class=format!("btn-ripple mr-2 text-xs {} \
+                                       rounded hover:scale-125 \
+                                       transition-transform duration-150 \
+                                       font-medium", link_class)
+                        on:click=move |_| {
+                            edit_id.set(Some(m_edit.id));
+                            f_carte.set(m_edit.card_number.clone());
+                            f_nom.set(m_edit.full_name.clone());
+                            f_adresse.set(m_edit.address.clone().unwrap_or_default());
+                            f_telephone.set(m_edit.phone.clone().unwrap_or_default());
+                            f_travail.set(m_edit.job.clone().unwrap_or_default());
+                            f_genre.set(m_edit.gender);
+                            f_naissance.set(m_edit.birth_date.clone().unwrap_or_default());
+                            f_statut.set(m_edit.status);
+                            modal_ouvert.set(true);
+                        }
+                    >
+                        <IconPencil class="w-4 h-4" />
+                    </button>
+                    <button
+                        title="Fafana"
+                        class="btn-ripple text-xs text-red-500 \
+                               dark:text-red-400 rounded \
+                               hover:scale-125 transition-transform \
+                               duration-150 font-medium"
+                        on:click=move |_| {
+                            let nom = nom_delete.clone();
+                            leptos::task::spawn_local(async move {
+                                let ok = confirm.ask(ConfirmOptions {
+                                    title:         "Hamafa mpikambana".into(),
+                                    message:       format!("Fafana i {nom} ? Voatahiry ny rakitrany ary azo averina ao amin'ny Fikirana izy."),
+                                    confirm_label: "Fafana".into(),
+                                    danger:        true,
+                                }).await;
+                                if !ok {
+                                    return;
+                                }
+                                delete_member_row(mid, nom, pin, refresh_ctr, toasts);
+                            });
+                        }
+                    >
+                        <IconTrash class="w-4 h-4" />
+                    </button>
+                </td>
+            </tr>
+        }
+    };
+
     view! {
         {move || {
             if loading.get() {
@@ -124,7 +401,7 @@ pub fn MemberTable(
                 }.into_any();
             }
 
-            if membres.get().is_empty() {
+            if !has_members.get() {
                 return view! {
                     <div class="bg-white/60 dark:bg-gray-800/60 backdrop-blur \
                                 rounded-2xl border border-gray-100 dark:border-gray-700 \
@@ -140,7 +417,7 @@ pub fn MemberTable(
                 }.into_any();
             }
 
-            if sorted_filtered.get().is_empty() {
+            if filtered_count.get() == 0 {
                 return view! {
                     <div class="bg-white/60 dark:bg-gray-800/60 backdrop-blur \
                                 rounded-2xl border border-gray-100 dark:border-gray-700 \
@@ -166,176 +443,106 @@ pub fn MemberTable(
                                     <tr class="bg-gray-50/80 dark:bg-gray-900/50 \
                                                border-b border-gray-100 dark:border-gray-700 \
                                                text-gray-600 dark:text-gray-400 font-semibold">
-                                        {transfer_to.map(|_| view! {
-                                            <th class="pl-4 pr-2 py-3 w-10">
-                                                <input
-                                                    type="checkbox"
-                                                    class="custom-check"
-                                                    title="Tout sélectionner"
-                                                    prop:checked=move || all_page_selected.get()
-                                                    on:change=move |ev: web_sys::Event| {
-                                                        let checked = checked_from_event(ev);
-                                                        let items   = page_items.get();
-                                                        selected.update(|s| {
-                                                            if checked {
-                                                                for m in &items {
-                                                                    if !s.contains(&m.id) {
-                                                                        s.push(m.id);
-                                                                    }
+                                        <th class="pl-4 pr-2 py-3 w-10">
+                                            <input
+                                                type="checkbox"
+                                                class="custom-check"
+                                                title="Tout sélectionner"
+                                                prop:checked=move || all_page_selected.get()
+                                                on:change=move |ev: web_sys::Event| {
+                                                    let checked = checked_from_event(ev);
+                                                    let items   = page_items.get();
+                                                    selected.update(|s| {
+                                                        if checked {
+                                                            for m in &items {
+                                                                if !s.contains(&m.id) {
+                                                                    s.push(m.id);
                                                                 }
-                                                            } else {
-                                                                let ids: Vec<i64> = items.iter().map(|m| m.id).collect();
-                                                                s.retain(|id| !ids.contains(id));
                                                             }
-                                                        });
-                                                    }
-                                                />
-                                            </th>
-                                        })}
-                                        <Th label="N° Karatra" col=SortCol::Carte     sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
-                                        <Th label="Anarana sy fanampiny"    col=SortCol::Nom       sort_col=sort_col sort_dir=sort_dir />
-                                        <Th label="Adiresy"         col=SortCol::Adresse   sort_col=sort_col sort_dir=sort_dir extra_class="hidden md:table-cell" />
-                                        <Th label="Finday"          col=SortCol::Telephone sort_col=sort_col sort_dir=sort_dir extra_class="hidden lg:table-cell" />
-                                        <Th label="Asa"             col=SortCol::Travail   sort_col=sort_col sort_dir=sort_dir extra_class="hidden md:table-cell" />
-                                        <Th label="Lahy/Vavy"       col=SortCol::Genre     sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
-                                        <th class="px-3 py-3 text-right pr-4">"Hetsika"</th>
+                                                        } else {
+                                                            let ids: Vec<i64> = items.iter().map(|m| m.id).collect();
+                                                            s.retain(|id| !ids.contains(id));
+                                                        }
+                                                    });
+                                                }
+                                            />
+                                        </th>
+                                        <Th label="table.card" col=SortCol::Carte     sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
+                                        <Th label="table.name"      col=SortCol::Nom       sort_col=sort_col sort_dir=sort_dir />
+                                        <Th label="table.address"   col=SortCol::Adresse   sort_col=sort_col sort_dir=sort_dir extra_class="hidden md:table-cell" />
+                                        <Th label="table.phone"     col=SortCol::Telephone sort_col=sort_col sort_dir=sort_dir extra_class="hidden lg:table-cell" />
+                                        <Th label="table.job"       col=SortCol::Travail   sort_col=sort_col sort_dir=sort_dir extra_class="hidden md:table-cell" />
+                                        <Th label="table.gender"    col=SortCol::Genre     sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
+                                        {move || if year_columns.get() {
+                                            view! {
+                                                <Th label="table.this_year" text=(current_year - 1).to_string() col=SortCol::AnneePrecedente sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
+                                                <Th label="table.this_year" text=current_year.to_string()       col=SortCol::AnneeCourante   sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
+                                            }.into_any()
+                                        } else {
+                                            view! {
+                                                <Th label="table.this_year" col=SortCol::AnneeCourante sort_col=sort_col sort_dir=sort_dir extra_class="hidden sm:table-cell" />
+                                            }.into_any()
+                                        }}
+                                        <th class="px-3 py-3 text-right pr-4">{move || tr("table.actions")}</th>
                                     </tr>
                                 </thead>
                                 <tbody>
-                                    <For
-                                        each=move || page_items.get()
-                                        key=|m| m.id
-                                        children=move |m: MemberWithTotal| {
-                                            let m_edit = m.clone();
-                                            let mid    = m.id;
-                                            let genre_label = if m.gender == "M" { "♂ Lahy" } else { "♀ Vavy" };
-
-                                            view! {
-                                                <tr class=move || {
-                                                    let sliding = transferring_ids.get().contains(&mid);
-                                                    format!(
-                                                        "tr-hover border-b border-gray-50 \
-                                                         dark:border-gray-700/50 \
-                                                         {} transition-colors duration-150{}",
-                                                        row_hover,
-                                                        if sliding { " row-sliding-out" } else { "" }
-                                                    )
-                                                }>
-                                                    {transfer_to.map(|_| view! {
-                                                        <td class="pl-4 pr-2 py-2.5">
-                                                            <input
-                                                                type="checkbox"
-                                                                class="custom-check"
-                                                                prop:checked=move || selected.get().contains(&mid)
-                                                                on:change=move |ev: web_sys::Event| {
-                                                                    let checked = checked_from_event(ev);
-                                                                    selected.update(|s| {
-                                                                        if checked {
-                                                                            if !s.contains(&mid) { s.push(mid); }
-                                                                        } else {
-                                                                            s.retain(|&id| id != mid);
-                                                                        }
-                                                                    });
-                                                                }
-                                                            />
+                                    {move || match sections.get() {
+                                        None => view! {
+                                            <For
+                                                each=move || page_items.get()
+                                                key=|m| m.id
+                                                children=row_view
+                                            />
+                                        }.into_any(),
+                                        Some(list) => list
+                                            .into_iter()
+                                            .map(|section| {
+                                                let key   = section.address.clone().unwrap_or_default();
+                                                let label = section.address.unwrap_or_else(|| "Tsy misy adiresy".into());
+                                                let count = section.rows.len();
+                                                let rows  = section.rows;
+                                                let open  = Memo::new({
+                                                    let key = key.clone();
+                                                    move |_| !collapsed.with(|c| c.contains(&key))
+                                                });
+                                                view! {
+                                                    <tr class="bg-gray-50/80 dark:bg-gray-900/40 \
+                                                               border-b border-gray-100 dark:border-gray-700">
+                                                        <td colspan=move || if year_columns.get() { "10" } else { "9" } class="px-4 py-2">
+                                                            <button
+                                                                type="button"
+                                                                aria-expanded=move || open.get().to_string()
+                                                                on:click=move |_| collapsed.update(|c| {
+                                                                    if !c.remove(&key) {
+                                                                        c.insert(key.clone());
+                                                                    }
+                                                                })
+                                                                class="w-full flex items-center gap-2 text-left \
+                                                                       text-xs font-semibold \
+                                                                       text-gray-700 dark:text-gray-200"
+                                                            >
+                                                                <span class=move || format!(
+                                                                    "transition-transform duration-150 {}",
+                                                                    if open.get() { "rotate-90" } else { "" }
+                                                                )>
+                                                                    <IconChevronRight class="w-3.5 h-3.5" />
+                                                                </span>
+                                                                <span class="capitalize">{label}</span>
+                                                                <span class="font-normal text-gray-400 dark:text-gray-500">
+                                                                    {format!("{count} mpikambana")}
+                                                                </span>
+                                                            </button>
                                                         </td>
+                                                    </tr>
+                                                    {move || open.get().then(|| {
+                                                        rows.clone().into_iter().map(row_view).collect_view()
                                                     })}
-                                                    <td class="hidden sm:table-cell px-3 py-2.5 \
-                                                               font-mono text-xs \
-                                                               text-gray-500 dark:text-gray-400 \
-                                                               whitespace-nowrap">
-                                                        {m.card_number.clone()}
-                                                    </td>
-                                                    <td class="px-3 py-2.5 font-semibold \
-                                                               text-gray-800 dark:text-white \
-                                                               whitespace-nowrap">
-                                                        {m.full_name.clone()}
-                                                    </td>
-                                                    <td class="hidden md:table-cell px-3 py-2.5 \
-                                                               text-gray-600 dark:text-gray-300 \
-                                                               max-w-[140px] truncate">
-                                                        {m.address.clone().unwrap_or_else(|| "—".into())}
-                                                    </td>
-                                                    <td class="hidden lg:table-cell px-3 py-2.5 \
-                                                               text-gray-600 dark:text-gray-300 \
-                                                               whitespace-nowrap">
-                                                        {m.phone.clone().unwrap_or_else(|| "—".into())}
-                                                    </td>
-                                                    <td class="hidden md:table-cell px-3 py-2.5 \
-                                                               text-gray-600 dark:text-gray-300 \
-                                                               max-w-[120px] truncate">
-                                                        {m.job.clone().unwrap_or_else(|| "—".into())}
-                                                    </td>
-                                                    <td class="hidden sm:table-cell px-3 py-2.5 \
-                                                               text-gray-600 dark:text-gray-300 \
-                                                               whitespace-nowrap">
-                                                        {genre_label}
-                                                    </td>
-                                                    <td class="px-3 py-2.5 pr-4 text-right whitespace-nowrap">
-                                                        <button
-                                                            title="Rakitra"
-                                                            class="btn-ripple mr-2 text-xs text-amber-500 \
-                                                                   dark:text-amber-400 rounded \
-                                                                   hover:scale-125 transition-transform \
-                                                                   duration-150 font-medium"
-                                                            on:click=move |_| {
-                                                                contrib_membre_id.set(mid);
-                                                                contrib_membre_nom.set(m.full_name.clone());
-                                                                contrib_open.set(true);
-                                                            }
-                                                        >
-                                                            <IconCoins class="w-4 h-4" />
-                                                        </button>
-                                                        <button
-                                                            title="Hanova"
-                                                            class=format!("btn-ripple mr-2 text-xs {} \
-                                                                           rounded hover:scale-125 \
-                                                                           transition-transform duration-150 \
-                                                                           font-medium", link_class)
-                                                            on:click=move |_| {
-                                                                edit_id.set(Some(m_edit.id));
-                                                                f_carte.set(m_edit.card_number.clone());
-                                                                f_nom.set(m_edit.full_name.clone());
-                                                                f_adresse.set(m_edit.address.clone().unwrap_or_default());
-                                                                f_telephone.set(m_edit.phone.clone().unwrap_or_default());
-                                                                f_travail.set(m_edit.job.clone().unwrap_or_default());
-                                                                f_genre.set(m_edit.gender.clone());
-                                                                modal_ouvert.set(true);
-                                                            }
-                                                        >
-                                                            <IconPencil class="w-4 h-4" />
-                                                        </button>
-                                                        <button
-                                                            title="Fafana"
-                                                            class="btn-ripple text-xs text-red-500 \
-                                                                   dark:text-red-400 rounded \
-                                                                   hover:scale-125 transition-transform \
-                                                                   duration-150 font-medium"
-                                                            on:click=move |_| {
-                                                                let ok = web_sys::window()
-                                                                    .and_then(|w| {
-                                                                        w.confirm_with_message(
-                                                                            "Fafana ity mpikambana ity ? Tsy azo averina io.",
-                                                                        ).ok()
-                                                                    })
-                                                                    .unwrap_or(false);
-                                                                if ok {
-                                                                    leptos::task::spawn_local(async move {
-                                                                        match db_service::delete_member(mid).await {
-                                                                            Ok(_)  => refresh_ctr.update(|n| *n += 1),
-                                                                            Err(e) => notif_error.set(Some(e)),
-                                                                        }
-                                                                    });
-                                                                }
-                                                            }
-                                                        >
-                                                            <IconTrash class="w-4 h-4" />
-                                                        </button>
-                                                    </td>
-                                                </tr>
-                                            }
-                                        }
-                                    />
+                                                }
+                                            })
+                                            .collect_view()
+                                            .into_any(),
+                                    }}
                                 </tbody>
                             </table>
                         </div>
@@ -346,7 +553,7 @@ pub fn MemberTable(
                         <div class="flex items-center justify-between flex-wrap gap-2 px-1">
                             <span class="text-xs text-gray-500 dark:text-gray-400">
                                 {move || {
-                                    let total = sorted_filtered.get().len();
+                                    let total = filtered_count.get();
                                     let p     = page.get();
                                     let from  = (p * PAGE_SIZE + 1).min(total);
                                     let to    = ((p + 1) * PAGE_SIZE).min(total);
@@ -366,7 +573,7 @@ pub fn MemberTable(
                                 >
                                     <span class="flex items-center gap-1">
                                         <IconChevronLeft class="w-3.5 h-3.5" />
-                                        "Teo aloha"
+                                        {move || tr("action.previous")}
                                     </span>
                                 </button>
                                 <span class="px-3 py-1.5 text-xs font-medium \
@@ -384,7 +591,7 @@ pub fn MemberTable(
                                            hover:bg-gray-50 dark:hover:bg-gray-700 transition"
                                 >
                                     <span class="flex items-center gap-1">
-                                        "Manaraka"
+                                        {move || tr("action.next")}
                                         <IconChevronRight class="w-3.5 h-3.5" />
                                     </span>
                                 </button>
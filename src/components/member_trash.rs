@@ -0,0 +1,137 @@
+/// Corbeille des membres — page Paramètres.
+///
+/// Un membre supprimé disparaît des listes mais garde ses cotisations, qui comptent
+/// toujours dans les totaux annuels. Il peut être restauré, ou effacé définitivement
+/// avec ses cotisations (PIN requis).
+use leptos::prelude::*;
+
+use crate::{
+    app::{DataVersionCtx, PinCtx, ToastProviderCtx},
+    components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
+        icons::{IconRefresh, IconTrash},
+    },
+    models::member::DeletedMember,
+    services::db_service,
+};
+
+#[component]
+pub fn MemberTrash() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let confirm = use_confirm();
+
+    let list: RwSignal<Vec<DeletedMember>> = RwSignal::new(vec![]);
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        leptos::task::spawn_local(async move {
+            match db_service::get_deleted_members().await {
+                Ok(items) => list.set(items),
+                Err(e) => toasts.error(e),
+            }
+        });
+    });
+
+    let restore = move |m: DeletedMember| {
+        leptos::task::spawn_local(async move {
+            match db_service::restore_member(m.id).await {
+                Ok(_) => {
+                    toasts.success(format!("Naverina i {}", m.full_name));
+                    data_version.bump();
+                }
+                Err(e) => toasts.error(e),
+            }
+        });
+    };
+
+    let purge = move |m: DeletedMember| {
+        leptos::task::spawn_local(async move {
+            let ok = confirm.ask(ConfirmOptions {
+                title:         "Hamafa tanteraka".into(),
+                message:       format!(
+                    "Fafana tanteraka i {} sy ny rakitra {} ? Tsy azo averina io.",
+                    m.full_name, m.contribution_count,
+                ),
+                confirm_label: "Fafana tanteraka".into(),
+                danger:        true,
+            }).await;
+            if !ok {
+                return;
+            }
+            pin.guard(move |token| {
+                leptos::task::spawn_local(async move {
+                    match db_service::purge_member(m.id, token.as_deref()).await {
+                        Ok(_) => {
+                            toasts.info(format!("Voafafa tanteraka i {}", m.full_name));
+                            data_version.bump();
+                        }
+                        Err(e) => toasts.error(e),
+                    }
+                });
+            });
+        });
+    };
+
+    move || (!list.get().is_empty()).then(|| view! {
+        <section class="max-w-xl p-4 sm:p-5 \
+                        bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+            <h2 class="text-sm font-semibold text-gray-800 dark:text-white flex items-center gap-2">
+                <IconTrash class="w-4 h-4 text-gray-500 dark:text-gray-400" />
+                "Mpikambana voafafa"
+            </h2>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5 mb-3">
+                "Mbola isaina ao amin'ny totalin'ny taona ny rakitr'izy ireo."
+            </p>
+            <ul class="divide-y divide-gray-100 dark:divide-gray-700/60">
+                {list.get().into_iter().map(|m| {
+                    let (m_restore, m_purge) = (m.clone(), m.clone());
+                    view! {
+                        <li class="flex items-center justify-between gap-3 py-2 text-sm">
+                            <div class="min-w-0">
+                                <p class="font-medium text-gray-800 dark:text-white truncate">
+                                    {m.full_name.clone()}
+                                </p>
+                                <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                                    {format!(
+                                        "{} · {} · rakitra {} · voafafa {}",
+                                        m.card_number,
+                                        m.member_type.label(),
+                                        m.contribution_count,
+                                        m.deleted_on(),
+                                    )}
+                                </p>
+                            </div>
+                            <div class="flex items-center gap-1 shrink-0">
+                                <button
+                                    type="button"
+                                    title="Averina"
+                                    on:click=move |_| restore(m_restore.clone())
+                                    class="btn-ripple flex items-center gap-1 px-2.5 py-1 text-xs \
+                                           font-semibold rounded-lg transition-colors \
+                                           text-blue-600 dark:text-blue-400 \
+                                           hover:bg-blue-50 dark:hover:bg-blue-900/30"
+                                >
+                                    <IconRefresh class="w-3.5 h-3.5" />
+                                    "Averina"
+                                </button>
+                                <button
+                                    type="button"
+                                    title="Fafana tanteraka"
+                                    on:click=move |_| purge(m_purge.clone())
+                                    class="btn-ripple p-1.5 rounded-lg transition-colors \
+                                           text-red-500 dark:text-red-400 \
+                                           hover:bg-red-50 dark:hover:bg-red-900/30"
+                                >
+                                    <IconTrash class="w-3.5 h-3.5" />
+                                </button>
+                            </div>
+                        </li>
+                    }
+                }).collect_view()}
+            </ul>
+        </section>
+    })
+}
@@ -0,0 +1,190 @@
+/// Modal de fusion de deux fiches membre (doublons).
+///
+/// Affiche les deux fiches sélectionnées côte à côte ; l'utilisateur choisit
+/// celle à conserver. Les cotisations de l'autre lui sont réattribuées et ses
+/// champs vides (adresse, téléphone, travail) complétés, puis le doublon est
+/// effacé. Action protégée par le PIN admin.
+use leptos::prelude::*;
+
+use crate::{
+    app::{DataVersionCtx, PinCtx, ToastProviderCtx},
+    components::{
+        icons::{IconAlertTriangle, IconMerge},
+        modal_wrapper::ModalWrapper,
+    },
+    i18n::tr,
+    models::member::Member,
+    services::db_service,
+};
+
+/// Fiche chargée avec son nombre de cotisations, pour aider au choix.
+#[derive(Clone)]
+struct Candidate {
+    member:        Member,
+    contributions: usize,
+}
+
+async fn load_candidate(id: i64) -> Result<Candidate, String> {
+    let member = db_service::get_member(id).await?;
+    let contributions = db_service::get_contributions(id).await?.len();
+    Ok(Candidate { member, contributions })
+}
+
+#[component]
+pub fn MergeModal(
+    /// Signal d'ouverture du modal.
+    open:     RwSignal<bool>,
+    /// Sélection du tableau : exactement deux ids ; vidée après la fusion.
+    selected: RwSignal<Vec<i64>>,
+) -> impl IntoView {
+    let toasts       = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let pin          = use_context::<PinCtx>().expect("PinCtx manquant");
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+
+    let candidates: RwSignal<Option<Vec<Candidate>>> = RwSignal::new(None);
+    let keep_id:    RwSignal<Option<i64>>            = RwSignal::new(None);
+    let loading:    RwSignal<bool>                   = RwSignal::new(false);
+    let erreur:     RwSignal<Option<String>>         = RwSignal::new(None);
+
+    let ids = selected.get_untracked();
+    leptos::task::spawn_local(async move {
+        let mut loaded = Vec::with_capacity(ids.len());
+        for id in ids {
+            match load_candidate(id).await {
+                Ok(c) => loaded.push(c),
+                Err(e) => {
+                    erreur.set(Some(e));
+                    return;
+                }
+            }
+        }
+        // Par défaut : la fiche la plus ancienne
+        let oldest = loaded.iter().min_by(|a, b| a.member.created_at.cmp(&b.member.created_at));
+        keep_id.set(oldest.map(|c| c.member.id));
+        candidates.set(Some(loaded));
+    });
+
+    let confirmer = move |_| {
+        let Some(keep) = keep_id.get_untracked() else { return };
+        let Some(remove) = selected.get_untracked().into_iter().find(|&id| id != keep) else { return };
+        erreur.set(None);
+        pin.guard(move |token| {
+            loading.set(true);
+            leptos::task::spawn_local(async move {
+                match db_service::merge_members(keep, remove, token.as_deref()).await {
+                    Ok(m) => {
+                        selected.set(vec![]);
+                        open.set(false);
+                        data_version.bump();
+                        toasts.success(format!("Natambatra ho iray ny taratasin'i {}", m.full_name));
+                    }
+                    Err(e) => erreur.set(Some(e)),
+                }
+                loading.set(false);
+            });
+        });
+    };
+
+    let card = move |c: Candidate| {
+        let id = c.member.id;
+        let field = |label: &'static str, value: Option<String>| view! {
+            <div class="flex justify-between gap-2">
+                <span class="text-gray-400 dark:text-gray-500">{label}</span>
+                <span class="text-right truncate">{value.unwrap_or_else(|| "—".into())}</span>
+            </div>
+        };
+        view! {
+            <label class=move || format!(
+                "block p-3 rounded-xl border cursor-pointer transition-colors text-xs \
+                 text-gray-700 dark:text-gray-300 {}",
+                if keep_id.get() == Some(id) {
+                    "border-amber-400 bg-amber-50 dark:bg-amber-900/20"
+                } else {
+                    "border-gray-200 dark:border-gray-600 hover:bg-gray-50 dark:hover:bg-gray-700/40"
+                }
+            )>
+                <div class="flex items-center gap-2 mb-2">
+                    <input
+                        type="radio"
+                        name="merge-keep"
+                        prop:checked=move || keep_id.get() == Some(id)
+                        on:change=move |_| keep_id.set(Some(id))
+                    />
+                    <span class="text-sm font-semibold text-gray-800 dark:text-white truncate">
+                        {c.member.full_name.clone()}
+                    </span>
+                </div>
+                <div class="space-y-1">
+                    {field("Karatra", Some(c.member.card_number.clone()))}
+                    {field("Adiresy", c.member.address.clone())}
+                    {field("Finday", c.member.phone.clone())}
+                    {field("Asa", c.member.job.clone())}
+                    {field("Fandoavana", Some(c.contributions.to_string()))}
+                </div>
+            </label>
+        }
+    };
+
+    view! {
+        <ModalWrapper
+            card_class="max-w-lg overflow-hidden"
+            on_close=Callback::new(move |()| if !loading.get_untracked() { open.set(false) })
+        >
+            <div class="px-6 pt-5 pb-3 border-b border-gray-100 dark:border-gray-700">
+                <h2 class="text-base font-bold text-gray-800 dark:text-white flex items-center gap-2">
+                    <IconMerge class="w-5 h-5 text-amber-500" />
+                    "Hampiraisina ny taratasy roa"
+                </h2>
+                <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5">
+                    "Safidio izay tazonina : hafindra ao aminy ny fandoavana rehetra, \
+                     ary hofafana tanteraka ilay iray."
+                </p>
+            </div>
+            <div class="px-6 py-5 space-y-4">
+                {move || erreur.get().map(|e| view! {
+                    <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                                bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                                border border-red-200 dark:border-red-700/50">
+                        <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                        <span>{e}</span>
+                    </div>
+                })}
+                {move || match candidates.get() {
+                    None => view! {
+                        <p class="text-sm text-center text-gray-400 py-6">"…"</p>
+                    }.into_any(),
+                    Some(list) => view! {
+                        <div class="grid grid-cols-1 sm:grid-cols-2 gap-3">
+                            {list.into_iter().map(card).collect_view()}
+                        </div>
+                    }.into_any(),
+                }}
+                <div class="flex gap-3">
+                    <button
+                        type="button"
+                        disabled=move || loading.get()
+                        on:click=move |_| open.set(false)
+                        class="btn-ripple flex-1 px-4 py-2.5 text-sm font-medium \
+                               text-gray-600 dark:text-gray-300 \
+                               bg-gray-100 dark:bg-gray-700 \
+                               hover:bg-gray-200 dark:hover:bg-gray-600 \
+                               disabled:opacity-50 rounded-xl transition-colors"
+                    >
+                        {move || tr("action.cancel")}
+                    </button>
+                    <button
+                        type="button"
+                        disabled=move || loading.get() || keep_id.get().is_none()
+                        on:click=confirmer
+                        class="btn-ripple flex-1 px-4 py-2.5 text-sm font-semibold \
+                               text-white bg-amber-500 hover:bg-amber-600 \
+                               disabled:opacity-60 disabled:cursor-wait \
+                               rounded-xl transition-colors shadow-sm"
+                    >
+                        {move || if loading.get() { "Mampiray…" } else { "Hampiraisina" }}
+                    </button>
+                </div>
+            </div>
+        </ModalWrapper>
+    }
+}
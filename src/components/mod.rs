@@ -1,14 +1,33 @@
+pub mod bar_chart;
+pub mod confirm_dialog;
 pub mod contribution_edit_modal;
 pub mod contribution_modal;
+pub mod db_health_panel;
+pub mod demo_seed;
+pub mod display_menu;
 pub mod icons;
+pub mod language_switcher;
+pub mod maintenance_panel;
 pub mod member_form;
+pub mod member_notes;
 pub mod member_page;
 pub mod member_table;
+pub mod member_trash;
+pub mod merge_modal;
 pub mod modal_wrapper;
+pub mod monthly_totals;
 pub mod navbar;
 pub mod phone_input;
+pub mod pin_prompt_modal;
+pub mod pin_settings;
 pub mod sky_canvas;
+pub mod sparkline;
+pub mod tasks_menu;
 pub mod theme_switcher;
+pub mod toast_stack;
 pub mod titlebar;
 pub mod transfer_modal;
+pub mod year_breakdown;
+pub mod year_comparison;
+pub mod year_member_totals;
 pub mod year_toast;
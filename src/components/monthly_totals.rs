@@ -0,0 +1,76 @@
+/// Totaux mois par mois d'une année — Archives, sous la bannière de l'année.
+///
+/// Regroupement par date de paiement : un paiement de janvier rattaché à l'année
+/// précédente compte dans le janvier de l'année suivante.
+use leptos::prelude::*;
+
+use crate::{
+    app::DataVersionCtx,
+    components::icons::IconAlertTriangle,
+    models::year_summary::MonthlyTotal,
+    services::db_service,
+    utils::{format_ariary_str, MoneyFormat},
+};
+
+#[component]
+pub fn MonthlyTotalsTable(year: i32) -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let months: RwSignal<Vec<MonthlyTotal>> = RwSignal::new(vec![]);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+
+    // Rechargé après une modification de cotisation
+    Effect::new(move |_| {
+        data_version.version.track();
+        leptos::task::spawn_local(async move {
+            match db_service::get_monthly_totals(year).await {
+                Ok(liste) => {
+                    erreur.set(None);
+                    months.set(liste);
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+        });
+    });
+
+    // Année sans aucun paiement : rien à afficher
+    let has_data = Memo::new(move |_| months.get().iter().any(|m| m.count > 0));
+
+    view! {
+        {move || erreur.get().map(|e| view! {
+            <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                <span>{e}</span>
+            </div>
+        })}
+        {move || has_data.get().then(|| view! {
+            <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 \
+                        shadow-sm px-4 py-3">
+                <p class="text-xs font-semibold uppercase tracking-wide \
+                          text-gray-500 dark:text-gray-400 mb-2">
+                    {format!("Isam-bolana — {year}")}
+                </p>
+                <div class="grid grid-cols-3 sm:grid-cols-4 lg:grid-cols-6 gap-2">
+                    {months.get().into_iter().map(|m| {
+                        let empty = m.count == 0;
+                        let cell_cls = if empty {
+                            "text-gray-400 dark:text-gray-500"
+                        } else {
+                            "text-gray-700 dark:text-gray-200"
+                        };
+                        view! {
+                            <div class=format!("rounded-xl px-2.5 py-2 \
+                                                bg-gray-50/80 dark:bg-gray-700/40 {cell_cls}")
+                                 title=format!("{} rakitra", m.count)>
+                                <p class="text-[11px] font-medium">{m.label()}</p>
+                                <p class="text-xs font-mono font-semibold truncate">
+                                    {format_ariary_str(&m.total, MoneyFormat::default())}
+                                </p>
+                            </div>
+                        }
+                    }).collect_view()}
+                </div>
+            </div>
+        })}
+    }
+}
@@ -1,28 +1,42 @@
-/// Barre de navigation avec 4 onglets et indicateur glissant animé.
+/// Barre de navigation avec 6 onglets et indicateur glissant animé.
 
 const LOGO_SRC: &str = include_str!("../../assets/logo_data_uri.txt");
 
 use leptos::prelude::*;
 use leptos_router::{
     components::A,
-    hooks::use_location,
+    hooks::{use_location, use_navigate},
 };
 
 use crate::components::icons::{
-    IconArchive, IconBookOpen, IconCross, IconHome, IconRefresh,
+    IconArchive, IconBarChart, IconBookOpen, IconCoins, IconCross, IconDatabase, IconFileText,
+    IconHome, IconRefresh, IconSearch, IconSettings,
 };
+use crate::app::{MemberDataCtx, SettingsCtx};
+use crate::components::display_menu::DisplayMenu;
+use crate::components::language_switcher::LanguageSwitcher;
+use crate::components::maintenance_panel::MaintenancePanel;
+use crate::components::tasks_menu::TasksMenu;
 use crate::components::theme_switcher::ThemeSwitcher;
+use crate::i18n::tr;
+use crate::models::member::MemberType;
+use crate::pages::recherche::search_href;
 
 struct Tab {
-    label: &'static str,
-    path:  &'static str,
+    /// Clé de traduction du libellé (`i18n::tr`)
+    label:   &'static str,
+    path:    &'static str,
+    /// Onglet d'une liste de membres : badge d'effectif + préchargement au survol
+    members: Option<MemberType>,
 }
 
 const TABS: &[Tab] = &[
-    Tab { label: "Fandraisana",  path: "/"            },
-    Tab { label: "Mpandray",     path: "/communiants" },
-    Tab { label: "Tsy Mpandray",   path: "/cathekomens" },
-    Tab { label: "Tahiry",       path: "/archives"    },
+    Tab { label: "nav.home",        path: "/",             members: None },
+    Tab { label: "nav.communiants", path: "/communiants",  members: Some(MemberType::Communiant) },
+    Tab { label: "nav.cathekomens", path: "/cathekomens",  members: Some(MemberType::Cathekomen) },
+    Tab { label: "nav.archives",    path: "/archives",     members: None },
+    Tab { label: "nav.stats",       path: "/statistiques", members: None },
+    Tab { label: "nav.settings",    path: "/parametres",   members: None },
 ];
 
 fn tab_icon(i: usize) -> impl IntoView {
@@ -31,26 +45,32 @@ fn tab_icon(i: usize) -> impl IntoView {
         1 => view! { <IconCross    class="w-4 h-4" /> }.into_any(),
         2 => view! { <IconBookOpen class="w-4 h-4" /> }.into_any(),
         3 => view! { <IconArchive  class="w-4 h-4" /> }.into_any(),
+        4 => view! { <IconBarChart class="w-4 h-4" /> }.into_any(),
+        5 => view! { <IconSettings class="w-4 h-4" /> }.into_any(),
         _ => view! { <span /> }.into_any(),
     }
 }
 
-fn active_index(pathname: &str) -> usize {
-    TABS.iter()
-        .position(|t| {
-            if t.path == "/" {
-                pathname == "/"
-            } else {
-                pathname.starts_with(t.path)
-            }
-        })
-        .unwrap_or(0)
+/// `None` hors des onglets (page de recherche) : aucun onglet en surbrillance.
+fn active_index(pathname: &str) -> Option<usize> {
+    TABS.iter().position(|t| {
+        if t.path == "/" {
+            pathname == "/"
+        } else {
+            pathname.starts_with(t.path)
+        }
+    })
 }
 
 #[component]
 pub fn Navbar() -> impl IntoView {
     let location = use_location();
     let idx = Memo::new(move |_| active_index(&location.pathname.get()));
+    let maintenance_open = RwSignal::new(false);
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant");
+    let member_data = use_context::<MemberDataCtx>().expect("MemberDataCtx manquant");
+    let navigate = use_navigate();
+    let recherche = RwSignal::new(String::new());
 
     view! {
         <header class="sticky top-0 z-50 \
@@ -66,7 +86,7 @@ pub fn Navbar() -> impl IntoView {
                         <img src=LOGO_SRC class="w-6 h-6 sm:w-7 sm:h-7 object-cover rounded" alt="Logo" />
                         <div class="leading-tight hidden xs:block sm:block">
                             <p class="font-bold text-gray-800 dark:text-white text-xs sm:text-sm md:text-base">
-                                "FJKM Ambalavao Isotry"
+                                {move || settings.church_name()}
                             </p>
                         </div>
                     </div>
@@ -79,16 +99,22 @@ pub fn Navbar() -> impl IntoView {
                                 .iter()
                                 .enumerate()
                                 .map(|(i, tab)| {
-                                    let path  = tab.path;
-                                    let label = tab.label;
+                                    let path    = tab.path;
+                                    let label   = tab.label;
+                                    let members = tab.members;
                                     view! {
                                         <A
                                             href=path
+                                            on:mouseenter=move |_| {
+                                                if let Some(t) = members {
+                                                    member_data.prefetch(t);
+                                                }
+                                            }
                                             attr:class=move || {
                                                 let base = "nav-tab flex items-center gap-1.5 px-2 sm:px-4 \
                                                             py-4 sm:py-5 text-xs sm:text-sm font-medium \
                                                             whitespace-nowrap shrink-0";
-                                                if idx.get() == i {
+                                                if idx.get() == Some(i) {
                                                     format!("{base} text-blue-600 dark:text-blue-400")
                                                 } else {
                                                     format!("{base} text-gray-500 dark:text-gray-400 \
@@ -97,7 +123,17 @@ pub fn Navbar() -> impl IntoView {
                                             }
                                         >
                                             {tab_icon(i)}
-                                            <span class="hidden sm:inline">{label}</span>
+                                            <span class="hidden sm:inline">{move || tr(label)}</span>
+                                            {members.map(|t| view! {
+                                                {move || member_data.counts.get().map(|c| view! {
+                                                    <span class="px-1.5 py-0.5 rounded-full text-[0.65rem] \
+                                                                 font-semibold tabular-nums leading-none \
+                                                                 bg-gray-100 dark:bg-gray-800 \
+                                                                 text-gray-600 dark:text-gray-300">
+                                                        {c.of(t)}
+                                                    </span>
+                                                })}
+                                            })}
                                         </A>
                                     }
                                 })
@@ -106,17 +142,83 @@ pub fn Navbar() -> impl IntoView {
                             // ── Indicateur glissant ────────────────────────────
                             <div
                                 class="nav-indicator"
-                                style=move || {
-                                    format!("transform: translateX({}%);", idx.get() * 100)
+                                style=move || match idx.get() {
+                                    Some(i) => format!(
+                                        "width: {}%; transform: translateX({}%);",
+                                        100.0 / TABS.len() as f64,
+                                        i * 100,
+                                    ),
+                                    None => "opacity: 0;".to_string(),
                                 }
                             />
                         </div>
                     </nav>
 
-                    // ── Droite : actualiser + thème ───────────────────────────
+                    // ── Droite : recherche + tâches + maintenance + actualiser + affichage + langue + thème ─
                     <div class="shrink-0 flex items-center gap-1">
+                        <form
+                            role="search"
+                            class="relative"
+                            on:submit=move |ev| {
+                                ev.prevent_default();
+                                let q = recherche.get_untracked();
+                                if !q.trim().is_empty() {
+                                    navigate(&search_href(&q), Default::default());
+                                }
+                            }
+                        >
+                            <span class="absolute left-2 top-1/2 -translate-y-1/2 text-gray-400 \
+                                         pointer-events-none">
+                                <IconSearch class="w-3.5 h-3.5" />
+                            </span>
+                            <input
+                                type="search"
+                                placeholder=move || tr("nav.search")
+                                aria-label=move || tr("nav.search_label")
+                                class="w-24 sm:w-36 focus:w-40 sm:focus:w-52 pl-7 pr-2 py-1.5 text-xs \
+                                       bg-gray-100/80 dark:bg-gray-800/80 \
+                                       border border-transparent focus:border-blue-300 \
+                                       dark:focus:border-blue-600 rounded-lg \
+                                       text-gray-800 dark:text-white \
+                                       placeholder-gray-400 dark:placeholder-gray-500 \
+                                       focus:outline-none transition-all"
+                                prop:value=move || recherche.get()
+                                on:input=move |ev| recherche.set(event_target_value(&ev))
+                            />
+                        </form>
+                        <TasksMenu />
+                        <button
+                            title=move || tr("nav.maintenance")
+                            class="p-2 rounded-lg text-gray-500 dark:text-gray-400 \
+                                   hover:text-blue-600 dark:hover:text-blue-400 \
+                                   hover:bg-gray-100 dark:hover:bg-gray-800 \
+                                   transition-colors"
+                            on:click=move |_| maintenance_open.set(true)
+                        >
+                            <IconDatabase class="w-4 h-4" />
+                        </button>
+                        <A
+                            href="/saisie"
+                            attr:title=move || tr("nav.batch")
+                            attr:class="p-2 rounded-lg text-gray-500 dark:text-gray-400 \
+                                        hover:text-blue-600 dark:hover:text-blue-400 \
+                                        hover:bg-gray-100 dark:hover:bg-gray-800 \
+                                        transition-colors"
+                        >
+                            <IconCoins class="w-4 h-4" />
+                        </A>
+                        <A
+                            href="/journal"
+                            attr:title=move || tr("nav.journal")
+                            attr:class="p-2 rounded-lg text-gray-500 dark:text-gray-400 \
+                                        hover:text-blue-600 dark:hover:text-blue-400 \
+                                        hover:bg-gray-100 dark:hover:bg-gray-800 \
+                                        transition-colors"
+                        >
+                            <IconFileText class="w-4 h-4" />
+                        </A>
                         <button
-                            title="Mamerina"
+                            title=move || tr("nav.refresh")
                             class="p-2 rounded-lg text-gray-500 dark:text-gray-400 \
                                    hover:text-blue-600 dark:hover:text-blue-400 \
                                    hover:bg-gray-100 dark:hover:bg-gray-800 \
@@ -127,11 +229,14 @@ pub fn Navbar() -> impl IntoView {
                         >
                             <IconRefresh class="w-4 h-4" />
                         </button>
+                        <DisplayMenu />
+                        <LanguageSwitcher />
                         <ThemeSwitcher />
                     </div>
 
                 </div>
             </div>
+            {move || maintenance_open.get().then(|| view! { <MaintenancePanel open=maintenance_open /> })}
         </header>
     }
 }
@@ -0,0 +1,114 @@
+/// Demande du code PIN admin avant une action destructrice.
+///
+/// Ouvert par `PinCtx::guard` ; le jeton renvoyé par `verify_pin` est transmis
+/// à l'action en attente. Les refus (PIN incorrect, verrouillage) restent affichés ici.
+use leptos::prelude::*;
+
+use crate::{
+    app::PinCtx,
+    components::{
+        icons::{IconAlertTriangle, IconLock},
+        modal_wrapper::ModalWrapper,
+    },
+    i18n::tr,
+    services::db_service,
+};
+
+#[component]
+pub fn PinPromptModal() -> impl IntoView {
+    let ctx = use_context::<PinCtx>().expect("PinCtx manquant");
+
+    let pin:      RwSignal<String>         = RwSignal::new(String::new());
+    let checking: RwSignal<bool>           = RwSignal::new(false);
+    let erreur:   RwSignal<Option<String>> = RwSignal::new(None);
+
+    let soumettre = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let value = pin.get();
+        if value.trim().is_empty() {
+            return;
+        }
+        checking.set(true);
+        erreur.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::verify_pin(value.trim()).await {
+                Ok(token) => ctx.confirm(token),
+                Err(e) => {
+                    erreur.set(Some(e));
+                    pin.set(String::new());
+                }
+            }
+            checking.set(false);
+        });
+    };
+
+    view! {
+        <ModalWrapper
+            on_close=Callback::new(move |()| ctx.cancel())
+            card_class="max-w-xs overflow-hidden"
+        >
+            <form on:submit=soumettre class="px-6 py-5 space-y-4">
+                <div class="text-center">
+                    <div class="flex justify-center mb-2">
+                        <IconLock class="w-8 h-8 text-amber-500" />
+                    </div>
+                    <h2 class="text-base font-bold text-gray-800 dark:text-white">
+                        "Kaody PIN admin"
+                    </h2>
+                    <p class="text-xs text-gray-500 dark:text-gray-400 mt-1">
+                        "Ilaina ny PIN amin'ity hetsika tsy azo averina ity."
+                    </p>
+                </div>
+
+                <input
+                    type="password"
+                    inputmode="numeric"
+                    autocomplete="off"
+                    maxlength="20"
+                    placeholder="••••"
+                    autofocus
+                    class="w-full px-3 py-2 text-center text-lg tracking-widest rounded-xl \
+                           bg-gray-50 dark:bg-gray-700/60 \
+                           border border-gray-200 dark:border-gray-600 \
+                           text-gray-800 dark:text-white \
+                           focus:outline-none focus:ring-2 focus:ring-amber-400"
+                    prop:value=move || pin.get()
+                    on:input=move |ev| pin.set(event_target_value(&ev))
+                />
+
+                {move || erreur.get().map(|e| view! {
+                    <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                                bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                                border border-red-200 dark:border-red-700/50">
+                        <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                        <span>{e}</span>
+                    </div>
+                })}
+
+                <div class="flex gap-3">
+                    <button
+                        type="button"
+                        on:click=move |_| ctx.cancel()
+                        class="btn-ripple flex-1 px-4 py-2 text-sm font-medium \
+                               text-gray-600 dark:text-gray-300 \
+                               bg-gray-100 dark:bg-gray-700 \
+                               hover:bg-gray-200 dark:hover:bg-gray-600 \
+                               rounded-xl transition-colors"
+                    >
+                        {move || tr("action.cancel")}
+                    </button>
+                    <button
+                        type="submit"
+                        disabled=move || checking.get()
+                        class="btn-ripple flex-1 px-4 py-2 text-sm font-semibold text-white \
+                               bg-amber-500 hover:bg-amber-600 \
+                               disabled:opacity-60 disabled:cursor-wait \
+                               rounded-xl transition-colors shadow-sm"
+                    >
+                        {move || if checking.get() { "Manamarina…" } else { "Hamafisina" }}
+                    </button>
+                </div>
+            </form>
+        </ModalWrapper>
+    }
+}
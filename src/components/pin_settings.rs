@@ -0,0 +1,232 @@
+/// Code PIN admin — page Paramètres.
+///
+/// Définir, changer ou supprimer le PIN qui protège les actions destructrices.
+/// Changer ou supprimer exige l'ancien PIN (vérifié côté serveur, avec le même
+/// verrouillage que `verify_pin`). Le PIN saisi ne quitte jamais ce formulaire
+/// autrement que vers la commande correspondante.
+use leptos::prelude::*;
+
+use crate::{
+    app::{PinCtx, ToastProviderCtx},
+    components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
+        icons::{IconAlertTriangle, IconLock},
+    },
+    i18n::tr,
+    services::{config_service, db_service},
+};
+
+/// Longueur autorisée, alignée sur le backend (`PIN_MIN_LEN`..=`PIN_MAX_LEN`).
+pub const PIN_MIN_LEN: usize = 4;
+pub const PIN_MAX_LEN: usize = 6;
+
+/// Message d'erreur si `pin` n'a pas le format attendu (4 à 6 chiffres).
+pub fn pin_format_error(pin: &str) -> Option<String> {
+    let ok = (PIN_MIN_LEN..=PIN_MAX_LEN).contains(&pin.len())
+        && pin.chars().all(|c| c.is_ascii_digit());
+    (!ok).then(|| format!("Isa {PIN_MIN_LEN} ka hatramin'ny {PIN_MAX_LEN} ny kaody PIN."))
+}
+
+const INPUT: &str = "w-full px-3 py-2 text-sm tracking-widest \
+                     bg-gray-50 dark:bg-gray-700/60 \
+                     border border-gray-200 dark:border-gray-600 \
+                     rounded-xl text-gray-800 dark:text-white \
+                     focus:outline-none focus:ring-2 focus:ring-amber-400 transition";
+
+#[component]
+fn PinInput(id: &'static str, label: &'static str, value: RwSignal<String>) -> impl IntoView {
+    view! {
+        <div>
+            <label
+                class="block text-xs font-semibold text-gray-600 dark:text-gray-400 mb-1"
+                for=id
+            >
+                {label}
+            </label>
+            <input
+                id=id
+                type="password"
+                inputmode="numeric"
+                autocomplete="off"
+                maxlength=PIN_MAX_LEN.to_string()
+                class=INPUT
+                prop:value=move || value.get()
+                on:input=move |ev| value.set(event_target_value(&ev))
+            />
+        </div>
+    }
+}
+
+#[component]
+pub fn PinSettings() -> impl IntoView {
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let pin_ctx = use_context::<PinCtx>().expect("PinCtx manquant");
+    let confirm = use_confirm();
+
+    let configured: RwSignal<Option<bool>>   = RwSignal::new(None);
+    let f_ancien:   RwSignal<String>         = RwSignal::new(String::new());
+    let f_nouveau:  RwSignal<String>         = RwSignal::new(String::new());
+    let f_confirm:  RwSignal<String>         = RwSignal::new(String::new());
+    let busy:       RwSignal<bool>           = RwSignal::new(false);
+    let erreur:     RwSignal<Option<String>> = RwSignal::new(None);
+
+    leptos::task::spawn_local(async move {
+        match db_service::has_pin().await {
+            Ok(has) => configured.set(Some(has)),
+            Err(e)  => erreur.set(Some(e)),
+        }
+    });
+
+    let done = move |has: bool, msg: &'static str| {
+        f_ancien.set(String::new());
+        f_nouveau.set(String::new());
+        f_confirm.set(String::new());
+        configured.set(Some(has));
+        pin_ctx.set_configured(has);
+        toasts.success(msg);
+    };
+
+    // ── Définir / changer ────────────────────────────────────────────────────
+    let soumettre = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        erreur.set(None);
+        let nouveau = f_nouveau.get();
+        if let Some(e) = pin_format_error(&nouveau) {
+            erreur.set(Some(e));
+            return;
+        }
+        if nouveau != f_confirm.get() {
+            erreur.set(Some("Tsy mitovy ny kaody PIN roa.".into()));
+            return;
+        }
+        let ancien = f_ancien.get();
+        let changement = configured.get_untracked() == Some(true);
+        busy.set(true);
+        leptos::task::spawn_local(async move {
+            let res = if changement {
+                db_service::change_pin(&ancien, &nouveau).await
+            } else {
+                config_service::set_pin(&nouveau).await
+            };
+            match res {
+                Ok(()) => done(true, if changement { "Voaova ny kaody PIN." } else { "Voapetraka ny kaody PIN." }),
+                Err(e) => erreur.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    // ── Supprimer ────────────────────────────────────────────────────────────
+    let supprimer = move |_| {
+        erreur.set(None);
+        let ancien = f_ancien.get();
+        if ancien.is_empty() {
+            erreur.set(Some("Ampidiro ny kaody PIN ankehitriny.".into()));
+            return;
+        }
+        leptos::task::spawn_local(async move {
+            let ok = confirm
+                .ask(ConfirmOptions {
+                    title:         "Hanala ny kaody PIN ?".into(),
+                    message:       "Tsy hangatahina intsony ny PIN amin'ny famafana sy ny \
+                                    famerenana."
+                        .into(),
+                    confirm_label: "Esory".into(),
+                    danger:        true,
+                })
+                .await;
+            if !ok {
+                return;
+            }
+            busy.set(true);
+            match db_service::remove_pin(&ancien).await {
+                Ok(()) => done(false, "Nesorina ny kaody PIN."),
+                Err(e) => erreur.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <section class="max-w-xl p-4 sm:p-5 \
+                        bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+            <h2 class="text-sm font-semibold text-gray-800 dark:text-white flex items-center gap-2">
+                <IconLock class="w-4 h-4 text-amber-500" />
+                "Kaody PIN admin"
+            </h2>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5 mb-3">
+                {move || match configured.get() {
+                    Some(true)  => "Angatahina amin'ny famafana, ny fanokafana taona sy ny famerenana tahiry.",
+                    Some(false) => "Tsy misy PIN : afaka mamafa na manokatra taona ny rehetra.",
+                    None        => "…",
+                }}
+            </p>
+
+            <form on:submit=soumettre class="space-y-3">
+                {move || erreur.get().map(|e| view! {
+                    <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                                bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                                border border-red-200 dark:border-red-700/50">
+                        <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                        <span>{e}</span>
+                    </div>
+                })}
+
+                <Show when=move || configured.get() == Some(true)>
+                    <PinInput id="pin-ancien" label="PIN ankehitriny" value=f_ancien />
+                </Show>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-3">
+                    <PinInput id="pin-nouveau" label="PIN vaovao" value=f_nouveau />
+                    <PinInput id="pin-confirm" label="Averina ny PIN vaovao" value=f_confirm />
+                </div>
+
+                <div class="flex justify-end gap-2">
+                    <Show when=move || configured.get() == Some(true)>
+                        <button
+                            type="button"
+                            on:click=supprimer
+                            disabled=move || busy.get()
+                            class="btn-ripple px-4 py-2 text-sm font-medium rounded-xl transition-colors \
+                                   text-red-600 dark:text-red-400 \
+                                   hover:bg-red-50 dark:hover:bg-red-900/30 \
+                                   disabled:opacity-60 disabled:cursor-wait"
+                        >
+                            "Esory ny PIN"
+                        </button>
+                    </Show>
+                    <button
+                        type="submit"
+                        disabled=move || busy.get() || configured.get().is_none()
+                        class="btn-ripple px-4 py-2 text-sm font-semibold text-white \
+                               bg-amber-500 hover:bg-amber-600 \
+                               disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors shadow-sm"
+                    >
+                        {move || if busy.get() {
+                            tr("action.saving")
+                        } else if configured.get() == Some(true) {
+                            "Ovay ny PIN".into()
+                        } else {
+                            "Apetraho ny PIN".into()
+                        }}
+                    </button>
+                </div>
+            </form>
+        </section>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_format_error() {
+        assert!(pin_format_error("1234").is_none());
+        assert!(pin_format_error("123456").is_none());
+        assert!(pin_format_error("123").is_some());
+        assert!(pin_format_error("1234567").is_some());
+        assert!(pin_format_error("12a4").is_some());
+        assert!(pin_format_error("").is_some());
+    }
+}
@@ -4,6 +4,8 @@
 ///         étoiles filantes très rares (1-2 max à l'écran).
 /// Jour  : ciel dégradé 14h30 + soleil avec halo pulsé + 9 nuages parallax.
 /// Transition : cross-fade 800 ms avec dissolution douce des éléments.
+/// Animations réduites (`use_reduced_motion`) : ciel complet dessiné une fois, puis
+/// la boucle rAF s'arrête (ni scintillement, ni étoiles filantes, ni nuages qui avancent).
 use std::cell::{Cell, RefCell};
 use std::f64::consts::TAU;
 use std::rc::Rc;
@@ -13,7 +15,11 @@ use leptos::prelude::*;
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
-use crate::theme::{Theme, ThemeCtx};
+use crate::{
+    app::PrintModeCtx,
+    theme::ThemeCtx,
+    utils::use_reduced_motion,
+};
 
 // ─── Thread-locals ────────────────────────────────────────────────────────────
 
@@ -389,10 +395,13 @@ struct SkyAnim {
     prev_dark:  bool,
     blend:      f64,   // 0 → 1 (thème entrant)
     in_trans:   bool,
+
+    // Animations réduites : une seule image, sans fondu ni mouvement
+    still:      bool,
 }
 
 impl SkyAnim {
-    fn new(ctx: CanvasRenderingContext2d, w: f64, h: f64, dark: bool) -> Self {
+    fn new(ctx: CanvasRenderingContext2d, w: f64, h: f64, dark: bool, still: bool) -> Self {
         Self {
             ctx, w, h, t: 0.0,
             stars:     (0..300).map(|_| Star::random(w, h)).collect(),
@@ -401,6 +410,7 @@ impl SkyAnim {
             clouds:    (0..9).map(|_| Cloud::random(w, h)).collect(),
             is_dark: dark, prev_dark: dark,
             blend: 1.0, in_trans: false,
+            still,
         }
     }
 
@@ -415,6 +425,7 @@ impl SkyAnim {
     fn draw_frame(&mut self) {
         // Consomme le changement de thème en attente
         if let Some(dark) = take_pending() { self.switch_theme(dark); }
+        if self.still { self.blend = 1.0; self.in_trans = false; }
 
         // Avance la transition : 800 ms ≈ 48 frames → +0.021/frame
         if self.in_trans {
@@ -454,6 +465,7 @@ impl SkyAnim {
             for c in &self.clouds   { c.draw(ctx, 1.0); }
         }
 
+        if self.still { return; }
         self.t += 1.0;
 
         // ── Étoiles filantes ────────────────────────────────────────────────
@@ -474,25 +486,33 @@ impl SkyAnim {
 
 // ─── Lancement de la boucle rAF ──────────────────────────────────────────────
 
-fn start_animation(canvas: HtmlCanvasElement, dark: bool) {
-    let window = match web_sys::window() { Some(w) => w, None => return };
-    let vw = window.inner_width().unwrap().as_f64().unwrap_or(1280.0);
-    let vh = window.inner_height().unwrap().as_f64().unwrap_or(800.0);
+/// Ajuste le canvas à la fenêtre et renvoie son contexte 2D avec ses dimensions.
+fn prepare_canvas(canvas: &HtmlCanvasElement) -> Option<(CanvasRenderingContext2d, f64, f64)> {
+    let window = web_sys::window()?;
+    let vw = window.inner_width().ok()?.as_f64().unwrap_or(1280.0);
+    let vh = window.inner_height().ok()?.as_f64().unwrap_or(800.0);
     canvas.set_width(vw as u32);
     canvas.set_height(vh as u32);
+    let ctx = canvas.get_context("2d").ok().flatten()?.dyn_into().ok()?;
+    Some((ctx, vw, vh))
+}
 
-    let ctx: CanvasRenderingContext2d = match canvas
-        .get_context("2d").ok().flatten()
-        .and_then(|c| c.dyn_into().ok())
-    {
-        Some(c) => c,
-        None    => return,
-    };
+/// Arrête la boucle en cours : sa génération est dépassée au prochain frame.
+fn stop_animation() {
+    bump_gen();
+    STARTED.with(|s| s.set(false));
+}
+
+/// Démarre la boucle ; `still` (animations réduites) : elle s'arrête après la
+/// première image, par le même mécanisme de génération que `stop_animation`.
+fn start_animation(canvas: HtmlCanvasElement, dark: bool, still: bool) {
+    let window = match web_sys::window() { Some(w) => w, None => return };
+    let Some((ctx, vw, vh)) = prepare_canvas(&canvas) else { return };
 
     STARTED.with(|s| s.set(true));
     let my_gen = bump_gen();
 
-    let anim = Rc::new(RefCell::new(SkyAnim::new(ctx, vw, vh, dark)));
+    let anim = Rc::new(RefCell::new(SkyAnim::new(ctx, vw, vh, dark, still)));
 
     // Pattern rAF auto-référentiel (doc officielle wasm-bindgen)
     let f: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
@@ -507,6 +527,11 @@ fn start_animation(canvas: HtmlCanvasElement, dark: bool) {
                 return;
             }
             anim.borrow_mut().draw_frame();
+            if still {
+                stop_animation();
+                let _ = f.borrow_mut().take();
+                return;
+            }
             win.request_animation_frame(
                 f.borrow().as_ref().unwrap().as_ref().unchecked_ref(),
             ).unwrap();
@@ -524,30 +549,37 @@ fn start_animation(canvas: HtmlCanvasElement, dark: bool) {
 pub fn SkyCanvas() -> impl IntoView {
     let canvas_ref: NodeRef<leptos::html::Canvas> = NodeRef::new();
     let theme_ctx = use_context::<ThemeCtx>().expect("ThemeCtx manquant");
+    let reduced = use_reduced_motion();
+    let print_mode = use_context::<PrintModeCtx>().expect("PrintModeCtx manquant");
 
     Effect::new(move |_| {
-        let is_dark = match theme_ctx.theme.get() {
-            Theme::Dark   => true,
-            Theme::Light  => false,
-            Theme::System => web_sys::window()
-                .and_then(|w| w.match_media("(prefers-color-scheme: dark)").ok().flatten())
-                .map(|mq| mq.matches())
-                .unwrap_or(false),
-        };
-
-        if STARTED.with(|s| s.get()) {
+        let is_dark = theme_ctx.theme.get().is_dark();
+
+        // Page imprimable : plus de boucle, relancée au retour
+        if print_mode.active.get() {
+            stop_animation();
+        } else if reduced.get() {
+            stop_animation();
+            if let Some(canvas) = canvas_ref.get() {
+                start_animation(canvas, is_dark, true);
+            }
+        } else if STARTED.with(|s| s.get()) {
             // La boucle tourne déjà → signale simplement le changement de thème
             notify_theme(is_dark);
         } else if let Some(canvas) = canvas_ref.get() {
-            start_animation(canvas, is_dark);
+            start_animation(canvas, is_dark, false);
         }
     });
 
+    // Masqué plutôt que démonté : le canvas est réutilisé au retour
     view! {
         <canvas
             id="sky-canvas"
             node_ref=canvas_ref
-            style="position:fixed;inset:0;width:100%;height:100%;z-index:-1;pointer-events:none;"
+            style=move || format!(
+                "position:fixed;inset:0;width:100%;height:100%;z-index:-1;pointer-events:none;{}",
+                if print_mode.active.get() { "display:none;" } else { "" }
+            )
         />
     }
 }
@@ -0,0 +1,82 @@
+/// Courbe SVG minimale (sparkline) — sans bibliothèque JS.
+///
+/// Une polyligne qui occupe toute la largeur du conteneur ; les valeurs nulles sont
+/// tracées à zéro (pas de point sauté), pour que la courbe ne cache pas les creux.
+/// La couleur suit `currentColor` : la classe `text-*` du composant décide.
+use leptos::prelude::*;
+
+// ─── Géométrie ────────────────────────────────────────────────────────────────
+
+const WIDTH:  f64 = 300.0;
+const HEIGHT: f64 = 40.0;
+/// Marge verticale : le trait ne touche pas les bords
+const PAD:    f64 = 2.0;
+
+/// Points "x,y x,y …" de la polyligne dans un repère `WIDTH` × `HEIGHT`
+/// (y vers le bas). Toutes les valeurs nulles : ligne plate en bas.
+pub fn sparkline_points(values: &[f64]) -> String {
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+    let step = if values.len() > 1 { WIDTH / (values.len() - 1) as f64 } else { 0.0 };
+    let plot_h = HEIGHT - 2.0 * PAD;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let ratio = if max > 0.0 { v.max(0.0) / max } else { 0.0 };
+            let y = HEIGHT - PAD - ratio * plot_h;
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ─── Composant ────────────────────────────────────────────────────────────────
+
+#[component]
+pub fn Sparkline(
+    values: Vec<f64>,
+    /// Texte alternatif (lecteurs d'écran, infobulle).
+    label: String,
+    #[prop(default = "")]
+    class: &'static str,
+) -> impl IntoView {
+    let points = sparkline_points(&values);
+    let title = label.clone();
+    view! {
+        <svg
+            viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+            preserveAspectRatio="none"
+            class=format!("w-full h-10 {class}")
+            role="img"
+            aria-label=label
+        >
+            <title>{title}</title>
+            <polyline
+                points=points
+                fill="none"
+                stroke="currentColor"
+                stroke-width="1.5"
+                stroke-linejoin="round"
+                stroke-linecap="round"
+                vector-effect="non-scaling-stroke"
+            />
+        </svg>
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_points() {
+        assert_eq!(sparkline_points(&[]), "");
+        assert_eq!(sparkline_points(&[5.0]), "0.0,2.0");
+        // Semaine vide tracée à zéro, maximum en haut
+        assert_eq!(sparkline_points(&[0.0, 10.0, 5.0]), "0.0,38.0 150.0,2.0 300.0,20.0");
+        // Que des zéros : ligne plate en bas
+        assert_eq!(sparkline_points(&[0.0, 0.0]), "0.0,38.0 300.0,38.0");
+    }
+}
@@ -0,0 +1,80 @@
+/// Cloche de la Navbar : tâches en attente (`TasksCtx`).
+///
+/// Un badge compte les rappels ; le menu les liste avec un lien vers la page
+/// où les traiter. Simple rappel : rien n'est corrigé automatiquement.
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+use crate::app::TasksCtx;
+use crate::components::icons::IconBell;
+use crate::i18n::tr;
+
+#[component]
+pub fn TasksMenu() -> impl IntoView {
+    let tasks = use_context::<TasksCtx>().expect("TasksCtx manquant");
+    let open = RwSignal::new(false);
+    let count = move || tasks.tasks.with(Vec::len);
+
+    view! {
+        <div class="relative">
+            <button
+                title=move || tr("nav.tasks")
+                aria-haspopup="true"
+                aria-expanded=move || open.get().to_string()
+                class="relative p-2 rounded-lg text-gray-500 dark:text-gray-400 \
+                       hover:text-blue-600 dark:hover:text-blue-400 \
+                       hover:bg-gray-100 dark:hover:bg-gray-800 \
+                       transition-colors"
+                on:click=move |_| open.update(|o| *o = !*o)
+            >
+                <IconBell class="w-4 h-4" />
+                {move || (count() > 0).then(|| view! {
+                    <span class="absolute top-0.5 right-0.5 min-w-[1rem] h-4 px-1 \
+                                 rounded-full bg-amber-500 text-white \
+                                 text-[0.6rem] font-bold leading-4 text-center tabular-nums">
+                        {count}
+                    </span>
+                })}
+            </button>
+            {move || open.get().then(|| view! {
+                // Fond transparent : un clic ailleurs ferme le menu
+                <div class="fixed inset-0 z-40" on:click=move |_| open.set(false) />
+                <div
+                    class="absolute right-0 mt-2 z-50 w-72 py-2 \
+                           bg-white dark:bg-gray-800 \
+                           border border-gray-200 dark:border-gray-700 \
+                           rounded-xl shadow-xl animate-fade-in"
+                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                        if ev.key() == "Escape" {
+                            open.set(false);
+                        }
+                    }
+                >
+                    <p class="px-3 pb-1 text-xs font-semibold text-gray-500 dark:text-gray-400">
+                        {move || tr("nav.tasks")}
+                    </p>
+                    {move || {
+                        let list = tasks.tasks.get();
+                        if list.is_empty() {
+                            return view! {
+                                <p class="px-3 py-2 text-sm text-gray-400 dark:text-gray-500">
+                                    {tr("nav.tasks_none")}
+                                </p>
+                            }.into_any();
+                        }
+                        list.into_iter().map(|t| view! {
+                            <A
+                                href=t.href()
+                                on:click=move |_| open.set(false)
+                                attr:class="block px-3 py-2 text-sm text-gray-700 dark:text-gray-200 \
+                                            hover:bg-gray-50 dark:hover:bg-gray-700/60 transition-colors"
+                            >
+                                {t.describe()}
+                            </A>
+                        }).collect_view().into_any()
+                    }}
+                </div>
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,310 @@
+/// Pile de notifications (toasts) en haut à droite, alimentée par `ToastProviderCtx`.
+///
+/// Au plus `MAX_VISIBLE_TOASTS` sont affichés ; les suivants attendent dans la file
+/// et apparaissent dès qu'une place se libère. Le délai d'un toast ne commence
+/// qu'à son affichage.
+use leptos::prelude::*;
+
+use crate::{
+    app::ToastProviderCtx,
+    components::icons::{IconAlertTriangle, IconX},
+    utils::sleep_ms,
+};
+
+/// Toasts affichés simultanément.
+pub const MAX_VISIBLE_TOASTS: usize = 4;
+/// Durée de l'animation `toast-exit` (style/main.css).
+pub const TOAST_EXIT_MS: u32 = 400;
+/// Délai pour annuler une suppression depuis son toast.
+pub const UNDO_TOAST_MS: u32 = 6_000;
+/// Bouton des erreurs passagères (`ToastProviderCtx::error_with_retry`).
+pub const RETRY_LABEL: &str = "Avereno";
+
+// ─── File ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Error,
+    Info,
+}
+
+impl ToastKind {
+    /// Affichage par défaut : une erreur reste plus longtemps.
+    pub fn default_duration_ms(self) -> u32 {
+        match self {
+            ToastKind::Error => 7_000,
+            ToastKind::Success | ToastKind::Info => 4_000,
+        }
+    }
+}
+
+/// Bouton d'un toast (ex. « Averina » après une suppression) ; le toast se ferme au clic.
+#[derive(Debug, Clone, Copy)]
+pub struct ToastAction {
+    pub label: &'static str,
+    pub run:   Callback<()>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id:          u32,
+    pub kind:        ToastKind,
+    pub message:     String,
+    pub duration_ms: u32,
+    pub action:      Option<ToastAction>,
+    /// Animation de sortie en cours ; retiré de la file à la fin.
+    pub leaving:     bool,
+}
+
+/// File des toasts, dans l'ordre d'arrivée.
+#[derive(Debug, Clone, Default)]
+pub struct ToastQueue {
+    items:   Vec<Toast>,
+    next_id: u32,
+}
+
+impl ToastQueue {
+    /// Ajoute un toast en fin de file et renvoie son identifiant.
+    pub fn push(&mut self, kind: ToastKind, message: String, duration_ms: u32) -> u32 {
+        self.push_with_action(kind, message, duration_ms, None)
+    }
+
+    /// Idem avec un bouton d'action. Une erreur identique encore affichée ou en
+    /// attente n'est pas empilée une seconde fois : son identifiant est renvoyé.
+    pub fn push_with_action(
+        &mut self,
+        kind: ToastKind,
+        message: String,
+        duration_ms: u32,
+        action: Option<ToastAction>,
+    ) -> u32 {
+        if kind == ToastKind::Error {
+            let same = self.items.iter().find(|t| t.kind == kind && !t.leaving && t.message == message);
+            if let Some(t) = same {
+                return t.id;
+            }
+        }
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.items.push(Toast { id, kind, message, duration_ms, action, leaving: false });
+        id
+    }
+
+    /// Toasts affichés (y compris ceux en train de sortir).
+    pub fn visible(&self) -> &[Toast] {
+        &self.items[..self.items.len().min(MAX_VISIBLE_TOASTS)]
+    }
+
+    /// Toasts en attente d'une place.
+    pub fn queued(&self) -> usize {
+        self.items.len().saturating_sub(MAX_VISIBLE_TOASTS)
+    }
+
+    pub fn is_leaving(&self, id: u32) -> bool {
+        self.items.iter().any(|t| t.id == id && t.leaving)
+    }
+
+    /// Lance la sortie ; `false` si le toast n'existe plus ou sort déjà.
+    pub fn mark_leaving(&mut self, id: u32) -> bool {
+        match self.items.iter_mut().find(|t| t.id == id && !t.leaving) {
+            Some(t) => {
+                t.leaving = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.items.retain(|t| t.id != id);
+    }
+}
+
+// ─── Composants ───────────────────────────────────────────────────────────────
+
+#[component]
+pub fn ToastStack() -> impl IntoView {
+    let ctx = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+
+    view! {
+        <div
+            aria-live="polite"
+            aria-label="Fampahafantarana"
+            class="fixed top-14 right-5 z-[100] flex flex-col items-end gap-2 \
+                   w-full max-w-xs pointer-events-none"
+        >
+            <For
+                each=move || ctx.toasts.with(|q| q.visible().to_vec())
+                key=|t| t.id
+                children=move |t: Toast| view! { <ToastItem toast=t /> }
+            />
+            {move || {
+                let n = ctx.toasts.with(ToastQueue::queued);
+                (n > 0).then(|| view! {
+                    <p class="text-xs text-gray-500 dark:text-gray-400 pr-1">
+                        {format!("+{n} miandry")}
+                    </p>
+                })
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ToastItem(toast: Toast) -> impl IntoView {
+    let ctx = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let id = toast.id;
+
+    // Le délai court à partir de l'affichage, pas de l'ajout dans la file
+    leptos::task::spawn_local(async move {
+        sleep_ms(toast.duration_ms).await;
+        ctx.dismiss(id);
+    });
+
+    let leaving = Memo::new(move |_| ctx.toasts.with(|q| q.is_leaving(id)));
+
+    let (border, text, button) = match toast.kind {
+        ToastKind::Success => (
+            "border-green-200 dark:border-green-700",
+            "text-green-700 dark:text-green-300",
+            "text-green-400 hover:text-green-600 dark:hover:text-green-200",
+        ),
+        ToastKind::Error => (
+            "border-red-200 dark:border-red-700",
+            "text-red-700 dark:text-red-300",
+            "text-red-400 hover:text-red-600 dark:hover:text-red-200",
+        ),
+        ToastKind::Info => (
+            "border-blue-200 dark:border-blue-700",
+            "text-blue-700 dark:text-blue-300",
+            "text-blue-400 hover:text-blue-600 dark:hover:text-blue-200",
+        ),
+    };
+    let icon = match toast.kind {
+        ToastKind::Success => view! {
+            <span class="text-green-500 dark:text-green-400 shrink-0 mt-0.5 text-lg leading-none">"✓"</span>
+        }.into_any(),
+        ToastKind::Error => view! {
+            <IconAlertTriangle class="w-5 h-5 text-red-500 dark:text-red-400 shrink-0 mt-0.5" />
+        }.into_any(),
+        ToastKind::Info => view! {
+            <span class="text-blue-500 dark:text-blue-400 shrink-0 mt-0.5 text-lg leading-none">"ℹ"</span>
+        }.into_any(),
+    };
+
+    view! {
+        <div
+            role=if toast.kind == ToastKind::Error { "alert" } else { "status" }
+            class=move || format!(
+                "pointer-events-auto flex items-start gap-3 w-full \
+                 px-4 py-3 rounded-2xl shadow-2xl border \
+                 bg-white dark:bg-gray-800 {border} {}",
+                if leaving.get() { "toast-exit" } else { "toast-enter" },
+            )
+        >
+            {icon}
+            <p class=format!("text-sm {text} flex-1 leading-snug")>{toast.message}</p>
+            {toast.action.map(|action| view! {
+                <button
+                    type="button"
+                    on:click=move |_| {
+                        ctx.dismiss(id);
+                        action.run.run(());
+                    }
+                    class=format!("btn-ripple text-sm font-semibold underline {text} \
+                                   rounded px-1 shrink-0 hover:opacity-80")
+                >
+                    {action.label}
+                </button>
+            })}
+            <button
+                type="button"
+                aria-label="Akatona"
+                on:click=move |_| ctx.dismiss(id)
+                class=format!("btn-ripple {button} rounded p-0.5 transition-colors")
+            >
+                <IconX class="w-4 h-4" />
+            </button>
+        </div>
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(q: &mut ToastQueue, msg: &str) -> u32 {
+        q.push(ToastKind::Info, msg.into(), 1000)
+    }
+
+    #[test]
+    fn file_limitee_aux_toasts_visibles() {
+        let mut q = ToastQueue::default();
+        let ids: Vec<u32> = (0..6).map(|i| push(&mut q, &i.to_string())).collect();
+        assert_eq!(q.visible().len(), MAX_VISIBLE_TOASTS);
+        assert_eq!(q.queued(), 2);
+        assert_eq!(q.visible()[0].id, ids[0]);
+
+        // Une place libérée : le premier en attente apparaît
+        q.remove(ids[1]);
+        assert_eq!(q.queued(), 1);
+        assert_eq!(q.visible().last().map(|t| t.id), Some(ids[4]));
+    }
+
+    #[test]
+    fn sortie_puis_retrait() {
+        let mut q = ToastQueue::default();
+        let id = push(&mut q, "a");
+        assert!(q.mark_leaving(id));
+        assert!(q.is_leaving(id));
+        // Fermeture manuelle puis délai écoulé : une seule sortie
+        assert!(!q.mark_leaving(id));
+        // Toujours visible pendant l'animation
+        assert_eq!(q.visible().len(), 1);
+        q.remove(id);
+        assert!(q.visible().is_empty());
+        assert!(!q.mark_leaving(id));
+    }
+
+    #[test]
+    fn identifiants_uniques() {
+        let mut q = ToastQueue::default();
+        let a = push(&mut q, "a");
+        q.remove(a);
+        let b = push(&mut q, "b");
+        assert_ne!(a, b);
+        assert_eq!(ToastKind::Error.default_duration_ms(), 7_000);
+    }
+
+    #[test]
+    fn action_conservee() {
+        let mut q = ToastQueue::default();
+        let action = ToastAction { label: "Averina", run: Callback::new(|()| {}) };
+        let id = q.push_with_action(ToastKind::Info, "Voafafa".into(), UNDO_TOAST_MS, Some(action));
+        let t = &q.visible()[0];
+        assert_eq!((t.id, t.duration_ms), (id, UNDO_TOAST_MS));
+        assert_eq!(t.action.map(|a| a.label), Some("Averina"));
+    }
+
+    #[test]
+    fn erreur_identique_non_dupliquee() {
+        let mut q = ToastQueue::default();
+        let retry = ToastAction { label: RETRY_LABEL, run: Callback::new(|()| {}) };
+        let a = q.push_with_action(ToastKind::Error, "database is locked".into(), 7_000, Some(retry));
+        // Deux chargements échouent ensemble : un seul toast « Avereno »
+        assert_eq!(q.push(ToastKind::Error, "database is locked".into(), 7_000), a);
+        assert_eq!(q.visible().len(), 1);
+        assert_eq!(q.visible()[0].action.map(|a| a.label), Some(RETRY_LABEL));
+        // Les succès et les autres erreurs s'empilent
+        q.push(ToastKind::Success, "Voatahiry".into(), 4_000);
+        q.push(ToastKind::Success, "Voatahiry".into(), 4_000);
+        q.push(ToastKind::Error, "Tsy hita".into(), 7_000);
+        assert_eq!(q.visible().len(), 4);
+        // Erreur en train de sortir (clic sur « Avereno ») : la suivante s'affiche
+        assert!(q.mark_leaving(a));
+        assert_ne!(q.push(ToastKind::Error, "database is locked".into(), 7_000), a);
+    }
+}
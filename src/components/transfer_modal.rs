@@ -1,7 +1,11 @@
 /// Modal de confirmation de transfert de membres (Cathécomènes → Communiants).
 use leptos::prelude::*;
 
-use crate::components::{icons::{IconCross, IconInfo}, modal_wrapper::ModalWrapper};
+use crate::{
+    components::{icons::{IconCross, IconInfo}, modal_wrapper::ModalWrapper},
+    i18n::tr,
+    models::member::MemberType,
+};
 
 /// Modal de confirmation avant le transfert de membres sélectionnés.
 #[allow(unused_variables)]
@@ -13,8 +17,8 @@ pub fn TransferModal(
     loading:          RwSignal<bool>,
     /// IDs des membres sélectionnés (pour afficher le compteur).
     selected:         RwSignal<Vec<i64>>,
-    /// Type cible du transfert (ex: "Communiant").
-    _transfer_to:      &'static str,
+    /// Type cible du transfert.
+    transfer_to:      MemberType,
     /// Callback déclenché quand l'utilisateur confirme.
     on_confirm:       Callback<()>,
 ) -> impl IntoView {
@@ -37,8 +41,8 @@ pub fn TransferModal(
                         {move || {
                             let n = selected.get().len();
                             format!(
-                                "Hamindra mpikambana {} ho any amin'ny Mpandray ?",
-                                n
+                                "Hamindra mpikambana {} ho any amin'ny {} ?",
+                                n, transfer_to.label()
                             )
                         }}
                     </p>
@@ -63,7 +67,7 @@ pub fn TransferModal(
                                    hover:bg-gray-200 dark:hover:bg-gray-600 \
                                    disabled:opacity-50 rounded-xl transition-colors"
                         >
-                            "Foana"
+                            {move || tr("action.cancel")}
                         </button>
                         <button
                             type="button"
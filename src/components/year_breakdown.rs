@@ -0,0 +1,92 @@
+/// Répartition d'une année par genre et par type de membre — Archives, au-dessus
+/// du tableau des cotisations (rapport annuel présenté au conseil).
+///
+/// Grille 2 × 2 : Lahy / Vavy, Mpandray / Tsy Mpandray. Les cotisations dont le
+/// membre n'existe plus sont signalées à part ("Tsy fantatra").
+use leptos::prelude::*;
+
+use crate::{
+    app::DataVersionCtx,
+    components::icons::IconAlertTriangle,
+    models::year_summary::{BreakdownEntry, YearBreakdown},
+    services::db_service,
+    utils::{format_ariary_str, MoneyFormat},
+};
+
+/// Groupes connus (grille) et groupe "Inconnu" éventuel, d'une même série.
+fn split_unknown(entries: Vec<BreakdownEntry>) -> (Vec<BreakdownEntry>, Option<BreakdownEntry>) {
+    let (known, unknown): (Vec<_>, Vec<_>) = entries.into_iter().partition(|e| e.key != "Inconnu");
+    (known, unknown.into_iter().next())
+}
+
+#[component]
+pub fn YearBreakdownCards(year: i32) -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let data: RwSignal<Option<YearBreakdown>> = RwSignal::new(None);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+
+    // Rechargé après une modification de cotisation
+    Effect::new(move |_| {
+        data_version.version.track();
+        leptos::task::spawn_local(async move {
+            match db_service::get_year_breakdown(year).await {
+                Ok(b) => {
+                    erreur.set(None);
+                    data.set(Some(b));
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+        });
+    });
+
+    view! {
+        {move || erreur.get().map(|e| view! {
+            <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                <span>{e}</span>
+            </div>
+        })}
+        {move || {
+            let b = data.get()?;
+            // Année sans cotisation : rien à afficher
+            if b.by_type.iter().all(|e| e.count == 0) {
+                return None;
+            }
+            let (genders, unknown) = split_unknown(b.by_gender);
+            let (types, _) = split_unknown(b.by_type);
+            Some(view! {
+                <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                            rounded-2xl border border-gray-100 dark:border-gray-700 \
+                            shadow-sm px-4 py-3">
+                    <p class="text-xs font-semibold uppercase tracking-wide \
+                              text-gray-500 dark:text-gray-400 mb-2">
+                        {format!("Fizarana — {year}")}
+                    </p>
+                    <div class="grid grid-cols-2 gap-2">
+                        {genders.into_iter().chain(types).map(|e| view! {
+                            <div class="rounded-xl px-3 py-2 bg-gray-50/80 dark:bg-gray-700/40 \
+                                        text-gray-700 dark:text-gray-200">
+                                <p class="text-[11px] font-medium">
+                                    {format!("{} · {} rakitra", e.label(), e.count)}
+                                </p>
+                                <p class="text-sm font-mono font-semibold truncate">
+                                    {format_ariary_str(&e.total, MoneyFormat::default())}
+                                </p>
+                            </div>
+                        }).collect_view()}
+                    </div>
+                    {unknown.map(|e| view! {
+                        <p class="mt-2 text-xs text-amber-600 dark:text-amber-400">
+                            {format!(
+                                "{} : {} rakitra, {}",
+                                e.label(),
+                                e.count,
+                                format_ariary_str(&e.total, MoneyFormat::default()),
+                            )}
+                        </p>
+                    })}
+                </div>
+            })
+        }}
+    }
+}
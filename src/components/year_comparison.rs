@@ -0,0 +1,231 @@
+/// Tableaux comparatifs des années — Archives → "Fampitahana".
+///
+/// `YearComparisonTable` : une ligne par année (total, sous-totaux Mpandray /
+/// Tsy Mpandray, variation en % par rapport à l'année précédente).
+/// `MonthlyComparisonTable` : deux années mois par mois, avec l'écart cumulé.
+use std::cmp::Ordering;
+
+use leptos::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::{
+    components::icons::IconAlertTriangle,
+    i18n::tr,
+    models::year_summary::{MonthlyComparison, YearComparison},
+    services::db_service,
+    utils::{format_ariary_str, money::parse_decimal, MoneyFormat},
+};
+
+/// Formate une variation : "+12,5 %", "-3 %", "—" si non définie.
+fn fmt_variation(pct: Option<f64>) -> String {
+    match pct {
+        None => "—".to_string(),
+        Some(p) => {
+            let sign = if p > 0.0 { "+" } else { "" };
+            let s = format!("{sign}{p:.1}");
+            let s = s.strip_suffix(".0").unwrap_or(&s).replace('.', ",");
+            format!("{s}\u{202f}%")
+        }
+    }
+}
+
+/// Formate un écart cumulé : "+1 500 Ar", "-200 Ar", "0 Ar".
+fn fmt_delta(diff: &str) -> String {
+    let s = format_ariary_str(diff, MoneyFormat::default());
+    if parse_decimal(diff).round() > Decimal::ZERO {
+        format!("+{s}")
+    } else {
+        s
+    }
+}
+
+/// Couleur de l'écart : vert en avance, rouge en retard.
+fn delta_class(diff: &str) -> &'static str {
+    match parse_decimal(diff).round().cmp(&Decimal::ZERO) {
+        Ordering::Greater => "text-emerald-600 dark:text-emerald-400",
+        Ordering::Less    => "text-red-600 dark:text-red-400",
+        Ordering::Equal   => "text-gray-400 dark:text-gray-500",
+    }
+}
+
+#[component]
+pub fn YearComparisonTable(
+    /// Années à comparer (ordre indifférent — le backend trie ASC).
+    years: Vec<i32>,
+) -> impl IntoView {
+    let rows: RwSignal<Vec<YearComparison>> = RwSignal::new(vec![]);
+    let loading = RwSignal::new(true);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+
+    leptos::task::spawn_local(async move {
+        match db_service::get_year_comparison(&years).await {
+            Ok(liste) => rows.set(liste),
+            Err(e)    => erreur.set(Some(e)),
+        }
+        loading.set(false);
+    });
+
+    view! {
+        <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                    rounded-2xl border border-gray-100 \
+                    dark:border-gray-700 overflow-hidden shadow-sm">
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+            {move || if loading.get() {
+                view! {
+                    <div class="flex justify-center py-8">
+                        <div class="w-6 h-6 border-4 border-blue-400 \
+                                    border-t-transparent rounded-full animate-spin" />
+                    </div>
+                }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="w-full text-sm">
+                            <thead>
+                                <tr class="bg-gray-50/80 dark:bg-gray-700/50 \
+                                           text-gray-600 dark:text-gray-300 \
+                                           text-xs uppercase tracking-wide">
+                                    <th class="text-left px-4 py-3 font-semibold">{move || tr("table.year")}</th>
+                                    <th class="text-right px-4 py-3 font-semibold">{move || tr("table.total")}</th>
+                                    <th class="text-right px-4 py-3 font-semibold \
+                                               hidden sm:table-cell">{move || tr("nav.communiants")}</th>
+                                    <th class="text-right px-4 py-3 font-semibold \
+                                               hidden sm:table-cell">{move || tr("nav.cathekomens")}</th>
+                                    <th class="text-right px-4 py-3 font-semibold">{move || tr("table.variation")}</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100 dark:divide-gray-700/50">
+                                {rows.get().into_iter().map(|r| {
+                                    let var_cls = match r.variation_pct {
+                                        Some(p) if p > 0.0 => "text-emerald-600 dark:text-emerald-400",
+                                        Some(p) if p < 0.0 => "text-red-600 dark:text-red-400",
+                                        _                  => "text-gray-400 dark:text-gray-500",
+                                    };
+                                    view! {
+                                        <tr class="text-gray-700 dark:text-gray-200">
+                                            <td class="px-4 py-2.5 font-semibold">{r.year}</td>
+                                            <td class="px-4 py-2.5 text-right font-mono">
+                                                {format_ariary_str(&r.total, MoneyFormat::default())}
+                                            </td>
+                                            <td class="px-4 py-2.5 text-right font-mono hidden sm:table-cell">
+                                                {format_ariary_str(&r.communiants, MoneyFormat::default())}
+                                            </td>
+                                            <td class="px-4 py-2.5 text-right font-mono hidden sm:table-cell">
+                                                {format_ariary_str(&r.cathekomens, MoneyFormat::default())}
+                                            </td>
+                                            <td class=format!("px-4 py-2.5 text-right font-mono font-semibold {var_cls}")>
+                                                {fmt_variation(r.variation_pct)}
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn MonthlyComparisonTable(
+    /// Année suivie (en général l'année sélectionnée).
+    year_a: i32,
+    /// Année de référence (en général la précédente).
+    year_b: i32,
+) -> impl IntoView {
+    let data: RwSignal<Option<MonthlyComparison>> = RwSignal::new(None);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+
+    leptos::task::spawn_local(async move {
+        match db_service::compare_years(year_a, year_b).await {
+            Ok(c)  => data.set(Some(c)),
+            Err(e) => erreur.set(Some(e)),
+        }
+    });
+
+    view! {
+        <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                    rounded-2xl border border-gray-100 \
+                    dark:border-gray-700 overflow-hidden shadow-sm">
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+            {move || match data.get() {
+                None => (erreur.get().is_none()).then(|| view! {
+                    <div class="flex justify-center py-8">
+                        <div class="w-6 h-6 border-4 border-blue-400 \
+                                    border-t-transparent rounded-full animate-spin" />
+                    </div>
+                }).into_any(),
+                Some(c) => view! {
+                    <div class="overflow-x-auto">
+                        <table class="w-full text-sm">
+                            <thead>
+                                <tr class="bg-gray-50/80 dark:bg-gray-700/50 \
+                                           text-gray-600 dark:text-gray-300 \
+                                           text-xs uppercase tracking-wide">
+                                    <th class="text-left px-4 py-3 font-semibold">"Volana"</th>
+                                    <th class="text-right px-4 py-3 font-semibold">{c.year_a}</th>
+                                    <th class="text-right px-4 py-3 font-semibold">{c.year_b}</th>
+                                    <th class="text-right px-4 py-3 font-semibold">"Elanelana"</th>
+                                </tr>
+                            </thead>
+                            <tbody class="divide-y divide-gray-100 dark:divide-gray-700/50">
+                                {c.months.into_iter().map(|m| view! {
+                                    <tr class="text-gray-700 dark:text-gray-200">
+                                        <td class="px-4 py-2 font-semibold">{m.label()}</td>
+                                        <td class="px-4 py-2 text-right font-mono">
+                                            {format_ariary_str(&m.total_a, MoneyFormat::default())}
+                                        </td>
+                                        <td class="px-4 py-2 text-right font-mono">
+                                            {format_ariary_str(&m.total_b, MoneyFormat::default())}
+                                        </td>
+                                        <td class=format!(
+                                            "px-4 py-2 text-right font-mono font-semibold {}",
+                                            delta_class(&m.cumulative_diff),
+                                        )>
+                                            {fmt_delta(&m.cumulative_diff)}
+                                        </td>
+                                    </tr>
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any(),
+            }}
+        </div>
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_variation() {
+        assert_eq!(fmt_variation(None), "—");
+        assert_eq!(fmt_variation(Some(12.5)), "+12,5\u{202f}%");
+        assert_eq!(fmt_variation(Some(-3.0)), "-3\u{202f}%");
+        assert_eq!(fmt_variation(Some(0.0)), "0\u{202f}%");
+    }
+
+    #[test]
+    fn test_fmt_delta() {
+        assert_eq!(fmt_delta("1500.50"), "+1\u{202f}501\u{202f}Ar");
+        assert_eq!(fmt_delta("-200"), "-200\u{202f}Ar");
+        assert_eq!(fmt_delta("0.00"), "0\u{202f}Ar");
+        assert_eq!(delta_class("-0.4"), delta_class("0"));
+    }
+}
@@ -0,0 +1,103 @@
+/// Totaux par membre d'une année clôturée — Archives, onglet « Isaky ny mpikambana ».
+///
+/// Instantané figé à la clôture : un membre renommé ou supprimé depuis garde ici
+/// le nom et la carte qu'il avait ce jour-là.
+use leptos::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::{
+    components::icons::IconAlertTriangle,
+    models::year_summary::YearMemberSnapshot,
+    services::db_service,
+    utils::{format_ariary, format_ariary_str, money::parse_decimal, MoneyFormat},
+};
+
+#[component]
+pub fn YearMemberTotalsTable(year: i32) -> impl IntoView {
+    let totals:  RwSignal<Option<Vec<YearMemberSnapshot>>> = RwSignal::new(None);
+    let erreur:  RwSignal<Option<String>>                  = RwSignal::new(None);
+
+    // Figé : pas de rechargement après une écriture
+    leptos::task::spawn_local(async move {
+        match db_service::get_year_member_totals(year).await {
+            Ok(liste) => totals.set(Some(liste)),
+            Err(e)    => erreur.set(Some(e)),
+        }
+    });
+
+    view! {
+        {move || erreur.get().map(|e| view! {
+            <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                <span>{e}</span>
+            </div>
+        })}
+        {move || totals.get().map(|liste| {
+            if liste.is_empty() {
+                return view! {
+                    <p class="text-center py-14 text-sm text-gray-400 dark:text-gray-500">
+                        {format!("Tsy misy mpikambana nandoa tamin'ny {year}")}
+                    </p>
+                }.into_any();
+            }
+            let total: Decimal = liste.iter().map(|t| parse_decimal(&t.total)).sum();
+            let count = liste.len();
+            view! {
+                <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                            rounded-2xl border border-gray-100 \
+                            dark:border-gray-700 overflow-hidden shadow-sm">
+                    <div class="overflow-x-auto">
+                        <table class="w-full text-sm">
+                            <thead>
+                                <tr class="bg-gray-50/80 dark:bg-gray-900/50 \
+                                           border-b border-gray-100 dark:border-gray-700 \
+                                           text-gray-600 dark:text-gray-400 font-semibold text-left">
+                                    <th class="px-4 py-3 w-12">"#"</th>
+                                    <th class="px-4 py-3 hidden sm:table-cell">"Karatra"</th>
+                                    <th class="px-4 py-3">"Mpikambana"</th>
+                                    <th class="px-4 py-3 text-right">"Totaly"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {liste.into_iter().enumerate().map(|(i, t)| view! {
+                                    <tr class="border-b border-gray-50 dark:border-gray-700/50">
+                                        <td class="px-4 py-2.5 text-xs text-gray-400 dark:text-gray-500">
+                                            {i + 1}
+                                        </td>
+                                        <td class="px-4 py-2.5 hidden sm:table-cell font-mono text-xs \
+                                                   text-gray-500 dark:text-gray-400">
+                                            {if t.card_number.is_empty() { "—".to_string() } else { t.card_number }}
+                                        </td>
+                                        <td class="px-4 py-2.5 font-medium text-gray-800 dark:text-gray-100">
+                                            {if t.member_name.is_empty() {
+                                                format!("#{}", t.member_id)
+                                            } else {
+                                                t.member_name
+                                            }}
+                                        </td>
+                                        <td class="px-4 py-2.5 text-right font-mono font-semibold \
+                                                   text-gray-800 dark:text-gray-100">
+                                            {format_ariary_str(&t.total, MoneyFormat::default())}
+                                        </td>
+                                    </tr>
+                                }).collect_view()}
+                            </tbody>
+                            <tfoot>
+                                <tr class="bg-gray-50/80 dark:bg-gray-700/50 \
+                                           border-t border-gray-200 dark:border-gray-600">
+                                    <td class="px-4 py-2.5 text-xs text-gray-500 dark:text-gray-400" colspan="3">
+                                        {format!("{count} mpikambana")}
+                                    </td>
+                                    <td class="px-4 py-2.5 text-right font-mono font-bold \
+                                               text-gray-800 dark:text-gray-100">
+                                        {format_ariary(&total, MoneyFormat::default())}
+                                    </td>
+                                </tr>
+                            </tfoot>
+                        </table>
+                    </div>
+                </div>
+            }.into_any()
+        })}
+    }
+}
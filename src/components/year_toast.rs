@@ -1,12 +1,27 @@
-/// Toast de notification — clôture automatique d'une année.
+/// Toast de notification — clôture de l'année précédente.
 ///
-/// Affiché en bas à droite quand `ToastCtx.data` passe à `Some(YearSummary)`.
-/// Auto-dismiss après 8 s (7.6 s affichage + 0.4 s animation de sortie).
+/// Affiché en bas à droite selon `ToastCtx` :
+/// - `preview` : année précédente encore ouverte ; total provisoire et note
+///   proposée, modifiable. Reste affiché jusqu'au choix « Hikatona » (clôture
+///   avec la note saisie) ou « Any aoriana » ;
+/// - `data` = `Closed` : année clôturée, auto-dismiss après 8 s (7.6 s affichage
+///   + 0.4 s de sortie).
 use leptos::prelude::*;
 
-use crate::components::icons::{IconBell, IconX};
+use crate::components::icons::{IconBell, IconLock, IconX};
+use crate::i18n::tr;
+use crate::models::year_summary::YearCloseStatus;
+use crate::services::db_service;
 use crate::theme::ToastCtx;
-use crate::utils::{format_ariary, sleep_ms};
+use crate::utils::{format_ariary_str, sleep_ms, MoneyFormat};
+
+/// Texte du rappel d'une année encore ouverte.
+fn open_year_message(year: i32) -> String {
+    format!(
+        "Mbola azo ampidirina ny fandoavana tamin'ny {year}. \
+         Akatony rehefa vita ny fandoavana : hamarino ny fanamarihana aloha.",
+    )
+}
 
 // ── Composant ─────────────────────────────────────────────────────────────────
 
@@ -15,105 +30,209 @@ pub fn YearToast() -> impl IntoView {
     let ctx = use_context::<ToastCtx>().expect("ToastCtx manquant");
     let visible  = RwSignal::new(false);
     let exiting  = RwSignal::new(false);
+    let closing  = RwSignal::new(false);
+    let note:  RwSignal<String>         = RwSignal::new(String::new());
+    let error: RwSignal<Option<String>> = RwSignal::new(None);
 
-    // Réagit quand une clôture vient d'être effectuée
-    Effect::new(move |_| {
-        if ctx.data.get().is_some() {
-            visible.set(true);
-            exiting.set(false);
-            // Auto-dismiss
+    let dismiss = move || {
+        if !exiting.get_untracked() {
+            exiting.set(true);
             leptos::task::spawn_local(async move {
-                sleep_ms(7_600).await;
-                exiting.set(true);
                 sleep_ms(400).await;
                 visible.set(false);
                 ctx.data.set(None);
+                ctx.preview.set(None);
             });
         }
-    });
+    };
 
-    let close = move |_| {
-        if !exiting.get() {
-            exiting.set(true);
+    // Clôture effectuée : disparaît toute seule
+    Effect::new(move |_| {
+        if let Some(YearCloseStatus::Closed { .. }) = ctx.data.get() {
+            visible.set(true);
+            exiting.set(false);
             leptos::task::spawn_local(async move {
-                sleep_ms(400).await;
-                visible.set(false);
-                ctx.data.set(None);
+                sleep_ms(7_600).await;
+                dismiss();
             });
         }
+    });
+
+    // Année encore ouverte : note proposée recopiée dans le champ, reste affiché
+    Effect::new(move |_| {
+        if let Some(p) = ctx.preview.get() {
+            note.set(p.default_note);
+            error.set(None);
+            visible.set(true);
+            exiting.set(false);
+        }
+    });
+
+    let close_now = move |year: i32| {
+        closing.set(true);
+        error.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::close_year(year, Some(note.get_untracked())).await {
+                Ok(summary) => {
+                    ctx.preview.set(None);
+                    ctx.data.set(Some(YearCloseStatus::Closed { summary }));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            closing.set(false);
+        });
     };
 
     move || {
         if !visible.get() {
             return view! { <div /> }.into_any();
         }
-        let summary = match ctx.data.get() {
-            Some(s) => s,
-            None    => return view! { <div /> }.into_any(),
-        };
-        let year  = summary.year;
-        let total = format_ariary(&summary.total);
-        let note  = summary.note.clone();
 
         let wrapper_cls = if exiting.get() {
             "fixed bottom-6 right-6 z-50 w-80 rounded-2xl shadow-2xl overflow-hidden toast-exit"
         } else {
             "fixed bottom-6 right-6 z-50 w-80 rounded-2xl shadow-2xl overflow-hidden toast-enter"
         };
-        let progress_cls = if exiting.get() {
-            "h-full bg-amber-500"
-        } else {
-            "h-full bg-amber-500 toast-progress"
+
+        let header = move |title: &'static str, year: i32| view! {
+            // ── Bande ambre : icône + titre + bouton fermer ────────────────────
+            <div class="bg-gradient-to-r from-amber-500 to-orange-400 \
+                        px-4 py-3 flex items-center gap-3">
+                <div class="bell-ring select-none shrink-0">
+                    <IconBell class="w-6 h-6 text-white" />
+                </div>
+                <div class="flex-1 min-w-0">
+                    <p class="text-white font-bold text-sm leading-tight">{title}</p>
+                    <p class="text-amber-100 text-xs mt-0.5">{year.to_string()}</p>
+                </div>
+                <button
+                    on:click=move |_| dismiss()
+                    class="text-white/70 hover:text-white flex-shrink-0 \
+                           transition-colors duration-150 p-0.5 rounded"
+                    aria-label="Akatona"
+                >
+                    <IconX class="w-4 h-4" />
+                </button>
+            </div>
         };
 
-        view! {
-            <div class={wrapper_cls}>
-                // ── Bande ambre : icône + titre + bouton fermer ────────────────
-                <div class="bg-gradient-to-r from-amber-500 to-orange-400 \
-                            px-4 py-3 flex items-center gap-3">
-                    <div class="bell-ring select-none shrink-0">
-                        <IconBell class="w-6 h-6 text-white" />
-                    </div>
-                    <div class="flex-1 min-w-0">
-                        <p class="text-white font-bold text-sm leading-tight">
-                            "Taona voakatona ho azy"
+        if let Some(YearCloseStatus::Closed { summary }) = ctx.data.get() {
+            let total = format_ariary_str(&summary.total, MoneyFormat::default());
+            let progress_cls = if exiting.get() {
+                "h-full bg-amber-500"
+            } else {
+                "h-full bg-amber-500 toast-progress"
+            };
+            return view! {
+                <div class={wrapper_cls}>
+                    {header("Taona voakatona", summary.year)}
+
+                    // ── Corps : total + note ───────────────────────────────────
+                    <div class="bg-white dark:bg-gray-800 px-4 py-3">
+                        <p class="text-xs text-gray-500 dark:text-gray-400 mb-1">
+                            "Fitambarana voatahiry"
                         </p>
-                        <p class="text-amber-100 text-xs mt-0.5">
-                            {year.to_string()}
+                        <p class="text-lg font-bold text-gray-800 dark:text-white font-mono">
+                            {total}
                         </p>
+                        {summary.breakdown().map(|b| view! {
+                            <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5">
+                                {b}
+                            </p>
+                        })}
+                        {summary.note.map(|n| view! {
+                            <p class="text-xs text-gray-400 dark:text-gray-500 mt-1.5 italic \
+                                      leading-snug line-clamp-2">
+                                {n}
+                            </p>
+                        })}
+                    </div>
+
+                    // ── Barre de progression ───────────────────────────────────
+                    <div class="h-1 bg-amber-100 dark:bg-amber-900/30">
+                        <div class={progress_cls} style="width:100%" />
                     </div>
-                    <button
-                        on:click=close
-                        class="text-white/70 hover:text-white flex-shrink-0 \
-                               transition-colors duration-150 p-0.5 rounded"
-                        aria-label="Akatona"
-                    >
-                        <IconX class="w-4 h-4" />
-                    </button>
                 </div>
+            }
+            .into_any();
+        }
+
+        let Some(preview) = ctx.preview.get() else {
+            return view! { <div /> }.into_any();
+        };
+        let year = preview.year;
+        let total = format_ariary_str(&preview.total, MoneyFormat::default());
+        view! {
+            <div class={wrapper_cls}>
+                {header("Mbola misokatra ny taona", year)}
 
-                // ── Corps : total + note ───────────────────────────────────────
-                <div class="bg-white dark:bg-gray-800 px-4 py-3">
-                    <p class="text-xs text-gray-500 dark:text-gray-400 mb-1">
-                        "Fitambarana voatahiry"
-                    </p>
-                    <p class="text-lg font-bold text-gray-800 dark:text-white font-mono">
-                        {total}
-                    </p>
-                    {note.map(|n| view! {
-                        <p class="text-xs text-gray-400 dark:text-gray-500 mt-1.5 italic \
-                                  leading-snug line-clamp-2">
-                            {n}
+                // ── Corps : total provisoire, note modifiable + actions ────────
+                <div class="bg-white dark:bg-gray-800 px-4 py-3 space-y-3">
+                    <div>
+                        <p class="text-xs text-gray-500 dark:text-gray-400 mb-1">
+                            "Fitambarana hatreto"
+                        </p>
+                        <p class="text-lg font-bold text-gray-800 dark:text-white font-mono">
+                            {total}
                         </p>
+                        <p class="text-xs text-gray-400 dark:text-gray-500 mt-1.5 leading-snug">
+                            {open_year_message(year)}
+                        </p>
+                    </div>
+                    <textarea
+                        rows="4"
+                        aria-label="Fanamarihana"
+                        class="w-full px-2.5 py-2 text-xs leading-snug \
+                               bg-gray-50 dark:bg-gray-700/60 \
+                               border border-gray-200 dark:border-gray-600 \
+                               rounded-xl text-gray-800 dark:text-white resize-none \
+                               focus:outline-none focus:ring-2 focus:ring-amber-400"
+                        prop:value=move || note.get()
+                        on:input=move |ev| note.set(event_target_value(&ev))
+                    />
+                    {move || error.get().map(|e| view! {
+                        <p class="text-xs text-red-600 dark:text-red-400">{e}</p>
                     })}
-                </div>
-
-                // ── Barre de progression ───────────────────────────────────────
-                <div class="h-1 bg-amber-100 dark:bg-amber-900/30">
-                    <div class={progress_cls} style="width:100%" />
+                    <div class="flex gap-2">
+                        <button
+                            type="button"
+                            disabled=move || closing.get()
+                            on:click=move |_| dismiss()
+                            class="btn-ripple flex-1 px-3 py-2 text-xs font-medium \
+                                   text-gray-600 dark:text-gray-300 \
+                                   bg-gray-100 dark:bg-gray-700 \
+                                   hover:bg-gray-200 dark:hover:bg-gray-600 \
+                                   disabled:opacity-50 rounded-xl transition-colors"
+                        >
+                            {move || tr("action.later")}
+                        </button>
+                        <button
+                            type="button"
+                            disabled=move || closing.get()
+                            on:click=move |_| close_now(year)
+                            class="btn-ripple flex-1 flex items-center justify-center gap-1.5 \
+                                   px-3 py-2 text-xs font-semibold text-white \
+                                   bg-amber-500 hover:bg-amber-600 \
+                                   disabled:opacity-60 disabled:cursor-wait \
+                                   rounded-xl transition-colors shadow-sm"
+                        >
+                            <IconLock class="w-3.5 h-3.5" />
+                            {move || if closing.get() { "Mikatona…" } else { "Hikatona" }}
+                        </button>
+                    </div>
                 </div>
             </div>
         }
         .into_any()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rappel_annee_ouverte() {
+        assert!(open_year_message(2024).starts_with("Mbola azo ampidirina ny fandoavana tamin'ny 2024."));
+    }
+}
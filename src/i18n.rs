@@ -0,0 +1,148 @@
+/// Traduction de l'interface (malgache / français).
+///
+/// `tr(clé)` renvoie le libellé dans la langue de `LangCtx` ; appelé dans une
+/// closure réactive, le texte suit le sélecteur de langue de la Navbar.
+/// La langue est enregistrée côté serveur (`get_language` / `set_language`),
+/// qui traduit aussi ses messages d'erreur.
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// ─── Langue ──────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    Mg,
+    Fr,
+}
+
+impl Lang {
+    /// Libellé court du sélecteur.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Mg => "MG",
+            Lang::Fr => "FR",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            Lang::Mg => Lang::Fr,
+            Lang::Fr => Lang::Mg,
+        }
+    }
+}
+
+/// Langue courante de l'interface, chargée une fois la source configurée.
+#[derive(Clone, Copy)]
+pub struct LangCtx {
+    pub lang: RwSignal<Lang>,
+}
+
+// ─── Recherche ───────────────────────────────────────────────────────────────
+
+/// Libellé de `key` dans `lang` ; une clé inconnue est renvoyée telle quelle.
+pub fn lookup(lang: Lang, key: &str) -> &str {
+    STRINGS
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, mg, fr)| match lang {
+            Lang::Mg => *mg,
+            Lang::Fr => *fr,
+        })
+        .unwrap_or(key)
+}
+
+/// Libellé de `key` dans la langue de `LangCtx` (malgache hors contexte).
+pub fn tr(key: &str) -> String {
+    let lang = use_context::<LangCtx>().map(|c| c.lang.get()).unwrap_or_default();
+    lookup(lang, key).to_string()
+}
+
+// ─── Table ───────────────────────────────────────────────────────────────────
+
+/// (clé, malgache, français).
+const STRINGS: &[(&str, &str, &str)] = &[
+    // Navigation
+    ("nav.home",           "Fandraisana",          "Accueil"),
+    ("nav.communiants",    "Mpandray",             "Communiants"),
+    ("nav.cathekomens",    "Tsy Mpandray",         "Catéchumènes"),
+    ("nav.archives",       "Tahiry",               "Archives"),
+    ("nav.stats",          "Statistika",           "Statistiques"),
+    ("nav.settings",       "Fikirana",             "Paramètres"),
+    ("nav.search",         "Hikaroka…",            "Rechercher…"),
+    ("nav.search_label",   "Hikaroka mpikambana",  "Rechercher un membre"),
+    ("nav.maintenance",    "Fikojakojana",         "Maintenance"),
+    ("nav.journal",        "Diarin'ny asa",        "Journal des actions"),
+    ("nav.batch",          "Fandoavana maro",      "Saisie en série"),
+    ("nav.refresh",        "Mamerina",             "Actualiser"),
+    ("nav.tasks",          "Asa miandry",          "Tâches en attente"),
+    ("nav.tasks_none",     "Tsy misy asa miandry", "Aucune tâche en attente"),
+    ("nav.language",       "Hanova fiteny",        "Changer de langue"),
+    // Boutons
+    ("action.cancel",      "Foana",                "Annuler"),
+    ("action.save",        "Tehirizina",           "Enregistrer"),
+    ("action.saving",      "Mitahiry…",            "Enregistrement…"),
+    ("action.load_more",   "Hijery bebe kokoa",    "Voir plus"),
+    ("action.retry",       "Andramo indray",       "Réessayer"),
+    ("action.previous",    "Teo aloha",            "Précédent"),
+    ("action.next",        "Manaraka",             "Suivant"),
+    ("action.later",       "Any aoriana",          "Plus tard"),
+    // En-têtes de tableaux
+    ("table.card",         "N° Karatra",           "N° de carte"),
+    ("table.name",         "Anarana sy fanampiny", "Nom complet"),
+    ("table.address",      "Adiresy",              "Adresse"),
+    ("table.phone",        "Finday",               "Téléphone"),
+    ("table.job",          "Asa",                  "Profession"),
+    ("table.gender",       "Lahy/Vavy",            "Genre"),
+    ("table.this_year",    "Ity taona ity",        "Cette année"),
+    ("table.actions",      "Hetsika",              "Actions"),
+    ("table.member",       "Mpikambana",           "Membre"),
+    ("table.receipt",      "Rosia",                "Reçu"),
+    ("table.period",       "Vanim-potoana",        "Période"),
+    ("table.amount",       "Vola",                 "Montant"),
+    ("table.date",         "Daty",                 "Date"),
+    ("table.recorded_by",  "Nampiditra",           "Saisi par"),
+    ("table.year",         "Taona",                "Année"),
+    ("table.changes",      "Fanovana",             "Modifications"),
+    ("table.total",        "Fitambarana",          "Total"),
+    ("table.variation",    "Fiovana",              "Variation"),
+    // Filtres
+    ("filter.all",         "Rehetra",              "Tout"),
+    ("filter.unpaid",      "Tsy nandoa",           "Sans cotisation"),
+    ("filter.by_year",     "Isan-taona",           "Afficher par année"),
+    // États vides
+    ("empty.journal",      "Mbola tsy misy asa voasoratra.", "Aucune action enregistrée."),
+    ("empty.no_data",      "Tsy misy angona",      "Aucune donnée"),
+    // Données de démonstration ({n}, {members}, {contributions}, {total} remplacés)
+    ("demo.confirm_title", "Hampiditra angona fanandramana ?", "Insérer des données de démonstration ?"),
+    ("demo.confirm_message",
+     "{n} mpikambana tsy misy marina sy ny fandoavany. Amin'ny tahiry foana ihany.",
+     "{n} membres fictifs et leurs cotisations. Uniquement dans une base vide."),
+    ("demo.confirm",       "Ampidiro",             "Insérer"),
+    ("demo.done",
+     "{members} mpikambana, {contributions} fandoavana ({total}) nampidirina",
+     "{members} membres, {contributions} cotisations ({total}) insérés"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup(Lang::Mg, "action.save"), "Tehirizina");
+        assert_eq!(lookup(Lang::Fr, "action.save"), "Enregistrer");
+        assert_eq!(lookup(Lang::Fr, "inconnue"), "inconnue");
+        assert_eq!(Lang::Mg.toggled(), Lang::Fr);
+    }
+
+    #[test]
+    fn test_strings_uniques() {
+        for (i, (key, mg, fr)) in STRINGS.iter().enumerate() {
+            assert!(!mg.is_empty() && !fr.is_empty(), "{key}");
+            assert!(STRINGS[i + 1..].iter().all(|(k, _, _)| k != key), "clé en double : {key}");
+        }
+    }
+}
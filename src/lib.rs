@@ -1,5 +1,6 @@
 mod app;
 mod components;
+mod i18n;
 mod models;
 mod pages;
 mod services;
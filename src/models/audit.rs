@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Ligne du journal d'audit (retournée par `get_audit_log`), la plus récente d'abord.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub id:        i64,
+    /// "YYYY-MM-DDTHH:MM:SS", UTC
+    pub timestamp: String,
+    /// 'create' | 'update' | 'delete' | 'transfer' | 'close' | 'reopen'
+    pub action:    String,
+    /// 'member' | 'contribution' | 'year'
+    pub entity:    String,
+    /// Id du membre / de la cotisation, ou l'année
+    pub entity_id: i64,
+    /// Objet JSON
+    pub details:   Option<String>,
+}
+
+/// Filtres proposés sur la page Journal : (valeur `entity`, libellé).
+pub const ENTITY_FILTERS: [(&str, &str); 4] = [
+    ("",             "Rehetra"),
+    ("member",       "Mpikambana"),
+    ("contribution", "Latsakemboka"),
+    ("year",         "Taona"),
+];
+
+impl AuditEntry {
+    pub fn action_label(&self) -> &str {
+        match self.action.as_str() {
+            "create"   => "Nampiana",
+            "update"   => "Novaina",
+            "delete"   => "Nofafana",
+            "purge"    => "Nofafana tanteraka",
+            "restore"  => "Naverina",
+            "transfer" => "Nafindra",
+            "merge"    => "Natambatra",
+            "close"    => "Nakatona",
+            "reopen"   => "Nosokafana indray",
+            other      => other,
+        }
+    }
+
+    /// "Mpikambana #12", "Latsakemboka #40", "Taona 2024".
+    pub fn target_label(&self) -> String {
+        match self.entity.as_str() {
+            "member"       => format!("Mpikambana #{}", self.entity_id),
+            "contribution" => format!("Latsakemboka #{}", self.entity_id),
+            "year"         => format!("Taona {}", self.entity_id),
+            other          => format!("{other} #{}", self.entity_id),
+        }
+    }
+
+    /// Détails JSON à plat : "full_name : Rabe · card_number : C-0001".
+    /// Chaîne brute si le JSON est illisible.
+    pub fn details_summary(&self) -> Option<String> {
+        let raw = self.details.as_deref()?;
+        let Ok(serde_json::Value::Object(map)) = serde_json::from_str(raw) else {
+            return Some(raw.to_string());
+        };
+        let parts: Vec<String> = map
+            .iter()
+            .filter(|(_, v)| !v.is_null())
+            .map(|(k, v)| match v {
+                serde_json::Value::String(s) => format!("{k} : {s}"),
+                other => format!("{k} : {other}"),
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn libelles_et_details() {
+        let json = r#"{"id":3,"timestamp":"2025-03-14T08:00:00","action":"delete",
+            "entity":"contribution","entity_id":40,
+            "details":"{\"receipt_number\":\"2025-00012\",\"amount\":\"5000\",\"member_id\":7}"}"#;
+        let e: AuditEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(e.action_label(), "Nofafana");
+        assert_eq!(e.target_label(), "Latsakemboka #40");
+        assert_eq!(
+            e.details_summary().as_deref(),
+            Some("amount : 5000 · member_id : 7 · receipt_number : 2025-00012")
+        );
+
+        let year = AuditEntry {
+            action: "close".into(),
+            entity: "year".into(),
+            entity_id: 2024,
+            details: Some(r#"{"note":null}"#.into()),
+            ..e.clone()
+        };
+        assert_eq!(year.target_label(), "Taona 2024");
+        assert_eq!(year.details_summary(), None);
+        assert_eq!(AuditEntry { details: Some("???".into()), ..e }.details_summary().as_deref(), Some("???"));
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// Fichier de sauvegarde présent dans `app_data_dir/backups/`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupInfo {
+    pub file_name:  String,
+    pub size_bytes: u64,
+    /// Heure locale "YYYY-MM-DDTHH:MM:SS"
+    pub created_at: String,
+}
+
+/// Entrée du journal applicatif (sauvegardes, restaurations…).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppLog {
+    pub id:         i64,
+    /// "info" | "error"
+    pub level:      String,
+    pub source:     String,
+    pub message:    String,
+    pub created_at: String,
+}
+
+/// Mode d'import d'une archive JSON (`import_archive`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Remplace toute la base par l'archive.
+    Replace,
+    /// Ajoute à la base les membres et cotisations absents.
+    Merge,
+}
+
+/// Bilan d'un import d'archive JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveImportReport {
+    pub members_inserted:       usize,
+    pub members_skipped:        usize,
+    pub contributions_inserted: usize,
+    pub contributions_skipped:  usize,
+}
+
+impl ArchiveImportReport {
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "{} mpikambana, {} fandoavana nampidirina",
+            self.members_inserted, self.contributions_inserted,
+        );
+        let skipped = self.members_skipped + self.contributions_skipped;
+        if skipped > 0 {
+            text.push_str(&format!(" ({skipped} efa teo na tsy nampidirina)"));
+        }
+        text
+    }
+}
+
+/// Bilan de `seed_demo_data` (données de démonstration).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SeedReport {
+    pub members_inserted:       usize,
+    pub contributions_inserted: usize,
+    /// Decimal sérialisé en chaîne
+    pub total:                  String,
+    pub years:                  Vec<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bilan_import_archive() {
+        let report = ArchiveImportReport { members_inserted: 2, contributions_inserted: 5, ..Default::default() };
+        assert_eq!(report.summary(), "2 mpikambana, 5 fandoavana nampidirina");
+        let merged = ArchiveImportReport { members_skipped: 1, contributions_skipped: 2, ..report };
+        assert!(merged.summary().ends_with("(3 efa teo na tsy nampidirina)"));
+        assert_eq!(serde_json::to_string(&ImportMode::Merge).unwrap(), "\"merge\"");
+    }
+}
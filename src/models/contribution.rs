@@ -9,11 +9,16 @@ pub struct Contribution {
     pub member_id:     i64,
     /// "YYYY-MM-DD"
     pub payment_date:  String,
-    /// Période libre : "2024-01", "T1-2025", etc.
+    /// "2025", ou "2025-T1".."2025-T4" si les trimestres sont activés
     pub period:        String,
     /// Decimal sérialisé en chaîne, ex. "15000.50"
     pub amount:        String,
     pub recorded_year: i32,
+    /// Numéro de reçu "2025-00123", fixé à la création
+    pub receipt_number: String,
+    /// Opérateur qui a saisi la cotisation ; `None` pour les anciennes lignes
+    #[serde(default)]
+    pub recorded_by:   Option<String>,
 }
 
 /// Données saisies pour enregistrer une cotisation.
@@ -26,6 +31,12 @@ pub struct ContributionInput {
     pub period:       String,
     /// "15000.50"
     pub amount:       String,
+    /// Autorise une période d'une autre année que `payment_date` (arriérés, avances).
+    #[serde(default)]
+    pub allow_mismatch: bool,
+    /// Opérateur de saisie ; `None` si non renseigné.
+    #[serde(default)]
+    pub recorded_by:  Option<String>,
 }
 
 /// Cotisation enrichie du nom du membre (JOIN SQL côté backend).
@@ -40,10 +51,70 @@ pub struct ContributionWithMember {
     /// Decimal sérialisé en chaîne, ex. "15000.50"
     pub amount:        String,
     pub recorded_year: i32,
+    pub receipt_number: String,
+    #[serde(default)]
+    pub recorded_by:   Option<String>,
     /// Résumé des modifications d'audit, ex. "10000 Ar → 5000 Ar · 2024 → 2025"
     pub audit_summary: Option<String>,
 }
 
+/// Champs sur lesquels porte la recherche des Archives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContributionSearchScope {
+    #[default]
+    All,
+    /// Nom du membre ou numéro de reçu
+    Member,
+    /// Période ou date de paiement
+    Period,
+    /// Partie entière du montant, par préfixe (`utils::amount::amount_matches`)
+    Amount,
+}
+
+impl ContributionSearchScope {
+    pub const ALL: [Self; 4] = [Self::All, Self::Member, Self::Period, Self::Amount];
+
+    /// Valeur de `<select>`, identique au JSON envoyé au backend.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All    => "all",
+            Self::Member => "member",
+            Self::Period => "period",
+            Self::Amount => "amount",
+        }
+    }
+
+    /// Inverse de `as_str` ; toute autre valeur : `All`.
+    pub fn from_value(s: &str) -> Self {
+        Self::ALL.into_iter().find(|v| v.as_str() == s).unwrap_or_default()
+    }
+
+    /// Clé `i18n::tr` du libellé.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::All    => "filter.all",
+            Self::Member => "table.member",
+            Self::Period => "table.period",
+            Self::Amount => "table.amount",
+        }
+    }
+
+    /// La recherche porte (aussi) sur le montant.
+    pub fn includes_amount(self) -> bool {
+        matches!(self, Self::All | Self::Amount)
+    }
+}
+
+/// Sous-total annuel des cotisations d'un membre (`get_member_year_totals`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberYearTotal {
+    pub year:               i32,
+    /// Decimal sérialisé en chaîne
+    pub total:              String,
+    pub contribution_count: i64,
+}
+
 /// Données pour modifier une cotisation (avec PIN et motif).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ContributionEditInput {
@@ -52,4 +123,20 @@ pub struct ContributionEditInput {
     pub amount:       String,
     pub pin:          String,
     pub reason:       String,
+    #[serde(default)]
+    pub allow_mismatch: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portee_recherche_valeur_select() {
+        for scope in ContributionSearchScope::ALL {
+            assert_eq!(ContributionSearchScope::from_value(scope.as_str()), scope);
+            assert_eq!(serde_json::to_string(&scope).unwrap(), format!("\"{}\"", scope.as_str()));
+        }
+        assert_eq!(ContributionSearchScope::from_value("?"), ContributionSearchScope::All);
+    }
 }
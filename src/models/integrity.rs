@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// Anomalie relevée par `run_integrity_check` (miroir de l'enum backend, tag `kind`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntegrityIssue {
+    Sqlite { message: String },
+    OrphanContribution { contribution_id: i64, member_id: i64 },
+    RecordedYearMismatch { contribution_id: i64, recorded_year: i32, payment_year: i32 },
+    YearTotalMismatch {
+        year:     i32,
+        /// Decimal sérialisé en chaîne
+        stored:   String,
+        computed: String,
+    },
+}
+
+impl IntegrityIssue {
+    /// Corrigé par `repair_integrity` ; les autres demandent une intervention manuelle.
+    pub fn is_repairable(&self) -> bool {
+        matches!(self, Self::RecordedYearMismatch { .. } | Self::YearTotalMismatch { .. })
+    }
+
+    /// Description affichée dans la bannière de l'accueil.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Sqlite { message } => format!("Rakitra SQLite simba : {message}"),
+            Self::OrphanContribution { contribution_id, member_id } => format!(
+                "Rakitra #{contribution_id} : tsy hita ny mpikambana #{member_id}"
+            ),
+            Self::RecordedYearMismatch { contribution_id, recorded_year, payment_year } => format!(
+                "Rakitra #{contribution_id} : taona {recorded_year} fa naloa tamin'ny {payment_year}"
+            ),
+            Self::YearTotalMismatch { year, stored, computed } => format!(
+                "Totaly {year} : {stored} voatahiry, {computed} no marina"
+            ),
+        }
+    }
+}
+
+/// Résultat du contrôle d'intégrité ; `issues` vide = base saine.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntegrityReport {
+    pub checked_at: String,
+    pub issues:     Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub fn has_repairable(&self) -> bool {
+        self.issues.iter().any(IntegrityIssue::is_repairable)
+    }
+}
+
+/// Nombre de lignes d'une table (`DbHealth`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows:  i64,
+}
+
+/// Résultat de `get_db_health` : panneau « À propos de la base » des Paramètres.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DbHealth {
+    /// Dernière migration appliquée ; 0 hors Tauri (base de démonstration)
+    pub schema_version: i64,
+    pub tables:         Vec<TableRowCount>,
+    /// Fichier principal, hors journal WAL
+    pub size_bytes:     u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapport_backend_deserialise() {
+        let json = r#"{"checked_at":"2025-01-02T08:00:00","issues":[
+            {"kind":"orphan_contribution","contribution_id":7,"member_id":99},
+            {"kind":"year_total_mismatch","year":2024,"stored":"999","computed":"5100"}]}"#;
+        let report: IntegrityReport = serde_json::from_str(json).unwrap();
+        assert_eq!(report.issues.len(), 2);
+        assert!(!report.issues[0].is_repairable());
+        assert!(report.has_repairable());
+        assert!(report.issues[1].describe().contains("2024"));
+    }
+}
@@ -1,5 +1,126 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::utils::money::parse_decimal;
+
+/// Genre — sérialisé tel quel ("M" | "F"), comme `db::models::Gender` côté backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub enum Gender {
+    #[default]
+    M,
+    F,
+}
+
+impl Gender {
+    /// Valeur brute (attribut `value` des `<option>`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gender::M => "M",
+            Gender::F => "F",
+        }
+    }
+
+    /// Inverse de `as_str` — `None` pour toute autre valeur de `<select>`.
+    pub fn from_value(s: &str) -> Option<Self> {
+        match s {
+            "M" => Some(Gender::M),
+            "F" => Some(Gender::F),
+            _   => None,
+        }
+    }
+
+    /// Libellé court affiché dans le tableau.
+    pub fn label(self) -> &'static str {
+        match self {
+            Gender::M => "Lahy",
+            Gender::F => "Vavy",
+        }
+    }
+}
+
+/// Type de membre — sérialisé tel quel ("Communiant" | "Cathekomen").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MemberType {
+    #[default]
+    Communiant,
+    Cathekomen,
+}
+
+impl MemberType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemberType::Communiant => "Communiant",
+            MemberType::Cathekomen => "Cathekomen",
+        }
+    }
+
+    /// Libellé malgache ("Mpandray" | "Tsy Mpandray").
+    pub fn label(self) -> &'static str {
+        match self {
+            MemberType::Communiant => "Mpandray",
+            MemberType::Cathekomen => "Tsy Mpandray",
+        }
+    }
+
+    /// Page de la liste de ce type.
+    pub fn list_path(self) -> &'static str {
+        match self {
+            MemberType::Communiant => "/communiants",
+            MemberType::Cathekomen => "/cathekomens",
+        }
+    }
+
+    /// Version imprimable de la liste (`/imprimer/communiants`…).
+    pub fn print_path(self) -> String {
+        format!("/imprimer{}", self.list_path())
+    }
+
+    /// Type désigné par le segment d'URL de `list_path` ("communiants" | "cathekomens").
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        [MemberType::Communiant, MemberType::Cathekomen]
+            .into_iter()
+            .find(|t| t.list_path().trim_start_matches('/') == slug)
+    }
+}
+
+/// Statut du membre, indépendant du type ("Actif" | "Inactif" | "Décédé").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MemberStatus {
+    #[default]
+    Actif,
+    Inactif,
+    #[serde(rename = "Décédé")]
+    Decede,
+}
+
+impl MemberStatus {
+    pub const ALL: [MemberStatus; 3] = [MemberStatus::Actif, MemberStatus::Inactif, MemberStatus::Decede];
+
+    /// Valeur brute (attribut `value` des `<option>`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemberStatus::Actif   => "Actif",
+            MemberStatus::Inactif => "Inactif",
+            MemberStatus::Decede  => "Décédé",
+        }
+    }
+
+    /// Inverse de `as_str` — `None` pour toute autre valeur de `<select>`.
+    pub fn from_value(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|st| st.as_str() == s)
+    }
+
+    /// Libellé malgache.
+    pub fn label(self) -> &'static str {
+        match self {
+            MemberStatus::Actif   => "Mavitrika",
+            MemberStatus::Inactif => "Tsy mavitrika",
+            MemberStatus::Decede  => "Nodimandry",
+        }
+    }
+}
+
 /// Membre de l'église — miroir du modèle backend Tauri.
 /// `amount` et `total` sont des chaînes : le backend sérialise `Decimal` en string.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -10,11 +131,23 @@ pub struct Member {
     pub address:     Option<String>,
     pub phone:       Option<String>,
     pub job:         Option<String>,
-    /// "M" | "F"
-    pub gender:      String,
-    /// "Communiant" | "Cathekomen"
-    pub member_type: String,
+    pub gender:      Gender,
+    pub member_type: MemberType,
+    /// Nom de famille / prénom(s) ; `None` si non déduits du nom complet
+    #[serde(default)]
+    pub surname:     Option<String>,
+    #[serde(default)]
+    pub given_name:  Option<String>,
+    /// Absent des anciens serveurs distants : `Actif`
+    #[serde(default)]
+    pub status:      MemberStatus,
+    /// "YYYY-MM-DD" ; absent des anciens serveurs distants
+    #[serde(default)]
+    pub birth_date:  Option<String>,
     pub created_at:  String,
+    /// Dernier changement de type, UTC ; absent des anciens serveurs distants
+    #[serde(default)]
+    pub transferred_at: Option<String>,
 }
 
 /// Membre avec total des contributions (retourné par `get_members_by_type_with_total`).
@@ -26,10 +159,215 @@ pub struct MemberWithTotal {
     pub address:             Option<String>,
     pub phone:               Option<String>,
     pub job:                 Option<String>,
-    pub gender:              String,
-    pub member_type:         String,
+    pub gender:              Gender,
+    pub member_type:         MemberType,
+    /// Clé de tri « Nom » quand il est connu (`sort_name`)
+    #[serde(default)]
+    pub surname:             Option<String>,
+    #[serde(default)]
+    pub given_name:          Option<String>,
+    #[serde(default)]
+    pub status:              MemberStatus,
+    #[serde(default)]
+    pub birth_date:          Option<String>,
     pub created_at:          String,
+    /// Décimal exact en texte ("15000.5") : `parse_decimal` / `format_ariary_str`
     pub total_contributions: String,
+    /// "0" si aucune cotisation enregistrée cette année
+    #[serde(default)]
+    pub current_year_total:  String,
+    /// Total de l'année précédente : vue « par année » seulement (`MemberYearTotals`)
+    #[serde(default)]
+    pub previous_year_total: Option<String>,
+}
+
+impl MemberWithTotal {
+    /// Clé du tri par nom, comme le backend : "RAKOTO Jean" pour "Jean RAKOTO" quand
+    /// le nom de famille est connu, sinon le nom complet tel que saisi.
+    pub fn sort_name(&self) -> String {
+        match &self.surname {
+            Some(s) => format!("{s} {}", self.given_name.as_deref().unwrap_or("")),
+            None    => self.full_name.clone(),
+        }
+    }
+
+    /// Ligne provisoire affichée pendant la création (`utils::optimistic`).
+    pub fn provisional(id: i64, input: &MemberInput) -> Self {
+        Self {
+            id,
+            card_number:         input.card_number.clone(),
+            full_name:           input.full_name.clone(),
+            address:             input.address.clone(),
+            phone:               input.phone.clone(),
+            job:                 input.job.clone(),
+            gender:              input.gender,
+            member_type:         input.member_type,
+            surname:             input.surname.clone(),
+            given_name:          input.given_name.clone(),
+            status:              input.status,
+            birth_date:          input.birth_date.clone(),
+            created_at:          String::new(),
+            total_contributions: "0".into(),
+            current_year_total:  "0".into(),
+            previous_year_total: None,
+        }
+    }
+}
+
+/// Membre tout juste créé : aucune cotisation.
+impl From<Member> for MemberWithTotal {
+    fn from(m: Member) -> Self {
+        Self {
+            id:                  m.id,
+            card_number:         m.card_number,
+            full_name:           m.full_name,
+            address:             m.address,
+            phone:               m.phone,
+            job:                 m.job,
+            gender:              m.gender,
+            member_type:         m.member_type,
+            surname:             m.surname,
+            given_name:          m.given_name,
+            status:              m.status,
+            birth_date:          m.birth_date,
+            created_at:          m.created_at,
+            total_contributions: "0".into(),
+            current_year_total:  "0".into(),
+            previous_year_total: None,
+        }
+    }
+}
+
+/// Membre et ses totaux pour quelques années (retourné par `get_members_with_year_totals`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberYearTotals {
+    pub member:      MemberWithTotal,
+    /// Année → total exact en texte ; une année sans cotisation est absente
+    pub year_totals: BTreeMap<i32, String>,
+}
+
+impl MemberYearTotals {
+    /// Membre des colonnes « par année » : `year` en total courant, `year - 1` en
+    /// total précédent ("0" si rien versé).
+    pub fn into_year_view(self, year: i32) -> MemberWithTotal {
+        let total = |y| self.year_totals.get(&y).cloned().unwrap_or_else(|| "0".into());
+        MemberWithTotal {
+            current_year_total:  total(year),
+            previous_year_total: Some(total(year - 1)),
+            ..self.member
+        }
+    }
+}
+
+/// Ligne allégée des tableaux (retournée par `get_member_rows`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberRow {
+    pub id:                  i64,
+    pub card_number:         String,
+    pub full_name:           String,
+    pub address:             Option<String>,
+    pub phone:               Option<String>,
+    pub job:                 Option<String>,
+    pub gender:              Gender,
+    #[serde(default)]
+    pub status:              MemberStatus,
+    /// Reprise par le formulaire de modification
+    #[serde(default)]
+    pub birth_date:          Option<String>,
+    /// Décimal exact en texte ; "0" si aucune cotisation enregistrée cette année
+    pub current_year_total:  String,
+    /// Colonne de l'année précédente (vue « par année »)
+    #[serde(default)]
+    pub previous_year_total: Option<String>,
+}
+
+impl MemberRow {
+    /// Rien versé pendant l'année en cours (les diacres relancent ces membres).
+    pub fn unpaid_this_year(&self) -> bool {
+        !self.current_year_total.is_empty() && parse_decimal(&self.current_year_total).is_zero()
+    }
+}
+
+/// Mode client (petites listes) : même tableau que le mode paginé côté serveur.
+impl From<MemberWithTotal> for MemberRow {
+    fn from(m: MemberWithTotal) -> Self {
+        Self {
+            id:                  m.id,
+            card_number:         m.card_number,
+            full_name:           m.full_name,
+            address:             m.address,
+            phone:               m.phone,
+            job:                 m.job,
+            gender:              m.gender,
+            status:              m.status,
+            birth_date:          m.birth_date,
+            current_year_total:  m.current_year_total,
+            previous_year_total: m.previous_year_total,
+        }
+    }
+}
+
+/// Membres d'un même quartier (retourné par `get_members_grouped_by_address`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddressGroup {
+    /// Adresse normalisée (`member_index::address_key`) ; `None` : sans adresse
+    pub address: Option<String>,
+    pub members: Vec<MemberWithTotal>,
+}
+
+/// Colonne de tri de `get_member_rows`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSortCol {
+    CardNumber,
+    #[default]
+    FullName,
+    Address,
+    Phone,
+    Job,
+    Gender,
+    CurrentYearTotal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MemberSort {
+    pub col:  MemberSortCol,
+    pub desc: bool,
+}
+
+/// Filtres de `get_member_rows`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MemberFilter {
+    pub search:      String,
+    pub gender:      Option<Gender>,
+    /// `None` : tous les statuts
+    pub status:      Option<MemberStatus>,
+    /// Seulement les membres sans cotisation cette année
+    pub unpaid_only: bool,
+}
+
+/// Membre réduit pour un sélecteur (retourné par `search_members_lite`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberLite {
+    pub id:          i64,
+    pub full_name:   String,
+    pub card_number: String,
+}
+
+/// Effectifs par type (retournés par `get_member_counts`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct MemberCounts {
+    pub communiants: i64,
+    pub cathekomens: i64,
+}
+
+impl MemberCounts {
+    pub fn of(self, member_type: MemberType) -> i64 {
+        match member_type {
+            MemberType::Communiant => self.communiants,
+            MemberType::Cathekomen => self.cathekomens,
+        }
+    }
 }
 
 /// Données saisies pour créer ou modifier un membre.
@@ -40,6 +378,298 @@ pub struct MemberInput {
     pub address:     Option<String>,
     pub phone:       Option<String>,
     pub job:         Option<String>,
-    pub gender:      String,
-    pub member_type: String,
+    pub gender:      Gender,
+    pub member_type: MemberType,
+    /// Parties saisies séparément ; le backend les déduit de `full_name` si absentes
+    pub surname:     Option<String>,
+    pub given_name:  Option<String>,
+    pub status:      MemberStatus,
+    /// "YYYY-MM-DD" (valeur d'un `<input type="date">`)
+    pub birth_date:  Option<String>,
+}
+
+/// Anniversaire à venir (retourné par `get_upcoming_birthdays`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpcomingBirthday {
+    pub member_id:   i64,
+    pub full_name:   String,
+    pub member_type: MemberType,
+    pub birth_date:  String,
+    /// Date de l'anniversaire ("YYYY-MM-DD")
+    pub date:        String,
+    pub days_until:  i64,
+    /// Âge atteint ce jour-là
+    pub age:         i32,
+}
+
+impl UpcomingBirthday {
+    /// "Androany", "Rahampitso" ou "Afaka 5 andro".
+    pub fn when_label(&self) -> String {
+        days_until_label(self.days_until)
+    }
+
+    /// "2026-01-02" → "02/01".
+    pub fn day_month(&self) -> String {
+        match (self.date.get(5..7), self.date.get(8..10)) {
+            (Some(m), Some(d)) => format!("{d}/{m}"),
+            _ => self.date.clone(),
+        }
+    }
+}
+
+fn days_until_label(days: i64) -> String {
+    match days {
+        0 => "Androany".into(),
+        1 => "Rahampitso".into(),
+        n => format!("Afaka {n} andro"),
+    }
+}
+
+/// Carte à renouveler, à la date anniversaire de l'inscription (`get_upcoming_renewals`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenewalDue {
+    pub member_id:     i64,
+    pub full_name:     String,
+    pub card_number:   String,
+    pub member_type:   MemberType,
+    /// Date d'inscription ("YYYY-MM-DD")
+    pub registered_on: String,
+    /// Échéance ("YYYY-MM-DD")
+    pub date:          String,
+    pub days_until:    i64,
+    /// Années d'inscription atteintes ce jour-là
+    pub years:         i32,
+}
+
+impl RenewalDue {
+    /// "Androany", "Rahampitso" ou "Afaka 5 andro".
+    pub fn when_label(&self) -> String {
+        days_until_label(self.days_until)
+    }
+
+    /// "2026-01-02" → "02/01/2026".
+    pub fn due_on(&self) -> String {
+        match (self.date.get(0..4), self.date.get(5..7), self.date.get(8..10)) {
+            (Some(y), Some(m), Some(d)) => format!("{d}/{m}/{y}"),
+            _ => self.date.clone(),
+        }
+    }
+}
+
+/// Membre à la corbeille (retourné par `get_deleted_members`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeletedMember {
+    pub id:                 i64,
+    pub card_number:        String,
+    pub full_name:          String,
+    pub member_type:        MemberType,
+    /// "YYYY-MM-DDTHH:MM:SS" (UTC)
+    pub deleted_at:         String,
+    /// Cotisations conservées, effacées par une purge
+    pub contribution_count: i64,
+}
+
+impl DeletedMember {
+    /// "2025-03-07T10:15:00" → "07/03/2025".
+    pub fn deleted_on(&self) -> String {
+        match (self.deleted_at.get(0..4), self.deleted_at.get(5..7), self.deleted_at.get(8..10)) {
+            (Some(y), Some(m), Some(d)) => format!("{d}/{m}/{y}"),
+            _ => self.deleted_at.clone(),
+        }
+    }
+}
+
+/// Note libre sur un membre (`get_member_notes`, la plus récente d'abord).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberNote {
+    pub id:         i64,
+    pub member_id:  i64,
+    /// "YYYY-MM-DDTHH:MM:SS" (UTC)
+    pub created_at: String,
+    pub text:       String,
+}
+
+/// Ligne du CSV écartée à l'import (numéro de ligne dans le fichier, 1 = en-tête).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportRowError {
+    pub line:    usize,
+    pub message: String,
+}
+
+/// Bilan d'un import CSV (retourné par `import_members_csv`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ImportReport {
+    pub inserted: usize,
+    pub skipped:  usize,
+    pub errors:   Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    /// "12 mpikambana nampidirina, andalana 3 tsy nampidirina".
+    pub fn summary(&self) -> String {
+        match self.skipped {
+            0 => format!("{} mpikambana nampidirina", self.inserted),
+            n => format!("{} mpikambana nampidirina, andalana {n} tsy nampidirina", self.inserted),
+        }
+    }
+}
+
+/// Téléphone non reconnu, laissé tel quel par `normalize_all_phones`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InvalidPhone {
+    pub member_id:   i64,
+    pub card_number: String,
+    pub phone:       String,
+}
+
+/// Bilan de la remise en forme des téléphones (retourné par `normalize_all_phones`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PhoneFixReport {
+    pub updated: usize,
+    pub invalid: Vec<InvalidPhone>,
+}
+
+impl PhoneFixReport {
+    /// "3 laharana nahitsy, 1 tsy fantatra".
+    pub fn summary(&self) -> String {
+        match self.invalid.len() {
+            0 => format!("{} laharana nahitsy", self.updated),
+            n => format!("{} laharana nahitsy, {n} tsy fantatra", self.updated),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enums_serde_aller_retour() {
+        for g in [Gender::M, Gender::F] {
+            let json = serde_json::to_string(&g).unwrap();
+            assert_eq!(json, format!("\"{}\"", g.as_str()));
+            assert_eq!(serde_json::from_str::<Gender>(&json).unwrap(), g);
+            assert_eq!(Gender::from_value(g.as_str()), Some(g));
+        }
+        for t in [MemberType::Communiant, MemberType::Cathekomen] {
+            let json = serde_json::to_string(&t).unwrap();
+            assert_eq!(json, format!("\"{}\"", t.as_str()));
+            assert_eq!(serde_json::from_str::<MemberType>(&json).unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn chemins_impression() {
+        assert_eq!(MemberType::Cathekomen.print_path(), "/imprimer/cathekomens");
+        assert_eq!(MemberType::from_slug("communiants"), Some(MemberType::Communiant));
+        assert_eq!(MemberType::from_slug("cathekomens"), Some(MemberType::Cathekomen));
+        assert_eq!(MemberType::from_slug("Communiant"), None);
+    }
+
+    #[test]
+    fn valeur_inconnue_rejetee() {
+        assert!(serde_json::from_str::<Gender>("\"X\"").is_err());
+        assert!(serde_json::from_str::<MemberType>("\"communiant\"").is_err());
+        assert_eq!(Gender::from_value("Rehetra"), None);
+        assert!(serde_json::from_str::<MemberStatus>("\"Decede\"").is_err());
+    }
+
+    #[test]
+    fn statut_format_backend() {
+        assert_eq!(serde_json::to_string(&MemberStatus::Decede).unwrap(), "\"Décédé\"");
+        assert_eq!(MemberStatus::from_value("Inactif"), Some(MemberStatus::Inactif));
+        assert_eq!(MemberStatus::from_value("Rehetra"), None);
+        // Ancien serveur distant sans statut : actif
+        let json = r#"{"id":1,"card_number":"C001","full_name":"Alice","address":null,
+            "phone":null,"job":null,"gender":"F","member_type":"Communiant",
+            "created_at":"2024-01-01"}"#;
+        assert_eq!(serde_json::from_str::<Member>(json).unwrap().status, MemberStatus::Actif);
+    }
+
+    #[test]
+    fn sans_cotisation_annee() {
+        let json = r#"{"id":1,"card_number":"C001","full_name":"Alice","address":null,
+            "phone":null,"job":null,"gender":"F","member_type":"Communiant",
+            "created_at":"2024-01-01","total_contributions":"5000"}"#;
+        let m: MemberWithTotal = serde_json::from_str(json).unwrap();
+        // Champ absent (ancien serveur distant) : pas de fausse alerte
+        let mut row = MemberRow::from(m);
+        assert!(!row.unpaid_this_year());
+        row.current_year_total = "0".into();
+        assert!(row.unpaid_this_year());
+        row.current_year_total = "0.00".into();
+        assert!(row.unpaid_this_year());
+        row.current_year_total = "0.5".into();
+        assert!(!row.unpaid_this_year());
+    }
+
+    #[test]
+    fn requete_lignes_format_backend() {
+        let sort = MemberSort { col: MemberSortCol::CurrentYearTotal, desc: true };
+        assert_eq!(
+            serde_json::to_string(&sort).unwrap(),
+            r#"{"col":"current_year_total","desc":true}"#
+        );
+        let filter = MemberFilter { gender: Some(Gender::F), ..Default::default() };
+        assert_eq!(
+            serde_json::to_string(&filter).unwrap(),
+            r#"{"search":"","gender":"F","status":null,"unpaid_only":false}"#
+        );
+    }
+
+    #[test]
+    fn anniversaire_libelles() {
+        let json = r#"{"member_id":3,"full_name":"Koto","member_type":"Communiant",
+            "birth_date":"1975-12-30","date":"2025-12-30","days_until":2,"age":50}"#;
+        let mut b: UpcomingBirthday = serde_json::from_str(json).unwrap();
+        assert_eq!(b.when_label(), "Afaka 2 andro");
+        assert_eq!(b.day_month(), "30/12");
+        b.days_until = 0;
+        assert_eq!(b.when_label(), "Androany");
+        b.days_until = 1;
+        assert_eq!(b.when_label(), "Rahampitso");
+    }
+
+    #[test]
+    fn renouvellement_libelles() {
+        let json = r#"{"member_id":2,"full_name":"Bako","card_number":"C-0002","member_type":"Communiant",
+            "registered_on":"2024-02-29","date":"2026-02-28","days_until":1,"years":2}"#;
+        let mut r: RenewalDue = serde_json::from_str(json).unwrap();
+        assert_eq!(r.when_label(), "Rahampitso");
+        assert_eq!(r.due_on(), "28/02/2026");
+        r.days_until = 12;
+        assert_eq!(r.when_label(), "Afaka 12 andro");
+    }
+
+    #[test]
+    fn bilan_import() {
+        let json = r#"{"inserted":12,"skipped":2,"errors":[
+            {"line":4,"message":"Le nom complet est requis."},
+            {"line":9,"message":"Numéro de carte 'C001' déjà attribué à un membre existant."}]}"#;
+        let r: ImportReport = serde_json::from_str(json).unwrap();
+        assert_eq!(r.errors[1].line, 9);
+        assert_eq!(r.summary(), "12 mpikambana nampidirina, andalana 2 tsy nampidirina");
+        let ok = ImportReport { inserted: 5, ..Default::default() };
+        assert_eq!(ok.summary(), "5 mpikambana nampidirina");
+    }
+
+    #[test]
+    fn bilan_telephones() {
+        let json = r#"{"updated":3,"invalid":[
+            {"member_id":7,"card_number":"C007","phone":"tsy misy"}]}"#;
+        let r: PhoneFixReport = serde_json::from_str(json).unwrap();
+        assert_eq!(r.invalid[0].card_number, "C007");
+        assert_eq!(r.summary(), "3 laharana nahitsy, 1 tsy fantatra");
+        assert_eq!(PhoneFixReport::default().summary(), "0 laharana nahitsy");
+    }
+
+    #[test]
+    fn corbeille_date() {
+        let json = r#"{"id":4,"card_number":"C004","full_name":"Koto","member_type":"Cathekomen",
+            "deleted_at":"2025-03-07T10:15:00","contribution_count":3}"#;
+        let mut d: DeletedMember = serde_json::from_str(json).unwrap();
+        assert_eq!(d.deleted_on(), "07/03/2025");
+        d.deleted_at = "?".into();
+        assert_eq!(d.deleted_on(), "?");
+    }
 }
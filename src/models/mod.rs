@@ -1,3 +1,10 @@
+pub mod audit;
+pub mod backup;
 pub mod contribution;
+pub mod integrity;
 pub mod member;
+pub mod page;
+pub mod settings;
+pub mod statistics;
+pub mod task;
 pub mod year_summary;
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Page de résultats retournée par les commandes paginées du backend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Page<T> {
+    pub items:       Vec<T>,
+    /// Nombre total de lignes (toutes pages confondues)
+    pub total_count: i64,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Page { items: Vec::new(), total_count: 0 }
+    }
+}
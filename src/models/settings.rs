@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Paramètres de l'église (table `settings` côté backend).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Settings {
+    pub church_name:      String,
+    pub church_address:   String,
+    /// Trésorier, imprimé sous la signature des reçus.
+    #[serde(default)]
+    pub treasurer_name:   String,
+    /// Libellé de la monnaie sur les reçus, ex. "Ariary".
+    #[serde(default)]
+    pub currency_label:   String,
+    /// Decimal sérialisé en chaîne, ex. "12000"
+    pub default_pledge:   String,
+    pub backup_retention: usize,
+    /// Périodes trimestrielles "2025-T1".."2025-T4" acceptées en plus de l'année.
+    pub quarter_periods:  bool,
+    /// Contrôle d'intégrité de la base au démarrage.
+    pub startup_integrity_check: bool,
+    /// Clôture automatique de l'année précédente après le délai de grâce.
+    pub auto_close_enabled: bool,
+    /// Jours après le début de l'exercice avant la clôture automatique.
+    pub auto_close_grace_days: u32,
+    /// Jours d'avance admis sur une date de paiement.
+    pub max_future_days:  u32,
+    /// Fenêtre (jours) des renouvellements de carte affichés à l'accueil.
+    pub renewal_window_days: u32,
+    /// Premier mois de l'exercice : 1 = année civile, 7 = juillet → juin.
+    pub fiscal_year_start_month: u32,
+    /// Période préremplie dans `ContributionModal` ; vide = année de la date.
+    pub default_period:   String,
+    /// Montants rapides (Decimal en chaîne), proposés sous le champ montant.
+    pub quick_amounts:    Vec<String>,
+    /// Modèles des numéros de carte générés, ex. "C-%04d".
+    pub card_pattern_communiant: String,
+    pub card_pattern_cathekomen: String,
+    /// Opérateurs proposés dans `ContributionModal` ; vide = saisie libre.
+    pub operators:        Vec<String>,
+}
+
+/// Exercice du mois `month` (1..=12) de `year`, désigné par son année de début :
+/// avec un exercice juillet → juin, mars 2025 relève de 2024. Même règle que
+/// `fiscal_year_of` côté backend ; `start_month` hors de 2..=12 : année civile.
+pub fn fiscal_year_of(year: i32, month: u32, start_month: u32) -> i32 {
+    if (2..=12).contains(&start_month) && month < start_month {
+        year - 1
+    } else {
+        year
+    }
+}
+
+/// Libellé d'un exercice : "2024" en année civile, "2024–2025" sinon.
+pub fn fiscal_year_label(year: i32, start_month: u32) -> String {
+    if (2..=12).contains(&start_month) {
+        format!("{year}\u{2013}{}", year + 1)
+    } else {
+        year.to_string()
+    }
+}
+
+/// Exercice en cours selon l'horloge du navigateur.
+pub fn current_fiscal_year(start_month: u32) -> i32 {
+    let d = js_sys::Date::new_0();
+    fiscal_year_of(d.get_full_year() as i32, d.get_month() + 1, start_month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exercice_et_libelle() {
+        assert_eq!(fiscal_year_of(2025, 1, 1), 2025);
+        assert_eq!(fiscal_year_of(2025, 12, 0), 2025);
+        assert_eq!(fiscal_year_of(2025, 6, 7), 2024);
+        assert_eq!(fiscal_year_of(2025, 7, 7), 2025);
+        assert_eq!(fiscal_year_label(2024, 1), "2024");
+        assert_eq!(fiscal_year_label(2024, 7), "2024\u{2013}2025");
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// Nombre de membres pour un couple (type, genre).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GenderCount {
+    /// "Communiant" | "Cathekomen"
+    pub member_type: String,
+    /// "M" | "F"
+    pub gender:      String,
+    pub count:       i64,
+}
+
+/// Nouveaux membres inscrits une année donnée.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearCount {
+    pub year:  i32,
+    pub count: i64,
+}
+
+/// Total des cotisations d'une année.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearTotal {
+    pub year:  i32,
+    /// Decimal sérialisé en chaîne
+    pub total: String,
+}
+
+/// Séries pré-agrégées retournées par `get_statistics`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Statistics {
+    pub gender_by_type:      Vec<GenderCount>,
+    pub new_members_by_year: Vec<YearCount>,
+    pub totals_by_year:      Vec<YearTotal>,
+}
+
+/// Chiffres de la page d'accueil, calculés en une seule requête (`get_dashboard_stats`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DashboardStats {
+    pub year:                    i32,
+    pub communiants:             i64,
+    pub cathekomens:             i64,
+    pub men:                     i64,
+    pub women:                   i64,
+    /// Decimal sérialisé en chaîne
+    pub current_year_total:      String,
+    /// Decimal sérialisé en chaîne
+    pub previous_year_total:     String,
+    pub contributions_this_year: i64,
+    /// "YYYY-MM-DD" ; None si aucune cotisation
+    pub last_contribution_date:  Option<String>,
+}
+
+impl DashboardStats {
+    /// Date de la dernière cotisation en "JJ/MM/AAAA".
+    pub fn last_contribution_on(&self) -> Option<String> {
+        self.last_contribution_date.as_deref().map(|d| {
+            match (d.get(0..4), d.get(5..7), d.get(8..10)) {
+                (Some(y), Some(m), Some(day)) => format!("{day}/{m}/{y}"),
+                _ => d.to_string(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_contribution_on_formate_la_date() {
+        let mut stats = DashboardStats {
+            year:                    2025,
+            communiants:             0,
+            cathekomens:             0,
+            men:                     0,
+            women:                   0,
+            current_year_total:      "0".into(),
+            previous_year_total:     "0".into(),
+            contributions_this_year: 0,
+            last_contribution_date:  None,
+        };
+        assert_eq!(stats.last_contribution_on(), None);
+        stats.last_contribution_date = Some("2025-03-07".into());
+        assert_eq!(stats.last_contribution_on().as_deref(), Some("07/03/2025"));
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// Rappel renvoyé par `get_pending_tasks` (miroir de l'enum backend, tag `kind`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingTask {
+    UnclosedYear { year: i32, contributions: i64 },
+    IncompleteMembers { count: i64 },
+    FutureContributions { count: i64 },
+}
+
+impl PendingTask {
+    /// Texte affiché dans le menu de la cloche.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::UnclosedYear { year, contributions } => format!(
+                "Tsy mbola mikatona ny taona {year} ({contributions} fandoavana)"
+            ),
+            Self::IncompleteMembers { count } => format!(
+                "{count} mpikambana tsy misy finday na adiresy"
+            ),
+            Self::FutureContributions { count } => format!(
+                "{count} fandoavana misy daty mbola ho avy"
+            ),
+        }
+    }
+
+    /// Page où traiter la tâche.
+    pub fn href(&self) -> &'static str {
+        match self {
+            Self::UnclosedYear { .. } | Self::FutureContributions { .. } => "/archives",
+            Self::IncompleteMembers { .. } => "/communiants",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taches_backend_deserialisees() {
+        let json = r#"[{"kind":"unclosed_year","year":2024,"contributions":12},
+                       {"kind":"incomplete_members","count":3}]"#;
+        let tasks: Vec<PendingTask> = serde_json::from_str(json).unwrap();
+        assert_eq!(tasks[0], PendingTask::UnclosedYear { year: 2024, contributions: 12 });
+        assert!(tasks[0].describe().contains("2024"));
+        assert_eq!(tasks[1].href(), "/communiants");
+    }
+}
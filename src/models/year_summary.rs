@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    models::member::{Gender, MemberType},
+    utils::money::{format_ariary_str, MoneyFormat},
+};
+
 /// Résumé financier d'une année.
 /// `total` est recalculé automatiquement à chaque modification de contribution.
 /// `closed_at` est `None` quand l'année est encore ouverte.
@@ -7,8 +12,255 @@ use serde::{Deserialize, Serialize};
 pub struct YearSummary {
     pub year: i32,
     /// Decimal sérialisé en chaîne, ex. "1800000.00"
-    pub total:     String,
+    pub total:             String,
+    /// Sous-totaux par type actuel du membre (figés à la clôture)
+    #[serde(default)]
+    pub total_communiant:  String,
+    #[serde(default)]
+    pub total_cathekomen:  String,
+    /// Membres distincts ayant cotisé
+    #[serde(default)]
+    pub contributor_count:  i64,
+    /// Cotisations enregistrées dans l'année
+    #[serde(default)]
+    pub contribution_count: i64,
     /// ISO datetime de clôture, ex. "2025-01-10T14:30:00", ou None si ouvert
-    pub closed_at: Option<String>,
-    pub note:      Option<String>,
+    pub closed_at:          Option<String>,
+    pub note:               Option<String>,
+}
+
+impl YearSummary {
+    /// "Mpandray : 7 500 Ar · Tsy Mpandray : 1 000 Ar" ; `None` si le backend
+    /// (serveur distant plus ancien) n'a pas envoyé les sous-totaux.
+    pub fn breakdown(&self) -> Option<String> {
+        if self.total_communiant.is_empty() && self.total_cathekomen.is_empty() {
+            return None;
+        }
+        let fmt = |v: &str| format_ariary_str(v, MoneyFormat::default());
+        Some(format!(
+            "{} : {} · {} : {}",
+            MemberType::Communiant.label(),
+            fmt(&self.total_communiant),
+            MemberType::Cathekomen.label(),
+            fmt(&self.total_cathekomen),
+        ))
+    }
+
+    /// "12 rakitra · 5 mpikambana" ; `None` pour une année sans cotisation
+    /// (ou un serveur plus ancien qui ne compte pas les cotisations).
+    pub fn participation(&self) -> Option<String> {
+        (self.contribution_count > 0).then(|| {
+            format!("{} rakitra · {} mpikambana", self.contribution_count, self.contributor_count)
+        })
+    }
+}
+
+/// Résultat de la vérification de clôture de l'année précédente.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum YearCloseStatus {
+    AlreadyClosed,
+    /// Clôture effectuée (automatique ou demandée).
+    Closed { summary: YearSummary },
+    /// Délai de grâce de janvier en cours : rappel non destructif.
+    PendingUserAction {
+        year:          i32,
+        /// Decimal sérialisé en chaîne
+        total:         String,
+        /// "YYYY-MM-DD"
+        auto_close_on: String,
+    },
+    /// Clôture automatique désactivée dans les paramètres : année encore ouverte.
+    Disabled {
+        year:  i32,
+        /// Decimal sérialisé en chaîne
+        total: String,
+    },
+}
+
+/// Aperçu de la clôture d'une année (`preview_year_close`) : rien n'est encore écrit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearClosePreview {
+    pub year:           i32,
+    /// Decimal sérialisé en chaîne
+    pub total:          String,
+    /// Note générée, modifiable avant la clôture
+    pub default_note:   String,
+    pub already_closed: bool,
+}
+
+/// Ligne du comparatif annuel (total + sous-totaux par type de membre).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearComparison {
+    pub year:          i32,
+    /// Decimal sérialisé en chaîne
+    pub total:         String,
+    pub communiants:   String,
+    pub cathekomens:   String,
+    /// Variation vs l'année précédente en %, None si l'année précédente est à 0
+    pub variation_pct: Option<f64>,
+}
+
+/// Noms des mois en malgache, janvier en tête.
+pub const MONTH_NAMES: [&str; 12] = [
+    "Janoary", "Febroary", "Martsa", "Aprily", "Mey", "Jona",
+    "Jolay", "Aogositra", "Septambra", "Oktobra", "Novambra", "Desambra",
+];
+
+/// Total des cotisations payées un mois donné (12 entrées par année).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonthlyTotal {
+    /// 1 = janvier … 12 = décembre
+    pub month: u32,
+    pub count: i64,
+    /// Decimal sérialisé en chaîne
+    pub total: String,
+}
+
+/// Total d'un membre figé à la clôture (`get_year_member_totals`) : nom et carte
+/// du jour de la clôture, même si le membre a été renommé ou supprimé depuis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearMemberSnapshot {
+    pub year:        i32,
+    pub member_id:   i64,
+    pub member_name: String,
+    pub card_number: String,
+    /// Decimal sérialisé en chaîne
+    pub total:       String,
+}
+
+/// Nom malgache du mois `month` (1 = janvier), "?" hors de 1..=12.
+fn month_label(month: u32) -> &'static str {
+    month
+        .checked_sub(1)
+        .and_then(|i| MONTH_NAMES.get(i as usize))
+        .copied()
+        .unwrap_or("?")
+}
+
+impl MonthlyTotal {
+    pub fn label(&self) -> &'static str {
+        month_label(self.month)
+    }
+}
+
+/// Total des cotisations d'une semaine ISO (52 ou 53 entrées par année).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeekTotal {
+    /// 1 … 52 (ou 53)
+    pub week:  u32,
+    pub count: i64,
+    /// Decimal sérialisé en chaîne
+    pub total: String,
+}
+
+/// Un mois du comparatif de deux années.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonthComparison {
+    /// 1 = janvier … 12 = décembre
+    pub month:           u32,
+    /// Decimals sérialisés en chaîne
+    pub total_a:         String,
+    pub total_b:         String,
+    /// Cumul de l'année A moins celui de l'année B, de janvier à ce mois inclus
+    pub cumulative_diff: String,
+}
+
+impl MonthComparison {
+    pub fn label(&self) -> &'static str {
+        month_label(self.month)
+    }
+}
+
+/// Comparatif mois par mois de deux années (12 mois).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonthlyComparison {
+    pub year_a: i32,
+    pub year_b: i32,
+    pub months: Vec<MonthComparison>,
+}
+
+/// Cotisations d'un groupe (genre ou type de membre) sur une année.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BreakdownEntry {
+    /// "M" / "F", "Communiant" / "Cathekomen", ou "Inconnu" (membre disparu)
+    pub key:   String,
+    pub count: i64,
+    /// Decimal sérialisé en chaîne
+    pub total: String,
+}
+
+impl BreakdownEntry {
+    /// Libellé malgache du groupe.
+    pub fn label(&self) -> &'static str {
+        match self.key.as_str() {
+            "M"          => Gender::M.label(),
+            "F"          => Gender::F.label(),
+            "Communiant" => MemberType::Communiant.label(),
+            "Cathekomen" => MemberType::Cathekomen.label(),
+            _            => "Tsy fantatra",
+        }
+    }
+}
+
+/// Répartition des cotisations d'une année par genre et par type de membre.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearBreakdown {
+    pub year:      i32,
+    pub by_gender: Vec<BreakdownEntry>,
+    pub by_type:   Vec<BreakdownEntry>,
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repartition_par_type() {
+        let json = r#"{"year":2024,"total":"8500.50","total_communiant":"7500.50",
+            "total_cathekomen":"1000","contributor_count":2,"contribution_count":3,
+            "closed_at":null,"note":null}"#;
+        let s: YearSummary = serde_json::from_str(json).unwrap();
+        let line = s.breakdown().unwrap().replace('\u{202f}', " ");
+        assert_eq!(line, "Mpandray : 7 501 Ar · Tsy Mpandray : 1 000 Ar");
+        assert_eq!(s.participation().as_deref(), Some("3 rakitra · 2 mpikambana"));
+
+        // Ancien format sans sous-totaux ni effectifs
+        let old: YearSummary =
+            serde_json::from_str(r#"{"year":2020,"total":"10","closed_at":null,"note":null}"#).unwrap();
+        assert!(old.breakdown().is_none());
+        assert!(old.participation().is_none());
+    }
+
+    #[test]
+    fn totaux_mensuels() {
+        let json = r#"[{"month":1,"count":2,"total":"12500.50"},{"month":12,"count":0,"total":"0"}]"#;
+        let months: Vec<MonthlyTotal> = serde_json::from_str(json).unwrap();
+        assert_eq!(months[0].label(), "Janoary");
+        assert_eq!(months[1].label(), "Desambra");
+        assert_eq!(MonthlyTotal { month: 0, count: 0, total: "0".into() }.label(), "?");
+    }
+
+    #[test]
+    fn repartition_genre_type() {
+        let json = r#"{"year":2024,
+            "by_gender":[{"key":"M","count":1,"total":"10000"},{"key":"Inconnu","count":1,"total":"300"}],
+            "by_type":[{"key":"Cathekomen","count":2,"total":"3500.50"}]}"#;
+        let b: YearBreakdown = serde_json::from_str(json).unwrap();
+        assert_eq!(b.by_gender[0].label(), "Lahy");
+        assert_eq!(b.by_gender[1].label(), "Tsy fantatra");
+        assert_eq!(b.by_type[0].label(), "Tsy Mpandray");
+    }
+
+    #[test]
+    fn comparatif_mensuel() {
+        let json = r#"{"year_a":2025,"year_b":2024,"months":[
+            {"month":3,"total_a":"1000","total_b":"2500.50","cumulative_diff":"-1500.50"}]}"#;
+        let c: MonthlyComparison = serde_json::from_str(json).unwrap();
+        assert_eq!((c.year_a, c.year_b), (2025, 2024));
+        assert_eq!(c.months[0].label(), "Martsa");
+        assert_eq!(c.months[0].cumulative_diff, "-1500.50");
+    }
 }
@@ -1,9 +1,20 @@
 use js_sys::{Date, Math};
 use leptos::prelude::*;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
-use crate::components::icons::PageIcon;
+use crate::app::{DataVersionCtx, IntegrityCtx, SettingsCtx};
+use crate::components::{
+    icons::{IconAlertTriangle, IconCake, IconChevronRight, IconRefresh, IconX, PageIcon},
+    sparkline::Sparkline,
+};
+use crate::models::{
+    member::{RenewalDue, UpcomingBirthday},
+    statistics::DashboardStats,
+};
 use crate::services::db_service;
-use crate::utils::{format_ariary, sleep_ms};
+use crate::utils::{
+    format_ariary, format_ariary_str, money::parse_decimal, sleep_ms, use_reduced_motion, MoneyFormat,
+};
 
 // ─── Versets bibliques — sélection aléatoire à chaque ouverture ──────────────
 
@@ -44,9 +55,10 @@ const VERSES: &[(&str, &str)] = &[
 
 const ANIM_STEPS: i64 = 35;
 
-async fn animate_count(signal: RwSignal<i64>, target: i64) {
-    if target <= 0 {
-        signal.set(0);
+/// Défilement jusqu'à `target` ; valeur finale directe si `reduced` (animations réduites).
+async fn animate_count(signal: RwSignal<i64>, target: i64, reduced: bool) {
+    if target <= 0 || reduced {
+        signal.set(target.max(0));
         return;
     }
     for i in 1..=ANIM_STEPS {
@@ -56,6 +68,237 @@ async fn animate_count(signal: RwSignal<i64>, target: i64) {
     signal.set(target);
 }
 
+// ─── Bannière d'intégrité ─────────────────────────────────────────────────────
+
+/// Anomalies trouvées au démarrage ; « Hamboarina » lance les corrections automatiques.
+#[component]
+fn IntegrityBanner() -> impl IntoView {
+    let ctx = use_context::<IntegrityCtx>().expect("IntegrityCtx manquant");
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let repairing: RwSignal<bool>           = RwSignal::new(false);
+    let erreur:    RwSignal<Option<String>> = RwSignal::new(None);
+
+    let repair = move |_| {
+        repairing.set(true);
+        erreur.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::repair_integrity().await {
+                Ok(report) => {
+                    ctx.report.set((!report.issues.is_empty()).then_some(report));
+                    data_version.bump();
+                }
+                Err(e)     => erreur.set(Some(e)),
+            }
+            repairing.set(false);
+        });
+    };
+
+    move || ctx.report.get().map(|report| {
+        let can_repair = report.has_repairable();
+        view! {
+            <section class="max-w-2xl mx-auto w-full px-4">
+                <div role="alert"
+                     class="p-4 rounded-2xl text-sm \
+                            bg-amber-50 dark:bg-amber-900/20 \
+                            border border-amber-200 dark:border-amber-700/50 \
+                            text-amber-800 dark:text-amber-200">
+                    <div class="flex items-start gap-3">
+                        <IconAlertTriangle class="w-5 h-5 shrink-0 mt-0.5" />
+                        <div class="flex-1 min-w-0 space-y-1">
+                            <p class="font-semibold">
+                                {format!("Olana {} hita tao amin'ny angona", report.issues.len())}
+                            </p>
+                            <ul class="text-xs space-y-0.5 list-disc pl-4">
+                                {report.issues.iter().map(|i| view! { <li>{i.describe()}</li> }).collect_view()}
+                            </ul>
+                            {move || erreur.get().map(|e| view! {
+                                <p class="text-xs text-red-600 dark:text-red-400">{e}</p>
+                            })}
+                        </div>
+                        <button
+                            title="Hidio"
+                            on:click=move |_| ctx.report.set(None)
+                            class="btn-ripple p-1 rounded-lg hover:bg-amber-100 dark:hover:bg-amber-800/40"
+                        >
+                            <IconX class="w-4 h-4" />
+                        </button>
+                    </div>
+                    {can_repair.then(|| view! {
+                        <div class="flex justify-end mt-3">
+                            <button
+                                on:click=repair
+                                disabled=move || repairing.get()
+                                class="btn-ripple inline-flex items-center gap-1.5 px-3 py-1.5 \
+                                       text-xs font-semibold text-white rounded-xl \
+                                       bg-amber-600 hover:bg-amber-700 \
+                                       disabled:opacity-60 disabled:cursor-wait transition-colors"
+                            >
+                                <IconRefresh class="w-3.5 h-3.5" />
+                                {move || if repairing.get() { "Amboarina…" } else { "Hamboarina" }}
+                            </button>
+                        </div>
+                    })}
+                </div>
+            </section>
+        }
+    })
+}
+
+// ─── Anniversaires à venir ────────────────────────────────────────────────────
+
+/// Fenêtre de la carte des anniversaires (jours, aujourd'hui compris).
+const BIRTHDAY_WINDOW_DAYS: u32 = 14;
+
+/// Membres dont l'anniversaire tombe dans les deux semaines ; masquée si personne.
+#[component]
+fn BirthdayCard() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let list: RwSignal<Vec<UpcomingBirthday>> = RwSignal::new(vec![]);
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        leptos::task::spawn_local(async move {
+            if let Ok(items) = db_service::get_upcoming_birthdays(BIRTHDAY_WINDOW_DAYS).await {
+                list.set(items);
+            }
+        });
+    });
+
+    move || (!list.get().is_empty()).then(|| view! {
+        <section class="max-w-2xl mx-auto w-full px-4">
+            <div class="rounded-2xl \
+                        border border-pink-100 dark:border-pink-900/40 \
+                        bg-white/60 dark:bg-gray-800/60 backdrop-blur \
+                        px-6 py-5 shadow-sm">
+                <div class="flex items-center gap-2 mb-3">
+                    <IconCake class="w-4 h-4 text-pink-500 dark:text-pink-400" />
+                    <p class="text-xs font-semibold \
+                               text-pink-500 dark:text-pink-400 \
+                               uppercase tracking-widest">
+                        "Tsingerin-taona hatongavana"
+                    </p>
+                </div>
+                <ul class="divide-y divide-gray-100 dark:divide-gray-700/60">
+                    {list.get().into_iter().map(|b| {
+                        let today = b.days_until == 0;
+                        view! {
+                            <li class="flex items-center justify-between gap-3 py-2 text-sm">
+                                <div class="min-w-0">
+                                    <p class="font-medium text-gray-800 dark:text-white truncate">
+                                        {b.full_name.clone()}
+                                    </p>
+                                    <p class="text-xs text-gray-500 dark:text-gray-400">
+                                        {format!("{} · {} taona", b.member_type.label(), b.age)}
+                                    </p>
+                                </div>
+                                <div class="text-right shrink-0">
+                                    <p class=if today {
+                                        "text-xs font-semibold text-pink-600 dark:text-pink-400"
+                                    } else {
+                                        "text-xs font-medium text-gray-600 dark:text-gray-300"
+                                    }>
+                                        {b.when_label()}
+                                    </p>
+                                    <p class="text-xs text-gray-400 dark:text-gray-500 tabular-nums">
+                                        {b.day_month()}
+                                    </p>
+                                </div>
+                            </li>
+                        }
+                    }).collect_view()}
+                </ul>
+            </div>
+        </section>
+    })
+}
+
+// ─── Renouvellements de carte ─────────────────────────────────────────────────
+
+/// Fenêtre tant que les Paramètres ne sont pas chargés (`renewal_window_days`).
+const DEFAULT_RENEWAL_WINDOW_DAYS: u32 = 30;
+
+/// Cartes dont l'anniversaire d'inscription approche, repliée par défaut ; masquée si aucune.
+#[component]
+fn RenewalCard() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant").settings;
+    let list: RwSignal<Vec<RenewalDue>> = RwSignal::new(vec![]);
+    let open: RwSignal<bool> = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        let window = settings.with(|s| {
+            s.as_ref().map_or(DEFAULT_RENEWAL_WINDOW_DAYS, |s| s.renewal_window_days)
+        });
+        leptos::task::spawn_local(async move {
+            if let Ok(items) = db_service::get_upcoming_renewals(window).await {
+                list.set(items);
+            }
+        });
+    });
+
+    move || (!list.get().is_empty()).then(|| view! {
+        <section class="max-w-2xl mx-auto w-full px-4">
+            <div class="rounded-2xl \
+                        border border-sky-100 dark:border-sky-900/40 \
+                        bg-white/60 dark:bg-gray-800/60 backdrop-blur \
+                        px-6 py-5 shadow-sm">
+                <button
+                    type="button"
+                    aria-expanded=move || open.get().to_string()
+                    on:click=move |_| open.update(|o| *o = !*o)
+                    class="w-full flex items-center gap-2 text-left"
+                >
+                    <IconRefresh class="w-4 h-4 text-sky-500 dark:text-sky-400" />
+                    <p class="flex-1 text-xs font-semibold \
+                               text-sky-500 dark:text-sky-400 \
+                               uppercase tracking-widest">
+                        {move || format!("Fanavaozana karatra ({})", list.with(Vec::len))}
+                    </p>
+                    <span class=move || format!(
+                        "text-sky-400 transition-transform duration-150 {}",
+                        if open.get() { "rotate-90" } else { "" }
+                    )>
+                        <IconChevronRight class="w-4 h-4" />
+                    </span>
+                </button>
+                {move || open.get().then(|| view! {
+                    <ul class="mt-3 divide-y divide-gray-100 dark:divide-gray-700/60">
+                        {list.get().into_iter().map(|r| {
+                            let today = r.days_until == 0;
+                            view! {
+                                <li class="flex items-center justify-between gap-3 py-2 text-sm">
+                                    <div class="min-w-0">
+                                        <p class="font-medium text-gray-800 dark:text-white truncate">
+                                            {r.full_name.clone()}
+                                        </p>
+                                        <p class="text-xs text-gray-500 dark:text-gray-400">
+                                            <span class="font-mono">{r.card_number.clone()}</span>
+                                            {format!(" · {} · {} taona", r.member_type.label(), r.years)}
+                                        </p>
+                                    </div>
+                                    <div class="text-right shrink-0">
+                                        <p class=if today {
+                                            "text-xs font-semibold text-sky-600 dark:text-sky-400"
+                                        } else {
+                                            "text-xs font-medium text-gray-600 dark:text-gray-300"
+                                        }>
+                                            {r.when_label()}
+                                        </p>
+                                        <p class="text-xs text-gray-400 dark:text-gray-500 tabular-nums">
+                                            {r.due_on()}
+                                        </p>
+                                    </div>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                })}
+            </div>
+        </section>
+    })
+}
+
 // ─── Composant principal ──────────────────────────────────────────────────────
 
 #[component]
@@ -69,30 +312,46 @@ pub fn Accueil() -> impl IntoView {
     let communiants_display: RwSignal<i64> = RwSignal::new(0);
     let cathekumens_display: RwSignal<i64> = RwSignal::new(0);
     let contributions_display: RwSignal<i64> = RwSignal::new(0);
+    let stats: RwSignal<Option<DashboardStats>> = RwSignal::new(None);
+    // Totaux hebdomadaires de l'année (courbe sous la carte des cotisations)
+    let weekly: RwSignal<Vec<f64>> = RwSignal::new(vec![]);
+
+    let reduced_motion = use_reduced_motion();
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant");
 
-    // Chargement + animation au montage
+    // Chargement (une seule requête) + animation au montage
     Effect::new(move |_| {
+        let reduced = reduced_motion.get_untracked();
         leptos::task::spawn_local(async move {
-            if let Ok(list) = db_service::get_members_by_type("Communiant").await {
-                animate_count(communiants_display, list.len() as i64).await;
-            }
-            if let Ok(list) = db_service::get_members_by_type("Cathekomen").await {
-                animate_count(cathekumens_display, list.len() as i64).await;
-            }
-            if let Ok(Some(summary)) = db_service::get_year_summary(current_year).await {
-                if let Ok(total) = summary.total.parse::<f64>() {
-                    animate_count(contributions_display, total as i64).await;
-                }
+            if let Ok(weeks) = db_service::get_weekly_totals(current_year).await {
+                weekly.set(
+                    weeks.iter().map(|w| parse_decimal(&w.total).to_f64().unwrap_or(0.0)).collect(),
+                );
             }
+            let Ok(s) = db_service::get_dashboard_stats().await else { return };
+            let total = i64::try_from(parse_decimal(&s.current_year_total).trunc()).unwrap_or(0);
+            let (communiants, cathekomens) = (s.communiants, s.cathekomens);
+            stats.set(Some(s));
+            animate_count(communiants_display, communiants, reduced).await;
+            animate_count(cathekumens_display, cathekomens, reduced).await;
+            animate_count(contributions_display, total, reduced).await;
         });
     });
 
     view! {
         <div class="animate-fade-in space-y-6 sm:space-y-10">
 
+            <IntegrityBanner />
+
             // ── Verset du jour ─────────────────────────────────────────────────
             <section class="text-center px-4 pt-8 sm:pt-12 md:pt-16 pb-2">
 
+                // Nom de l'église (Paramètres), valeur par défaut tant que non chargé
+                <h1 class="text-base sm:text-lg font-bold mb-2 \
+                           text-gray-800 dark:text-white">
+                    {move || settings.church_name()}
+                </h1>
+
                 // Étiquette discrète
                 // Clair : blue-800 sur ciel bleu → contraste ~6:1 ✓
                 // Sombre : indigo-300 sur ardoise → contraste ~8:1 ✓
@@ -154,6 +413,10 @@ pub fn Accueil() -> impl IntoView {
 
             </section>
 
+            <BirthdayCard />
+
+            <RenewalCard />
+
             // ── Cotisations de l'année en cours ───────────────────────────────
             <section class="max-w-2xl mx-auto w-full px-4 pb-6">
                 <div class="rounded-2xl \
@@ -170,12 +433,45 @@ pub fn Accueil() -> impl IntoView {
                         <p class="text-xs text-gray-500 dark:text-gray-400 mt-0.5">
                             "Fitambarana adidy amin'ity taona ity"
                         </p>
+                        {move || stats.get().map(|s| view! {
+                            <p class="text-xs text-gray-500 dark:text-gray-400 mt-1.5">
+                                {format!(
+                                    "{} rakitra · {} : {}",
+                                    s.contributions_this_year,
+                                    s.year - 1,
+                                    format_ariary_str(&s.previous_year_total, MoneyFormat::compact()),
+                                )}
+                            </p>
+                            {s.last_contribution_on().map(|d| view! {
+                                <p class="text-xs text-gray-500 dark:text-gray-400">
+                                    {format!("Farany : {d}")}
+                                </p>
+                            })}
+                        })}
                     </div>
                     <p class="text-2xl sm:text-3xl font-bold font-mono \
                                text-gray-800 dark:text-white shrink-0">
-                        {move || format_ariary(&contributions_display.get().to_string())}
+                        {move || format_ariary(
+                            &Decimal::from(contributions_display.get()),
+                            MoneyFormat::compact(),
+                        )}
                     </p>
                 </div>
+                {move || {
+                    let values = weekly.get();
+                    (!values.is_empty()).then(|| view! {
+                        <div class="mt-2 px-2">
+                            <Sparkline
+                                values=values
+                                label=format!("Adidy isan-kerinandro {current_year}")
+                                class="text-amber-500 dark:text-amber-400"
+                            />
+                            <p class="text-[0.65rem] text-gray-400 dark:text-gray-500 text-right">
+                                "Isan-kerinandro"
+                            </p>
+                        </div>
+                    })
+                }}
             </section>
 
         </div>
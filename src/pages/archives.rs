@@ -1,115 +1,472 @@
 /// Page Archives — onglets par année, tableau des cotisations, bannière de clôture.
+/// Une année ouverte accepte de nouvelles cotisations (rattrapage des fiches papier).
 use leptos::prelude::*;
+use rust_decimal::Decimal;
+use wasm_bindgen::JsCast;
+use web_sys::Url;
 
 use crate::{
+    app::{DataVersionCtx, PinCtx, SettingsCtx, ToastProviderCtx},
     components::{
         contribution_edit_modal::ContributionEditModal,
+        contribution_modal::{ConfettiLayer, ContributionModal},
+        monthly_totals::MonthlyTotalsTable,
+        year_breakdown::YearBreakdownCards,
+        year_comparison::{MonthlyComparisonTable, YearComparisonTable},
+        year_member_totals::YearMemberTotalsTable,
         icons::{
-            IconAlertTriangle, IconArchive, IconFileText, IconLock, IconPencil, IconSearch,
+            IconArchive, IconChevronLeft, IconChevronRight, IconFileText,
+            IconDownload, IconLock, IconPencil, IconPlus, IconPrinter, IconSave, IconSearch,
+            IconTrash,
         },
     },
+    i18n::tr,
     models::{
-        contribution::{Contribution, ContributionWithMember},
+        contribution::{Contribution, ContributionSearchScope, ContributionWithMember},
+        settings::{current_fiscal_year, fiscal_year_label},
         year_summary::YearSummary,
     },
     services::db_service,
-    utils::format_ariary,
+    utils::{
+        download::{click_download, download_bytes, download_text, text_blob_url},
+        amount::amount_matches, format_ariary, format_ariary_str, money::parse_decimal,
+        use_debounced_signal, MoneyFormat,
+        RequestGen, SEARCH_DEBOUNCE_MS,
+    },
 };
 
 // ── Helpers locaux ────────────────────────────────────────────────────────────
 
-/// Année courante depuis JS (WASM-compatible).
-fn current_year() -> i32 {
-    js_sys::Date::new_0().get_full_year() as i32
+/// Nombre de cotisations chargées par page pour l'année sélectionnée.
+const PAGE_SIZE: i64 = 50;
+
+/// Années archivées affichées en onglet ; les plus anciennes passent dans la liste « Taona hafa… ».
+const RECENT_ARCHIVED_TABS: usize = 4;
+
+/// Onglets affichés, du plus récent au plus ancien : toutes les années entre la
+/// plus ancienne ayant des données et l'année courante, y compris celles sans cotisation.
+fn tab_year_range(data_years: &[i32], cur_year: i32) -> Vec<i32> {
+    let oldest = data_years.iter().copied().min().unwrap_or(cur_year).min(cur_year);
+    let newest = data_years.iter().copied().max().unwrap_or(cur_year).max(cur_year);
+    (oldest..=newest).rev().collect()
+}
+
+/// Sépare les années de `tab_year_range` en onglets (année courante et suivantes,
+/// puis les `RECENT_ARCHIVED_TABS` plus récentes) et années du sélecteur.
+fn split_tab_years(years: &[i32], cur_year: i32) -> (Vec<i32>, Vec<i32>) {
+    let archived = years.iter().filter(|&&y| y < cur_year).count();
+    let pills = years.len() - archived.saturating_sub(RECENT_ARCHIVED_TABS);
+    (years[..pills].to_vec(), years[pills..].to_vec())
+}
+
+/// Résumés hors de la plage des onglets, sans cotisation, clôture ni note :
+/// typiquement une année future créée par erreur, que l'on peut effacer.
+fn stray_years(summaries: &[YearSummary], tab_years: &[i32]) -> Vec<i32> {
+    summaries
+        .iter()
+        .filter(|s| !tab_years.contains(&s.year) && !year_has_data(Some(s)) && s.note.is_none())
+        .map(|s| s.year)
+        .collect()
+}
+
+/// Une année a des données si elle est clôturée ou si son total n'est pas nul.
+fn year_has_data(summary: Option<&YearSummary>) -> bool {
+    summary.is_some_and(|s| s.closed_at.is_some() || !parse_decimal(&s.total).is_zero())
+}
+
+/// Index de l'onglet visé par une touche (onglets triés du plus récent au plus ancien).
+fn tab_key_target(key: &str, current: usize, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match key {
+        "ArrowRight" => Some((current + 1).min(len - 1)),
+        "ArrowLeft"  => Some(current.saturating_sub(1)),
+        "Home"       => Some(0),
+        "End"        => Some(len - 1),
+        _            => None,
+    }
+}
+
+/// Filtres de recherche actifs : le texte cherché, puis une portée autre que « Tout ».
+fn active_filter_count(query: &str, scope: ContributionSearchScope) -> usize {
+    usize::from(!query.trim().is_empty()) + usize::from(scope != ContributionSearchScope::All)
+}
+
+/// En-têtes et lignes du CSV « sélection » : les cotisations affichées, montants bruts.
+fn selection_csv(rows: &[ContributionWithMember]) -> (Vec<String>, Vec<Vec<String>>) {
+    let headers = ["Mpikambana", "Vanim-potoana", "Vola", "Daty"]
+        .map(String::from)
+        .to_vec();
+    let lines = rows
+        .iter()
+        .map(|c| vec![c.member_name.clone(), c.period.clone(), c.amount.clone(), c.payment_date.clone()])
+        .collect();
+    (headers, lines)
+}
+
+/// Horodatage du nom de fichier exporté : "20250314-093005".
+fn export_stamp() -> String {
+    let d = js_sys::Date::new_0();
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        d.get_full_year(),
+        d.get_month() + 1,
+        d.get_date(),
+        d.get_hours(),
+        d.get_minutes(),
+        d.get_seconds(),
+    )
+}
+
+fn year_tab_id(year: i32) -> String {
+    format!("year-tab-{year}")
+}
+
+/// Ouvre le rapport HTML dans une nouvelle fenêtre qui lance l'impression au chargement.
+/// Si la fenêtre est refusée par le webview, le fichier est téléchargé à la place.
+fn open_print_window(html: &str, filename: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or("Pas de window")?;
+    let printable = html.replace(
+        "</body>",
+        "<script>window.addEventListener('load',()=>window.print())</script></body>",
+    );
+    let url = text_blob_url(&printable, "text/html;charset=utf-8")?;
+
+    // L'URL reste valide tant que la fenêtre d'impression en a besoin
+    if let Ok(Some(_)) = window.open_with_url_and_target(&url, "_blank") {
+        return Ok(());
+    }
+    let res = click_download(&url, filename);
+    let _ = Url::revoke_object_url(&url);
+    res
 }
 
 // ── Composant principal ───────────────────────────────────────────────────────
 
 #[component]
 pub fn Archives() -> impl IntoView {
-    let cur_year = current_year();
+    // Exercice juillet → juin : onglets « 2024–2025 », désignés par l'année de début
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant").settings;
+    let fiscal_start = settings.with_untracked(|s| s.as_ref().map_or(1, |s| s.fiscal_year_start_month));
+    let cur_year = current_fiscal_year(fiscal_start);
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
 
     // Liste des résumés annuels (triés DESC par le backend)
     let summaries: RwSignal<Vec<YearSummary>> = RwSignal::new(vec![]);
-    // Cotisations de l'année sélectionnée
+    // Années ayant des cotisations ou une clôture : bornes des onglets
+    let data_years: RwSignal<Vec<i32>> = RwSignal::new(vec![]);
+    // Cotisations affichées (page courante uniquement) : année sélectionnée, ou
+    // résultats de la recherche toutes années confondues
     let contributions: RwSignal<Vec<ContributionWithMember>> = RwSignal::new(vec![]);
-    // Toutes les cotisations (toutes années) pour la recherche globale
-    let all_contributions: RwSignal<Vec<ContributionWithMember>> = RwSignal::new(vec![]);
+    // Pagination : page courante (0-based) + nombre total de cotisations (année ou recherche)
+    let cont_page: RwSignal<i64> = RwSignal::new(0);
+    let cont_total: RwSignal<i64> = RwSignal::new(0);
     // État de chargement
     let loading_sum  = RwSignal::new(true);
     let loading_cont = RwSignal::new(false);
-    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
 
     // Année sélectionnée (défaut : année courante)
     let selected_year: RwSignal<i32> = RwSignal::new(cur_year);
-    // Recherche par nom de membre
+    // Recherche (nom, reçu, période, date, montant) et champs visés
     let recherche: RwSignal<String> = RwSignal::new(String::new());
+    let recherche_q = use_debounced_signal(recherche, SEARCH_DEBOUNCE_MS);
+    let portee: RwSignal<ContributionSearchScope> = RwSignal::new(ContributionSearchScope::All);
+
+    // Tableau comparatif des années (année courante + 3 précédentes)
+    let show_comparison = RwSignal::new(false);
+
+    // Rapport imprimable / export CSV / export PDF en cours de génération
+    let printing  = RwSignal::new(false);
+    let exporting = RwSignal::new(false);
+    let exporting_pdf = RwSignal::new(false);
+
+    let export_pdf = move |_| {
+        let year = selected_year.get_untracked();
+        exporting_pdf.set(true);
+        leptos::task::spawn_local(async move {
+            let filename = format!("tahiry-{year}.pdf");
+            let res = match db_service::export_year_report_pdf(year).await {
+                Ok(bytes) => download_bytes(&bytes, "application/pdf", &filename),
+                Err(e)    => Err(e),
+            };
+            match res {
+                Ok(()) => toasts.info(format!("Voaalefa : {filename}")),
+                Err(e) => toasts.error(e),
+            }
+            exporting_pdf.set(false);
+        });
+    };
+
+    let export_csv = move |_| {
+        let year = selected_year.get_untracked();
+        exporting.set(true);
+        leptos::task::spawn_local(async move {
+            let res = match db_service::export_contributions_csv(year).await {
+                Ok(csv) => download_text(&csv, "text/csv;charset=utf-8", &format!("rakitra-{year}.csv")),
+                Err(e)  => Err(e),
+            };
+            if let Err(e) = res {
+                toasts.error(e);
+            }
+            exporting.set(false);
+        });
+    };
+
+    // Lignes affichées → fichier CSV sur le poste, puis ouverture du dossier
+    let exporting_sel = RwSignal::new(false);
+    let export_selection = move |_| {
+        let (headers, rows) = contributions.with_untracked(|c| selection_csv(c));
+        let dest = format!("fandoavana-{}-{}.csv", selected_year.get_untracked(), export_stamp());
+        exporting_sel.set(true);
+        leptos::task::spawn_local(async move {
+            match db_service::write_csv(&dest, &headers, &rows, false).await {
+                Ok(path) => {
+                    toasts.success(format!("Voatahiry : {path}"));
+                    if let Err(e) = db_service::reveal_in_file_manager(&path).await {
+                        toasts.error(e);
+                    }
+                }
+                Err(e) => toasts.error(e),
+            }
+            exporting_sel.set(false);
+        });
+    };
 
     // Contribution en cours d'édition (None = modal fermé)
     let editing: RwSignal<Option<ContributionWithMember>> = RwSignal::new(None);
 
-    // ── Charger les résumés + toutes les cotisations au montage ──────────────
+    // Note de l'année clôturée, modifiable dans la bannière (garde PIN)
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let note_editing: RwSignal<bool>   = RwSignal::new(false);
+    let note_draft:   RwSignal<String> = RwSignal::new(String::new());
+    let note_saving:  RwSignal<bool>   = RwSignal::new(false);
+
+    // Année clôturée : cotisations une à une, ou totaux par membre figés
+    let per_member: RwSignal<bool> = RwSignal::new(false);
+
+    // Changer d'onglet abandonne l'édition en cours
+    Effect::new(move |_| {
+        selected_year.track();
+        note_editing.set(false);
+        per_member.set(false);
+    });
+
+    let save_note = move |year: i32| {
+        let draft = note_draft.get_untracked();
+        pin.guard(move |token| {
+            note_saving.set(true);
+            leptos::task::spawn_local(async move {
+                let note = Some(draft.trim()).filter(|n| !n.is_empty());
+                match db_service::update_year_note(year, note, token.as_deref()).await {
+                    Ok(updated) => {
+                        summaries.update(|list| {
+                            if let Some(s) = list.iter_mut().find(|s| s.year == year) {
+                                *s = updated;
+                            }
+                        });
+                        note_editing.set(false);
+                        toasts.success(format!("Voatahiry ny fanamarihana {year}"));
+                    }
+                    Err(e) => toasts.error(e),
+                }
+                note_saving.set(false);
+            });
+        });
+    };
+
+    // « Avereno » d'un chargement échoué : résumés et page de cotisations
+    let retry_ctr: RwSignal<u32> = RwSignal::new(0);
+    let retry = move || retry_ctr.update(|n| *n += 1);
+
+    // ── Charger les résumés au montage ────────────────────────────────────────
     Effect::new(move |_| {
+        retry_ctr.track();
         leptos::task::spawn_local(async move {
             loading_sum.set(true);
             match db_service::get_year_summaries().await {
                 Ok(liste) => summaries.set(liste),
-                Err(e)    => erreur.set(Some(e)),
+                Err(e)    => toasts.error_with_retry(e, retry),
             }
-            match db_service::get_all_contributions_with_member().await {
-                Ok(liste) => all_contributions.set(liste),
-                Err(e)    => erreur.set(Some(e)),
+            if let Ok(years) = db_service::get_years_with_contributions().await {
+                data_years.set(years);
             }
             loading_sum.set(false);
         });
     });
 
-    // ── Charger les cotisations quand l'année change ──────────────────────────
-    Effect::new(move |_| {
-        let year = selected_year.get();
-        recherche.set(String::new());
+    // ── Nouvelle recherche : retour à la première page ────────────────────────
+    Effect::new(move |prev: Option<(String, ContributionSearchScope)>| {
+        let key = (recherche_q.get(), portee.get());
+        if prev.is_some_and(|p| p != key) {
+            cont_page.set(0);
+        }
+        key
+    });
+
+    // ── Charger une page de cotisations (filtrage et pagination côté SQL) ─────
+    //    Recherche active → toutes années ; sinon année sélectionnée.
+    //    Changement d'onglet rapide : seule la dernière réponse est affichée.
+    //    Après une écriture (data_version), la page est rechargée sans spinner.
+    let cont_gen = RequestGen::new();
+    Effect::new(move |prev: Option<(i32, i64, String, ContributionSearchScope)>| {
+        data_version.version.track();
+        retry_ctr.track();
+        let key = (selected_year.get(), cont_page.get(), recherche_q.get(), portee.get());
+        let (year, page, q, scope) = key.clone();
+        let fresh = prev.as_ref() != Some(&key);
+        let gen = cont_gen.next();
         leptos::task::spawn_local(async move {
-            loading_cont.set(true);
-            contributions.set(vec![]);
-            match db_service::get_contributions_by_year_with_member(year).await {
-                Ok(liste) => contributions.set(liste),
-                Err(e)    => erreur.set(Some(e)),
+            if fresh {
+                loading_cont.set(true);
+                contributions.set(vec![]);
+            }
+            let (year, filter) = if q.trim().is_empty() { (Some(year), None) } else { (None, Some(q)) };
+            let res = db_service::get_contributions_by_year_with_member(
+                year, filter.as_deref(), scope, PAGE_SIZE, page * PAGE_SIZE,
+            ).await;
+            if !cont_gen.is_current(gen) {
+                return;
+            }
+            match res {
+                Ok(p) => {
+                    contributions.set(p.items);
+                    cont_total.set(p.total_count);
+                }
+                Err(e) => toasts.error_with_retry(e, retry),
             }
             loading_cont.set(false);
         });
+        key
     });
 
-    // ── Liste des onglets : années DB + année courante si absente ─────────────
-    let tab_years = Memo::new(move |_| {
-        let mut years: Vec<i32> = summaries.get().iter().map(|s| s.year).collect();
-        if !years.contains(&cur_year) {
-            years.push(cur_year);
-        }
-        years.sort_unstable_by(|a, b| b.cmp(a));
-        years
+    let total_pages = Memo::new(move |_| ((cont_total.get() + PAGE_SIZE - 1) / PAGE_SIZE).max(1));
+
+    // ── Liste des onglets : plage continue jusqu'à l'année courante ───────────
+    let tab_years = Memo::new(move |_| data_years.with(|years| tab_year_range(years, cur_year)));
+    // Onglets visibles / années du sélecteur « Taona hafa… »
+    let tab_split = Memo::new(move |_| tab_years.with(|years| split_tab_years(years, cur_year)));
+    let hidden_summaries = Memo::new(move |_| {
+        tab_years.with(|years| summaries.with(|list| stray_years(list, years)))
     });
 
+    let select_year = move |y: i32| {
+        if y != selected_year.get_untracked() {
+            cont_page.set(0);
+            recherche.set(String::new());
+            selected_year.set(y);
+        }
+    };
+
+    // ← → entre onglets, Début / Fin : année la plus récente / la plus ancienne
+    let on_tabs_keydown = move |ev: web_sys::KeyboardEvent| {
+        let (years, _) = tab_split.get_untracked();
+        let Some(current) = years.iter().position(|&y| y == selected_year.get_untracked()) else {
+            return;
+        };
+        let Some(target) = tab_key_target(&ev.key(), current, years.len()) else {
+            return;
+        };
+        ev.prevent_default();
+        let y = years[target];
+        select_year(y);
+        let tab = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.get_element_by_id(&year_tab_id(y)))
+            .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok());
+        if let Some(tab) = tab {
+            let _ = tab.focus();
+        }
+    };
+
     // ── Détail de l'année sélectionnée ───────────────────────────────────────
     let year_detail = Memo::new(move |_| {
         let sel = selected_year.get();
         summaries.get().into_iter().find(|s| s.year == sel)
     });
 
-    // ── Cotisations filtrées :
-    //    - si recherche active → toutes années, triées par date ASC
-    //    - si vide            → année sélectionnée uniquement
-    let filtered = Memo::new(move |_| {
-        let q = recherche.get().to_lowercase();
-        if q.is_empty() {
-            contributions.get()
-        } else {
-            all_contributions.get()
-                .into_iter()
-                .filter(|c| c.member_name.to_lowercase().contains(&q))
-                .collect::<Vec<_>>()
+    // ── Rechargement après une écriture (édition, suppression, annulation) ────
+    let reload = move || {
+        // Les totaux des listes de membres ont changé ; recharge aussi la page
+        // affichée (audit_summary à jour)
+        data_version.bump();
+        leptos::task::spawn_local(async move {
+            if let Ok(liste) = db_service::get_year_summaries().await {
+                summaries.set(liste);
+            }
+            if let Ok(years) = db_service::get_years_with_contributions().await {
+                data_years.set(years);
+            }
+        });
+    };
+
+    // ── « Afeno » : efface le résumé d'une année restée vide ─────────────────
+    let hiding: RwSignal<Option<i32>> = RwSignal::new(None);
+    let hide_year = move |year: i32| {
+        hiding.set(Some(year));
+        leptos::task::spawn_local(async move {
+            match db_service::delete_empty_year_summary(year).await {
+                Ok(()) => {
+                    summaries.update(|list| list.retain(|s| s.year != year));
+                    toasts.success(format!("Nafenina ny taona {}", fiscal_year_label(year, fiscal_start)));
+                }
+                Err(e) => toasts.error(e),
+            }
+            hiding.set(None);
+        });
+    };
+
+    // ── Nouvelle cotisation (année ouverte) ──────────────────────────────────
+    let adding:          RwSignal<bool> = RwSignal::new(false);
+    let added_ctr:       RwSignal<u32>  = RwSignal::new(0);
+    let confetti_active: RwSignal<bool> = RwSignal::new(false);
+    Effect::new(move |prev: Option<u32>| {
+        let n = added_ctr.get();
+        if prev.is_some() {
+            reload();
         }
+        n
     });
 
+    // ── Suppression (PIN) avec « Averina » pendant quelques secondes ──────────
+    let delete_contribution = move |c: ContributionWithMember| {
+        pin.guard(move |token| {
+            leptos::task::spawn_local(async move {
+                if let Err(e) = db_service::delete_contribution(c.id, token.as_deref()).await {
+                    toasts.error(e);
+                    return;
+                }
+                reload();
+                let id = c.id;
+                toasts.undoable(format!("Voafafa ny rakitra an'i {}", c.member_name), move || {
+                    leptos::task::spawn_local(async move {
+                        match db_service::restore_contribution(id).await {
+                            Ok(_)  => reload(),
+                            Err(e) => toasts.error(e),
+                        }
+                    });
+                });
+            });
+        });
+    };
+
+    // ── Reçu imprimable d'une cotisation ──────────────────────────────────────
+    let printing_receipt: RwSignal<Option<String>> = RwSignal::new(None);
+    let print_receipt = move |receipt: String| {
+        printing_receipt.set(Some(receipt.clone()));
+        leptos::task::spawn_local(async move {
+            let res = match db_service::export_receipt(&receipt).await {
+                Ok(html) => open_print_window(&html, &format!("rosia-{receipt}.html")),
+                Err(e)   => Err(e),
+            };
+            if let Err(e) = res {
+                toasts.error(e);
+            }
+            printing_receipt.set(None);
+        });
+    };
+
     // ── Année ouverte ? ───────────────────────────────────────────────────────
     let is_year_open = Memo::new(move |_| {
         year_detail.get()
@@ -133,30 +490,10 @@ pub fn Archives() -> impl IntoView {
                 </p>
             </div>
 
-            // ── Message d'erreur ──────────────────────────────────────────────
-            {move || erreur.get().map(|e| view! {
-                <div class="p-3 sm:p-4 bg-red-50 dark:bg-red-900/30 \
-                            border border-red-200 dark:border-red-700 \
-                            rounded-xl text-red-700 dark:text-red-300 text-sm \
-                            flex items-start gap-2">
-                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
-                    <span>{e}</span>
-                </div>
-            })}
-
             // ── Modal d'édition ───────────────────────────────────────────────
             {move || editing.get().map(|contrib| {
-                let year = selected_year.get();
                 let on_saved = Callback::new(move |_updated: Contribution| {
-                    // Recharger les deux listes pour avoir l'audit_summary à jour
-                    leptos::task::spawn_local(async move {
-                        if let Ok(liste) = db_service::get_contributions_by_year_with_member(year).await {
-                            contributions.set(liste);
-                        }
-                        if let Ok(liste) = db_service::get_all_contributions_with_member().await {
-                            all_contributions.set(liste);
-                        }
-                    });
+                    reload();
                     editing.set(None);
                 });
                 let on_cancel = Callback::new(move |_| editing.set(None));
@@ -169,6 +506,17 @@ pub fn Archives() -> impl IntoView {
                 }
             })}
 
+            // ── Modal de saisie (membre choisi dans le modal) ─────────────────
+            {move || adding.get().then(|| view! {
+                <ContributionModal
+                    open=adding
+                    refresh_ctr=added_ctr
+                    confetti_active=confetti_active
+                    year=selected_year.get_untracked()
+                />
+            })}
+            <ConfettiLayer active=confetti_active />
+
             // ── Onglets d'années ──────────────────────────────────────────────
             {move || {
                 if loading_sum.get() {
@@ -183,73 +531,226 @@ pub fn Archives() -> impl IntoView {
                         </div>
                     }.into_any();
                 }
+                let (pills, older) = tab_split.get();
                 view! {
-                    <div class="flex gap-2 overflow-x-auto pb-1">
-                        {tab_years.get().into_iter().map(|y| {
-                            let is_active  = y == selected_year.get();
-                            let is_current = y == cur_year;
-                            let detail = summaries.get().into_iter().find(|s| s.year == y);
-                            let is_closed = detail
-                                .as_ref()
-                                .and_then(|d| d.closed_at.as_ref())
-                                .is_some();
-
-                            let btn_cls = if is_active {
-                                "flex-shrink-0 px-4 py-1.5 rounded-full text-sm font-semibold \
-                                 bg-blue-600 text-white shadow-sm transition-all duration-200"
-                            } else {
-                                "flex-shrink-0 px-4 py-1.5 rounded-full text-sm font-medium \
-                                 bg-white/70 dark:bg-gray-800/70 \
-                                 text-gray-700 dark:text-gray-300 \
-                                 border border-gray-200 dark:border-gray-600 \
-                                 hover:border-blue-400 dark:hover:border-blue-500 \
-                                 hover:text-blue-600 dark:hover:text-blue-400 \
-                                 transition-all duration-200 backdrop-blur"
-                            };
+                    <div class="flex flex-wrap items-center gap-2">
+                        <div
+                            role="tablist"
+                            aria-label="Taona"
+                            class="flex gap-2 overflow-x-auto pb-1"
+                            on:keydown=on_tabs_keydown
+                        >
+                            {pills.into_iter().map(|y| {
+                                let is_active  = move || y == selected_year.get();
+                                let is_current = y == cur_year;
+                                let detail = summaries.get().into_iter().find(|s| s.year == y);
+                                let is_closed = detail
+                                    .as_ref()
+                                    .and_then(|d| d.closed_at.as_ref())
+                                    .is_some();
+                                let has_data = year_has_data(detail.as_ref());
+
+                                let btn_cls = move || if is_active() {
+                                    "flex-shrink-0 px-4 py-1.5 rounded-full text-sm font-semibold \
+                                     bg-blue-600 text-white shadow-sm transition-all duration-200 \
+                                     focus:outline-none focus-visible:ring-2 focus-visible:ring-blue-400"
+                                } else if has_data {
+                                    "flex-shrink-0 px-4 py-1.5 rounded-full text-sm font-medium \
+                                     bg-white/70 dark:bg-gray-800/70 \
+                                     text-gray-700 dark:text-gray-300 \
+                                     border border-gray-200 dark:border-gray-600 \
+                                     hover:border-blue-400 dark:hover:border-blue-500 \
+                                     hover:text-blue-600 dark:hover:text-blue-400 \
+                                     transition-all duration-200 backdrop-blur"
+                                } else {
+                                    // Année sans cotisation : sélectionnable, mais en retrait
+                                    "flex-shrink-0 px-4 py-1.5 rounded-full text-sm font-normal \
+                                     bg-transparent text-gray-400 dark:text-gray-500 \
+                                     border border-dashed border-gray-300 dark:border-gray-600 \
+                                     hover:border-blue-300 dark:hover:border-blue-600 \
+                                     hover:text-blue-500 dark:hover:text-blue-400 \
+                                     transition-all duration-200"
+                                };
 
+                                view! {
+                                    <button
+                                        id=year_tab_id(y)
+                                        role="tab"
+                                        aria-selected=move || is_active().to_string()
+                                        tabindex=move || if is_active() { "0" } else { "-1" }
+                                        title=(!has_data).then_some("Tsy misy raki-tsoratra")
+                                        class=btn_cls
+                                        on:click=move |_| select_year(y)
+                                    >
+                                        <span class="flex items-center gap-1">
+                                            {if is_current && !is_closed {
+                                                format!("{} ✦", fiscal_year_label(y, fiscal_start))
+                                            } else {
+                                                fiscal_year_label(y, fiscal_start)
+                                            }}
+                                            {is_closed.then(|| view! {
+                                                <IconLock class="w-3 h-3 opacity-80" />
+                                            })}
+                                        </span>
+                                    </button>
+                                }
+                            }).collect_view()}
+                        </div>
+                        // Années plus anciennes : sélecteur plutôt qu'une rangée d'onglets sans fin
+                        {(!older.is_empty()).then(|| {
+                            let in_older = older.clone();
+                            let is_older = Memo::new(move |_| in_older.contains(&selected_year.get()));
                             view! {
-                                <button
-                                    class={btn_cls}
-                                    on:click=move |_| selected_year.set(y)
-                                >
-                                    <span class="flex items-center gap-1">
-                                        {if is_current && !is_closed {
-                                            format!("{} ✦", y)
+                                <select
+                                    aria-label="Taona hafa"
+                                    class=move || format!(
+                                        "px-3 py-1.5 text-sm rounded-full border transition-colors \
+                                         focus:outline-none focus:ring-2 focus:ring-blue-400 {}",
+                                        if is_older.get() {
+                                            "bg-blue-600 text-white border-blue-600 font-semibold"
                                         } else {
-                                            y.to_string()
-                                        }}
-                                        {is_closed.then(|| view! {
-                                            <IconLock class="w-3 h-3 opacity-80" />
-                                        })}
-                                    </span>
-                                </button>
+                                            "bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                                             text-gray-700 dark:text-gray-300 \
+                                             border-gray-200 dark:border-gray-600"
+                                        }
+                                    )
+                                    prop:value=move || {
+                                        let y = selected_year.get();
+                                        if is_older.get() { y.to_string() } else { String::new() }
+                                    }
+                                    on:change=move |ev| {
+                                        if let Ok(y) = event_target_value(&ev).parse() {
+                                            select_year(y);
+                                        }
+                                    }
+                                >
+                                    <option value="" disabled=true>"Taona hafa…"</option>
+                                    {older.into_iter().map(|y| view! {
+                                        <option value=y.to_string()>{fiscal_year_label(y, fiscal_start)}</option>
+                                    }).collect_view()}
+                                </select>
                             }
-                        }).collect_view()}
+                        })}
                     </div>
+                    // Résumés vides hors plage (année future saisie par erreur…)
+                    {move || {
+                        let stray = hidden_summaries.get();
+                        (!stray.is_empty()).then(|| view! {
+                            <p class="flex flex-wrap items-center gap-2 text-xs text-gray-500 dark:text-gray-400">
+                                "Taona tsy misy raki-tsoratra :"
+                                {stray.into_iter().map(|y| view! {
+                                    <span class="inline-flex items-center gap-1">
+                                        {fiscal_year_label(y, fiscal_start)}
+                                        <button
+                                            type="button"
+                                            disabled=move || hiding.get().is_some()
+                                            on:click=move |_| hide_year(y)
+                                            class="underline hover:text-blue-600 dark:hover:text-blue-400 \
+                                                   disabled:opacity-50"
+                                        >
+                                            "Afeno"
+                                        </button>
+                                    </span>
+                                }).collect_view()}
+                            </p>
+                        })
+                    }}
                 }.into_any()
             }}
 
-            // ── Barre de recherche ────────────────────────────────────────────
-            <div class="relative w-full max-w-xs sm:max-w-sm">
-                <span class="absolute left-3 top-1/2 -translate-y-1/2 \
-                             text-gray-400 dark:text-gray-500 pointer-events-none">
-                    <IconSearch class="w-4 h-4" />
-                </span>
-                <input
-                    type="text"
-                    placeholder="Ikaroka mpikambana…"
-                    class="w-full pl-9 pr-3 py-2 text-sm rounded-xl \
+            // ── Barre de recherche + bouton comparaison ──────────────────────
+            <div class="flex flex-wrap items-center gap-2">
+                <div class="relative w-full max-w-xs sm:max-w-sm">
+                    <span class="absolute left-3 top-1/2 -translate-y-1/2 \
+                                 text-gray-400 dark:text-gray-500 pointer-events-none">
+                        <IconSearch class="w-4 h-4" />
+                    </span>
+                    <input
+                        type="text"
+                        placeholder="Ikaroka anarana, rosia, daty, vola…"
+                        class="w-full pl-9 pr-3 py-2 text-sm rounded-xl \
+                               bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                               border border-gray-200 dark:border-gray-600 \
+                               text-gray-800 dark:text-gray-200 \
+                               placeholder-gray-400 dark:placeholder-gray-500 \
+                               focus:outline-none focus:ring-2 focus:ring-blue-400/50 \
+                               transition-all duration-200"
+                        prop:value=move || recherche.get()
+                        on:input=move |ev| recherche.set(event_target_value(&ev))
+                    />
+                </div>
+                <select
+                    aria-label="Karohina ao amin'ny"
+                    class="px-3 py-2 text-sm \
+                           bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                           border border-gray-200 dark:border-gray-600 \
+                           rounded-xl text-gray-800 dark:text-white \
+                           focus:outline-none focus:ring-2 focus:ring-blue-400 transition"
+                    prop:value=move || portee.get().as_str()
+                    on:change=move |ev| portee.set(ContributionSearchScope::from_value(&event_target_value(&ev)))
+                >
+                    {ContributionSearchScope::ALL.into_iter().map(|scope| view! {
+                        <option value=scope.as_str()>{move || tr(scope.label_key())}</option>
+                    }).collect_view()}
+                </select>
+                <button
+                    class=move || if show_comparison.get() {
+                        "px-4 py-2 rounded-xl text-sm font-semibold \
+                         bg-blue-600 text-white shadow-sm transition-all duration-200"
+                    } else {
+                        "px-4 py-2 rounded-xl text-sm font-medium \
+                         bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                         text-gray-700 dark:text-gray-300 \
+                         border border-gray-200 dark:border-gray-600 \
+                         hover:border-blue-400 dark:hover:border-blue-500 \
+                         transition-all duration-200"
+                    }
+                    on:click=move |_| show_comparison.update(|v| *v = !*v)
+                >
+                    "Fampitahana"
+                </button>
+                <button
+                    title=move || format!("Rakitra {} (CSV)", selected_year.get())
+                    disabled=move || exporting.get()
+                    on:click=export_csv
+                    class="inline-flex items-center gap-1.5 px-4 py-2 rounded-xl text-sm font-medium \
                            bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                           text-gray-700 dark:text-gray-300 \
                            border border-gray-200 dark:border-gray-600 \
-                           text-gray-800 dark:text-gray-200 \
-                           placeholder-gray-400 dark:placeholder-gray-500 \
-                           focus:outline-none focus:ring-2 focus:ring-blue-400/50 \
+                           hover:border-blue-400 dark:hover:border-blue-500 \
+                           disabled:opacity-60 disabled:cursor-wait \
                            transition-all duration-200"
-                    prop:value=move || recherche.get()
-                    on:input=move |ev| recherche.set(event_target_value(&ev))
-                />
+                >
+                    <IconDownload class="w-4 h-4" />
+                    {move || if exporting.get() { "Manondrana…" } else { "CSV" }}
+                </button>
+                <button
+                    title=move || format!("Tahiry {} (PDF)", selected_year.get())
+                    disabled=move || exporting_pdf.get()
+                    on:click=export_pdf
+                    class="inline-flex items-center gap-1.5 px-4 py-2 rounded-xl text-sm font-medium \
+                           bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                           text-gray-700 dark:text-gray-300 \
+                           border border-gray-200 dark:border-gray-600 \
+                           hover:border-blue-400 dark:hover:border-blue-500 \
+                           disabled:opacity-60 disabled:cursor-wait \
+                           transition-all duration-200"
+                >
+                    <IconFileText class="w-4 h-4" />
+                    {move || if exporting_pdf.get() { "Manondrana…" } else { "PDF" }}
+                </button>
             </div>
 
+            // ── Comparaison des années ────────────────────────────────────────
+            {move || show_comparison.get().then(|| view! {
+                <YearComparisonTable years=(cur_year - 3..=cur_year).collect() />
+            })}
+            // Mois par mois : année sélectionnée face à la précédente
+            {move || show_comparison.get().then(|| {
+                let year = selected_year.get();
+                view! { <MonthlyComparisonTable year_a=year year_b=year - 1 /> }
+            })}
+
             // ── Contenu de l'année sélectionnée ──────────────────────────────
             {move || {
                 let sel = selected_year.get();
@@ -261,11 +762,13 @@ pub fn Archives() -> impl IntoView {
 
                         // ── Bannière clôture ──────────────────────────────────
                         {detail.clone().filter(|_| is_closed).map(|d| {
-                            let total_fmt   = format_ariary(&d.total);
+                            let total_fmt   = format_ariary_str(&d.total, MoneyFormat::default());
                             let closed_date = d.closed_at.as_deref()
                                 .map(|dt| dt.chars().take(10).collect::<String>())
                                 .unwrap_or_default();
                             let note = d.note.clone();
+                            let breakdown = d.breakdown();
+                            let participation = d.participation();
                             view! {
                                 <div class="bg-gradient-to-r from-amber-50 to-orange-50 \
                                             dark:from-amber-900/20 dark:to-orange-900/20 \
@@ -279,11 +782,92 @@ pub fn Archives() -> impl IntoView {
                                                 "Taona " {sel.to_string()}
                                                 " — voakatona ny " {closed_date}
                                             </p>
-                                            {note.map(|n| view! {
-                                                <p class="text-sm \
-                                                           text-amber-700 dark:text-amber-400 \
-                                                           mt-0.5 italic">
-                                                    {n}
+                                            {move || if note_editing.get() {
+                                                view! {
+                                                    <div class="mt-2 space-y-2">
+                                                        <textarea
+                                                            rows="3"
+                                                            maxlength="1000"
+                                                            aria-label="Fanamarihana"
+                                                            class="w-full px-3 py-2 text-sm rounded-xl \
+                                                                   bg-white/80 dark:bg-gray-800/80 \
+                                                                   border border-amber-200 dark:border-amber-700 \
+                                                                   text-gray-800 dark:text-gray-100 \
+                                                                   focus:outline-none focus:ring-2 focus:ring-amber-400"
+                                                            prop:value=move || note_draft.get()
+                                                            on:input=move |ev| note_draft.set(event_target_value(&ev))
+                                                            on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                                if ev.key() == "Escape" {
+                                                                    note_editing.set(false);
+                                                                }
+                                                            }
+                                                        />
+                                                        <div class="flex gap-2">
+                                                            <button
+                                                                type="button"
+                                                                disabled=move || note_saving.get()
+                                                                on:click=move |_| save_note(sel)
+                                                                class="btn-ripple inline-flex items-center gap-1.5 \
+                                                                       px-3 py-1.5 text-xs font-semibold text-white \
+                                                                       bg-amber-600 hover:bg-amber-700 rounded-xl \
+                                                                       disabled:opacity-60 disabled:cursor-wait \
+                                                                       transition-colors"
+                                                            >
+                                                                <IconSave class="w-3.5 h-3.5" />
+                                                                {move || tr(if note_saving.get() { "action.saving" } else { "action.save" })}
+                                                            </button>
+                                                            <button
+                                                                type="button"
+                                                                on:click=move |_| note_editing.set(false)
+                                                                class="btn-ripple px-3 py-1.5 text-xs font-medium \
+                                                                       text-amber-800 dark:text-amber-200 rounded-xl \
+                                                                       hover:bg-amber-100 dark:hover:bg-amber-800/40 \
+                                                                       transition-colors"
+                                                            >
+                                                                {move || tr("action.cancel")}
+                                                            </button>
+                                                        </div>
+                                                    </div>
+                                                }.into_any()
+                                            } else {
+                                                let shown = note.clone();
+                                                let current = note.clone();
+                                                view! {
+                                                    <div class="flex items-start gap-1.5 mt-0.5">
+                                                        {shown.map(|n| view! {
+                                                            <p class="text-sm \
+                                                                       text-amber-700 dark:text-amber-400 \
+                                                                       italic">
+                                                                {n}
+                                                            </p>
+                                                        })}
+                                                        <button
+                                                            type="button"
+                                                            title="Hanova ny fanamarihana"
+                                                            on:click=move |_| {
+                                                                note_draft.set(current.clone().unwrap_or_default());
+                                                                note_editing.set(true);
+                                                            }
+                                                            class="btn-ripple p-0.5 rounded shrink-0 \
+                                                                   text-amber-600 dark:text-amber-400 \
+                                                                   hover:bg-amber-100 dark:hover:bg-amber-800/40 \
+                                                                   transition-colors"
+                                                        >
+                                                            <IconPencil class="w-3.5 h-3.5" />
+                                                        </button>
+                                                    </div>
+                                                }.into_any()
+                                            }}
+                                            {breakdown.map(|b| view! {
+                                                <p class="text-xs font-medium \
+                                                           text-amber-700/80 dark:text-amber-400/80 \
+                                                           mt-1">
+                                                    {b}
+                                                </p>
+                                            })}
+                                            {participation.map(|p| view! {
+                                                <p class="text-xs text-amber-700/80 dark:text-amber-400/80">
+                                                    {p}
                                                 </p>
                                             })}
                                         </div>
@@ -295,6 +879,37 @@ pub fn Archives() -> impl IntoView {
                                                        text-amber-800 dark:text-amber-200">
                                                 {total_fmt}
                                             </p>
+                                            <button
+                                                type="button"
+                                                disabled=move || printing.get()
+                                                on:click=move |_| {
+                                                    printing.set(true);
+                                                    leptos::task::spawn_local(async move {
+                                                        let res = match db_service::export_year_report(sel).await {
+                                                            Ok(html) => open_print_window(
+                                                                &html,
+                                                                &format!("tahiry-{sel}.html"),
+                                                            ),
+                                                            Err(e) => Err(e),
+                                                        };
+                                                        if let Err(e) = res {
+                                                            toasts.error(e);
+                                                        }
+                                                        printing.set(false);
+                                                    });
+                                                }
+                                                class="btn-ripple mt-2 inline-flex items-center gap-1.5 \
+                                                       px-3 py-1.5 text-xs font-semibold \
+                                                       text-amber-800 dark:text-amber-200 \
+                                                       bg-white/70 dark:bg-amber-900/40 \
+                                                       hover:bg-white dark:hover:bg-amber-900/60 \
+                                                       border border-amber-300 dark:border-amber-700/60 \
+                                                       disabled:opacity-60 disabled:cursor-wait \
+                                                       rounded-xl transition-colors"
+                                            >
+                                                <IconPrinter class="w-3.5 h-3.5" />
+                                                {move || if printing.get() { "Manomana…" } else { "Hanonta" }}
+                                            </button>
                                         </div>
                                     </div>
                                 </div>
@@ -303,7 +918,8 @@ pub fn Archives() -> impl IntoView {
 
                         // ── Badge "En cours" si année ouverte ─────────────────
                         {(!is_closed).then(|| {
-                            let total_opt = detail.as_ref().map(|d| format_ariary(&d.total));
+                            let total_opt = detail.as_ref().map(|d| format_ariary_str(&d.total, MoneyFormat::default()));
+                            let participation = detail.as_ref().and_then(|d| d.participation());
                             view! {
                                 <div class="flex flex-wrap items-center justify-between gap-3 \
                                             bg-emerald-50/70 dark:bg-emerald-900/20 \
@@ -316,19 +932,76 @@ pub fn Archives() -> impl IntoView {
                                                      text-emerald-700 dark:text-emerald-300">
                                             "Taona " {sel.to_string()} " mandeha"
                                         </span>
+                                        {participation.map(|p| view! {
+                                            <span class="text-xs text-emerald-600/80 dark:text-emerald-400/80">
+                                                "· " {p}
+                                            </span>
+                                        })}
                                     </div>
-                                    {total_opt.map(|t| view! {
-                                        <span class="text-sm font-semibold font-mono \
-                                                     text-emerald-700 dark:text-emerald-300">
-                                            {t}
-                                        </span>
-                                    })}
+                                    <div class="flex items-center gap-3">
+                                        {total_opt.map(|t| view! {
+                                            <span class="text-sm font-semibold font-mono \
+                                                         text-emerald-700 dark:text-emerald-300">
+                                                {t}
+                                            </span>
+                                        })}
+                                        <button
+                                            type="button"
+                                            on:click=move |_| adding.set(true)
+                                            class="btn-ripple inline-flex items-center gap-1 px-3 py-1.5 \
+                                                   text-xs font-semibold rounded-xl text-white \
+                                                   bg-emerald-600 hover:bg-emerald-700 \
+                                                   transition-colors shadow-sm"
+                                        >
+                                            <IconPlus class="w-3.5 h-3.5" />
+                                            "Rakitra vaovao"
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        })}
+
+                        // ── Totaux mensuels ───────────────────────────────────
+                        <MonthlyTotalsTable year=sel />
+
+                        // ── Répartition genre / type ──────────────────────────
+                        <YearBreakdownCards year=sel />
+
+                        // ── Vue : cotisations / par membre (année clôturée) ───
+                        {move || (is_closed && recherche_q.get().is_empty()).then(|| {
+                            let tab = move |active: bool, label: &'static str| view! {
+                                <button
+                                    type="button"
+                                    role="tab"
+                                    aria-selected=move || (per_member.get() == active).to_string()
+                                    on:click=move |_| per_member.set(active)
+                                    class=move || format!(
+                                        "px-3 py-1.5 text-xs font-semibold rounded-lg transition-colors {}",
+                                        if per_member.get() == active {
+                                            "bg-white dark:bg-gray-700 text-gray-800 dark:text-white shadow-sm"
+                                        } else {
+                                            "text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-200"
+                                        }
+                                    )
+                                >
+                                    {label}
+                                </button>
+                            };
+                            view! {
+                                <div role="tablist"
+                                     class="inline-flex gap-1 p-1 rounded-xl \
+                                            bg-gray-100/80 dark:bg-gray-800/80">
+                                    {tab(false, "Latsakemboka")}
+                                    {tab(true, "Isaky ny mpikambana")}
                                 </div>
                             }
                         })}
 
                         // ── Tableau des cotisations ───────────────────────────
                         {move || {
+                            if is_closed && per_member.get() && recherche_q.get().is_empty() {
+                                return view! { <YearMemberTotalsTable year=sel /> }.into_any();
+                            }
                             if loading_cont.get() {
                                 return view! {
                                     <div class="flex justify-center py-12">
@@ -338,14 +1011,15 @@ pub fn Archives() -> impl IntoView {
                                     </div>
                                 }.into_any();
                             }
-                            if filtered.get().is_empty() {
-                                let (is_empty_data, msg, sub) = if contributions.get().is_empty() {
+                            if contributions.get().is_empty() {
+                                let (is_empty_data, msg, sub) = if recherche_q.get().is_empty() {
                                     (true, "Tsy misy raki-tsoratra voasoratra",
-                                     format!("ho an'ny taona {}", selected_year.get()))
+                                     format!("ho an'ny taona {}",
+                                             fiscal_year_label(selected_year.get(), fiscal_start)))
                                 } else {
                                     (false, "Tsy misy vokatra",
                                      format!("tsy misy mpikambana mifanaraka amin'ny \"{}\"",
-                                             recherche.get()))
+                                             recherche_q.get()))
                                 };
                                 return view! {
                                     <div class="bg-white/60 dark:bg-gray-800/60 backdrop-blur \
@@ -376,35 +1050,43 @@ pub fn Archives() -> impl IntoView {
                                                            text-gray-600 dark:text-gray-300 \
                                                            text-xs uppercase tracking-wide">
                                                     <th class="text-left px-4 py-3 font-semibold">
-                                                        "Mpikambana"
+                                                        {move || tr("table.member")}
                                                     </th>
                                                     <th class="text-left px-4 py-3 font-semibold \
                                                                hidden sm:table-cell">
-                                                        "Vanim-potoana"
+                                                        {move || tr("table.receipt")}
+                                                    </th>
+                                                    <th class="text-left px-4 py-3 font-semibold \
+                                                               hidden sm:table-cell">
+                                                        {move || tr("table.period")}
                                                     </th>
                                                     <th class="text-right px-4 py-3 font-semibold">
-                                                        "Vola"
+                                                        {move || tr("table.amount")}
                                                     </th>
                                                     <th class="text-right px-4 py-3 font-semibold \
                                                                hidden sm:table-cell">
-                                                        "Daty"
+                                                        {move || tr("table.date")}
                                                     </th>
-                                                    {move || if recherche.get().is_empty() {
+                                                    <th class="text-left px-4 py-3 font-semibold \
+                                                               hidden md:table-cell">
+                                                        {move || tr("table.recorded_by")}
+                                                    </th>
+                                                    {move || if recherche_q.get().is_empty() {
                                                         view! { <th class="hidden" /> }.into_any()
                                                     } else {
                                                         view! {
                                                             <th class="text-right px-4 py-3 font-semibold \
                                                                        hidden sm:table-cell">
-                                                                "Taona"
+                                                                {move || tr("table.year")}
                                                             </th>
                                                         }.into_any()
                                                     }}
                                                     // Colonne Fanovana (audit) — toujours visible
                                                     <th class="text-left px-4 py-3 font-semibold \
                                                                hidden md:table-cell">
-                                                        "Fanovana"
+                                                        {move || tr("table.changes")}
                                                     </th>
-                                                    // Colonne actions (édition) — année ouverte seulement
+                                                    // Colonne actions (édition, suppression) — année ouverte seulement
                                                     {open.then(|| view! {
                                                         <th class="px-3 py-3" />
                                                     })}
@@ -412,10 +1094,15 @@ pub fn Archives() -> impl IntoView {
                                             </thead>
                                             <tbody class="divide-y divide-gray-100 \
                                                           dark:divide-gray-700/50">
-                                                {filtered.get().into_iter().map(|c| {
-                                                    let montant = format_ariary(&c.amount);
+                                                {contributions.get().into_iter().map(|c| {
+                                                    let montant = format_ariary_str(&c.amount, MoneyFormat::default());
+                                                    // Montant retrouvé par la recherche : mis en évidence
+                                                    let montant_trouve = portee.get_untracked().includes_amount()
+                                                        && amount_matches(&recherche_q.get_untracked(), &parse_decimal(&c.amount));
                                                     let audit   = c.audit_summary.clone();
                                                     let c_edit  = c.clone();
+                                                    let receipt = c.receipt_number.clone();
+                                                    let receipt_busy = receipt.clone();
                                                     view! {
                                                         <tr class="tr-hover hover:bg-blue-50/40 \
                                                                    dark:hover:bg-blue-900/10 \
@@ -425,14 +1112,37 @@ pub fn Archives() -> impl IntoView {
                                                                        font-medium">
                                                                 {c.member_name}
                                                             </td>
+                                                            <td class="px-4 py-2.5 hidden sm:table-cell">
+                                                                <button
+                                                                    type="button"
+                                                                    title="Hanonta ny rosia"
+                                                                    disabled=move || printing_receipt.get()
+                                                                        .is_some_and(|r| r == receipt_busy)
+                                                                    on:click=move |_| print_receipt(receipt.clone())
+                                                                    class="inline-flex items-center gap-1 \
+                                                                           font-mono text-xs \
+                                                                           text-gray-500 dark:text-gray-400 \
+                                                                           hover:text-blue-600 dark:hover:text-blue-400 \
+                                                                           disabled:opacity-50 disabled:cursor-wait \
+                                                                           transition-colors"
+                                                                >
+                                                                    <IconPrinter class="w-3 h-3" />
+                                                                    {c.receipt_number.clone()}
+                                                                </button>
+                                                            </td>
                                                             <td class="px-4 py-2.5 \
                                                                        text-gray-500 dark:text-gray-400 \
                                                                        hidden sm:table-cell">
                                                                 {c.period}
                                                             </td>
-                                                            <td class="px-4 py-2.5 text-right \
-                                                                       font-mono font-semibold \
-                                                                       text-gray-800 dark:text-gray-100">
+                                                            <td class=if montant_trouve {
+                                                                "px-4 py-2.5 text-right font-mono font-semibold \
+                                                                 text-amber-700 dark:text-amber-300 \
+                                                                 bg-amber-50/70 dark:bg-amber-900/20"
+                                                            } else {
+                                                                "px-4 py-2.5 text-right font-mono font-semibold \
+                                                                 text-gray-800 dark:text-gray-100"
+                                                            }>
                                                                 {montant}
                                                             </td>
                                                             <td class="px-4 py-2.5 text-right \
@@ -440,7 +1150,12 @@ pub fn Archives() -> impl IntoView {
                                                                        text-xs hidden sm:table-cell">
                                                                 {c.payment_date.clone()}
                                                             </td>
-                                                            {move || if recherche.get().is_empty() {
+                                                            <td class="px-4 py-2.5 text-xs \
+                                                                       text-gray-500 dark:text-gray-400 \
+                                                                       hidden md:table-cell">
+                                                                {c.recorded_by.clone().unwrap_or_else(|| "—".into())}
+                                                            </td>
+                                                            {move || if recherche_q.get().is_empty() {
                                                                 view! { <td class="hidden" /> }.into_any()
                                                             } else {
                                                                 view! {
@@ -457,11 +1172,11 @@ pub fn Archives() -> impl IntoView {
                                                                        italic max-w-[200px] truncate">
                                                                 {audit.unwrap_or_default()}
                                                             </td>
-                                                            // Bouton édition (année ouverte)
+                                                            // Boutons édition / suppression (année ouverte)
                                                             {open.then(|| {
-                                                                let c2 = c_edit.clone();
+                                                                let (c2, c3) = (c_edit.clone(), c_edit.clone());
                                                                 view! {
-                                                                    <td class="px-3 py-2.5 text-center">
+                                                                    <td class="px-3 py-2.5 text-center whitespace-nowrap">
                                                                         <button
                                                                             class="p-1.5 rounded-lg \
                                                                                    text-gray-400 hover:text-blue-600 \
@@ -473,6 +1188,17 @@ pub fn Archives() -> impl IntoView {
                                                                         >
                                                                             <IconPencil class="w-3.5 h-3.5" />
                                                                         </button>
+                                                                        <button
+                                                                            class="p-1.5 rounded-lg \
+                                                                                   text-gray-400 hover:text-red-600 \
+                                                                                   dark:hover:text-red-400 \
+                                                                                   hover:bg-red-50 dark:hover:bg-red-900/20 \
+                                                                                   transition-colors"
+                                                                            title="Fafana"
+                                                                            on:click=move |_| delete_contribution(c3.clone())
+                                                                        >
+                                                                            <IconTrash class="w-3.5 h-3.5" />
+                                                                        </button>
                                                                     </td>
                                                                 }
                                                             })}
@@ -481,15 +1207,26 @@ pub fn Archives() -> impl IntoView {
                                                 }).collect_view()}
                                             </tbody>
                                             // ── Pied de tableau : total ───────
+                                            //    Sans recherche : total réel de l'année (year_summaries),
+                                            //    pas seulement la somme de la page affichée.
+                                            //    Recherche : somme des résultats s'ils tiennent
+                                            //    sur une page, sinon pas de total.
                                             {move || {
-                                                let total: f64 = filtered.get()
-                                                    .iter()
-                                                    .filter_map(|c| c.amount.parse::<f64>().ok())
-                                                    .sum();
-                                                let total_fmt = format_ariary(
-                                                    &format!("{:.0}", total)
+                                                let total = if recherche_q.get().is_empty() {
+                                                    year_detail.get().map(|d| parse_decimal(&d.total))
+                                                } else if total_pages.get() == 1 {
+                                                    Some(contributions.get()
+                                                        .iter()
+                                                        .map(|c| parse_decimal(&c.amount))
+                                                        .sum::<Decimal>())
+                                                } else {
+                                                    None
+                                                };
+                                                let count = cont_total.get();
+                                                let total_fmt = total.map_or_else(
+                                                    || "—".to_string(),
+                                                    |t| format_ariary(&t, MoneyFormat::default()),
                                                 );
-                                                let count = filtered.get().len();
                                                 view! {
                                                     <tfoot>
                                                         <tr class="bg-gray-50/80 dark:bg-gray-700/50 \
@@ -499,15 +1236,27 @@ pub fn Archives() -> impl IntoView {
                                                                        text-gray-500 dark:text-gray-400 \
                                                                        font-medium">
                                                                 {count.to_string()} " raki-tsoratra"
+                                                                {move || {
+                                                                    let n = active_filter_count(&recherche_q.get(), portee.get());
+                                                                    (n > 0).then(|| view! {
+                                                                        <span class="ml-1.5 px-1.5 py-0.5 rounded-md \
+                                                                                     bg-blue-100 dark:bg-blue-900/40 \
+                                                                                     text-blue-700 dark:text-blue-300">
+                                                                            {format!("{n} sivana")}
+                                                                        </span>
+                                                                    })
+                                                                }}
                                                             </td>
                                                             <td class="hidden sm:table-cell" />
+                                                            <td class="hidden sm:table-cell" />
                                                             <td class="px-4 py-2.5 text-right \
                                                                        font-mono font-bold \
                                                                        text-gray-800 dark:text-white">
                                                                 {total_fmt}
                                                             </td>
                                                             <td class="hidden sm:table-cell" />
-                                                            {move || if recherche.get().is_empty() {
+                                                            <td class="hidden md:table-cell" />
+                                                            {move || if recherche_q.get().is_empty() {
                                                                 view! { <td class="hidden" /> }.into_any()
                                                             } else {
                                                                 view! { <td class="hidden sm:table-cell" /> }.into_any()
@@ -522,6 +1271,83 @@ pub fn Archives() -> impl IntoView {
                                             }}
                                         </table>
                                     </div>
+
+                                    // ── Export des lignes affichées ───────────────────
+                                    <div class="flex justify-end px-4 py-2 border-t \
+                                                border-gray-100 dark:border-gray-700">
+                                        <button
+                                            type="button"
+                                            title="Ireo fandoavana hita eto ihany, ho rakitra CSV"
+                                            disabled=move || exporting_sel.get()
+                                            on:click=export_selection
+                                            class="inline-flex items-center gap-1.5 px-3 py-1.5 \
+                                                   text-xs font-medium rounded-lg \
+                                                   text-gray-600 dark:text-gray-300 \
+                                                   hover:bg-gray-100 dark:hover:bg-gray-700 \
+                                                   disabled:opacity-60 disabled:cursor-wait \
+                                                   transition-colors"
+                                        >
+                                            <IconDownload class="w-3.5 h-3.5" />
+                                            {move || if exporting_sel.get() {
+                                                "Manondrana…"
+                                            } else {
+                                                "Hamoaka izay hita (CSV)"
+                                            }}
+                                        </button>
+                                    </div>
+
+                                    // ── Pagination (masquée si page unique) ───────────
+                                    {move || (total_pages.get() > 1).then(|| view! {
+                                        <div class="flex items-center justify-between flex-wrap gap-2 \
+                                                    px-4 py-2.5 border-t border-gray-100 \
+                                                    dark:border-gray-700">
+                                            <span class="text-xs text-gray-500 dark:text-gray-400">
+                                                {move || {
+                                                    let total = cont_total.get();
+                                                    let p     = cont_page.get();
+                                                    let from  = (p * PAGE_SIZE + 1).min(total);
+                                                    let to    = ((p + 1) * PAGE_SIZE).min(total);
+                                                    format!("{from}–{to} amin'ny {total}")
+                                                }}
+                                            </span>
+                                            <div class="flex items-center gap-1">
+                                                <button
+                                                    disabled=move || cont_page.get() == 0
+                                                    on:click=move |_| cont_page.update(|p| *p = (*p - 1).max(0))
+                                                    class="btn-ripple px-3 py-1.5 text-xs rounded-lg \
+                                                           bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                                                           border border-gray-200 dark:border-gray-600 \
+                                                           text-gray-700 dark:text-gray-300 \
+                                                           disabled:opacity-40 disabled:cursor-not-allowed \
+                                                           hover:bg-gray-50 dark:hover:bg-gray-700 transition"
+                                                >
+                                                    <span class="flex items-center gap-1">
+                                                        <IconChevronLeft class="w-3.5 h-3.5" />
+                                                        {move || tr("action.previous")}
+                                                    </span>
+                                                </button>
+                                                <span class="px-3 py-1.5 text-xs font-medium \
+                                                             text-gray-700 dark:text-gray-300">
+                                                    {move || format!("{} / {}", cont_page.get() + 1, total_pages.get())}
+                                                </span>
+                                                <button
+                                                    disabled=move || cont_page.get() + 1 >= total_pages.get()
+                                                    on:click=move |_| cont_page.update(|p| *p += 1)
+                                                    class="btn-ripple px-3 py-1.5 text-xs rounded-lg \
+                                                           bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                                                           border border-gray-200 dark:border-gray-600 \
+                                                           text-gray-700 dark:text-gray-300 \
+                                                           disabled:opacity-40 disabled:cursor-not-allowed \
+                                                           hover:bg-gray-50 dark:hover:bg-gray-700 transition"
+                                                >
+                                                    <span class="flex items-center gap-1">
+                                                        {move || tr("action.next")}
+                                                        <IconChevronRight class="w-3.5 h-3.5" />
+                                                    </span>
+                                                </button>
+                                            </div>
+                                        </div>
+                                    })}
                                 </div>
                             }.into_any()
                         }}
@@ -533,3 +1359,96 @@ pub fn Archives() -> impl IntoView {
         </div>
     }
 }
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(year: i32, total: &str, closed: bool) -> YearSummary {
+        YearSummary {
+            year,
+            total:              total.into(),
+            total_communiant:   String::new(),
+            total_cathekomen:   String::new(),
+            contributor_count:  0,
+            contribution_count: 0,
+            closed_at:          closed.then(|| "2025-01-16T08:00:00".into()),
+            note:               None,
+        }
+    }
+
+    #[test]
+    fn onglets_sans_trou() {
+        assert_eq!(tab_year_range(&[2023, 2021], 2025), [2025, 2024, 2023, 2022, 2021]);
+        assert_eq!(tab_year_range(&[], 2025), [2025]);
+        // Année future déjà saisie : conservée
+        assert_eq!(tab_year_range(&[2026, 2025], 2025), [2026, 2025]);
+    }
+
+    #[test]
+    fn onglets_et_annees_anciennes() {
+        let years = tab_year_range(&[2015], 2025);
+        let (pills, older) = split_tab_years(&years, 2025);
+        assert_eq!(pills, [2025, 2024, 2023, 2022, 2021]);
+        assert_eq!(older, (2015..=2020).rev().collect::<Vec<_>>());
+        // Année suivante avec données : onglet en plus
+        let (pills, older) = split_tab_years(&[2026, 2025, 2024], 2025);
+        assert_eq!((pills, older), (vec![2026, 2025, 2024], vec![]));
+    }
+
+    #[test]
+    fn resumes_vides_hors_plage() {
+        let mut noted = summary(2030, "0", false);
+        noted.note = Some("Asa".into());
+        let list = [summary(2099, "0", false), noted, summary(2024, "0", false), summary(2019, "0", true)];
+        assert_eq!(stray_years(&list, &[2025, 2024]), [2099]);
+    }
+
+    #[test]
+    fn annee_vide() {
+        assert!(!year_has_data(None));
+        assert!(!year_has_data(Some(&summary(2022, "0", false))));
+        assert!(year_has_data(Some(&summary(2022, "0", true))));
+        assert!(year_has_data(Some(&summary(2023, "1500", false))));
+    }
+
+    #[test]
+    fn filtres_actifs() {
+        assert_eq!(active_filter_count("", ContributionSearchScope::All), 0);
+        assert_eq!(active_filter_count("  ", ContributionSearchScope::Amount), 1);
+        assert_eq!(active_filter_count("50 000", ContributionSearchScope::All), 1);
+        assert_eq!(active_filter_count("50 000", ContributionSearchScope::Amount), 2);
+    }
+
+    #[test]
+    fn navigation_clavier() {
+        assert_eq!(tab_key_target("ArrowRight", 0, 3), Some(1));
+        assert_eq!(tab_key_target("ArrowRight", 2, 3), Some(2));
+        assert_eq!(tab_key_target("ArrowLeft", 0, 3), Some(0));
+        assert_eq!(tab_key_target("Home", 2, 3), Some(0));
+        assert_eq!(tab_key_target("End", 0, 3), Some(2));
+        assert_eq!(tab_key_target("Enter", 1, 3), None);
+        assert_eq!(tab_key_target("End", 0, 0), None);
+    }
+
+    #[test]
+    fn csv_de_la_selection() {
+        let c = ContributionWithMember {
+            id:             1,
+            member_id:      7,
+            member_name:    "RABE Jean".into(),
+            payment_date:   "2025-03-14".into(),
+            period:         "2025-03".into(),
+            amount:         "2500.50".into(),
+            recorded_year:  2025,
+            receipt_number: "2025-00001".into(),
+            recorded_by:    None,
+            audit_summary:  None,
+        };
+        let (headers, rows) = selection_csv(&[c]);
+        assert_eq!(headers, ["Mpikambana", "Vanim-potoana", "Vola", "Daty"]);
+        assert_eq!(rows, [["RABE Jean", "2025-03", "2500.50", "2025-03-14"]]);
+    }
+}
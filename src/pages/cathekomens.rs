@@ -1,11 +1,11 @@
 use leptos::prelude::*;
-use crate::components::member_page::MemberPage;
+use crate::{components::member_page::MemberPage, models::member::MemberType};
 
 #[component]
 pub fn Cathekomens() -> impl IntoView {
     view! {
         <MemberPage
-            member_type="Cathekomen"
+            member_type=MemberType::Cathekomen
             icon="book"
             title="Tsy Mpandray"
             subtitle=""
@@ -13,7 +13,7 @@ pub fn Cathekomens() -> impl IntoView {
             row_hover="hover:bg-emerald-50/50 dark:hover:bg-emerald-900/10"
             link_class="text-emerald-600 dark:text-emerald-400"
             spin_class="border-emerald-500"
-            transfer_to="Communiant"
+            transfer_to=MemberType::Communiant
         />
     }
 }
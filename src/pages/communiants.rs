@@ -1,11 +1,11 @@
 use leptos::prelude::*;
-use crate::components::member_page::MemberPage;
+use crate::{components::member_page::MemberPage, models::member::MemberType};
 
 #[component]
 pub fn Communiants() -> impl IntoView {
     view! {
         <MemberPage
-            member_type="Communiant"
+            member_type=MemberType::Communiant
             icon="cross"
             title="Mpandray"
             subtitle=""
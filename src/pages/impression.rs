@@ -0,0 +1,167 @@
+/// Liste imprimable des membres d'un type (`/imprimer/:member_type`).
+///
+/// Tableau noir sur blanc, toutes les lignes sans pagination, pour les réunions
+/// du conseil. Tant que la page est affichée, `PrintModeCtx` masque le ciel, la
+/// barre de titre et la Navbar ; l'impression est lancée dès le chargement.
+use leptos::prelude::*;
+use leptos_router::hooks::{use_navigate, use_params_map};
+use rust_decimal::Decimal;
+
+use crate::{
+    app::{PrintModeCtx, SettingsCtx},
+    components::icons::{IconChevronLeft, IconPrinter},
+    models::member::{Gender, MemberType, MemberWithTotal},
+    services::db_service,
+    utils::{format_ariary, format_ariary_str, money::parse_decimal, sleep_ms, MoneyFormat},
+};
+
+/// Feuille injectée avec la page : marges, pas de coupure dans une ligne,
+/// barre d'outils masquée à l'impression.
+const PRINT_CSS: &str = "\
+@page { size: A4; margin: 12mm; }
+@media print {
+    html, body { background: #fff !important; }
+    .no-print { display: none !important; }
+    thead { display: table-header-group; }
+    tr { break-inside: avoid; }
+}";
+
+fn print_window() {
+    if let Some(w) = web_sys::window() {
+        let _ = w.print();
+    }
+}
+
+fn today() -> String {
+    let d = js_sys::Date::new_0();
+    format!("{:02}/{:02}/{:04}", d.get_date(), d.get_month() + 1, d.get_full_year())
+}
+
+#[component]
+pub fn ImpressionMembres() -> impl IntoView {
+    let print_mode = use_context::<PrintModeCtx>().expect("PrintModeCtx manquant");
+    let settings = use_context::<SettingsCtx>().expect("SettingsCtx manquant");
+    let navigate = use_navigate();
+
+    print_mode.active.set(true);
+    on_cleanup(move || print_mode.active.set(false));
+
+    let params = use_params_map();
+    let member_type = params
+        .with_untracked(|p| p.get("member_type"))
+        .and_then(|s| MemberType::from_slug(&s));
+
+    let membres: RwSignal<Option<Vec<MemberWithTotal>>> = RwSignal::new(None);
+    let erreur:  RwSignal<Option<String>>               = RwSignal::new(None);
+    let current_year = js_sys::Date::new_0().get_full_year() as i32;
+
+    if let Some(t) = member_type {
+        leptos::task::spawn_local(async move {
+            match db_service::get_members_by_type_with_total(t, None).await {
+                Ok(mut liste) => {
+                    liste.sort_by_cached_key(|m| m.sort_name().to_lowercase());
+                    membres.set(Some(liste));
+                    // Laisse le tableau se rendre avant d'ouvrir la boîte d'impression
+                    sleep_ms(100).await;
+                    print_window();
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+        });
+    }
+
+    let retour = member_type.map_or("/", |t| t.list_path());
+    let church = move || settings.settings.get().map(|s| s.church_name).unwrap_or_default();
+
+    view! {
+        <style>{PRINT_CSS}</style>
+        <div class="bg-white text-black min-h-screen p-6 sm:p-8 rounded-none">
+            <div class="no-print flex items-center justify-between gap-3 mb-6">
+                <button
+                    type="button"
+                    on:click=move |_| navigate(retour, Default::default())
+                    class="btn-ripple inline-flex items-center gap-1.5 px-3 py-2 text-sm font-medium \
+                           text-gray-700 hover:bg-gray-100 rounded-xl transition-colors"
+                >
+                    <IconChevronLeft class="w-4 h-4" />
+                    "Hiverina"
+                </button>
+                <button
+                    type="button"
+                    on:click=move |_| print_window()
+                    disabled=move || membres.with(Option::is_none)
+                    class="btn-ripple inline-flex items-center gap-1.5 px-4 py-2 text-sm font-semibold \
+                           text-white bg-gray-800 hover:bg-gray-900 \
+                           disabled:opacity-50 rounded-xl transition-colors"
+                >
+                    <IconPrinter class="w-4 h-4" />
+                    "Hanonta"
+                </button>
+            </div>
+
+            {match member_type {
+                None => view! {
+                    <p class="text-center mt-20">"Karazana mpikambana tsy fantatra"</p>
+                }.into_any(),
+                Some(t) => view! {
+                    <header class="mb-4 border-b-2 border-black pb-2">
+                        <p class="text-xs uppercase tracking-wide">{church}</p>
+                        <h1 class="text-xl font-bold">{format!("Lisitry ny {}", t.label())}</h1>
+                        <p class="text-xs">
+                            {move || {
+                                let n = membres.with(|m| m.as_ref().map_or(0, Vec::len));
+                                format!("{n} mpikambana — {}", today())
+                            }}
+                        </p>
+                    </header>
+                    {move || erreur.get().map(|e| view! { <p class="text-sm">{e}</p> })}
+                    {move || membres.get().map(|liste| {
+                        let total: Decimal = liste.iter().map(|m| parse_decimal(&m.current_year_total)).sum();
+                        view! {
+                            <table class="w-full text-xs border-collapse">
+                                <thead>
+                                    <tr class="border-b border-black text-left">
+                                        <th class="py-1 pr-2">"#"</th>
+                                        <th class="py-1 pr-2">"Karatra"</th>
+                                        <th class="py-1 pr-2">"Anarana"</th>
+                                        <th class="py-1 pr-2">"Adiresy"</th>
+                                        <th class="py-1 pr-2">"Finday"</th>
+                                        <th class="py-1 pr-2">"Asa"</th>
+                                        <th class="py-1 pr-2">"Lahy/Vavy"</th>
+                                        <th class="py-1 text-right">{format!("Taona {current_year}")}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {liste.into_iter().enumerate().map(|(i, m)| view! {
+                                        <tr class="border-b border-gray-300">
+                                            <td class="py-1 pr-2">{i + 1}</td>
+                                            <td class="py-1 pr-2 font-mono">{m.card_number}</td>
+                                            <td class="py-1 pr-2 font-semibold">{m.full_name}</td>
+                                            <td class="py-1 pr-2">{m.address.unwrap_or_default()}</td>
+                                            <td class="py-1 pr-2 whitespace-nowrap">{m.phone.unwrap_or_default()}</td>
+                                            <td class="py-1 pr-2">{m.job.unwrap_or_default()}</td>
+                                            <td class="py-1 pr-2">
+                                                {match m.gender { Gender::M => "Lahy", Gender::F => "Vavy" }}
+                                            </td>
+                                            <td class="py-1 text-right font-mono whitespace-nowrap">
+                                                {format_ariary_str(&m.current_year_total, MoneyFormat::default())}
+                                            </td>
+                                        </tr>
+                                    }).collect_view()}
+                                </tbody>
+                                <tfoot>
+                                    <tr class="border-t-2 border-black font-bold">
+                                        <td class="py-1" colspan="7">"Totaly"</td>
+                                        <td class="py-1 text-right font-mono whitespace-nowrap">
+                                            {format_ariary(&total, MoneyFormat::default())}
+                                        </td>
+                                    </tr>
+                                </tfoot>
+                            </table>
+                        }
+                    })}
+                }.into_any(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,177 @@
+/// Journal d'audit — lecture seule : créations, modifications, suppressions,
+/// transferts de membres, cotisations, clôtures et réouvertures d'années,
+/// les plus récentes d'abord, filtrables par type d'élément.
+use leptos::prelude::*;
+
+use crate::{
+    app::DataVersionCtx,
+    components::{
+        icons::{IconAlertTriangle, IconFileText},
+        maintenance_panel::format_datetime,
+    },
+    i18n::tr,
+    models::audit::{AuditEntry, ENTITY_FILTERS},
+    services::db_service,
+    utils::RequestGen,
+};
+
+/// Entrées chargées à chaque page.
+const PAGE_SIZE: i64 = 50;
+
+/// Couleur du badge d'action.
+fn action_class(action: &str) -> &'static str {
+    match action {
+        "create"            => "bg-emerald-50 dark:bg-emerald-900/30 text-emerald-700 dark:text-emerald-300",
+        "delete"            => "bg-red-50 dark:bg-red-900/30 text-red-700 dark:text-red-300",
+        "close" | "reopen"  => "bg-amber-50 dark:bg-amber-900/30 text-amber-700 dark:text-amber-300",
+        _                   => "bg-blue-50 dark:bg-blue-900/30 text-blue-700 dark:text-blue-300",
+    }
+}
+
+#[component]
+pub fn Journal() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+
+    let entity:  RwSignal<String>          = RwSignal::new(String::new());
+    let entries: RwSignal<Vec<AuditEntry>> = RwSignal::new(vec![]);
+    let total:   RwSignal<i64>             = RwSignal::new(0);
+    let loading: RwSignal<bool>            = RwSignal::new(false);
+    let erreur:  RwSignal<Option<String>>  = RwSignal::new(None);
+    let gen = RequestGen::new();
+
+    // `offset` 0 : remplace la liste ; sinon ajoute la page suivante
+    let load = move |offset: i64| {
+        let g = gen.next();
+        let filter = entity.get_untracked();
+        loading.set(true);
+        leptos::task::spawn_local(async move {
+            let entity = (!filter.is_empty()).then_some(filter.as_str());
+            let res = db_service::get_audit_log(PAGE_SIZE, offset, entity).await;
+            if !gen.is_current(g) {
+                return;
+            }
+            match res {
+                Ok(page) => {
+                    erreur.set(None);
+                    total.set(page.total_count);
+                    if offset == 0 {
+                        entries.set(page.items);
+                    } else {
+                        entries.update(|list| list.extend(page.items));
+                    }
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        entity.track();
+        load(0);
+    });
+
+    view! {
+        <div class="animate-fade-in space-y-4 max-w-3xl mx-auto">
+            <div class="flex flex-wrap items-end justify-between gap-3">
+                <div>
+                    <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                               flex items-center gap-2">
+                        <IconFileText class="w-6 h-6 text-gray-600 dark:text-gray-400" />
+                        {move || tr("nav.journal")}
+                    </h1>
+                    <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5">
+                        {move || format!("Asa {} voasoratra", total.get())}
+                    </p>
+                </div>
+                <select
+                    aria-label="Sivana"
+                    class="px-3 py-2 text-sm bg-white/70 dark:bg-gray-800/70 \
+                           border border-gray-200 dark:border-gray-600 rounded-xl \
+                           text-gray-700 dark:text-gray-200 \
+                           focus:outline-none focus:ring-2 focus:ring-blue-400"
+                    prop:value=move || entity.get()
+                    on:change=move |ev| entity.set(event_target_value(&ev))
+                >
+                    {ENTITY_FILTERS.iter().map(|(value, label)| view! {
+                        <option value=*value>{*label}</option>
+                    }).collect_view()}
+                </select>
+            </div>
+
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+
+            {move || {
+                let list = entries.get();
+                if list.is_empty() {
+                    return (!loading.get()).then(|| view! {
+                        <p class="text-center text-sm text-gray-500 dark:text-gray-400 py-10">
+                            {move || tr("empty.journal")}
+                        </p>
+                    }).into_any();
+                }
+                view! {
+                    <ul class="divide-y divide-gray-100 dark:divide-gray-700/60 \
+                               bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                               rounded-2xl border border-gray-100 dark:border-gray-700 \
+                               shadow-sm">
+                        {list.into_iter().map(|e| view! {
+                            <li class="flex items-start gap-3 px-4 py-2.5 text-sm">
+                                <span class=format!(
+                                    "shrink-0 mt-0.5 px-2 py-0.5 text-xs font-semibold rounded-lg {}",
+                                    action_class(&e.action),
+                                )>
+                                    {e.action_label().to_string()}
+                                </span>
+                                <div class="min-w-0 flex-1">
+                                    <p class="font-medium text-gray-800 dark:text-white">
+                                        {e.target_label()}
+                                    </p>
+                                    {e.details_summary().map(|d| view! {
+                                        <p class="text-xs text-gray-500 dark:text-gray-400 break-words">
+                                            {d}
+                                        </p>
+                                    })}
+                                </div>
+                                <span class="shrink-0 text-xs text-gray-400 font-mono">
+                                    {format_datetime(&e.timestamp)}
+                                </span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+
+            {move || if loading.get() {
+                view! {
+                    <div class="flex justify-center py-4">
+                        <div class="w-6 h-6 border-4 border-blue-400 \
+                                    border-t-transparent rounded-full animate-spin" />
+                    </div>
+                }.into_any()
+            } else {
+                let shown = entries.with(Vec::len) as i64;
+                (shown < total.get()).then(|| view! {
+                    <div class="flex justify-center">
+                        <button
+                            type="button"
+                            on:click=move |_| load(shown)
+                            class="btn-ripple px-4 py-2 text-sm font-medium \
+                                   text-blue-600 dark:text-blue-400 \
+                                   hover:bg-blue-50 dark:hover:bg-blue-900/30 \
+                                   rounded-xl transition-colors"
+                        >
+                            {move || tr("action.load_more")}
+                        </button>
+                    </div>
+                }).into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,379 @@
+/// Fiche d'un membre (`/membre/:id`) : tous ses champs, ses notes, ses cotisations
+/// groupées par année d'enregistrement avec sous-totaux, modification et suppression.
+use leptos::prelude::*;
+use leptos_router::{
+    components::A,
+    hooks::{use_navigate, use_params_map},
+};
+
+use crate::{
+    app::{DataVersionCtx, PinCtx, ToastProviderCtx},
+    components::{
+        confirm_dialog::{use_confirm, ConfirmOptions},
+        icons::{IconAlertTriangle, IconChevronLeft, IconPencil, IconTrash, PageIcon},
+        maintenance_panel::format_datetime,
+        member_form::MemberForm,
+        member_notes::MemberNotes,
+    },
+    i18n::tr,
+    models::{
+        contribution::{Contribution, MemberYearTotal},
+        member::{Gender, Member, MemberStatus, MemberType},
+    },
+    services::db_service,
+    utils::{format_ariary_str, MoneyFormat, RequestGen},
+};
+
+/// Sous-total d'une année et ses cotisations.
+type YearGroup = (MemberYearTotal, Vec<Contribution>);
+
+/// Cotisations réparties sous le sous-total de leur année, dans l'ordre de
+/// `totals` (plus récente d'abord) ; l'ordre des cotisations est conservé.
+fn group_by_year(contributions: Vec<Contribution>, totals: Vec<MemberYearTotal>) -> Vec<YearGroup> {
+    let mut groups: Vec<YearGroup> = totals.into_iter().map(|t| (t, vec![])).collect();
+    for c in contributions {
+        if let Some((_, list)) = groups.iter_mut().find(|(t, _)| t.year == c.recorded_year) {
+            list.push(c);
+        }
+    }
+    groups
+}
+
+fn page_icon(member_type: MemberType) -> &'static str {
+    match member_type {
+        MemberType::Communiant => "cross",
+        MemberType::Cathekomen => "book",
+    }
+}
+
+#[component]
+pub fn MemberDetail() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let toasts = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let pin = use_context::<PinCtx>().expect("PinCtx manquant");
+    let confirm = use_confirm();
+    let navigate = use_navigate();
+
+    let params = use_params_map();
+    let member_id = Memo::new(move |_| {
+        params.with(|p| p.get("id")).and_then(|id| id.parse::<i64>().ok())
+    });
+
+    let member:  RwSignal<Option<Member>> = RwSignal::new(None);
+    let groups:  RwSignal<Vec<YearGroup>> = RwSignal::new(vec![]);
+    let loading: RwSignal<bool>           = RwSignal::new(true);
+    let erreur:  RwSignal<Option<String>> = RwSignal::new(None);
+    let gen = RequestGen::new();
+    let loaded_id = Memo::new(move |_| member.with(|m| m.as_ref().map(|m| m.id)));
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        let Some(id) = member_id.get() else {
+            erreur.set(Some("Mpikambana tsy hita".into()));
+            loading.set(false);
+            return;
+        };
+        let g = gen.next();
+        leptos::task::spawn_local(async move {
+            let res = async {
+                let m = db_service::get_member(id).await?;
+                let contributions = db_service::get_contributions(id).await?;
+                let totals = db_service::get_member_year_totals(id).await?;
+                Ok::<_, String>((m, group_by_year(contributions, totals)))
+            }
+            .await;
+            if !gen.is_current(g) {
+                return;
+            }
+            match res {
+                Ok((m, g)) => {
+                    erreur.set(None);
+                    member.set(Some(m));
+                    groups.set(g);
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    });
+
+    // ── Formulaire de modification ─────────────────────────────────────────────
+    let modal_ouvert: RwSignal<bool>        = RwSignal::new(false);
+    let edit_id:      RwSignal<Option<i64>> = RwSignal::new(None);
+    let f_carte:     RwSignal<String> = RwSignal::new(String::new());
+    let f_nom:       RwSignal<String> = RwSignal::new(String::new());
+    let f_adresse:   RwSignal<String> = RwSignal::new(String::new());
+    let f_telephone: RwSignal<String> = RwSignal::new(String::new());
+    let f_travail:   RwSignal<String> = RwSignal::new(String::new());
+    let f_genre:     RwSignal<Gender> = RwSignal::new(Gender::M);
+    let f_naissance: RwSignal<String> = RwSignal::new(String::new());
+    let f_statut:    RwSignal<MemberStatus> = RwSignal::new(MemberStatus::Actif);
+    let f_loading:   RwSignal<bool>   = RwSignal::new(false);
+
+    let open_edit = move |_| {
+        let Some(m) = member.get_untracked() else { return };
+        edit_id.set(Some(m.id));
+        f_carte.set(m.card_number);
+        f_nom.set(m.full_name);
+        f_adresse.set(m.address.unwrap_or_default());
+        f_telephone.set(m.phone.unwrap_or_default());
+        f_travail.set(m.job.unwrap_or_default());
+        f_genre.set(m.gender);
+        f_naissance.set(m.birth_date.unwrap_or_default());
+        f_statut.set(m.status);
+        modal_ouvert.set(true);
+    };
+
+    // ── Suppression : retour à la liste, annulable depuis le toast ─────────────
+    let do_delete = move |_| {
+        let Some(m) = member.get_untracked() else { return };
+        let navigate = navigate.clone();
+        leptos::task::spawn_local(async move {
+            let ok = confirm.ask(ConfirmOptions {
+                title:         "Hamafa mpikambana".into(),
+                message:       format!("Fafana i {} ? Voatahiry ny rakitrany ary azo averina ao amin'ny Fikirana izy.", m.full_name),
+                confirm_label: "Fafana".into(),
+                danger:        true,
+            }).await;
+            if !ok {
+                return;
+            }
+            pin.guard(move |token| {
+                leptos::task::spawn_local(async move {
+                    match db_service::delete_member(m.id, token.as_deref()).await {
+                        Ok(()) => {
+                            data_version.bump();
+                            let mid = m.id;
+                            toasts.undoable(format!("Voafafa i {}", m.full_name), move || {
+                                leptos::task::spawn_local(async move {
+                                    match db_service::restore_member(mid).await {
+                                        Ok(m)  => {
+                                            data_version.bump();
+                                            toasts.success(format!("Naverina i {}", m.full_name));
+                                        }
+                                        Err(e) => toasts.error(e),
+                                    }
+                                });
+                            });
+                            navigate(m.member_type.list_path(), Default::default());
+                        }
+                        Err(e) => toasts.error(e),
+                    }
+                });
+            });
+        });
+    };
+
+    view! {
+        <div class="animate-fade-in space-y-4 max-w-3xl mx-auto">
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+
+            {move || if loading.get() && member.get().is_none() {
+                view! {
+                    <div class="flex justify-center py-16">
+                        <div class="w-8 h-8 border-4 border-blue-500 \
+                                    border-t-transparent rounded-full animate-spin" />
+                    </div>
+                }.into_any()
+            } else {
+                member.get().map(|m| view! {
+                    // ── En-tête ────────────────────────────────────────────────
+                    <div class="flex flex-wrap items-start justify-between gap-3">
+                        <div class="min-w-0">
+                            <A
+                                href=m.member_type.list_path()
+                                attr:class="inline-flex items-center gap-1 text-xs font-medium \
+                                            text-blue-600 dark:text-blue-400 hover:underline"
+                            >
+                                <IconChevronLeft class="w-3.5 h-3.5" />
+                                {m.member_type.label()}
+                            </A>
+                            <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                                       flex items-center gap-2 mt-1">
+                                <PageIcon name=page_icon(m.member_type) class="w-6 h-6 text-gray-600 dark:text-gray-400" />
+                                {m.full_name.clone()}
+                            </h1>
+                            <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5 font-mono">
+                                {m.card_number.clone()}
+                            </p>
+                        </div>
+                        <div class="flex items-center gap-2 shrink-0">
+                            <button
+                                type="button"
+                                on:click=open_edit
+                                class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs sm:text-sm \
+                                       font-semibold text-blue-600 dark:text-blue-400 \
+                                       hover:bg-blue-50 dark:hover:bg-blue-900/30 \
+                                       rounded-xl transition-colors"
+                            >
+                                <IconPencil class="w-4 h-4" />
+                                "Hanova"
+                            </button>
+                            <button
+                                type="button"
+                                on:click=do_delete.clone()
+                                class="btn-ripple flex items-center gap-1.5 px-3 py-2 text-xs sm:text-sm \
+                                       font-semibold text-red-600 dark:text-red-400 \
+                                       hover:bg-red-50 dark:hover:bg-red-900/30 \
+                                       rounded-xl transition-colors"
+                            >
+                                <IconTrash class="w-4 h-4" />
+                                "Fafana"
+                            </button>
+                        </div>
+                    </div>
+
+                    // ── Champs ─────────────────────────────────────────────────
+                    <dl class="grid grid-cols-1 sm:grid-cols-2 gap-x-6 gap-y-3 px-4 py-4 text-sm \
+                               bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                               rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+                        {[
+                            (tr("table.address"), m.address.clone()),
+                            (tr("table.phone"), m.phone.clone()),
+                            (tr("table.job"), m.job.clone()),
+                            (tr("table.gender"), Some(m.gender.label().to_string())),
+                            ("Daty nahaterahana".to_string(), m.birth_date.clone()),
+                            ("Nisoratra".to_string(), Some(format_datetime(&m.created_at))),
+                            ("Nafindra".to_string(), m.transferred_at.as_deref().map(format_datetime)),
+                        ].into_iter().map(|(label, value)| view! {
+                            <div>
+                                <dt class="text-xs text-gray-500 dark:text-gray-400">{label}</dt>
+                                <dd class="text-gray-800 dark:text-white break-words">
+                                    {value.unwrap_or_else(|| "—".into())}
+                                </dd>
+                            </div>
+                        }).collect_view()}
+                    </dl>
+                })
+                .into_any()
+            }}
+
+            // ── Notes (rechargées seulement si l'on change de membre) ───────────
+            {move || loaded_id.get().map(|id| view! { <MemberNotes member_id=id /> })}
+
+            // ── Cotisations par année ──────────────────────────────────────────
+            {move || {
+                // Rien tant que la fiche n'est pas chargée
+                member.with(|m| m.as_ref().map(|m| m.id))?;
+                let list = groups.get();
+                if list.is_empty() {
+                    return Some(view! {
+                        <p class="text-center text-sm text-gray-500 dark:text-gray-400 py-10">
+                            "Mbola tsy nandoa rakitra"
+                        </p>
+                    }.into_any());
+                }
+                Some(view! {
+                    <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                                rounded-2xl border border-gray-100 dark:border-gray-700 \
+                                overflow-hidden shadow-sm">
+                        <div class="overflow-x-auto">
+                            <table class="w-full text-sm">
+                                <thead>
+                                    <tr class="bg-gray-50/80 dark:bg-gray-900/50 \
+                                               border-b border-gray-100 dark:border-gray-700 \
+                                               text-gray-600 dark:text-gray-400 font-semibold text-left">
+                                        <th class="px-4 py-3">{move || tr("table.date")}</th>
+                                        <th class="px-3 py-3">{move || tr("table.period")}</th>
+                                        <th class="px-3 py-3 hidden sm:table-cell">{move || tr("table.receipt")}</th>
+                                        <th class="px-4 py-3 text-right">{move || tr("table.amount")}</th>
+                                    </tr>
+                                </thead>
+                                {list.into_iter().map(|(total, contributions)| view! {
+                                    <tbody>
+                                        <tr class="bg-blue-50/60 dark:bg-blue-900/20 \
+                                                   text-gray-800 dark:text-white font-semibold">
+                                            <td class="px-4 py-2" colspan="3">
+                                                {format!("{} · rakitra {}", total.year, total.contribution_count)}
+                                            </td>
+                                            <td class="px-4 py-2 text-right font-mono whitespace-nowrap">
+                                                {format_ariary_str(&total.total, MoneyFormat::default())}
+                                            </td>
+                                        </tr>
+                                        {contributions.into_iter().map(|c| view! {
+                                            <tr class="border-b border-gray-50 dark:border-gray-700/50 \
+                                                       text-gray-600 dark:text-gray-300">
+                                                <td class="px-4 py-2 whitespace-nowrap">{c.payment_date}</td>
+                                                <td class="px-3 py-2 whitespace-nowrap">{c.period}</td>
+                                                <td class="px-3 py-2 hidden sm:table-cell font-mono text-xs">
+                                                    {c.receipt_number}
+                                                </td>
+                                                <td class="px-4 py-2 text-right font-mono whitespace-nowrap">
+                                                    {format_ariary_str(&c.amount, MoneyFormat::default())}
+                                                </td>
+                                            </tr>
+                                        }).collect_view()}
+                                    </tbody>
+                                }).collect_view()}
+                            </table>
+                        </div>
+                    </div>
+                }.into_any())
+            }}
+
+            // ── Modal formulaire ───────────────────────────────────────────────
+            {move || {
+                let member_type = member.with(|m| m.as_ref().map(|m| m.member_type))?;
+                modal_ouvert.get().then(|| view! {
+                    <MemberForm
+                        open=modal_ouvert
+                        edit_id=edit_id
+                        member_type=member_type
+                        btn_class="bg-blue-600 hover:bg-blue-700"
+                        refresh_ctr=data_version.version
+                        f_carte=f_carte
+                        f_nom=f_nom
+                        f_adresse=f_adresse
+                        f_telephone=f_telephone
+                        f_travail=f_travail
+                        f_genre=f_genre
+                        f_naissance=f_naissance
+                        f_statut=f_statut
+                        f_loading=f_loading
+                    />
+                })
+            }}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contribution(id: i64, year: i32) -> Contribution {
+        Contribution {
+            id,
+            member_id:      1,
+            payment_date:   format!("{year}-01-0{id}"),
+            period:         year.to_string(),
+            amount:         "1000".into(),
+            recorded_year:  year,
+            receipt_number: format!("{year}-0000{id}"),
+            recorded_by:    None,
+        }
+    }
+
+    fn total(year: i32, count: i64) -> MemberYearTotal {
+        MemberYearTotal { year, total: (count * 1000).to_string(), contribution_count: count }
+    }
+
+    #[test]
+    fn test_group_by_year() {
+        let groups = group_by_year(
+            vec![contribution(3, 2025), contribution(2, 2023), contribution(1, 2025)],
+            vec![total(2025, 2), total(2023, 1)],
+        );
+        let ids: Vec<(i32, Vec<i64>)> = groups
+            .iter()
+            .map(|(t, list)| (t.year, list.iter().map(|c| c.id).collect()))
+            .collect();
+        assert_eq!(ids, vec![(2025, vec![3, 1]), (2023, vec![2])]);
+        assert!(group_by_year(vec![], vec![]).is_empty());
+    }
+}
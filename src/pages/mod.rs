@@ -2,4 +2,11 @@ pub mod accueil;
 pub mod archives;
 pub mod cathekomens;
 pub mod communiants;
+pub mod impression;
+pub mod journal;
+pub mod member_detail;
+pub mod parametres;
+pub mod recherche;
+pub mod saisie;
 pub mod setup;
+pub mod statistiques;
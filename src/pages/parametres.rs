@@ -0,0 +1,517 @@
+/// Page Paramètres — informations de l'église, réglages généraux, affichage de ce
+/// poste, code PIN admin, corbeille des membres et état de la base.
+///
+/// Le formulaire est chargé depuis `get_settings` et enregistré d'un bloc
+/// (`update_settings`) ; `SettingsCtx` est mis à jour pour la barre de titre.
+/// L'affichage (animations réduites) reste propre au poste : `MotionCtx`, localStorage.
+use leptos::prelude::*;
+
+use crate::{
+    app::SettingsCtx,
+    components::{
+        db_health_panel::DbHealthPanel,
+        icons::{IconAlertTriangle, IconSave, IconSettings},
+        member_trash::MemberTrash,
+        pin_settings::PinSettings,
+    },
+    i18n::tr,
+    models::{settings::Settings, year_summary::MONTH_NAMES},
+    services::db_service,
+    theme::MotionCtx,
+    utils::{
+        amount::{format_amount_list, parse_amount_list},
+        use_reduced_motion,
+    },
+};
+
+const LABEL: &str = "block text-xs font-semibold text-gray-600 dark:text-gray-400 mb-1";
+const INPUT: &str = "w-full px-3 py-2 text-sm \
+                     bg-gray-50 dark:bg-gray-700/60 \
+                     border border-gray-200 dark:border-gray-600 \
+                     rounded-xl text-gray-800 dark:text-white \
+                     placeholder-gray-400 dark:placeholder-gray-500 \
+                     focus:outline-none focus:ring-2 focus:ring-blue-400 transition";
+
+#[component]
+pub fn Parametres() -> impl IntoView {
+    let ctx = use_context::<SettingsCtx>().expect("SettingsCtx manquant");
+    let motion = use_context::<MotionCtx>().expect("MotionCtx manquant");
+    let reduced = use_reduced_motion();
+
+    let f_nom:       RwSignal<String> = RwSignal::new(String::new());
+    let f_adresse:   RwSignal<String> = RwSignal::new(String::new());
+    let f_tresorier: RwSignal<String> = RwSignal::new(String::new());
+    let f_monnaie:   RwSignal<String> = RwSignal::new(String::new());
+    let f_adidy:     RwSignal<String> = RwSignal::new(String::new());
+    let f_retention: RwSignal<String> = RwSignal::new(String::new());
+    let f_trimestre: RwSignal<bool>   = RwSignal::new(true);
+    let f_integrite: RwSignal<bool>   = RwSignal::new(true);
+    let f_cloture:   RwSignal<bool>   = RwSignal::new(true);
+    let f_delai:     RwSignal<String> = RwSignal::new(String::new());
+    let f_avance:    RwSignal<String> = RwSignal::new(String::new());
+    let f_renouvellement: RwSignal<String> = RwSignal::new(String::new());
+    let f_exercice:  RwSignal<u32>    = RwSignal::new(1);
+    let f_periode:   RwSignal<String> = RwSignal::new(String::new());
+    let f_montants:  RwSignal<String> = RwSignal::new(String::new());
+    let f_carte_c:   RwSignal<String> = RwSignal::new(String::new());
+    let f_carte_k:   RwSignal<String> = RwSignal::new(String::new());
+    let f_operateurs: RwSignal<String> = RwSignal::new(String::new());
+
+    let loading: RwSignal<bool>           = RwSignal::new(true);
+    let saving:  RwSignal<bool>           = RwSignal::new(false);
+    let erreur:  RwSignal<Option<String>> = RwSignal::new(None);
+    let succes:  RwSignal<bool>           = RwSignal::new(false);
+
+    let fill = move |s: Settings| {
+        f_nom.set(s.church_name);
+        f_adresse.set(s.church_address);
+        f_tresorier.set(s.treasurer_name);
+        f_monnaie.set(s.currency_label);
+        f_adidy.set(s.default_pledge);
+        f_retention.set(s.backup_retention.to_string());
+        f_trimestre.set(s.quarter_periods);
+        f_integrite.set(s.startup_integrity_check);
+        f_cloture.set(s.auto_close_enabled);
+        f_delai.set(s.auto_close_grace_days.to_string());
+        f_avance.set(s.max_future_days.to_string());
+        f_renouvellement.set(s.renewal_window_days.to_string());
+        f_exercice.set(s.fiscal_year_start_month);
+        f_periode.set(s.default_period);
+        f_montants.set(format_amount_list(&s.quick_amounts));
+        f_carte_c.set(s.card_pattern_communiant);
+        f_carte_k.set(s.card_pattern_cathekomen);
+        f_operateurs.set(s.operators.join(", "));
+    };
+
+    leptos::task::spawn_local(async move {
+        match db_service::get_settings().await {
+            Ok(s)  => fill(s),
+            Err(e) => erreur.set(Some(e)),
+        }
+        loading.set(false);
+    });
+
+    let soumettre = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        erreur.set(None);
+        succes.set(false);
+        let Ok(backup_retention) = f_retention.get().trim().parse::<usize>() else {
+            erreur.set(Some("Isa tsy mety ny tahiry tazonina.".into()));
+            return;
+        };
+        let Ok(auto_close_grace_days) = f_delai.get().trim().parse::<u32>() else {
+            erreur.set(Some("Isa tsy mety ny andro alohan'ny fanakatonana.".into()));
+            return;
+        };
+        let Ok(max_future_days) = f_avance.get().trim().parse::<u32>() else {
+            erreur.set(Some("Isa tsy mety ny andro mialoha azo ekena.".into()));
+            return;
+        };
+        let Ok(renewal_window_days) = f_renouvellement.get().trim().parse::<u32>() else {
+            erreur.set(Some("Isa tsy mety ny andro fampahatsiahivana fanavaozana karatra.".into()));
+            return;
+        };
+        let input = Settings {
+            church_name:    f_nom.get().trim().to_string(),
+            church_address: f_adresse.get().trim().to_string(),
+            treasurer_name: f_tresorier.get().trim().to_string(),
+            currency_label: f_monnaie.get().trim().to_string(),
+            default_pledge: f_adidy.get().trim().replace([' ', '\u{a0}'], ""),
+            backup_retention,
+            quarter_periods: f_trimestre.get(),
+            startup_integrity_check: f_integrite.get(),
+            auto_close_enabled: f_cloture.get(),
+            auto_close_grace_days,
+            max_future_days,
+            renewal_window_days,
+            fiscal_year_start_month: f_exercice.get(),
+            default_period: f_periode.get().trim().to_string(),
+            quick_amounts: parse_amount_list(&f_montants.get()),
+            card_pattern_communiant: f_carte_c.get().trim().to_string(),
+            card_pattern_cathekomen: f_carte_k.get().trim().to_string(),
+            operators: f_operateurs
+                .get()
+                .split(',')
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        saving.set(true);
+        leptos::task::spawn_local(async move {
+            match db_service::update_settings(&input).await {
+                Ok(saved) => {
+                    fill(saved.clone());
+                    ctx.settings.set(Some(saved));
+                    succes.set(true);
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+            saving.set(false);
+        });
+    };
+
+    view! {
+        <div class="animate-fade-in space-y-4 sm:space-y-6">
+
+            // ── En-tête ───────────────────────────────────────────────────────
+            <div>
+                <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                            flex items-center gap-2">
+                    <IconSettings class="w-6 h-6 text-gray-600 dark:text-gray-400" />
+                    "Fikirana"
+                </h1>
+                <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5 sm:mt-1">
+                    "Mombamomba ny fiangonana sy ny fandrindrana ankapobeny."
+                </p>
+            </div>
+
+            // ── Formulaire ────────────────────────────────────────────────────
+            <form
+                on:submit=soumettre
+                class="max-w-xl space-y-4 p-4 sm:p-5 \
+                       bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                       rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm"
+            >
+                {move || erreur.get().map(|e| view! {
+                    <div class="flex items-start gap-2 p-3 text-xs rounded-xl \
+                                bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-300 \
+                                border border-red-200 dark:border-red-700/50">
+                        <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                        <span>{e}</span>
+                    </div>
+                })}
+                {move || succes.get().then(|| view! {
+                    <div class="p-3 text-xs rounded-xl \
+                                bg-emerald-50 dark:bg-emerald-900/20 text-emerald-700 dark:text-emerald-300 \
+                                border border-emerald-200 dark:border-emerald-700/50">
+                        "Voatahiry ny fikirana."
+                    </div>
+                })}
+
+                <div>
+                    <label class=LABEL for="settings-nom">"Anaran'ny fiangonana *"</label>
+                    <input
+                        id="settings-nom"
+                        type="text"
+                        class=INPUT
+                        required
+                        disabled=move || loading.get()
+                        prop:value=move || f_nom.get()
+                        on:input=move |ev| f_nom.set(event_target_value(&ev))
+                    />
+                </div>
+                <div>
+                    <label class=LABEL for="settings-adresse">"Adiresy"</label>
+                    <input
+                        id="settings-adresse"
+                        type="text"
+                        class=INPUT
+                        disabled=move || loading.get()
+                        prop:value=move || f_adresse.get()
+                        on:input=move |ev| f_adresse.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-tresorier">"Mpitahiry vola"</label>
+                        <input
+                            id="settings-tresorier"
+                            type="text"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_tresorier.get()
+                            on:input=move |ev| f_tresorier.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div>
+                        <label class=LABEL for="settings-monnaie">"Anaran'ny vola"</label>
+                        <input
+                            id="settings-monnaie"
+                            type="text"
+                            placeholder="Ariary"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_monnaie.get()
+                            on:input=move |ev| f_monnaie.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <p class="sm:col-span-2 -mt-2 text-xs text-gray-500 dark:text-gray-400">
+                        "Hita eo amin'ny rosia : sonia sy anaran'ny vola."
+                    </p>
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-adidy">"Adidy mahazatra (Ar)"</label>
+                        <input
+                            id="settings-adidy"
+                            type="text"
+                            inputmode="decimal"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_adidy.get()
+                            on:input=move |ev| f_adidy.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div>
+                        <label class=LABEL for="settings-retention">"Tahiry tazonina"</label>
+                        <input
+                            id="settings-retention"
+                            type="number" min="1" max="365"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_retention.get()
+                            on:input=move |ev| f_retention.set(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-periode">"Vanim-potoana mahazatra"</label>
+                        <input
+                            id="settings-periode"
+                            type="text"
+                            placeholder="Taonan'ny daty"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_periode.get()
+                            on:input=move |ev| f_periode.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div>
+                        <label class=LABEL for="settings-montants">"Vola haingana (Ar)"</label>
+                        <input
+                            id="settings-montants"
+                            type="text"
+                            inputmode="decimal"
+                            placeholder="5 000 ; 10 000 ; 20 000"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_montants.get()
+                            on:input=move |ev| f_montants.set(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-exercice">"Fanombohan'ny taom-piasana"</label>
+                        <select
+                            id="settings-exercice"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            on:change=move |ev| {
+                                if let Ok(m) = event_target_value(&ev).parse() {
+                                    f_exercice.set(m);
+                                }
+                            }
+                        >
+                            {MONTH_NAMES.iter().zip(1u32..).map(|(name, m)| view! {
+                                <option value=m.to_string() selected=move || f_exercice.get() == m>
+                                    {*name}
+                                </option>
+                            }).collect_view()}
+                        </select>
+                        <p class="mt-1 text-xs text-gray-500 dark:text-gray-400">
+                            "Janoary = taona feno ; Jolay = Jolay ka hatramin'ny Jona (« 2024–2025 »). \
+                             Tsy azo ovaina rehefa misy fandoavana voarakitra."
+                        </p>
+                    </div>
+                    <div>
+                        <label class=LABEL for="settings-avance">"Andro mialoha azo ekena"</label>
+                        <input
+                            id="settings-avance"
+                            type="number" min="0" max="366"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_avance.get()
+                            on:input=move |ev| f_avance.set(event_target_value(&ev))
+                        />
+                        <p class="mt-1 text-xs text-gray-500 dark:text-gray-400">
+                            "Daty nandoavana aorian'ny androany : isan'andro farafahabetsany."
+                        </p>
+                    </div>
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-renouvellement">"Fanavaozana karatra (andro)"</label>
+                        <input
+                            id="settings-renouvellement"
+                            type="number" min="0" max="366"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_renouvellement.get()
+                            on:input=move |ev| f_renouvellement.set(event_target_value(&ev))
+                        />
+                        <p class="mt-1 text-xs text-gray-500 dark:text-gray-400">
+                            "Karatra havaozina ao anatin'izany andro izany, aseho eo amin'ny fandraisana."
+                        </p>
+                    </div>
+                </div>
+                <div class="grid grid-cols-1 sm:grid-cols-2 gap-4">
+                    <div>
+                        <label class=LABEL for="settings-carte-c">"Karatra Mpandray"</label>
+                        <input
+                            id="settings-carte-c"
+                            type="text"
+                            placeholder="C-%04d"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_carte_c.get()
+                            on:input=move |ev| f_carte_c.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div>
+                        <label class=LABEL for="settings-carte-k">"Karatra Tsy Mpandray"</label>
+                        <input
+                            id="settings-carte-k"
+                            type="text"
+                            placeholder="K-%04d"
+                            class=INPUT
+                            disabled=move || loading.get()
+                            prop:value=move || f_carte_k.get()
+                            on:input=move |ev| f_carte_k.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <p class="sm:col-span-2 -mt-2 text-xs text-gray-500 dark:text-gray-400">
+                        "%04d = laharana misy tarehimarika 4 (0001, 0002…) ; %d = tsy misy aotra."
+                    </p>
+                </div>
+                <div>
+                    <label class=LABEL for="settings-operateurs">"Mpampiditra rakitra"</label>
+                    <input
+                        id="settings-operateurs"
+                        type="text"
+                        placeholder="Naina, Tiana"
+                        class=INPUT
+                        disabled=move || loading.get()
+                        prop:value=move || f_operateurs.get()
+                        on:input=move |ev| f_operateurs.set(event_target_value(&ev))
+                    />
+                    <p class="mt-1 text-xs text-gray-500 dark:text-gray-400">
+                        "Sarahana amin'ny faingo ; raha foana dia soratana malalaka ny anarana."
+                    </p>
+                </div>
+                <label class="flex items-start gap-2 text-sm text-gray-700 dark:text-gray-300 \
+                              cursor-pointer select-none">
+                    <input
+                        type="checkbox"
+                        class="mt-1 accent-blue-600"
+                        disabled=move || loading.get()
+                        prop:checked=move || f_trimestre.get()
+                        on:change=move |ev| f_trimestre.set(event_target_checked(&ev))
+                    />
+                    <span>
+                        "Vanim-potoana isan-telovolana"
+                        <span class="block text-xs text-gray-500 dark:text-gray-400">
+                            "Ekena koa ny « 2025-T1 » ka hatramin'ny « 2025-T4 »."
+                        </span>
+                    </span>
+                </label>
+                <label class="flex items-start gap-2 text-sm text-gray-700 dark:text-gray-300 \
+                              cursor-pointer select-none">
+                    <input
+                        type="checkbox"
+                        class="mt-1 accent-blue-600"
+                        disabled=move || loading.get()
+                        prop:checked=move || f_integrite.get()
+                        on:change=move |ev| f_integrite.set(event_target_checked(&ev))
+                    />
+                    <span>
+                        "Fanamarinana ny angona isaky ny manokatra"
+                        <span class="block text-xs text-gray-500 dark:text-gray-400">
+                            "Rakitra tsy misy tompony, taona diso, totaly tsy mifanaraka."
+                        </span>
+                    </span>
+                </label>
+                <div class="flex flex-wrap items-start gap-x-4 gap-y-2">
+                    <label class="flex-1 min-w-[14rem] flex items-start gap-2 text-sm \
+                                  text-gray-700 dark:text-gray-300 cursor-pointer select-none">
+                        <input
+                            type="checkbox"
+                            class="mt-1 accent-blue-600"
+                            disabled=move || loading.get()
+                            prop:checked=move || f_cloture.get()
+                            on:change=move |ev| f_cloture.set(event_target_checked(&ev))
+                        />
+                        <span>
+                            "Fanakatonana ho azy ny taona teo aloha"
+                            <span class="block text-xs text-gray-500 dark:text-gray-400">
+                                "Aorian'ny andro voafaritra taorian'ny fanombohan'ny taom-piasana."
+                            </span>
+                        </span>
+                    </label>
+                    <div class="w-32">
+                        <label class=LABEL for="settings-delai">"Andro"</label>
+                        <input
+                            id="settings-delai"
+                            type="number" min="0" max="90"
+                            class=INPUT
+                            disabled=move || loading.get() || !f_cloture.get()
+                            prop:value=move || f_delai.get()
+                            on:input=move |ev| f_delai.set(event_target_value(&ev))
+                        />
+                    </div>
+                </div>
+
+                <div class="flex justify-end">
+                    <button
+                        type="submit"
+                        disabled=move || loading.get() || saving.get()
+                        class="btn-ripple flex items-center gap-1.5 px-4 py-2 text-sm font-semibold \
+                               text-white bg-blue-600 hover:bg-blue-700 \
+                               disabled:opacity-60 disabled:cursor-wait rounded-xl transition-colors shadow-sm"
+                    >
+                        <IconSave class="w-4 h-4" />
+                        {move || tr(if saving.get() { "action.saving" } else { "action.save" })}
+                    </button>
+                </div>
+            </form>
+
+            // ── Affichage (ce poste) ──────────────────────────────────────────
+            <div class="max-w-xl space-y-2 p-4 sm:p-5 \
+                        bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+                <label class="flex items-start gap-2 text-sm text-gray-700 dark:text-gray-300 \
+                              cursor-pointer select-none">
+                    <input
+                        type="checkbox"
+                        class="mt-1 accent-blue-600"
+                        prop:checked=move || reduced.get()
+                        on:change=move |ev| motion.choice.set(Some(event_target_checked(&ev)))
+                    />
+                    <span>
+                        "Ahena ny sary mihetsika"
+                        <span class="block text-xs text-gray-500 dark:text-gray-400">
+                            "Lanitra tsy mihetsika, tsy misy confetti. Amin'ity ordinatera ity ihany."
+                        </span>
+                    </span>
+                </label>
+                {move || match motion.choice.get() {
+                    Some(_) => view! {
+                        <button
+                            type="button"
+                            on:click=move |_| motion.choice.set(None)
+                            class="text-xs text-blue-600 dark:text-blue-400 hover:underline"
+                        >
+                            "Araho ny safidin'ny rafitra"
+                        </button>
+                    }.into_any(),
+                    None => view! {
+                        <p class="text-xs text-gray-400 dark:text-gray-500">
+                            "Manaraka ny safidin'ny rafitra (prefers-reduced-motion)."
+                        </p>
+                    }.into_any(),
+                }}
+            </div>
+
+            // ── Code PIN ──────────────────────────────────────────────────────
+            <PinSettings />
+
+            // ── Corbeille ─────────────────────────────────────────────────────
+            <MemberTrash />
+
+            // ── À propos de la base ───────────────────────────────────────────
+            <DbHealthPanel />
+        </div>
+    }
+}
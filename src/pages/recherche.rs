@@ -0,0 +1,192 @@
+/// Résultats de la recherche globale (barre de navigation) : Mpandray et
+/// Tsy Mpandray confondus, recherchés par nom, carte, téléphone ou adresse.
+/// Un numéro de reçu ("2025-00123") retrouve en plus la cotisation correspondante.
+use leptos::prelude::*;
+use leptos_router::{components::A, hooks::use_query_map};
+
+use crate::{
+    app::DataVersionCtx,
+    components::icons::{IconAlertTriangle, IconSearch},
+    models::{
+        contribution::ContributionWithMember,
+        member::MemberWithTotal,
+    },
+    services::db_service,
+    utils::{format_ariary_str, MoneyFormat, RequestGen},
+};
+
+/// Lien vers la page de résultats pour `query`.
+pub fn search_href(query: &str) -> String {
+    format!("/recherche?q={}", String::from(js_sys::encode_uri_component(query.trim())))
+}
+
+/// Requête de la forme d'un numéro de reçu : année, tiret, séquence ("2025-00123").
+fn is_receipt_query(query: &str) -> bool {
+    query.trim().split_once('-').is_some_and(|(year, seq)| {
+        year.len() == 4
+            && !seq.is_empty()
+            && year.bytes().chain(seq.bytes()).all(|b| b.is_ascii_digit())
+    })
+}
+
+#[component]
+pub fn Recherche() -> impl IntoView {
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let query_map = use_query_map();
+    let query = Memo::new(move |_| query_map.with(|q| q.get("q")).unwrap_or_default());
+
+    let results: RwSignal<Vec<MemberWithTotal>> = RwSignal::new(vec![]);
+    let receipt: RwSignal<Option<ContributionWithMember>> = RwSignal::new(None);
+    let loading: RwSignal<bool> = RwSignal::new(false);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+    let gen = RequestGen::new();
+
+    Effect::new(move |_| {
+        data_version.version.track();
+        let q = query.get();
+        let g = gen.next();
+        loading.set(true);
+        leptos::task::spawn_local(async move {
+            let res = db_service::search_members(&q, None).await;
+            // Reçu introuvable : pas une erreur, la recherche par membre suffit
+            let found = if is_receipt_query(&q) {
+                db_service::get_contribution_by_receipt(q.trim()).await.ok()
+            } else {
+                None
+            };
+            if !gen.is_current(g) {
+                return;
+            }
+            receipt.set(found);
+            match res {
+                Ok(list) => {
+                    erreur.set(None);
+                    results.set(list);
+                }
+                Err(e) => erreur.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    });
+
+    view! {
+        <div class="animate-fade-in space-y-4 max-w-3xl mx-auto">
+            <div>
+                <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                           flex items-center gap-2">
+                    <IconSearch class="w-6 h-6 text-gray-600 dark:text-gray-400" />
+                    "Fikarohana"
+                </h1>
+                <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5">
+                    {move || {
+                        let q = query.get();
+                        if q.trim().is_empty() {
+                            "Soraty anarana, laharana karatra, finday na adiresy.".to_string()
+                        } else if loading.get() {
+                            format!("« {q} » …")
+                        } else {
+                            format!("« {q} » : valiny {}", results.get().len())
+                        }
+                    }}
+                </p>
+            </div>
+
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 text-red-700 dark:text-red-300 text-sm flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+
+            {move || receipt.get().map(|c| view! {
+                <div class="flex items-center justify-between gap-3 px-4 py-2.5 text-sm \
+                            bg-blue-50/70 dark:bg-blue-900/20 \
+                            rounded-2xl border border-blue-100 dark:border-blue-800/50">
+                    <div class="min-w-0">
+                        <p class="font-medium text-gray-800 dark:text-white truncate">
+                            {c.member_name.clone()}
+                        </p>
+                        <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                            <span class="font-mono">{format!("Rosia {}", c.receipt_number)}</span>
+                            {format!(" · {} · {}", c.payment_date, c.period)}
+                        </p>
+                    </div>
+                    <div class="text-right shrink-0">
+                        <A
+                            href="/archives"
+                            attr:class="text-xs font-semibold \
+                                        text-blue-600 dark:text-blue-400 hover:underline"
+                        >
+                            {format!("Tahiry {}", c.recorded_year)}
+                        </A>
+                        <p class="text-xs font-mono text-gray-600 dark:text-gray-300">
+                            {format_ariary_str(&c.amount, MoneyFormat::default())}
+                        </p>
+                    </div>
+                </div>
+            })}
+
+            {move || {
+                let list = results.get();
+                if list.is_empty() {
+                    let empty = !loading.get()
+                        && !query.get().trim().is_empty()
+                        && receipt.get().is_none();
+                    return empty.then(|| view! {
+                        <p class="text-center text-sm text-gray-500 dark:text-gray-400 py-10">
+                            "Tsy nahitana mpikambana mifanaraka amin'izany."
+                        </p>
+                    }).into_any();
+                }
+                view! {
+                    <ul class="divide-y divide-gray-100 dark:divide-gray-700/60 \
+                               bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                               rounded-2xl border border-gray-100 dark:border-gray-700 \
+                               shadow-sm">
+                        {list.into_iter().map(|m| view! {
+                            <li class="flex items-center justify-between gap-3 px-4 py-2.5 text-sm">
+                                <div class="min-w-0">
+                                    <p class="font-medium text-gray-800 dark:text-white truncate">
+                                        {m.full_name.clone()}
+                                    </p>
+                                    <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                                        <span class="font-mono">{m.card_number.clone()}</span>
+                                        {m.phone.clone().map(|p| format!(" · {p}"))}
+                                        {m.address.clone().map(|a| format!(" · {a}"))}
+                                    </p>
+                                </div>
+                                <div class="text-right shrink-0">
+                                    <A
+                                        href=m.member_type.list_path()
+                                        attr:class="text-xs font-semibold \
+                                                    text-blue-600 dark:text-blue-400 hover:underline"
+                                    >
+                                        {m.member_type.label()}
+                                    </A>
+                                    <p class="text-xs font-mono text-gray-600 dark:text-gray-300">
+                                        {format_ariary_str(&m.total_contributions, MoneyFormat::default())}
+                                    </p>
+                                </div>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_receipt_query() {
+        assert!(is_receipt_query("2025-00123"));
+        assert!(is_receipt_query(" 2024-7 "));
+        assert!(!is_receipt_query("C-0042"));
+        assert!(!is_receipt_query("2025"));
+        assert!(!is_receipt_query("2025-"));
+        assert!(!is_receipt_query("034 12-345"));
+    }
+}
@@ -0,0 +1,457 @@
+/// Saisie en série des cotisations (`/saisie`) — collecte du dimanche.
+///
+/// Parcours au clavier : recherche du membre (Entrée retient le premier
+/// résultat), montant, Entrée ajoute la ligne à la file et ramène le curseur
+/// dans la recherche. Date, période et opérateur restent communs à la série.
+/// La file est envoyée d'un bloc (`create_contributions_batch`) : si une ligne
+/// est refusée, rien n'est enregistré et la file reste intacte.
+use leptos::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::{
+    app::{DataVersionCtx, SettingsCtx, ToastProviderCtx},
+    components::{
+        contribution_modal::{period_mismatch, split_period, today},
+        icons::{IconAlertTriangle, IconCoins, IconSave, IconSearch, IconTrash},
+    },
+    i18n::tr,
+    models::{contribution::ContributionInput, member::MemberLite},
+    services::db_service,
+    utils::{
+        amount::{amount_to_backend, parse_amount_input},
+        format_ariary, format_ariary_str, money::parse_decimal, use_debounced_signal,
+        MoneyFormat, RequestGen, SEARCH_DEBOUNCE_MS,
+    },
+};
+
+const LABEL: &str = "block text-xs font-semibold text-gray-600 dark:text-gray-400 mb-1";
+const INPUT: &str = "w-full px-3 py-2 text-sm \
+                     bg-white/80 dark:bg-gray-700/60 \
+                     border border-gray-200 dark:border-gray-600 \
+                     rounded-xl text-gray-800 dark:text-white \
+                     placeholder-gray-400 dark:placeholder-gray-500 \
+                     focus:outline-none focus:ring-2 focus:ring-emerald-400 transition";
+
+/// Période proposée : celle des Paramètres si elle est canonique, sinon le mois
+/// de `today` ("2025-06-15" → "2025-06").
+pub fn initial_period(today: &str, default_period: &str) -> String {
+    match split_period(default_period) {
+        Some(_) => default_period.trim().to_string(),
+        None => today.get(..7).unwrap_or(today).to_string(),
+    }
+}
+
+/// Ligne en attente d'envoi.
+#[derive(Clone)]
+struct Queued {
+    member: MemberLite,
+    input:  ContributionInput,
+}
+
+#[component]
+pub fn SaisieSerie() -> impl IntoView {
+    let toasts       = use_context::<ToastProviderCtx>().expect("ToastProviderCtx manquant");
+    let data_version = use_context::<DataVersionCtx>().expect("DataVersionCtx manquant");
+    let settings     = use_context::<SettingsCtx>().expect("SettingsCtx manquant").settings;
+
+    let default_period = settings.with_untracked(|s| s.as_ref().map(|s| s.default_period.clone()));
+
+    // ── Champs communs à la série ─────────────────────────────────────────────
+    let f_date:     RwSignal<String> = RwSignal::new(today());
+    let f_period:   RwSignal<String> = RwSignal::new(initial_period(&today(), &default_period.unwrap_or_default()));
+    let f_mismatch: RwSignal<bool>   = RwSignal::new(false);
+    let f_operator: RwSignal<String> = RwSignal::new(String::new());
+
+    // ── Ligne en cours ────────────────────────────────────────────────────────
+    let picked:       RwSignal<Option<MemberLite>> = RwSignal::new(None);
+    let query:        RwSignal<String>             = RwSignal::new(String::new());
+    let results:      RwSignal<Vec<MemberLite>>    = RwSignal::new(vec![]);
+    let f_amount:     RwSignal<String>             = RwSignal::new(String::new());
+    let search_node:  NodeRef<leptos::html::Input> = NodeRef::new();
+    let amount_node:  NodeRef<leptos::html::Input> = NodeRef::new();
+
+    // ── File d'attente ────────────────────────────────────────────────────────
+    let queue:   RwSignal<Vec<Queued>>    = RwSignal::new(vec![]);
+    let erreur:  RwSignal<Option<String>> = RwSignal::new(None);
+    let sending: RwSignal<bool>           = RwSignal::new(false);
+
+    let q = use_debounced_signal(query, SEARCH_DEBOUNCE_MS);
+    let gen = RequestGen::new();
+    Effect::new(move |_| {
+        let q = q.get();
+        let g = gen.next();
+        if q.trim().is_empty() {
+            results.set(vec![]);
+            return;
+        }
+        leptos::task::spawn_local(async move {
+            let res = db_service::search_members_lite(&q).await;
+            if gen.is_current(g) {
+                results.set(res.unwrap_or_default());
+            }
+        });
+    });
+
+    let focus = |node: NodeRef<leptos::html::Input>| {
+        if let Some(el) = node.get_untracked() {
+            let _ = el.focus();
+        }
+    };
+    Effect::new(move |_| focus(search_node));
+
+    let pick = move |m: MemberLite| {
+        picked.set(Some(m));
+        query.set(String::new());
+        results.set(vec![]);
+        focus(amount_node);
+    };
+
+    // Entrée dans la recherche : premier résultat
+    let on_search_keydown = move |ev: web_sys::KeyboardEvent| {
+        if ev.key() != "Enter" {
+            return;
+        }
+        ev.prevent_default();
+        if let Some(m) = results.get_untracked().into_iter().next() {
+            pick(m);
+        }
+    };
+
+    let on_amount_input = move |_| {
+        let Some(el) = amount_node.get() else { return };
+        let formatted = parse_amount_input(&el.value()).display();
+        f_amount.set(formatted.clone());
+        el.set_value(&formatted);
+    };
+
+    // Entrée dans le montant : ligne ajoutée à la file, retour à la recherche
+    let on_amount_keydown = move |ev: web_sys::KeyboardEvent| {
+        match ev.key().as_str() {
+            "Enter" => ev.prevent_default(),
+            "Escape" => {
+                picked.set(None);
+                focus(search_node);
+                return;
+            }
+            _ => return,
+        }
+        let Some(member) = picked.get_untracked() else {
+            focus(search_node);
+            return;
+        };
+        let amount = amount_to_backend(&f_amount.get_untracked());
+        if amount.is_empty() {
+            erreur.set(Some("Ampidiro ny vola marina.".into()));
+            return;
+        }
+        let input = ContributionInput {
+            member_id:      member.id,
+            payment_date:   f_date.get_untracked(),
+            period:         f_period.get_untracked().trim().to_string(),
+            amount,
+            allow_mismatch: f_mismatch.get_untracked(),
+            recorded_by:    Some(f_operator.get_untracked().trim().to_string()).filter(|o| !o.is_empty()),
+        };
+        queue.update(|list| list.push(Queued { member, input }));
+        erreur.set(None);
+        picked.set(None);
+        f_amount.set(String::new());
+        focus(search_node);
+    };
+
+    let queue_total = Memo::new(move |_| {
+        queue.with(|list| list.iter().map(|r| parse_decimal(&r.input.amount)).sum::<Decimal>())
+    });
+
+    let envoyer = move |_| {
+        let inputs: Vec<ContributionInput> = queue.with_untracked(|list| {
+            list.iter().map(|r| r.input.clone()).collect()
+        });
+        if inputs.is_empty() {
+            return;
+        }
+        sending.set(true);
+        erreur.set(None);
+        leptos::task::spawn_local(async move {
+            match db_service::create_contributions_batch(&inputs).await {
+                Ok(saved) => {
+                    queue.set(vec![]);
+                    data_version.bump();
+                    toasts.success(format!("Voatahiry ny rakitra {}", saved.len()));
+                }
+                // Ligne refusée : rien n'est enregistré, la file reste à corriger
+                Err(e) if e.is_input_error() => erreur.set(Some(e.to_string())),
+                Err(e) => toasts.error(e.to_string()),
+            }
+            sending.set(false);
+            focus(search_node);
+        });
+    };
+
+    let operators = Memo::new(move |_| {
+        settings.with(|s| s.as_ref().map(|s| s.operators.clone()).unwrap_or_default())
+    });
+
+    view! {
+        <div class="animate-fade-in space-y-4 max-w-3xl mx-auto">
+            <div>
+                <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                           flex items-center gap-2">
+                    <IconCoins class="w-6 h-6 text-emerald-500" />
+                    {move || tr("nav.batch")}
+                </h1>
+                <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5">
+                    "Entrée : mifidy ny mpikambana voalohany, avy eo manampy ny andalana."
+                </p>
+            </div>
+
+            // ── Date, période, opérateur ─────────────────────────────────────
+            <div class="grid grid-cols-1 sm:grid-cols-3 gap-3 p-4 \
+                        bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 shadow-sm">
+                <div>
+                    <label class=LABEL>"Daty *"</label>
+                    <input
+                        type="date" required
+                        class=INPUT
+                        prop:value=move || f_date.get()
+                        on:input=move |ev| f_date.set(event_target_value(&ev))
+                    />
+                </div>
+                <div>
+                    <label class=LABEL>"Vanim-potoana *"</label>
+                    <input
+                        type="text"
+                        placeholder="2025-03"
+                        class=format!("{INPUT} font-mono")
+                        prop:value=move || f_period.get()
+                        on:input=move |ev| f_period.set(event_target_value(&ev))
+                    />
+                </div>
+                <div>
+                    <label class=LABEL>"Nampiditra"</label>
+                    {move || {
+                        let ops = operators.get();
+                        if ops.is_empty() {
+                            view! {
+                                <input
+                                    type="text"
+                                    placeholder="Anaran'ny mpampiditra"
+                                    class=INPUT
+                                    prop:value=move || f_operator.get()
+                                    on:input=move |ev| f_operator.set(event_target_value(&ev))
+                                />
+                            }.into_any()
+                        } else {
+                            view! {
+                                <select
+                                    class=INPUT
+                                    on:change=move |ev| f_operator.set(event_target_value(&ev))
+                                >
+                                    <option value="">"—"</option>
+                                    {ops.into_iter().map(|o| {
+                                        let (value, current) = (o.clone(), o.clone());
+                                        view! {
+                                            <option
+                                                value=value
+                                                selected=move || f_operator.get() == current
+                                            >
+                                                {o}
+                                            </option>
+                                        }
+                                    }).collect_view()}
+                                </select>
+                            }.into_any()
+                        }
+                    }}
+                </div>
+                {move || period_mismatch(&f_date.get(), &f_period.get()).then(|| view! {
+                    <label class="sm:col-span-3 flex items-start gap-2 text-xs \
+                                  text-amber-700 dark:text-amber-300 cursor-pointer select-none">
+                        <input
+                            type="checkbox"
+                            class="mt-0.5 accent-amber-500"
+                            prop:checked=move || f_mismatch.get()
+                            on:change=move |ev| f_mismatch.set(event_target_checked(&ev))
+                        />
+                        "Vanim-potoana tamin'ny taona hafa (trosa taloha na aloa mialoha)"
+                    </label>
+                })}
+            </div>
+
+            // ── Ligne en cours ───────────────────────────────────────────────
+            <div class="grid grid-cols-1 sm:grid-cols-3 gap-3">
+                <div class="sm:col-span-2">
+                    <label class=LABEL>"Mpikambana *"</label>
+                    {move || picked.get().map(|m| view! {
+                        <div class="flex items-center justify-between gap-2 px-3 py-2 text-sm \
+                                    rounded-xl bg-emerald-50 dark:bg-emerald-900/30 \
+                                    text-emerald-800 dark:text-emerald-200">
+                            <span class="truncate">
+                                {m.full_name}
+                                <span class="font-mono text-xs opacity-75">
+                                    {format!(" · {}", m.card_number)}
+                                </span>
+                            </span>
+                            <button
+                                type="button"
+                                on:click=move |_| { picked.set(None); focus(search_node); }
+                                class="shrink-0 text-xs font-semibold \
+                                       text-emerald-700 dark:text-emerald-300 hover:underline"
+                            >
+                                "Hanova"
+                            </button>
+                        </div>
+                    })}
+                    <div class="relative" class:hidden=move || picked.with(Option::is_some)>
+                        <span class="absolute left-3 top-1/2 -translate-y-1/2 \
+                                     text-gray-400 pointer-events-none">
+                            <IconSearch class="w-4 h-4" />
+                        </span>
+                        <input
+                            type="text"
+                            node_ref=search_node
+                            placeholder="Anarana na laharana karatra…"
+                            class=format!("{INPUT} pl-9")
+                            prop:value=move || query.get()
+                            on:input=move |ev| query.set(event_target_value(&ev))
+                            on:keydown=on_search_keydown
+                        />
+                    </div>
+                    <ul class="mt-1 max-h-48 overflow-y-auto rounded-xl empty:hidden \
+                               border border-gray-100 dark:border-gray-700 \
+                               bg-white/80 dark:bg-gray-800/80 \
+                               divide-y divide-gray-100 dark:divide-gray-700/60">
+                        {move || results.get().into_iter().map(|m| {
+                            let label = format!("{} · {}", m.full_name, m.card_number);
+                            view! {
+                                <li>
+                                    <button
+                                        type="button"
+                                        on:click=move |_| pick(m.clone())
+                                        class="w-full text-left px-3 py-1.5 text-sm \
+                                               text-gray-700 dark:text-gray-200 \
+                                               hover:bg-emerald-50 dark:hover:bg-emerald-900/30"
+                                    >
+                                        {label}
+                                    </button>
+                                </li>
+                            }
+                        }).collect_view()}
+                    </ul>
+                </div>
+                <div>
+                    <label class=LABEL>"Vola (Ariary) *"</label>
+                    <input
+                        type="text"
+                        inputmode="decimal"
+                        placeholder="0"
+                        node_ref=amount_node
+                        class=format!("{INPUT} font-mono")
+                        prop:value=move || f_amount.get()
+                        on:input=on_amount_input
+                        on:keydown=on_amount_keydown
+                    />
+                </div>
+            </div>
+
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 bg-red-50 dark:bg-red-900/30 \
+                            border border-red-200 dark:border-red-700 \
+                            rounded-xl text-red-700 dark:text-red-300 text-xs \
+                            flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+
+            // ── File d'attente ───────────────────────────────────────────────
+            <div class="bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                        rounded-2xl border border-gray-100 dark:border-gray-700 \
+                        overflow-hidden shadow-sm">
+                {move || {
+                    let list = queue.get();
+                    if list.is_empty() {
+                        return view! {
+                            <p class="text-center py-10 text-sm text-gray-400 dark:text-gray-500">
+                                "Tsy misy andalana miandry"
+                            </p>
+                        }.into_any();
+                    }
+                    view! {
+                        <table class="w-full text-sm">
+                            <tbody>
+                                {list.into_iter().enumerate().map(|(i, r)| view! {
+                                    <tr class="border-b border-gray-50 dark:border-gray-700/50">
+                                        <td class="px-4 py-2 w-12 text-xs text-gray-400 dark:text-gray-500">
+                                            {i + 1}
+                                        </td>
+                                        <td class="px-4 py-2 font-medium text-gray-800 dark:text-gray-100">
+                                            {r.member.full_name}
+                                            <span class="font-mono text-xs text-gray-400">
+                                                {format!(" · {}", r.member.card_number)}
+                                            </span>
+                                        </td>
+                                        <td class="px-4 py-2 font-mono text-xs text-gray-500 dark:text-gray-400">
+                                            {r.input.period}
+                                        </td>
+                                        <td class="px-4 py-2 text-right font-mono font-semibold \
+                                                   text-gray-800 dark:text-gray-100">
+                                            {format_ariary_str(&r.input.amount, MoneyFormat::default())}
+                                        </td>
+                                        <td class="px-2 py-2 w-10">
+                                            <button
+                                                type="button"
+                                                title="Esorina"
+                                                on:click=move |_| queue.update(|l| { l.remove(i); })
+                                                class="p-1 rounded-lg text-gray-400 hover:text-red-600 \
+                                                       hover:bg-red-50 dark:hover:bg-red-900/30"
+                                            >
+                                                <IconTrash class="w-3.5 h-3.5" />
+                                            </button>
+                                        </td>
+                                    </tr>
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    }.into_any()
+                }}
+                <div class="flex items-center justify-between gap-3 px-4 py-3 \
+                            bg-gray-50/80 dark:bg-gray-900/50 \
+                            border-t border-gray-100 dark:border-gray-700">
+                    <span class="text-sm font-mono font-bold text-gray-800 dark:text-gray-100">
+                        {move || format!(
+                            "{} · {}",
+                            queue.with(Vec::len),
+                            format_ariary(&queue_total.get(), MoneyFormat::default()),
+                        )}
+                    </span>
+                    <button
+                        type="button"
+                        disabled=move || sending.get() || queue.with(Vec::is_empty)
+                        on:click=envoyer
+                        class="btn-ripple flex items-center gap-1.5 px-4 py-2 text-sm font-semibold \
+                               text-white bg-emerald-600 hover:bg-emerald-700 \
+                               disabled:opacity-60 disabled:cursor-not-allowed \
+                               rounded-xl transition-colors shadow-sm"
+                    >
+                        <IconSave class="w-4 h-4" />
+                        {move || if sending.get() { tr("action.saving") } else { tr("action.save") }}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn periode_proposee() {
+        assert_eq!(initial_period("2025-06-15", ""), "2025-06");
+        assert_eq!(initial_period("2025-06-15", " 2025-T2 "), "2025-T2");
+        assert_eq!(initial_period("2025-06-15", "Janvier"), "2025-06");
+    }
+}
@@ -2,12 +2,16 @@
 ///
 /// L'utilisateur choisit le mode (Serveur ou Client) et,
 /// en mode Client, entre l'adresse IP du PC serveur.
-/// En mode Serveur, il définit le code PIN administrateur (une seule fois).
+/// En mode Serveur, il définit le code PIN administrateur (modifiable ensuite
+/// depuis les Paramètres, ancien PIN exigé).
 /// Après validation, la config est sauvegardée et l'app passe en mode normal.
 use leptos::prelude::*;
 
-use crate::services::config_service::{
-    save_config, set_pin, start_mock_server, test_server_connection, AppConfig, AppMode,
+use crate::{
+    components::pin_settings::{pin_format_error, PIN_MAX_LEN, PIN_MIN_LEN},
+    services::config_service::{
+        save_config, set_pin, start_mock_server, test_server_connection, AppConfig, AppMode,
+    },
 };
 
 #[component]
@@ -89,10 +93,10 @@ pub fn SetupPage(
 
         // Validation PIN mode Serveur
         if current_mode == AppMode::Server {
-            if pin.trim().len() < 4 {
-                error_msg.set(Some(
-                    "Le code PIN doit contenir au moins 4 chiffres.".to_string()
-                ));
+            if pin_format_error(&pin).is_some() {
+                error_msg.set(Some(format!(
+                    "Le code PIN doit contenir de {PIN_MIN_LEN} à {PIN_MAX_LEN} chiffres."
+                )));
                 return;
             }
             if pin != conf {
@@ -114,6 +118,7 @@ pub fn SetupPage(
                 "0.0.0.0".to_string()
             },
             server_port: port,
+            data_dir: None,
         };
 
         leptos::task::spawn_local(async move {
@@ -238,8 +243,8 @@ pub fn SetupPage(
                             <input
                                 type="password"
                                 inputmode="numeric"
-                                maxlength="20"
-                                placeholder="Minimum 4 chiffres"
+                                maxlength="6"
+                                placeholder="4 à 6 chiffres"
                                 class="w-full px-3 py-2 rounded-lg border
                                        border-slate-300 dark:border-slate-600
                                        bg-white dark:bg-slate-700
@@ -260,7 +265,7 @@ pub fn SetupPage(
                             <input
                                 type="password"
                                 inputmode="numeric"
-                                maxlength="20"
+                                maxlength="6"
                                 placeholder="Répétez le PIN"
                                 class="w-full px-3 py-2 rounded-lg border
                                        border-slate-300 dark:border-slate-600
@@ -275,8 +280,8 @@ pub fn SetupPage(
                         </div>
 
                         <p class="text-xs text-slate-400 dark:text-slate-500">
-                            "⚠ Ce code PIN est définitif et ne peut pas être modifié ultérieurement. "
-                            "Il sera requis pour modifier des contributions dans les archives."
+                            "Il sera requis pour les suppressions, la réouverture d'une année et "
+                            "la modification des contributions archivées. Modifiable dans Paramètres."
                         </p>
                     </div>
                 </Show>
@@ -0,0 +1,182 @@
+/// Page Statistiques — graphiques SVG à partir des séries agrégées par le backend.
+///
+/// Trois graphiques : membres par genre et par type, nouveaux membres par année,
+/// total des cotisations par année.
+use leptos::prelude::*;
+
+use crate::{
+    components::{
+        bar_chart::{BarChart, BarGroup, BarSeries},
+        icons::{IconAlertTriangle, IconBarChart},
+    },
+    i18n::tr,
+    models::{
+        member::{Gender, MemberType},
+        statistics::Statistics,
+    },
+    services::db_service,
+    utils::{format_ariary, money::parse_decimal, MoneyFormat},
+};
+
+// ── Séries ────────────────────────────────────────────────────────────────────
+
+const SERIES_GENRE: [BarSeries; 2] = [
+    BarSeries { name: "Lahy", light: "#3b82f6", dark: "#60a5fa" },
+    BarSeries { name: "Vavy", light: "#ec4899", dark: "#f472b6" },
+];
+
+const SERIES_MEMBRES: [BarSeries; 1] = [
+    BarSeries { name: "Mpikambana vaovao", light: "#10b981", dark: "#34d399" },
+];
+
+const SERIES_ADIDY: [BarSeries; 1] = [
+    BarSeries { name: "Adidy", light: "#f59e0b", dark: "#fbbf24" },
+];
+
+fn fmt_count(v: f64) -> String {
+    format!("{}", v as i64)
+}
+
+fn fmt_money_compact(v: f64) -> String {
+    let d = parse_decimal(&format!("{v:.0}"));
+    format_ariary(&d, MoneyFormat::compact())
+}
+
+// ── Agrégation → groupes de barres ────────────────────────────────────────────
+
+/// Un groupe par type de membre, valeurs [M, F].
+fn gender_groups(stats: &Statistics) -> Vec<BarGroup> {
+    [MemberType::Communiant, MemberType::Cathekomen]
+        .iter()
+        .map(|t| {
+            let count = |g: Gender| {
+                stats.gender_by_type
+                    .iter()
+                    .find(|c| c.member_type == t.as_str() && c.gender == g.as_str())
+                    .map(|c| c.count as f64)
+                    .unwrap_or(0.0)
+            };
+            BarGroup { label: t.label().to_string(), values: vec![count(Gender::M), count(Gender::F)] }
+        })
+        .collect()
+}
+
+// ── Composant principal ───────────────────────────────────────────────────────
+
+#[component]
+pub fn Statistiques() -> impl IntoView {
+    let stats: RwSignal<Option<Statistics>> = RwSignal::new(None);
+    let loading = RwSignal::new(true);
+    let erreur: RwSignal<Option<String>> = RwSignal::new(None);
+
+    Effect::new(move |_| {
+        leptos::task::spawn_local(async move {
+            loading.set(true);
+            match db_service::get_statistics().await {
+                Ok(s)  => stats.set(Some(s)),
+                Err(e) => erreur.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    });
+
+    let card = "bg-white/70 dark:bg-gray-800/70 backdrop-blur \
+                rounded-2xl border border-gray-100 dark:border-gray-700 \
+                shadow-sm p-4 sm:p-5";
+    let card_title = "text-sm font-semibold text-gray-700 dark:text-gray-200 mb-3";
+
+    view! {
+        <div class="animate-fade-in space-y-4 sm:space-y-6">
+
+            // ── En-tête ───────────────────────────────────────────────────────
+            <div>
+                <h1 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-white \
+                            flex items-center gap-2">
+                    <IconBarChart class="w-6 h-6 text-gray-600 dark:text-gray-400" />
+                    "Statistika"
+                </h1>
+                <p class="text-gray-500 dark:text-gray-400 text-xs sm:text-sm mt-0.5 sm:mt-1">
+                    "Fijery ankapobeny ny mpikambana sy ny adidy."
+                </p>
+            </div>
+
+            // ── Message d'erreur ──────────────────────────────────────────────
+            {move || erreur.get().map(|e| view! {
+                <div class="p-3 sm:p-4 bg-red-50 dark:bg-red-900/30 \
+                            border border-red-200 dark:border-red-700 \
+                            rounded-xl text-red-700 dark:text-red-300 text-sm \
+                            flex items-start gap-2">
+                    <IconAlertTriangle class="w-4 h-4 shrink-0 mt-0.5" />
+                    <span>{e}</span>
+                </div>
+            })}
+
+            {move || {
+                if loading.get() {
+                    return view! {
+                        <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                            <div class="h-64 bg-gray-200 dark:bg-gray-700 rounded-2xl animate-pulse" />
+                            <div class="h-64 bg-gray-200 dark:bg-gray-700 rounded-2xl animate-pulse" />
+                            <div class="h-64 bg-gray-200 dark:bg-gray-700 rounded-2xl animate-pulse md:col-span-2" />
+                        </div>
+                    }.into_any();
+                }
+                let Some(s) = stats.get() else {
+                    return view! { <span /> }.into_any();
+                };
+
+                let genre = gender_groups(&s);
+                let nouveaux: Vec<BarGroup> = s.new_members_by_year
+                    .iter()
+                    .map(|y| BarGroup { label: y.year.to_string(), values: vec![y.count as f64] })
+                    .collect();
+                let adidy: Vec<BarGroup> = s.totals_by_year
+                    .iter()
+                    .map(|y| BarGroup {
+                        label:  y.year.to_string(),
+                        values: vec![y.total.parse::<f64>().unwrap_or(0.0)],
+                    })
+                    .collect();
+
+                view! {
+                    <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                        <div class=card>
+                            <h2 class=card_title>"Mpikambana araka ny maha-lahy/vavy"</h2>
+                            <BarChart series=SERIES_GENRE.to_vec() groups=genre format_value=fmt_count />
+                        </div>
+                        <div class=card>
+                            <h2 class=card_title>"Mpikambana vaovao isan-taona"</h2>
+                            {if nouveaux.is_empty() {
+                                view! { <EmptyChart /> }.into_any()
+                            } else {
+                                view! {
+                                    <BarChart series=SERIES_MEMBRES.to_vec() groups=nouveaux format_value=fmt_count />
+                                }.into_any()
+                            }}
+                        </div>
+                        <div class=format!("{card} md:col-span-2")>
+                            <h2 class=card_title>"Fitambaran'ny adidy isan-taona"</h2>
+                            {if adidy.is_empty() {
+                                view! { <EmptyChart /> }.into_any()
+                            } else {
+                                view! {
+                                    <BarChart series=SERIES_ADIDY.to_vec() groups=adidy format_value=fmt_money_compact />
+                                }.into_any()
+                            }}
+                        </div>
+                    </div>
+                }.into_any()
+            }}
+
+        </div>
+    }
+}
+
+#[component]
+fn EmptyChart() -> impl IntoView {
+    view! {
+        <p class="text-center text-sm text-gray-400 dark:text-gray-500 py-10">
+            {move || tr("empty.no_data")}
+        </p>
+    }
+}
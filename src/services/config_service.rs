@@ -16,11 +16,45 @@ pub struct AppConfig {
     pub mode: AppMode,
     pub server_ip: String,
     pub server_port: u16,
+    /// Dossier de la base choisi après un échec d'ouverture (`use_data_dir`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<String>,
+}
+
+/// Échec d'ouverture de la base renvoyé par `is_ready` (`AppError` sérialisé).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StartupError {
+    /// `io`, `migration`, `corrupt`, `internal`…
+    pub kind:    String,
+    pub message: String,
+}
+
+impl StartupError {
+    fn from_js(e: JsValue) -> Self {
+        serde_wasm_bindgen::from_value(e.clone()).unwrap_or_else(|_| StartupError {
+            kind:    "internal".into(),
+            message: error_text(e),
+        })
+    }
+
+    /// Cause probable et piste de solution, selon `kind`.
+    pub fn hint(&self) -> &'static str {
+        match self.kind.as_str() {
+            "corrupt" => "Simba ny rakitry ny angona. Avereno avy amin'ny tahiry iray, \
+                          na mifidiana toerana hafa.",
+            "io" => "Tsy azo idirana ny toerana misy ny angona (lalana, alalana, kapila feno). \
+                     Mifidiana toerana hafa.",
+            "migration" => "Tsy mifanaraka amin'ity dikan'ny rindrankajy ity ny angona. \
+                            Havaozy ny rindrankajy, na mifidiana toerana hafa.",
+            _ => "Andramo indray, na jereo amin'ny fanandramana ny rindrankajy.",
+        }
+    }
 }
 
 // ─── Helper ───────────────────────────────────────────────────────────────────
 
-async fn invoke_raw(cmd: &str, args: JsValue) -> Result<JsValue, String> {
+/// Erreur brute de la commande : `{ kind, message }` pour une `AppError`.
+async fn invoke_js(cmd: &str, args: JsValue) -> Result<JsValue, JsValue> {
     let window = web_sys::window().ok_or("Pas de window")?;
     let tauri = Reflect::get(&window, &JsValue::from_str("__TAURI__"))
         .map_err(|_| "Tauri introuvable")?;
@@ -31,15 +65,23 @@ async fn invoke_raw(cmd: &str, args: JsValue) -> Result<JsValue, String> {
         .dyn_into::<Function>()
         .map_err(|_| "invoke n'est pas une Function")?;
 
-    let promise = invoke_fn
-        .call2(&core, &JsValue::from_str(cmd), &args)
-        .map_err(|e| format!("{e:?}"))?;
+    let promise = invoke_fn.call2(&core, &JsValue::from_str(cmd), &args)?;
 
     JsFuture::from(
         promise.dyn_into::<Promise>().map_err(|_| "Pas une Promise")?,
     )
     .await
-    .map_err(|e| e.as_string().unwrap_or_else(|| format!("{e:?}")))
+}
+
+/// Texte affichable d'une erreur : le `message` d'une `AppError`, sinon la chaîne brute.
+fn error_text(e: JsValue) -> String {
+    e.as_string()
+        .or_else(|| Reflect::get(&e, &JsValue::from_str("message")).ok()?.as_string())
+        .unwrap_or_else(|| format!("{e:?}"))
+}
+
+async fn invoke_raw(cmd: &str, args: JsValue) -> Result<JsValue, String> {
+    invoke_js(cmd, args).await.map_err(error_text)
 }
 
 async fn invoke_cmd<T: for<'de> Deserialize<'de>>(
@@ -61,17 +103,38 @@ pub async fn get_config() -> Result<Option<AppConfig>, String> {
     invoke_cmd("get_config", to_js(&serde_json::json!({}))).await
 }
 
+/// `false` tant que la base s'initialise au démarrage ; `Err` si l'initialisation a échoué.
+pub async fn is_ready() -> Result<bool, StartupError> {
+    let value = invoke_js("is_ready", to_js(&serde_json::json!({})))
+        .await
+        .map_err(StartupError::from_js)?;
+    serde_wasm_bindgen::from_value(value).map_err(|e| StartupError {
+        kind:    "internal".into(),
+        message: e.to_string(),
+    })
+}
+
 /// Sauvegarde la configuration et initialise la source de données.
 pub async fn save_config(config: &AppConfig) -> Result<(), String> {
     invoke_raw("save_config", to_js(&serde_json::json!({ "config": config }))).await.map(|_| ())
 }
 
 /// Supprime la configuration et remet l'app en état non-configuré.
-#[allow(dead_code)]
 pub async fn reset_config() -> Result<(), String> {
     invoke_raw("reset_config", to_js(&serde_json::json!({}))).await.map(|_| ())
 }
 
+/// Ouvre la base dans `path` (chemin absolu) après un échec au démarrage.
+/// `false` : base ouverte, mais choix non retenu pour le prochain lancement.
+pub async fn use_data_dir(path: &str) -> Result<bool, String> {
+    invoke_cmd("use_data_dir", to_js(&serde_json::json!({ "path": path }))).await
+}
+
+/// Base de démonstration en mémoire, en lecture seule, jusqu'au redémarrage.
+pub async fn start_demo_mode() -> Result<(), String> {
+    invoke_raw("start_demo_mode", to_js(&serde_json::json!({}))).await.map(|_| ())
+}
+
 /// Teste si le serveur à l'adresse ip:port est accessible.
 pub async fn test_server_connection(ip: &str, port: u16) -> Result<bool, String> {
     invoke_cmd(
@@ -91,3 +154,16 @@ pub async fn start_mock_server() -> Result<u16, String> {
 pub async fn set_pin(pin: &str) -> Result<(), String> {
     invoke_raw("set_pin", to_js(&serde_json::json!({ "pin": pin }))).await.map(|_| ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_hint_selon_kind() {
+        let hint = |kind: &str| StartupError { kind: kind.into(), message: String::new() }.hint();
+        assert_ne!(hint("corrupt"), hint("io"));
+        assert_ne!(hint("migration"), hint("io"));
+        assert_eq!(hint("internal"), hint("inconnu"));
+    }
+}
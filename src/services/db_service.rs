@@ -3,20 +3,170 @@
 ///
 /// Accède à `window.__TAURI__.core.invoke` via `js_sys::Reflect` (namespacing wasm-bindgen).
 /// Toutes les fonctions sont `async` et retournent `Result<T, String>`.
+/// Sans pont Tauri (navigateur seul), les commandes vont à `mock_db`.
+use std::cell::{Cell, RefCell};
+
 use js_sys::{Function, Promise, Reflect};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 
+use crate::i18n::Lang;
+use crate::services::mock_db::{self, MockDb};
 use crate::models::{
-    contribution::{Contribution, ContributionEditInput, ContributionInput, ContributionWithMember},
-    member::{Member, MemberInput, MemberWithTotal},
-    year_summary::YearSummary,
+    audit::AuditEntry,
+    backup::{AppLog, ArchiveImportReport, BackupInfo, ImportMode, SeedReport},
+    contribution::{
+        Contribution, ContributionEditInput, ContributionInput, ContributionSearchScope,
+        ContributionWithMember, MemberYearTotal,
+    },
+    integrity::{DbHealth, IntegrityReport},
+    member::{
+        AddressGroup, DeletedMember, ImportReport, Member, MemberCounts, MemberFilter, MemberInput, MemberLite,
+        MemberNote, MemberRow, MemberSort, MemberStatus, MemberType, MemberWithTotal, MemberYearTotals, PhoneFixReport, RenewalDue, UpcomingBirthday,
+    },
+    page::Page,
+    settings::Settings,
+    statistics::{DashboardStats, Statistics},
+    task::PendingTask,
+    year_summary::{
+        MonthlyComparison, MonthlyTotal, WeekTotal, YearBreakdown, YearClosePreview,
+        YearCloseStatus, YearComparison, YearMemberSnapshot, YearSummary,
+    },
 };
 
+// ─── Erreurs ──────────────────────────────────────────────────────────────────
+
+/// Erreur structurée des commandes (`{ kind, message }` côté backend).
+/// Validation et conflit s'affichent dans le formulaire, le reste en bannière.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    Validation(String),
+    NotFound(String),
+    Conflict(String),
+    /// Base de données, réseau, source non prête, IPC…
+    Other(String),
+}
+
+impl ApiError {
+    fn from_kind(kind: &str, message: String) -> Self {
+        match kind {
+            "validation" => ApiError::Validation(message),
+            "not_found"  => ApiError::NotFound(message),
+            "conflict"   => ApiError::Conflict(message),
+            _            => ApiError::Other(message),
+        }
+    }
+
+    /// Rejet de `invoke` : objet `{ kind, message }`, ou simple chaîne
+    /// (commandes de configuration / fenêtre).
+    fn from_js(value: JsValue) -> Self {
+        #[derive(Deserialize)]
+        struct Body {
+            kind:    String,
+            message: String,
+        }
+        if let Some(s) = value.as_string() {
+            return ApiError::Other(s);
+        }
+        match serde_wasm_bindgen::from_value::<Body>(value.clone()) {
+            Ok(b)  => Self::from_kind(&b.kind, b.message),
+            Err(_) => ApiError::Other(format!("{value:?}")),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::Validation(m)
+            | ApiError::NotFound(m)
+            | ApiError::Conflict(m)
+            | ApiError::Other(m) => m,
+        }
+    }
+
+    /// Erreur liée à la saisie (à afficher près des champs).
+    pub fn is_input_error(&self) -> bool {
+        matches!(self, ApiError::Validation(_) | ApiError::Conflict(_))
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<ApiError> for String {
+    fn from(e: ApiError) -> Self {
+        e.message().to_string()
+    }
+}
+
+impl From<&str> for ApiError {
+    fn from(s: &str) -> Self {
+        ApiError::Other(s.to_string())
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(s: String) -> Self {
+        ApiError::Other(s)
+    }
+}
+
+// ─── Mode du backend ──────────────────────────────────────────────────────────
+
+/// Backend joint par les commandes : l'application Tauri, ou la base simulée
+/// quand la page tourne dans un simple navigateur (`trunk serve`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendMode {
+    Tauri,
+    Mock,
+}
+
+thread_local! {
+    static MODE: Cell<Option<BackendMode>> = const { Cell::new(None) };
+    static MOCK: RefCell<Option<MockDb>>   = const { RefCell::new(None) };
+}
+
+/// Présence de `window.__TAURI__`, vérifiée au premier appel puis mémorisée.
+pub fn backend_mode() -> BackendMode {
+    MODE.with(|mode| {
+        *mode.get().get_or_insert_with(|| {
+            let bridge = web_sys::window()
+                .and_then(|w| Reflect::get(&w, &JsValue::from_str("__TAURI__")).ok())
+                .is_some_and(|t| !t.is_undefined() && !t.is_null());
+            if bridge { BackendMode::Tauri } else { BackendMode::Mock }
+        })
+    })
+}
+
+/// Commande exécutée par `mock_db` ; la base de démonstration est créée au premier appel.
+fn invoke_mock(cmd: &str, args: JsValue) -> Result<JsValue, ApiError> {
+    let args: serde_json::Value = serde_wasm_bindgen::from_value(args).unwrap_or_default();
+    let d = js_sys::Date::new_0();
+    let today = format!("{:04}-{:02}-{:02}", d.get_full_year(), d.get_month() + 1, d.get_date());
+    let now: String = String::from(d.to_iso_string()).chars().take(19).collect();
+    let value = MOCK.with(|mock| {
+        let mut mock = mock.borrow_mut();
+        let db = mock.get_or_insert_with(|| MockDb::with_demo_data(&today, &now));
+        mock_db::dispatch(db, cmd, &args, &today, &now)
+    })?;
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| ApiError::Other(e.to_string()))
+}
+
 // ─── Helpers internes ─────────────────────────────────────────────────────────
 
 async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, String> {
+    invoke_api(cmd, args).await.map_err(String::from)
+}
+
+async fn invoke_api(cmd: &str, args: JsValue) -> Result<JsValue, ApiError> {
+    if backend_mode() == BackendMode::Mock {
+        return invoke_mock(cmd, args);
+    }
     let window = web_sys::window().ok_or("Pas de window")?;
 
     let tauri = Reflect::get(&window, &JsValue::from_str("__TAURI__"))
@@ -38,7 +188,7 @@ async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, String> {
             .map_err(|_| "invoke n'a pas retourné une Promise")?,
     )
     .await
-    .map_err(|e| e.as_string().unwrap_or_else(|| format!("{e:?}")))
+    .map_err(ApiError::from_js)
 }
 
 fn to_js<T: Serialize>(val: &T) -> JsValue {
@@ -47,8 +197,16 @@ fn to_js<T: Serialize>(val: &T) -> JsValue {
 
 /// Invoke une commande Tauri et désérialise la réponse en `T`.
 async fn invoke_cmd<T: for<'de> Deserialize<'de>>(cmd: &str, args: JsValue) -> Result<T, String> {
-    serde_wasm_bindgen::from_value(invoke(cmd, args).await?)
-        .map_err(|e| e.to_string())
+    invoke_cmd_api(cmd, args).await.map_err(String::from)
+}
+
+/// Comme `invoke_cmd`, en conservant le type d'erreur (formulaires).
+async fn invoke_cmd_api<T: for<'de> Deserialize<'de>>(
+    cmd: &str,
+    args: JsValue,
+) -> Result<T, ApiError> {
+    serde_wasm_bindgen::from_value(invoke_api(cmd, args).await?)
+        .map_err(|e| ApiError::Other(e.to_string()))
 }
 
 // ─── Member ───────────────────────────────────────────────────────────────────
@@ -57,42 +215,189 @@ pub async fn get_members() -> Result<Vec<Member>, String> {
     invoke_cmd("get_members", to_js(&serde_json::json!({}))).await
 }
 
-pub async fn get_members_by_type(member_type: &str) -> Result<Vec<Member>, String> {
+pub async fn get_members_by_type(member_type: MemberType) -> Result<Vec<Member>, String> {
     invoke_cmd("get_members_by_type", to_js(&serde_json::json!({ "memberType": member_type }))).await
 }
 
+/// Effectifs par type, sans charger les listes (badges de la Navbar).
+pub async fn get_member_counts() -> Result<MemberCounts, String> {
+    invoke_cmd("get_member_counts", to_js(&serde_json::json!({}))).await
+}
+
+/// Anniversaires des `days` prochains jours (carte de l'accueil).
+pub async fn get_upcoming_birthdays(days: u32) -> Result<Vec<UpcomingBirthday>, String> {
+    invoke_cmd("get_upcoming_birthdays", to_js(&serde_json::json!({ "days": days }))).await
+}
+
+/// Cartes à renouveler dans les `within_days` prochains jours (carte de l'accueil).
+pub async fn get_upcoming_renewals(within_days: u32) -> Result<Vec<RenewalDue>, String> {
+    invoke_cmd("get_upcoming_renewals", to_js(&serde_json::json!({ "withinDays": within_days }))).await
+}
+
+/// Membres d'un type ; `status` absent = tous les statuts.
 pub async fn get_members_by_type_with_total(
-    member_type: &str,
+    member_type: MemberType,
+    status: Option<MemberStatus>,
 ) -> Result<Vec<MemberWithTotal>, String> {
     invoke_cmd(
         "get_members_by_type_with_total",
+        to_js(&serde_json::json!({ "memberType": member_type, "status": status })),
+    )
+    .await
+}
+
+/// Membres d'un type et leurs totaux pour `years` (colonnes « par année »).
+pub async fn get_members_with_year_totals(
+    member_type: MemberType,
+    years: &[i32],
+) -> Result<Vec<MemberYearTotals>, String> {
+    invoke_cmd(
+        "get_members_with_year_totals",
+        to_js(&serde_json::json!({ "memberType": member_type, "years": years })),
+    )
+    .await
+}
+
+/// Membres d'un type regroupés par adresse normalisée, « sans adresse » en dernier.
+pub async fn get_members_grouped_by_address(member_type: MemberType) -> Result<Vec<AddressGroup>, String> {
+    invoke_cmd(
+        "get_members_grouped_by_address",
         to_js(&serde_json::json!({ "memberType": member_type })),
     )
     .await
 }
 
+/// Membres sans cotisation en `year` ; `member_type` absent = les deux types.
+pub async fn get_members_without_contribution(
+    year: i32,
+    member_type: Option<MemberType>,
+) -> Result<Vec<Member>, String> {
+    invoke_cmd(
+        "get_members_without_contribution",
+        to_js(&serde_json::json!({ "year": year, "memberType": member_type })),
+    )
+    .await
+}
+
+/// `page` commence à 0 ; tri, filtres et pagination sont faits en SQL.
+pub async fn get_member_rows(
+    member_type: MemberType,
+    page: usize,
+    page_size: usize,
+    sort: MemberSort,
+    filter: &MemberFilter,
+) -> Result<Page<MemberRow>, String> {
+    invoke_cmd(
+        "get_member_rows",
+        to_js(&serde_json::json!({
+            "memberType": member_type,
+            "page":       page,
+            "pageSize":   page_size,
+            "sort":       sort,
+            "filter":     filter,
+        })),
+    )
+    .await
+}
+
 pub async fn get_member(id: i64) -> Result<Member, String> {
     invoke_cmd("get_member", to_js(&serde_json::json!({ "id": id }))).await
 }
 
-pub async fn create_member(input: &MemberInput) -> Result<Member, String> {
-    invoke_cmd("create_member", to_js(&serde_json::json!({ "member": input }))).await
+/// Recherche par numéro de carte (lecteur de code-barres) ; `None` si inconnu.
+pub async fn get_member_by_card(card_number: &str) -> Result<Option<Member>, String> {
+    invoke_cmd(
+        "get_member_by_card",
+        to_js(&serde_json::json!({ "cardNumber": card_number })),
+    )
+    .await
+}
+
+/// Recherche dans les deux types de membres ; `limit` : 50 par défaut côté backend.
+pub async fn search_members(query: &str, limit: Option<i64>) -> Result<Vec<MemberWithTotal>, String> {
+    invoke_cmd(
+        "search_members",
+        to_js(&serde_json::json!({ "query": query, "limit": limit })),
+    )
+    .await
+}
+
+/// Sélecteur de membre : id, nom et carte des membres dont le nom ou la carte contient `query`.
+pub async fn search_members_lite(query: &str) -> Result<Vec<MemberLite>, String> {
+    invoke_cmd(
+        "search_members_lite",
+        to_js(&serde_json::json!({ "query": query })),
+    )
+    .await
 }
 
-pub async fn update_member(id: i64, input: &MemberInput) -> Result<Member, String> {
+/// Membres au nom proche (casse, accents, ordre des mots, fautes de frappe).
+pub async fn find_similar_members(full_name: &str) -> Result<Vec<Member>, String> {
     invoke_cmd(
+        "find_similar_members",
+        to_js(&serde_json::json!({ "fullName": full_name })),
+    )
+    .await
+}
+
+pub async fn create_member(input: &MemberInput) -> Result<Member, ApiError> {
+    invoke_cmd_api("create_member", to_js(&serde_json::json!({ "member": input }))).await
+}
+
+pub async fn update_member(id: i64, input: &MemberInput) -> Result<Member, ApiError> {
+    invoke_cmd_api(
         "update_member",
         to_js(&serde_json::json!({ "id": id, "member": input })),
     )
     .await
 }
 
-pub async fn delete_member(id: i64) -> Result<(), String> {
-    invoke("delete_member", to_js(&serde_json::json!({ "id": id }))).await.map(|_| ())
+/// `pin_token` : jeton de `verify_pin`, exigé si un PIN admin est configuré.
+pub async fn delete_member(id: i64, pin_token: Option<&str>) -> Result<(), String> {
+    invoke(
+        "delete_member",
+        to_js(&serde_json::json!({ "id": id, "pinToken": pin_token })),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Corbeille : membres supprimés (leurs cotisations comptent toujours dans les totaux).
+pub async fn get_deleted_members() -> Result<Vec<DeletedMember>, String> {
+    invoke_cmd("get_deleted_members", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn restore_member(id: i64) -> Result<Member, String> {
+    invoke_cmd("restore_member", to_js(&serde_json::json!({ "id": id }))).await
+}
+
+/// Prochain numéro de carte libre selon le modèle des paramètres, ex. "C-0043".
+pub async fn next_card_number(member_type: MemberType) -> Result<String, String> {
+    invoke_cmd("next_card_number", to_js(&serde_json::json!({ "memberType": member_type }))).await
+}
+
+/// Efface un membre de la corbeille et ses cotisations ; exige le jeton PIN.
+pub async fn purge_member(id: i64, pin_token: Option<&str>) -> Result<(), String> {
+    invoke(
+        "purge_member",
+        to_js(&serde_json::json!({ "id": id, "pinToken": pin_token })),
+    )
+    .await
+    .map(|_| ())
 }
 
-/// Transfère une liste de membres vers un nouveau type (ex: "Communiant").
-pub async fn transfer_members(ids: &[i64], new_type: &str) -> Result<usize, String> {
+/// Fusionne le doublon `remove_id` dans `keep_id` ; exige le jeton PIN. Retourne la fiche conservée.
+pub async fn merge_members(keep_id: i64, remove_id: i64, pin_token: Option<&str>) -> Result<Member, String> {
+    invoke_cmd(
+        "merge_members",
+        to_js(&serde_json::json!({ "keepId": keep_id, "removeId": remove_id, "pinToken": pin_token })),
+    )
+    .await
+}
+
+/// Transfère une liste de membres vers un nouveau type (ex: "Communiant") ; tout ou
+/// rien. Retourne les membres transférés, tels qu'enregistrés.
+pub async fn transfer_members(ids: &[i64], new_type: MemberType) -> Result<Vec<Member>, String> {
     invoke_cmd(
         "transfer_members",
         to_js(&serde_json::json!({ "ids": ids, "newType": new_type })),
@@ -110,6 +415,48 @@ pub async fn get_contributions(member_id: i64) -> Result<Vec<Contribution>, Stri
     .await
 }
 
+/// Sous-totaux par année des cotisations d'un membre, la plus récente d'abord.
+pub async fn get_member_year_totals(member_id: i64) -> Result<Vec<MemberYearTotal>, String> {
+    invoke_cmd(
+        "get_member_year_totals",
+        to_js(&serde_json::json!({ "memberId": member_id })),
+    )
+    .await
+}
+
+/// Ajoute une note au membre ; texte vide refusé par le backend.
+pub async fn add_member_note(member_id: i64, text: &str) -> Result<MemberNote, String> {
+    invoke_cmd(
+        "add_member_note",
+        to_js(&serde_json::json!({ "memberId": member_id, "text": text })),
+    )
+    .await
+}
+
+/// Notes du membre, les plus récentes d'abord.
+pub async fn get_member_notes(member_id: i64) -> Result<Vec<MemberNote>, String> {
+    invoke_cmd(
+        "get_member_notes",
+        to_js(&serde_json::json!({ "memberId": member_id })),
+    )
+    .await
+}
+
+pub async fn delete_member_note(id: i64) -> Result<(), String> {
+    invoke("delete_member_note", to_js(&serde_json::json!({ "id": id })))
+        .await
+        .map(|_| ())
+}
+
+/// `limit` dernières cotisations du membre, les plus récentes d'abord.
+pub async fn get_recent_contributions(member_id: i64, limit: i64) -> Result<Vec<Contribution>, String> {
+    invoke_cmd(
+        "get_recent_contributions",
+        to_js(&serde_json::json!({ "memberId": member_id, "limit": limit })),
+    )
+    .await
+}
+
 pub async fn get_contributions_by_year(year: i32) -> Result<Vec<Contribution>, String> {
     invoke_cmd(
         "get_contributions_by_year",
@@ -118,32 +465,71 @@ pub async fn get_contributions_by_year(year: i32) -> Result<Vec<Contribution>, S
     .await
 }
 
-pub async fn create_contribution(input: &ContributionInput) -> Result<Contribution, String> {
+/// Périodes déjà utilisées pour une année (suggestions de saisie).
+pub async fn get_distinct_periods(year: i32) -> Result<Vec<String>, String> {
     invoke_cmd(
+        "get_distinct_periods",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+pub async fn create_contribution(input: &ContributionInput) -> Result<Contribution, ApiError> {
+    invoke_cmd_api(
         "create_contribution",
         to_js(&serde_json::json!({ "contribution": input })),
     )
     .await
 }
 
-pub async fn delete_contribution(id: i64) -> Result<(), String> {
-    invoke("delete_contribution", to_js(&serde_json::json!({ "id": id }))).await.map(|_| ())
+/// Enregistre une série de cotisations en une transaction : tout ou rien.
+pub async fn create_contributions_batch(
+    inputs: &[ContributionInput],
+) -> Result<Vec<Contribution>, ApiError> {
+    invoke_cmd_api(
+        "create_contributions_batch",
+        to_js(&serde_json::json!({ "contributions": inputs })),
+    )
+    .await
 }
 
-pub async fn get_contributions_by_year_with_member(
-    year: i32,
-) -> Result<Vec<ContributionWithMember>, String> {
+pub async fn delete_contribution(id: i64, pin_token: Option<&str>) -> Result<(), String> {
+    invoke(
+        "delete_contribution",
+        to_js(&serde_json::json!({ "id": id, "pinToken": pin_token })),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Annule la suppression de la cotisation `id` (sous son ancien id s'il est libre).
+pub async fn restore_contribution(id: i64) -> Result<Contribution, String> {
+    invoke_cmd("restore_contribution", to_js(&serde_json::json!({ "id": id }))).await
+}
+
+/// Cotisation d'un reçu papier ("2025-00123"), avec le nom du membre.
+pub async fn get_contribution_by_receipt(receipt: &str) -> Result<ContributionWithMember, String> {
     invoke_cmd(
-        "get_contributions_by_year_with_member",
-        to_js(&serde_json::json!({ "year": year })),
+        "get_contribution_by_receipt",
+        to_js(&serde_json::json!({ "receipt": receipt })),
     )
     .await
 }
 
-pub async fn get_all_contributions_with_member() -> Result<Vec<ContributionWithMember>, String> {
+/// Page de cotisations avec nom du membre. `year` : `None` = toutes les années ;
+/// `query` : texte cherché dans les champs de `scope`, filtré côté SQL.
+pub async fn get_contributions_by_year_with_member(
+    year: Option<i32>,
+    query: Option<&str>,
+    scope: ContributionSearchScope,
+    limit: i64,
+    offset: i64,
+) -> Result<Page<ContributionWithMember>, String> {
     invoke_cmd(
-        "get_all_contributions_with_member",
-        to_js(&serde_json::json!({})),
+        "get_contributions_by_year_with_member",
+        to_js(&serde_json::json!({
+            "year": year, "query": query, "scope": scope, "limit": limit, "offset": offset,
+        })),
     )
     .await
 }
@@ -162,6 +548,18 @@ pub async fn get_year_summary(year: i32) -> Result<Option<YearSummary>, String>
     .await
 }
 
+/// Années ayant des cotisations ou une clôture, la plus récente d'abord.
+pub async fn get_years_with_contributions() -> Result<Vec<i32>, String> {
+    invoke_cmd("get_years_with_contributions", to_js(&serde_json::json!({}))).await
+}
+
+/// Supprime le résumé d'une année sans aucune donnée (refusé sinon).
+pub async fn delete_empty_year_summary(year: i32) -> Result<(), String> {
+    invoke("delete_empty_year_summary", to_js(&serde_json::json!({ "year": year })))
+        .await
+        .map(|_| ())
+}
+
 pub async fn close_year(year: i32, note: Option<String>) -> Result<YearSummary, String> {
     invoke_cmd(
         "close_year",
@@ -170,17 +568,173 @@ pub async fn close_year(year: i32, note: Option<String>) -> Result<YearSummary,
     .await
 }
 
-pub async fn reopen_year(year: i32) -> Result<YearSummary, String> {
-    invoke_cmd("reopen_year", to_js(&serde_json::json!({ "year": year }))).await
+/// Réouvre `year` ; sans `cascade`, refusé si une année suivante est encore clôturée.
+/// Retourne les années rouvertes, par ordre croissant.
+pub async fn reopen_year(
+    year: i32,
+    cascade: bool,
+    pin_token: Option<&str>,
+) -> Result<Vec<YearSummary>, String> {
+    invoke_cmd(
+        "reopen_year",
+        to_js(&serde_json::json!({ "year": year, "cascade": cascade, "pinToken": pin_token })),
+    )
+    .await
 }
 
-pub async fn check_and_close_previous_year() -> Result<Option<YearSummary>, String> {
-    invoke_cmd("check_and_close_previous_year", to_js(&serde_json::json!({}))).await
+/// Note d'une année clôturée ; `None` ou vide efface la note.
+pub async fn update_year_note(
+    year: i32,
+    note: Option<&str>,
+    pin_token: Option<&str>,
+) -> Result<YearSummary, String> {
+    invoke_cmd(
+        "update_year_note",
+        to_js(&serde_json::json!({ "year": year, "note": note, "pinToken": pin_token })),
+    )
+    .await
+}
+
+/// `grace_days` : `None` → paramètres de clôture automatique, `Some(0)` → clôture immédiate.
+pub async fn check_and_close_previous_year(grace_days: Option<u32>) -> Result<YearCloseStatus, String> {
+    invoke_cmd(
+        "check_and_close_previous_year",
+        to_js(&serde_json::json!({ "graceDays": grace_days })),
+    )
+    .await
+}
+
+/// Total et note proposée pour la clôture de `year`, sans rien écrire.
+pub async fn preview_year_close(year: i32) -> Result<YearClosePreview, String> {
+    invoke_cmd("preview_year_close", to_js(&serde_json::json!({ "year": year }))).await
+}
+
+pub async fn get_year_comparison(years: &[i32]) -> Result<Vec<YearComparison>, String> {
+    invoke_cmd(
+        "get_year_comparison",
+        to_js(&serde_json::json!({ "years": years })),
+    )
+    .await
+}
+
+/// Totaux par membre figés à la clôture de `year`, du plus fort au plus faible ;
+/// vide pour une année ouverte.
+pub async fn get_year_member_totals(year: i32) -> Result<Vec<YearMemberSnapshot>, String> {
+    invoke_cmd(
+        "get_year_member_totals",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// 12 entrées (janvier → décembre), à zéro pour un mois sans cotisation.
+pub async fn get_monthly_totals(year: i32) -> Result<Vec<MonthlyTotal>, String> {
+    invoke_cmd(
+        "get_monthly_totals",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Une entrée par semaine ISO de `year`, à zéro pour une semaine sans cotisation.
+pub async fn get_weekly_totals(year: i32) -> Result<Vec<WeekTotal>, String> {
+    invoke_cmd(
+        "get_weekly_totals",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Cotisations de `year` par genre et par type de membre.
+pub async fn get_year_breakdown(year: i32) -> Result<YearBreakdown, String> {
+    invoke_cmd(
+        "get_year_breakdown",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Comparatif mois par mois : totaux des deux années et écart cumulé (A − B).
+pub async fn compare_years(year_a: i32, year_b: i32) -> Result<MonthlyComparison, String> {
+    invoke_cmd(
+        "compare_years",
+        to_js(&serde_json::json!({ "yearA": year_a, "yearB": year_b })),
+    )
+    .await
+}
+
+// ─── Statistiques ─────────────────────────────────────────────────────────────
+
+pub async fn get_statistics() -> Result<Statistics, String> {
+    invoke_cmd("get_statistics", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn get_dashboard_stats() -> Result<DashboardStats, String> {
+    invoke_cmd("get_dashboard_stats", to_js(&serde_json::json!({}))).await
+}
+
+// ─── Paramètres ───────────────────────────────────────────────────────────────
+
+pub async fn get_settings() -> Result<Settings, String> {
+    invoke_cmd("get_settings", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn update_settings(settings: &Settings) -> Result<Settings, String> {
+    invoke_cmd("update_settings", to_js(&serde_json::json!({ "settings": settings }))).await
+}
+
+pub async fn get_language() -> Result<Lang, String> {
+    invoke_cmd("get_language", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn set_language(lang: Lang) -> Result<Lang, String> {
+    invoke_cmd("set_language", to_js(&serde_json::json!({ "lang": lang }))).await
+}
+
+// ─── Intégrité ────────────────────────────────────────────────────────────────
+
+pub async fn run_integrity_check() -> Result<IntegrityReport, String> {
+    invoke_cmd("run_integrity_check", to_js(&serde_json::json!({}))).await
+}
+
+/// Réaligne `recorded_year` et recalcule les totaux ; renvoie le rapport restant.
+pub async fn repair_integrity() -> Result<IntegrityReport, String> {
+    invoke_cmd("repair_integrity", to_js(&serde_json::json!({}))).await
+}
+
+/// Version du schéma, lignes par table et taille de la base.
+pub async fn get_db_health() -> Result<DbHealth, String> {
+    invoke_cmd("get_db_health", to_js(&serde_json::json!({}))).await
+}
+
+/// Rappels de la cloche de la Navbar ; liste vide = rien à faire.
+pub async fn get_pending_tasks() -> Result<Vec<PendingTask>, String> {
+    invoke_cmd("get_pending_tasks", to_js(&serde_json::json!({}))).await
+}
+
+/// Réécrit les téléphones existants au format "+261 3X XX XXX XX".
+pub async fn normalize_all_phones() -> Result<PhoneFixReport, String> {
+    invoke_cmd("normalize_all_phones", to_js(&serde_json::json!({}))).await
+}
+
+// ─── Journal d'audit ──────────────────────────────────────────────────────────
+
+/// `entity` : 'member' | 'contribution' | 'year', `None` = tout.
+pub async fn get_audit_log(
+    limit: i64,
+    offset: i64,
+    entity: Option<&str>,
+) -> Result<Page<AuditEntry>, String> {
+    invoke_cmd(
+        "get_audit_log",
+        to_js(&serde_json::json!({ "limit": limit, "offset": offset, "entity": entity })),
+    )
+    .await
 }
 
 // ─── Import / Export CSV ──────────────────────────────────────────────────────
 
-pub async fn export_members_csv(member_type: &str) -> Result<String, String> {
+pub async fn export_members_csv(member_type: MemberType) -> Result<String, String> {
     invoke_cmd(
         "export_members_csv",
         to_js(&serde_json::json!({ "memberType": member_type })),
@@ -188,7 +742,7 @@ pub async fn export_members_csv(member_type: &str) -> Result<String, String> {
     .await
 }
 
-pub async fn export_members_excel(member_type: &str) -> Result<Vec<u8>, String> {
+pub async fn export_members_excel(member_type: MemberType) -> Result<Vec<u8>, String> {
     invoke_cmd(
         "export_members_excel",
         to_js(&serde_json::json!({ "memberType": member_type })),
@@ -196,7 +750,79 @@ pub async fn export_members_excel(member_type: &str) -> Result<Vec<u8>, String>
     .await
 }
 
-pub async fn import_members_csv(csv_content: &str, member_type: &str) -> Result<usize, String> {
+/// Reçu d'une cotisation : document HTML autonome prêt à imprimer.
+pub async fn export_receipt(receipt: &str) -> Result<String, String> {
+    invoke_cmd("export_receipt", to_js(&serde_json::json!({ "receipt": receipt }))).await
+}
+
+/// Rapport d'archive d'une année : document HTML autonome prêt à imprimer.
+pub async fn export_year_report(year: i32) -> Result<String, String> {
+    invoke_cmd(
+        "export_year_report",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Rapport d'archive d'une année en PDF (plusieurs pages si besoin).
+pub async fn export_year_report_pdf(year: i32) -> Result<Vec<u8>, String> {
+    invoke_cmd(
+        "export_year_report_pdf",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Cotisations d'une année en CSV (BOM, CRLF, ligne TOTAL) pour un tableur.
+pub async fn export_contributions_csv(year: i32) -> Result<String, String> {
+    invoke_cmd(
+        "export_contributions_csv",
+        to_js(&serde_json::json!({ "year": year })),
+    )
+    .await
+}
+
+/// Relevé annuel d'un membre (CSV) ; une année sans cotisation donne un total à 0.
+pub async fn export_member_statement_csv(member_id: i64, year: i32) -> Result<String, String> {
+    invoke_cmd(
+        "export_member_statement_csv",
+        to_js(&serde_json::json!({ "memberId": member_id, "year": year })),
+    )
+    .await
+}
+
+/// Écrit un CSV sur ce poste (chemin relatif : dossier Téléchargements) et
+/// retourne le chemin complet ; un fichier existant est refusé sans `overwrite`.
+pub async fn write_csv(
+    dest_path: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+    overwrite: bool,
+) -> Result<String, String> {
+    invoke_cmd(
+        "write_csv",
+        to_js(&serde_json::json!({
+            "destPath":  dest_path,
+            "headers":   headers,
+            "rows":      rows,
+            "overwrite": overwrite,
+        })),
+    )
+    .await
+}
+
+/// Ouvre le gestionnaire de fichiers sur `path` (dossier parent sous Linux).
+pub async fn reveal_in_file_manager(path: &str) -> Result<(), String> {
+    invoke("reveal_in_file_manager", to_js(&serde_json::json!({ "path": path })))
+        .await
+        .map(|_| ())
+}
+
+/// Lignes sans colonne type : `member_type` (page courante).
+pub async fn import_members_csv(
+    csv_content: &str,
+    member_type: MemberType,
+) -> Result<ImportReport, String> {
     invoke_cmd(
         "import_members_csv",
         to_js(&serde_json::json!({ "csvContent": csv_content, "memberType": member_type })),
@@ -206,7 +832,29 @@ pub async fn import_members_csv(csv_content: &str, member_type: &str) -> Result<
 
 // ─── PIN + édition contribution ───────────────────────────────────────────────
 
-pub async fn verify_pin(pin: &str) -> Result<bool, String> {
+pub async fn has_pin() -> Result<bool, String> {
+    invoke_cmd("has_pin", to_js(&serde_json::json!({}))).await
+}
+
+/// Remplace le PIN admin (l'ancien est exigé).
+pub async fn change_pin(old_pin: &str, new_pin: &str) -> Result<(), String> {
+    invoke(
+        "change_pin",
+        to_js(&serde_json::json!({ "oldPin": old_pin, "newPin": new_pin })),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Supprime le PIN admin (l'ancien est exigé).
+pub async fn remove_pin(old_pin: &str) -> Result<(), String> {
+    invoke("remove_pin", to_js(&serde_json::json!({ "oldPin": old_pin })))
+        .await
+        .map(|_| ())
+}
+
+/// Vérifie le PIN et retourne un jeton court pour les actions destructrices.
+pub async fn verify_pin(pin: &str) -> Result<String, String> {
     invoke_cmd("verify_pin", to_js(&serde_json::json!({ "pin": pin }))).await
 }
 
@@ -221,16 +869,99 @@ pub async fn update_contribution(
     .await
 }
 
+// ─── Sauvegardes / Maintenance ────────────────────────────────────────────────
+
+pub async fn list_backups() -> Result<Vec<BackupInfo>, String> {
+    invoke_cmd("list_backups", to_js(&serde_json::json!({}))).await
+}
+
+/// Sauvegarde immédiate — retourne le nom du fichier créé.
+pub async fn backup_now() -> Result<String, String> {
+    invoke_cmd("backup_now", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn restore_backup(file_name: &str, pin_token: Option<&str>) -> Result<(), String> {
+    invoke(
+        "restore_backup",
+        to_js(&serde_json::json!({ "fileName": file_name, "pinToken": pin_token })),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Base complète en JSON portable (`DatabaseArchive` côté backend).
+pub async fn export_archive() -> Result<String, String> {
+    invoke_cmd("export_archive", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn import_archive(
+    json: &str,
+    mode: ImportMode,
+    pin_token: Option<&str>,
+) -> Result<ArchiveImportReport, String> {
+    invoke_cmd(
+        "import_archive",
+        to_js(&serde_json::json!({ "json": json, "mode": mode, "pinToken": pin_token })),
+    )
+    .await
+}
+
+/// Données fictives de démonstration ; refusé si la base contient déjà des données.
+pub async fn seed_demo_data(member_count: usize, years: &[i32], seed: u64) -> Result<SeedReport, String> {
+    invoke_cmd(
+        "seed_demo_data",
+        to_js(&serde_json::json!({
+            "memberCount": member_count,
+            "years": years,
+            "seed": seed,
+        })),
+    )
+    .await
+}
+
+pub async fn get_backup_retention() -> Result<usize, String> {
+    invoke_cmd("get_backup_retention", to_js(&serde_json::json!({}))).await
+}
+
+pub async fn set_backup_retention(keep: usize) -> Result<(), String> {
+    invoke("set_backup_retention", to_js(&serde_json::json!({ "keep": keep })))
+        .await
+        .map(|_| ())
+}
+
+pub async fn get_app_logs(limit: i64) -> Result<Vec<AppLog>, String> {
+    invoke_cmd("get_app_logs", to_js(&serde_json::json!({ "limit": limit }))).await
+}
+
 // ─── Fenêtre ──────────────────────────────────────────────────────────────────
 
 pub async fn minimize_window() -> Result<(), String> {
     invoke("minimize_window", JsValue::NULL).await.map(|_| ())
 }
 
-pub async fn toggle_maximize() -> Result<(), String> {
-    invoke("toggle_maximize", JsValue::NULL).await.map(|_| ())
+/// Retourne le nouvel état maximisé de la fenêtre.
+pub async fn toggle_maximize() -> Result<bool, String> {
+    invoke_cmd("toggle_maximize", JsValue::NULL).await
+}
+
+pub async fn is_window_maximized() -> Result<bool, String> {
+    invoke_cmd("is_window_maximized", JsValue::NULL).await
 }
 
 pub async fn close_window() -> Result<(), String> {
     invoke("close_window", JsValue::NULL).await.map(|_| ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erreur_par_kind() {
+        assert_eq!(ApiError::from_kind("validation", "x".into()), ApiError::Validation("x".into()));
+        assert_eq!(ApiError::from_kind("conflict", "x".into()), ApiError::Conflict("x".into()));
+        assert_eq!(ApiError::from_kind("db", "x".into()), ApiError::Other("x".into()));
+        assert!(ApiError::Conflict("x".into()).is_input_error());
+        assert!(!ApiError::NotFound("x".into()).is_input_error());
+    }
+}
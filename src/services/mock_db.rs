@@ -0,0 +1,1089 @@
+//! Backend simulé pour le développement dans un navigateur (`trunk serve`), quand
+//! `window.__TAURI__` est absent : `db_service` lui passe alors ses commandes.
+//!
+//! Membres, cotisations, notes et années clôturées vivent en mémoire et sont
+//! perdus au rechargement de la page. Seules les commandes utiles à la navigation
+//! sont simulées, avec les messages de validation du backend ; les autres
+//! (sauvegardes, exports, PIN…) renvoient une erreur explicite.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    i18n::Lang,
+    models::{
+        contribution::{Contribution, ContributionInput, ContributionWithMember, MemberYearTotal},
+        integrity::{DbHealth, IntegrityReport, TableRowCount},
+        member::{
+            AddressGroup, DeletedMember, Gender, Member, MemberCounts, MemberFilter, MemberInput, MemberLite,
+            MemberNote, MemberRow, MemberSort, MemberSortCol, MemberStatus, MemberType, MemberWithTotal, MemberYearTotals,
+        },
+        page::Page,
+        settings::Settings,
+        statistics::DashboardStats,
+        year_summary::{YearCloseStatus, YearSummary},
+    },
+    services::db_service::ApiError,
+    utils::member_index::address_key,
+};
+
+/// Membres créés au premier appel : de quoi remplir les listes et l'accueil.
+const DEMO_MEMBERS: &[(&str, &str, Gender, MemberType)] = &[
+    ("C-0001", "RAKOTO Jean",        Gender::M, MemberType::Communiant),
+    ("C-0002", "RASOA Marie",        Gender::F, MemberType::Communiant),
+    ("C-0003", "RANDRIA Paul",       Gender::M, MemberType::Communiant),
+    ("C-0004", "Hanitra RAVELO",     Gender::F, MemberType::Communiant),
+    ("K-0001", "RAHARY Nirina",      Gender::F, MemberType::Cathekomen),
+    ("K-0002", "ANDRIAMAHEFA Toky",  Gender::M, MemberType::Cathekomen),
+];
+
+// ─── Messages de validation ───────────────────────────────────────────────────
+
+/// (français, malgache) : mêmes textes que `i18n::MESSAGES` côté backend.
+fn message(key: &str) -> (&'static str, &'static str) {
+    match key {
+        "amount.required" => ("Le montant est requis.", "Ilaina ny vola."),
+        "amount.negative" => (
+            "Le montant ne peut pas être négatif.",
+            "Tsy azo atao latsaky ny aotra ny vola.",
+        ),
+        "amount.invalid" => ("Montant invalide : '{0}'.", "Vola tsy mety : '{0}'."),
+        "member.card_required" => ("Le numéro de carte est requis.", "Ilaina ny laharan'ny karatra."),
+        "member.name_required" => ("Le nom complet est requis.", "Ilaina ny anarana feno."),
+        "member.note_required" => ("La note est vide.", "Tsy misy soratra ilay fanamarihana."),
+        "contribution.period_required" => (
+            "La période est requise (ex : '2025').",
+            "Ilaina ny fe-potoana (ohatra : '2025').",
+        ),
+        "contribution.payment_date_invalid" => (
+            "Date de paiement invalide : '{0}'. Format attendu : YYYY-MM-DD.",
+            "Daty nandoavana tsy mety : '{0}'. Soraty toy izao : TTTT-VV-AA.",
+        ),
+        "contribution.member_missing" => (
+            "Membre #{0} introuvable ou supprimé.",
+            "Tsy hita na voafafa ny mpikambana #{0}.",
+        ),
+        "year.not_empty" => (
+            "L'année {0} contient des données (cotisations, clôture ou note) : elle ne peut pas être masquée.",
+            "Misy raki-tsoratra ny taona {0} (fandoavana, fanakatonana na fanamarihana) : tsy azo afenina.",
+        ),
+        "year.not_closed" => ("L'année {0} n'est pas clôturée.", "Tsy mbola mihidy ny taona {0}."),
+        _ => ("Saisie invalide.", "Tsy mety ny fampidirana."),
+    }
+}
+
+fn not_found(what: &str, id: i64) -> ApiError {
+    ApiError::NotFound(format!("{what} #{id} introuvable."))
+}
+
+/// "2025-03-07" → 2025 ; `None` si la date n'a pas la forme "YYYY-MM-DD".
+fn date_year(date: &str) -> Option<i32> {
+    let b = date.as_bytes();
+    let shape = b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b.iter().enumerate().all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit());
+    shape.then(|| date[..4].parse().ok()).flatten()
+}
+
+/// Modèle de carte des paramètres ("C-%04d") appliqué au numéro `n`.
+fn format_card(pattern: &str, n: i64) -> String {
+    let Some((prefix, rest)) = pattern.split_once('%') else {
+        return format!("{pattern}{n}");
+    };
+    let Some((width, suffix)) = rest.split_once('d') else {
+        return format!("{prefix}{n}");
+    };
+    let width: usize = width.trim_start_matches('0').parse().unwrap_or(0);
+    format!("{prefix}{n:0width$}{suffix}")
+}
+
+fn empty_to_none(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// ─── Stockage ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+struct MockMember {
+    member:     Member,
+    deleted_at: Option<String>,
+}
+
+/// Base simulée ; `today` ("YYYY-MM-DD") et `now` (horodatage UTC) sont fournis
+/// par l'appelant pour que le stockage reste testable hors navigateur.
+#[derive(Debug, Clone)]
+pub struct MockDb {
+    members:       Vec<MockMember>,
+    contributions: Vec<Contribution>,
+    notes:         Vec<MemberNote>,
+    /// Années clôturées : (année, date de clôture, note)
+    closed:        Vec<(i32, String, Option<String>)>,
+    settings:      Settings,
+    lang:          Lang,
+    next_id:       i64,
+}
+
+impl MockDb {
+    /// Base vide, paramètres par défaut du backend.
+    pub fn new() -> Self {
+        Self {
+            members:       Vec::new(),
+            contributions: Vec::new(),
+            notes:         Vec::new(),
+            closed:        Vec::new(),
+            settings:      Settings {
+                church_name:      "FJKM Ambalavao Isotry".into(),
+                church_address:   String::new(),
+                treasurer_name:   String::new(),
+                currency_label:   "Ariary".into(),
+                default_pledge:   "0".into(),
+                backup_retention: 14,
+                quarter_periods:  true,
+                startup_integrity_check: false,
+                auto_close_enabled: false,
+                auto_close_grace_days: 15,
+                max_future_days:  7,
+                renewal_window_days: 30,
+                fiscal_year_start_month: 1,
+                default_period:   String::new(),
+                quick_amounts:    vec!["5000".into(), "10000".into(), "20000".into()],
+                card_pattern_communiant: "C-%04d".into(),
+                card_pattern_cathekomen: "K-%04d".into(),
+                operators:        Vec::new(),
+            },
+            lang:          Lang::default(),
+            next_id:       1,
+        }
+    }
+
+    /// Base de démonstration : quelques membres et des cotisations de l'année de `today`.
+    pub fn with_demo_data(today: &str, now: &str) -> Self {
+        let mut db = Self::new();
+        let year = date_year(today).unwrap_or(2025);
+        for (i, &(card, name, gender, member_type)) in DEMO_MEMBERS.iter().enumerate() {
+            let input = MemberInput {
+                card_number: card.into(),
+                full_name:   name.into(),
+                address:     Some("Ambalavao Isotry".into()),
+                gender,
+                member_type,
+                ..MemberInput::default()
+            };
+            let Ok(member) = db.create_member(&input, now) else { continue };
+            // Un membre sur trois n'a encore rien versé cette année
+            if i % 3 == 2 {
+                continue;
+            }
+            for (period, amount) in [(format!("{year}"), "10000"), (format!("{}", year - 1), "5000")] {
+                let input = ContributionInput {
+                    member_id:    member.id,
+                    payment_date: format!("{}-01-15", &period),
+                    period,
+                    amount:       amount.into(),
+                    ..ContributionInput::default()
+                };
+                let _ = db.create_contribution(&input);
+            }
+        }
+        db
+    }
+
+    fn error(&self, key: &str, args: &[&dyn std::fmt::Display]) -> ApiError {
+        let (fr, mg) = message(key);
+        let mut text = match self.lang {
+            Lang::Fr => fr.to_string(),
+            Lang::Mg => mg.to_string(),
+        };
+        for (i, arg) in args.iter().enumerate() {
+            text = text.replace(&format!("{{{i}}}"), &arg.to_string());
+        }
+        ApiError::Validation(text)
+    }
+
+    fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    fn active(&self) -> impl Iterator<Item = &Member> {
+        self.members.iter().filter(|m| m.deleted_at.is_none()).map(|m| &m.member)
+    }
+
+    fn position(&self, id: i64) -> Result<usize, ApiError> {
+        self.members
+            .iter()
+            .position(|m| m.member.id == id && m.deleted_at.is_none())
+            .ok_or_else(|| not_found("Membre", id))
+    }
+
+    // ── Membres ──────────────────────────────────────────────────────────────
+
+    pub fn member(&self, id: i64) -> Result<Member, ApiError> {
+        self.position(id).map(|i| self.members[i].member.clone())
+    }
+
+    pub fn members_by_type(&self, member_type: Option<MemberType>) -> Vec<Member> {
+        let mut list: Vec<Member> = self
+            .active()
+            .filter(|m| member_type.is_none_or(|t| m.member_type == t))
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+        list
+    }
+
+    pub fn member_counts(&self) -> MemberCounts {
+        let count = |t| self.active().filter(|m| m.member_type == t).count() as i64;
+        MemberCounts {
+            communiants: count(MemberType::Communiant),
+            cathekomens: count(MemberType::Cathekomen),
+        }
+    }
+
+    fn with_total(&self, m: &Member, year: i32) -> MemberWithTotal {
+        let (mut total, mut year_total) = (Decimal::ZERO, Decimal::ZERO);
+        for c in self.contributions.iter().filter(|c| c.member_id == m.id) {
+            let amount = Decimal::from_str(&c.amount).unwrap_or_default();
+            total += amount;
+            if c.recorded_year == year {
+                year_total += amount;
+            }
+        }
+        MemberWithTotal {
+            total_contributions: total.to_string(),
+            current_year_total:  year_total.to_string(),
+            ..MemberWithTotal::from(m.clone())
+        }
+    }
+
+    /// `status = None` : tous les statuts.
+    pub fn members_with_total(
+        &self,
+        member_type: MemberType,
+        status: Option<MemberStatus>,
+        year: i32,
+    ) -> Vec<MemberWithTotal> {
+        self.members_by_type(Some(member_type))
+            .iter()
+            .filter(|m| status.is_none_or(|s| m.status == s))
+            .map(|m| self.with_total(m, year))
+            .collect()
+    }
+
+    /// Totaux par année : seules les années demandées ayant des cotisations figurent.
+    pub fn members_with_year_totals(&self, member_type: MemberType, years: &[i32], year: i32) -> Vec<MemberYearTotals> {
+        self.members_with_total(member_type, None, year)
+            .into_iter()
+            .map(|member| {
+                let mut year_totals: BTreeMap<i32, Decimal> = BTreeMap::new();
+                for c in self.contributions.iter().filter(|c| c.member_id == member.id && years.contains(&c.recorded_year)) {
+                    *year_totals.entry(c.recorded_year).or_default() += Decimal::from_str(&c.amount).unwrap_or_default();
+                }
+                let year_totals = year_totals.into_iter().map(|(y, t)| (y, t.to_string())).collect();
+                MemberYearTotals { member, year_totals }
+            })
+            .collect()
+    }
+
+    /// Même regroupement que le backend : quartiers triés, « sans adresse » en dernier.
+    pub fn members_by_address(&self, member_type: MemberType, year: i32) -> Vec<AddressGroup> {
+        let members = self.members_with_total(member_type, None, year);
+        let mut groups: Vec<AddressGroup> = Vec::new();
+        for m in members {
+            let address = address_key(m.address.as_deref());
+            match groups.iter_mut().find(|g| g.address == address) {
+                Some(g) => g.members.push(m),
+                None    => groups.push(AddressGroup { address, members: vec![m] }),
+            }
+        }
+        groups.sort_by(|a, b| (a.address.is_none(), &a.address).cmp(&(b.address.is_none(), &b.address)));
+        groups
+    }
+
+    /// Même tri, filtres et pagination que `get_member_rows` en SQL.
+    pub fn member_rows(
+        &self,
+        member_type: MemberType,
+        page: usize,
+        page_size: usize,
+        sort: MemberSort,
+        filter: &MemberFilter,
+        year: i32,
+    ) -> Page<MemberRow> {
+        let query = filter.search.trim().to_lowercase();
+        let mut rows: Vec<MemberWithTotal> = self
+            .members_with_total(member_type, filter.status, year)
+            .into_iter()
+            .filter(|m| filter.gender.is_none_or(|g| m.gender == g))
+            .filter(|m| !filter.unpaid_only || Decimal::from_str(&m.current_year_total).unwrap_or_default().is_zero())
+            .filter(|m| {
+                query.is_empty()
+                    || [Some(&m.full_name), Some(&m.card_number), m.address.as_ref(), m.phone.as_ref(), m.job.as_ref()]
+                        .into_iter()
+                        .flatten()
+                        .any(|f| f.to_lowercase().contains(&query))
+            })
+            .collect();
+        let text = |v: &Option<String>| v.clone().unwrap_or_default();
+        rows.sort_by(|a, b| {
+            let ord = match sort.col {
+                MemberSortCol::CardNumber       => a.card_number.cmp(&b.card_number),
+                MemberSortCol::FullName         => a.sort_name().cmp(&b.sort_name()),
+                MemberSortCol::Address          => text(&a.address).cmp(&text(&b.address)),
+                MemberSortCol::Phone            => text(&a.phone).cmp(&text(&b.phone)),
+                MemberSortCol::Job              => text(&a.job).cmp(&text(&b.job)),
+                MemberSortCol::Gender           => a.gender.cmp(&b.gender),
+                MemberSortCol::CurrentYearTotal => Decimal::from_str(&a.current_year_total)
+                    .unwrap_or_default()
+                    .cmp(&Decimal::from_str(&b.current_year_total).unwrap_or_default()),
+            }
+            .then(a.id.cmp(&b.id));
+            if sort.desc { ord.reverse() } else { ord }
+        });
+        let page_size = page_size.max(1);
+        Page {
+            total_count: rows.len() as i64,
+            items:       rows.into_iter().skip(page * page_size).take(page_size).map(MemberRow::from).collect(),
+        }
+    }
+
+    pub fn member_by_card(&self, card_number: &str) -> Option<Member> {
+        let card = card_number.trim();
+        self.active().find(|m| m.card_number.eq_ignore_ascii_case(card)).cloned()
+    }
+
+    pub fn search_members(&self, query: &str, limit: usize, year: i32) -> Vec<MemberWithTotal> {
+        let q = query.trim().to_lowercase();
+        if q.is_empty() {
+            return Vec::new();
+        }
+        self.members_by_type(None)
+            .iter()
+            .filter(|m| m.full_name.to_lowercase().contains(&q) || m.card_number.to_lowercase().contains(&q))
+            .take(limit)
+            .map(|m| self.with_total(m, year))
+            .collect()
+    }
+
+    fn validate_member(&self, input: &MemberInput, id: Option<i64>) -> Result<MemberInput, ApiError> {
+        let mut input = input.clone();
+        input.card_number = input.card_number.trim().to_string();
+        input.surname     = empty_to_none(input.surname);
+        input.given_name  = empty_to_none(input.given_name);
+        input.full_name   = input.full_name.trim().to_string();
+        if input.full_name.is_empty() {
+            input.full_name = [input.surname.as_deref(), input.given_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+        if input.card_number.is_empty() {
+            return Err(self.error("member.card_required", &[]));
+        }
+        if input.full_name.is_empty() {
+            return Err(self.error("member.name_required", &[]));
+        }
+        let owner = self
+            .members
+            .iter()
+            .find(|m| m.member.card_number == input.card_number && Some(m.member.id) != id);
+        if let Some(owner) = owner {
+            let (name, card) = (&owner.member.full_name, &input.card_number);
+            return Err(ApiError::Conflict(match owner.deleted_at {
+                Some(_) => format!(
+                    "Efa an'i {name} (voafafa) ny laharana karatra '{card}'. \
+                     Avereno izy na mifidiana laharana hafa."
+                ),
+                None => format!("Efa an'i {name} ny laharana karatra '{card}'. Mifidiana laharana hafa."),
+            }));
+        }
+        input.address = empty_to_none(input.address);
+        input.phone   = empty_to_none(input.phone);
+        input.job     = empty_to_none(input.job);
+        Ok(input)
+    }
+
+    pub fn create_member(&mut self, input: &MemberInput, now: &str) -> Result<Member, ApiError> {
+        let input = self.validate_member(input, None)?;
+        let member = Member {
+            id:          self.next_id(),
+            card_number: input.card_number,
+            full_name:   input.full_name,
+            address:     input.address,
+            phone:       input.phone,
+            job:         input.job,
+            gender:      input.gender,
+            member_type: input.member_type,
+            surname:     input.surname,
+            given_name:  input.given_name,
+            status:      input.status,
+            birth_date:  input.birth_date,
+            created_at:  now.to_string(),
+            transferred_at: None,
+        };
+        self.members.push(MockMember { member: member.clone(), deleted_at: None });
+        Ok(member)
+    }
+
+    pub fn update_member(&mut self, id: i64, input: &MemberInput) -> Result<Member, ApiError> {
+        let i = self.position(id)?;
+        let input = self.validate_member(input, Some(id))?;
+        let m = &mut self.members[i].member;
+        m.card_number = input.card_number;
+        m.full_name   = input.full_name;
+        m.address     = input.address;
+        m.phone       = input.phone;
+        m.job         = input.job;
+        m.gender      = input.gender;
+        m.member_type = input.member_type;
+        m.surname     = input.surname;
+        m.given_name  = input.given_name;
+        m.status      = input.status;
+        m.birth_date  = input.birth_date;
+        Ok(m.clone())
+    }
+
+    /// Corbeille : les cotisations restent, comme côté backend.
+    pub fn delete_member(&mut self, id: i64, now: &str) -> Result<(), ApiError> {
+        let i = self.position(id)?;
+        self.members[i].deleted_at = Some(now.to_string());
+        Ok(())
+    }
+
+    pub fn deleted_members(&self) -> Vec<DeletedMember> {
+        self.members
+            .iter()
+            .filter_map(|m| {
+                let deleted_at = m.deleted_at.clone()?;
+                Some(DeletedMember {
+                    id:                 m.member.id,
+                    card_number:        m.member.card_number.clone(),
+                    full_name:          m.member.full_name.clone(),
+                    member_type:        m.member.member_type,
+                    deleted_at,
+                    contribution_count: self.contributions.iter().filter(|c| c.member_id == m.member.id).count() as i64,
+                })
+            })
+            .collect()
+    }
+
+    pub fn restore_member(&mut self, id: i64) -> Result<Member, ApiError> {
+        let m = self
+            .members
+            .iter_mut()
+            .find(|m| m.member.id == id && m.deleted_at.is_some())
+            .ok_or_else(|| not_found("Membre", id))?;
+        m.deleted_at = None;
+        Ok(m.member.clone())
+    }
+
+    /// Premier numéro libre du modèle des paramètres.
+    pub fn next_card_number(&self, member_type: MemberType) -> String {
+        let pattern = match member_type {
+            MemberType::Communiant => &self.settings.card_pattern_communiant,
+            MemberType::Cathekomen => &self.settings.card_pattern_cathekomen,
+        };
+        (1..)
+            .map(|n| format_card(pattern, n))
+            .find(|card| self.members.iter().all(|m| &m.member.card_number != card))
+            .unwrap_or_default()
+    }
+
+    // ── Cotisations ──────────────────────────────────────────────────────────
+
+    /// Plus récentes d'abord, comme le backend.
+    pub fn contributions(&self, member_id: i64) -> Vec<Contribution> {
+        let mut list: Vec<Contribution> =
+            self.contributions.iter().filter(|c| c.member_id == member_id).cloned().collect();
+        list.sort_by(|a, b| b.payment_date.cmp(&a.payment_date).then(b.id.cmp(&a.id)));
+        list
+    }
+
+    pub fn contributions_by_year(&self, year: i32) -> Vec<Contribution> {
+        self.contributions.iter().filter(|c| c.recorded_year == year).cloned().collect()
+    }
+
+    pub fn create_contribution(&mut self, input: &ContributionInput) -> Result<Contribution, ApiError> {
+        self.position(input.member_id)
+            .map_err(|_| self.error("contribution.member_missing", &[&input.member_id]))?;
+        let Some(year) = date_year(&input.payment_date) else {
+            return Err(self.error("contribution.payment_date_invalid", &[&input.payment_date]));
+        };
+        if input.period.trim().is_empty() {
+            return Err(self.error("contribution.period_required", &[]));
+        }
+        let raw = input.amount.trim().replace(' ', "").replace(',', ".");
+        if raw.is_empty() {
+            return Err(self.error("amount.required", &[]));
+        }
+        let amount = Decimal::from_str(&raw).map_err(|_| self.error("amount.invalid", &[&input.amount]))?;
+        if amount.is_sign_negative() {
+            return Err(self.error("amount.negative", &[]));
+        }
+        let sequence = self.contributions.iter().filter(|c| c.recorded_year == year).count() + 1;
+        let contribution = Contribution {
+            id:            self.next_id(),
+            member_id:     input.member_id,
+            payment_date:  input.payment_date.clone(),
+            period:        input.period.trim().to_string(),
+            amount:        amount.normalize().to_string(),
+            recorded_year: year,
+            receipt_number: format!("{year}-{sequence:05}"),
+            recorded_by:   input.recorded_by.clone(),
+        };
+        self.contributions.push(contribution.clone());
+        Ok(contribution)
+    }
+
+    pub fn delete_contribution(&mut self, id: i64) -> Result<(), ApiError> {
+        let before = self.contributions.len();
+        self.contributions.retain(|c| c.id != id);
+        if self.contributions.len() == before {
+            return Err(not_found("Cotisation", id));
+        }
+        Ok(())
+    }
+
+    /// Sous-totaux par année, la plus récente d'abord.
+    pub fn member_year_totals(&self, member_id: i64) -> Vec<MemberYearTotal> {
+        let mut years: Vec<i32> = self
+            .contributions
+            .iter()
+            .filter(|c| c.member_id == member_id)
+            .map(|c| c.recorded_year)
+            .collect();
+        years.sort_unstable_by(|a, b| b.cmp(a));
+        years.dedup();
+        years
+            .into_iter()
+            .map(|year| {
+                let list: Vec<&Contribution> = self
+                    .contributions
+                    .iter()
+                    .filter(|c| c.member_id == member_id && c.recorded_year == year)
+                    .collect();
+                MemberYearTotal {
+                    year,
+                    total: list.iter().map(|c| Decimal::from_str(&c.amount).unwrap_or_default()).sum::<Decimal>().to_string(),
+                    contribution_count: list.len() as i64,
+                }
+            })
+            .collect()
+    }
+
+    /// Archives : recherche sur le nom, le reçu, la période ou la date.
+    pub fn contributions_with_member(
+        &self,
+        year: Option<i32>,
+        query: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Page<ContributionWithMember> {
+        let q = query.trim().to_lowercase();
+        let name = |id: i64| {
+            self.members.iter().find(|m| m.member.id == id).map(|m| m.member.full_name.clone()).unwrap_or_default()
+        };
+        let mut rows: Vec<ContributionWithMember> = self
+            .contributions
+            .iter()
+            .filter(|c| year.is_none_or(|y| c.recorded_year == y))
+            .map(|c| ContributionWithMember {
+                id:            c.id,
+                member_id:     c.member_id,
+                member_name:   name(c.member_id),
+                payment_date:  c.payment_date.clone(),
+                period:        c.period.clone(),
+                amount:        c.amount.clone(),
+                recorded_year: c.recorded_year,
+                receipt_number: c.receipt_number.clone(),
+                recorded_by:   c.recorded_by.clone(),
+                audit_summary: None,
+            })
+            .filter(|c| {
+                q.is_empty()
+                    || [&c.member_name, &c.receipt_number, &c.period, &c.payment_date]
+                        .iter()
+                        .any(|f| f.to_lowercase().contains(&q))
+            })
+            .collect();
+        rows.sort_by(|a, b| b.payment_date.cmp(&a.payment_date).then(b.id.cmp(&a.id)));
+        Page {
+            total_count: rows.len() as i64,
+            items:       rows.into_iter().skip(offset).take(limit.max(1)).collect(),
+        }
+    }
+
+    // ── Notes ────────────────────────────────────────────────────────────────
+
+    pub fn add_member_note(&mut self, member_id: i64, text: &str, now: &str) -> Result<MemberNote, ApiError> {
+        self.position(member_id)?;
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(self.error("member.note_required", &[]));
+        }
+        let note = MemberNote {
+            id:         self.next_id(),
+            member_id,
+            created_at: now.to_string(),
+            text:       text.to_string(),
+        };
+        self.notes.push(note.clone());
+        Ok(note)
+    }
+
+    pub fn member_notes(&self, member_id: i64) -> Vec<MemberNote> {
+        let mut list: Vec<MemberNote> = self.notes.iter().filter(|n| n.member_id == member_id).cloned().collect();
+        list.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        list
+    }
+
+    pub fn delete_member_note(&mut self, id: i64) -> Result<(), ApiError> {
+        let before = self.notes.len();
+        self.notes.retain(|n| n.id != id);
+        if self.notes.len() == before {
+            return Err(not_found("Note", id));
+        }
+        Ok(())
+    }
+
+    // ── Années ───────────────────────────────────────────────────────────────
+
+    /// Résumé calculé à la volée ; `closed_at` seulement si l'année a été clôturée.
+    pub fn year_summary(&self, year: i32) -> YearSummary {
+        let list: Vec<&Contribution> = self.contributions.iter().filter(|c| c.recorded_year == year).collect();
+        let type_of = |id: i64| self.members.iter().find(|m| m.member.id == id).map(|m| m.member.member_type);
+        let sum = |t: Option<MemberType>| {
+            list.iter()
+                .filter(|c| t.is_none_or(|t| type_of(c.member_id) == Some(t)))
+                .map(|c| Decimal::from_str(&c.amount).unwrap_or_default())
+                .sum::<Decimal>()
+                .to_string()
+        };
+        let mut contributors: Vec<i64> = list.iter().map(|c| c.member_id).collect();
+        contributors.sort_unstable();
+        contributors.dedup();
+        let closed = self.closed.iter().find(|(y, _, _)| *y == year);
+        YearSummary {
+            year,
+            total:              sum(None),
+            total_communiant:   sum(Some(MemberType::Communiant)),
+            total_cathekomen:   sum(Some(MemberType::Cathekomen)),
+            contributor_count:  contributors.len() as i64,
+            contribution_count: list.len() as i64,
+            closed_at:          closed.map(|(_, at, _)| at.clone()),
+            note:               closed.and_then(|(_, _, note)| note.clone()),
+        }
+    }
+
+    /// Années ayant des cotisations ou clôturées, la plus récente d'abord.
+    pub fn year_summaries(&self) -> Vec<YearSummary> {
+        let mut years: Vec<i32> = self
+            .contributions
+            .iter()
+            .map(|c| c.recorded_year)
+            .chain(self.closed.iter().map(|(y, _, _)| *y))
+            .collect();
+        years.sort_unstable_by(|a, b| b.cmp(a));
+        years.dedup();
+        years.into_iter().map(|y| self.year_summary(y)).collect()
+    }
+
+    /// Aucun résumé vide n'est conservé ici : seule une année avec données est refusée.
+    pub fn delete_empty_year_summary(&self, year: i32) -> Result<(), ApiError> {
+        let summary = self.year_summary(year);
+        if summary.contribution_count > 0 || summary.closed_at.is_some() {
+            return Err(self.error("year.not_empty", &[&year]));
+        }
+        Ok(())
+    }
+
+    pub fn close_year(&mut self, year: i32, note: Option<String>, now: &str) -> YearSummary {
+        if !self.closed.iter().any(|(y, _, _)| *y == year) {
+            self.closed.push((year, now.to_string(), empty_to_none(note)));
+        }
+        self.year_summary(year)
+    }
+
+    pub fn update_year_note(&mut self, year: i32, note: Option<String>) -> Result<YearSummary, ApiError> {
+        let Some(closed) = self.closed.iter_mut().find(|(y, _, _)| *y == year) else {
+            return Err(self.error("year.not_closed", &[&year]));
+        };
+        closed.2 = empty_to_none(note);
+        Ok(self.year_summary(year))
+    }
+
+    /// Effectifs : membres au statut `Actif` seulement, comme côté backend.
+    pub fn dashboard_stats(&self, year: i32) -> DashboardStats {
+        let count = |keep: &dyn Fn(&Member) -> bool| {
+            self.active().filter(|m| m.status == MemberStatus::Actif && keep(m)).count() as i64
+        };
+        let gender = |g| count(&|m| m.gender == g);
+        let this_year = self.contributions_by_year(year);
+        DashboardStats {
+            year,
+            communiants:             count(&|m| m.member_type == MemberType::Communiant),
+            cathekomens:             count(&|m| m.member_type == MemberType::Cathekomen),
+            men:                     gender(Gender::M),
+            women:                   gender(Gender::F),
+            current_year_total:      self.year_summary(year).total,
+            previous_year_total:     self.year_summary(year - 1).total,
+            contributions_this_year: this_year.len() as i64,
+            last_contribution_date:  self.contributions.iter().map(|c| c.payment_date.clone()).max(),
+        }
+    }
+
+    /// Comme `get_db_health` : pas de migrations ni de fichier dans le navigateur.
+    fn health(&self) -> DbHealth {
+        let table = |table: &str, rows: usize| TableRowCount { table: table.into(), rows: rows as i64 };
+        // Un résumé par année cotisée ou clôturée
+        let years: BTreeSet<i32> = self
+            .contributions
+            .iter()
+            .map(|c| c.recorded_year)
+            .chain(self.closed.iter().map(|(y, ..)| *y))
+            .collect();
+        DbHealth {
+            schema_version: 0,
+            tables:         vec![
+                table("contributions", self.contributions.len()),
+                table("member_notes", self.notes.len()),
+                table("members", self.members.len()),
+                table("year_summaries", years.len()),
+            ],
+            size_bytes:     0,
+        }
+    }
+}
+
+impl Default for MockDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ─── Commandes ────────────────────────────────────────────────────────────────
+
+/// Argument `key` (camelCase, comme pour `invoke`) désérialisé ; absent = `null`.
+fn arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<T, ApiError> {
+    serde_json::from_value(args.get(key).cloned().unwrap_or(Value::Null))
+        .map_err(|e| ApiError::Other(format!("{key} : {e}")))
+}
+
+fn json<T: Serialize>(value: T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::Other(e.to_string()))
+}
+
+/// Exécute la commande `cmd` sur `db`. `today` : "YYYY-MM-DD", `now` : horodatage UTC.
+pub fn dispatch(db: &mut MockDb, cmd: &str, args: &Value, today: &str, now: &str) -> Result<Value, ApiError> {
+    let year = date_year(today).unwrap_or_default();
+    match cmd {
+        // ── Membres ──
+        "get_members"          => json(db.members_by_type(None)),
+        "get_members_by_type"  => json(db.members_by_type(Some(arg(args, "memberType")?))),
+        "get_member_counts"    => json(db.member_counts()),
+        "get_members_by_type_with_total" => {
+            json(db.members_with_total(arg(args, "memberType")?, arg(args, "status")?, year))
+        }
+        "get_members_with_year_totals" => {
+            let years: Vec<i32> = arg(args, "years")?;
+            json(db.members_with_year_totals(arg(args, "memberType")?, &years, year))
+        }
+        "get_members_grouped_by_address" => json(db.members_by_address(arg(args, "memberType")?, year)),
+        "get_member_rows" => json(db.member_rows(
+            arg(args, "memberType")?,
+            arg(args, "page")?,
+            arg(args, "pageSize")?,
+            arg(args, "sort")?,
+            &arg(args, "filter")?,
+            year,
+        )),
+        "get_member"           => json(db.member(arg(args, "id")?)?),
+        "get_member_by_card"   => json(db.member_by_card(&arg::<String>(args, "cardNumber")?)),
+        "search_members" => {
+            let limit: Option<usize> = arg(args, "limit")?;
+            json(db.search_members(&arg::<String>(args, "query")?, limit.unwrap_or(50), year))
+        }
+        "search_members_lite" => json(
+            db.search_members(&arg::<String>(args, "query")?, 50, year)
+                .into_iter()
+                .map(|m| MemberLite { id: m.id, full_name: m.full_name, card_number: m.card_number })
+                .collect::<Vec<_>>(),
+        ),
+        "create_member"        => json(db.create_member(&arg(args, "member")?, now)?),
+        "update_member"        => json(db.update_member(arg(args, "id")?, &arg(args, "member")?)?),
+        "delete_member"        => json(db.delete_member(arg(args, "id")?, now)?),
+        "get_deleted_members"  => json(db.deleted_members()),
+        "restore_member"       => json(db.restore_member(arg(args, "id")?)?),
+        "next_card_number"     => json(db.next_card_number(arg(args, "memberType")?)),
+        "find_similar_members" | "get_upcoming_birthdays" | "get_upcoming_renewals" => {
+            json(Vec::<Value>::new())
+        }
+        "get_members_without_contribution" => {
+            let year: i32 = arg(args, "year")?;
+            let list: Vec<Member> = db
+                .members_by_type(arg(args, "memberType")?)
+                .into_iter()
+                .filter(|m| !db.contributions.iter().any(|c| c.member_id == m.id && c.recorded_year == year))
+                .collect();
+            json(list)
+        }
+
+        // ── Notes ──
+        "add_member_note"      => json(db.add_member_note(arg(args, "memberId")?, &arg::<String>(args, "text")?, now)?),
+        "get_member_notes"     => json(db.member_notes(arg(args, "memberId")?)),
+        "delete_member_note"   => json(db.delete_member_note(arg(args, "id")?)?),
+
+        // ── Cotisations ──
+        "get_contributions"    => json(db.contributions(arg(args, "memberId")?)),
+        "get_recent_contributions" => {
+            let limit: usize = arg(args, "limit")?;
+            json(db.contributions(arg(args, "memberId")?).into_iter().take(limit).collect::<Vec<_>>())
+        }
+        "get_contributions_by_year" => json(db.contributions_by_year(arg(args, "year")?)),
+        "get_member_year_totals" => json(db.member_year_totals(arg(args, "memberId")?)),
+        "create_contribution"  => json(db.create_contribution(&arg(args, "contribution")?)?),
+        "create_contributions_batch" => {
+            let inputs: Vec<ContributionInput> = arg(args, "contributions")?;
+            // Tout ou rien, comme la transaction du backend
+            let mut staged = db.clone();
+            let created = inputs
+                .iter()
+                .map(|input| staged.create_contribution(input))
+                .collect::<Result<Vec<_>, _>>()?;
+            *db = staged;
+            json(created)
+        }
+        "delete_contribution"  => json(db.delete_contribution(arg(args, "id")?)?),
+        "get_contributions_by_year_with_member" => {
+            let query: Option<String> = arg(args, "query")?;
+            json(db.contributions_with_member(
+                arg(args, "year")?,
+                query.as_deref().unwrap_or(""),
+                arg(args, "limit")?,
+                arg(args, "offset")?,
+            ))
+        }
+
+        // ── Années et statistiques ──
+        "get_year_summaries"   => json(db.year_summaries()),
+        "get_years_with_contributions" => json(db.year_summaries().iter().map(|s| s.year).collect::<Vec<_>>()),
+        "delete_empty_year_summary"    => json(db.delete_empty_year_summary(arg(args, "year")?)?),
+        "get_year_summary" => {
+            let summary = db.year_summary(arg(args, "year")?);
+            json((summary.closed_at.is_some() || summary.contribution_count > 0).then_some(summary))
+        }
+        "close_year"           => json(db.close_year(arg(args, "year")?, arg(args, "note")?, now)),
+        "update_year_note"     => json(db.update_year_note(arg(args, "year")?, arg(args, "note")?)?),
+        "check_and_close_previous_year" => {
+            let previous = db.year_summary(year - 1);
+            json(match previous.closed_at {
+                Some(_) => YearCloseStatus::AlreadyClosed,
+                None    => YearCloseStatus::Disabled { year: year - 1, total: previous.total },
+            })
+        }
+        "get_dashboard_stats"  => json(db.dashboard_stats(year)),
+
+        // ── Paramètres et divers ──
+        "get_settings"         => json(&db.settings),
+        "update_settings" => {
+            db.settings = arg(args, "settings")?;
+            json(&db.settings)
+        }
+        "get_language"         => json(db.lang),
+        "set_language" => {
+            db.lang = arg(args, "lang")?;
+            json(db.lang)
+        }
+        "has_pin"              => json(false),
+        "get_pending_tasks"    => json(Vec::<Value>::new()),
+        "run_integrity_check"  => json(IntegrityReport { checked_at: now.to_string(), issues: Vec::new() }),
+        "get_db_health"        => json(db.health()),
+        // Fenêtre : rien à faire dans un onglet de navigateur
+        "minimize_window" | "close_window" => Ok(Value::Null),
+        "toggle_maximize" | "is_window_maximized" => json(false),
+
+        _ => Err(ApiError::Other(format!("Tsy misy amin'ny fanandramana : {cmd}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TODAY: &str = "2025-03-10";
+    const NOW: &str = "2025-03-10T08:00:00";
+
+    fn input(card: &str, name: &str) -> MemberInput {
+        MemberInput { card_number: card.into(), full_name: name.into(), ..MemberInput::default() }
+    }
+
+    fn contribution(member_id: i64, date: &str, amount: &str) -> ContributionInput {
+        ContributionInput {
+            member_id,
+            payment_date: date.into(),
+            period:       date[..4].into(),
+            amount:       amount.into(),
+            ..ContributionInput::default()
+        }
+    }
+
+    #[test]
+    fn test_membres_crud_et_validation() {
+        let mut db = MockDb::new();
+        let jean = db.create_member(&input(" C-0001 ", "RAKOTO Jean"), NOW).unwrap();
+        assert_eq!(jean.card_number, "C-0001");
+        assert_eq!(db.member_counts().communiants, 1);
+        assert_eq!(db.next_card_number(MemberType::Communiant), "C-0002");
+
+        assert_eq!(
+            db.create_member(&input("", "X"), NOW),
+            Err(ApiError::Validation("Ilaina ny laharan'ny karatra.".into()))
+        );
+        assert!(matches!(db.create_member(&input("C-0001", "Bis"), NOW), Err(ApiError::Conflict(_))));
+        db.lang = Lang::Fr;
+        assert_eq!(
+            db.create_member(&input("C-0009", "  "), NOW),
+            Err(ApiError::Validation("Le nom complet est requis.".into()))
+        );
+
+        let renamed = db.update_member(jean.id, &input("C-0001", "RAKOTO Jean Paul")).unwrap();
+        assert_eq!(db.member(jean.id).unwrap(), renamed);
+
+        // Corbeille : la carte reste prise, le membre disparaît des listes
+        db.delete_member(jean.id, NOW).unwrap();
+        assert!(db.members_by_type(None).is_empty());
+        assert_eq!(db.deleted_members()[0].id, jean.id);
+        assert!(matches!(db.create_member(&input("C-0001", "Autre"), NOW), Err(ApiError::Conflict(_))));
+        db.restore_member(jean.id).unwrap();
+        assert_eq!(db.member_counts().communiants, 1);
+        assert!(matches!(db.member(999), Err(ApiError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_cotisations_et_totaux() {
+        let mut db = MockDb::new();
+        let a = db.create_member(&input("C-0001", "RAKOTO Jean"), NOW).unwrap().id;
+        let b = db.create_member(&input("C-0002", "Bako Randria"), NOW).unwrap().id;
+        let c1 = db.create_contribution(&contribution(a, "2025-02-01", "10 000")).unwrap();
+        assert_eq!((c1.amount.as_str(), c1.receipt_number.as_str()), ("10000", "2025-00001"));
+        db.create_contribution(&contribution(a, "2024-12-01", "2500,50")).unwrap();
+        db.create_contribution(&contribution(b, "2025-03-01", "5000")).unwrap();
+
+        assert!(matches!(db.create_contribution(&contribution(a, "2025-02-01", "abc")), Err(ApiError::Validation(_))));
+        assert!(matches!(db.create_contribution(&contribution(a, "01/02/2025", "1")), Err(ApiError::Validation(_))));
+        assert_eq!(
+            db.create_contribution(&contribution(42, "2025-02-01", "1")),
+            Err(ApiError::Validation("Tsy hita na voafafa ny mpikambana #42.".into()))
+        );
+
+        let totals = db.members_with_total(MemberType::Communiant, None, 2025);
+        let jean = totals.iter().find(|m| m.id == a).unwrap();
+        assert_eq!((jean.total_contributions.as_str(), jean.current_year_total.as_str()), ("12500.5", "10000"));
+        assert_eq!(db.member_year_totals(a).iter().map(|t| t.year).collect::<Vec<_>>(), [2025, 2024]);
+        assert_eq!(db.year_summary(2025).total, "15000");
+        assert_eq!(db.year_summary(2025).contributor_count, 2);
+        assert_eq!(db.dashboard_stats(2025).previous_year_total, "2500.5");
+
+        db.delete_contribution(c1.id).unwrap();
+        assert_eq!(db.year_summary(2025).total, "5000");
+        assert!(db.delete_contribution(c1.id).is_err());
+        assert!(db.close_year(2024, Some("Vita".into()), NOW).closed_at.is_some());
+        assert_eq!(db.update_year_note(2024, Some("  Vita tsara ".into())).unwrap().note.as_deref(), Some("Vita tsara"));
+        assert!(db.update_year_note(2025, None).is_err());
+        assert_eq!(db.year_summaries().iter().map(|s| s.year).collect::<Vec<_>>(), [2025, 2024]);
+    }
+
+    #[test]
+    fn test_lignes_tri_filtres_pagination() {
+        let mut db = MockDb::new();
+        for (card, name) in [("C-0001", "Jean RAKOTO"), ("C-0002", "Bako Randria"), ("C-0003", "Andry RATSIMBA")] {
+            let input = MemberInput { surname: None, ..input(card, name) };
+            db.create_member(&input, NOW).unwrap();
+        }
+        // Parties du nom saisies : tri par nom de famille
+        let id = db.member_by_card("c-0001").unwrap().id;
+        let named = MemberInput { surname: Some("RAKOTO".into()), given_name: Some("Jean".into()), ..input("C-0001", "Jean RAKOTO") };
+        db.update_member(id, &named).unwrap();
+        db.create_contribution(&contribution(id, "2025-01-05", "1000")).unwrap();
+
+        let rows = |sort, filter: MemberFilter, page| {
+            let p = db.member_rows(MemberType::Communiant, page, 2, sort, &filter, 2025);
+            (p.items.into_iter().map(|r| r.card_number).collect::<Vec<_>>(), p.total_count)
+        };
+        let by_name = MemberSort { col: MemberSortCol::FullName, desc: false };
+        assert_eq!(rows(by_name, MemberFilter::default(), 0), (vec!["C-0003".into(), "C-0002".into()], 3));
+        assert_eq!(rows(by_name, MemberFilter::default(), 1), (vec!["C-0001".into()], 3));
+        let unpaid = MemberFilter { unpaid_only: true, ..MemberFilter::default() };
+        assert_eq!(rows(by_name, unpaid, 0).1, 2);
+        let search = MemberFilter { search: "RANDRIA".into(), ..MemberFilter::default() };
+        assert_eq!(rows(by_name, search, 0).0, ["C-0002"]);
+        let by_total = MemberSort { col: MemberSortCol::CurrentYearTotal, desc: true };
+        assert_eq!(rows(by_total, MemberFilter::default(), 0).0[0], "C-0001");
+    }
+
+    #[test]
+    fn test_dispatch_commandes() {
+        let mut db = MockDb::with_demo_data(TODAY, NOW);
+        let mut call = |cmd: &str, args: Value| dispatch(&mut db, cmd, &args, TODAY, NOW);
+
+        let counts: MemberCounts = serde_json::from_value(call("get_member_counts", json!({})).unwrap()).unwrap();
+        assert_eq!((counts.communiants, counts.cathekomens), (4, 2));
+        let created = call(
+            "create_member",
+            json!({ "member": MemberInput { member_type: MemberType::Cathekomen, ..input("K-0003", "RABE Fidy") } }),
+        )
+        .unwrap();
+        let id = created["id"].as_i64().unwrap();
+        let batch = json!({ "contributions": [contribution(id, "2025-02-01", "1000"), contribution(id, "2025-02-02", "x")] });
+        assert!(matches!(call("create_contributions_batch", batch), Err(ApiError::Validation(_))));
+        assert_eq!(call("get_contributions", json!({ "memberId": id })).unwrap(), json!([]));
+
+        let page = call(
+            "get_member_rows",
+            json!({
+                "memberType": "Cathekomen",
+                "page":       0,
+                "pageSize":   10,
+                "sort":       MemberSort::default(),
+                "filter":     MemberFilter::default(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(page["total_count"], 3);
+        let inactive = MemberInput { status: MemberStatus::Inactif, member_type: MemberType::Cathekomen, ..input("K-0003", "RABE Fidy") };
+        call("update_member", json!({ "id": id, "member": inactive })).unwrap();
+        let list = call("get_members_by_type_with_total", json!({ "memberType": "Cathekomen", "status": "Inactif" })).unwrap();
+        assert_eq!(list[0]["id"], id);
+        let groups = call("get_members_grouped_by_address", json!({ "memberType": "Cathekomen" })).unwrap();
+        assert_eq!(groups[0]["address"], "ambalavao isotry");
+        assert_eq!(groups[1]["address"], Value::Null);
+        assert_eq!(groups[1]["members"][0]["id"], id);
+        call("create_contribution", json!({ "contribution": contribution(id, "2024-06-01", "1500") })).unwrap();
+        let by_year = call("get_members_with_year_totals", json!({ "memberType": "Cathekomen", "years": [2024, 2025] })).unwrap();
+        let fidy = by_year.as_array().unwrap().iter().find(|m| m["member"]["id"] == id).unwrap();
+        assert_eq!(fidy["year_totals"], json!({ "2024": "1500" }));
+        assert_eq!(call("get_dashboard_stats", json!({})).unwrap()["cathekomens"], 2);
+        let health = call("get_db_health", json!({})).unwrap();
+        assert_eq!(health["tables"][2], json!({ "table": "members", "rows": 7 }));
+        assert_eq!(call("get_language", json!({})).unwrap(), json!("mg"));
+        assert!(matches!(call("backup_now", json!({})), Err(ApiError::Other(_))));
+        assert!(matches!(call("get_member", json!({})), Err(ApiError::Other(_))));
+    }
+
+    #[test]
+    fn test_modele_de_carte() {
+        assert_eq!(format_card("C-%04d", 7), "C-0007");
+        assert_eq!(format_card("%d/K", 12), "12/K");
+        assert_eq!(format_card("K", 3), "K3");
+        assert_eq!(date_year("2025-13-40"), Some(2025));
+        assert_eq!(date_year("2025-1-4"), None);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod config_service;
 pub mod db_service;
+pub mod mock_db;
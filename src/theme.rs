@@ -1,11 +1,14 @@
-/// Gestion du thème clair/sombre/système.
+/// Gestion du thème clair/sombre/système et de la réduction des animations.
 ///
-/// Contient l'enum `Theme`, les contextes Leptos `ThemeCtx` / `ToastCtx`
+/// Contient l'enum `Theme`, les contextes Leptos `ThemeCtx` / `MotionCtx` / `ToastCtx`
 /// et les helpers DOM (lecture/écriture localStorage, application au <html>).
 use leptos::prelude::*;
 use web_sys::window;
 
-use crate::{models::year_summary::YearSummary, utils::sleep_ms};
+use crate::{
+    models::year_summary::{YearClosePreview, YearCloseStatus},
+    utils::sleep_ms,
+};
 
 // ─── Enum Thème ──────────────────────────────────────────────────────────────
 
@@ -33,6 +36,15 @@ impl Theme {
         }
     }
 
+    /// Résout le thème effectif (System → préférence de l'OS).
+    pub fn is_dark(self) -> bool {
+        match self {
+            Theme::Dark   => true,
+            Theme::Light  => false,
+            Theme::System => system_prefers_dark(),
+        }
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             Theme::Light  => "Lumineux",
@@ -49,11 +61,30 @@ pub struct ThemeCtx {
     pub theme: RwSignal<Theme>,
 }
 
+/// Animations réduites : ciel statique, pas de confettis, compteurs sans défilement.
+/// Par défaut, suit `prefers-reduced-motion` tant que l'utilisateur n'a rien choisi ;
+/// lire l'état effectif avec `utils::use_reduced_motion`.
+#[derive(Clone, Copy)]
+pub struct MotionCtx {
+    /// Choix explicite (Paramètres, menu d'affichage) ; `None` = suit le système.
+    pub choice: RwSignal<Option<bool>>,
+    /// `prefers-reduced-motion`, mis à jour quand l'OS change.
+    pub system: RwSignal<bool>,
+}
+
+impl MotionCtx {
+    pub fn reduced(self) -> bool {
+        self.choice.get().unwrap_or_else(|| self.system.get())
+    }
+}
+
 /// Contexte pour le toast de clôture annuelle.
-/// `data` contient le résumé de l'année venant d'être clôturée, ou `None`.
+/// `data` : `Closed` (année venant d'être clôturée) ; `preview` : année précédente
+/// encore ouverte, en attente de « Hikatona » ; `None` quand rien n'est affiché.
 #[derive(Clone, Copy)]
 pub struct ToastCtx {
-    pub data: RwSignal<Option<YearSummary>>,
+    pub data:    RwSignal<Option<YearCloseStatus>>,
+    pub preview: RwSignal<Option<YearClosePreview>>,
 }
 
 // ─── Helpers DOM ─────────────────────────────────────────────────────────────
@@ -72,6 +103,40 @@ pub(crate) fn save_theme(theme: Theme) {
     }
 }
 
+const MOTION_KEY: &str = "fjkm_reduced_motion";
+
+/// Choix enregistré ("on" / "off") ; `None` si l'utilisateur n'a rien choisi.
+pub(crate) fn load_motion_choice() -> Option<bool> {
+    let saved = window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(MOTION_KEY).ok().flatten());
+    match saved.as_deref() {
+        Some("on")  => Some(true),
+        Some("off") => Some(false),
+        _           => None,
+    }
+}
+
+/// `None` efface le choix : le système fait de nouveau foi.
+pub(crate) fn save_motion_choice(choice: Option<bool>) {
+    if let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = match choice {
+            Some(reduced) => storage.set_item(MOTION_KEY, if reduced { "on" } else { "off" }),
+            None          => storage.remove_item(MOTION_KEY),
+        };
+    }
+}
+
+/// Classe `reduce-motion` sur <html> : coupe les animations CSS (style/main.css).
+pub fn apply_motion_to_dom(reduced: bool) {
+    if let Some(html) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.document_element())
+    {
+        let _ = html.class_list().toggle_with_force("reduce-motion", reduced);
+    }
+}
+
 fn system_prefers_dark() -> bool {
     window()
         .and_then(|w| w.match_media("(prefers-color-scheme: dark)").ok().flatten())
@@ -80,11 +145,7 @@ fn system_prefers_dark() -> bool {
 }
 
 pub fn apply_theme_to_dom(theme: Theme, with_transition: bool) {
-    let dark = match theme {
-        Theme::Dark   => true,
-        Theme::Light  => false,
-        Theme::System => system_prefers_dark(),
-    };
+    let dark = theme.is_dark();
     if let Some(html) = window()
         .and_then(|w| w.document())
         .and_then(|d| d.document_element())
@@ -0,0 +1,336 @@
+//! Saisie d'un montant dans un champ texte : frappe au clavier ou collage.
+//!
+//! Le même analyseur produit l'affichage ("15 000,50") et la valeur envoyée au
+//! backend ("15000.50") : les deux ne peuvent pas diverger.
+//!
+//! La recherche par montant (Archives) suit sa propre règle : "50.000" y désigne
+//! cinquante mille, comme le filtre SQL du backend (`validate::amount_search_digits`).
+use rust_decimal::Decimal;
+
+use super::money::{group_digits, NARROW_NBSP};
+
+/// Nombre maximal de décimales conservées.
+pub const MAX_DECIMALS: usize = 2;
+
+/// Résultat de l'analyse d'une saisie.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AmountInput {
+    /// Chiffres de la partie entière, sans zéros de tête superflus.
+    pub int_digits: String,
+    /// `Some` dès qu'un séparateur décimal a été tapé (éventuellement vide).
+    pub dec_digits: Option<String>,
+    /// Des caractères ont été ignorés (lettres, signe, décimales en trop…).
+    pub dropped:    bool,
+}
+
+fn is_separator(c: char) -> bool {
+    c == '.' || c == ','
+}
+
+/// Espaces de regroupement, retirés sans avertissement.
+fn is_group_space(c: char) -> bool {
+    c.is_whitespace() || c == NARROW_NBSP || c == '\u{a0}'
+}
+
+/// Analyse une saisie brute.
+///
+/// "." et "," sont tous deux acceptés comme séparateur décimal ; le dernier
+/// séparateur est décimal, les précédents sont des séparateurs de milliers.
+/// Exception : un dernier séparateur suivi d'exactement 3 chiffres est lui aussi
+/// un séparateur de milliers quand un autre séparateur le précède et que c'est
+/// un point, ou le même caractère ("15,000.50", "1.234.567", "1,234,567").
+pub fn parse_amount_input(raw: &str) -> AmountInput {
+    let mut dropped = false;
+    let kept: Vec<char> = raw
+        .chars()
+        .filter(|&c| {
+            if c.is_ascii_digit() || is_separator(c) {
+                true
+            } else {
+                if !is_group_space(c) {
+                    dropped = true;
+                }
+                false
+            }
+        })
+        .collect();
+
+    let seps: Vec<usize> = kept
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| is_separator(**c))
+        .map(|(i, _)| i)
+        .collect();
+
+    let decimal_at = seps.last().copied().filter(|&last| {
+        let digits_after = kept.len() - last - 1;
+        let sep = kept[last];
+        let grouping = digits_after == 3
+            && seps.len() > 1
+            && (sep == '.' || seps.iter().all(|&i| kept[i] == sep));
+        !grouping
+    });
+
+    let mut int_digits = String::new();
+    let mut dec_digits = decimal_at.map(|_| String::new());
+    for (i, &c) in kept.iter().enumerate() {
+        match decimal_at {
+            Some(d) if i > d => {
+                let dec = dec_digits.get_or_insert_with(String::new);
+                if dec.len() < MAX_DECIMALS {
+                    dec.push(c);
+                } else {
+                    dropped = true;
+                }
+            }
+            Some(d) if i == d => {}
+            _ if is_separator(c) => {
+                // Séparateur de milliers : doit précéder un groupe de 3 chiffres
+                let group = kept[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+                if group != 3 {
+                    dropped = true;
+                }
+            }
+            _ => int_digits.push(c),
+        }
+    }
+
+    let trimmed = int_digits.trim_start_matches('0');
+    let int_digits = if trimmed.is_empty() && !int_digits.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    };
+
+    AmountInput { int_digits, dec_digits, dropped }
+}
+
+impl AmountInput {
+    /// Aucun chiffre saisi.
+    pub fn is_empty(&self) -> bool {
+        self.int_digits.is_empty() && self.dec_digits.as_deref().unwrap_or("").is_empty()
+    }
+
+    /// Affichage dans le champ : "1 234,50" (espace fine), virgule conservée en cours de frappe.
+    pub fn display(&self) -> String {
+        let int_fmt = group_digits(&self.int_digits, NARROW_NBSP);
+        match &self.dec_digits {
+            Some(dec) if self.int_digits.is_empty() => format!("0,{dec}"),
+            Some(dec) => format!("{int_fmt},{dec}"),
+            None => int_fmt,
+        }
+    }
+
+    /// Valeur pour le backend : "1234.50" ; chaîne vide si aucun chiffre.
+    pub fn to_backend(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let int = if self.int_digits.is_empty() { "0" } else { &self.int_digits };
+        match self.dec_digits.as_deref() {
+            Some(dec) if !dec.is_empty() => format!("{int}.{dec}"),
+            _ => int.to_string(),
+        }
+    }
+}
+
+/// Valeur backend d'un champ déjà formaté par `AmountInput::display`.
+pub fn amount_to_backend(display: &str) -> String {
+    parse_amount_input(display).to_backend()
+}
+
+/// Séparateur d'une liste de montants saisie en un seul champ ("5 000 ; 10 000").
+pub const AMOUNT_LIST_SEP: char = ';';
+
+/// Valeurs backend d'une liste de montants ; les éléments vides sont ignorés.
+pub fn parse_amount_list(raw: &str) -> Vec<String> {
+    raw.split(AMOUNT_LIST_SEP)
+        .map(amount_to_backend)
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// Affichage d'une liste de montants backend : "5 000 ; 10 000".
+pub fn format_amount_list(amounts: &[String]) -> String {
+    amounts
+        .iter()
+        .map(|a| parse_amount_input(a).display())
+        .collect::<Vec<_>>()
+        .join(&format!(" {AMOUNT_LIST_SEP} "))
+}
+
+// ─── Recherche par montant ────────────────────────────────────────────────────
+
+/// Chiffres de la partie entière d'un montant recherché : "50 000", "50.000",
+/// "50,000" et "50000" donnent "50000" ; les décimales ("5000,50") et un suffixe
+/// "Ar" sont ignorés. `None` si la saisie n'est pas un montant.
+pub fn amount_search_digits(query: &str) -> Option<String> {
+    let compact: String = query.chars().filter(|&c| !is_group_space(c)).collect();
+    let compact = compact
+        .strip_suffix("Ar")
+        .or_else(|| compact.strip_suffix("ar"))
+        .unwrap_or(&compact);
+    if compact.is_empty() || !compact.chars().all(|c| c.is_ascii_digit() || is_separator(c)) {
+        return None;
+    }
+    // Dernier séparateur suivi d'un ou deux chiffres : décimales
+    let int_part = match compact.rfind(is_separator) {
+        Some(i) if (1..=2).contains(&(compact.len() - i - 1)) => &compact[..i],
+        _ => compact,
+    };
+    let digits: String = int_part.chars().filter(char::is_ascii_digit).collect();
+    (!digits.is_empty()).then_some(digits)
+}
+
+/// La partie entière de `amount` commence par les chiffres de `query` : "50 000"
+/// retrouve 50 000 et 500 000, pas 5 000.
+pub fn amount_matches(query: &str, amount: &Decimal) -> bool {
+    amount_search_digits(query)
+        .is_some_and(|digits| amount.trunc().abs().to_string().starts_with(&digits))
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn show(raw: &str) -> String {
+        parse_amount_input(raw).display().replace(NARROW_NBSP, " ")
+    }
+
+    fn backend(raw: &str) -> String {
+        parse_amount_input(raw).to_backend()
+    }
+
+    #[test]
+    fn test_vide_et_separateur_seul() {
+        assert_eq!(parse_amount_input(""), AmountInput::default());
+        assert!(parse_amount_input("").is_empty());
+        assert_eq!(backend(""), "");
+        assert_eq!(show(","), "0,");
+        assert_eq!(show("."), "0,");
+        assert_eq!(backend(","), "");
+        assert!(!parse_amount_input(",").dropped);
+    }
+
+    #[test]
+    fn test_entiers() {
+        assert_eq!(show("15000"), "15 000");
+        assert_eq!(backend("15000"), "15000");
+        assert_eq!(show("0"), "0");
+        assert_eq!(show("000120"), "120");
+        assert_eq!(backend("00"), "0");
+    }
+
+    #[test]
+    fn test_collage_point_decimal() {
+        assert_eq!(backend("15000.5"), "15000.5");
+        assert_eq!(show("15000.5"), "15 000,5");
+        assert_eq!(backend("15000.50"), "15000.50");
+    }
+
+    #[test]
+    fn test_collage_avec_milliers() {
+        // Format anglo-saxon
+        assert_eq!(backend("15,000.50"), "15000.50");
+        assert_eq!(backend("1,234,567.89"), "1234567.89");
+        // Format français / malgache
+        assert_eq!(backend("15.000,50"), "15000.50");
+        assert_eq!(backend("15 000,50"), "15000.50");
+        assert_eq!(backend("1\u{202f}234\u{202f}567,8"), "1234567.8");
+        // Séparateurs de milliers seuls
+        assert_eq!(backend("1.234.567"), "1234567");
+        assert_eq!(backend("1,234,567"), "1234567");
+        assert_eq!(backend("15.000,5"), "15000.5");
+        for raw in ["15,000.50", "15.000,50", "1.234.567"] {
+            assert!(!parse_amount_input(raw).dropped, "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_separateur_unique_decimal() {
+        // Un seul séparateur : toujours décimal, quitte à tronquer
+        assert_eq!(backend("15,5"), "15.5");
+        let p = parse_amount_input("15.000");
+        assert_eq!(p.to_backend(), "15.00");
+        assert!(p.dropped);
+    }
+
+    #[test]
+    fn test_decimales_plafonnees() {
+        let p = parse_amount_input("12,3456");
+        assert_eq!(p.to_backend(), "12.34");
+        assert!(p.dropped);
+    }
+
+    #[test]
+    fn test_caracteres_ignores_signales() {
+        let p = parse_amount_input("15 000 Ar");
+        assert_eq!(p.to_backend(), "15000");
+        assert!(p.dropped);
+        let p = parse_amount_input("-500");
+        assert_eq!(p.to_backend(), "500");
+        assert!(p.dropped);
+        // Groupe de milliers incomplet
+        assert!(parse_amount_input("1.23,5").dropped);
+        // Espaces : silencieux
+        assert!(!parse_amount_input(" 1 500 ").dropped);
+    }
+
+    #[test]
+    fn test_sequence_de_frappe() {
+        // Chaque frappe est ajoutée à l'affichage précédent, comme dans le champ
+        let mut field = String::new();
+        for key in ["1", "2", "3", "4", "5", ".", "7", "5"] {
+            field.push_str(key);
+            let p = parse_amount_input(&field);
+            assert!(!p.dropped, "{field}");
+            field = p.display();
+        }
+        assert_eq!(field.replace(NARROW_NBSP, " "), "12 345,75");
+        assert_eq!(amount_to_backend(&field), "12345.75");
+
+        // Troisième décimale refusée
+        field.push('9');
+        let p = parse_amount_input(&field);
+        assert!(p.dropped);
+        assert_eq!(p.display(), field[..field.len() - 1]);
+    }
+
+    #[test]
+    fn test_affichage_aller_retour() {
+        for raw in ["0", "7", "1234", "1234,5", "98765432,10", "0,05", "15,000.50"] {
+            let once = parse_amount_input(raw);
+            let twice = parse_amount_input(&once.display());
+            assert_eq!(once.to_backend(), twice.to_backend(), "{raw}");
+            assert!(!twice.dropped, "{raw}");
+        }
+    }
+
+    #[test]
+    fn test_liste_de_montants() {
+        assert_eq!(parse_amount_list("5 000 ; 10000;; 7500,5 "), vec!["5000", "10000", "7500.5"]);
+        assert!(parse_amount_list(" ; ").is_empty());
+        let list = vec!["5000".to_string(), "7500.5".to_string()];
+        let shown = format_amount_list(&list);
+        assert_eq!(shown, "5\u{202f}000 ; 7\u{202f}500,5");
+        assert_eq!(parse_amount_list(&shown), list);
+    }
+
+    #[test]
+    fn test_recherche_montant() {
+        let d = |s: &str| Decimal::from_str_exact(s).unwrap();
+        for q in ["50000", "50 000", "50.000", "50,000", "50 000 Ar"] {
+            assert_eq!(amount_search_digits(q).as_deref(), Some("50000"), "recherche {q:?}");
+            assert!(amount_matches(q, &d("50000")), "recherche {q:?}");
+            assert!(amount_matches(q, &d("500000.50")), "recherche {q:?}");
+            assert!(!amount_matches(q, &d("5000")), "recherche {q:?}");
+            assert!(!amount_matches(q, &d("150000")), "recherche {q:?}");
+        }
+        assert_eq!(amount_search_digits("5000,50").as_deref(), Some("5000"));
+        for q in ["", "Rakoto", "2024-T1", "-500"] {
+            assert_eq!(amount_search_digits(q), None, "recherche {q:?}");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Anti-rebond des saisies et protection contre les réponses async périmées.
+use leptos::prelude::*;
+
+use super::sleep_ms;
+
+/// Délai par défaut des champs de recherche.
+pub const SEARCH_DEBOUNCE_MS: u32 = 250;
+
+/// Compteur de génération — même principe que `ANIM_GEN` dans `sky_canvas.rs` :
+/// chaque requête prend un numéro, seule la plus récente a le droit d'écrire.
+#[derive(Clone, Copy)]
+pub struct RequestGen(StoredValue<u32>);
+
+impl RequestGen {
+    pub fn new() -> Self {
+        Self(StoredValue::new(0))
+    }
+
+    /// Démarre une nouvelle requête et invalide les précédentes.
+    pub fn next(&self) -> u32 {
+        let v = self.0.get_value().wrapping_add(1);
+        self.0.set_value(v);
+        v
+    }
+
+    /// `false` si une requête plus récente a été lancée depuis `gen`.
+    pub fn is_current(&self, gen: u32) -> bool {
+        self.0.get_value() == gen
+    }
+}
+
+impl Default for RequestGen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sort d'une nouvelle valeur de la source dans `use_debounced_signal`.
+#[derive(Debug, PartialEq)]
+enum DebounceStep {
+    /// Déjà la valeur publiée
+    Unchanged,
+    /// Retour à la valeur par défaut (champ vidé) : publiée sans attendre
+    Immediate,
+    /// Publiée après le délai, si aucune autre valeur n'arrive entre-temps
+    Delayed,
+}
+
+fn debounce_step<T: Default + PartialEq>(value: &T, published: &T) -> DebounceStep {
+    if value == published {
+        DebounceStep::Unchanged
+    } else if *value == T::default() {
+        DebounceStep::Immediate
+    } else {
+        DebounceStep::Delayed
+    }
+}
+
+/// Copie de `source` mise à jour après `delay_ms` sans nouvelle frappe.
+///
+/// Revenir à la valeur par défaut (champ vidé) s'applique immédiatement.
+pub fn use_debounced_signal<T>(source: RwSignal<T>, delay_ms: u32) -> ReadSignal<T>
+where
+    T: Clone + Default + PartialEq + Send + Sync + 'static,
+{
+    let debounced = RwSignal::new(source.get_untracked());
+    let gen = RequestGen::new();
+
+    Effect::new(move |_| {
+        let value = source.get();
+        let g = gen.next();
+        match debounce_step(&value, &debounced.get_untracked()) {
+            DebounceStep::Unchanged => {}
+            DebounceStep::Immediate => debounced.set(value),
+            DebounceStep::Delayed => leptos::task::spawn_local(async move {
+                sleep_ms(delay_ms).await;
+                if gen.is_current(g) {
+                    debounced.set(value);
+                }
+            }),
+        }
+    });
+
+    debounced.read_only()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debounce_step() {
+        let s = |v: &str, p: &str| debounce_step(&v.to_string(), &p.to_string());
+        assert_eq!(s("ra", "ra"), DebounceStep::Unchanged);
+        assert_eq!(s("rak", "ra"), DebounceStep::Delayed);
+        assert_eq!(s("", "rakoto"), DebounceStep::Immediate);
+        assert_eq!(s("", ""), DebounceStep::Unchanged);
+    }
+
+    #[test]
+    fn test_request_gen_seule_la_derniere_compte() {
+        let gen = RequestGen::new();
+        let first = gen.next();
+        assert!(gen.is_current(first));
+        let second = gen.next();
+        assert!(!gen.is_current(first));
+        assert!(gen.is_current(second));
+    }
+}
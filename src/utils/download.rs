@@ -0,0 +1,53 @@
+//! Fichiers générés côté backend (CSV, HTML, PDF) proposés à l'enregistrement par le webview.
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// URL `blob:` d'un contenu texte ; à libérer avec `Url::revoke_object_url`.
+pub fn text_blob_url(content: &str, mime: &str) -> Result<String, String> {
+    let parts = Array::new();
+    parts.push(&JsValue::from_str(content));
+    let opts = BlobPropertyBag::new();
+    opts.set_type(mime);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &opts)
+        .map_err(|e| format!("Erreur Blob : {e:?}"))?;
+    Url::create_object_url_with_blob(&blob).map_err(|e| format!("Erreur URL : {e:?}"))
+}
+
+/// Déclenche l'enregistrement de `url` sous `filename` via un lien `download`.
+pub fn click_download(url: &str, filename: &str) -> Result<(), String> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("Pas de document")?;
+    let a = document
+        .create_element("a")
+        .map_err(|e| format!("{e:?}"))?
+        .dyn_into::<HtmlAnchorElement>()
+        .map_err(|e| format!("{e:?}"))?;
+    a.set_href(url);
+    a.set_download(filename);
+    a.click();
+    Ok(())
+}
+
+/// Enregistre un contenu texte (CSV, HTML…) sous `filename`.
+pub fn download_text(content: &str, mime: &str, filename: &str) -> Result<(), String> {
+    let url = text_blob_url(content, mime)?;
+    let res = click_download(&url, filename);
+    let _ = Url::revoke_object_url(&url);
+    res
+}
+
+/// Enregistre un contenu binaire (PDF…) sous `filename`.
+pub fn download_bytes(bytes: &[u8], mime: &str, filename: &str) -> Result<(), String> {
+    let parts = Array::new();
+    parts.push(&Uint8Array::from(bytes));
+    let opts = BlobPropertyBag::new();
+    opts.set_type(mime);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &opts)
+        .map_err(|e| format!("Erreur Blob : {e:?}"))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(|e| format!("Erreur URL : {e:?}"))?;
+    let res = click_download(&url, filename);
+    let _ = Url::revoke_object_url(&url);
+    res
+}
@@ -0,0 +1,190 @@
+//! Brouillons de formulaires gardés dans localStorage : une saisie perdue (clic
+//! hors du modal, onglet fermé) peut être reprise à la prochaine ouverture.
+//!
+//! La sérialisation et l'accès au stockage sont séparés du hook Leptos pour être
+//! testés sans navigateur (`DraftStore`).
+use leptos::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{sleep_ms, RequestGen};
+
+/// Délai sans nouvelle frappe avant d'écrire le brouillon.
+pub const DRAFT_SAVE_DELAY_MS: u32 = 400;
+
+/// Préfixe des clés localStorage, à côté de `fjkm_theme`.
+const DRAFT_PREFIX: &str = "fjkm_draft_";
+
+/// Stockage clé → texte : localStorage dans le navigateur, une table en mémoire
+/// dans les tests.
+pub trait DraftStore {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&self, key: &str, value: &str);
+    fn remove(&self, key: &str);
+}
+
+impl DraftStore for web_sys::Storage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.get_item(key).ok().flatten()
+    }
+    fn set(&self, key: &str, value: &str) {
+        let _ = self.set_item(key, value);
+    }
+    fn remove(&self, key: &str) {
+        let _ = self.remove_item(key);
+    }
+}
+
+fn storage_key(key: &str) -> String {
+    format!("{DRAFT_PREFIX}{key}")
+}
+
+pub fn save_draft<T: Serialize>(store: &impl DraftStore, key: &str, value: &T) {
+    if let Ok(json) = serde_json::to_string(value) {
+        store.set(&storage_key(key), &json);
+    }
+}
+
+/// Brouillon enregistré sous `key` ; un brouillon illisible (format changé depuis)
+/// est effacé.
+pub fn load_draft<T: DeserializeOwned>(store: &impl DraftStore, key: &str) -> Option<T> {
+    let raw = store.get(&storage_key(key))?;
+    let value = serde_json::from_str(&raw).ok();
+    if value.is_none() {
+        store.remove(&storage_key(key));
+    }
+    value
+}
+
+pub fn clear_draft(store: &impl DraftStore, key: &str) {
+    store.remove(&storage_key(key));
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+// ─── Hook ─────────────────────────────────────────────────────────────────────
+
+/// Brouillon d'un formulaire ouvert (`use_form_draft`).
+pub struct FormDraft<T: Send + Sync + 'static> {
+    /// Brouillon trouvé à l'ouverture, proposé jusqu'à `take_offer` ou `discard`
+    pub offered: RwSignal<Option<T>>,
+    key:         StoredValue<String>,
+    gen:         RequestGen,
+}
+
+impl<T: Send + Sync + 'static> Clone for FormDraft<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + 'static> Copy for FormDraft<T> {}
+
+impl<T: Clone + Send + Sync + 'static> FormDraft<T> {
+    /// Reprend le brouillon proposé ; l'offre disparaît.
+    pub fn take_offer(self) -> Option<T> {
+        let draft = self.offered.get_untracked();
+        self.offered.set(None);
+        draft
+    }
+
+    /// Refuse le brouillon proposé, qui est effacé.
+    pub fn discard(self) {
+        self.offered.set(None);
+        self.clear();
+    }
+
+    /// Efface le brouillon (formulaire enregistré) et annule une écriture en attente.
+    pub fn clear(self) {
+        self.gen.next();
+        if let Some(store) = local_storage() {
+            self.key.with_value(|key| clear_draft(&store, key));
+        }
+    }
+}
+
+/// Garde `snapshot()` dans localStorage sous `key`, `DRAFT_SAVE_DELAY_MS` après la
+/// dernière modification des signaux qu'il lit. `None` : rien à garder (formulaire
+/// intact), le brouillon précédent reste en place.
+pub fn use_form_draft<T>(
+    key: String,
+    snapshot: impl Fn() -> Option<T> + Send + Sync + 'static,
+) -> FormDraft<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let offered = RwSignal::new(local_storage().and_then(|store| load_draft(&store, &key)));
+    let draft = FormDraft { offered, key: StoredValue::new(key), gen: RequestGen::new() };
+
+    Effect::new(move |_| {
+        let Some(value) = snapshot() else { return };
+        let g = draft.gen.next();
+        leptos::task::spawn_local(async move {
+            sleep_ms(DRAFT_SAVE_DELAY_MS).await;
+            if let (true, Some(store)) = (draft.gen.is_current(g), local_storage()) {
+                draft.key.with_value(|key| save_draft(&store, key, &value));
+            }
+        });
+    });
+
+    draft
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryStore(RefCell<HashMap<String, String>>);
+
+    impl DraftStore for MemoryStore {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+        fn set(&self, key: &str, value: &str) {
+            self.0.borrow_mut().insert(key.into(), value.into());
+        }
+        fn remove(&self, key: &str) {
+            self.0.borrow_mut().remove(key);
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Draft {
+        name:    String,
+        address: Option<String>,
+    }
+
+    #[test]
+    fn test_brouillon_enregistre_repris_efface() {
+        let store = MemoryStore::default();
+        let draft = Draft { name: "RAKOTO Jean".into(), address: Some("Tanambao".into()) };
+        assert_eq!(load_draft::<Draft>(&store, "member_Communiant_new"), None);
+
+        save_draft(&store, "member_Communiant_new", &draft);
+        assert!(store.get("fjkm_draft_member_Communiant_new").is_some());
+        assert_eq!(load_draft(&store, "member_Communiant_new"), Some(draft.clone()));
+        // Une autre fiche n'a pas de brouillon
+        assert_eq!(load_draft::<Draft>(&store, "member_Communiant_12"), None);
+
+        let edited = Draft { address: None, ..draft };
+        save_draft(&store, "member_Communiant_new", &edited);
+        assert_eq!(load_draft(&store, "member_Communiant_new"), Some(edited));
+
+        clear_draft(&store, "member_Communiant_new");
+        assert_eq!(load_draft::<Draft>(&store, "member_Communiant_new"), None);
+    }
+
+    #[test]
+    fn test_brouillon_illisible_efface() {
+        let store = MemoryStore::default();
+        store.set("fjkm_draft_member_Cathekomen_new", "{\"nom\":");
+        assert_eq!(load_draft::<Draft>(&store, "member_Cathekomen_new"), None);
+        assert!(store.0.borrow().is_empty());
+    }
+}
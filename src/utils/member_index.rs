@@ -0,0 +1,267 @@
+//! Index de recherche des listes de membres filtrées en mémoire (mode client).
+//!
+//! Construit une fois par chargement de la liste, et non à chaque frappe : les
+//! champs cherchables sont mis en minuscules d'avance et le total de l'année
+//! converti pour le tri. Le filtrage ne renvoie que des positions dans l'index ;
+//! seules les lignes de la page affichée sont ensuite clonées.
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use crate::{
+    components::member_table::{SortCol, SortDir},
+    models::member::{Gender, MemberStatus, MemberWithTotal},
+    utils::money::parse_decimal,
+};
+
+/// Séparateur des champs concaténés : une recherche ne peut pas chevaucher deux champs.
+const FIELD_SEP: char = '\u{1f}';
+
+fn text(opt: &Option<String>) -> &str {
+    opt.as_deref().unwrap_or("")
+}
+
+/// Clé de regroupement d'une adresse, comme `validate::address_key` côté backend :
+/// minuscules, espaces en trop retirés ; `None` pour une adresse absente ou blanche.
+pub fn address_key(address: Option<&str>) -> Option<String> {
+    let key = address?.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    (!key.is_empty()).then_some(key)
+}
+
+#[derive(Debug, Default)]
+pub struct MemberIndex {
+    members:     Vec<MemberWithTotal>,
+    /// Nom, carte, adresse, téléphone et travail en minuscules, séparés par `FIELD_SEP`
+    haystacks:   Vec<String>,
+    /// `current_year_total` déjà converti
+    totals:      Vec<Decimal>,
+    /// `previous_year_total` (vue « par année ») ; 0 sans donnée
+    prev_totals: Vec<Decimal>,
+    /// Clé du tri par nom (`MemberWithTotal::sort_name`)
+    names:       Vec<String>,
+    /// Quartier de la vue « par adresse » (`address_key`)
+    addresses:   Vec<Option<String>>,
+}
+
+impl MemberIndex {
+    pub fn build(members: &[MemberWithTotal]) -> Self {
+        let haystacks = members
+            .iter()
+            .map(|m| {
+                [
+                    m.full_name.as_str(),
+                    &m.card_number,
+                    text(&m.address),
+                    text(&m.phone),
+                    text(&m.job),
+                ]
+                .join(&FIELD_SEP.to_string())
+                .to_lowercase()
+            })
+            .collect();
+        let totals = members.iter().map(|m| parse_decimal(&m.current_year_total)).collect();
+        let prev_totals = members
+            .iter()
+            .map(|m| m.previous_year_total.as_deref().map_or(Decimal::ZERO, parse_decimal))
+            .collect();
+        let names = members.iter().map(MemberWithTotal::sort_name).collect();
+        let addresses = members.iter().map(|m| address_key(m.address.as_deref())).collect();
+        Self { members: members.to_vec(), haystacks, totals, prev_totals, names, addresses }
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&MemberWithTotal> {
+        self.members.get(pos)
+    }
+
+    /// Positions des membres retenus par `query` (sur tous les champs cherchables)
+    /// `genre` et `statut`, dans l'ordre de tri demandé.
+    pub fn filter_sorted(
+        &self,
+        query: &str,
+        genre: Option<Gender>,
+        statut: Option<MemberStatus>,
+        col: SortCol,
+        dir: SortDir,
+    ) -> Vec<usize> {
+        let q = query.to_lowercase();
+        let mut positions: Vec<usize> = (0..self.members.len())
+            .filter(|&i| {
+                genre.is_none_or(|g| self.members[i].gender == g)
+                    && statut.is_none_or(|s| self.members[i].status == s)
+                    && (q.is_empty() || self.haystacks[i].contains(&q))
+            })
+            .collect();
+
+        positions.sort_by(|&a, &b| {
+            let (ma, mb) = (&self.members[a], &self.members[b]);
+            let ord = match col {
+                SortCol::Carte     => ma.card_number.cmp(&mb.card_number),
+                SortCol::Nom       => self.names[a].cmp(&self.names[b]),
+                SortCol::Adresse   => text(&ma.address).cmp(text(&mb.address)),
+                SortCol::Telephone => text(&ma.phone).cmp(text(&mb.phone)),
+                SortCol::Travail   => text(&ma.job).cmp(text(&mb.job)),
+                SortCol::Genre     => ma.gender.cmp(&mb.gender),
+                SortCol::AnneeCourante => self.totals[a].cmp(&self.totals[b]),
+                SortCol::AnneePrecedente => self.prev_totals[a].cmp(&self.prev_totals[b]),
+            };
+            if dir == SortDir::Desc { ord.reverse() } else { ord }
+        });
+        positions
+    }
+
+    /// `positions` (déjà filtrées et triées) regroupées par quartier : adresses par
+    /// ordre alphabétique, « sans adresse » en dernier, ordre gardé dans chaque groupe.
+    pub fn group_by_address(&self, positions: &[usize]) -> Vec<(Option<String>, Vec<usize>)> {
+        let mut groups: BTreeMap<Option<String>, Vec<usize>> = BTreeMap::new();
+        for &pos in positions {
+            groups.entry(self.addresses[pos].clone()).or_default().push(pos);
+        }
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        // `None` se range en tête d'une BTreeMap
+        if groups.first().is_some_and(|(key, _)| key.is_none()) {
+            groups.rotate_left(1);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::member::MemberType;
+
+    fn member(id: i64, name: &str, card: &str, job: Option<&str>, gender: Gender, total: &str) -> MemberWithTotal {
+        MemberWithTotal {
+            id,
+            card_number:         card.into(),
+            full_name:           name.into(),
+            address:             Some("Ambalavao".into()),
+            phone:               None,
+            job:                 job.map(Into::into),
+            gender,
+            member_type:         MemberType::Communiant,
+            surname:             None,
+            given_name:          None,
+            status:              MemberStatus::Actif,
+            birth_date:          None,
+            created_at:          String::new(),
+            total_contributions: total.into(),
+            current_year_total:  total.into(),
+            previous_year_total: None,
+        }
+    }
+
+    fn index() -> MemberIndex {
+        MemberIndex::build(&[
+            member(1, "RAKOTO Jean", "C-0002", Some("Mpampianatra"), Gender::M, "5000"),
+            member(2, "Rasoa Marie", "C-0001", None, Gender::F, "0"),
+            MemberWithTotal {
+                status: MemberStatus::Inactif,
+                ..member(3, "Randria Paul", "C-0003", Some("Mpamboly"), Gender::M, "12000")
+            },
+        ])
+    }
+
+    fn ids(ix: &MemberIndex, positions: &[usize]) -> Vec<i64> {
+        positions.iter().map(|&p| ix.get(p).unwrap().id).collect()
+    }
+
+    #[test]
+    fn test_index_champs_en_minuscules() {
+        let ix = index();
+        assert_eq!(ix.haystacks[0], "rakoto jean\u{1f}c-0002\u{1f}ambalavao\u{1f}\u{1f}mpampianatra");
+        assert_eq!(ix.totals[2], Decimal::from(12000));
+        assert!(MemberIndex::build(&[]).get(0).is_none());
+    }
+
+    #[test]
+    fn test_filtre_recherche_et_genre() {
+        let ix = index();
+        let f = |q: &str, g| ids(&ix, &ix.filter_sorted(q, g, None, SortCol::Nom, SortDir::Asc));
+        assert_eq!(f("", None), vec![1, 3, 2]);
+        assert_eq!(f("RAKOTO", None), vec![1]);
+        assert_eq!(f("c-000", None), vec![1, 3, 2]);
+        assert_eq!(f("mpam", None), vec![1, 3]);
+        assert_eq!(f("ambalavao", Some(Gender::F)), vec![2]);
+        // Pas de correspondance à cheval sur deux champs (nom + carte)
+        assert!(f("jean c", None).is_empty());
+    }
+
+    #[test]
+    fn test_filtre_statut() {
+        let ix = index();
+        let f = |s| ids(&ix, &ix.filter_sorted("", None, s, SortCol::Nom, SortDir::Asc));
+        assert_eq!(f(Some(MemberStatus::Actif)), vec![1, 2]);
+        assert_eq!(f(Some(MemberStatus::Inactif)), vec![3]);
+        assert!(f(Some(MemberStatus::Decede)).is_empty());
+    }
+
+    #[test]
+    fn test_tri() {
+        let ix = index();
+        let f = |col, dir| ids(&ix, &ix.filter_sorted("", None, None, col, dir));
+        assert_eq!(f(SortCol::Carte, SortDir::Asc), vec![2, 1, 3]);
+        assert_eq!(f(SortCol::AnneeCourante, SortDir::Desc), vec![3, 1, 2]);
+        assert_eq!(f(SortCol::Travail, SortDir::Asc), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_tri_annee_precedente() {
+        // Tri numérique ("900" avant "12000.5"), sans donnée comptée comme 0
+        let prev = |id, total: Option<&str>| MemberWithTotal {
+            previous_year_total: total.map(Into::into),
+            ..member(id, "X", "C-0000", None, Gender::M, "0")
+        };
+        let ix = MemberIndex::build(&[prev(1, Some("12000.5")), prev(2, None), prev(3, Some("900"))]);
+        let f = |dir| ids(&ix, &ix.filter_sorted("", None, None, SortCol::AnneePrecedente, dir));
+        assert_eq!(f(SortDir::Asc), vec![2, 3, 1]);
+        assert_eq!(f(SortDir::Desc), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_tri_par_nom_de_famille() {
+        // "Andry RATSIMBA" se range à RATSIMBA ; sans nom de famille, le nom tel que saisi
+        let named = |id, name: &str, surname: &str, given: &str| MemberWithTotal {
+            surname:    Some(surname.into()),
+            given_name: Some(given.into()),
+            ..member(id, name, "C-0000", None, Gender::M, "0")
+        };
+        let ix = MemberIndex::build(&[
+            named(4, "Andry RATSIMBA", "RATSIMBA", "Andry"),
+            member(5, "Bako Randria", "C-0005", None, Gender::F, "0"),
+            named(6, "RAKOTO Jean", "RAKOTO", "Jean"),
+        ]);
+        let f = |dir| ids(&ix, &ix.filter_sorted("", None, None, SortCol::Nom, dir));
+        assert_eq!(f(SortDir::Asc), vec![5, 6, 4]);
+        assert_eq!(f(SortDir::Desc), vec![4, 6, 5]);
+    }
+
+    #[test]
+    fn test_groupes_par_adresse() {
+        let at = |id, name: &str, address: Option<&str>| MemberWithTotal {
+            address: address.map(Into::into),
+            ..member(id, name, "C-0000", None, Gender::M, "0")
+        };
+        let ix = MemberIndex::build(&[
+            at(1, "Koto",  Some("Tanambao II")),
+            at(2, "Bako",  None),
+            at(3, "Alice", Some(" tanambao  ii")),
+            at(4, "Hery",  Some("   ")),
+            at(5, "Lova",  Some("Ambohimena")),
+        ]);
+        let positions = ix.filter_sorted("", None, None, SortCol::Nom, SortDir::Asc);
+        let groups: Vec<(Option<String>, Vec<i64>)> = ix
+            .group_by_address(&positions)
+            .into_iter()
+            .map(|(key, group)| (key, ids(&ix, &group)))
+            .collect();
+        assert_eq!(groups, [
+            (Some("ambohimena".into()),  vec![5]),
+            (Some("tanambao ii".into()), vec![3, 1]),
+            (None,                       vec![2, 4]),
+        ]);
+        // Filtres appliqués avant : un quartier sans membre retenu disparaît
+        let positions = ix.filter_sorted("koto", None, None, SortCol::Nom, SortDir::Asc);
+        assert_eq!(ix.group_by_address(&positions), [(Some("tanambao ii".into()), vec![0])]);
+    }
+}
@@ -0,0 +1,30 @@
+//! Utilitaires partagés entre les composants frontend (WASM).
+
+pub mod amount;
+pub mod debounce;
+pub mod download;
+pub mod form_draft;
+pub mod member_index;
+pub mod money;
+pub mod motion;
+pub mod optimistic;
+pub mod scanner;
+
+pub use debounce::{use_debounced_signal, RequestGen, SEARCH_DEBOUNCE_MS};
+pub use form_draft::use_form_draft;
+pub use money::{format_ariary, format_ariary_str, MoneyFormat};
+pub use motion::use_reduced_motion;
+
+use js_sys::{Function, Promise};
+use wasm_bindgen_futures::JsFuture;
+
+/// Attendre `ms` millisecondes (non-bloquant, WASM-compatible).
+pub async fn sleep_ms(ms: u32) {
+    let promise = Promise::new(&mut |resolve: Function, _: Function| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32)
+            .unwrap();
+    });
+    let _ = JsFuture::from(promise).await;
+}
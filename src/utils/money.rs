@@ -0,0 +1,191 @@
+//! Formatage des montants en Ariary — point d'entrée unique pour tout le frontend.
+//!
+//! Le backend sérialise les `Decimal` en chaîne ("15000.50") ; on les reparse ici
+//! avec `rust_decimal` pour éviter les arrondis de `f64` sur les gros totaux.
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::str::FromStr;
+
+/// Espace fine insécable (U+202F) — séparateur par défaut des milliers.
+pub const NARROW_NBSP: char = '\u{202f}';
+
+/// Options de formatage d'un montant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoneyFormat {
+    /// Séparateur des milliers (U+202F par défaut).
+    pub group_sep: char,
+    /// Nombre de décimales affichées (0 = entier arrondi).
+    pub decimals:  u32,
+    /// Mode compact pour les cartes du tableau de bord : "1,2 M Ar".
+    pub compact:   bool,
+}
+
+impl Default for MoneyFormat {
+    fn default() -> Self {
+        MoneyFormat { group_sep: NARROW_NBSP, decimals: 0, compact: false }
+    }
+}
+
+impl MoneyFormat {
+    /// Format compact ("1,2 M Ar", "3,5 Md Ar") ; les montants < 1 million restent complets.
+    pub fn compact() -> Self {
+        MoneyFormat { compact: true, ..Self::default() }
+    }
+
+    /// Format avec décimales ("15 000,50 Ar").
+    pub fn with_decimals(decimals: u32) -> Self {
+        MoneyFormat { decimals, ..Self::default() }
+    }
+}
+
+/// Regroupe les chiffres d'une partie entière par milliers : "1234567" → "1 234 567".
+pub fn group_digits(digits: &str, sep: char) -> String {
+    let len = digits.chars().count();
+    let mut result = String::with_capacity(len + len / 3 * sep.len_utf8());
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (len - i).is_multiple_of(3) {
+            result.push(sep);
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Parse un montant envoyé par le backend ; chaîne invalide → 0.
+pub fn parse_decimal(amount_str: &str) -> Decimal {
+    Decimal::from_str(amount_str.trim()).unwrap_or(Decimal::ZERO)
+}
+
+/// Formate un nombre (sans unité) avec séparateur de milliers et virgule décimale.
+fn format_number(value: Decimal, decimals: u32, sep: char) -> String {
+    let rounded = value.round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero);
+    let negative = rounded.is_sign_negative() && !rounded.is_zero();
+    let abs = rounded.abs().to_string();
+    let (int_part, frac_part) = match abs.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (abs.as_str(), ""),
+    };
+
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+    out.push_str(&group_digits(int_part, sep));
+    if decimals > 0 {
+        let mut frac = frac_part.to_string();
+        while frac.len() < decimals as usize {
+            frac.push('0');
+        }
+        out.push(',');
+        out.push_str(&frac);
+    }
+    out
+}
+
+/// Formate un montant en Ariary selon `opts`.
+///
+/// - défaut : "1 234 567 Ar" (arrondi à l'entier, U+202F entre les groupes)
+/// - `decimals = 2` : "15 000,50 Ar"
+/// - `compact` : "1,2 M Ar" (≥ 1 million), "3,5 Md Ar" (≥ 1 milliard)
+pub fn format_ariary(value: &Decimal, opts: MoneyFormat) -> String {
+    if opts.compact {
+        let abs = value.abs();
+        let (unit, divisor) = if abs >= Decimal::from(1_000_000_000i64) {
+            ("Md", Decimal::from(1_000_000_000i64))
+        } else if abs >= Decimal::from(1_000_000i64) {
+            ("M", Decimal::from(1_000_000i64))
+        } else {
+            ("", Decimal::ONE)
+        };
+        if !unit.is_empty() {
+            let scaled = (value / divisor)
+                .round_dp_with_strategy(1, RoundingStrategy::MidpointAwayFromZero)
+                .normalize();
+            let decimals = if scaled.fract().is_zero() { 0 } else { 1 };
+            return format!(
+                "{}{sep}{unit}{sep}Ar",
+                format_number(scaled, decimals, opts.group_sep),
+                sep = opts.group_sep,
+            );
+        }
+    }
+    format!("{}{}Ar", format_number(*value, opts.decimals, opts.group_sep), opts.group_sep)
+}
+
+/// Raccourci pour les montants reçus du backend sous forme de chaîne.
+pub fn format_ariary_str(amount_str: &str, opts: MoneyFormat) -> String {
+    format_ariary(&parse_decimal(amount_str), opts)
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(format_ariary(&Decimal::ZERO, MoneyFormat::default()), "0\u{202f}Ar");
+        assert_eq!(format_ariary(&d("0.00"), MoneyFormat::with_decimals(2)), "0,00\u{202f}Ar");
+    }
+
+    #[test]
+    fn test_groupement_milliers() {
+        assert_eq!(format_ariary(&d("999"), MoneyFormat::default()), "999\u{202f}Ar");
+        assert_eq!(format_ariary(&d("1000"), MoneyFormat::default()), "1\u{202f}000\u{202f}Ar");
+        assert_eq!(
+            format_ariary(&d("1234567"), MoneyFormat::default()),
+            "1\u{202f}234\u{202f}567\u{202f}Ar"
+        );
+    }
+
+    #[test]
+    fn test_separateur_personnalise() {
+        let opts = MoneyFormat { group_sep: ' ', ..MoneyFormat::default() };
+        assert_eq!(format_ariary(&d("15000"), opts), "15 000 Ar");
+    }
+
+    #[test]
+    fn test_negatifs() {
+        assert_eq!(format_ariary(&d("-1500"), MoneyFormat::default()), "-1\u{202f}500\u{202f}Ar");
+        assert_eq!(format_ariary(&d("-0.4"), MoneyFormat::default()), "0\u{202f}Ar");
+        assert_eq!(format_ariary(&d("-2500000"), MoneyFormat::compact()), "-2,5\u{202f}M\u{202f}Ar");
+    }
+
+    #[test]
+    fn test_decimales() {
+        assert_eq!(format_ariary(&d("15000.5"), MoneyFormat::with_decimals(2)), "15\u{202f}000,50\u{202f}Ar");
+        assert_eq!(format_ariary(&d("15000.456"), MoneyFormat::with_decimals(2)), "15\u{202f}000,46\u{202f}Ar");
+        // Par défaut : arrondi à l'entier le plus proche
+        assert_eq!(format_ariary(&d("15000.5"), MoneyFormat::default()), "15\u{202f}001\u{202f}Ar");
+        assert_eq!(format_ariary(&d("15000.49"), MoneyFormat::default()), "15\u{202f}000\u{202f}Ar");
+    }
+
+    #[test]
+    fn test_tres_grands_montants() {
+        assert_eq!(
+            format_ariary(&d("123456789012345"), MoneyFormat::default()),
+            "123\u{202f}456\u{202f}789\u{202f}012\u{202f}345\u{202f}Ar"
+        );
+        assert_eq!(format_ariary(&d("3500000000"), MoneyFormat::compact()), "3,5\u{202f}Md\u{202f}Ar");
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(format_ariary(&d("1200000"), MoneyFormat::compact()), "1,2\u{202f}M\u{202f}Ar");
+        assert_eq!(format_ariary(&d("2000000"), MoneyFormat::compact()), "2\u{202f}M\u{202f}Ar");
+        assert_eq!(format_ariary(&d("1249999"), MoneyFormat::compact()), "1,2\u{202f}M\u{202f}Ar");
+        // Sous le million : format complet
+        assert_eq!(format_ariary(&d("850000"), MoneyFormat::compact()), "850\u{202f}000\u{202f}Ar");
+    }
+
+    #[test]
+    fn test_format_ariary_str() {
+        assert_eq!(format_ariary_str("15000.50", MoneyFormat::default()), "15\u{202f}001\u{202f}Ar");
+        assert_eq!(format_ariary_str("abc", MoneyFormat::default()), "0\u{202f}Ar");
+        assert_eq!(format_ariary_str(" 42 ", MoneyFormat::default()), "42\u{202f}Ar");
+    }
+}
@@ -0,0 +1,32 @@
+//! Animations réduites : préférence `prefers-reduced-motion` du système, suivie en
+//! direct, et choix explicite de l'utilisateur (`MotionCtx`) qui la remplace.
+
+use leptos::prelude::*;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::window;
+
+use crate::theme::MotionCtx;
+
+const QUERY: &str = "(prefers-reduced-motion: reduce)";
+
+/// Préférence actuelle du système (matchMedia).
+pub fn prefers_reduced_motion() -> bool {
+    window()
+        .and_then(|w| w.match_media(QUERY).ok().flatten())
+        .is_some_and(|mq| mq.matches())
+}
+
+/// Met `system` à jour quand la préférence du système change (réglage de l'OS
+/// modifié pendant que l'application est ouverte). Écouteur jamais retiré.
+pub fn watch_system_motion(system: RwSignal<bool>) {
+    let Some(mq) = window().and_then(|w| w.match_media(QUERY).ok().flatten()) else { return };
+    let on_change = Closure::<dyn Fn()>::new(move || system.set(prefers_reduced_motion()));
+    let _ = mq.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    on_change.forget();
+}
+
+/// Animations réduites effectives : choix enregistré, sinon préférence du système.
+pub fn use_reduced_motion() -> Signal<bool> {
+    let motion = use_context::<MotionCtx>().expect("MotionCtx manquant");
+    Signal::derive(move || motion.reduced())
+}
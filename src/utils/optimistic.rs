@@ -0,0 +1,96 @@
+//! Mises à jour optimistes d'une liste affichée : la ligne apparaît avant la
+//! réponse du backend, sous un identifiant provisoire négatif, puis elle est
+//! remplacée par la ligne enregistrée (`confirm`) ou retirée (`rollback`).
+use crate::models::member::MemberWithTotal;
+
+/// Ligne identifiée par l'id de la base (négatif tant qu'elle est provisoire).
+pub trait Keyed {
+    fn key(&self) -> i64;
+}
+
+impl Keyed for MemberWithTotal {
+    fn key(&self) -> i64 {
+        self.id
+    }
+}
+
+/// Id provisoire libre : toujours négatif, distinct des autres lignes provisoires.
+pub fn temp_id<T: Keyed>(list: &[T]) -> i64 {
+    list.iter().map(Keyed::key).min().unwrap_or(0).min(0) - 1
+}
+
+/// Ajoute la ligne provisoire `item` en fin de liste.
+pub fn apply_optimistic<T: Keyed>(list: &mut Vec<T>, item: T) {
+    list.push(item);
+}
+
+/// Remplace la ligne provisoire `temp` par `saved`. Si la liste a été rechargée
+/// entre-temps et contient déjà `saved`, la ligne provisoire est simplement retirée.
+pub fn confirm<T: Keyed>(list: &mut Vec<T>, temp: i64, saved: T) {
+    let pos = list.iter().position(|m| m.key() == temp);
+    if list.iter().any(|m| m.key() == saved.key()) {
+        rollback(list, temp);
+        return;
+    }
+    match pos {
+        Some(i) => list[i] = saved,
+        None    => list.push(saved),
+    }
+}
+
+/// Retire la ligne provisoire `temp` (échec de l'enregistrement).
+pub fn rollback<T: Keyed>(list: &mut Vec<T>, temp: i64) {
+    list.retain(|m| m.key() != temp);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Row(i64, &'static str);
+
+    impl Keyed for Row {
+        fn key(&self) -> i64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_temp_id() {
+        assert_eq!(temp_id::<Row>(&[]), -1);
+        assert_eq!(temp_id(&[Row(4, "a"), Row(9, "b")]), -1);
+        assert_eq!(temp_id(&[Row(4, "a"), Row(-1, "b"), Row(-2, "c")]), -3);
+    }
+
+    #[test]
+    fn test_confirm_remplace_la_ligne_provisoire() {
+        let mut list = vec![Row(1, "a")];
+        let temp = temp_id(&list);
+        apply_optimistic(&mut list, Row(temp, "b"));
+        apply_optimistic(&mut list, Row(temp - 1, "c"));
+        confirm(&mut list, temp, Row(7, "b"));
+        assert_eq!(list, vec![Row(1, "a"), Row(7, "b"), Row(temp - 1, "c")]);
+    }
+
+    #[test]
+    fn test_confirm_apres_rechargement() {
+        // Rechargée avec la ligne enregistrée : pas de doublon
+        let mut list = vec![Row(1, "a"), Row(7, "b"), Row(-1, "b")];
+        confirm(&mut list, -1, Row(7, "b"));
+        assert_eq!(list, vec![Row(1, "a"), Row(7, "b")]);
+        // Rechargée avant l'enregistrement : ligne provisoire perdue, ajoutée en fin
+        let mut list = vec![Row(1, "a")];
+        confirm(&mut list, -1, Row(7, "b"));
+        assert_eq!(list, vec![Row(1, "a"), Row(7, "b")]);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut list = vec![Row(1, "a"), Row(-1, "b")];
+        rollback(&mut list, -1);
+        assert_eq!(list, vec![Row(1, "a")]);
+        rollback(&mut list, -1);
+        assert_eq!(list, vec![Row(1, "a")]);
+    }
+}
@@ -0,0 +1,125 @@
+//! Détection d'un lecteur de code-barres USB.
+//!
+//! Le lecteur se comporte comme un clavier : il tape le numéro de carte imprimé
+//! puis Entrée, bien plus vite qu'une saisie humaine. Une séquence d'au moins
+//! `SCAN_MIN_CHARS` caractères terminée par Entrée en moins de
+//! `SCAN_MAX_DURATION_MS` est considérée comme un scan.
+
+/// Nombre minimal de caractères d'un scan.
+pub const SCAN_MIN_CHARS: usize = 7;
+/// Durée maximale entre le premier caractère et Entrée.
+pub const SCAN_MAX_DURATION_MS: f64 = 300.0;
+
+/// Frappes accumulées depuis le dernier Entrée (ou la dernière remise à zéro).
+#[derive(Debug, Clone, Default)]
+pub struct ScanBuffer {
+    chars:    String,
+    /// Instant (ms) du premier caractère de la séquence
+    first_at: f64,
+}
+
+impl ScanBuffer {
+    /// Enregistre la touche `key` (`KeyboardEvent.key`) reçue à `at_ms`.
+    /// Renvoie le numéro de carte quand Entrée termine une séquence de scan.
+    pub fn feed(&mut self, key: &str, at_ms: f64) -> Option<String> {
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            // Caractère imprimable
+            (Some(c), None) if !c.is_control() => {
+                // Séquence trop ancienne : ce caractère en commence une nouvelle
+                if self.chars.is_empty() || at_ms - self.first_at > SCAN_MAX_DURATION_MS {
+                    self.chars.clear();
+                    self.first_at = at_ms;
+                }
+                self.chars.push(c);
+                None
+            }
+            _ if key == "Enter" => {
+                let chars = std::mem::take(&mut self.chars);
+                let card = chars.trim();
+                let fast = at_ms - self.first_at <= SCAN_MAX_DURATION_MS;
+                (fast && card.chars().count() >= SCAN_MIN_CHARS).then(|| card.to_string())
+            }
+            // Modificateurs envoyés par certains lecteurs pour les majuscules
+            _ if matches!(key, "Shift" | "CapsLock") => None,
+            // Effacement, flèches… : saisie manuelle
+            _ => {
+                self.chars.clear();
+                None
+            }
+        }
+    }
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tape `text` à raison d'une touche toutes les `step_ms`, puis Entrée.
+    fn type_then_enter(buf: &mut ScanBuffer, text: &str, start_ms: f64, step_ms: f64) -> Option<String> {
+        let mut at = start_ms;
+        for c in text.chars() {
+            assert_eq!(buf.feed(&c.to_string(), at), None);
+            at += step_ms;
+        }
+        buf.feed("Enter", at)
+    }
+
+    #[test]
+    fn scan_rapide_reconnu() {
+        let mut buf = ScanBuffer::default();
+        assert_eq!(type_then_enter(&mut buf, "C-0042-17", 1000.0, 15.0), Some("C-0042-17".into()));
+        // Le tampon est vidé : un second scan repart de zéro
+        assert_eq!(type_then_enter(&mut buf, "K-123456", 5000.0, 10.0), Some("K-123456".into()));
+    }
+
+    #[test]
+    fn saisie_humaine_ignoree() {
+        let mut buf = ScanBuffer::default();
+        // ~8 caractères/seconde : bien trop lent
+        assert_eq!(type_then_enter(&mut buf, "C-0042-17", 0.0, 120.0), None);
+        // Rapide mais trop court (6 caractères)
+        assert_eq!(type_then_enter(&mut buf, "C-0042", 2000.0, 10.0), None);
+        // Entrée seule
+        assert_eq!(buf.feed("Enter", 3000.0), None);
+    }
+
+    #[test]
+    fn frappes_anciennes_oubliees() {
+        let mut buf = ScanBuffer::default();
+        // Quelques lettres tapées à la main, puis un scan une seconde plus tard
+        buf.feed("a", 0.0);
+        buf.feed("b", 150.0);
+        assert_eq!(type_then_enter(&mut buf, "C-000777", 1200.0, 12.0), Some("C-000777".into()));
+    }
+
+    #[test]
+    fn touches_speciales() {
+        let mut buf = ScanBuffer::default();
+        // Majuscules précédées de Shift : sans effet sur la séquence
+        let mut at = 0.0;
+        for key in ["Shift", "C", "-", "0", "0", "Shift", "A", "4", "2"] {
+            assert_eq!(buf.feed(key, at), None);
+            at += 10.0;
+        }
+        assert_eq!(buf.feed("Enter", at), Some("C-00A42".into()));
+
+        // Retour arrière : saisie manuelle, séquence abandonnée
+        for (i, key) in ["C", "-", "0", "0", "Backspace", "4", "2", "1", "7"].iter().enumerate() {
+            buf.feed(key, 500.0 + i as f64 * 10.0);
+        }
+        assert_eq!(buf.feed("Enter", 600.0), None);
+    }
+
+    #[test]
+    fn duree_mesuree_jusqu_a_entree() {
+        let mut buf = ScanBuffer::default();
+        // Caractères rapides mais Entrée tardif
+        for (i, c) in "C-004217".chars().enumerate() {
+            buf.feed(&c.to_string(), i as f64 * 10.0);
+        }
+        assert_eq!(buf.feed("Enter", 400.0), None);
+    }
+}